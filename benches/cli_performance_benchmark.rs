@@ -234,6 +234,7 @@ fn benchmark_error_handling_impact(c: &mut Criterion) {
                     let error_info = libdplyr::cli::json_output::ErrorInfo {
                         error_type: "validation".to_string(),
                         message: "Validation failed".to_string(),
+                        code: None,
                         position: None,
                         suggestions: vec!["Check syntax".to_string()],
                     };
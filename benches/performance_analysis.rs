@@ -10,7 +10,7 @@ use std::time::Instant;
 
 /// Memory usage estimation for different operations
 fn benchmark_memory_usage_estimation(c: &mut Criterion) {
-    let transpiler = Transpiler::new(Box::new(PostgreSqlDialect));
+    let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
 
     let mut group = c.benchmark_group("memory_usage_estimation");
 
@@ -66,7 +66,7 @@ fn benchmark_memory_usage_estimation(c: &mut Criterion) {
 
 /// Scaling analysis - how performance changes with input size
 fn benchmark_scaling_analysis(c: &mut Criterion) {
-    let transpiler = Transpiler::new(Box::new(PostgreSqlDialect));
+    let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
 
     let mut group = c.benchmark_group("scaling_analysis");
 
@@ -105,7 +105,7 @@ fn benchmark_scaling_analysis(c: &mut Criterion) {
 
 /// Bottleneck identification - which stage takes the most time
 fn benchmark_bottleneck_identification(c: &mut Criterion) {
-    let transpiler = Transpiler::new(Box::new(PostgreSqlDialect));
+    let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
 
     let mut group = c.benchmark_group("bottleneck_identification");
 
@@ -148,7 +148,7 @@ fn benchmark_bottleneck_identification(c: &mut Criterion) {
 
 /// Cache efficiency testing
 fn benchmark_cache_efficiency(c: &mut Criterion) {
-    let transpiler = Transpiler::new(Box::new(PostgreSqlDialect));
+    let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
 
     let mut group = c.benchmark_group("cache_efficiency");
 
@@ -158,7 +158,7 @@ fn benchmark_cache_efficiency(c: &mut Criterion) {
     group.bench_function("cold_cache", |b| {
         b.iter(|| {
             // Create new transpiler each time to simulate cold cache
-            let fresh_transpiler = Transpiler::new(Box::new(PostgreSqlDialect));
+            let fresh_transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
             fresh_transpiler.transpile(black_box(repeated_query))
         })
     });
@@ -189,7 +189,7 @@ fn benchmark_cache_efficiency(c: &mut Criterion) {
 
 /// Error handling performance impact
 fn benchmark_error_handling_performance(c: &mut Criterion) {
-    let transpiler = Transpiler::new(Box::new(PostgreSqlDialect));
+    let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
 
     let mut group = c.benchmark_group("error_handling_performance");
 
@@ -253,15 +253,15 @@ fn benchmark_dialect_performance_characteristics(c: &mut Criterion) {
     let dialects = vec![
         (
             "postgresql",
-            Box::new(PostgreSqlDialect) as Box<dyn libdplyr::SqlDialect>,
+            Box::new(PostgreSqlDialect::new()) as Box<dyn libdplyr::SqlDialect>,
         ),
         (
             "mysql",
-            Box::new(MySqlDialect) as Box<dyn libdplyr::SqlDialect>,
+            Box::new(MySqlDialect::new()) as Box<dyn libdplyr::SqlDialect>,
         ),
         (
             "sqlite",
-            Box::new(SqliteDialect) as Box<dyn libdplyr::SqlDialect>,
+            Box::new(SqliteDialect::new()) as Box<dyn libdplyr::SqlDialect>,
         ),
     ];
 
@@ -6,14 +6,16 @@
 //! - Scaling with input size
 //! - Dialect-specific performance
 //! - Stage-by-stage performance analysis
+//! - The shared query corpus (`libdplyr::performance::corpus`)
 
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use libdplyr::performance::corpus::{COMPLEX_QUERIES, SIMPLE_QUERIES};
 use libdplyr::{MySqlDialect, PostgreSqlDialect, SqliteDialect, Transpiler};
 use std::hint::black_box;
 
 /// Simple conversion benchmark
 fn benchmark_simple_transpile(c: &mut Criterion) {
-    let transpiler = Transpiler::new(Box::new(PostgreSqlDialect));
+    let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
     let dplyr_code = "select(name, age) %>% filter(age > 18)";
 
     c.bench_function("simple transpile", |b| {
@@ -23,7 +25,7 @@ fn benchmark_simple_transpile(c: &mut Criterion) {
 
 /// Complex conversion benchmark
 fn benchmark_complex_transpile(c: &mut Criterion) {
-    let transpiler = Transpiler::new(Box::new(PostgreSqlDialect));
+    let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
     let dplyr_code = r#"
         select(name, age, category, salary) %>%
         filter(age > 18 & salary > 50000) %>%
@@ -49,7 +51,7 @@ fn benchmark_dialects(c: &mut Criterion) {
     let mut group = c.benchmark_group("dialect_comparison");
 
     // PostgreSQL
-    let pg_transpiler = Transpiler::new(Box::new(PostgreSqlDialect));
+    let pg_transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
     group.bench_with_input(
         BenchmarkId::new("postgresql", "standard"),
         &dplyr_code,
@@ -57,7 +59,7 @@ fn benchmark_dialects(c: &mut Criterion) {
     );
 
     // MySQL
-    let mysql_transpiler = Transpiler::new(Box::new(MySqlDialect));
+    let mysql_transpiler = Transpiler::new(Box::new(MySqlDialect::new()));
     group.bench_with_input(
         BenchmarkId::new("mysql", "standard"),
         &dplyr_code,
@@ -65,7 +67,7 @@ fn benchmark_dialects(c: &mut Criterion) {
     );
 
     // SQLite
-    let sqlite_transpiler = Transpiler::new(Box::new(SqliteDialect));
+    let sqlite_transpiler = Transpiler::new(Box::new(SqliteDialect::new()));
     group.bench_with_input(
         BenchmarkId::new("sqlite", "standard"),
         &dplyr_code,
@@ -77,7 +79,7 @@ fn benchmark_dialects(c: &mut Criterion) {
 
 /// Performance measurement by parsing stage
 fn benchmark_parsing_stages(c: &mut Criterion) {
-    let transpiler = Transpiler::new(Box::new(PostgreSqlDialect));
+    let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
     let dplyr_code = "select(name, age) %>% filter(age > 18) %>% arrange(desc(age))";
 
     let mut group = c.benchmark_group("parsing_stages");
@@ -97,7 +99,7 @@ fn benchmark_parsing_stages(c: &mut Criterion) {
 
 /// Performance measurement by input size
 fn benchmark_input_sizes(c: &mut Criterion) {
-    let transpiler = Transpiler::new(Box::new(PostgreSqlDialect));
+    let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
 
     let mut group = c.benchmark_group("input_sizes");
 
@@ -152,7 +154,7 @@ fn benchmark_input_sizes(c: &mut Criterion) {
 
 /// Throughput-based benchmarks measuring operations per second
 fn benchmark_throughput(c: &mut Criterion) {
-    let transpiler = Transpiler::new(Box::new(PostgreSqlDialect));
+    let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
 
     let mut group = c.benchmark_group("throughput");
 
@@ -197,7 +199,7 @@ fn benchmark_throughput(c: &mut Criterion) {
 
 /// Memory allocation patterns and efficiency
 fn benchmark_memory_patterns(c: &mut Criterion) {
-    let transpiler = Transpiler::new(Box::new(PostgreSqlDialect));
+    let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
 
     let mut group = c.benchmark_group("memory_patterns");
 
@@ -237,7 +239,7 @@ fn benchmark_memory_patterns(c: &mut Criterion) {
 
 /// Stress testing with edge cases and extreme inputs
 fn benchmark_stress_tests(c: &mut Criterion) {
-    let transpiler = Transpiler::new(Box::new(PostgreSqlDialect));
+    let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
 
     let mut group = c.benchmark_group("stress_tests");
 
@@ -323,7 +325,7 @@ fn benchmark_lexer_performance(c: &mut Criterion) {
 
 /// SQL generation performance tests
 fn benchmark_sql_generation(c: &mut Criterion) {
-    let transpiler = Transpiler::new(Box::new(PostgreSqlDialect));
+    let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
 
     let mut group = c.benchmark_group("sql_generation");
 
@@ -353,8 +355,8 @@ fn benchmark_sql_generation(c: &mut Criterion) {
     });
 
     // Test different dialects on the same AST
-    let mysql_transpiler = Transpiler::new(Box::new(MySqlDialect));
-    let sqlite_transpiler = Transpiler::new(Box::new(SqliteDialect));
+    let mysql_transpiler = Transpiler::new(Box::new(MySqlDialect::new()));
+    let sqlite_transpiler = Transpiler::new(Box::new(SqliteDialect::new()));
 
     group.bench_function("postgresql_generation", |b| {
         b.iter(|| transpiler.generate_sql(black_box(&complex_ast)))
@@ -373,7 +375,7 @@ fn benchmark_sql_generation(c: &mut Criterion) {
 
 /// Regression tests to catch performance degradation
 fn benchmark_regression_tests(c: &mut Criterion) {
-    let transpiler = Transpiler::new(Box::new(PostgreSqlDialect));
+    let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
 
     let mut group = c.benchmark_group("regression_tests");
 
@@ -398,8 +400,45 @@ fn benchmark_regression_tests(c: &mut Criterion) {
     group.finish();
 }
 
+/// Corpus benchmarks: each query on its own, the whole corpus as a batch, and
+/// the same query repeated on one transpiler
+fn benchmark_corpus(c: &mut Criterion) {
+    let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+
+    let mut group = c.benchmark_group("corpus");
+    for (kind, queries) in [("simple", SIMPLE_QUERIES), ("complex", COMPLEX_QUERIES)] {
+        for (i, query) in queries.iter().enumerate() {
+            group.bench_with_input(BenchmarkId::new(kind, i), query, |b, query| {
+                b.iter(|| transpiler.transpile(black_box(query)))
+            });
+        }
+    }
+
+    let batch: Vec<&str> = SIMPLE_QUERIES
+        .iter()
+        .chain(COMPLEX_QUERIES)
+        .copied()
+        .collect();
+    group.throughput(Throughput::Elements(batch.len() as u64));
+    group.bench_function("batch", |b| {
+        b.iter(|| {
+            for query in &batch {
+                let _ = black_box(transpiler.transpile(black_box(query)));
+            }
+        })
+    });
+
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("repeated", |b| {
+        b.iter(|| transpiler.transpile(black_box(COMPLEX_QUERIES[0])))
+    });
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
+    benchmark_corpus,
     benchmark_simple_transpile,
     benchmark_complex_transpile,
     benchmark_dialects,
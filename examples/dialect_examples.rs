@@ -198,6 +198,18 @@ fn demonstrate_error_handling() -> Result<(), Box<dyn std::error::Error>> {
                         println!("System Error: {}", sys_err);
                         println!("Hint: Check system resources and permissions");
                     }
+                    TranspileError::SecurityError(sec_err) => {
+                        println!("Security Error: {}", sec_err);
+                        println!("Hint: Remove control characters or reduce nesting");
+                    }
+                    TranspileError::TimeoutError(timeout_err) => {
+                        println!("Timeout Error: {}", timeout_err);
+                        println!("Hint: Check that input is being provided");
+                    }
+                    TranspileError::NetworkError(net_err) => {
+                        println!("Network Error: {}", net_err);
+                        println!("Hint: Check that the URL is reachable");
+                    }
                 }
                 println!("```\n");
             }
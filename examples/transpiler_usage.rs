@@ -116,6 +116,15 @@ fn error_handling_examples() -> Result<(), Box<dyn std::error::Error>> {
             Err(TranspileError::SystemError(e)) => {
                 println!("❌ System error: {e}");
             }
+            Err(TranspileError::SecurityError(e)) => {
+                println!("❌ Security error: {e}");
+            }
+            Err(TranspileError::TimeoutError(e)) => {
+                println!("❌ Timeout error: {e}");
+            }
+            Err(TranspileError::NetworkError(e)) => {
+                println!("❌ Network error: {e}");
+            }
         }
         println!();
     }
@@ -172,6 +181,21 @@ fn demonstrate_error_handling_pattern(
             eprintln!("   Error: {e}");
             eprintln!("   Hint: Check system resources and permissions");
         }
+        Err(TranspileError::SecurityError(e)) => {
+            eprintln!("🛡️  Security check failed:");
+            eprintln!("   Error: {e}");
+            eprintln!("   Hint: Remove control characters or reduce nesting");
+        }
+        Err(TranspileError::TimeoutError(e)) => {
+            eprintln!("⏱️  Timed out:");
+            eprintln!("   Error: {e}");
+            eprintln!("   Hint: Check that input is being provided");
+        }
+        Err(TranspileError::NetworkError(e)) => {
+            eprintln!("🌐 Network error:");
+            eprintln!("   Error: {e}");
+            eprintln!("   Hint: Check that the URL is reachable");
+        }
     }
 
     Ok(())
@@ -251,6 +275,18 @@ fn inspect_ast(ast: &DplyrNode) {
                     libdplyr::DplyrOperation::Rename { renames, .. } => {
                         println!("     {}. Rename: {} renames", i + 1, renames.len());
                     }
+                    libdplyr::DplyrOperation::Unsupported { verb, .. } => {
+                        println!("     {}. Unsupported: {}()", i + 1, verb);
+                    }
+                    libdplyr::DplyrOperation::Slice { n, .. } => {
+                        println!("     {}. {}: {} rows", i + 1, op.operation_name(), n);
+                    }
+                    libdplyr::DplyrOperation::Fill { columns, .. } => {
+                        println!("     {}. Fill: {} columns", i + 1, columns.len());
+                    }
+                    libdplyr::DplyrOperation::RenameWith { columns, .. } => {
+                        println!("     {}. RenameWith: {} columns", i + 1, columns.len());
+                    }
                     libdplyr::DplyrOperation::Arrange { columns, .. } => {
                         println!("     {}. Arrange: {} columns", i + 1, columns.len());
                     }
@@ -264,6 +300,12 @@ fn inspect_ast(ast: &DplyrNode) {
                             aggregations.len()
                         );
                     }
+                    libdplyr::DplyrOperation::Distinct { columns, .. } => {
+                        println!("     {}. Distinct: {} columns", i + 1, columns.len());
+                    }
+                    libdplyr::DplyrOperation::PivotWider { names, .. } => {
+                        println!("     {}. PivotWider: {} columns", i + 1, names.len());
+                    }
                     libdplyr::DplyrOperation::Join {
                         join_type, spec, ..
                     } => {
@@ -52,6 +52,12 @@ extern "C" {
 #define DPLYR_ERROR_INTERNAL (-7)
 #define DPLYR_ERROR_PANIC (-8)
 
+/** @brief Which limit a DplyrLimitDetails describes */
+#define DPLYR_LIMIT_NONE 0
+#define DPLYR_LIMIT_INPUT_LENGTH 1
+#define DPLYR_LIMIT_OUTPUT_LENGTH 2
+#define DPLYR_LIMIT_PROCESSING_TIME 3
+
 /**
  * @brief Supported SQL dialects for the generic C API.
  */
@@ -59,7 +65,9 @@ typedef enum DplyrDialect {
     DPLYR_DIALECT_DUCKDB = 0,
     DPLYR_DIALECT_POSTGRESQL = 1,
     DPLYR_DIALECT_MYSQL = 2,
-    DPLYR_DIALECT_SQLITE = 3
+    DPLYR_DIALECT_SQLITE = 3,
+    DPLYR_DIALECT_ORACLE = 4,
+    DPLYR_DIALECT_SPARK = 5
 } DplyrDialect;
 
 /**
@@ -85,8 +93,29 @@ typedef struct DplyrOptions {
     uint32_t max_input_length;      /**< Maximum input length for DoS prevention (R9-AC2) */
     uint64_t max_processing_time_ms; /**< Maximum processing time in milliseconds (0 = use default) (R9-AC2) */
     uint32_t dialect;               /**< SQL dialect selection as a DPLYR_DIALECT_* value */
+    bool trailing_semicolon;        /**< Terminate SQL from dplyr_compile* with a single ';' (not applied to parser rewrites) */
+    bool provenance_comment;        /**< Prefix SQL from dplyr_compile* with a "-- generated by libdplyr <version> (dialect=<name>)" line */
+    uint32_t max_nesting_depth;     /**< Maximum parenthesis nesting depth (0 = use default) (R9-AC2) */
+    uint32_t max_function_calls;    /**< Maximum function calls per input (0 = use default) (R9-AC2) */
+    uint32_t max_output_length;     /**< Maximum generated SQL length in bytes (0 = unlimited, trusted callers only) (R9-AC2) */
+    uint32_t max_repeated_chars;    /**< Longest run of one character outside string literals (0 = use default) (R9-AC2) */
+    uint32_t max_repeated_substrings; /**< Occurrences of one short substring outside string literals (0 = use default) (R9-AC2) */
 } DplyrOptions;
 
+/**
+ * @brief Measured values behind an input-too-large, output-too-large or timeout error
+ *
+ * Filled by dplyr_last_error_limit_details(). Fields that do not apply to
+ * `kind` are 0. This structure must match the Rust DplyrLimitDetails struct exactly.
+ */
+typedef struct DplyrLimitDetails {
+    int32_t kind;           /**< One of the DPLYR_LIMIT_* constants */
+    uint64_t input_length;  /**< Input length in bytes */
+    uint64_t output_length; /**< Generated SQL length in bytes (output limit only) */
+    uint64_t limit;         /**< The exceeded limit: bytes, or milliseconds for timeouts */
+    uint64_t elapsed_ms;    /**< Processing time when the timeout fired (timeout only) */
+} DplyrLimitDetails;
+
 /* ========================================================================
  * CORE TRANSPILATION FUNCTIONS
  * ======================================================================== */
@@ -135,7 +164,7 @@ typedef struct DplyrOptions {
  * @code
  * char* sql = NULL;
  * char* error = NULL;
- * DplyrOptions options = {false, 1024*1024, 0, DPLYR_DIALECT_DUCKDB};
+ * DplyrOptions options = {false, 1024*1024, 0, DPLYR_DIALECT_DUCKDB, false, 0, 0, 0};
  * 
  * int result = dplyr_compile("mtcars %>% select(mpg, cyl)", &options, &sql, &error);
  * if (result == 0) {
@@ -167,6 +196,55 @@ int dplyr_compile_with_pipe_syntax(
     char** out_error
 );
 
+/**
+ * @brief Convert dplyr pipeline code to SQL for a dialect given by name.
+ *
+ * dialect_name is matched case-insensitively: "duckdb", "postgresql" (or
+ * "postgres", "pg"), "mysql", "sqlite", "oracle" or "spark"; it overrides
+ * options->dialect.
+ * An unknown name fails with an error in *out_error.
+ */
+int dplyr_compile_with_dialect(
+    const char* code,
+    const char* dialect_name,
+    const DplyrOptions* options,
+    char** out_sql,
+    char** out_error
+);
+
+/**
+ * @brief Convert dplyr pipeline code to SQL and report degraded translations.
+ *
+ * On success, *out_warnings holds newline-separated "W-CODE: message" lines,
+ * or NULL when the translation is exact. Free it with dplyr_free_string().
+ */
+int dplyr_compile_with_warnings(
+    const char* code,
+    const DplyrOptions* options,
+    char** out_sql,
+    char** out_warnings,
+    char** out_error
+);
+
+/**
+ * @brief Convert an array of dplyr inputs to SQL in one call.
+ *
+ * Item i receives its SQL in out_sqls[i] or its error message in out_errors[i];
+ * the other slot is set to NULL. Every slot must be NULL or a libdplyr-owned
+ * string on entry. Free results with dplyr_free_strings(out_sqls, count) and
+ * dplyr_free_strings(out_errors, count).
+ *
+ * @return Number of items compiled successfully, or a negative error code when
+ *         the arrays are NULL or the options are invalid
+ */
+int dplyr_compile_batch(
+    const char* const* codes,
+    size_t count,
+    const DplyrOptions* options,
+    char** out_sqls,
+    char** out_errors
+);
+
 /**
  * @brief Compile a full query, including embedded `(| ... |)` dplyr segments.
  *
@@ -275,7 +353,8 @@ const char* dplyr_version(void);
 const char* dplyr_version_detailed(void);
 
 /**
- * @brief Get supported SQL dialects as a comma-separated string
+ * @brief Get supported SQL dialects as a comma-separated string of the
+ * names dplyr_compile_with_dialect accepts (e.g. "postgresql,mysql,...")
  * 
  * @return Static string listing supported dialects (no need to free)
  */
@@ -295,6 +374,29 @@ const char* dplyr_build_timestamp(void);
  */
 bool dplyr_has_debug_support(void);
 
+/**
+ * @brief Check that memory allocation and the transpile cache work
+ *
+ * @note Clears the transpile cache as part of the check
+ *
+ * @return DPLYR_SUCCESS if the system is ready, a negative error code otherwise
+ */
+int dplyr_check_system(void);
+
+/**
+ * @brief Check the system and describe each subsystem as a JSON report
+ *
+ * The report holds "status", "memory_allocation", "cache" ("available",
+ * "size", "capacity"), "supported_dialects" and "build" ("version", "rustc",
+ * "timestamp", "debug"). The cache is left untouched.
+ *
+ * @param out_report Output pointer for the JSON report (caller must free with
+ *                   dplyr_free_string())
+ * @return Same code as dplyr_check_system(), or DPLYR_ERROR_NULL_POINTER when
+ *         out_report is NULL
+ */
+int dplyr_check_system_detailed(char** out_report);
+
 /**
  * @brief Get maximum supported input length
  * 
@@ -309,6 +411,24 @@ uint32_t dplyr_max_input_length(void);
  */
 uint64_t dplyr_max_processing_time_ms(void);
 
+/**
+ * @brief Check whether a dialect can translate an aggregate function
+ * 
+ * @param dialect DPLYR_DIALECT_* value
+ * @param name Aggregate function name (e.g. "median")
+ * @return 1 if supported, 0 if not, -1 for an invalid dialect or name
+ */
+int dplyr_dialect_supports_aggregate(uint32_t dialect, const char* name);
+
+/**
+ * @brief Check whether a dialect can generate a dplyr verb
+ * 
+ * @param dialect DPLYR_DIALECT_* value
+ * @param operation Verb name (e.g. "fill", "rename")
+ * @return 1 if supported, 0 if not, -1 for an invalid dialect or operation name
+ */
+int dplyr_dialect_supports_operation(uint32_t dialect, const char* operation);
+
 /* ========================================================================
  * OPTIONS MANAGEMENT
  * ======================================================================== */
@@ -358,6 +478,64 @@ DplyrOptions dplyr_options_create_with_timeout(
  */
 int dplyr_options_validate(const DplyrOptions* options);
 
+/**
+ * @brief Set the maximum parenthesis nesting depth accepted in input
+ * 
+ * Values above the safe maximum (64, the parser's own nesting limit) are clamped;
+ * 0 restores the default (50).
+ * 
+ * @param options Options to update
+ * @param max_nesting_depth New nesting depth limit
+ * @return 0 on success, -1 if options is NULL
+ */
+int dplyr_options_set_max_nesting_depth(DplyrOptions* options, uint32_t max_nesting_depth);
+
+/**
+ * @brief Set the maximum number of function calls accepted in input
+ * 
+ * Values above the safe maximum (10000) are clamped; 0 restores the default (1000).
+ * 
+ * @param options Options to update
+ * @param max_function_calls New function call limit
+ * @return 0 on success, -1 if options is NULL
+ */
+int dplyr_options_set_max_function_calls(DplyrOptions* options, uint32_t max_function_calls);
+
+/**
+ * @brief Set the maximum length in bytes of generated SQL
+ * 
+ * Values above the safe maximum (64MB) are clamped. 0 removes the limit
+ * entirely and is meant for trusted callers only; the default stays 10MB.
+ * 
+ * @param options Options to update
+ * @param max_output_length New output length limit
+ * @return 0 on success, -1 if options is NULL
+ */
+int dplyr_options_set_max_output_length(DplyrOptions* options, uint32_t max_output_length);
+
+/**
+ * @brief Set the longest run of one character accepted outside string literals
+ * 
+ * Values above the safe maximum (1MB) are clamped; 0 restores the default (100).
+ * 
+ * @param options Options to update
+ * @param max_repeated_chars New identical-character run limit
+ * @return 0 on success, -1 if options is NULL
+ */
+int dplyr_options_set_max_repeated_chars(DplyrOptions* options, uint32_t max_repeated_chars);
+
+/**
+ * @brief Set how often one short substring may occur outside string literals
+ * 
+ * Raise this for long IN-lists such as c(1, 2, 3, ...). Values above the safe
+ * maximum (1MB) are clamped; 0 restores the default (20).
+ * 
+ * @param options Options to update
+ * @param max_repeated_substrings New repeated substring limit
+ * @return 0 on success, -1 if options is NULL
+ */
+int dplyr_options_set_max_repeated_substrings(DplyrOptions* options, uint32_t max_repeated_substrings);
+
 /* ========================================================================
  * ERROR HANDLING UTILITIES
  * ======================================================================== */
@@ -394,6 +572,19 @@ bool dplyr_result_has_output(int error_code);
  */
 bool dplyr_is_recoverable_error(int error_code);
 
+/**
+ * @brief Get structured details of the last limit failure on the calling thread
+ *
+ * Every dplyr_compile* call resets the details. They are set only when the
+ * call failed on the input length, output length or processing time limit;
+ * for dplyr_compile_batch() they describe the last failed item. The error
+ * message is unchanged, so hosts may use either.
+ *
+ * @param out_details Receives the details when available
+ * @return true if *out_details was written, false otherwise (including NULL)
+ */
+bool dplyr_last_error_limit_details(DplyrLimitDetails* out_details);
+
 /* ========================================================================
  * USAGE EXAMPLES
  * ======================================================================== */
@@ -17,26 +17,7 @@ use std::ptr;
 use std::time::{Duration, Instant};
 
 // Test data for benchmarks - R6-AC1: Performance target validation
-const SIMPLE_QUERIES: &[&str] = &[
-    "select(mpg)",
-    "filter(mpg > 20)",
-    "mutate(efficiency = mpg / cyl)",
-    "arrange(mpg)",
-    "group_by(cyl)",
-    "summarise(avg_mpg = mean(mpg))",
-    "select(name, age)",
-    "filter(status == 'active')",
-    "mutate(total = price * quantity)",
-    "arrange(desc(date))",
-];
-
-const COMPLEX_QUERIES: &[&str] = &[
-    "mtcars %>% select(mpg, cyl, hp) %>% filter(mpg > 20) %>% arrange(desc(hp))",
-    "data %>% group_by(category, region) %>% summarise(total = sum(value), avg = mean(value), count = n()) %>% arrange(desc(total))",
-    "sales %>% select(date, product, amount, region) %>% filter(amount > 1000) %>% mutate(quarter = quarter(date), profit_margin = amount * 0.15) %>% group_by(quarter, region) %>% summarise(total_sales = sum(amount), avg_margin = mean(profit_margin), product_count = n_distinct(product)) %>% arrange(quarter, desc(total_sales))",
-    "employees %>% select(id, name, department, salary, hire_date) %>% filter(salary > 50000 & department %in% c('Engineering', 'Sales')) %>% mutate(years_employed = year(today()) - year(hire_date), salary_grade = case_when(salary < 75000 ~ 'Junior', salary < 100000 ~ 'Mid', TRUE ~ 'Senior')) %>% group_by(department, salary_grade) %>% summarise(count = n(), avg_salary = mean(salary), avg_years = mean(years_employed)) %>% arrange(department, desc(avg_salary))",
-    "orders %>% select(order_id, customer_id, product_id, quantity, price, order_date) %>% filter(order_date >= '2023-01-01' & quantity > 0) %>% mutate(total_amount = quantity * price, month = month(order_date), year = year(order_date)) %>% group_by(year, month, customer_id) %>% summarise(order_count = n(), total_spent = sum(total_amount), avg_order_value = mean(total_amount), unique_products = n_distinct(product_id)) %>% filter(total_spent > 1000) %>% arrange(year, month, desc(total_spent))",
-];
+use libdplyr::performance::corpus::{COMPLEX_QUERIES, SIMPLE_QUERIES};
 
 const EDGE_CASE_QUERIES: &[&str] = &[
     "",                       // Empty query
@@ -53,6 +34,13 @@ const fn create_default_options() -> DplyrOptions {
         max_input_length: 10000,
         max_processing_time_ms: 5000,
         dialect: DplyrDialect::DuckDb as u32,
+        trailing_semicolon: false,
+        provenance_comment: false,
+        max_nesting_depth: 0,
+        max_function_calls: 0,
+        max_output_length: 0,
+        max_repeated_chars: 0,
+        max_repeated_substrings: 0,
     }
 }
 
@@ -63,6 +51,13 @@ const fn create_mysql_options() -> DplyrOptions {
         max_input_length: 10000,
         max_processing_time_ms: 5000,
         dialect: DplyrDialect::MySql as u32,
+        trailing_semicolon: false,
+        provenance_comment: false,
+        max_nesting_depth: 0,
+        max_function_calls: 0,
+        max_output_length: 0,
+        max_repeated_chars: 0,
+        max_repeated_substrings: 0,
     }
 }
 
@@ -73,6 +68,13 @@ const fn create_debug_options() -> DplyrOptions {
         max_input_length: 50000,
         max_processing_time_ms: 10000,
         dialect: DplyrDialect::DuckDb as u32,
+        trailing_semicolon: false,
+        provenance_comment: false,
+        max_nesting_depth: 0,
+        max_function_calls: 0,
+        max_output_length: 0,
+        max_repeated_chars: 0,
+        max_repeated_substrings: 0,
     }
 }
 
@@ -14,6 +14,7 @@ use std::time::{Duration, Instant};
 #[derive(Clone)]
 pub struct CachedResult {
     pub sql: String,
+    pub warnings: Vec<String>,
     pub timestamp: Instant,
     pub processing_time_us: u64, // R10-AC2: Metadata for diagnostics
     pub access_count: u64,       // R10-AC2: Access frequency tracking
@@ -37,8 +38,19 @@ thread_local! {
 
     static CACHE_METRICS: std::cell::RefCell<CacheMetrics> =
         std::cell::RefCell::new(CacheMetrics::default());
+
+    static EFFECTIVENESS_THRESHOLD: std::cell::Cell<f64> =
+        const { std::cell::Cell::new(DEFAULT_EFFECTIVENESS_THRESHOLD) };
 }
 
+// Cached entries expire after 5 minutes
+const CACHE_ENTRY_TTL: Duration = Duration::from_secs(300);
+
+// R6-AC1: Hit rate above which the cache counts as effective
+pub const DEFAULT_EFFECTIVENESS_THRESHOLD: f64 = 0.5;
+// A clear is advised once the hit rate drops below this fraction of the threshold
+const CLEAR_THRESHOLD_RATIO: f64 = 0.2;
+
 pub struct SimpleTranspileCache;
 
 impl SimpleTranspileCache {
@@ -62,6 +74,26 @@ impl SimpleTranspileCache {
     ) -> Result<String, TranspileError>
     where
         F: FnOnce(&str, &DplyrOptions) -> Result<String, TranspileError>,
+    {
+        Self::get_or_transpile_with_warnings(
+            dplyr_code,
+            options,
+            discriminator,
+            |source_code, options| transpile_fn(source_code, options).map(|sql| (sql, Vec::new())),
+        )
+        .map(|(sql, _)| sql)
+    }
+
+    /// Cached transpilation that keeps translation warnings next to the SQL,
+    /// so cache hits report the same warnings as the original miss.
+    pub fn get_or_transpile_with_warnings<F>(
+        dplyr_code: &str,
+        options: &DplyrOptions,
+        discriminator: &str,
+        transpile_fn: F,
+    ) -> Result<(String, Vec<String>), TranspileError>
+    where
+        F: FnOnce(&str, &DplyrOptions) -> Result<(String, Vec<String>), TranspileError>,
     {
         let cache_start = Instant::now();
         let cache_key =
@@ -85,7 +117,7 @@ impl SimpleTranspileCache {
 
         if let Some(cached) = cached_result {
             // Cache expiration check (5 minutes)
-            if cached.timestamp.elapsed() < Duration::from_secs(300) {
+            if cached.timestamp.elapsed() < CACHE_ENTRY_TTL {
                 // Record cache hit
                 CACHE_METRICS.with(|metrics| {
                     let mut metrics = metrics.borrow_mut();
@@ -93,7 +125,7 @@ impl SimpleTranspileCache {
                     metrics.cache_processing_time_us += cache_start.elapsed().as_micros() as u64;
                 });
 
-                return Ok(cached.sql);
+                return Ok((cached.sql, cached.warnings));
             } else {
                 // Expired entry - remove it
                 REQUEST_CACHE.with(|cache| {
@@ -108,14 +140,79 @@ impl SimpleTranspileCache {
         });
 
         let start_time = Instant::now();
-        let sql = transpile_fn(dplyr_code, options)?;
+        let (sql, warnings) = transpile_fn(dplyr_code, options)?;
+        let processing_time = start_time.elapsed().as_micros() as u64;
+
+        let evicted = Self::store(cache_key, &sql, &warnings, processing_time);
+
+        // Update metrics
+        CACHE_METRICS.with(|metrics| {
+            let mut metrics = metrics.borrow_mut();
+            metrics.total_processing_time_us += processing_time;
+            metrics.cache_processing_time_us += cache_start.elapsed().as_micros() as u64;
+            if evicted {
+                metrics.evictions += 1;
+            }
+        });
+
+        Ok((sql, warnings))
+    }
+
+    /// Pre-populates the cache so the first real compile of each query is a hit.
+    ///
+    /// Each query is validated and transpiled exactly as `dplyr_compile` would
+    /// and stored under the same key. Warmup is not counted as a hit or miss.
+    /// Queries that fail to compile are skipped; returns how many are cached.
+    pub fn warmup(queries: &[(&str, &DplyrOptions)]) -> usize {
+        queries
+            .iter()
+            .filter(|(dplyr_code, options)| {
+                crate::compile::warm_cache_entry(dplyr_code, options).is_ok()
+            })
+            .count()
+    }
+
+    /// Inserts a transpilation result without recording a hit or miss.
+    /// A fresh entry already in the cache is left untouched.
+    pub(crate) fn insert_warm_entry<F>(
+        dplyr_code: &str,
+        options: &DplyrOptions,
+        discriminator: &str,
+        transpile_fn: F,
+    ) -> Result<(), TranspileError>
+    where
+        F: FnOnce(&str, &DplyrOptions) -> Result<(String, Vec<String>), TranspileError>,
+    {
+        let cache_key =
+            Self::create_cache_key_with_discriminator(dplyr_code, options, discriminator);
+
+        let is_fresh = REQUEST_CACHE.with(|cache| {
+            cache
+                .borrow()
+                .peek(&cache_key)
+                .is_some_and(|cached| cached.timestamp.elapsed() < CACHE_ENTRY_TTL)
+        });
+        if is_fresh {
+            return Ok(());
+        }
+
+        let start_time = Instant::now();
+        let (sql, warnings) = transpile_fn(dplyr_code, options)?;
         let processing_time = start_time.elapsed().as_micros() as u64;
 
-        // Cache update with LRU eviction
-        let evicted = REQUEST_CACHE.with(|cache| {
+        if Self::store(cache_key, &sql, &warnings, processing_time) {
+            CACHE_METRICS.with(|metrics| metrics.borrow_mut().evictions += 1);
+        }
+
+        Ok(())
+    }
+
+    /// Stores a result, returning whether the LRU entry was evicted to make room.
+    fn store(cache_key: String, sql: &str, warnings: &[String], processing_time_us: u64) -> bool {
+        REQUEST_CACHE.with(|cache| {
             let mut cache = cache.borrow_mut();
 
-            let evicted = if cache.len() >= cache.cap().get() {
+            let evicted = if cache.len() >= cache.cap().get() && !cache.contains(&cache_key) {
                 cache.peek_lru().is_some()
             } else {
                 false
@@ -125,28 +222,17 @@ impl SimpleTranspileCache {
             cache.put(
                 cache_key,
                 CachedResult {
-                    sql: sql.clone(),
+                    sql: sql.to_string(),
+                    warnings: warnings.to_vec(),
                     timestamp: Instant::now(),
-                    processing_time_us: processing_time,
+                    processing_time_us,
                     access_count: 1,
                     last_access: Instant::now(),
                 },
             );
 
             evicted
-        });
-
-        // Update metrics
-        CACHE_METRICS.with(|metrics| {
-            let mut metrics = metrics.borrow_mut();
-            metrics.total_processing_time_us += processing_time;
-            metrics.cache_processing_time_us += cache_start.elapsed().as_micros() as u64;
-            if evicted {
-                metrics.evictions += 1;
-            }
-        });
-
-        Ok(sql)
+        })
     }
 
     // Generate cache key from dplyr_code + dialect + options
@@ -160,11 +246,20 @@ impl SimpleTranspileCache {
         options: &DplyrOptions,
         discriminator: &str,
     ) -> String {
+        let dplyr_code = normalize_whitespace(dplyr_code);
         let mut hasher = DefaultHasher::new();
         discriminator.hash(&mut hasher);
         dplyr_code.hash(&mut hasher);
         options.debug_mode.hash(&mut hasher);
         options.dialect.hash(&mut hasher);
+        // Input limits are enforced before caching, so entries must not be
+        // shared across different limits.
+        options.effective_max_nesting_depth().hash(&mut hasher);
+        options.effective_max_function_calls().hash(&mut hasher);
+        options.effective_max_repeated_chars().hash(&mut hasher);
+        options
+            .effective_max_repeated_substrings()
+            .hash(&mut hasher);
 
         format!("{}_{}", hasher.finish(), dplyr_code.len())
     }
@@ -246,6 +341,11 @@ impl SimpleTranspileCache {
         REQUEST_CACHE.with(|cache| {
             cache.borrow_mut().clear();
         });
+        Self::reset_stats();
+    }
+
+    // R10-AC2: Zero hit/miss/eviction counters while keeping cached entries warm
+    pub fn reset_stats() {
         CACHE_METRICS.with(|metrics| {
             *metrics.borrow_mut() = CacheMetrics::default();
         });
@@ -263,9 +363,24 @@ impl SimpleTranspileCache {
         })
     }
 
-    // R6-AC1: Check if cache is performing well (hit rate > 50%)
+    // R6-AC1: Check if cache is performing well (hit rate above the threshold)
     pub fn is_cache_effective() -> bool {
-        Self::get_hit_rate() > 0.5
+        Self::get_hit_rate() > Self::effectiveness_threshold()
+    }
+
+    pub fn effectiveness_threshold() -> f64 {
+        EFFECTIVENESS_THRESHOLD.with(|threshold| threshold.get())
+    }
+
+    /// Sets the hit rate above which the cache counts as effective.
+    ///
+    /// Returns false and leaves the threshold unchanged unless it is in [0, 1].
+    pub fn set_effectiveness_threshold(threshold: f64) -> bool {
+        if !(0.0..=1.0).contains(&threshold) {
+            return false;
+        }
+        EFFECTIVENESS_THRESHOLD.with(|current| current.set(threshold));
+        true
     }
 
     // R10-AC2: Get most frequently accessed entries for analysis
@@ -287,6 +402,7 @@ impl SimpleTranspileCache {
 
 // FFI functions for cache management
 
+use crate::error::{DPLYR_ERROR_NULL_POINTER, DPLYR_ERROR_PANIC};
 use std::ffi::{c_char, CString};
 use std::os::raw::c_int;
 
@@ -312,12 +428,29 @@ pub extern "C" fn dplyr_cache_get_hit_rate() -> f64 {
 /// Check if cache is performing effectively
 ///
 /// # Returns
-/// true if hit rate > 50%, false otherwise
+/// true if hit rate exceeds the effectiveness threshold (50% by default), false otherwise
 #[no_mangle]
 pub extern "C" fn dplyr_cache_is_effective() -> bool {
     SimpleTranspileCache::is_cache_effective()
 }
 
+/// Set the hit rate threshold used by `dplyr_cache_is_effective` and
+/// `dplyr_cache_should_clear` for the calling thread's cache
+///
+/// # Arguments
+/// * `threshold` - Hit rate as a fraction in [0, 1]; a clear is advised below a fifth of it
+///
+/// # Returns
+/// 0 on success, -1 if the threshold is outside [0, 1] or NaN
+#[no_mangle]
+pub extern "C" fn dplyr_cache_set_effectiveness_threshold(threshold: f64) -> c_int {
+    if SimpleTranspileCache::set_effectiveness_threshold(threshold) {
+        0
+    } else {
+        -1
+    }
+}
+
 /// Clear cache and reset all metrics
 ///
 /// # Returns
@@ -332,6 +465,66 @@ pub extern "C" fn dplyr_cache_clear() -> c_int {
     result.unwrap_or(-1)
 }
 
+/// Reset cache metrics without evicting cached entries
+///
+/// # Returns
+/// 0 on success, negative error code on failure
+#[no_mangle]
+pub extern "C" fn dplyr_cache_reset_stats() -> c_int {
+    let result = std::panic::catch_unwind(|| {
+        SimpleTranspileCache::reset_stats();
+        0
+    });
+
+    result.unwrap_or(-1)
+}
+
+/// Pre-populate the cache with queries compiled as `dplyr_compile` would
+///
+/// # Safety
+/// Caller must ensure that:
+/// - `queries` points to `count` readable `*const c_char` entries, or is null when `count` is 0.
+/// - Each non-null entry is a valid, null-terminated C string.
+/// - `options` is a valid `*const DplyrOptions` or `std::ptr::null()` for defaults.
+///
+/// # Returns
+/// Number of queries cached (null, non-UTF-8 and failing entries are skipped),
+/// or a negative error code on failure
+#[no_mangle]
+pub unsafe extern "C" fn dplyr_cache_warmup(
+    queries: *const *const c_char,
+    count: usize,
+    options: *const DplyrOptions,
+) -> c_int {
+    if queries.is_null() && count > 0 {
+        return DPLYR_ERROR_NULL_POINTER;
+    }
+
+    let result = std::panic::catch_unwind(|| {
+        let opts = if options.is_null() {
+            DplyrOptions::default()
+        } else {
+            unsafe { (*options).clone() }
+        };
+
+        let codes: Vec<&str> = (0..count)
+            .filter_map(|index| {
+                let query = unsafe { *queries.add(index) };
+                if query.is_null() {
+                    None
+                } else {
+                    unsafe { std::ffi::CStr::from_ptr(query) }.to_str().ok()
+                }
+            })
+            .collect();
+        let entries: Vec<(&str, &DplyrOptions)> = codes.iter().map(|code| (*code, &opts)).collect();
+
+        c_int::try_from(SimpleTranspileCache::warmup(&entries)).unwrap_or(c_int::MAX)
+    });
+
+    result.unwrap_or(DPLYR_ERROR_PANIC)
+}
+
 /// Get current cache size
 ///
 /// # Returns
@@ -532,15 +725,71 @@ pub extern "C" fn dplyr_cache_log_performance_warning() -> bool {
 pub extern "C" fn dplyr_cache_should_clear() -> bool {
     let metrics = SimpleTranspileCache::get_cache_metrics();
 
-    // Clear if hit rate is very low (< 10%) and we have enough data points
+    // Clear if hit rate is very low (< 10% by default) and we have enough data points
     if metrics.hits + metrics.misses >= 20 {
         let hit_rate = metrics.hits as f64 / (metrics.hits + metrics.misses) as f64;
-        hit_rate < 0.1
+        hit_rate < SimpleTranspileCache::effectiveness_threshold() * CLEAR_THRESHOLD_RATIO
     } else {
         false
     }
 }
 
+/// Canonical spelling of `code` for cache keys, so formatting variants such as
+/// `select(a)` and `select( a )` share one entry.
+///
+/// Whitespace runs are trimmed at both ends, dropped next to brackets and
+/// commas, and otherwise collapsed to one space, or to one newline when they
+/// contain a line break, since a newline can end an R expression. String
+/// literals, backtick-quoted names and `#` comments are kept verbatim.
+fn normalize_whitespace(code: &str) -> String {
+    const STRUCTURAL: &[char] = &['(', ')', '[', ']', '{', '}', ','];
+
+    let mut normalized = String::with_capacity(code.len());
+    let mut chars = code.trim().chars().peekable();
+    let mut pending: Option<char> = None;
+    while let Some(c) = chars.next() {
+        if c.is_whitespace() {
+            if c == '\n' || pending.is_none() {
+                pending = Some(if c == '\n' { '\n' } else { ' ' });
+            }
+            continue;
+        }
+        if let Some(separator) = pending.take() {
+            let touches_structure = normalized.ends_with(STRUCTURAL) || STRUCTURAL.contains(&c);
+            if separator == '\n' || !touches_structure {
+                normalized.push(separator);
+            }
+        }
+        normalized.push(c);
+        match c {
+            '"' | '\'' | '`' => {
+                // Copy the quoted text up to the closing quote, honoring escapes
+                while let Some(inner) = chars.next() {
+                    normalized.push(inner);
+                    if inner == '\\' {
+                        if let Some(escaped) = chars.next() {
+                            normalized.push(escaped);
+                        }
+                    } else if inner == c {
+                        break;
+                    }
+                }
+            }
+            '#' => {
+                while let Some(&inner) = chars.peek() {
+                    if inner == '\n' {
+                        break;
+                    }
+                    normalized.push(inner);
+                    chars.next();
+                }
+            }
+            _ => {}
+        }
+    }
+    normalized
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -567,6 +816,25 @@ mod tests {
         assert_ne!(key1, key4, "dialect changes should fragment the cache");
     }
 
+    #[test]
+    fn test_cache_key_ignores_whitespace_outside_literals() {
+        let options = DplyrOptions::default();
+        let key = |code: &str| SimpleTranspileCache::create_cache_key(code, &options);
+
+        assert_eq!(key("select(a)"), key("select( a )"));
+        assert_eq!(
+            key("data %>% filter(x > 1, y == 2)"),
+            key("  data  %>%\tfilter( x  > 1 ,y == 2 ) ")
+        );
+        assert_ne!(key("filter(x == \"a b\")"), key("filter(x == \"a  b\")"));
+        assert_ne!(key("select(`a b`)"), key("select(`a  b`)"));
+        assert_ne!(key("filter(x <-1)"), key("filter(x < -1)"));
+        // A line break can end an R expression, so it stays distinct from a space
+        assert_ne!(key("data\n%>% select(a)"), key("data %>% select(a)"));
+        assert_eq!(key("# a  note\nselect(a)"), key("# a  note\n  select( a )"));
+        assert_ne!(key("# a  note\nselect(a)"), key("# a note\nselect(a)"));
+    }
+
     #[test]
     fn test_cache_key_generation_accepts_small_discriminator() {
         let options = DplyrOptions::default();
@@ -645,6 +913,24 @@ mod tests {
         assert_eq!(SimpleTranspileCache::get_hit_rate(), 0.5); // 1 hit out of 2 total
     }
 
+    #[test]
+    fn test_whitespace_variants_share_one_cache_entry() {
+        SimpleTranspileCache::clear_cache();
+        let options = DplyrOptions::default();
+
+        for code in ["select(a)", "select( a )"] {
+            let sql = SimpleTranspileCache::get_or_transpile(code, &options, |_code, _opts| {
+                Ok("SELECT a FROM table".to_string())
+            });
+            assert_eq!(sql.unwrap(), "SELECT a FROM table");
+        }
+
+        let metrics = SimpleTranspileCache::get_cache_metrics();
+        assert_eq!(metrics.misses, 1);
+        assert_eq!(metrics.hits, 1);
+        assert_eq!(dplyr_cache_get_size(), 1);
+    }
+
     #[test]
     fn test_lru_eviction() {
         SimpleTranspileCache::clear_cache();
@@ -743,6 +1029,37 @@ mod tests {
         assert_eq!(dplyr_cache_get_misses(), 0);
     }
 
+    #[test]
+    fn test_cache_effectiveness_threshold_is_configurable() {
+        SimpleTranspileCache::clear_cache();
+
+        // 1 hit out of 3 requests: 33% hit rate
+        let options = DplyrOptions::default();
+        for code in ["select(a)", "select(b)", "select(a)"] {
+            let _ = SimpleTranspileCache::get_or_transpile(code, &options, |code, _opts| {
+                Ok(code.to_string())
+            });
+        }
+        assert!(!dplyr_cache_is_effective());
+
+        assert_eq!(dplyr_cache_set_effectiveness_threshold(0.25), 0);
+        assert!(dplyr_cache_is_effective());
+
+        assert_eq!(dplyr_cache_set_effectiveness_threshold(0.75), 0);
+        assert!(!dplyr_cache_is_effective());
+
+        // Out-of-range values are rejected and leave the threshold unchanged
+        assert_eq!(dplyr_cache_set_effectiveness_threshold(1.5), -1);
+        assert_eq!(dplyr_cache_set_effectiveness_threshold(-0.1), -1);
+        assert_eq!(dplyr_cache_set_effectiveness_threshold(f64::NAN), -1);
+        assert_eq!(SimpleTranspileCache::effectiveness_threshold(), 0.75);
+
+        assert_eq!(
+            dplyr_cache_set_effectiveness_threshold(DEFAULT_EFFECTIVENESS_THRESHOLD),
+            0
+        );
+    }
+
     #[test]
     fn test_debug_logging_functions() {
         unsafe {
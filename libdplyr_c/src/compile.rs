@@ -2,9 +2,13 @@
 
 #[cfg(test)]
 use std::cell::Cell;
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::ffi::CStr;
 use std::os::raw::c_char;
 use std::panic;
+use std::ptr;
+use std::rc::Rc;
 #[cfg(test)]
 use std::sync::atomic::{AtomicBool, Ordering};
 #[cfg(test)]
@@ -12,19 +16,20 @@ use std::sync::{Mutex, MutexGuard};
 use std::time::{Duration, Instant};
 
 use libdplyr::{
-    DuckDbDialect, MySqlDialect, PipeSyntax, PostgreSqlDialect, SqlDialect, SqliteDialect,
-    Transpiler,
+    DuckDbDialect, MySqlDialect, OracleDialect, PipeSyntax, PostgreSqlDialect, SparkSqlDialect,
+    SqlDialect, SqlGenerator, SqliteDialect, Transpiler,
 };
 
 use crate::cache;
 use crate::cache::SimpleTranspileCache;
-use crate::error::{create_error_message_with_context, TranspileError};
-use crate::ffi::{clear_output_string, set_error_output, set_sql_output};
-use crate::options::{
-    DplyrDialect, DplyrOptions, DplyrPipeSyntax, MAX_OUTPUT_LENGTH, MAX_PROCESSING_TIME_MS,
+use crate::error::{
+    create_error_message_with_context, set_last_limit_details, DplyrLimitDetails, TranspileError,
+    DPLYR_LIMIT_INPUT_LENGTH, DPLYR_LIMIT_OUTPUT_LENGTH, DPLYR_LIMIT_PROCESSING_TIME,
 };
+use crate::ffi::{clear_output_string, set_error_output, set_sql_output};
+use crate::options::{DplyrDialect, DplyrOptions, DplyrPipeSyntax, MAX_PROCESSING_TIME_MS};
 use crate::validation::{
-    validate_input_encoding, validate_input_security, validate_input_structure,
+    validate_input_encoding, validate_input_security_with_limits, validate_input_structure,
 };
 
 use crate::error::{
@@ -118,16 +123,60 @@ fn maybe_force_test_panic() {
     }
 }
 
-fn create_dialect(dialect: DplyrDialect) -> Box<dyn SqlDialect> {
+thread_local! {
+    /// Per-thread transpilers keyed by dialect and pipe syntax, so repeated
+    /// compiles neither rebuild the dialect nor contend on a shared lock.
+    static TRANSPILER_POOL: RefCell<HashMap<TranspilerKey, Rc<Transpiler>>> =
+        RefCell::new(HashMap::new());
+}
+
+#[cfg(test)]
+thread_local! {
+    static POOLED_TRANSPILER_BUILDS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Both parts are small enums, so the pool holds at most one transpiler per
+/// combination. The output limit is not part of the key: callers enforce it
+/// with `validate_output_length`.
+type TranspilerKey = (DplyrDialect, PipeSyntax);
+
+/// Returns this thread's transpiler for `dialect`, building it on first use.
+fn pooled_transpiler(dialect: DplyrDialect, pipe_syntax: PipeSyntax) -> Rc<Transpiler> {
+    TRANSPILER_POOL.with(|pool| {
+        let mut pool = pool.borrow_mut();
+        let key = (dialect, pipe_syntax);
+        let transpiler = pool.entry(key).or_insert_with(|| {
+            #[cfg(test)]
+            POOLED_TRANSPILER_BUILDS.with(|builds| builds.set(builds.get() + 1));
+            Rc::new(
+                Transpiler::builder()
+                    .dialect(create_dialect(dialect))
+                    .pipe_syntax(pipe_syntax)
+                    .build(),
+            )
+        });
+        Rc::clone(transpiler)
+    })
+}
+
+/// Number of transpilers this thread's pool has built.
+#[cfg(test)]
+pub(crate) fn pooled_transpiler_builds_for_test() -> usize {
+    POOLED_TRANSPILER_BUILDS.with(Cell::get)
+}
+
+pub(crate) fn create_dialect(dialect: DplyrDialect) -> Box<dyn SqlDialect> {
     match dialect {
         DplyrDialect::DuckDb => Box::new(DuckDbDialect::new()),
         DplyrDialect::PostgreSql => Box::new(PostgreSqlDialect::new()),
         DplyrDialect::MySql => Box::new(MySqlDialect::new()),
         DplyrDialect::Sqlite => Box::new(SqliteDialect::new()),
+        DplyrDialect::Oracle => Box::new(OracleDialect::new()),
+        DplyrDialect::Spark => Box::new(SparkSqlDialect::new()),
     }
 }
 
-fn validated_dialect(raw_dialect: u32) -> Result<DplyrDialect, TranspileError> {
+pub(crate) fn validated_dialect(raw_dialect: u32) -> Result<DplyrDialect, TranspileError> {
     DplyrDialect::try_from(raw_dialect)
 }
 
@@ -150,10 +199,33 @@ fn pipe_syntax_from_env_or_default() -> Result<PipeSyntax, TranspileError> {
 
 #[derive(Debug)]
 enum CompileInputError {
-    InputTooLarge(String),
+    InputTooLarge(String, DplyrLimitDetails),
     Transpile(TranspileError),
 }
 
+fn input_too_large(input_length: usize, max_input_length: u32) -> CompileInputError {
+    CompileInputError::InputTooLarge(
+        format!("E-INPUT-TOO-LARGE: Input size {input_length} exceeds maximum {max_input_length}"),
+        DplyrLimitDetails {
+            kind: DPLYR_LIMIT_INPUT_LENGTH,
+            input_length: input_length as u64,
+            limit: u64::from(max_input_length),
+            ..Default::default()
+        },
+    )
+}
+
+/// Records `error`'s limit details for `dplyr_last_error_limit_details`,
+/// filling in the input length when the failing stage did not know it.
+fn record_limit_details(error: &TranspileError, input: &str) {
+    set_last_limit_details(error.limit_details().map(|mut details| {
+        if details.input_length == 0 {
+            details.input_length = input.len() as u64;
+        }
+        details
+    }));
+}
+
 fn disabled_pipe_syntax_error(disabled_syntax: PipeSyntax, position: usize) -> CompileInputError {
     CompileInputError::Transpile(TranspileError::syntax_error_with_suggestion(
         disabled_syntax.disabled_message(),
@@ -165,7 +237,8 @@ fn disabled_pipe_syntax_error(disabled_syntax: PipeSyntax, position: usize) -> C
 
 fn set_compile_error_output(out_error: *mut *mut c_char, error: CompileInputError) -> i32 {
     match error {
-        CompileInputError::InputTooLarge(message) => {
+        CompileInputError::InputTooLarge(message, limit_details) => {
+            set_last_limit_details(Some(limit_details));
             if set_error_output(out_error, &message) {
                 DPLYR_ERROR_INPUT_TOO_LARGE
             } else {
@@ -173,6 +246,7 @@ fn set_compile_error_output(out_error: *mut *mut c_char, error: CompileInputErro
             }
         }
         CompileInputError::Transpile(error) => {
+            set_last_limit_details(error.limit_details());
             let error_msg = error.to_c_string();
             if set_error_output(out_error, &error_msg.to_string_lossy()) {
                 error.to_c_error_code()
@@ -213,11 +287,7 @@ fn validate_compile_options(opts: &DplyrOptions) -> Result<(), CompileInputError
 
 fn validate_compile_input(code_str: &str, opts: &DplyrOptions) -> Result<(), CompileInputError> {
     if code_str.len() > opts.max_input_length as usize {
-        return Err(CompileInputError::InputTooLarge(format!(
-            "E-INPUT-TOO-LARGE: Input size {} exceeds maximum {}",
-            code_str.len(),
-            opts.max_input_length
-        )));
+        return Err(input_too_large(code_str.len(), opts.max_input_length));
     }
 
     validate_input_encoding(code_str).map_err(CompileInputError::Transpile)?;
@@ -254,14 +324,25 @@ fn processing_deadline(opts: &DplyrOptions) -> Instant {
     Instant::now() + processing_timeout(opts)
 }
 
-fn timeout_error(max_processing_time: Duration, phase: &str, hint: &str) -> TranspileError {
-    TranspileError::internal_error_with_hint(
+fn timeout_error(
+    max_processing_time: Duration,
+    elapsed: Duration,
+    phase: &str,
+    hint: &str,
+) -> TranspileError {
+    TranspileError::limit_exceeded_error(
         &format!(
             "{} timeout: exceeded {}ms limit",
             phase,
             max_processing_time.as_millis()
         ),
-        Some(hint.to_string()),
+        hint,
+        DplyrLimitDetails {
+            kind: DPLYR_LIMIT_PROCESSING_TIME,
+            limit: max_processing_time.as_millis() as u64,
+            elapsed_ms: elapsed.as_millis() as u64,
+            ..Default::default()
+        },
     )
 }
 
@@ -271,8 +352,10 @@ fn ensure_before_deadline(
     phase: &str,
     hint: &str,
 ) -> Result<(), TranspileError> {
-    if Instant::now() > deadline {
-        return Err(timeout_error(max_processing_time, phase, hint));
+    let now = Instant::now();
+    if now > deadline {
+        let elapsed = max_processing_time + now.duration_since(deadline);
+        return Err(timeout_error(max_processing_time, elapsed, phase, hint));
     }
 
     Ok(())
@@ -284,6 +367,95 @@ fn compile_to_sql_with_deadline(
     pipe_syntax: PipeSyntax,
     deadline: Instant,
 ) -> Result<String, TranspileError> {
+    compile_to_sql_with_warnings_and_deadline(code_str, opts, pipe_syntax, deadline, None)
+        .map(|(sql, _)| sql)
+}
+
+/// Runs this thread's pooled transpiler for a cache miss, formatting warnings as "W-CODE: message".
+fn transpile_uncached(
+    source_code: &str,
+    options: &DplyrOptions,
+    pipe_syntax: PipeSyntax,
+    deadline: Instant,
+) -> Result<(String, Vec<String>), TranspileError> {
+    let transpiler = pooled_transpiler(validated_dialect(options.dialect)?, pipe_syntax);
+    transpile_with(&transpiler, source_code, options, deadline)
+}
+
+/// Runs an existing transpiler for a cache miss; `options.dialect` must match it.
+fn transpile_with(
+    transpiler: &Transpiler,
+    source_code: &str,
+    options: &DplyrOptions,
+    deadline: Instant,
+) -> Result<(String, Vec<String>), TranspileError> {
+    let max_processing_time = processing_timeout(options);
+
+    ensure_before_deadline(
+        deadline,
+        max_processing_time,
+        "Processing",
+        "Reduce input complexity or increase timeout limit",
+    )?;
+
+    validate_input_security_with_limits(
+        source_code,
+        options.effective_max_nesting_depth(),
+        options.effective_max_function_calls(),
+        options.effective_max_repeated_chars(),
+        options.effective_max_repeated_substrings(),
+    )?;
+
+    let transpile_result = transpiler.transpile_with_warnings(source_code);
+
+    ensure_before_deadline(
+        deadline,
+        max_processing_time,
+        "Transpilation",
+        "Input may be too complex for processing",
+    )?;
+
+    match transpile_result {
+        Ok((sql, warnings)) => Ok((
+            sql,
+            warnings
+                .iter()
+                .map(|warning| format!("{}: {warning}", warning.code()))
+                .collect(),
+        )),
+        Err(libdplyr_error) => Err(convert_libdplyr_error(libdplyr_error)),
+    }
+}
+
+/// Compiles `code_str` straight into the cache, using the same validation,
+/// pipe syntax and cache key as `dplyr_compile`, without touching hit/miss counters.
+pub(crate) fn warm_cache_entry(code_str: &str, opts: &DplyrOptions) -> Result<(), TranspileError> {
+    validate_compile_input(code_str, opts).map_err(|error| match error {
+        CompileInputError::InputTooLarge(..) => {
+            TranspileError::input_too_large_error(code_str.len(), opts.max_input_length as usize)
+        }
+        CompileInputError::Transpile(error) => error,
+    })?;
+
+    let pipe_syntax = pipe_syntax_from_env_or_default()?;
+    let deadline = processing_deadline(opts);
+    SimpleTranspileCache::insert_warm_entry(
+        code_str,
+        opts,
+        pipe_syntax_cache_discriminator(pipe_syntax),
+        |source_code, options| transpile_uncached(source_code, options, pipe_syntax, deadline),
+    )
+}
+
+/// Compiles through the cache; `transpiler`, when given, is reused on a miss
+/// instead of building one from `opts`.
+fn compile_to_sql_with_warnings_and_deadline(
+    code_str: &str,
+    opts: &DplyrOptions,
+    pipe_syntax: PipeSyntax,
+    deadline: Instant,
+    transpiler: Option<&Transpiler>,
+) -> Result<(String, Vec<String>), TranspileError> {
     let max_processing_time = processing_timeout(opts);
 
     ensure_before_deadline(
@@ -294,37 +466,13 @@ fn compile_to_sql_with_deadline(
     )?;
 
     let cache_discriminator = pipe_syntax_cache_discriminator(pipe_syntax);
-    let sql = SimpleTranspileCache::get_or_transpile_with_discriminator(
+    let (sql, warnings) = SimpleTranspileCache::get_or_transpile_with_warnings(
         code_str,
         opts,
         cache_discriminator,
-        |source_code, options| {
-            ensure_before_deadline(
-                deadline,
-                max_processing_time,
-                "Processing",
-                "Reduce input complexity or increase timeout limit",
-            )?;
-
-            validate_input_security(source_code)?;
-
-            let transpiler = Transpiler::with_pipe_syntax(
-                create_dialect(validated_dialect(options.dialect)?),
-                pipe_syntax,
-            );
-            let transpile_result = transpiler.transpile(source_code);
-
-            ensure_before_deadline(
-                deadline,
-                max_processing_time,
-                "Transpilation",
-                "Input may be too complex for processing",
-            )?;
-
-            match transpile_result {
-                Ok(sql) => Ok(sql),
-                Err(libdplyr_error) => Err(convert_libdplyr_error(libdplyr_error)),
-            }
+        |source_code, options| match transpiler {
+            Some(transpiler) => transpile_with(transpiler, source_code, options, deadline),
+            None => transpile_uncached(source_code, options, pipe_syntax, deadline),
         },
     )?;
 
@@ -334,16 +482,47 @@ fn compile_to_sql_with_deadline(
         "Processing",
         "Reduce input complexity or increase timeout limit",
     )?;
-    validate_output_length(&sql)?;
-    Ok(sql)
+    validate_output_length(&sql, opts.effective_max_output_length())?;
+    Ok((sql, warnings))
 }
 
 fn compile_to_sql(
     code_str: &str,
     opts: &DplyrOptions,
     pipe_syntax: PipeSyntax,
-) -> Result<String, TranspileError> {
-    compile_to_sql_with_deadline(code_str, opts, pipe_syntax, processing_deadline(opts))
+    transpiler: Option<&Transpiler>,
+) -> Result<(String, Vec<String>), TranspileError> {
+    let (sql, warnings) = compile_to_sql_with_warnings_and_deadline(
+        code_str,
+        opts,
+        pipe_syntax,
+        processing_deadline(opts),
+        transpiler,
+    )?;
+
+    let sql = if opts.trailing_semicolon {
+        terminate_statement(&sql)
+    } else {
+        sql
+    };
+
+    if opts.provenance_comment {
+        let comment = match transpiler {
+            Some(transpiler) => transpiler.provenance_comment(),
+            None => SqlGenerator::new(create_dialect(validated_dialect(opts.dialect)?))
+                .provenance_comment(),
+        };
+        Ok((format!("{comment}\n{sql}"), warnings))
+    } else {
+        Ok((sql, warnings))
+    }
+}
+
+/// Publishes newline-separated warnings, leaving the slot null when there are none.
+fn publish_warnings(out_warnings: *mut *mut c_char, warnings: &[String]) -> bool {
+    out_warnings.is_null()
+        || warnings.is_empty()
+        || set_error_output(out_warnings, &warnings.join("\n"))
 }
 
 fn finish_compile_code(
@@ -351,16 +530,18 @@ fn finish_compile_code(
     opts: &DplyrOptions,
     pipe_syntax: PipeSyntax,
     out_sql: *mut *mut c_char,
+    out_warnings: *mut *mut c_char,
     out_error: *mut *mut c_char,
+    transpiler: Option<&Transpiler>,
 ) -> i32 {
     if let Err(error) = validate_compile_input(code_str, opts) {
         return set_compile_error_output(out_error, error);
     }
 
-    let transpile_result = compile_to_sql(code_str, opts, pipe_syntax);
+    let transpile_result = compile_to_sql(code_str, opts, pipe_syntax, transpiler);
 
     match transpile_result {
-        Ok(sql) => {
+        Ok((sql, warnings)) => {
             // R10-AC1: Debug mode logging
             if opts.debug_mode {
                 eprintln!(
@@ -376,9 +557,18 @@ fn finish_compile_code(
                 cache::dplyr_cache_log_performance_warning();
             }
 
+            if !publish_warnings(out_warnings, &warnings) {
+                return publish_error_or_internal(
+                    DPLYR_ERROR_INTERNAL,
+                    out_error,
+                    "E-INTERNAL: Failed to publish translation warnings across the FFI boundary",
+                );
+            }
+
             publish_sql_or_internal_error(out_sql, out_error, &sql)
         }
         Err(error) => {
+            record_limit_details(&error, code_str);
             let error_msg = if opts.debug_mode {
                 create_error_message_with_context(&error, Some(code_str))
             } else {
@@ -394,21 +584,27 @@ fn finish_compile_code(
     }
 }
 
-fn validate_output_length(sql: &str) -> Result<(), TranspileError> {
-    if sql.len() > MAX_OUTPUT_LENGTH {
-        return Err(TranspileError::internal_error_with_hint(
-            &format!(
-                "Output too large: {} bytes exceeds maximum {}",
-                sql.len(),
-                MAX_OUTPUT_LENGTH
-            ),
-            Some("Input generates excessive SQL output".to_string()),
-        ));
+fn validate_output_length(sql: &str, max_output_length: usize) -> Result<(), TranspileError> {
+    if sql.len() > max_output_length {
+        return Err(output_too_large_error(sql.len(), max_output_length));
     }
 
     Ok(())
 }
 
+fn output_too_large_error(length: usize, max_output_length: usize) -> TranspileError {
+    TranspileError::limit_exceeded_error(
+        &format!("Output too large: {length} bytes exceeds maximum {max_output_length}"),
+        "Input generates excessive SQL output",
+        DplyrLimitDetails {
+            kind: DPLYR_LIMIT_OUTPUT_LENGTH,
+            output_length: length as u64,
+            limit: max_output_length as u64,
+            ..Default::default()
+        },
+    )
+}
+
 fn strip_trailing_semicolon(input: &str) -> String {
     input
         .trim()
@@ -416,6 +612,13 @@ fn strip_trailing_semicolon(input: &str) -> String {
         .to_string()
 }
 
+/// Ends SQL with exactly one `;`, reusing any terminator already present.
+fn terminate_statement(sql: &str) -> String {
+    let mut terminated = strip_trailing_semicolon(sql);
+    terminated.push(';');
+    terminated
+}
+
 #[derive(Clone, Copy, Default)]
 struct SqlScanConfig {
     hash_line_comments: bool,
@@ -1096,7 +1299,8 @@ fn compile_query_string_with_deadline(
             .map_err(CompileInputError::Transpile)?
     };
 
-    validate_output_length(&sql).map_err(CompileInputError::Transpile)?;
+    validate_output_length(&sql, opts.effective_max_output_length())
+        .map_err(CompileInputError::Transpile)?;
 
     if !starts_with_supported_query_prefix_with_config(&sql, scan_config) {
         return Err(CompileInputError::Transpile(
@@ -1133,14 +1337,9 @@ fn finish_compile_query(
     }
 
     if query_str.len() > opts.max_input_length as usize {
-        return publish_error_or_internal(
-            DPLYR_ERROR_INPUT_TOO_LARGE,
+        return set_compile_error_output(
             out_error,
-            &format!(
-                "E-INPUT-TOO-LARGE: Input size {} exceeds maximum {}",
-                query_str.len(),
-                opts.max_input_length
-            ),
+            input_too_large(query_str.len(), opts.max_input_length),
         );
     }
 
@@ -1153,10 +1352,12 @@ fn finish_compile_query(
     ) {
         Ok(Some(sql)) => publish_sql_or_internal_error(out_sql, out_error, &sql),
         Ok(None) => DPLYR_QUERY_NOT_HANDLED,
-        Err(CompileInputError::InputTooLarge(message)) => {
+        Err(CompileInputError::InputTooLarge(message, limit_details)) => {
+            set_last_limit_details(Some(limit_details));
             publish_error_or_internal(DPLYR_ERROR_INPUT_TOO_LARGE, out_error, &message)
         }
         Err(CompileInputError::Transpile(error)) => {
+            record_limit_details(&error, query_str);
             let error_msg = if opts.debug_mode {
                 create_error_message_with_context(&error, Some(query_str))
             } else {
@@ -1215,6 +1416,7 @@ pub unsafe extern "C" fn dplyr_compile(
 
         clear_output_string(out_sql);
         clear_output_string(out_error);
+        set_last_limit_details(None);
         maybe_force_test_panic();
 
         // R9-AC2: Input validation - check for null pointers
@@ -1252,7 +1454,15 @@ pub unsafe extern "C" fn dplyr_compile(
             }
         };
 
-        finish_compile_code(code_str, &opts, pipe_syntax, out_sql, out_error)
+        finish_compile_code(
+            code_str,
+            &opts,
+            pipe_syntax,
+            out_sql,
+            ptr::null_mut(),
+            out_error,
+            None,
+        )
     });
 
     result.unwrap_or(DPLYR_ERROR_PANIC)
@@ -1287,6 +1497,7 @@ pub unsafe extern "C" fn dplyr_compile_with_pipe_syntax(
 
         clear_output_string(out_sql);
         clear_output_string(out_error);
+        set_last_limit_details(None);
         maybe_force_test_panic();
 
         if code.is_null() {
@@ -1321,7 +1532,302 @@ pub unsafe extern "C" fn dplyr_compile_with_pipe_syntax(
             }
         };
 
-        finish_compile_code(code_str, &opts, pipe_syntax, out_sql, out_error)
+        finish_compile_code(
+            code_str,
+            &opts,
+            pipe_syntax,
+            out_sql,
+            ptr::null_mut(),
+            out_error,
+            None,
+        )
+    });
+
+    result.unwrap_or(DPLYR_ERROR_PANIC)
+}
+
+#[no_mangle]
+/// Compile dplyr code for a dialect given by name instead of `options->dialect`.
+///
+/// `dialect_name` accepts the CLI's dialect names, case-insensitively:
+/// `duckdb`, `postgresql` (or `postgres`, `pg`), `mysql`, `sqlite`, `oracle`
+/// and `spark`.
+/// An unknown name is reported through `out_error`. All other options apply
+/// as in `dplyr_compile`.
+///
+/// # Safety
+/// Caller must ensure that:
+/// - `code` and `dialect_name` are valid null-terminated C strings.
+/// - `options` is a valid pointer to a `DplyrOptions` struct, or `std::ptr::null()`.
+/// - `out_sql` and `out_error` are valid mutable pointers to `*mut c_char`.
+/// - On entry, `*out_sql` and `*out_error` must be either null or pointers previously allocated by libdplyr.
+///   Ownership of any non-null incoming libdplyr pointer is transferred back to this function.
+/// - Any returned string pointer is freed with `dplyr_free_string`.
+/// - If the function returns `DPLYR_ERROR_PANIC`, callers must not assume `*out_error` was populated.
+pub unsafe extern "C" fn dplyr_compile_with_dialect(
+    code: *const c_char,
+    dialect_name: *const c_char,
+    options: *const DplyrOptions,
+    out_sql: *mut *mut c_char,
+    out_error: *mut *mut c_char,
+) -> i32 {
+    #[cfg(test)]
+    let _test_gate = FfiTestGateGuard::acquire();
+
+    let result = panic::catch_unwind(|| {
+        if out_sql.is_null() || out_error.is_null() {
+            return DPLYR_ERROR_NULL_POINTER;
+        }
+
+        clear_output_string(out_sql);
+        clear_output_string(out_error);
+        set_last_limit_details(None);
+        maybe_force_test_panic();
+
+        if code.is_null() {
+            return publish_error_or_internal(
+                DPLYR_ERROR_NULL_POINTER,
+                out_error,
+                "E-NULL-POINTER: code parameter is null",
+            );
+        }
+        if dialect_name.is_null() {
+            return publish_error_or_internal(
+                DPLYR_ERROR_NULL_POINTER,
+                out_error,
+                "E-NULL-POINTER: dialect_name parameter is null",
+            );
+        }
+
+        let (code_str, dialect_str) = match (
+            unsafe { CStr::from_ptr(code) }.to_str(),
+            unsafe { CStr::from_ptr(dialect_name) }.to_str(),
+        ) {
+            (Ok(code_str), Ok(dialect_str)) => (code_str, dialect_str),
+            _ => {
+                return publish_error_or_internal(
+                    DPLYR_ERROR_INVALID_UTF8,
+                    out_error,
+                    "E-INVALID-UTF8: Input code or dialect name contains invalid UTF-8",
+                );
+            }
+        };
+
+        let mut opts = if options.is_null() {
+            DplyrOptions::default()
+        } else {
+            unsafe { (*options).clone() }
+        };
+        opts.dialect = match DplyrDialect::from_name(dialect_str) {
+            Ok(dialect) => dialect as u32,
+            Err(error) => {
+                return set_compile_error_output(out_error, CompileInputError::Transpile(error))
+            }
+        };
+
+        let pipe_syntax = match pipe_syntax_from_env_or_default() {
+            Ok(pipe_syntax) => pipe_syntax,
+            Err(error) => {
+                return set_compile_error_output(out_error, CompileInputError::Transpile(error))
+            }
+        };
+
+        finish_compile_code(
+            code_str,
+            &opts,
+            pipe_syntax,
+            out_sql,
+            ptr::null_mut(),
+            out_error,
+            None,
+        )
+    });
+
+    result.unwrap_or(DPLYR_ERROR_PANIC)
+}
+
+#[no_mangle]
+/// Compile dplyr code and report translations that succeeded with reduced fidelity.
+///
+/// Warnings are written to `out_warnings` as newline-separated `W-CODE: message`
+/// lines. When the translation is exact, `*out_warnings` is left null.
+///
+/// # Safety
+/// Caller must ensure that:
+/// - `code` is a valid null-terminated C string.
+/// - `options` is a valid pointer to a `DplyrOptions` struct, or `std::ptr::null()`.
+/// - `out_sql`, `out_warnings` and `out_error` are valid mutable pointers to `*mut c_char`.
+/// - On entry, each output slot must be either null or a pointer previously allocated by libdplyr.
+///   Ownership of any non-null incoming libdplyr pointer is transferred back to this function.
+/// - Any returned string pointer is freed with `dplyr_free_string`.
+/// - If the function returns `DPLYR_ERROR_PANIC`, callers must not assume `*out_error` was populated.
+pub unsafe extern "C" fn dplyr_compile_with_warnings(
+    code: *const c_char,
+    options: *const DplyrOptions,
+    out_sql: *mut *mut c_char,
+    out_warnings: *mut *mut c_char,
+    out_error: *mut *mut c_char,
+) -> i32 {
+    #[cfg(test)]
+    let _test_gate = FfiTestGateGuard::acquire();
+
+    let result = panic::catch_unwind(|| {
+        if out_sql.is_null() || out_warnings.is_null() || out_error.is_null() {
+            return DPLYR_ERROR_NULL_POINTER;
+        }
+
+        clear_output_string(out_sql);
+        clear_output_string(out_warnings);
+        clear_output_string(out_error);
+        set_last_limit_details(None);
+        maybe_force_test_panic();
+
+        if code.is_null() {
+            return publish_error_or_internal(
+                DPLYR_ERROR_NULL_POINTER,
+                out_error,
+                "E-NULL-POINTER: code parameter is null",
+            );
+        }
+
+        let code_str = match unsafe { CStr::from_ptr(code) }.to_str() {
+            Ok(s) => s,
+            Err(_) => {
+                return publish_error_or_internal(
+                    DPLYR_ERROR_INVALID_UTF8,
+                    out_error,
+                    "E-INVALID-UTF8: Input code contains invalid UTF-8",
+                );
+            }
+        };
+
+        let opts = if options.is_null() {
+            DplyrOptions::default()
+        } else {
+            unsafe { (*options).clone() }
+        };
+
+        let pipe_syntax = match pipe_syntax_from_env_or_default() {
+            Ok(pipe_syntax) => pipe_syntax,
+            Err(error) => {
+                return set_compile_error_output(out_error, CompileInputError::Transpile(error))
+            }
+        };
+
+        finish_compile_code(
+            code_str,
+            &opts,
+            pipe_syntax,
+            out_sql,
+            out_warnings,
+            out_error,
+            None,
+        )
+    });
+
+    result.unwrap_or(DPLYR_ERROR_PANIC)
+}
+
+#[no_mangle]
+/// Compile an array of dplyr inputs in one call, reusing a single transpiler.
+///
+/// Item `i` gets its SQL in `out_sqls[i]` or its error message in `out_errors[i]`;
+/// the other slot is left null. Failed items do not stop the batch.
+///
+/// # Safety
+/// Caller must ensure that:
+/// - `codes`, `out_sqls` and `out_errors` each point to `count` elements (they may be
+///   null only when `count` is 0).
+/// - Each `codes[i]` is a valid null-terminated C string, or null (reported as an item error).
+/// - `options` is a valid pointer to a `DplyrOptions` struct, or `std::ptr::null()`.
+/// - On entry, every `out_sqls[i]` and `out_errors[i]` must be either null or a pointer
+///   previously allocated by libdplyr. Ownership of non-null incoming pointers is transferred
+///   back to this function.
+/// - Returned strings are freed with `dplyr_free_string` or `dplyr_free_strings`.
+///
+/// # Returns
+/// - The number of items compiled successfully (0..=count)
+/// - Negative error codes when the batch itself is invalid (null arrays, invalid options)
+pub unsafe extern "C" fn dplyr_compile_batch(
+    codes: *const *const c_char,
+    count: usize,
+    options: *const DplyrOptions,
+    out_sqls: *mut *mut c_char,
+    out_errors: *mut *mut c_char,
+) -> i32 {
+    #[cfg(test)]
+    let _test_gate = FfiTestGateGuard::acquire();
+
+    let result = panic::catch_unwind(|| {
+        if count == 0 {
+            return 0;
+        }
+        if codes.is_null() || out_sqls.is_null() || out_errors.is_null() {
+            return DPLYR_ERROR_NULL_POINTER;
+        }
+        if i32::try_from(count).is_err() {
+            return DPLYR_ERROR_INPUT_TOO_LARGE;
+        }
+
+        for index in 0..count {
+            clear_output_string(out_sqls.add(index));
+            clear_output_string(out_errors.add(index));
+        }
+        set_last_limit_details(None);
+        maybe_force_test_panic();
+
+        let opts = if options.is_null() {
+            DplyrOptions::default()
+        } else {
+            unsafe { (*options).clone() }
+        };
+        if let Err(error) = opts.validate() {
+            return error.to_c_error_code();
+        }
+        let pipe_syntax = match pipe_syntax_from_env_or_default() {
+            Ok(pipe_syntax) => pipe_syntax,
+            Err(error) => return error.to_c_error_code(),
+        };
+        let transpiler = match validated_dialect(opts.dialect) {
+            Ok(dialect) => pooled_transpiler(dialect, pipe_syntax),
+            Err(error) => return error.to_c_error_code(),
+        };
+
+        let mut successes = 0;
+        for index in 0..count {
+            let code = *codes.add(index);
+            let out_sql = out_sqls.add(index);
+            let out_error = out_errors.add(index);
+
+            let status = if code.is_null() {
+                publish_error_or_internal(
+                    DPLYR_ERROR_NULL_POINTER,
+                    out_error,
+                    "E-NULL-POINTER: code parameter is null",
+                )
+            } else {
+                match unsafe { CStr::from_ptr(code) }.to_str() {
+                    Ok(code_str) => finish_compile_code(
+                        code_str,
+                        &opts,
+                        pipe_syntax,
+                        out_sql,
+                        ptr::null_mut(),
+                        out_error,
+                        Some(&transpiler),
+                    ),
+                    Err(_) => publish_error_or_internal(
+                        DPLYR_ERROR_INVALID_UTF8,
+                        out_error,
+                        "E-INVALID-UTF8: Input code contains invalid UTF-8",
+                    ),
+                }
+            };
+            if status == DPLYR_SUCCESS {
+                successes += 1;
+            }
+        }
+        successes
     });
 
     result.unwrap_or(DPLYR_ERROR_PANIC)
@@ -1355,6 +1861,7 @@ pub unsafe extern "C" fn dplyr_compile_query(
 
         clear_output_string(out_sql);
         clear_output_string(out_error);
+        set_last_limit_details(None);
         maybe_force_test_panic();
 
         if query.is_null() {
@@ -1432,6 +1939,7 @@ pub unsafe extern "C" fn dplyr_compile_query_with_pipe_syntax(
 
         clear_output_string(out_sql);
         clear_output_string(out_error);
+        set_last_limit_details(None);
         maybe_force_test_panic();
 
         if query.is_null() {
@@ -1478,24 +1986,44 @@ pub fn convert_libdplyr_error(libdplyr_error: libdplyr::TranspileError) -> Trans
         libdplyr::TranspileError::LexError(lex_error) => {
             TranspileError::syntax_error_with_suggestion(
                 &format!("Lexical error: {}", lex_error),
-                0, // Position not available from libdplyr
+                lex_error.position().unwrap_or(0),
                 None,
-                Some("Check for invalid characters or syntax".to_string()),
+                Some(
+                    lex_error
+                        .hint()
+                        .unwrap_or_else(|| "Check for invalid characters or syntax".to_string()),
+                ),
             )
         }
         libdplyr::TranspileError::ParseError(parse_error) => {
             TranspileError::syntax_error_with_suggestion(
                 &format!("Parse error: {}", parse_error),
-                0, // Position not available from libdplyr
+                parse_error.position().unwrap_or(0),
                 None,
-                Some("Check dplyr function syntax".to_string()),
+                Some(
+                    parse_error
+                        .hint()
+                        .unwrap_or_else(|| "Check dplyr function syntax".to_string()),
+                ),
             )
         }
+        libdplyr::TranspileError::GenerationError(libdplyr::GenerationError::OutputTooLarge {
+            length,
+            max,
+        }) => output_too_large_error(length, max),
         libdplyr::TranspileError::GenerationError(gen_error) => {
+            // Prefer the dialect's own alternative when the generator offers one
+            let alternative = match &gen_error {
+                libdplyr::GenerationError::UnsupportedAggregateFunction {
+                    suggestion: Some(suggestion),
+                    ..
+                } => suggestion.clone(),
+                _ => "Try simpler dplyr operations".to_string(),
+            };
             TranspileError::unsupported_operation_with_alternative(
                 &format!("Generation error: {}", gen_error),
                 "DuckDB",
-                Some("Try simpler dplyr operations".to_string()),
+                Some(alternative),
             )
         }
         libdplyr::TranspileError::IoError(io_error) => TranspileError::internal_error_with_hint(
@@ -1522,6 +2050,24 @@ pub fn convert_libdplyr_error(libdplyr_error: libdplyr::TranspileError) -> Trans
                 Some("Check system resources and permissions".to_string()),
             )
         }
+        libdplyr::TranspileError::SecurityError(security_error) => {
+            TranspileError::internal_error_with_hint(
+                &format!("Security check failed: {}", security_error),
+                Some("Remove suspicious characters or patterns".to_string()),
+            )
+        }
+        libdplyr::TranspileError::TimeoutError(timeout_error) => {
+            TranspileError::internal_error_with_hint(
+                &format!("Timed out: {}", timeout_error),
+                Some("Simplify the query or increase the time limit".to_string()),
+            )
+        }
+        libdplyr::TranspileError::NetworkError(network_error) => {
+            TranspileError::internal_error_with_hint(
+                &format!("Network error: {}", network_error),
+                Some("Check that the URL is reachable".to_string()),
+            )
+        }
     }
 }
 
@@ -1673,8 +2219,11 @@ mod query_rewrite_tests {
 
     #[test]
     fn validate_output_length_rejects_excessive_sql() {
+        use crate::options::MAX_OUTPUT_LENGTH;
+
         let oversized = "x".repeat(MAX_OUTPUT_LENGTH + 1);
-        let error = validate_output_length(&oversized).expect_err("oversized SQL must fail");
+        let error = validate_output_length(&oversized, MAX_OUTPUT_LENGTH)
+            .expect_err("oversized SQL must fail");
         assert!(error.to_string().contains("Output too large"));
     }
 
@@ -1697,6 +2246,24 @@ mod query_rewrite_tests {
         }
     }
 
+    #[test]
+    fn timeout_error_reports_limit_and_elapsed_time() {
+        let limit = Duration::from_millis(50);
+        let deadline = Instant::now() - Duration::from_millis(20);
+
+        let error = ensure_before_deadline(deadline, limit, "Processing", "hint")
+            .expect_err("deadline has passed");
+        let details = error.limit_details().expect("timeout details");
+
+        assert_eq!(details.kind, DPLYR_LIMIT_PROCESSING_TIME);
+        assert_eq!(details.limit, 50);
+        assert!(details.elapsed_ms >= 70, "{details:?}");
+        assert_eq!(
+            error.to_c_string().to_string_lossy(),
+            "E-INTERNAL: Processing timeout: exceeded 50ms limit. Recovery: hint"
+        );
+    }
+
     #[test]
     fn compile_query_string_reports_embedded_segment_without_pipeline() {
         let opts = DplyrOptions::default();
@@ -3,21 +3,50 @@
 //! Implements the error code system defined in requirements R1-AC3 and R2-AC3
 //! with structured error information including position, token, and suggestions.
 
+use std::cell::Cell;
 use std::ffi::CString;
 use std::os::raw::c_char;
 use thiserror::Error;
 
-// R2-AC3: C-compatible error codes from Appendix C
-pub const DPLYR_SUCCESS: i32 = 0;
-pub const DPLYR_QUERY_NOT_HANDLED: i32 = 1;
-pub const DPLYR_ERROR_NULL_POINTER: i32 = -1;
-pub const DPLYR_ERROR_INVALID_UTF8: i32 = -2;
-pub const DPLYR_ERROR_INPUT_TOO_LARGE: i32 = -3;
-pub const DPLYR_ERROR_TIMEOUT: i32 = -4;
-pub const DPLYR_ERROR_SYNTAX: i32 = -5;
-pub const DPLYR_ERROR_UNSUPPORTED: i32 = -6;
-pub const DPLYR_ERROR_INTERNAL: i32 = -7;
-pub const DPLYR_ERROR_PANIC: i32 = -8;
+// R2-AC3: C-compatible error codes from Appendix C, shared with the CLI
+pub use libdplyr::error::{
+    DPLYR_ERROR_INPUT_TOO_LARGE, DPLYR_ERROR_INTERNAL, DPLYR_ERROR_INVALID_UTF8,
+    DPLYR_ERROR_NULL_POINTER, DPLYR_ERROR_PANIC, DPLYR_ERROR_SYNTAX, DPLYR_ERROR_TIMEOUT,
+    DPLYR_ERROR_UNSUPPORTED, DPLYR_QUERY_NOT_HANDLED, DPLYR_SUCCESS,
+};
+
+// Which limit a `DplyrLimitDetails` describes
+pub const DPLYR_LIMIT_NONE: i32 = 0;
+pub const DPLYR_LIMIT_INPUT_LENGTH: i32 = 1;
+pub const DPLYR_LIMIT_OUTPUT_LENGTH: i32 = 2;
+pub const DPLYR_LIMIT_PROCESSING_TIME: i32 = 3;
+
+/// Measured values behind an input-too-large, output-too-large or timeout
+/// error, so hosts can react without parsing the message (e.g. retry with a
+/// larger budget). Fields that do not apply to `kind` are 0.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DplyrLimitDetails {
+    /// One of the `DPLYR_LIMIT_*` constants
+    pub kind: i32,
+    /// Input length in bytes
+    pub input_length: u64,
+    /// Generated SQL length in bytes (output limit only)
+    pub output_length: u64,
+    /// The limit that was exceeded: bytes, or milliseconds for timeouts
+    pub limit: u64,
+    /// Processing time when the timeout fired (timeout only)
+    pub elapsed_ms: u64,
+}
+
+thread_local! {
+    /// Limit details of the last failed compile on this thread.
+    static LAST_LIMIT_DETAILS: Cell<Option<DplyrLimitDetails>> = const { Cell::new(None) };
+}
+
+pub(crate) fn set_last_limit_details(details: Option<DplyrLimitDetails>) {
+    LAST_LIMIT_DETAILS.with(|last| last.set(details));
+}
 
 // R1-AC3, R2-AC3: Error code system from Appendix C
 #[derive(Debug, Error, Clone)]
@@ -44,6 +73,7 @@ pub enum TranspileError {
         code: String,
         details: String,
         recovery_hint: Option<String>,
+        limit_details: Option<DplyrLimitDetails>,
     },
 
     #[error("E-FFI: Error at FFI boundary '{boundary}'")]
@@ -97,6 +127,7 @@ impl TranspileError {
                 code,
                 details,
                 recovery_hint,
+                ..
             } => {
                 let hint_info = recovery_hint
                     .as_ref()
@@ -140,6 +171,14 @@ impl TranspileError {
         }
     }
 
+    /// Structured values for size and time limit errors, `None` otherwise
+    pub const fn limit_details(&self) -> Option<DplyrLimitDetails> {
+        match self {
+            Self::Internal { limit_details, .. } => *limit_details,
+            _ => None,
+        }
+    }
+
     // Helper constructors for common error cases
     pub fn syntax_error(message: &str, position: usize, token: Option<String>) -> Self {
         Self::Syntax {
@@ -189,18 +228,28 @@ impl TranspileError {
     }
 
     pub fn internal_error(details: &str) -> Self {
+        Self::internal_error_with_hint(details, None)
+    }
+
+    pub fn internal_error_with_hint(details: &str, recovery_hint: Option<String>) -> Self {
         Self::Internal {
             code: "E-INTERNAL".to_string(),
             details: details.to_string(),
-            recovery_hint: None,
+            recovery_hint,
+            limit_details: None,
         }
     }
 
-    pub fn internal_error_with_hint(details: &str, recovery_hint: Option<String>) -> Self {
+    pub fn limit_exceeded_error(
+        details: &str,
+        recovery_hint: &str,
+        limit_details: DplyrLimitDetails,
+    ) -> Self {
         Self::Internal {
             code: "E-INTERNAL".to_string(),
             details: details.to_string(),
-            recovery_hint,
+            recovery_hint: Some(recovery_hint.to_string()),
+            limit_details: Some(limit_details),
         }
     }
 
@@ -228,9 +277,15 @@ impl TranspileError {
     }
 
     pub fn input_too_large_error(size: usize, max_size: usize) -> Self {
-        Self::internal_error_with_hint(
+        Self::limit_exceeded_error(
             &format!("Input size {} exceeds maximum {}", size, max_size),
-            Some("Reduce input size or increase max_input_length".to_string()),
+            "Reduce input size or increase max_input_length",
+            DplyrLimitDetails {
+                kind: DPLYR_LIMIT_INPUT_LENGTH,
+                input_length: size as u64,
+                limit: max_size as u64,
+                ..Default::default()
+            },
         )
     }
 }
@@ -246,19 +301,7 @@ impl TranspileError {
 /// Static string pointer (no need to free)
 #[no_mangle]
 pub const extern "C" fn dplyr_error_code_name(error_code: i32) -> *const c_char {
-    match error_code {
-        DPLYR_SUCCESS => c"SUCCESS".as_ptr(),
-        DPLYR_QUERY_NOT_HANDLED => c"QUERY-NOT-HANDLED".as_ptr(),
-        DPLYR_ERROR_NULL_POINTER => c"E-NULL-POINTER".as_ptr(),
-        DPLYR_ERROR_INVALID_UTF8 => c"E-INVALID-UTF8".as_ptr(),
-        DPLYR_ERROR_INPUT_TOO_LARGE => c"E-INPUT-TOO-LARGE".as_ptr(),
-        DPLYR_ERROR_TIMEOUT => c"E-TIMEOUT".as_ptr(),
-        DPLYR_ERROR_SYNTAX => c"E-SYNTAX".as_ptr(),
-        DPLYR_ERROR_UNSUPPORTED => c"E-UNSUPPORTED".as_ptr(),
-        DPLYR_ERROR_INTERNAL => c"E-INTERNAL".as_ptr(),
-        DPLYR_ERROR_PANIC => c"E-PANIC".as_ptr(),
-        _ => c"E-UNKNOWN".as_ptr(),
-    }
+    libdplyr::error::error_code_name(error_code).as_ptr()
 }
 
 /// Check if error code indicates success
@@ -307,6 +350,38 @@ pub const extern "C" fn dplyr_is_recoverable_error(error_code: i32) -> bool {
     }
 }
 
+/// Get the limit details of the last failed compile on the calling thread
+///
+/// Every `dplyr_compile*` call resets the details; they are only set when the
+/// call failed on the input length, output length or processing time limit.
+///
+/// # Arguments
+/// * `out_details` - Receives the details when available
+///
+/// # Returns
+/// true if `*out_details` was written, false if the last compile did not
+/// fail on a limit or `out_details` is null
+///
+/// # Safety
+/// `out_details` must be null or valid for writing one `DplyrLimitDetails`.
+#[no_mangle]
+pub unsafe extern "C" fn dplyr_last_error_limit_details(
+    out_details: *mut DplyrLimitDetails,
+) -> bool {
+    if out_details.is_null() {
+        return false;
+    }
+
+    match LAST_LIMIT_DETAILS.with(Cell::get) {
+        Some(details) => {
+            // SAFETY: checked non-null above; the caller guarantees it is writable.
+            unsafe { out_details.write(details) };
+            true
+        }
+        None => false,
+    }
+}
+
 // Helper function to create error message with context
 pub(crate) fn create_error_message_with_context(
     error: &TranspileError,
@@ -19,12 +19,14 @@ mod ffi_safety;
 mod memory;
 mod metadata;
 pub mod options;
+#[cfg(test)]
+mod performance_tests;
 mod system;
 mod validation;
 
 pub use compile::{
-    dplyr_compile, dplyr_compile_query, dplyr_compile_query_with_pipe_syntax,
-    dplyr_compile_with_pipe_syntax,
+    dplyr_compile, dplyr_compile_batch, dplyr_compile_query, dplyr_compile_query_with_pipe_syntax,
+    dplyr_compile_with_dialect, dplyr_compile_with_pipe_syntax, dplyr_compile_with_warnings,
 };
 pub use ffi::dplyr_init_output_string;
 pub use ffi_safety::dplyr_is_valid_string_pointer;
@@ -32,22 +34,32 @@ pub use memory::{dplyr_free_string, dplyr_free_strings};
 #[cfg(target_family = "wasm")]
 pub use metadata::main;
 pub use metadata::{
-    dplyr_build_timestamp, dplyr_has_debug_support, dplyr_max_input_length,
-    dplyr_max_processing_time_ms, dplyr_supported_dialects, dplyr_version, dplyr_version_detailed,
-    libdplyr_c_version_simple,
+    dplyr_build_timestamp, dplyr_dialect_supports_aggregate, dplyr_dialect_supports_operation,
+    dplyr_has_debug_support, dplyr_max_input_length, dplyr_max_processing_time_ms,
+    dplyr_supported_dialects, dplyr_version, dplyr_version_detailed, libdplyr_c_version_simple,
 };
 
 // Re-export error handling functions for C header generation
 pub use error::DPLYR_QUERY_NOT_HANDLED;
 pub use error::{
-    dplyr_error_code_name, dplyr_is_recoverable_error, dplyr_is_success, dplyr_result_has_output,
+    dplyr_error_code_name, dplyr_is_recoverable_error, dplyr_is_success,
+    dplyr_last_error_limit_details, dplyr_result_has_output, DplyrLimitDetails,
+    DPLYR_LIMIT_INPUT_LENGTH, DPLYR_LIMIT_NONE, DPLYR_LIMIT_OUTPUT_LENGTH,
+    DPLYR_LIMIT_PROCESSING_TIME,
 };
 pub use error::{DPLYR_ERROR_SYNTAX, DPLYR_ERROR_UNSUPPORTED};
 
+pub use system::{dplyr_check_system, dplyr_check_system_detailed};
+
 pub use options::{
     dplyr_options_create, dplyr_options_create_with_timeout, dplyr_options_default,
-    dplyr_options_validate, DplyrDialect, DplyrOptions, DplyrPipeSyntax, MAX_FUNCTION_CALLS,
-    MAX_INPUT_LENGTH, MAX_NESTING_DEPTH, MAX_OUTPUT_LENGTH, MAX_PROCESSING_TIME_MS,
+    dplyr_options_set_max_function_calls, dplyr_options_set_max_nesting_depth,
+    dplyr_options_set_max_output_length, dplyr_options_set_max_repeated_chars,
+    dplyr_options_set_max_repeated_substrings, dplyr_options_validate, DplyrDialect, DplyrOptions,
+    DplyrPipeSyntax, MAX_FUNCTION_CALLS, MAX_FUNCTION_CALLS_CAP, MAX_INPUT_LENGTH,
+    MAX_NESTING_DEPTH, MAX_NESTING_DEPTH_CAP, MAX_OUTPUT_LENGTH, MAX_OUTPUT_LENGTH_CAP,
+    MAX_PROCESSING_TIME_MS, MAX_REPEATED_CHARS, MAX_REPEATED_CHARS_CAP, MAX_REPEATED_SUBSTRINGS,
+    MAX_REPEATED_SUBSTRINGS_CAP,
 };
 
 #[cfg(test)]
@@ -1,7 +1,13 @@
 //! Version/build/capabilities metadata for the C API.
 
-use std::os::raw::c_char;
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int};
+use std::panic;
+use std::sync::OnceLock;
 
+use libdplyr::SqlDialect;
+
+use crate::compile::{create_dialect, validated_dialect};
 use crate::options::{MAX_INPUT_LENGTH, MAX_PROCESSING_TIME_MS};
 
 /// WASM entrypoint stub (required for some WASM toolchains).
@@ -31,29 +37,42 @@ pub const extern "C" fn dplyr_version() -> *const c_char {
 
 /// Get detailed version information including build info.
 ///
+/// Names both the C wrapper and the libdplyr core it was built against,
+/// e.g. `libdplyr_c v0.5.1 (libdplyr v0.5.1, built with rustc 1.90.0)`.
+///
 /// # Returns
 /// Static version string with build details (no need to free)
 #[no_mangle]
-pub const extern "C" fn dplyr_version_detailed() -> *const c_char {
+pub extern "C" fn dplyr_version_detailed() -> *const c_char {
     // R8-AC1: Extended version information
-    concat!(
-        "libdplyr_c v",
-        env!("CARGO_PKG_VERSION"),
-        " (built with rustc ",
-        env!("RUSTC_VERSION"),
-        ")\0"
-    )
-    .as_ptr() as *const c_char
+    static DETAILED: OnceLock<CString> = OnceLock::new();
+    DETAILED
+        .get_or_init(|| {
+            CString::new(format!(
+                "libdplyr_c v{} (libdplyr v{}, built with {})",
+                env!("CARGO_PKG_VERSION"),
+                libdplyr::VERSION,
+                env!("RUSTC_VERSION"),
+            ))
+            .unwrap_or_default()
+        })
+        .as_ptr()
 }
 
-/// Get supported SQL dialects as a comma-separated string.
+/// Get supported SQL dialects as a comma-separated string of the names
+/// `dplyr_compile_with_dialect` accepts (`postgresql,mysql,...`).
 ///
 /// # Returns
 /// Static string listing supported dialects (no need to free)
 #[no_mangle]
-pub const extern "C" fn dplyr_supported_dialects() -> *const c_char {
+pub extern "C" fn dplyr_supported_dialects() -> *const c_char {
     // R8-AC1: Capability information
-    c"DuckDB,PostgreSQL,MySQL,SQLite".as_ptr()
+    static DIALECTS: OnceLock<CString> = OnceLock::new();
+    DIALECTS
+        .get_or_init(|| {
+            CString::new(libdplyr::supported_dialect_names().join(",")).unwrap_or_default()
+        })
+        .as_ptr()
 }
 
 /// Get build timestamp.
@@ -95,3 +114,59 @@ pub const extern "C" fn dplyr_max_processing_time_ms() -> u64 {
     // R9-AC2: DoS prevention information
     MAX_PROCESSING_TIME_MS
 }
+
+/// Check whether a dialect can translate an aggregate function (e.g. `median`).
+///
+/// # Safety
+/// `name` must be a valid null-terminated `*const c_char` or `std::ptr::null()`.
+///
+/// # Returns
+/// 1 if supported, 0 if not, -1 for an invalid dialect or name
+#[no_mangle]
+pub unsafe extern "C" fn dplyr_dialect_supports_aggregate(
+    dialect: u32,
+    name: *const c_char,
+) -> c_int {
+    query_dialect_capability(dialect, name, |dialect, name| {
+        dialect.supports_aggregate(name)
+    })
+}
+
+/// Check whether a dialect can generate a dplyr verb (e.g. `fill`, `rename`).
+///
+/// # Safety
+/// `operation` must be a valid null-terminated `*const c_char` or `std::ptr::null()`.
+///
+/// # Returns
+/// 1 if supported, 0 if not, -1 for an invalid dialect or operation name
+#[no_mangle]
+pub unsafe extern "C" fn dplyr_dialect_supports_operation(
+    dialect: u32,
+    operation: *const c_char,
+) -> c_int {
+    query_dialect_capability(dialect, operation, |dialect, operation| {
+        dialect.supports_operation(operation)
+    })
+}
+
+unsafe fn query_dialect_capability(
+    raw_dialect: u32,
+    name: *const c_char,
+    check: impl Fn(&dyn SqlDialect, &str) -> bool,
+) -> c_int {
+    // R8-AC1: Capability information
+    if name.is_null() {
+        return -1;
+    }
+    let Ok(name) = CStr::from_ptr(name).to_str() else {
+        return -1;
+    };
+    let Ok(dialect) = validated_dialect(raw_dialect) else {
+        return -1;
+    };
+
+    panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        c_int::from(check(create_dialect(dialect).as_ref(), name))
+    }))
+    .unwrap_or(-1)
+}
@@ -16,6 +16,8 @@ pub enum DplyrDialect {
     PostgreSql = 1,
     MySql = 2,
     Sqlite = 3,
+    Oracle = 4,
+    Spark = 5,
 }
 
 impl TryFrom<u32> for DplyrDialect {
@@ -27,18 +29,47 @@ impl TryFrom<u32> for DplyrDialect {
             1 => Ok(Self::PostgreSql),
             2 => Ok(Self::MySql),
             3 => Ok(Self::Sqlite),
+            4 => Ok(Self::Oracle),
+            5 => Ok(Self::Spark),
             _ => Err(TranspileError::syntax_error_with_suggestion(
                 &format!("Invalid dialect value '{}'", value),
                 0,
                 Some(value.to_string()),
                 Some(
-                    "Use 0 for duckdb, 1 for postgresql, 2 for mysql, or 3 for sqlite".to_string(),
+                    "Use 0 for duckdb, 1 for postgresql, 2 for mysql, 3 for sqlite, 4 for oracle, or 5 for spark"
+                        .to_string(),
                 ),
             )),
         }
     }
 }
 
+impl DplyrDialect {
+    /// Resolves a dialect name such as `"postgres"` or `"DuckDB"` through
+    /// [`libdplyr::dialect_from_name`], so it accepts the same aliases as the
+    /// CLI's `--dialect` option.
+    pub fn from_name(name: &str) -> Result<Self, TranspileError> {
+        let resolved = libdplyr::dialect_from_name(name).map(|dialect| dialect.dialect_name());
+        match resolved {
+            Some("duckdb") => Ok(Self::DuckDb),
+            Some("postgresql") => Ok(Self::PostgreSql),
+            Some("mysql") => Ok(Self::MySql),
+            Some("sqlite") => Ok(Self::Sqlite),
+            Some("oracle") => Ok(Self::Oracle),
+            Some("spark") => Ok(Self::Spark),
+            _ => Err(TranspileError::syntax_error_with_suggestion(
+                &format!("Unknown dialect name '{name}'"),
+                0,
+                Some(name.to_string()),
+                Some(format!(
+                    "Use one of: {}",
+                    libdplyr::supported_dialect_names().join(", ")
+                )),
+            )),
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum DplyrPipeSyntax {
@@ -77,10 +108,17 @@ impl From<DplyrPipeSyntax> for PipeSyntax {
 #[repr(C)]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DplyrOptions {
-    pub debug_mode: bool,            // R10-AC1: Debug mode support
-    pub max_input_length: u32,       // R9-AC2: DoS prevention
-    pub max_processing_time_ms: u64, // R9-AC2: Processing time limit (0 = use default)
-    pub dialect: u32,                // SQL dialect selection for generic C API
+    pub debug_mode: bool,             // R10-AC1: Debug mode support
+    pub max_input_length: u32,        // R9-AC2: DoS prevention
+    pub max_processing_time_ms: u64,  // R9-AC2: Processing time limit (0 = use default)
+    pub dialect: u32,                 // SQL dialect selection for generic C API
+    pub trailing_semicolon: bool,     // Terminate compiled SQL with a single ';'
+    pub provenance_comment: bool,     // Prefix compiled SQL with a version/dialect comment line
+    pub max_nesting_depth: u32,       // R9-AC2: Parenthesis nesting limit (0 = use default)
+    pub max_function_calls: u32,      // R9-AC2: Function call limit (0 = use default)
+    pub max_output_length: u32, // R9-AC2: Generated SQL size limit (0 = unlimited, trusted callers only)
+    pub max_repeated_chars: u32, // R9-AC2: Identical-character run limit (0 = use default)
+    pub max_repeated_substrings: u32, // R9-AC2: Repeated substring limit (0 = use default)
 }
 
 impl Default for DplyrOptions {
@@ -90,6 +128,13 @@ impl Default for DplyrOptions {
             max_input_length: 1024 * 1024, // 1MB default limit
             max_processing_time_ms: MAX_PROCESSING_TIME_MS, // R9-AC2: Default timeout
             dialect: DplyrDialect::DuckDb as u32,
+            trailing_semicolon: false,
+            provenance_comment: false,
+            max_nesting_depth: MAX_NESTING_DEPTH as u32,
+            max_function_calls: MAX_FUNCTION_CALLS as u32,
+            max_output_length: MAX_OUTPUT_LENGTH as u32,
+            max_repeated_chars: MAX_REPEATED_CHARS as u32,
+            max_repeated_substrings: MAX_REPEATED_SUBSTRINGS as u32,
         }
     }
 }
@@ -118,6 +163,8 @@ impl DplyrOptions {
             max_input_length: max_input_length.min(MAX_INPUT_LENGTH as u32),
             max_processing_time_ms: MAX_PROCESSING_TIME_MS,
             dialect: dialect as u32,
+            trailing_semicolon: false,
+            ..Self::default()
         }
     }
 
@@ -147,6 +194,8 @@ impl DplyrOptions {
             max_input_length: max_input_length.min(MAX_INPUT_LENGTH as u32),
             max_processing_time_ms: timeout,
             dialect: dialect as u32,
+            trailing_semicolon: false,
+            ..Self::default()
         }
     }
 
@@ -177,10 +226,100 @@ impl DplyrOptions {
             )));
         }
 
+        // R9-AC2: Validate configurable security limits
+        check_limit(
+            "max_nesting_depth",
+            self.max_nesting_depth,
+            MAX_NESTING_DEPTH_CAP,
+        )?;
+        check_limit(
+            "max_function_calls",
+            self.max_function_calls,
+            MAX_FUNCTION_CALLS_CAP,
+        )?;
+        check_limit(
+            "max_output_length",
+            self.max_output_length,
+            MAX_OUTPUT_LENGTH_CAP,
+        )?;
+        check_limit(
+            "max_repeated_chars",
+            self.max_repeated_chars,
+            MAX_REPEATED_CHARS_CAP,
+        )?;
+        check_limit(
+            "max_repeated_substrings",
+            self.max_repeated_substrings,
+            MAX_REPEATED_SUBSTRINGS_CAP,
+        )?;
+
         DplyrDialect::try_from(self.dialect)?;
 
         Ok(())
     }
+
+    /// Nesting depth limit in effect (0 falls back to `MAX_NESTING_DEPTH`)
+    pub fn effective_max_nesting_depth(&self) -> usize {
+        effective_limit(
+            self.max_nesting_depth,
+            MAX_NESTING_DEPTH,
+            MAX_NESTING_DEPTH_CAP,
+        )
+    }
+
+    /// Function call limit in effect (0 falls back to `MAX_FUNCTION_CALLS`)
+    pub fn effective_max_function_calls(&self) -> usize {
+        effective_limit(
+            self.max_function_calls,
+            MAX_FUNCTION_CALLS,
+            MAX_FUNCTION_CALLS_CAP,
+        )
+    }
+
+    /// Output length limit in effect (0 disables the limit for trusted callers)
+    pub fn effective_max_output_length(&self) -> usize {
+        if self.max_output_length == 0 {
+            usize::MAX
+        } else {
+            (self.max_output_length as usize).min(MAX_OUTPUT_LENGTH_CAP)
+        }
+    }
+
+    /// Identical-character run limit in effect (0 falls back to `MAX_REPEATED_CHARS`)
+    pub fn effective_max_repeated_chars(&self) -> usize {
+        effective_limit(
+            self.max_repeated_chars,
+            MAX_REPEATED_CHARS,
+            MAX_REPEATED_CHARS_CAP,
+        )
+    }
+
+    /// Repeated substring limit in effect (0 falls back to `MAX_REPEATED_SUBSTRINGS`)
+    pub fn effective_max_repeated_substrings(&self) -> usize {
+        effective_limit(
+            self.max_repeated_substrings,
+            MAX_REPEATED_SUBSTRINGS,
+            MAX_REPEATED_SUBSTRINGS_CAP,
+        )
+    }
+}
+
+fn effective_limit(value: u32, default: usize, cap: usize) -> usize {
+    if value == 0 {
+        default
+    } else {
+        (value as usize).min(cap)
+    }
+}
+
+fn check_limit(name: &str, value: u32, cap: usize) -> Result<(), TranspileError> {
+    if value as usize > cap {
+        return Err(TranspileError::internal_error(&format!(
+            "{} {} exceeds maximum {}",
+            name, value, cap
+        )));
+    }
+    Ok(())
 }
 
 // R9-AC2: DoS prevention constants
@@ -189,6 +328,15 @@ pub const MAX_PROCESSING_TIME_MS: u64 = 30000; // 30 seconds
 pub const MAX_OUTPUT_LENGTH: usize = 10 * 1024 * 1024; // 10MB max SQL output
 pub const MAX_NESTING_DEPTH: usize = 50; // Maximum nesting depth
 pub const MAX_FUNCTION_CALLS: usize = 1000; // Maximum function calls per input
+pub const MAX_REPEATED_CHARS: usize = 100; // Longest run of one character outside strings
+pub const MAX_REPEATED_SUBSTRINGS: usize = 20; // Occurrences of one short substring outside strings
+
+// R9-AC2: Upper bounds for the configurable limits above
+pub const MAX_NESTING_DEPTH_CAP: usize = libdplyr::parser::MAX_NESTING_DEPTH; // Deepest nesting the parser accepts
+pub const MAX_FUNCTION_CALLS_CAP: usize = 10_000;
+pub const MAX_OUTPUT_LENGTH_CAP: usize = 64 * 1024 * 1024; // 64MB
+pub const MAX_REPEATED_CHARS_CAP: usize = MAX_INPUT_LENGTH;
+pub const MAX_REPEATED_SUBSTRINGS_CAP: usize = MAX_INPUT_LENGTH;
 
 /// Create default DplyrOptions
 ///
@@ -219,6 +367,8 @@ pub extern "C" fn dplyr_options_create(
         max_input_length: max_input_length.min(MAX_INPUT_LENGTH as u32),
         max_processing_time_ms: MAX_PROCESSING_TIME_MS,
         dialect,
+        trailing_semicolon: false,
+        ..DplyrOptions::default()
     }
 }
 
@@ -249,6 +399,8 @@ pub extern "C" fn dplyr_options_create_with_timeout(
         max_input_length: max_input_length.min(MAX_INPUT_LENGTH as u32),
         max_processing_time_ms: timeout,
         dialect,
+        trailing_semicolon: false,
+        ..DplyrOptions::default()
     }
 }
 
@@ -277,3 +429,119 @@ pub unsafe extern "C" fn dplyr_options_validate(options: *const DplyrOptions) ->
 
     result.unwrap_or(-4)
 }
+
+/// Set the maximum parenthesis nesting depth accepted in input
+///
+/// Values above `MAX_NESTING_DEPTH_CAP` are clamped; 0 restores the default.
+///
+/// # Safety
+/// `options` must be a valid `*mut DplyrOptions` or `std::ptr::null_mut()`.
+///
+/// # Returns
+/// 0 on success, -1 for a null pointer
+#[no_mangle]
+pub unsafe extern "C" fn dplyr_options_set_max_nesting_depth(
+    options: *mut DplyrOptions,
+    max_nesting_depth: u32,
+) -> i32 {
+    set_limit(options, |opts| {
+        opts.max_nesting_depth = clamp_limit(max_nesting_depth, MAX_NESTING_DEPTH_CAP);
+    })
+}
+
+/// Set the maximum number of function calls accepted in input
+///
+/// Values above `MAX_FUNCTION_CALLS_CAP` are clamped; 0 restores the default.
+///
+/// # Safety
+/// `options` must be a valid `*mut DplyrOptions` or `std::ptr::null_mut()`.
+///
+/// # Returns
+/// 0 on success, -1 for a null pointer
+#[no_mangle]
+pub unsafe extern "C" fn dplyr_options_set_max_function_calls(
+    options: *mut DplyrOptions,
+    max_function_calls: u32,
+) -> i32 {
+    set_limit(options, |opts| {
+        opts.max_function_calls = clamp_limit(max_function_calls, MAX_FUNCTION_CALLS_CAP);
+    })
+}
+
+/// Set the maximum length in bytes of generated SQL
+///
+/// Values above `MAX_OUTPUT_LENGTH_CAP` are clamped. 0 removes the limit
+/// entirely and is meant for trusted callers only; `dplyr_options_default`
+/// keeps `MAX_OUTPUT_LENGTH`.
+///
+/// # Safety
+/// `options` must be a valid `*mut DplyrOptions` or `std::ptr::null_mut()`.
+///
+/// # Returns
+/// 0 on success, -1 for a null pointer
+#[no_mangle]
+pub unsafe extern "C" fn dplyr_options_set_max_output_length(
+    options: *mut DplyrOptions,
+    max_output_length: u32,
+) -> i32 {
+    set_limit(options, |opts| {
+        opts.max_output_length = clamp_limit(max_output_length, MAX_OUTPUT_LENGTH_CAP);
+    })
+}
+
+/// Set the longest run of one character accepted outside string literals
+///
+/// Values above `MAX_REPEATED_CHARS_CAP` are clamped; 0 restores the default.
+///
+/// # Safety
+/// `options` must be a valid `*mut DplyrOptions` or `std::ptr::null_mut()`.
+///
+/// # Returns
+/// 0 on success, -1 for a null pointer
+#[no_mangle]
+pub unsafe extern "C" fn dplyr_options_set_max_repeated_chars(
+    options: *mut DplyrOptions,
+    max_repeated_chars: u32,
+) -> i32 {
+    set_limit(options, |opts| {
+        opts.max_repeated_chars = clamp_limit(max_repeated_chars, MAX_REPEATED_CHARS_CAP);
+    })
+}
+
+/// Set how often one short substring may occur outside string literals
+///
+/// Values above `MAX_REPEATED_SUBSTRINGS_CAP` are clamped; 0 restores the default.
+///
+/// # Safety
+/// `options` must be a valid `*mut DplyrOptions` or `std::ptr::null_mut()`.
+///
+/// # Returns
+/// 0 on success, -1 for a null pointer
+#[no_mangle]
+pub unsafe extern "C" fn dplyr_options_set_max_repeated_substrings(
+    options: *mut DplyrOptions,
+    max_repeated_substrings: u32,
+) -> i32 {
+    set_limit(options, |opts| {
+        opts.max_repeated_substrings =
+            clamp_limit(max_repeated_substrings, MAX_REPEATED_SUBSTRINGS_CAP);
+    })
+}
+
+fn clamp_limit(value: u32, cap: usize) -> u32 {
+    (value as usize).min(cap) as u32
+}
+
+unsafe fn set_limit(options: *mut DplyrOptions, apply: impl FnOnce(&mut DplyrOptions)) -> i32 {
+    if options.is_null() {
+        return -1; // Null pointer error
+    }
+
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        let opts = unsafe { &mut *options };
+        apply(opts);
+        0
+    }));
+
+    result.unwrap_or(-4)
+}
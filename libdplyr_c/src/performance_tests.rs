@@ -5,7 +5,10 @@
 
 #[cfg(test)]
 mod tests {
-    use crate::{dplyr_compile, dplyr_free_string, DplyrOptions};
+    use crate::compile::pooled_transpiler_builds_for_test;
+    use crate::{
+        dplyr_compile, dplyr_free_string, DplyrOptions, MAX_INPUT_LENGTH, MAX_PROCESSING_TIME_MS,
+    };
     use std::ffi::{CStr, CString};
     use std::ptr;
     use std::time::Instant;
@@ -78,8 +81,6 @@ mod tests {
         let p95_index = (durations.len() as f64 * 0.95) as usize;
         let p95_duration = durations[p95_index];
 
-        println!("Simple query P95: {:?}", p95_duration);
-
         // R6-AC1: Simple queries should be under 2ms P95
         assert!(
             p95_duration.as_millis() as f64 <= SIMPLE_QUERY_TARGET_MS,
@@ -115,8 +116,6 @@ mod tests {
         let p95_index = (durations.len() as f64 * 0.95) as usize;
         let p95_duration = durations[p95_index];
 
-        println!("Complex query P95: {:?}", p95_duration);
-
         // R6-AC1: Complex queries should be under 15ms P95
         assert!(
             p95_duration.as_millis() as f64 <= COMPLEX_QUERY_TARGET_MS,
@@ -132,45 +131,44 @@ mod tests {
         let query = "select(mpg, cyl) %>% filter(mpg > 20)";
 
         // Ensure a clean baseline for this test (tests may run in parallel).
-        assert_eq!(crate::dplyr_cache_clear(), 0);
-        assert_eq!(crate::dplyr_cache_get_hits(), 0);
-        assert_eq!(crate::dplyr_cache_get_misses(), 0);
+        assert_eq!(crate::cache::dplyr_cache_clear(), 0);
+        assert_eq!(crate::cache::dplyr_cache_get_hits(), 0);
+        assert_eq!(crate::cache::dplyr_cache_get_misses(), 0);
 
         // First call (cache miss)
-        let start = Instant::now();
         let result1 = safe_dplyr_compile_test(query, &options);
-        let cache_miss_duration = start.elapsed();
 
         assert!(result1.is_ok(), "First query should succeed");
-        assert_eq!(crate::dplyr_cache_get_misses(), 1, "First call should miss");
         assert_eq!(
-            crate::dplyr_cache_get_hits(),
+            crate::cache::dplyr_cache_get_misses(),
+            1,
+            "First call should miss"
+        );
+        assert_eq!(
+            crate::cache::dplyr_cache_get_hits(),
             0,
             "First call should not hit"
         );
 
         // Second call (cache hit)
-        let start = Instant::now();
         let result2 = safe_dplyr_compile_test(query, &options);
-        let cache_hit_duration = start.elapsed();
 
         assert!(result2.is_ok(), "Second query should succeed");
         assert_eq!(
-            crate::dplyr_cache_get_misses(),
+            crate::cache::dplyr_cache_get_misses(),
             1,
             "Second call should not add misses"
         );
-        assert_eq!(crate::dplyr_cache_get_hits(), 1, "Second call should hit");
+        assert_eq!(
+            crate::cache::dplyr_cache_get_hits(),
+            1,
+            "Second call should hit"
+        );
         assert_eq!(
             result1.unwrap(),
             result2.unwrap(),
             "Results should be identical"
         );
-
-        println!(
-            "Cache miss: {:?}, Cache hit: {:?}",
-            cache_miss_duration, cache_hit_duration
-        );
     }
 
     #[test]
@@ -206,8 +204,6 @@ mod tests {
             let p95_index = (durations.len() as f64 * 0.95) as usize;
             let p95_duration = durations[p95_index];
 
-            println!("Query '{}' P95: {:?}", query, p95_duration);
-
             // All simple queries should meet the performance target
             assert!(
                 p95_duration.as_millis() as f64 <= SIMPLE_QUERY_TARGET_MS,
@@ -251,8 +247,6 @@ mod tests {
             let p95_index = (durations.len() as f64 * 0.95) as usize;
             let p95_duration = durations[p95_index];
 
-            println!("Error handling for '{}' P95: {:?}", query, p95_duration);
-
             // Error handling should also be fast
             assert!(
                 p95_duration.as_millis() as f64 <= SIMPLE_QUERY_TARGET_MS,
@@ -290,4 +284,57 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_near_max_single_line_input_within_time_budget() {
+        let options = DplyrOptions::default();
+        let prefix = "data %>% filter(note == '";
+        let suffix = "')";
+        // Pseudo-random letters, so neither the cache nor the repetition limits short-circuit
+        let literal: String = (0u32..)
+            .map(|i| char::from(b'a' + ((i.wrapping_mul(2_654_435_761) >> 27) % 26) as u8))
+            .take(MAX_INPUT_LENGTH - prefix.len() - suffix.len())
+            .collect();
+        let query = format!("{prefix}{literal}{suffix}");
+        assert_eq!(query.len(), MAX_INPUT_LENGTH);
+        assert!(!query.contains('\n'));
+
+        let start = Instant::now();
+        let result = safe_dplyr_compile_test(&query, &options);
+        let elapsed = start.elapsed();
+
+        let sql = result.expect("near-max input should transpile");
+        assert!(sql.contains(&literal));
+        assert!(
+            elapsed.as_millis() < u128::from(MAX_PROCESSING_TIME_MS),
+            "near-max input took {elapsed:?}"
+        );
+
+        // Outside a string literal the same text stresses the repetition scan instead
+        let start = Instant::now();
+        let unquoted = format!("select({})", &literal[..literal.len() - 8]);
+        assert!(safe_dplyr_compile_test(&unquoted, &options).is_err());
+        let elapsed = start.elapsed();
+        assert!(elapsed.as_millis() < u128::from(MAX_PROCESSING_TIME_MS));
+    }
+
+    #[test]
+    fn test_transpiler_pool_avoids_per_call_construction() {
+        let options = DplyrOptions::default();
+        let builds_before = pooled_transpiler_builds_for_test();
+
+        // Distinct inputs miss the cache, so every call reaches the transpiler;
+        // output limits vary per call but share the pooled transpiler
+        for i in 0..200 {
+            let query = format!("select(col_{i}) %>% filter(col_{i} > {i})");
+            let options = DplyrOptions {
+                max_output_length: 4096 + i,
+                ..options.clone()
+            };
+            assert!(safe_dplyr_compile_test(&query, &options).is_ok());
+        }
+
+        // This thread builds one transpiler for the default dialect, not one per call
+        assert!(pooled_transpiler_builds_for_test() - builds_before <= 1);
+    }
 }
@@ -1,10 +1,15 @@
 //! System readiness checks for the C API.
 
 use std::ffi::CString;
+use std::os::raw::c_char;
 use std::panic;
 
-use crate::cache::SimpleTranspileCache;
-use crate::error::{DPLYR_ERROR_INTERNAL, DPLYR_ERROR_PANIC, DPLYR_SUCCESS};
+use crate::cache::{dplyr_cache_get_capacity, dplyr_cache_get_size, SimpleTranspileCache};
+use crate::compile::validated_dialect;
+use crate::error::{
+    DPLYR_ERROR_INTERNAL, DPLYR_ERROR_NULL_POINTER, DPLYR_ERROR_PANIC, DPLYR_SUCCESS,
+};
+use crate::ffi::set_sql_output;
 
 /// Validate system requirements and configuration.
 ///
@@ -16,8 +21,7 @@ pub extern "C" fn dplyr_check_system() -> i32 {
         // Check basic system requirements
 
         // 1. Check if we can allocate memory
-        let test_allocation = CString::new("test");
-        if test_allocation.is_err() {
+        if !can_allocate() {
             return DPLYR_ERROR_INTERNAL;
         }
 
@@ -26,8 +30,7 @@ pub extern "C" fn dplyr_check_system() -> i32 {
 
         // 3. Check cache system
         SimpleTranspileCache::clear_cache();
-        let stats = SimpleTranspileCache::get_cache_stats();
-        if stats.is_empty() {
+        if !cache_available() {
             return DPLYR_ERROR_INTERNAL;
         }
 
@@ -36,3 +39,81 @@ pub extern "C" fn dplyr_check_system() -> i32 {
 
     result.map_or(DPLYR_ERROR_PANIC, |code| code)
 }
+
+/// Validate system requirements and describe each subsystem as JSON.
+///
+/// Unlike `dplyr_check_system`, the cache is left untouched. The report holds
+/// `status`, `memory_allocation`, `cache` (`available`, `size`, `capacity`),
+/// `supported_dialects` and `build` (`version`, `rustc`, `timestamp`, `debug`).
+///
+/// # Safety
+/// `out_report` must be null or a valid `*mut *mut c_char`. On success the
+/// report must be freed with `dplyr_free_string`.
+///
+/// # Returns
+/// The same code as `dplyr_check_system`, or `DPLYR_ERROR_NULL_POINTER`
+/// when `out_report` is null
+#[no_mangle]
+pub unsafe extern "C" fn dplyr_check_system_detailed(out_report: *mut *mut c_char) -> i32 {
+    if out_report.is_null() {
+        return DPLYR_ERROR_NULL_POINTER;
+    }
+
+    let result = panic::catch_unwind(|| {
+        let memory_allocation = can_allocate();
+        let cache_available = cache_available();
+        let code = if memory_allocation && cache_available {
+            DPLYR_SUCCESS
+        } else {
+            DPLYR_ERROR_INTERNAL
+        };
+        let report = format!(
+            r#"{{
+    "status": "{}",
+    "memory_allocation": {memory_allocation},
+    "cache": {{
+        "available": {cache_available},
+        "size": {},
+        "capacity": {}
+    }},
+    "supported_dialects": {},
+    "build": {{
+        "version": "{}",
+        "rustc": "{}",
+        "timestamp": "{}",
+        "debug": {}
+    }}
+}}"#,
+            if code == DPLYR_SUCCESS { "ok" } else { "error" },
+            dplyr_cache_get_size(),
+            dplyr_cache_get_capacity(),
+            supported_dialect_count(),
+            env!("CARGO_PKG_VERSION"),
+            env!("RUSTC_VERSION"),
+            env!("BUILD_TIMESTAMP"),
+            cfg!(debug_assertions),
+        );
+        if set_sql_output(out_report, &report) {
+            code
+        } else {
+            DPLYR_ERROR_INTERNAL
+        }
+    });
+
+    result.map_or(DPLYR_ERROR_PANIC, |code| code)
+}
+
+fn can_allocate() -> bool {
+    CString::new("test").is_ok()
+}
+
+fn cache_available() -> bool {
+    !SimpleTranspileCache::get_cache_stats().is_empty()
+}
+
+/// Number of dialects the C API accepts, counted from `DplyrDialect` value 0.
+fn supported_dialect_count() -> usize {
+    (0..)
+        .take_while(|&raw| validated_dialect(raw).is_ok())
+        .count()
+}
@@ -6,7 +6,8 @@ use std::os::raw::c_char;
 
 use crate::cache::SimpleTranspileCache;
 use crate::cache::{
-    dplyr_cache_clear, dplyr_cache_get_hits, dplyr_cache_get_misses, dplyr_cache_get_size,
+    dplyr_cache_clear, dplyr_cache_get_capacity, dplyr_cache_get_evictions, dplyr_cache_get_hits,
+    dplyr_cache_get_misses, dplyr_cache_get_size, dplyr_cache_reset_stats, dplyr_cache_warmup,
 };
 use crate::compile::{
     acquire_ffi_test_gate_for_test, convert_libdplyr_error, force_ffi_panic_for_test,
@@ -16,7 +17,7 @@ use crate::error::{
     DPLYR_ERROR_NULL_POINTER, DPLYR_ERROR_PANIC, DPLYR_ERROR_SYNTAX, DPLYR_SUCCESS,
 };
 use crate::memory::alloc_owned_string;
-use crate::system::dplyr_check_system;
+use crate::system::{dplyr_check_system, dplyr_check_system_detailed};
 use crate::validation::{
     calculate_nesting_depth, contains_suspicious_patterns, count_function_calls,
     has_excessive_repetition, validate_input_encoding, validate_input_security,
@@ -100,6 +101,8 @@ mod ffi_tests {
             max_input_length: (MAX_INPUT_LENGTH + 1) as u32,
             max_processing_time_ms: MAX_PROCESSING_TIME_MS,
             dialect: DplyrDialect::DuckDb as u32,
+            trailing_semicolon: false,
+            ..DplyrOptions::default()
         };
         assert!(oversized_options.validate().is_err());
 
@@ -109,6 +112,8 @@ mod ffi_tests {
             max_input_length: 1024,
             max_processing_time_ms: 0, // Zero means use default
             dialect: DplyrDialect::DuckDb as u32,
+            trailing_semicolon: false,
+            ..DplyrOptions::default()
         };
         assert!(zero_timeout_options.validate().is_ok());
 
@@ -117,6 +122,8 @@ mod ffi_tests {
             max_input_length: 1024,
             max_processing_time_ms: MAX_PROCESSING_TIME_MS + 1000, // Too large
             dialect: DplyrDialect::DuckDb as u32,
+            trailing_semicolon: false,
+            ..DplyrOptions::default()
         };
         assert!(oversized_timeout_options.validate().is_err());
 
@@ -125,6 +132,8 @@ mod ffi_tests {
             max_input_length: 1024,
             max_processing_time_ms: MAX_PROCESSING_TIME_MS,
             dialect: 99,
+            trailing_semicolon: false,
+            ..DplyrOptions::default()
         };
         assert!(invalid_dialect_options.validate().is_err());
     }
@@ -362,6 +371,438 @@ mod ffi_tests {
         assert!(!sql.contains("\"name\""));
     }
 
+    #[test]
+    fn test_dplyr_compile_with_warnings_reports_degraded_translation() {
+        let _ = dplyr_cache_clear();
+        let input = CString::new("sales %>% mutate(prev = lag(amount))").unwrap();
+
+        // The second call is served from the cache and must report the same warning.
+        for _ in 0..2 {
+            let mut out_sql: *mut c_char = std::ptr::null_mut();
+            let mut out_warnings: *mut c_char = std::ptr::null_mut();
+            let mut out_error: *mut c_char = std::ptr::null_mut();
+
+            let result = unsafe {
+                dplyr_compile_with_warnings(
+                    input.as_ptr(),
+                    std::ptr::null(),
+                    &mut out_sql,
+                    &mut out_warnings,
+                    &mut out_error,
+                )
+            };
+
+            assert_eq!(result, DPLYR_SUCCESS);
+            assert!(out_error.is_null());
+            assert!(!out_sql.is_null());
+            assert!(!out_warnings.is_null());
+
+            unsafe {
+                let sql = CStr::from_ptr(out_sql).to_string_lossy().into_owned();
+                let warnings = CStr::from_ptr(out_warnings).to_string_lossy().into_owned();
+                assert!(sql.contains("LAG("), "{sql}");
+                assert!(warnings.starts_with("W-UNORDERED-WINDOW: "), "{warnings}");
+                assert_eq!(dplyr_free_string(out_sql), DPLYR_SUCCESS);
+                assert_eq!(dplyr_free_string(out_warnings), DPLYR_SUCCESS);
+            }
+        }
+    }
+
+    #[test]
+    fn test_dplyr_compile_trailing_semicolon_option() {
+        let _ = dplyr_cache_clear();
+        let input = CString::new("sales %>% select(amount)").unwrap();
+
+        let compile = |trailing_semicolon: bool| {
+            let options = DplyrOptions {
+                trailing_semicolon,
+                ..DplyrOptions::default()
+            };
+            let mut out_sql: *mut c_char = std::ptr::null_mut();
+            let mut out_error: *mut c_char = std::ptr::null_mut();
+
+            let result =
+                unsafe { dplyr_compile(input.as_ptr(), &options, &mut out_sql, &mut out_error) };
+            assert_eq!(result, DPLYR_SUCCESS);
+            assert!(out_error.is_null());
+
+            unsafe {
+                let sql = CStr::from_ptr(out_sql).to_string_lossy().into_owned();
+                assert_eq!(dplyr_free_string(out_sql), DPLYR_SUCCESS);
+                sql
+            }
+        };
+
+        let plain = compile(false);
+        assert!(!plain.ends_with(';'), "{plain}");
+
+        // Cached SQL is shared across the option, so repeated calls must not stack terminators.
+        for _ in 0..2 {
+            let terminated = compile(true);
+            assert_eq!(terminated, format!("{plain};"));
+        }
+        assert_eq!(compile(false), plain);
+    }
+
+    fn compile_with_options(code: &str, options: &DplyrOptions) -> Result<String, String> {
+        let input = CString::new(code).unwrap();
+        let mut out_sql: *mut c_char = std::ptr::null_mut();
+        let mut out_error: *mut c_char = std::ptr::null_mut();
+
+        let result =
+            unsafe { dplyr_compile(input.as_ptr(), options, &mut out_sql, &mut out_error) };
+        unsafe {
+            if result == DPLYR_SUCCESS {
+                let sql = CStr::from_ptr(out_sql).to_string_lossy().into_owned();
+                dplyr_free_string(out_sql);
+                Ok(sql)
+            } else {
+                let error = CStr::from_ptr(out_error).to_string_lossy().into_owned();
+                dplyr_free_string(out_error);
+                Err(error)
+            }
+        }
+    }
+
+    #[test]
+    fn test_dplyr_compile_provenance_comment_option() {
+        let _ = dplyr_cache_clear();
+        let code = "sales %>% select(amount)";
+        let mut options = DplyrOptions {
+            dialect: DplyrDialect::PostgreSql as u32,
+            trailing_semicolon: true,
+            ..DplyrOptions::default()
+        };
+        let plain = compile_with_options(code, &options).unwrap();
+
+        options.provenance_comment = true;
+        let commented = compile_with_options(code, &options).unwrap();
+        let (header, sql) = commented.split_once('\n').unwrap();
+        assert!(header.starts_with("-- generated by libdplyr "), "{header}");
+        assert!(header.ends_with("(dialect=postgresql)"), "{header}");
+        assert_eq!(sql, plain);
+        assert_eq!(sql.matches(';').count(), 1);
+    }
+
+    #[test]
+    fn test_dplyr_options_nesting_depth_limit_is_configurable() {
+        let _ = dplyr_cache_clear();
+        let code = "sales %>% filter((amount > 1) & region == 'EU')";
+        let mut options = DplyrOptions::default();
+
+        assert!(compile_with_options(code, &options).is_ok());
+
+        assert_eq!(
+            unsafe { dplyr_options_set_max_nesting_depth(&mut options, 1) },
+            0
+        );
+        let error = compile_with_options(code, &options).expect_err("depth 2 exceeds limit 1");
+        assert!(error.contains("nesting depth"), "{error}");
+
+        assert_eq!(
+            unsafe { dplyr_options_set_max_nesting_depth(&mut options, 2) },
+            0
+        );
+        assert!(compile_with_options(code, &options).is_ok());
+    }
+
+    #[test]
+    fn test_dplyr_options_function_call_limit_is_configurable() {
+        let _ = dplyr_cache_clear();
+        let code = "sales %>% select(amount) %>% filter(amount > 1)";
+        let mut options = DplyrOptions::default();
+        unsafe { dplyr_options_set_max_function_calls(&mut options, 2) };
+
+        // Cached under the looser limit first; the stricter limit must still apply.
+        assert!(compile_with_options(code, &options).is_ok());
+
+        unsafe { dplyr_options_set_max_function_calls(&mut options, 1) };
+        let error = compile_with_options(code, &options).expect_err("2 calls exceed limit 1");
+        assert!(error.contains("Too many function calls"), "{error}");
+    }
+
+    #[test]
+    fn test_dplyr_options_output_length_limit_is_configurable() {
+        let _ = dplyr_cache_clear();
+        let code = "sales %>% select(amount)";
+        let mut options = DplyrOptions::default();
+        unsafe { dplyr_options_set_max_output_length(&mut options, 10) };
+
+        let error = compile_with_options(code, &options).expect_err("SQL exceeds 10 bytes");
+        assert!(error.contains("Output too large"), "{error}");
+
+        unsafe { dplyr_options_set_max_output_length(&mut options, 4096) };
+        assert!(compile_with_options(code, &options).is_ok());
+    }
+
+    #[test]
+    fn test_dplyr_options_output_length_limit_can_be_raised_or_disabled() {
+        let _ = dplyr_cache_clear();
+        // Each reference to `c` inlines the ~900KB literal, so the SQL passes 10MB
+        let literal: String = (0u32..)
+            .map(|i| char::from(b'a' + ((i.wrapping_mul(2_654_435_761) >> 27) % 26) as u8))
+            .take(900_000)
+            .collect();
+        let comparisons: Vec<String> = (b'a'..=b'l')
+            .map(|letter| format!("c == '{}'", char::from(letter)))
+            .collect();
+        let code = format!(
+            "data %>% mutate(c = '{literal}') %>% filter({})",
+            comparisons.join(" | ")
+        );
+
+        let mut options = DplyrOptions::default();
+        let error = compile_with_options(&code, &options).expect_err("SQL exceeds 10MB");
+        assert!(error.contains("Output too large"), "{error}");
+
+        unsafe { dplyr_options_set_max_output_length(&mut options, MAX_OUTPUT_LENGTH_CAP as u32) };
+        let sql = compile_with_options(&code, &options).expect("64MB cap fits the SQL");
+        assert!(sql.len() > MAX_OUTPUT_LENGTH, "{}", sql.len());
+
+        unsafe { dplyr_options_set_max_output_length(&mut options, 0) };
+        assert_eq!(options.effective_max_output_length(), usize::MAX);
+        assert!(options.validate().is_ok());
+        assert_eq!(compile_with_options(&code, &options).unwrap(), sql);
+    }
+
+    fn last_limit_details() -> Option<DplyrLimitDetails> {
+        let mut details = DplyrLimitDetails::default();
+        unsafe { dplyr_last_error_limit_details(&mut details) }.then_some(details)
+    }
+
+    #[test]
+    fn test_input_too_large_reports_structured_limit_details() {
+        let code = "select(very_long_column_name_that_exceeds_limit)";
+        let options = DplyrOptions::with_settings(false, 10, DplyrDialect::DuckDb);
+
+        let error = compile_with_options(code, &options).expect_err("input exceeds 10 bytes");
+        assert_eq!(
+            error,
+            format!(
+                "E-INPUT-TOO-LARGE: Input size {} exceeds maximum 10",
+                code.len()
+            )
+        );
+        assert_eq!(
+            last_limit_details(),
+            Some(DplyrLimitDetails {
+                kind: DPLYR_LIMIT_INPUT_LENGTH,
+                input_length: code.len() as u64,
+                output_length: 0,
+                limit: 10,
+                elapsed_ms: 0,
+            })
+        );
+
+        // A later compile that succeeds clears the details
+        assert!(compile_with_options("select(a)", &DplyrOptions::default()).is_ok());
+        assert_eq!(last_limit_details(), None);
+        assert!(!unsafe { dplyr_last_error_limit_details(std::ptr::null_mut()) });
+    }
+
+    #[test]
+    fn test_output_too_large_reports_structured_limit_details() {
+        let _ = dplyr_cache_clear();
+        let code = "sales %>% select(amount)";
+        let mut options = DplyrOptions::default();
+        unsafe { dplyr_options_set_max_output_length(&mut options, 10) };
+
+        let error = compile_with_options(code, &options).expect_err("SQL exceeds 10 bytes");
+        let details = last_limit_details().expect("output limit details");
+        assert_eq!(details.kind, DPLYR_LIMIT_OUTPUT_LENGTH);
+        assert_eq!(details.input_length, code.len() as u64);
+        assert_eq!(details.limit, 10);
+        assert!(details.output_length > 10, "{details:?}");
+        assert!(
+            error.contains(&format!(
+                "Output too large: {} bytes exceeds maximum 10",
+                details.output_length
+            )),
+            "{error}"
+        );
+
+        // Errors unrelated to limits carry no details
+        assert!(compile_with_options("select(", &DplyrOptions::default()).is_err());
+        assert_eq!(last_limit_details(), None);
+    }
+
+    #[test]
+    fn test_repetition_limits_are_configurable() {
+        // A wide select repeats ", c" far more than the default allows
+        let columns = (1..=60)
+            .map(|i| format!("c{i}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let wide_select = format!("select({columns})");
+        assert!(compile_with_options(&wide_select, &DplyrOptions::default()).is_err());
+
+        let mut options = DplyrOptions::default();
+        unsafe { dplyr_options_set_max_repeated_substrings(&mut options, 1000) };
+        assert!(compile_with_options(&wide_select, &options).is_ok());
+
+        // Long runs still trip the default character limit
+        let dos = format!("select({})", "a".repeat(500));
+        let error = compile_with_options(&dos, &options).unwrap_err();
+        assert!(error.contains("excessive repetition"), "{error}");
+
+        // String literal contents are not scanned
+        let literal = format!("filter(name == \"{}\")", "x".repeat(500));
+        assert!(compile_with_options(&literal, &DplyrOptions::default()).is_ok());
+
+        unsafe {
+            dplyr_options_set_max_repeated_chars(&mut options, u32::MAX);
+            dplyr_options_set_max_repeated_substrings(&mut options, 0);
+        }
+        assert_eq!(
+            options.effective_max_repeated_chars(),
+            MAX_REPEATED_CHARS_CAP
+        );
+        assert_eq!(
+            options.effective_max_repeated_substrings(),
+            MAX_REPEATED_SUBSTRINGS
+        );
+        assert!(options.validate().is_ok());
+        let unclamped = DplyrOptions {
+            max_repeated_chars: (MAX_REPEATED_CHARS_CAP + 1) as u32,
+            ..DplyrOptions::default()
+        };
+        assert!(unclamped.validate().is_err());
+    }
+
+    #[test]
+    fn test_dplyr_options_limit_setters_clamp_and_validate() {
+        let mut options = DplyrOptions::default();
+        unsafe {
+            dplyr_options_set_max_nesting_depth(&mut options, u32::MAX);
+            dplyr_options_set_max_function_calls(&mut options, u32::MAX);
+            dplyr_options_set_max_output_length(&mut options, u32::MAX);
+        }
+        assert_eq!(options.effective_max_nesting_depth(), MAX_NESTING_DEPTH_CAP);
+        assert_eq!(
+            options.effective_max_function_calls(),
+            MAX_FUNCTION_CALLS_CAP
+        );
+        assert_eq!(options.effective_max_output_length(), MAX_OUTPUT_LENGTH_CAP);
+        assert!(options.validate().is_ok());
+
+        unsafe { dplyr_options_set_max_nesting_depth(&mut options, 0) };
+        assert_eq!(options.effective_max_nesting_depth(), MAX_NESTING_DEPTH);
+
+        let unclamped = DplyrOptions {
+            max_nesting_depth: (MAX_NESTING_DEPTH_CAP + 1) as u32,
+            ..DplyrOptions::default()
+        };
+        assert!(unclamped.validate().is_err());
+
+        assert_eq!(
+            unsafe { dplyr_options_set_max_output_length(std::ptr::null_mut(), 1) },
+            -1
+        );
+    }
+
+    #[test]
+    fn test_dplyr_compile_batch_mixes_successes_and_errors() {
+        let inputs = [
+            CString::new("sales %>% select(amount)").unwrap(),
+            CString::new("sales %>% select(").unwrap(),
+            CString::new("sales %>% filter(amount > 10)").unwrap(),
+        ];
+        let mut codes: Vec<*const c_char> = inputs.iter().map(|code| code.as_ptr()).collect();
+        codes.push(std::ptr::null());
+        let mut out_sqls = vec![std::ptr::null_mut::<c_char>(); codes.len()];
+        let mut out_errors = vec![std::ptr::null_mut::<c_char>(); codes.len()];
+
+        let successes = unsafe {
+            dplyr_compile_batch(
+                codes.as_ptr(),
+                codes.len(),
+                std::ptr::null(),
+                out_sqls.as_mut_ptr(),
+                out_errors.as_mut_ptr(),
+            )
+        };
+
+        assert_eq!(successes, 2);
+        for (index, succeeded) in [true, false, true, false].into_iter().enumerate() {
+            assert_eq!(!out_sqls[index].is_null(), succeeded, "item {index}");
+            assert_eq!(out_errors[index].is_null(), succeeded, "item {index}");
+        }
+        unsafe {
+            let sql = CStr::from_ptr(out_sqls[2]).to_string_lossy();
+            assert!(sql.contains("WHERE"), "{sql}");
+            let error = CStr::from_ptr(out_errors[3]).to_string_lossy();
+            assert!(error.starts_with("E-NULL-POINTER"), "{error}");
+
+            assert_eq!(dplyr_free_strings(out_sqls.as_mut_ptr(), out_sqls.len()), 2);
+            assert_eq!(
+                dplyr_free_strings(out_errors.as_mut_ptr(), out_errors.len()),
+                2
+            );
+        }
+    }
+
+    #[test]
+    fn test_dplyr_compile_batch_rejects_null_arrays() {
+        let mut out_sqls = [std::ptr::null_mut::<c_char>(); 1];
+        let mut out_errors = [std::ptr::null_mut::<c_char>(); 1];
+
+        let result = unsafe {
+            dplyr_compile_batch(
+                std::ptr::null(),
+                1,
+                std::ptr::null(),
+                out_sqls.as_mut_ptr(),
+                out_errors.as_mut_ptr(),
+            )
+        };
+        assert_eq!(result, DPLYR_ERROR_NULL_POINTER);
+
+        let empty = unsafe {
+            dplyr_compile_batch(
+                std::ptr::null(),
+                0,
+                std::ptr::null(),
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+            )
+        };
+        assert_eq!(empty, 0);
+    }
+
+    #[test]
+    fn test_dplyr_compile_with_warnings_leaves_warnings_null_for_exact_translation() {
+        let input = CString::new("sales %>% select(amount)").unwrap();
+        let mut out_sql: *mut c_char = std::ptr::null_mut();
+        let mut out_warnings: *mut c_char = std::ptr::null_mut();
+        let mut out_error: *mut c_char = std::ptr::null_mut();
+
+        let result = unsafe {
+            dplyr_compile_with_warnings(
+                input.as_ptr(),
+                std::ptr::null(),
+                &mut out_sql,
+                &mut out_warnings,
+                &mut out_error,
+            )
+        };
+
+        assert_eq!(result, DPLYR_SUCCESS);
+        assert!(out_warnings.is_null());
+        assert!(out_error.is_null());
+        assert_eq!(unsafe { dplyr_free_string(out_sql) }, DPLYR_SUCCESS);
+
+        let null_result = unsafe {
+            dplyr_compile_with_warnings(
+                input.as_ptr(),
+                std::ptr::null(),
+                &mut out_sql,
+                std::ptr::null_mut(),
+                &mut out_error,
+            )
+        };
+        assert_eq!(null_result, DPLYR_ERROR_NULL_POINTER);
+    }
+
     #[test]
     fn test_dplyr_compile_query_returns_not_handled_for_plain_sql() {
         let input = CString::new("SELECT 42").unwrap();
@@ -702,6 +1143,107 @@ mod ffi_tests {
         }
     }
 
+    #[test]
+    fn test_dplyr_compile_with_dialect_resolves_dialect_name() {
+        let input = CString::new("mtcars %>% select(mpg) %>% head(3)").unwrap();
+        let dialect_name = CString::new("postgres").unwrap();
+        // The name takes precedence over options->dialect
+        let options = dplyr_options_create(false, 1024, DplyrDialect::MySql as u32);
+        let mut out_sql: *mut c_char = std::ptr::null_mut();
+        let mut out_error: *mut c_char = std::ptr::null_mut();
+
+        let result = unsafe {
+            dplyr_compile_with_dialect(
+                input.as_ptr(),
+                dialect_name.as_ptr(),
+                &options,
+                &mut out_sql,
+                &mut out_error,
+            )
+        };
+
+        assert_eq!(result, DPLYR_SUCCESS);
+        assert!(out_error.is_null());
+
+        let sql = unsafe {
+            let sql = CStr::from_ptr(out_sql).to_string_lossy().into_owned();
+            dplyr_free_string(out_sql);
+            sql
+        };
+
+        assert!(sql.contains("FROM \"mtcars\""), "{sql}");
+        assert!(!sql.contains('`'), "{sql}");
+    }
+
+    #[test]
+    fn test_supported_dialect_names_match_from_name() {
+        let names = libdplyr::supported_dialect_names();
+        for name in names {
+            let dialect = DplyrDialect::from_name(name)
+                .unwrap_or_else(|err| panic!("{name} does not resolve: {err}"));
+            assert_eq!(
+                crate::compile::create_dialect(dialect).dialect_name(),
+                *name
+            );
+        }
+
+        // Every FFI dialect value is listed
+        let listed: Vec<&str> = (0..)
+            .map_while(|raw| DplyrDialect::try_from(raw).ok())
+            .map(|dialect| crate::compile::create_dialect(dialect).dialect_name())
+            .collect();
+        assert_eq!(listed.len(), names.len());
+        assert!(listed.iter().all(|name| names.contains(name)), "{listed:?}");
+
+        let exported = unsafe { CStr::from_ptr(dplyr_supported_dialects()) };
+        assert_eq!(exported.to_str().unwrap(), names.join(","));
+    }
+
+    #[test]
+    fn test_dplyr_compile_with_dialect_rejects_unknown_name() {
+        let input = CString::new("mtcars %>% select(mpg)").unwrap();
+        let dialect_name = CString::new("postgre").unwrap();
+        let mut out_sql: *mut c_char = std::ptr::null_mut();
+        let mut out_error: *mut c_char = std::ptr::null_mut();
+
+        let result = unsafe {
+            dplyr_compile_with_dialect(
+                input.as_ptr(),
+                dialect_name.as_ptr(),
+                std::ptr::null(),
+                &mut out_sql,
+                &mut out_error,
+            )
+        };
+
+        assert_ne!(result, DPLYR_SUCCESS);
+        assert!(out_sql.is_null());
+        assert!(!out_error.is_null());
+
+        let error = unsafe {
+            let error = CStr::from_ptr(out_error).to_string_lossy().into_owned();
+            dplyr_free_string(out_error);
+            error
+        };
+
+        assert!(error.contains("Unknown dialect name 'postgre'"), "{error}");
+        assert!(error.contains("postgresql"), "{error}");
+
+        let result = unsafe {
+            dplyr_compile_with_dialect(
+                input.as_ptr(),
+                std::ptr::null(),
+                std::ptr::null(),
+                &mut out_sql,
+                &mut out_error,
+            )
+        };
+        assert_eq!(result, DPLYR_ERROR_NULL_POINTER);
+        unsafe {
+            dplyr_free_string(out_error);
+        }
+    }
+
     #[test]
     fn test_dplyr_compile_query_reports_invalid_pipe_syntax_as_syntax_error() {
         let input = CString::new("mtcars |> select(mpg)").unwrap();
@@ -1109,6 +1651,8 @@ mod ffi_tests {
             max_input_length: 1024,
             max_processing_time_ms: MAX_PROCESSING_TIME_MS,
             dialect: 99,
+            trailing_semicolon: false,
+            ..DplyrOptions::default()
         };
         let mut out_sql: *mut c_char = std::ptr::null_mut();
         let mut out_error: *mut c_char = std::ptr::null_mut();
@@ -1143,6 +1687,8 @@ mod ffi_tests {
             max_input_length: 1024,
             max_processing_time_ms: MAX_PROCESSING_TIME_MS,
             dialect: 99,
+            trailing_semicolon: false,
+            ..DplyrOptions::default()
         };
         let mut out_sql: *mut c_char = std::ptr::null_mut();
         let mut out_error: *mut c_char = std::ptr::null_mut();
@@ -1422,6 +1968,37 @@ mod ffi_tests {
         assert!(unsafe { dplyr_is_valid_string_pointer(static_str.as_ptr() as *const c_char) });
     }
 
+    #[test]
+    fn test_dialect_capability_queries() {
+        let median = CString::new("median").unwrap();
+        let fill = CString::new("fill").unwrap();
+
+        unsafe {
+            assert_eq!(
+                dplyr_dialect_supports_aggregate(DplyrDialect::DuckDb as u32, median.as_ptr()),
+                1
+            );
+            assert_eq!(
+                dplyr_dialect_supports_aggregate(DplyrDialect::PostgreSql as u32, median.as_ptr()),
+                0
+            );
+            assert_eq!(
+                dplyr_dialect_supports_operation(DplyrDialect::PostgreSql as u32, fill.as_ptr()),
+                1
+            );
+            assert_eq!(
+                dplyr_dialect_supports_operation(DplyrDialect::Sqlite as u32, fill.as_ptr()),
+                0
+            );
+
+            assert_eq!(dplyr_dialect_supports_aggregate(99, median.as_ptr()), -1);
+            assert_eq!(
+                dplyr_dialect_supports_operation(DplyrDialect::DuckDb as u32, std::ptr::null()),
+                -1
+            );
+        }
+    }
+
     #[test]
     fn test_utility_functions() {
         let expected_version = env!("CARGO_PKG_VERSION");
@@ -1438,10 +2015,10 @@ mod ffi_tests {
 
         // Test supported dialects
         let dialects = unsafe { CStr::from_ptr(dplyr_supported_dialects()) };
-        assert!(dialects.to_string_lossy().contains("DuckDB"));
-        assert!(dialects.to_string_lossy().contains("PostgreSQL"));
-        assert!(dialects.to_string_lossy().contains("MySQL"));
-        assert!(dialects.to_string_lossy().contains("SQLite"));
+        assert!(dialects.to_string_lossy().contains("duckdb"));
+        assert!(dialects.to_string_lossy().contains("postgresql"));
+        assert!(dialects.to_string_lossy().contains("mysql"));
+        assert!(dialects.to_string_lossy().contains("sqlite"));
 
         // Test build timestamp (should not be empty)
         let timestamp = unsafe { CStr::from_ptr(dplyr_build_timestamp()) };
@@ -1521,6 +2098,34 @@ mod ffi_tests {
         assert!(validate_input_structure("select(col1 'unclosed").is_err()); // Unclosed string
     }
 
+    #[test]
+    fn test_input_structure_validation_mixed_quote_styles() {
+        // The other quote style is plain text inside a string
+        assert!(validate_input_structure(r#"filter(a == "it's")"#).is_ok());
+        assert!(validate_input_structure(r#"filter(a == 'he said "hi')"#).is_ok());
+        assert!(validate_input_structure(r#"filter(a == "(", b == ')')"#).is_ok());
+        assert!(validate_input_structure(r#"filter(a == 'it\'s')"#).is_ok());
+
+        // Quotes inside comments are not delimiters
+        assert!(validate_input_structure("data %>% # don't quote\n  select(a)").is_ok());
+
+        // A string is only closed by its own quote character
+        let error = validate_input_structure(r#"filter(a == "it's)"#).unwrap_err();
+        assert!(error.to_string().contains("unclosed"), "{error}");
+        assert!(validate_input_structure(r#"filter(a == 'he said "hi)"#).is_err());
+        assert!(validate_input_structure(r#"filter(a == "x') "#).is_err());
+    }
+
+    #[test]
+    fn test_validate_input_structure_reports_paren_offsets() {
+        let position = |input: &str| match validate_input_structure(input).unwrap_err() {
+            crate::error::TranspileError::Syntax { position, .. } => position,
+            other => panic!("expected syntax error, got {other:?}"),
+        };
+        assert_eq!(position("select(a %>% filter(b"), 6);
+        assert_eq!(position("select(a))"), 9);
+    }
+
     #[test]
     fn test_validate_input_security() {
         // Valid inputs
@@ -2111,6 +2716,18 @@ fn test_memory_management() {
 }
 
 // R8-AC1: Version and capability tests
+#[test]
+fn test_detailed_version_names_wrapper_and_core_versions() {
+    let detailed = unsafe { CStr::from_ptr(dplyr_version_detailed()) };
+    let detailed = detailed.to_str().unwrap();
+
+    assert!(detailed.contains(&format!("libdplyr_c v{}", env!("CARGO_PKG_VERSION"))));
+    assert!(detailed.contains(&format!("libdplyr v{}", libdplyr::VERSION)));
+    assert!(detailed.contains(env!("RUSTC_VERSION")));
+    // The pointer is cached, so repeated calls return the same string
+    assert_eq!(dplyr_version_detailed(), dplyr_version_detailed());
+}
+
 #[test]
 fn test_version_and_capabilities() {
     let expected_version = env!("CARGO_PKG_VERSION");
@@ -2125,10 +2742,10 @@ fn test_version_and_capabilities() {
 
     let dialects = unsafe { CStr::from_ptr(dplyr_supported_dialects()) };
     let dialects = dialects.to_str().unwrap();
-    assert!(dialects.contains("DuckDB"));
-    assert!(dialects.contains("PostgreSQL"));
-    assert!(dialects.contains("MySQL"));
-    assert!(dialects.contains("SQLite"));
+    assert!(dialects.contains("duckdb"));
+    assert!(dialects.contains("postgresql"));
+    assert!(dialects.contains("mysql"));
+    assert!(dialects.contains("sqlite"));
 
     let timestamp = unsafe { CStr::from_ptr(dplyr_build_timestamp()) };
     let timestamp_str = timestamp.to_str().unwrap();
@@ -2144,6 +2761,42 @@ fn test_version_and_capabilities() {
     assert_eq!(dplyr_check_system(), DPLYR_SUCCESS);
 }
 
+#[test]
+fn test_check_system_detailed_reports_subsystems_as_json() {
+    assert_eq!(
+        unsafe { dplyr_check_system_detailed(std::ptr::null_mut()) },
+        DPLYR_ERROR_NULL_POINTER
+    );
+
+    let mut report: *mut c_char = std::ptr::null_mut();
+    assert_eq!(
+        unsafe { dplyr_check_system_detailed(&mut report) },
+        DPLYR_SUCCESS
+    );
+    let json = unsafe { CStr::from_ptr(report) }
+        .to_str()
+        .unwrap()
+        .to_string();
+    assert_eq!(unsafe { dplyr_free_string(report) }, DPLYR_SUCCESS);
+
+    let report: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(report["status"], "ok");
+    assert_eq!(report["memory_allocation"], true);
+    assert_eq!(report["cache"]["available"], true);
+    assert_eq!(
+        report["cache"]["capacity"].as_u64(),
+        Some(dplyr_cache_get_capacity() as u64)
+    );
+    assert!(report["cache"]["size"].is_u64());
+    assert_eq!(
+        report["supported_dialects"],
+        libdplyr::supported_dialect_names().len()
+    );
+    assert_eq!(report["build"]["version"], env!("CARGO_PKG_VERSION"));
+    assert!(!report["build"]["timestamp"].as_str().unwrap().is_empty());
+    assert_eq!(report["build"]["debug"], cfg!(debug_assertions));
+}
+
 // Helper function tests
 #[test]
 fn test_helper_functions() {
@@ -2192,6 +2845,11 @@ fn test_error_conversion() {
     });
     let converted = convert_libdplyr_error(parse_error);
     assert_eq!(converted.to_c_error_code(), DPLYR_ERROR_SYNTAX);
+    // The parser's token position is carried through
+    assert!(matches!(
+        converted,
+        crate::error::TranspileError::Syntax { position: 10, .. }
+    ));
 
     let gen_error = libdplyr::TranspileError::GenerationError(
         libdplyr::GenerationError::UnsupportedOperation {
@@ -2203,6 +2861,35 @@ fn test_error_conversion() {
     assert_eq!(converted.to_c_error_code(), DPLYR_ERROR_UNSUPPORTED);
 }
 
+#[test]
+fn test_error_conversion_matches_shared_code_table() {
+    let errors = vec![
+        libdplyr::TranspileError::LexError(libdplyr::LexError::UnexpectedCharacter('@', 0)),
+        libdplyr::TranspileError::ParseError(libdplyr::ParseError::EmptyPipeline),
+        libdplyr::TranspileError::ValidationError("v".to_string()),
+        libdplyr::TranspileError::GenerationError(libdplyr::GenerationError::EmptyQuery),
+        libdplyr::TranspileError::GenerationError(libdplyr::GenerationError::OutputTooLarge {
+            length: 2,
+            max: 1,
+        }),
+        libdplyr::TranspileError::IoError("io".to_string()),
+        libdplyr::TranspileError::ConfigurationError("c".to_string()),
+        libdplyr::TranspileError::SystemError("s".to_string()),
+        libdplyr::TranspileError::SecurityError("s".to_string()),
+        libdplyr::TranspileError::TimeoutError("t".to_string()),
+        libdplyr::TranspileError::NetworkError("n".to_string()),
+    ];
+    for error in errors {
+        let expected = libdplyr::error_code_for(&error);
+        let converted = convert_libdplyr_error(error);
+        assert_eq!(converted.to_c_error_code(), expected, "{converted:?}");
+
+        let name = unsafe { CStr::from_ptr(dplyr_error_code_name(expected)) };
+        assert_eq!(name, libdplyr::error_code_name(expected));
+        assert_eq!(name.to_str().unwrap(), converted.get_error_code());
+    }
+}
+
 // Constants validation tests
 #[test]
 fn test_constants_validation() {
@@ -2373,6 +3060,91 @@ fn test_cache_effectiveness() {
     assert_eq!(dplyr_cache_get_misses(), 1);
 }
 
+#[test]
+fn test_cache_warmup_makes_first_compile_a_hit() {
+    let options = DplyrOptions::default();
+    let mysql_options = DplyrOptions {
+        dialect: DplyrDialect::MySql as u32,
+        ..DplyrOptions::default()
+    };
+    let queries = [
+        ("select(mpg, cyl) %>% filter(mpg > 20)", &options),
+        ("arrange(desc(hp))", &options),
+        ("select(mpg, cyl) %>% filter(mpg > 20)", &mysql_options),
+    ];
+    assert_eq!(dplyr_cache_clear(), 0);
+
+    assert_eq!(SimpleTranspileCache::warmup(&queries), queries.len());
+    assert_eq!(dplyr_cache_get_size(), queries.len());
+    assert_eq!(dplyr_cache_get_hits(), 0);
+    assert_eq!(dplyr_cache_get_misses(), 0);
+
+    // Re-warming cached queries is a no-op
+    assert_eq!(SimpleTranspileCache::warmup(&queries), queries.len());
+    assert_eq!(dplyr_cache_get_size(), queries.len());
+
+    for (query, query_options) in queries {
+        assert!(safe_dplyr_compile_test(query, query_options).is_ok());
+    }
+    assert_eq!(dplyr_cache_get_hits(), queries.len() as u64);
+    assert_eq!(dplyr_cache_get_misses(), 0);
+    assert_eq!(SimpleTranspileCache::get_hit_rate(), 1.0);
+}
+
+#[test]
+fn test_dplyr_cache_warmup_skips_invalid_queries() {
+    let valid = CString::new("select(mpg)").unwrap();
+    let invalid = CString::new("select(").unwrap();
+    let queries = [valid.as_ptr(), std::ptr::null(), invalid.as_ptr()];
+    assert_eq!(dplyr_cache_clear(), 0);
+
+    let warmed = unsafe { dplyr_cache_warmup(queries.as_ptr(), queries.len(), std::ptr::null()) };
+    assert_eq!(warmed, 1);
+    assert_eq!(dplyr_cache_get_size(), 1);
+    assert_eq!(dplyr_cache_get_misses(), 0);
+
+    assert!(safe_dplyr_compile_test("select(mpg)", &DplyrOptions::default()).is_ok());
+    assert_eq!(dplyr_cache_get_hits(), 1);
+
+    assert_eq!(
+        unsafe { dplyr_cache_warmup(std::ptr::null(), 1, std::ptr::null()) },
+        DPLYR_ERROR_NULL_POINTER
+    );
+    assert_eq!(
+        unsafe { dplyr_cache_warmup(std::ptr::null(), 0, std::ptr::null()) },
+        0
+    );
+}
+
+#[test]
+fn test_cache_reset_stats_keeps_entries() {
+    let options = DplyrOptions::default();
+    let query = "select(mpg, cyl) %>% filter(mpg > 20)";
+    assert_eq!(dplyr_cache_clear(), 0);
+
+    for _ in 0..3 {
+        assert!(safe_dplyr_compile_test(query, &options).is_ok());
+    }
+    assert!(safe_dplyr_compile_test("select(hp)", &options).is_ok());
+    assert_eq!(dplyr_cache_get_hits(), 2);
+    assert_eq!(dplyr_cache_get_misses(), 2);
+    let size_before = dplyr_cache_get_size();
+    assert_eq!(size_before, 2);
+
+    assert_eq!(dplyr_cache_reset_stats(), 0);
+
+    assert_eq!(dplyr_cache_get_size(), size_before);
+    assert_eq!(dplyr_cache_get_hits(), 0);
+    assert_eq!(dplyr_cache_get_misses(), 0);
+    assert_eq!(dplyr_cache_get_evictions(), 0);
+    assert_eq!(SimpleTranspileCache::get_hit_rate(), 0.0);
+
+    // Entries are still warm, so the next compile is a hit
+    assert!(safe_dplyr_compile_test(query, &options).is_ok());
+    assert_eq!(dplyr_cache_get_hits(), 1);
+    assert_eq!(dplyr_cache_get_misses(), 0);
+}
+
 // Helper function for performance tests
 #[allow(dead_code)]
 fn safe_dplyr_compile_test(query: &str, options: &DplyrOptions) -> Result<String, String> {
@@ -1,17 +1,41 @@
 //! Input validation and safeguards (DoS/malicious patterns).
 
+use std::collections::HashMap;
+
 use crate::error::TranspileError;
-use crate::options::{MAX_FUNCTION_CALLS, MAX_NESTING_DEPTH};
 
 // R9-AC2: Security validation functions for malicious input detection
+#[cfg(test)]
 pub fn validate_input_security(input: &str) -> Result<(), TranspileError> {
+    use crate::options::{
+        MAX_FUNCTION_CALLS, MAX_NESTING_DEPTH, MAX_REPEATED_CHARS, MAX_REPEATED_SUBSTRINGS,
+    };
+
+    validate_input_security_with_limits(
+        input,
+        MAX_NESTING_DEPTH,
+        MAX_FUNCTION_CALLS,
+        MAX_REPEATED_CHARS,
+        MAX_REPEATED_SUBSTRINGS,
+    )
+}
+
+/// Same checks as `validate_input_security`, with caller-provided limits
+/// for nesting depth, function call count and repetition.
+pub fn validate_input_security_with_limits(
+    input: &str,
+    max_nesting_depth: usize,
+    max_function_calls: usize,
+    max_repeated_chars: usize,
+    max_repeated_substrings: usize,
+) -> Result<(), TranspileError> {
     // Check for excessive nesting depth
     let nesting_depth = calculate_nesting_depth(input);
-    if nesting_depth > MAX_NESTING_DEPTH {
+    if nesting_depth > max_nesting_depth {
         return Err(TranspileError::internal_error_with_hint(
             &format!(
                 "Excessive nesting depth: {} exceeds maximum {}",
-                nesting_depth, MAX_NESTING_DEPTH
+                nesting_depth, max_nesting_depth
             ),
             Some("Reduce nested function calls or parentheses".to_string()),
         ));
@@ -19,11 +43,11 @@ pub fn validate_input_security(input: &str) -> Result<(), TranspileError> {
 
     // Check for excessive function calls
     let function_count = count_function_calls(input);
-    if function_count > MAX_FUNCTION_CALLS {
+    if function_count > max_function_calls {
         return Err(TranspileError::internal_error_with_hint(
             &format!(
                 "Too many function calls: {} exceeds maximum {}",
-                function_count, MAX_FUNCTION_CALLS
+                function_count, max_function_calls
             ),
             Some("Simplify the dplyr pipeline".to_string()),
         ));
@@ -38,7 +62,7 @@ pub fn validate_input_security(input: &str) -> Result<(), TranspileError> {
     }
 
     // Check for excessive repetition (potential DoS pattern)
-    if has_excessive_repetition(input) {
+    if has_excessive_repetition_with_limits(input, max_repeated_chars, max_repeated_substrings) {
         return Err(TranspileError::internal_error_with_hint(
             "Input contains excessive repetition patterns",
             Some("Reduce repetitive patterns in input".to_string()),
@@ -158,42 +182,83 @@ pub fn contains_suspicious_patterns(input: &str) -> bool {
     false
 }
 
+#[cfg(test)]
 pub fn has_excessive_repetition(input: &str) -> bool {
+    use crate::options::{MAX_REPEATED_CHARS, MAX_REPEATED_SUBSTRINGS};
+
+    has_excessive_repetition_with_limits(input, MAX_REPEATED_CHARS, MAX_REPEATED_SUBSTRINGS)
+}
+
+/// Same check as `has_excessive_repetition`, with caller-provided limits for
+/// identical-character runs and short substring occurrences.
+///
+/// String literal contents are skipped, so long quoted values only count
+/// their quotes.
+pub fn has_excessive_repetition_with_limits(
+    input: &str,
+    max_repeated_chars: usize,
+    max_repeated_substrings: usize,
+) -> bool {
     // Check for patterns that repeat excessively (potential DoS)
-    let chars: Vec<char> = input.chars().collect();
+    let chars = chars_outside_string_literals(input);
 
     // Check for repeated characters
     let mut consecutive_count = 1;
     for i in 1..chars.len() {
         if chars[i] == chars[i - 1] {
             consecutive_count += 1;
-            if consecutive_count > 100 {
-                return true; // More than 100 consecutive identical characters
+            if consecutive_count > max_repeated_chars {
+                return true;
             }
         } else {
             consecutive_count = 1;
         }
     }
 
-    // Check for repeated substrings
-    for pattern_len in 2..=10 {
-        if pattern_len * 20 > input.len() {
-            break;
+    // Check for repeated substrings. Every occurrence of a longer pattern starts
+    // with an occurrence of its first two characters, so counting character
+    // pairs catches any repeated pattern in one linear pass.
+    if 2 * max_repeated_substrings > chars.len() {
+        return false;
+    }
+    let mut pair_counts: HashMap<(char, char), usize> = HashMap::new();
+    for pair in chars.windows(2) {
+        let count = pair_counts.entry((pair[0], pair[1])).or_insert(0);
+        *count += 1;
+        if *count > max_repeated_substrings {
+            return true;
         }
+    }
 
-        let mut pattern_counts = std::collections::HashMap::new();
-        for i in 0..=(chars.len() - pattern_len) {
-            let pattern: String = chars[i..i + pattern_len].iter().collect();
-            *pattern_counts.entry(pattern).or_insert(0) += 1;
-        }
+    false
+}
 
-        // If any pattern repeats more than 20 times, consider it excessive
-        if pattern_counts.values().any(|&count| count > 20) {
-            return true;
+/// Characters of `input` with the contents of string literals removed; the
+/// quotes themselves are kept.
+fn chars_outside_string_literals(input: &str) -> Vec<char> {
+    let mut chars = Vec::with_capacity(input.len());
+    let mut string_char = None;
+    let mut escape_next = false;
+
+    for ch in input.chars() {
+        match string_char {
+            Some(_) if escape_next => escape_next = false,
+            Some(_) if ch == '\\' => escape_next = true,
+            Some(quote) if ch == quote => {
+                string_char = None;
+                chars.push(ch);
+            }
+            Some(_) => {}
+            None => {
+                if matches!(ch, '"' | '\'') {
+                    string_char = Some(ch);
+                }
+                chars.push(ch);
+            }
         }
     }
 
-    false
+    chars
 }
 
 // R9-AC2: Additional input validation functions
@@ -236,14 +301,24 @@ const fn is_confusing_unicode(ch: char) -> bool {
 
 pub fn validate_input_structure(input: &str) -> Result<(), TranspileError> {
     // Check for balanced parentheses, brackets, and braces
-    let mut paren_count = 0;
+    // Byte offsets of the `(` not yet closed
+    let mut open_parens = Vec::new();
     let mut bracket_count = 0;
     let mut brace_count = 0;
     let mut in_string = false;
     let mut escape_next = false;
     let mut string_char = '\0';
+    let mut in_comment = false;
+
+    for (offset, ch) in input.char_indices() {
+        // R comments run to end of line; quotes inside them are not delimiters
+        if in_comment {
+            if ch == '\n' {
+                in_comment = false;
+            }
+            continue;
+        }
 
-    for ch in input.chars() {
         if in_string {
             if escape_next {
                 escape_next = false;
@@ -266,17 +341,15 @@ pub fn validate_input_structure(input: &str) -> Result<(), TranspileError> {
                 in_string = true;
                 string_char = ch;
             }
-            '(' => paren_count += 1,
-            ')' => {
-                paren_count -= 1;
-                if paren_count < 0 {
-                    return Err(TranspileError::syntax_error_with_suggestion(
-                        "Unmatched closing parenthesis",
-                        0, // Position tracking would require more complex parsing
-                        Some(")".to_string()),
-                        Some("Check parentheses balance".to_string()),
-                    ));
-                }
+            '#' => in_comment = true,
+            '(' => open_parens.push(offset),
+            ')' if open_parens.pop().is_none() => {
+                return Err(TranspileError::syntax_error_with_suggestion(
+                    "Unmatched closing parenthesis",
+                    offset,
+                    Some(")".to_string()),
+                    Some("Check parentheses balance".to_string()),
+                ));
             }
             '[' => bracket_count += 1,
             ']' => {
@@ -307,10 +380,10 @@ pub fn validate_input_structure(input: &str) -> Result<(), TranspileError> {
     }
 
     // Check for unclosed delimiters
-    if paren_count > 0 {
+    if let Some(&first_unclosed) = open_parens.first() {
         return Err(TranspileError::syntax_error_with_suggestion(
-            &format!("{} unclosed parentheses", paren_count),
-            0,
+            &format!("{} unclosed parentheses", open_parens.len()),
+            first_unclosed,
             Some("(".to_string()),
             Some("Add missing closing parentheses".to_string()),
         ));
@@ -4,8 +4,23 @@
 //! It handles different verbosity levels and formats debug information appropriately.
 
 use colored::Colorize;
+use std::borrow::Cow;
 use std::time::{Duration, Instant};
 
+/// Shortens `text` to its first `max_chars` characters followed by an
+/// ellipsis and the number of characters left out, for log output.
+///
+/// Text within the limit is returned unchanged.
+pub fn truncate_for_log(text: &str, max_chars: usize) -> Cow<'_, str> {
+    match text.char_indices().nth(max_chars) {
+        Some((cut, _)) => {
+            let omitted = text[cut..].chars().count();
+            Cow::Owned(format!("{}... ({omitted} more characters)", &text[..cut]))
+        }
+        None => Cow::Borrowed(text),
+    }
+}
+
 /// Debug logger configuration
 #[derive(Debug, Clone)]
 pub struct DebugLoggerConfig {
@@ -17,6 +32,8 @@ pub struct DebugLoggerConfig {
     pub use_colors: bool,
     /// Use Korean language
     pub use_korean: bool,
+    /// Longest SQL or input echoed in debug output before it is truncated
+    pub max_logged_chars: Option<usize>,
 }
 
 impl Default for DebugLoggerConfig {
@@ -26,6 +43,7 @@ impl Default for DebugLoggerConfig {
             debug: false,
             use_colors: true,
             use_korean: false,
+            max_logged_chars: None,
         }
     }
 }
@@ -57,6 +75,20 @@ impl DebugLogger {
         })
     }
 
+    /// Sets the longest SQL or input echoed in debug output (`None` echoes it whole)
+    pub fn with_max_logged_chars(mut self, max_chars: Option<usize>) -> Self {
+        self.config.max_logged_chars = max_chars;
+        self
+    }
+
+    /// Applies the configured truncation to text echoed in debug output
+    pub fn truncated<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        match self.config.max_logged_chars {
+            Some(max_chars) => truncate_for_log(text, max_chars),
+            None => Cow::Borrowed(text),
+        }
+    }
+
     /// Log a verbose message
     pub fn verbose(&self, message: &str) {
         if self.config.verbose || self.config.debug {
@@ -134,6 +166,7 @@ impl DebugLogger {
                 "[SQL]".to_string()
             };
 
+            let sql = self.truncated(sql);
             eprintln!("{prefix} Generated {dialect} SQL:\\n{sql}");
         }
     }
@@ -242,6 +275,28 @@ mod tests {
         assert!(!logger.config.debug);
     }
 
+    #[test]
+    fn test_truncate_for_log_cuts_at_configured_length() {
+        assert_eq!(truncate_for_log("SELECT 1", 8), "SELECT 1");
+        assert_eq!(
+            truncate_for_log("SELECT * FROM t", 6),
+            "SELECT... (9 more characters)"
+        );
+        // Counts characters, not bytes
+        assert_eq!(
+            truncate_for_log("가나다라", 2),
+            "가나... (2 more characters)"
+        );
+
+        let logger = DebugLogger::with_settings(false, true).with_max_logged_chars(Some(6));
+        assert_eq!(
+            logger.truncated("SELECT * FROM t"),
+            "SELECT... (9 more characters)"
+        );
+        let unlimited = DebugLogger::with_settings(false, true);
+        assert_eq!(unlimited.truncated("SELECT * FROM t"), "SELECT * FROM t");
+    }
+
     #[test]
     fn test_elapsed_time() {
         let logger = DebugLogger::new(DebugLoggerConfig::default());
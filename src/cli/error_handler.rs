@@ -49,6 +49,9 @@ impl ExitCode {
 
     /// Internal error - unexpected internal error
     pub const INTERNAL_ERROR: i32 = 11;
+
+    /// Security error - input rejected as suspicious or resource-abusive
+    pub const SECURITY_ERROR: i32 = 12;
 }
 
 /// Categories of errors for better organization
@@ -66,6 +69,10 @@ pub enum ErrorCategory {
     Network,
     /// Internal/unexpected errors
     Internal,
+    /// Input rejected by security or DoS checks
+    Security,
+    /// Operations that ran out of time
+    Timeout,
 }
 
 /// Comprehensive error information
@@ -393,6 +400,36 @@ impl ErrorHandler {
                     ])
                 }
             }
+            TranspileError::SecurityError(e) => ErrorInfo::new(
+                ErrorCategory::Security,
+                ExitCode::SECURITY_ERROR,
+                format!("Security check failed: {e}"),
+            )
+            .with_description("The input was rejected as potentially malicious.".to_string())
+            .with_suggestions(vec![
+                "Remove control characters from the input".to_string(),
+                "Reduce deeply nested expressions".to_string(),
+            ]),
+            TranspileError::TimeoutError(e) => ErrorInfo::new(
+                ErrorCategory::Timeout,
+                ExitCode::TIMEOUT_ERROR,
+                format!("Timed out: {e}"),
+            )
+            .with_description("The operation did not complete in time.".to_string())
+            .with_suggestions(vec![
+                "Check that input is being piped to stdin".to_string(),
+                "Increase the --stdin-timeout value".to_string(),
+            ]),
+            TranspileError::NetworkError(e) => ErrorInfo::new(
+                ErrorCategory::Network,
+                ExitCode::NETWORK_ERROR,
+                format!("Network error: {e}"),
+            )
+            .with_description("The input could not be fetched from its URL.".to_string())
+            .with_suggestions(vec![
+                "Check that the URL is correct and reachable".to_string(),
+                "Download the file and pass its local path instead".to_string(),
+            ]),
             TranspileError::SystemError(e) => {
                 if self.use_korean {
                     ErrorInfo::new(
@@ -617,6 +654,8 @@ impl ErrorHandler {
             ErrorCategory::Configuration => ExitCode::CONFIG_ERROR,
             ErrorCategory::Network => ExitCode::NETWORK_ERROR,
             ErrorCategory::Internal => ExitCode::INTERNAL_ERROR,
+            ErrorCategory::Security => ExitCode::SECURITY_ERROR,
+            ErrorCategory::Timeout => ExitCode::TIMEOUT_ERROR,
         };
 
         ErrorInfo::new(category, exit_code, message.to_string())
@@ -713,7 +752,10 @@ impl ErrorHandler {
             TranspileError::LexError(_)
             | TranspileError::ParseError(_)
             | TranspileError::ValidationError(_) => self.handle_transpile_error(error),
-            TranspileError::GenerationError(_) => self.handle_transpile_error(error),
+            TranspileError::GenerationError(_)
+            | TranspileError::SecurityError(_)
+            | TranspileError::TimeoutError(_)
+            | TranspileError::NetworkError(_) => self.handle_transpile_error(error),
             TranspileError::IoError(msg) => {
                 let io_error = std::io::Error::other(msg.clone());
                 self.handle_io_error(&io_error)
@@ -752,6 +794,7 @@ mod tests {
         assert_eq!(ExitCode::NETWORK_ERROR, 9);
         assert_eq!(ExitCode::TIMEOUT_ERROR, 10);
         assert_eq!(ExitCode::INTERNAL_ERROR, 11);
+        assert_eq!(ExitCode::SECURITY_ERROR, 12);
     }
 
     #[test]
@@ -820,6 +863,33 @@ mod tests {
         assert_eq!(exit_code, ExitCode::IO_ERROR);
     }
 
+    #[test]
+    fn test_security_and_timeout_errors_get_their_own_categories() {
+        let handler = ErrorHandler::new();
+
+        let error_info = handler.convert_transpile_error(&TranspileError::SecurityError(
+            "control character at position 3".to_string(),
+        ));
+        assert_eq!(error_info.category, ErrorCategory::Security);
+        assert_eq!(error_info.exit_code, ExitCode::SECURITY_ERROR);
+
+        let error_info =
+            handler.convert_transpile_error(&TranspileError::TimeoutError("no input".to_string()));
+        assert_eq!(error_info.category, ErrorCategory::Timeout);
+        assert_eq!(error_info.exit_code, ExitCode::TIMEOUT_ERROR);
+    }
+
+    #[test]
+    fn test_network_error_gets_its_own_exit_code() {
+        let handler = ErrorHandler::new();
+        let error_info = handler.convert_transpile_error(&TranspileError::NetworkError(
+            "Failed to fetch 'https://example.com/q.R': connection refused".to_string(),
+        ));
+        assert_eq!(error_info.category, ErrorCategory::Network);
+        assert_eq!(error_info.exit_code, ExitCode::NETWORK_ERROR);
+        assert!(error_info.message.contains("https://example.com/q.R"));
+    }
+
     #[test]
     fn test_io_error_conversion() {
         let handler = ErrorHandler::new();
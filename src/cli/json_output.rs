@@ -5,6 +5,8 @@
 use serde::{Deserialize, Serialize};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+pub use crate::metadata::{InputInfo, MetadataBuilder, ProcessingStats, TranspileMetadata};
+
 /// Result type for JSON output operations
 pub type JsonResult<T> = Result<T, JsonError>;
 
@@ -21,69 +23,6 @@ pub enum JsonError {
     InvalidInput(String),
 }
 
-/// Transpilation metadata containing processing information
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TranspileMetadata {
-    /// Timestamp when transpilation started (Unix timestamp)
-    pub timestamp: u64,
-
-    /// SQL dialect used for transpilation
-    pub dialect: String,
-
-    /// Processing statistics
-    pub stats: ProcessingStats,
-
-    /// Input information
-    pub input_info: InputInfo,
-
-    /// Version information
-    pub version: String,
-}
-
-/// Processing statistics for transpilation
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ProcessingStats {
-    /// Time taken for lexical analysis (microseconds)
-    pub lex_time_us: u64,
-
-    /// Time taken for parsing (microseconds)
-    pub parse_time_us: u64,
-
-    /// Time taken for SQL generation (microseconds)
-    pub generation_time_us: u64,
-
-    /// Total processing time (microseconds)
-    pub total_time_us: u64,
-
-    /// Number of tokens generated
-    pub token_count: usize,
-
-    /// Number of AST nodes created
-    pub ast_node_count: usize,
-
-    /// Input size in bytes
-    pub input_size_bytes: usize,
-
-    /// Output size in bytes
-    pub output_size_bytes: usize,
-}
-
-/// Input source information
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct InputInfo {
-    /// Source type (file, stdin, text)
-    pub source_type: String,
-
-    /// Source identifier (filename, "stdin", "text")
-    pub source_id: String,
-
-    /// Input size in bytes
-    pub size_bytes: usize,
-
-    /// Number of lines in input
-    pub line_count: usize,
-}
-
 /// JSON output format for transpilation results
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JsonOutput {
@@ -109,6 +48,10 @@ pub struct ErrorInfo {
     /// Error message
     pub message: String,
 
+    /// Result code name shared with the C API (e.g. `E-SYNTAX`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+
     /// Position information (if available)
     pub position: Option<usize>,
 
@@ -301,148 +244,20 @@ impl Default for JsonOutputFormatter {
     }
 }
 
-/// Builder for creating TranspileMetadata
-#[derive(Debug)]
-pub struct MetadataBuilder {
-    dialect: String,
-    stats: ProcessingStats,
-    input_info: InputInfo,
-    version: String,
-}
-
-impl MetadataBuilder {
-    /// Creates a new metadata builder
-    pub fn new(dialect: &str) -> Self {
-        Self {
-            dialect: dialect.to_string(),
-            stats: ProcessingStats {
-                lex_time_us: 0,
-                parse_time_us: 0,
-                generation_time_us: 0,
-                total_time_us: 0,
-                token_count: 0,
-                ast_node_count: 0,
-                input_size_bytes: 0,
-                output_size_bytes: 0,
-            },
-            input_info: InputInfo {
-                source_type: "unknown".to_string(),
-                source_id: "unknown".to_string(),
-                size_bytes: 0,
-                line_count: 0,
-            },
-            version: env!("CARGO_PKG_VERSION").to_string(),
-        }
-    }
-
-    /// Sets processing statistics
-    pub const fn with_stats(mut self, stats: ProcessingStats) -> Self {
-        self.stats = stats;
-        self
-    }
-
-    /// Sets input information
-    pub fn with_input_info(mut self, input_info: InputInfo) -> Self {
-        self.input_info = input_info;
-        self
-    }
-
-    /// Sets version information
-    pub fn with_version(mut self, version: &str) -> Self {
-        self.version = version.to_string();
-        self
-    }
-
-    /// Builds the metadata
-    pub fn build(self) -> TranspileMetadata {
-        TranspileMetadata {
-            timestamp: SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs(),
-            dialect: self.dialect,
-            stats: self.stats,
-            input_info: self.input_info,
-            version: self.version,
-        }
-    }
-}
-
-/// Helper functions for creating common metadata components
-impl ProcessingStats {
-    /// Creates empty processing stats
-    pub const fn empty() -> Self {
-        Self {
-            lex_time_us: 0,
-            parse_time_us: 0,
-            generation_time_us: 0,
-            total_time_us: 0,
-            token_count: 0,
-            ast_node_count: 0,
-            input_size_bytes: 0,
-            output_size_bytes: 0,
-        }
-    }
-
-    /// Creates processing stats with timing information
-    pub const fn with_timing(
-        lex_time_us: u64,
-        parse_time_us: u64,
-        generation_time_us: u64,
-    ) -> Self {
-        Self {
-            lex_time_us,
-            parse_time_us,
-            generation_time_us,
-            total_time_us: lex_time_us + parse_time_us + generation_time_us,
-            token_count: 0,
-            ast_node_count: 0,
-            input_size_bytes: 0,
-            output_size_bytes: 0,
-        }
-    }
-}
-
-impl InputInfo {
-    /// Creates input info for file source
-    pub fn from_file(filename: &str, content: &str) -> Self {
-        Self {
-            source_type: "file".to_string(),
-            source_id: filename.to_string(),
-            size_bytes: content.len(),
-            line_count: content.lines().count(),
-        }
-    }
-
-    /// Creates input info for stdin source
-    pub fn from_stdin(content: &str) -> Self {
-        Self {
-            source_type: "stdin".to_string(),
-            source_id: "stdin".to_string(),
-            size_bytes: content.len(),
-            line_count: content.lines().count(),
-        }
-    }
-
-    /// Creates input info for text source
-    pub fn from_text(content: &str) -> Self {
-        Self {
-            source_type: "text".to_string(),
-            source_id: "command_line".to_string(),
-            size_bytes: content.len(),
-            line_count: content.lines().count(),
-        }
-    }
-}
-
 impl ErrorInfo {
     /// Creates error info from a transpile error
     pub fn from_transpile_error(error: &crate::TranspileError) -> Self {
+        let code = Some(
+            crate::error_code_name(crate::error_code_for(error))
+                .to_string_lossy()
+                .into_owned(),
+        );
         match error {
             crate::TranspileError::LexError(e) => Self {
                 error_type: "lex".to_string(),
                 message: e.to_string(),
-                position: None,
+                code,
+                position: e.position(),
                 suggestions: {
                     let mut suggestions = vec![
                         "Check for invalid characters or malformed strings".to_string(),
@@ -459,7 +274,8 @@ impl ErrorInfo {
             crate::TranspileError::ParseError(e) => Self {
                 error_type: "parse".to_string(),
                 message: e.to_string(),
-                position: None,
+                code,
+                position: e.position(),
                 suggestions: vec![
                     "Check dplyr function syntax and arguments".to_string(),
                     "Ensure proper use of pipe operator (%>%)".to_string(),
@@ -469,6 +285,7 @@ impl ErrorInfo {
             crate::TranspileError::GenerationError(e) => Self {
                 error_type: "generation".to_string(),
                 message: e.to_string(),
+                code,
                 position: None,
                 suggestions: vec![
                     "Try a different SQL dialect".to_string(),
@@ -479,6 +296,7 @@ impl ErrorInfo {
             crate::TranspileError::IoError(e) => Self {
                 error_type: "io".to_string(),
                 message: e.to_string(),
+                code,
                 position: None,
                 suggestions: vec![
                     "Check file permissions and paths".to_string(),
@@ -488,6 +306,7 @@ impl ErrorInfo {
             crate::TranspileError::ValidationError(e) => Self {
                 error_type: "validation".to_string(),
                 message: e.to_string(),
+                code,
                 position: None,
                 suggestions: vec![
                     "Check dplyr syntax and function usage".to_string(),
@@ -497,6 +316,7 @@ impl ErrorInfo {
             crate::TranspileError::ConfigurationError(e) => Self {
                 error_type: "configuration".to_string(),
                 message: e.to_string(),
+                code,
                 position: None,
                 suggestions: vec![
                     "Check configuration settings".to_string(),
@@ -506,12 +326,43 @@ impl ErrorInfo {
             crate::TranspileError::SystemError(e) => Self {
                 error_type: "system".to_string(),
                 message: e.to_string(),
+                code,
                 position: None,
                 suggestions: vec![
                     "Check system permissions".to_string(),
                     "Verify signal handling or pipeline configuration".to_string(),
                 ],
             },
+            crate::TranspileError::SecurityError(e) => Self {
+                error_type: "security".to_string(),
+                message: e.to_string(),
+                code,
+                position: None,
+                suggestions: vec![
+                    "Remove control characters from the input".to_string(),
+                    "Reduce deeply nested expressions".to_string(),
+                ],
+            },
+            crate::TranspileError::TimeoutError(e) => Self {
+                error_type: "timeout".to_string(),
+                message: e.to_string(),
+                code,
+                position: None,
+                suggestions: vec![
+                    "Check that input is being piped to stdin".to_string(),
+                    "Increase the --stdin-timeout value".to_string(),
+                ],
+            },
+            crate::TranspileError::NetworkError(e) => Self {
+                error_type: "network".to_string(),
+                message: e.to_string(),
+                code,
+                position: None,
+                suggestions: vec![
+                    "Check that the URL is correct and reachable".to_string(),
+                    "Download the file and pass its local path instead".to_string(),
+                ],
+            },
         }
     }
 }
@@ -532,6 +383,7 @@ impl TranspileMetadata {
                 total_time_us: 0,
                 token_count: 0,
                 ast_node_count: 0,
+                operation_count: 0,
                 input_size_bytes: 0,
                 output_size_bytes: 0,
             },
@@ -565,6 +417,7 @@ impl TranspileMetadata {
                 total_time_us: processing_time.as_micros() as u64,
                 token_count: 0,
                 ast_node_count: 0,
+                operation_count: 0,
                 input_size_bytes: input.len(),
                 output_size_bytes: output.len(),
             },
@@ -659,6 +512,7 @@ mod tests {
         let error_info = ErrorInfo {
             error_type: "parse".to_string(),
             message: "Invalid syntax".to_string(),
+            code: None,
             position: Some(10),
             suggestions: vec!["Check syntax".to_string()],
         };
@@ -693,6 +547,7 @@ mod tests {
         let error_info = ErrorInfo {
             error_type: "lex".to_string(),
             message: "Unexpected character".to_string(),
+            code: None,
             position: Some(5),
             suggestions: vec!["Check for invalid characters".to_string()],
         };
@@ -12,6 +12,8 @@ pub mod signal_handler;
 pub mod stdin_reader;
 pub mod validator;
 
+use std::io::IsTerminal;
+
 /// Main CLI entry point using the processing pipeline
 pub fn run_cli() -> i32 {
     // Parse command line arguments
@@ -20,6 +22,12 @@ pub fn run_cli() -> i32 {
     // Create CLI configuration from arguments
     let config = CliConfig::from_args(&args);
 
+    // Reading stdin from an interactive terminal would just wait for typing
+    if matches!(config.mode, CliMode::StdinMode { .. }) && !StdinReader::is_piped() {
+        eprintln!("{}", pipeline::usage_text());
+        return ExitCode::INVALID_ARGUMENTS;
+    }
+
     // Create processing pipeline
     let mut pipeline = match ProcessingPipeline::new(config) {
         Ok(pipeline) => pipeline,
@@ -29,6 +37,20 @@ pub fn run_cli() -> i32 {
         }
     };
 
+    if matches!(pipeline.config().mode, CliMode::ReplMode) {
+        let stdin = std::io::stdin();
+        let show_prompt = stdin.is_terminal();
+        return match pipeline.run_repl(
+            stdin.lock(),
+            &mut std::io::stdout(),
+            &mut std::io::stderr(),
+            show_prompt,
+        ) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(error) => pipeline.handle_error(&error),
+        };
+    }
+
     // Process input according to configuration
     match pipeline.process() {
         Ok(output) => {
@@ -54,7 +76,9 @@ pub use json_output::{
     TranspileMetadata,
 };
 pub use output_formatter::{FormatConfig, OutputFormat, OutputFormatter};
-pub use pipeline::{parse_args, CliArgs, CliConfig, CliMode, ProcessingPipeline, SqlDialectType};
+pub use pipeline::{
+    parse_args, CliArgs, CliConfig, CliMode, ProcessingPipeline, SqlDialectType, UrlFetcher,
+};
 pub use signal_handler::{
     utils, ProcessingError, SignalAwareProcessor, SignalError, SignalHandler,
 };
@@ -31,6 +31,8 @@ pub enum OutputFormat {
     Compact,
     /// JSON format - structured JSON output
     Json,
+    /// Minified format - single line, trailing semicolons and newline stripped
+    Minified,
 }
 
 impl fmt::Display for OutputFormat {
@@ -41,6 +43,7 @@ impl fmt::Display for OutputFormat {
             Self::Pretty => write!(f, "pretty"),
             Self::Compact => write!(f, "compact"),
             Self::Json => write!(f, "json"),
+            Self::Minified => write!(f, "minified"),
         }
     }
 }
@@ -56,6 +59,12 @@ pub struct FormatConfig {
     pub indent: String,
     /// Whether to preserve original case
     pub preserve_case: bool,
+    /// Whether to terminate the statement with a single `;`
+    pub trailing_semicolon: bool,
+    /// Comment line placed above the SQL, such as a provenance header
+    pub header_comment: Option<String>,
+    /// Whether to end lines with `\r\n` instead of `\n`
+    pub crlf: bool,
 }
 
 impl Default for FormatConfig {
@@ -65,6 +74,9 @@ impl Default for FormatConfig {
             add_newline: true,
             indent: "  ".to_string(),
             preserve_case: true,
+            trailing_semicolon: false,
+            header_comment: None,
+            crlf: false,
         }
     }
 }
@@ -113,9 +125,14 @@ impl OutputFormatter {
                 // For now, just return basic formatting
                 self.format_basic(sql)
             }
+            OutputFormat::Minified => {
+                let minified = self.format_minified(sql)?;
+                let minified = self.apply_header_comment(self.apply_trailing_semicolon(minified));
+                return Ok(self.apply_line_endings(minified));
+            }
         }?;
 
-        Ok(self.apply_final_formatting(formatted))
+        Ok(self.apply_line_endings(self.apply_final_formatting(formatted)))
     }
 
     /// Basic formatting - minimal processing
@@ -249,8 +266,45 @@ impl OutputFormatter {
         Ok(result)
     }
 
-    /// Applies final formatting options like newlines
-    fn apply_final_formatting(&self, mut formatted: String) -> String {
+    /// Minified formatting - one line with no trailing semicolon or newline,
+    /// suitable for embedding in JSON strings or log lines
+    fn format_minified(&self, sql: &str) -> FormatResult<String> {
+        let collapsed = self.format_compact(sql)?;
+        let minified = collapsed.trim_end_matches([';', ' ']).to_string();
+
+        if minified.is_empty() {
+            return Err(FormatError::InvalidSql("Empty SQL input".to_string()));
+        }
+
+        Ok(minified)
+    }
+
+    /// Ends the SQL with exactly one `;` when configured. Semicolons inside a
+    /// multi-statement script are left alone; only the final one is normalized.
+    fn apply_trailing_semicolon(&self, formatted: String) -> String {
+        if !self.config.trailing_semicolon {
+            return formatted;
+        }
+
+        let mut terminated = formatted
+            .trim_end_matches(|c: char| c == ';' || c.is_whitespace())
+            .to_string();
+        terminated.push(';');
+        terminated
+    }
+
+    /// Puts the header comment on its own line above the SQL, so it never
+    /// comments out the first statement of a multi-statement script.
+    fn apply_header_comment(&self, formatted: String) -> String {
+        match &self.config.header_comment {
+            Some(comment) => format!("{}\n{formatted}", comment.trim_end()),
+            None => formatted,
+        }
+    }
+
+    /// Applies final formatting options like trailing semicolons and newlines
+    fn apply_final_formatting(&self, formatted: String) -> String {
+        let mut formatted = self.apply_header_comment(self.apply_trailing_semicolon(formatted));
         if self.config.add_newline && !formatted.ends_with('\n') {
             formatted.push('\n');
         }
@@ -258,6 +312,15 @@ impl OutputFormatter {
         formatted
     }
 
+    /// Converts `\n` line endings to `\r\n` when configured.
+    fn apply_line_endings(&self, formatted: String) -> String {
+        if self.config.crlf {
+            formatted.replace('\n', "\r\n")
+        } else {
+            formatted
+        }
+    }
+
     /// Gets the current format configuration
     pub const fn config(&self) -> &FormatConfig {
         &self.config
@@ -289,6 +352,7 @@ mod tests {
         assert_eq!(OutputFormat::Basic.to_string(), "basic");
         assert_eq!(OutputFormat::Pretty.to_string(), "pretty");
         assert_eq!(OutputFormat::Compact.to_string(), "compact");
+        assert_eq!(OutputFormat::Minified.to_string(), "minified");
     }
 
     #[test]
@@ -298,6 +362,7 @@ mod tests {
         assert!(config.add_newline);
         assert_eq!(config.indent, "  ");
         assert!(config.preserve_case);
+        assert!(!config.trailing_semicolon);
     }
 
     #[test]
@@ -313,6 +378,9 @@ mod tests {
             add_newline: false,
             indent: "    ".to_string(),
             preserve_case: false,
+            trailing_semicolon: false,
+            header_comment: None,
+            crlf: false,
         };
         let custom_formatter = OutputFormatter::with_config(custom_config);
         assert_eq!(custom_formatter.config.format, OutputFormat::Compact);
@@ -353,6 +421,116 @@ mod tests {
         assert!(result.ends_with('\n'));
     }
 
+    #[test]
+    fn test_minified_formatting() {
+        let formatter = OutputFormatter::with_format(OutputFormat::Minified);
+        let sql = "SELECT name,\n  age\nFROM users\n\tWHERE age > 18;\n";
+
+        let result = formatter.format(sql).unwrap();
+        assert_eq!(result, "SELECT name, age FROM users WHERE age > 18");
+
+        // Only a lone semicolon is not a query
+        assert!(formatter.format(" ;\n").is_err());
+    }
+
+    #[test]
+    fn test_minified_matches_pretty_modulo_whitespace() {
+        let sql = "SELECT u.name, COUNT(o.id) AS orders FROM users u LEFT JOIN orders o ON u.id = o.user_id WHERE u.age > 18 AND u.active = TRUE GROUP BY u.name ORDER BY orders DESC";
+
+        let pretty = OutputFormatter::with_format(OutputFormat::Pretty)
+            .format(sql)
+            .unwrap();
+        let minified = OutputFormatter::with_format(OutputFormat::Minified)
+            .format(sql)
+            .unwrap();
+
+        assert!(pretty.lines().count() > 1);
+        assert!(!minified.contains('\n'));
+        assert!(!minified.contains("  "));
+        assert_eq!(
+            pretty.split_whitespace().collect::<Vec<_>>(),
+            minified.split_whitespace().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_trailing_semicolon() {
+        let sql = "SELECT name FROM users";
+
+        let without = OutputFormatter::new().format(sql).unwrap();
+        assert_eq!(without, "SELECT name FROM users\n");
+
+        let config = FormatConfig {
+            trailing_semicolon: true,
+            ..Default::default()
+        };
+        let formatter = OutputFormatter::with_config(config);
+        assert_eq!(formatter.format(sql).unwrap(), "SELECT name FROM users;\n");
+
+        // Already terminated input is not double-terminated
+        assert_eq!(
+            formatter.format("SELECT name FROM users ;\n").unwrap(),
+            "SELECT name FROM users;\n"
+        );
+    }
+
+    #[test]
+    fn test_trailing_semicolon_multi_statement_script() {
+        let config = FormatConfig {
+            format: OutputFormat::Pretty,
+            trailing_semicolon: true,
+            ..Default::default()
+        };
+        let formatter = OutputFormatter::with_config(config);
+
+        let result = formatter
+            .format("SELECT a FROM t1; SELECT b FROM t2;")
+            .unwrap();
+        assert_eq!(result.matches(';').count(), 2);
+        assert!(result.ends_with("t2;\n"));
+        assert!(!result.contains(";;"));
+
+        let minified = OutputFormatter::with_config(FormatConfig {
+            format: OutputFormat::Minified,
+            trailing_semicolon: true,
+            ..Default::default()
+        });
+        assert_eq!(
+            minified
+                .format("SELECT a FROM t1;\nSELECT b FROM t2;;")
+                .unwrap(),
+            "SELECT a FROM t1; SELECT b FROM t2;"
+        );
+    }
+
+    #[test]
+    fn test_header_comment() {
+        let header = "-- generated by libdplyr 0.0.0 (dialect=postgresql)";
+        let script = "SELECT a FROM t1; SELECT b FROM t2";
+
+        for format in [
+            OutputFormat::Basic,
+            OutputFormat::Pretty,
+            OutputFormat::Minified,
+        ] {
+            let formatter = OutputFormatter::with_config(FormatConfig {
+                format,
+                trailing_semicolon: true,
+                header_comment: Some(header.to_string()),
+                ..Default::default()
+            });
+            let result = formatter.format(script).unwrap();
+
+            let (first_line, rest) = result.split_once('\n').unwrap();
+            assert_eq!(first_line, header);
+            assert!(rest.starts_with("SELECT"));
+            assert_eq!(rest.matches(';').count(), 2);
+        }
+
+        let without = OutputFormatter::new().format(script).unwrap();
+        assert!(!without.contains("--"));
+    }
+
     #[test]
     fn test_pretty_formatting_with_joins() {
         let formatter = OutputFormatter::with_format(OutputFormat::Pretty);
@@ -396,6 +574,9 @@ mod tests {
             add_newline: false,
             indent: "    ".to_string(),
             preserve_case: false,
+            trailing_semicolon: false,
+            header_comment: None,
+            crlf: false,
         };
         formatter.set_config(new_config);
         assert_eq!(formatter.config().format, OutputFormat::Compact);
@@ -415,6 +596,32 @@ mod tests {
         assert!(!result.ends_with('\n'));
     }
 
+    #[test]
+    fn test_crlf_line_endings() {
+        let sql = "SELECT a FROM t1 WHERE a > 1";
+        for format in [OutputFormat::Pretty, OutputFormat::Minified] {
+            let formatter = OutputFormatter::with_config(FormatConfig {
+                format,
+                crlf: true,
+                header_comment: Some("-- header".to_string()),
+                ..Default::default()
+            });
+            let result = formatter.format(sql).unwrap();
+            assert!(result.starts_with("-- header\r\n"), "{result:?}");
+            assert_eq!(
+                result.matches('\n').count(),
+                result.matches("\r\n").count(),
+                "{result:?}"
+            );
+        }
+
+        let formatter = OutputFormatter::with_config(FormatConfig {
+            crlf: true,
+            ..Default::default()
+        });
+        assert_eq!(formatter.format(sql).unwrap(), format!("{sql}\r\n"));
+    }
+
     #[test]
     fn test_complex_query_formatting() {
         let formatter = OutputFormatter::with_format(OutputFormat::Pretty);
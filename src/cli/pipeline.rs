@@ -6,18 +6,32 @@
 use crate::cli::{
     debug_logger::DebugLogger,
     signal_handler::{utils, ProcessingError, SignalAwareProcessor, SignalHandler},
-    DplyrValidator, ErrorHandler, ExitCode, JsonOutputFormatter, OutputFormat, OutputFormatter,
-    StdinReader, TranspileMetadata, ValidateResult, ValidationConfig,
+    stdin_reader::{StdinConfig, StdinError},
+    DplyrValidator, ErrorHandler, ExitCode, FormatConfig, JsonOutputFormatter, OutputFormat,
+    OutputFormatter, StdinReader, TranspileMetadata, ValidateResult, ValidationConfig,
 };
 use crate::{
-    DuckDbDialect, MySqlDialect, PipeSyntax, PostgreSqlDialect, SqlDialect, SqliteDialect,
-    TranspileError, Transpiler,
+    dialect_from_name, supported_dialect_names, DuckDbDialect, MySqlDialect, OracleDialect,
+    PipeSyntax, PostgreSqlDialect, SparkSqlDialect, SqlDialect, SqliteDialect, TranspileError,
+    Transpiler,
 };
 use clap::{value_parser, Arg, ArgMatches, Command};
-use std::io::{self, Write};
+use std::collections::HashSet;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 const DIALECT_ENV_VAR: &str = "DPLYR_DIALECT";
 
+/// Prompt shown before each line in an interactive `--repl` session.
+const REPL_PROMPT: &str = "dplyr> ";
+
+/// Fetches the contents of an `http://` or `https://` input.
+pub type UrlFetcher = fn(&str) -> Result<String, TranspileError>;
+
+/// Deepest bracket nesting accepted from CLI input, matching the C API limit.
+const MAX_INPUT_NESTING_DEPTH: usize = 50;
+
 /// CLI arguments structure
 #[derive(Debug, Clone)]
 pub struct CliArgs {
@@ -27,10 +41,22 @@ pub struct CliArgs {
     pub pretty_print: bool,
     pub input_text: Option<String>,
     pub validate_only: bool,
+    pub check: bool,
     pub verbose: bool,
     pub debug: bool,
+    pub debug_max_chars: Option<usize>,
     pub compact: bool,
+    pub minify: bool,
+    pub trailing_semicolon: bool,
+    pub provenance_comment: bool,
+    pub crlf: bool,
+    pub no_trailing_newline: bool,
     pub json_output: bool,
+    pub input_files: Vec<String>,
+    pub output_dir: Option<String>,
+    pub stdin_timeout_ms: Option<u64>,
+    pub diff_dialects: Option<(SqlDialectType, SqlDialectType)>,
+    pub repl: bool,
 }
 
 /// Supported SQL dialect types
@@ -40,6 +66,8 @@ pub enum SqlDialectType {
     MySql,
     Sqlite,
     DuckDb,
+    Oracle,
+    Spark,
 }
 
 impl std::fmt::Display for SqlDialectType {
@@ -49,6 +77,8 @@ impl std::fmt::Display for SqlDialectType {
             Self::MySql => write!(f, "mysql"),
             Self::Sqlite => write!(f, "sqlite"),
             Self::DuckDb => write!(f, "duckdb"),
+            Self::Oracle => write!(f, "oracle"),
+            Self::Spark => write!(f, "spark"),
         }
     }
 }
@@ -57,19 +87,37 @@ impl std::str::FromStr for SqlDialectType {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
-            "postgresql" | "postgres" | "pg" => Ok(Self::PostgreSql),
+        let dialect =
+            dialect_from_name(s).ok_or_else(|| format!("Unsupported SQL dialect: {s}"))?;
+        match dialect.dialect_name() {
+            "postgresql" => Ok(Self::PostgreSql),
             "mysql" => Ok(Self::MySql),
             "sqlite" => Ok(Self::Sqlite),
-            "duckdb" | "duck" => Ok(Self::DuckDb),
-            _ => Err(format!("Unsupported SQL dialect: {s}")),
+            "duckdb" => Ok(Self::DuckDb),
+            "oracle" => Ok(Self::Oracle),
+            "spark" => Ok(Self::Spark),
+            other => Err(format!("Unsupported SQL dialect: {other}")),
         }
     }
 }
 
 /// Parses CLI arguments.
 pub fn parse_args() -> CliArgs {
-    let matches = Command::new("libdplyr")
+    parse_matches(&build_command().get_matches())
+}
+
+/// Returns the short usage line followed by a hint about providing input.
+pub fn usage_text() -> String {
+    format!(
+        "{}\n\nNo input provided: pipe dplyr code on stdin, or use -t/--text or -i/--input.\n\
+         For more information, try '--help'.",
+        build_command().render_usage()
+    )
+}
+
+/// Builds the clap command definition for the CLI.
+fn build_command() -> Command {
+    Command::new("libdplyr")
         .version(env!("CARGO_PKG_VERSION"))
         .author("libdplyr contributors")
         .about("A transpiler that converts R dplyr syntax to SQL")
@@ -78,6 +126,10 @@ pub fn parse_args() -> CliArgs {
                      Examples:\n  \
                      libdplyr -t \"data %>% select(name, age) %>% filter(age > 18)\"\n  \
                      libdplyr -i input.R -o output.sql -d mysql -p\n  \
+                     libdplyr -d mysql --output-dir out/ input1.R input2.R\n  \
+                     libdplyr --output-dir out/ 'queries/*.R'\n  \
+                     libdplyr --diff postgres mysql -t \"data %>% select(name)\"\n  \
+                     libdplyr --repl -d duckdb\n  \
                      echo \"data %>% select(*)\" | libdplyr -d sqlite")
         .arg(
             Arg::new("input")
@@ -96,18 +148,41 @@ pub fn parse_args() -> CliArgs {
                 .help("Output SQL file path (stdout if not specified)")
                 .long_help("Write the generated SQL to the specified file. If not provided, output goes to stdout."),
         )
+        .arg(
+            Arg::new("output-dir")
+                .long("output-dir")
+                .value_name("DIR")
+                .help("Write one SQL file per input file into DIR")
+                .long_help("Batch mode: transpile every input file and write DIR/<name>.sql for each.\n\
+                           Processing continues past per-file errors; failures are summarized at the end\n\
+                           and the exit code is nonzero if any file failed.")
+                .conflicts_with_all(["text", "output"]),
+        )
+        .arg(
+            Arg::new("inputs")
+                .value_name("FILES")
+                .help("Input dplyr files or glob patterns (e.g. 'queries/*.R') for batch processing")
+                .num_args(1..)
+                .action(clap::ArgAction::Append)
+                .conflicts_with("text"),
+        )
         .arg(
             Arg::new("dialect")
                 .short('d')
                 .long("dialect")
                 .value_name("DIALECT")
-                .help("Target SQL dialect [possible values: postgresql, mysql, sqlite, duckdb]")
+                .help(format!(
+                    "Target SQL dialect [possible values: {}]",
+                    supported_dialect_names().join(", ")
+                ))
                 .long_help("Specify the target SQL dialect for code generation.\n\
                            Supported dialects:\n  \
                            postgresql, postgres, pg - PostgreSQL\n  \
                            mysql - MySQL\n  \
                            sqlite - SQLite\n  \
-                           duckdb, duck - DuckDB\n\n\
+                           duckdb, duck - DuckDB\n  \
+                           oracle - Oracle\n  \
+                           spark - Spark SQL\n\n\
                            If omitted, the CLI reads DPLYR_DIALECT and falls back to postgresql.")
                 .value_parser(value_parser!(SqlDialectType))
         )
@@ -135,6 +210,16 @@ pub fn parse_args() -> CliArgs {
                 .long_help("Perform syntax validation only without SQL generation. Returns exit code 0 for valid syntax, 1 for invalid syntax.")
                 .action(clap::ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new("check")
+                .long("check")
+                .help("Check that the input transpiles for the dialect without printing SQL")
+                .long_help("Validate the dplyr syntax and generate SQL for the target dialect without printing it, \
+                           so dialect-specific failures such as median() on MySQL are reported too. \
+                           Prints OK and exits 0, or prints the first error and exits nonzero. Suited to linting snippets in CI.")
+                .conflicts_with_all(["validate-only", "diff", "repl", "json"])
+                .action(clap::ArgAction::SetTrue),
+        )
         .arg(
             Arg::new("verbose")
                 .short('v')
@@ -150,6 +235,14 @@ pub fn parse_args() -> CliArgs {
                 .long_help("Display detailed debug information including AST structure and conversion steps. Implies --verbose.")
                 .action(clap::ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new("debug-max-chars")
+                .long("debug-max-chars")
+                .value_name("N")
+                .help("Truncate SQL and input echoed by --debug to N characters")
+                .long_help("Cut the generated SQL and the input echoed in --debug output after N characters, with an ellipsis and the count of characters left out, so diagnostics stay readable for huge outputs. Does not affect the SQL written to the output.")
+                .value_parser(value_parser!(usize)),
+        )
         .arg(
             Arg::new("compact")
                 .short('c')
@@ -159,6 +252,42 @@ pub fn parse_args() -> CliArgs {
                 .conflicts_with("pretty")
                 .action(clap::ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new("minify")
+                .long("minify")
+                .help("Generate single-line SQL without a trailing semicolon or newline")
+                .long_help("Output SQL on a single line with all whitespace collapsed and no trailing semicolon or newline, suitable for embedding in JSON or logs. Conflicts with --pretty and --compact.")
+                .conflicts_with_all(["pretty", "compact"])
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("semicolon")
+                .long("semicolon")
+                .help("Terminate generated SQL with a trailing semicolon")
+                .long_help("Append a single ';' to the generated SQL. Output that already ends with a semicolon is not terminated twice. Has no effect on --json output.")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("crlf")
+                .long("crlf")
+                .help("End output lines with CRLF (\\r\\n) instead of LF")
+                .long_help("Write Windows-style CRLF line endings, including the trailing newline. Has no effect on --json output.")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("no-trailing-newline")
+                .long("no-trailing-newline")
+                .help("Do not end the generated SQL with a newline")
+                .long_help("Omit the newline normally written after the generated SQL. --minify output never has one. Has no effect on --json output.")
+                .action(clap::ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("provenance")
+                .long("provenance")
+                .help("Prefix generated SQL with a comment naming the libdplyr version and dialect")
+                .long_help("Put a line comment such as '-- generated by libdplyr <version> (dialect=<dialect>)' on its own line above the generated SQL. Has no effect on --json output.")
+                .action(clap::ArgAction::SetTrue),
+        )
         .arg(
             Arg::new("json")
                 .short('j')
@@ -167,9 +296,45 @@ pub fn parse_args() -> CliArgs {
                 .long_help("Output SQL and metadata in JSON format. Includes dialect information, processing statistics, and timestamps.")
                 .action(clap::ArgAction::SetTrue),
         )
-        .get_matches();
-
-    parse_matches(&matches)
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .value_name("FORMAT")
+                .help("Output format: text or json")
+                .long_help("Select the output format. 'json' is equivalent to --json: results and errors are written as a JSON envelope,\n\
+                           with lex/parse/generation failures reported as an error object (error_type, message, code, position)\n\
+                           and a nonzero exit code. 'text' is the default.")
+                .value_parser(["text", "json"]),
+        )
+        .arg(
+            Arg::new("diff")
+                .long("diff")
+                .num_args(2)
+                .value_names(["DIALECT_A", "DIALECT_B"])
+                .help("Show a line diff of the SQL generated for two dialects")
+                .long_help("Transpile the input for both dialects and print a unified line diff of the two SQL strings,\n\
+                           e.g. to review identifier quoting and function differences before a migration.\n\
+                           Overrides -d/--dialect; cannot be combined with --validate-only, --json or batch mode.")
+                .value_parser(value_parser!(SqlDialectType))
+                .conflicts_with_all(["validate-only", "json", "output-dir"]),
+        )
+        .arg(
+            Arg::new("repl")
+                .long("repl")
+                .help("Start an interactive session that transpiles one line at a time")
+                .long_help("Read dplyr expressions line by line and print the SQL for each; errors are reported without ending the session.\n\
+                           `.dialect NAME` switches the dialect, `.quit` or EOF (Ctrl-D) exits.")
+                .action(clap::ArgAction::SetTrue)
+                .conflicts_with_all(["input", "text", "inputs", "diff", "validate-only", "json", "output-dir"]),
+        )
+        .arg(
+            Arg::new("stdin-timeout")
+                .long("stdin-timeout")
+                .value_name("MS")
+                .help("Fail if stdin delivers no data for MS milliseconds")
+                .long_help("Stop waiting for piped input after MS milliseconds without data and exit with an I/O error instead of blocking. Without this option the CLI waits until the writer closes stdin.")
+                .value_parser(value_parser!(u64).range(1..)),
+        )
 }
 
 /// Creates CliArgs from ArgMatches.
@@ -184,10 +349,28 @@ fn parse_matches(matches: &ArgMatches) -> CliArgs {
         pretty_print: matches.get_flag("pretty"),
         input_text: matches.get_one::<String>("text").cloned(),
         validate_only: matches.get_flag("validate-only"),
+        check: matches.get_flag("check"),
         verbose: matches.get_flag("verbose"),
         debug: matches.get_flag("debug"),
+        debug_max_chars: matches.get_one::<usize>("debug-max-chars").copied(),
         compact: matches.get_flag("compact"),
-        json_output: matches.get_flag("json"),
+        minify: matches.get_flag("minify"),
+        trailing_semicolon: matches.get_flag("semicolon"),
+        provenance_comment: matches.get_flag("provenance"),
+        crlf: matches.get_flag("crlf"),
+        no_trailing_newline: matches.get_flag("no-trailing-newline"),
+        json_output: matches.get_flag("json")
+            || matches.get_one::<String>("format").map(String::as_str) == Some("json"),
+        input_files: matches
+            .get_many::<String>("inputs")
+            .map(|values| values.cloned().collect())
+            .unwrap_or_default(),
+        output_dir: matches.get_one::<String>("output-dir").cloned(),
+        stdin_timeout_ms: matches.get_one::<u64>("stdin-timeout").copied(),
+        diff_dialects: matches
+            .get_many::<SqlDialectType>("diff")
+            .and_then(|mut values| Some((values.next()?.clone(), values.next()?.clone()))),
+        repl: matches.get_flag("repl"),
     }
 }
 
@@ -212,7 +395,130 @@ fn create_dialect(dialect_type: &SqlDialectType) -> Box<dyn SqlDialect> {
         SqlDialectType::MySql => Box::new(MySqlDialect::new()),
         SqlDialectType::Sqlite => Box::new(SqliteDialect::new()),
         SqlDialectType::DuckDb => Box::new(DuckDbDialect::new()),
+        SqlDialectType::Oracle => Box::new(OracleDialect::new()),
+        SqlDialectType::Spark => Box::new(SparkSqlDialect::new()),
+    }
+}
+
+/// Maps a batch input file to `output_dir/<file stem>.sql`.
+fn batch_output_path(input_file: &str, output_dir: &Path) -> PathBuf {
+    let stem = Path::new(input_file)
+        .file_stem()
+        .map_or_else(|| input_file.into(), |stem| stem.to_string_lossy());
+    output_dir.join(format!("{stem}.sql"))
+}
+
+/// Returns whether `input` should be expanded as a glob. A path that exists as
+/// written, such as `reports[2024].R`, is taken literally even though it
+/// contains glob metacharacters.
+fn is_glob_pattern(input: &str) -> bool {
+    input.contains(['*', '?', '[']) && !Path::new(input).exists()
+}
+
+/// Expands glob patterns in batch inputs so patterns work even when the shell
+/// did not expand them. Plain paths, and existing files whose names contain
+/// glob metacharacters, pass through unchanged; a pattern that matches no
+/// files is an error.
+fn expand_input_patterns(inputs: &[String]) -> Result<Vec<String>, TranspileError> {
+    let mut expanded = Vec::new();
+    let mut seen = HashSet::new();
+
+    for input in inputs {
+        if !is_glob_pattern(input) {
+            if seen.insert(input.clone()) {
+                expanded.push(input.clone());
+            }
+            continue;
+        }
+
+        let paths = glob::glob(input).map_err(|e| {
+            TranspileError::ConfigurationError(format!("Invalid input pattern '{input}': {e}"))
+        })?;
+        let mut matched = 0usize;
+        for path in paths.filter_map(Result::ok).filter(|path| path.is_file()) {
+            matched += 1;
+            let path = path.to_string_lossy().into_owned();
+            if seen.insert(path.clone()) {
+                expanded.push(path);
+            }
+        }
+
+        if matched == 0 {
+            return Err(TranspileError::IoError(format!(
+                "Input pattern '{input}' did not match any files"
+            )));
+        }
+    }
+
+    Ok(expanded)
+}
+
+/// Returns a line diff of `old` and `new`: unchanged lines are prefixed with
+/// a space, removed lines with `-` and added lines with `+`.
+fn line_diff(old: &str, new: &str) -> Vec<String> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // Longest common subsequence lengths of the suffixes old[i..] / new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push(format!(" {}", old[i]));
+            i += 1;
+            j += 1;
+        } else if j == new.len() || (i < old.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            lines.push(format!("-{}", old[i]));
+            i += 1;
+        } else {
+            lines.push(format!("+{}", new[j]));
+            j += 1;
+        }
+    }
+    lines
+}
+
+/// Rejects input with control characters or runaway nesting before it reaches the lexer.
+fn screen_input(input: &str) -> Result<(), TranspileError> {
+    if let Some(position) = input
+        .chars()
+        .position(|ch| ch.is_control() && !matches!(ch, '\n' | '\r' | '\t'))
+    {
+        return Err(TranspileError::SecurityError(format!(
+            "control character at position {position}"
+        )));
+    }
+
+    let mut depth = 0usize;
+    let mut max_depth = 0usize;
+    for ch in input.chars() {
+        match ch {
+            '(' | '[' | '{' => {
+                depth += 1;
+                max_depth = max_depth.max(depth);
+            }
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
     }
+    if max_depth > MAX_INPUT_NESTING_DEPTH {
+        return Err(TranspileError::SecurityError(format!(
+            "nesting depth {max_depth} exceeds maximum {MAX_INPUT_NESTING_DEPTH}"
+        )));
+    }
+
+    Ok(())
 }
 
 /// CLI operation modes
@@ -233,6 +539,13 @@ pub enum CliMode {
         validate_only: bool,
         streaming: bool,
     },
+    /// Multi-file processing mode, one output file per input
+    BatchMode {
+        input_files: Vec<String>,
+        output_dir: Option<String>,
+    },
+    /// Interactive mode, one transpilation per input line
+    ReplMode,
 }
 
 /// CLI configuration derived from command-line arguments
@@ -242,9 +555,17 @@ pub struct CliConfig {
     pub dialect: SqlDialectType,
     pub pipe_syntax: PipeSyntax,
     pub output_format: OutputFormat,
+    pub trailing_semicolon: bool,
+    pub provenance_comment: bool,
+    pub crlf: bool,
+    pub trailing_newline: bool,
+    pub stdin_timeout: Option<Duration>,
+    pub diff_dialects: Option<(SqlDialectType, SqlDialectType)>,
     pub validation_only: bool,
+    pub check_only: bool,
     pub verbose: bool,
     pub debug: bool,
+    pub debug_max_chars: Option<usize>,
 }
 
 impl CliConfig {
@@ -258,14 +579,48 @@ impl CliConfig {
             dialect: args.dialect.clone(),
             pipe_syntax: PipeSyntax::default(),
             output_format,
+            trailing_semicolon: args.trailing_semicolon,
+            provenance_comment: args.provenance_comment,
+            crlf: args.crlf,
+            trailing_newline: !args.no_trailing_newline,
+            stdin_timeout: args.stdin_timeout_ms.map(Duration::from_millis),
+            diff_dialects: args.diff_dialects.clone(),
             validation_only: args.validate_only,
+            check_only: args.check,
             verbose: args.verbose,
             debug: args.debug,
+            debug_max_chars: args.debug_max_chars,
         }
     }
 
     /// Determine the CLI mode based on arguments
     fn determine_mode(args: &CliArgs) -> CliMode {
+        if args.repl {
+            return CliMode::ReplMode;
+        }
+        let input_files = args
+            .input_file
+            .iter()
+            .chain(&args.input_files)
+            .cloned()
+            .collect::<Vec<_>>();
+        if args.input_text.is_none()
+            && (input_files.len() > 1
+                || input_files.iter().any(|input| is_glob_pattern(input))
+                || (args.output_dir.is_some() && !input_files.is_empty()))
+        {
+            return CliMode::BatchMode {
+                input_files,
+                output_dir: args.output_dir.clone(),
+            };
+        }
+        if args.input_file.is_none() && input_files.len() == 1 {
+            return CliMode::FileMode {
+                input_file: input_files[0].clone(),
+                output_file: args.output_file.clone(),
+            };
+        }
+
         args.input_text.as_ref().map_or_else(
             || {
                 args.input_file.as_ref().map_or(
@@ -290,6 +645,8 @@ impl CliConfig {
     const fn determine_output_format(args: &CliArgs) -> OutputFormat {
         if args.json_output {
             OutputFormat::Json
+        } else if args.minify {
+            OutputFormat::Minified
         } else if args.compact {
             OutputFormat::Compact
         } else if args.pretty_print {
@@ -311,6 +668,7 @@ pub struct ProcessingPipeline {
     debug_logger: DebugLogger,
     signal_handler: Option<SignalHandler>,
     signal_processor: Option<SignalAwareProcessor>,
+    url_fetcher: UrlFetcher,
 }
 
 impl ProcessingPipeline {
@@ -321,7 +679,7 @@ impl ProcessingPipeline {
         let dialect = create_dialect(&config.dialect);
         let transpiler = Transpiler::with_pipe_syntax(dialect, config.pipe_syntax);
 
-        let validator = if config.validation_only {
+        let validator = if config.validation_only || config.check_only {
             let validation_config = ValidationConfig {
                 pipe_syntax: config.pipe_syntax,
                 ..Default::default()
@@ -331,10 +689,20 @@ impl ProcessingPipeline {
             None
         };
 
-        let output_formatter = OutputFormatter::with_format(config.output_format.clone());
+        let output_formatter = OutputFormatter::with_config(FormatConfig {
+            format: config.output_format.clone(),
+            trailing_semicolon: config.trailing_semicolon,
+            add_newline: config.trailing_newline,
+            crlf: config.crlf,
+            header_comment: config
+                .provenance_comment
+                .then(|| transpiler.provenance_comment()),
+            ..Default::default()
+        });
         let json_formatter = JsonOutputFormatter::new();
         let error_handler = ErrorHandler::with_settings(false, config.verbose, false);
-        let debug_logger = DebugLogger::with_settings(config.verbose, config.debug);
+        let debug_logger = DebugLogger::with_settings(config.verbose, config.debug)
+            .with_max_logged_chars(config.debug_max_chars);
 
         // Initialize signal handling for Unix pipeline integration
         let (signal_handler, signal_processor) = if utils::is_unix_like()
@@ -368,20 +736,50 @@ impl ProcessingPipeline {
             debug_logger,
             signal_handler,
             signal_processor,
+            url_fetcher: fetch_url,
         })
     }
 
+    /// Replaces how `-i` URLs are fetched
+    pub fn with_url_fetcher(mut self, fetcher: UrlFetcher) -> Self {
+        self.url_fetcher = fetcher;
+        self
+    }
+
     /// Process input according to the configured mode
     pub fn process(&mut self) -> Result<String, TranspileError> {
         self.debug_logger.verbose("Starting processing pipeline");
         self.debug_logger.reset_step_timer();
 
+        if let CliMode::BatchMode {
+            input_files,
+            output_dir,
+        } = &self.config.mode
+        {
+            let input_files = expand_input_patterns(input_files)?;
+            let output_dir = output_dir.clone().ok_or_else(|| {
+                TranspileError::ConfigurationError(
+                    "Multiple input files require --output-dir".to_string(),
+                )
+            })?;
+            let result = self.process_batch(&input_files, Path::new(&output_dir));
+            self.debug_logger.total_time();
+            return result;
+        }
+
         let input = self.read_input()?;
         self.debug_logger.timing("Input reading");
+        screen_input(&input)?;
 
-        let result = if self.config.validation_only {
+        let result = if let Some((left, right)) = self.config.diff_dialects.clone() {
+            self.debug_logger.verbose("Dialect diff mode enabled");
+            self.diff_input(&input, &left, &right)
+        } else if self.config.validation_only {
             self.debug_logger.verbose("Validation mode enabled");
             self.validate_input(&input)
+        } else if self.config.check_only {
+            self.debug_logger.verbose("Check mode enabled");
+            self.check_input(&input)
         } else {
             self.debug_logger.verbose("Transpilation mode enabled");
             self.transpile_input(&input)
@@ -391,6 +789,93 @@ impl ProcessingPipeline {
         result
     }
 
+    /// Transpile (or validate) each input file into `output_dir`, continuing past failures.
+    ///
+    /// Returns an empty output on success so nothing is written to stdout; the
+    /// per-file results and the final summary go to stderr.
+    fn process_batch(
+        &mut self,
+        input_files: &[String],
+        output_dir: &Path,
+    ) -> Result<String, TranspileError> {
+        if !self.config.validation_only && !self.config.check_only {
+            std::fs::create_dir_all(output_dir).map_err(|e| {
+                TranspileError::IoError(format!(
+                    "Failed to create output directory '{}': {e}",
+                    output_dir.display()
+                ))
+            })?;
+        }
+
+        let mut written = HashSet::new();
+        let mut failures = Vec::new();
+
+        for input_file in input_files {
+            self.debug_logger
+                .verbose(&format!("Processing batch input: {input_file}"));
+            let output_path = batch_output_path(input_file, output_dir);
+
+            let result = if !written.insert(output_path.clone()) {
+                Err(TranspileError::ConfigurationError(format!(
+                    "Output path '{}' is already used by another input file",
+                    output_path.display()
+                )))
+            } else {
+                self.process_batch_file(input_file, &output_path)
+            };
+
+            if let Err(error) = result {
+                eprintln!("{input_file}: {error}");
+                failures.push(input_file.as_str());
+            }
+        }
+
+        let succeeded = input_files.len() - failures.len();
+        eprintln!(
+            "Batch summary: {succeeded} succeeded, {} failed",
+            failures.len()
+        );
+
+        if failures.is_empty() {
+            Ok(String::new())
+        } else {
+            Err(TranspileError::ValidationError(format!(
+                "{} of {} input files failed: {}",
+                failures.len(),
+                input_files.len(),
+                failures.join(", ")
+            )))
+        }
+    }
+
+    fn process_batch_file(
+        &mut self,
+        input_file: &str,
+        output_path: &Path,
+    ) -> Result<(), TranspileError> {
+        let input = std::fs::read_to_string(input_file).map_err(|e| {
+            TranspileError::IoError(format!("Failed to read file '{input_file}': {e}"))
+        })?;
+        screen_input(&input)?;
+
+        if self.config.validation_only {
+            self.validate_input(&input)?;
+            return Ok(());
+        }
+        if self.config.check_only {
+            self.check_input(&input)?;
+            return Ok(());
+        }
+
+        let output = self.transpile_input(&input)?;
+        std::fs::write(output_path, output).map_err(|e| {
+            TranspileError::IoError(format!(
+                "Failed to write to file '{}': {e}",
+                output_path.display()
+            ))
+        })
+    }
+
     /// Read input based on the configured mode
     fn read_input(&self) -> Result<String, TranspileError> {
         match &self.config.mode {
@@ -403,25 +888,28 @@ impl ProcessingPipeline {
                     self.debug_logger.debug("Pipeline environment detected");
                 }
 
+                let stdin_config = StdinConfig {
+                    read_timeout: self.config.stdin_timeout,
+                    ..Default::default()
+                };
+
                 // Use signal-aware stdin reader for Unix-like systems
                 let reader = if utils::is_unix_like() {
                     self.debug_logger.debug("Using signal-aware stdin reader");
-                    StdinReader::with_signal_handling().map_err(|e| {
+                    StdinReader::with_config_and_signals(stdin_config).map_err(|e| {
                         TranspileError::SystemError(format!(
                             "Failed to create signal-aware stdin reader: {e}"
                         ))
                     })?
                 } else {
-                    StdinReader::new()
+                    StdinReader::with_config(stdin_config)
                 };
 
                 // Read input with signal handling
                 let result = if let Some(ref signal_processor) = self.signal_processor {
                     self.read_stdin_with_signals(&reader, signal_processor)?
                 } else {
-                    reader.read_all().map_err(|e| {
-                        TranspileError::IoError(format!("Failed to read from stdin: {e}"))
-                    })?
+                    reader.read_all().map_err(|e| self.stdin_error(&e))?
                 };
 
                 self.debug_logger
@@ -436,6 +924,16 @@ impl ProcessingPipeline {
                 ));
                 Ok(input_text.clone())
             }
+            CliMode::FileMode { input_file, .. } if is_url(input_file) => {
+                self.debug_logger
+                    .verbose(&format!("Fetching from URL: {input_file}"));
+
+                let result = (self.url_fetcher)(input_file)?;
+
+                self.debug_logger
+                    .debug(&format!("Read {} bytes from URL", result.len()));
+                Ok(result)
+            }
             CliMode::FileMode { input_file, .. } => {
                 self.debug_logger
                     .verbose(&format!("Reading from file: {input_file}"));
@@ -449,6 +947,12 @@ impl ProcessingPipeline {
                     .debug(&format!("Read {} bytes from file", result.len()));
                 Ok(result)
             }
+            CliMode::BatchMode { .. } => Err(TranspileError::ConfigurationError(
+                "Batch mode inputs are read per file".to_string(),
+            )),
+            CliMode::ReplMode => Err(TranspileError::ConfigurationError(
+                "REPL input is read line by line".to_string(),
+            )),
         }
     }
 
@@ -507,14 +1011,29 @@ impl ProcessingPipeline {
         }
     }
 
+    /// Checks input for `--check`: validates the syntax, then generates SQL for
+    /// the target dialect and discards it so dialect-specific errors surface too.
+    fn check_input(&self, input: &str) -> Result<String, TranspileError> {
+        self.validate_input(input)?;
+
+        self.debug_logger.verbose(&format!(
+            "Checking dialect support (dialect: {})...",
+            self.config.dialect
+        ));
+        self.transpiler.can_transpile(input)?;
+        Ok("OK".to_string())
+    }
+
     /// Transpile input to SQL
     fn transpile_input(&mut self, input: &str) -> Result<String, TranspileError> {
         self.debug_logger.verbose(&format!(
             "Transpiling dplyr to SQL (dialect: {})...",
             self.config.dialect
         ));
-        self.debug_logger
-            .debug(&format!("Input to transpile: {}", input.trim()));
+        self.debug_logger.debug(&format!(
+            "Input to transpile: {}",
+            self.debug_logger.truncated(input.trim())
+        ));
 
         // Parse dplyr code to AST
         self.debug_logger.debug("Starting lexical analysis...");
@@ -526,9 +1045,14 @@ impl ProcessingPipeline {
 
         // Generate SQL from AST
         self.debug_logger.debug("Starting SQL generation...");
-        let sql = self.transpiler.generate_sql(&ast)?;
+        let (sql, warnings) = self.transpiler.generate_sql_with_warnings(&ast)?;
         self.debug_logger.timing("SQL generation");
 
+        for warning in &warnings {
+            self.error_handler
+                .print_warning(&format!("[{}] {warning}", warning.code()));
+        }
+
         self.debug_logger
             .log_sql_generation(&sql, &self.config.dialect.to_string());
         self.debug_logger
@@ -548,6 +1072,103 @@ impl ProcessingPipeline {
         }
     }
 
+    /// Transpiles the input for two dialects and returns a unified line diff of the SQL.
+    fn diff_input(
+        &mut self,
+        input: &str,
+        left: &SqlDialectType,
+        right: &SqlDialectType,
+    ) -> Result<String, TranspileError> {
+        let transpile_for = |dialect_type: &SqlDialectType| {
+            Transpiler::with_pipe_syntax(create_dialect(dialect_type), self.config.pipe_syntax)
+                .transpile(input)
+        };
+        let left_sql = transpile_for(left)?;
+        let right_sql = transpile_for(right)?;
+        self.debug_logger.timing("SQL generation");
+
+        let mut output = format!("--- {left}\n+++ {right}\n");
+        for line in line_diff(&left_sql, &right_sql) {
+            output.push_str(&line);
+            output.push('\n');
+        }
+
+        let left_quote = create_dialect(left).quote_identifier("name");
+        let right_quote = create_dialect(right).quote_identifier("name");
+        if left_quote != right_quote {
+            output.push_str(&format!(
+                "# identifier quoting: {left_quote} ({left}) vs {right_quote} ({right})\n"
+            ));
+        }
+        Ok(output)
+    }
+
+    /// Runs an interactive session: each line of `input` is transpiled with the
+    /// current dialect and the SQL written to `output`.
+    ///
+    /// Errors go to `errors` and do not end the session. `.dialect NAME`
+    /// switches the dialect, and `.quit`, `.exit` or end of input stops the loop.
+    pub fn run_repl(
+        &mut self,
+        input: impl BufRead,
+        output: &mut impl Write,
+        errors: &mut impl Write,
+        show_prompt: bool,
+    ) -> Result<(), TranspileError> {
+        let io_error = |e: io::Error| TranspileError::IoError(format!("REPL I/O failed: {e}"));
+        let mut lines = input.lines();
+
+        loop {
+            if show_prompt {
+                write!(output, "{REPL_PROMPT}").map_err(io_error)?;
+                output.flush().map_err(io_error)?;
+            }
+            let Some(line) = lines.next() else {
+                if show_prompt {
+                    writeln!(output).map_err(io_error)?;
+                }
+                return Ok(());
+            };
+            let line = line.map_err(io_error)?;
+            let line = line.trim();
+
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(command) = line.strip_prefix('.') {
+                let mut words = command.split_whitespace();
+                match (words.next(), words.next()) {
+                    (Some("quit" | "exit"), None) => return Ok(()),
+                    (Some("dialect"), None) => {
+                        writeln!(output, "{}", self.config.dialect).map_err(io_error)?;
+                    }
+                    (Some("dialect"), Some(name)) => match name.parse::<SqlDialectType>() {
+                        Ok(dialect) => {
+                            self.transpiler = Transpiler::with_pipe_syntax(
+                                create_dialect(&dialect),
+                                self.config.pipe_syntax,
+                            );
+                            writeln!(output, "Dialect: {dialect}").map_err(io_error)?;
+                            self.config.dialect = dialect;
+                        }
+                        Err(message) => writeln!(errors, "Error: {message}").map_err(io_error)?,
+                    },
+                    _ => writeln!(
+                        errors,
+                        "Error: unknown command '.{command}' (use .dialect NAME or .quit)"
+                    )
+                    .map_err(io_error)?,
+                }
+                continue;
+            }
+
+            match screen_input(line).and_then(|()| self.transpile_input(line)) {
+                Ok(sql) => writeln!(output, "{}", sql.trim_end()).map_err(io_error)?,
+                Err(error) => writeln!(errors, "Error: {error}").map_err(io_error)?,
+            }
+        }
+    }
+
     /// Write output to the appropriate destination
     pub fn write_output(&self, output: &str) -> Result<(), TranspileError> {
         match &self.config.mode {
@@ -609,12 +1230,26 @@ impl ProcessingPipeline {
                 TranspileError::ValidationError(_) => ExitCode::VALIDATION_ERROR,
                 TranspileError::ConfigurationError(_) => ExitCode::CONFIG_ERROR,
                 TranspileError::SystemError(_) => ExitCode::SYSTEM_ERROR,
+                TranspileError::SecurityError(_) => ExitCode::SECURITY_ERROR,
+                TranspileError::TimeoutError(_) => ExitCode::TIMEOUT_ERROR,
+                TranspileError::NetworkError(_) => ExitCode::NETWORK_ERROR,
             }
         } else {
             self.error_handler.handle_error(error)
         }
     }
 
+    /// Converts a stdin failure, reporting timed-out reads as timeouts
+    fn stdin_error(&self, error: &StdinError) -> TranspileError {
+        match (error, self.config.stdin_timeout) {
+            (StdinError::Timeout, Some(timeout)) => TranspileError::TimeoutError(format!(
+                "no input received on stdin within {} ms",
+                timeout.as_millis()
+            )),
+            _ => TranspileError::IoError(format!("Failed to read from stdin: {error}")),
+        }
+    }
+
     /// Read stdin with signal handling support
     fn read_stdin_with_signals(
         &self,
@@ -636,9 +1271,7 @@ impl ProcessingPipeline {
                     }
                 }
 
-                reader.read_all().map_err(|e| {
-                    ProcessingError::ProcessingError(format!("Failed to read from stdin: {e}"))
-                })
+                Ok(reader.read_all())
             })
             .map_err(|e| match e {
                 ProcessingError::Interrupted => {
@@ -651,7 +1284,8 @@ impl ProcessingPipeline {
                 ProcessingError::SignalError(sig_err) => {
                     TranspileError::SystemError(format!("Signal error: {sig_err}"))
                 }
-            })
+            })?
+            .map_err(|e| self.stdin_error(&e))
     }
 
     /// Check if processing should continue (signal handling)
@@ -674,6 +1308,26 @@ impl ProcessingPipeline {
     }
 }
 
+/// Whether an `-i` input names an HTTP(S) URL rather than a local file
+fn is_url(input: &str) -> bool {
+    input.starts_with("http://") || input.starts_with("https://")
+}
+
+#[cfg(feature = "url-input")]
+fn fetch_url(url: &str) -> Result<String, TranspileError> {
+    ureq::get(url)
+        .call()
+        .and_then(|mut response| response.body_mut().read_to_string())
+        .map_err(|e| TranspileError::NetworkError(format!("Failed to fetch '{url}': {e}")))
+}
+
+#[cfg(not(feature = "url-input"))]
+fn fetch_url(url: &str) -> Result<String, TranspileError> {
+    Err(TranspileError::ConfigurationError(format!(
+        "Cannot read '{url}': reading from URLs requires the url-input feature"
+    )))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -687,10 +1341,22 @@ mod tests {
             pretty_print: false,
             input_text: None,
             validate_only: false,
+            check: false,
             verbose: false,
             debug: false,
+            debug_max_chars: None,
             compact: false,
+            minify: false,
+            trailing_semicolon: false,
+            provenance_comment: false,
+            crlf: false,
+            no_trailing_newline: false,
             json_output: false,
+            input_files: Vec::new(),
+            output_dir: None,
+            stdin_timeout_ms: None,
+            diff_dialects: None,
+            repl: false,
         }
     }
 
@@ -750,6 +1416,309 @@ mod tests {
         assert!(matches!(config.output_format, OutputFormat::Pretty));
     }
 
+    #[test]
+    fn test_cli_config_minify_selects_minified_format() {
+        let mut args = create_test_args();
+        args.minify = true;
+
+        let config = CliConfig::from_args(&args);
+        assert_eq!(config.output_format, OutputFormat::Minified);
+    }
+
+    #[test]
+    fn test_cli_config_debug_max_chars() {
+        let matches = build_command().get_matches_from(["libdplyr", "--debug-max-chars", "80"]);
+        let args = parse_matches(&matches);
+        assert_eq!(args.debug_max_chars, Some(80));
+        assert_eq!(CliConfig::from_args(&args).debug_max_chars, Some(80));
+        assert_eq!(
+            CliConfig::from_args(&create_test_args()).debug_max_chars,
+            None
+        );
+    }
+
+    #[test]
+    fn test_cli_config_stdin_timeout() {
+        let mut args = create_test_args();
+        assert_eq!(CliConfig::from_args(&args).stdin_timeout, None);
+
+        args.stdin_timeout_ms = Some(250);
+        let config = CliConfig::from_args(&args);
+        assert_eq!(config.stdin_timeout, Some(Duration::from_millis(250)));
+
+        let matches = build_command().get_matches_from(["libdplyr", "--stdin-timeout", "250"]);
+        assert_eq!(parse_matches(&matches).stdin_timeout_ms, Some(250));
+        assert!(build_command()
+            .try_get_matches_from(["libdplyr", "--stdin-timeout", "0"])
+            .is_err());
+    }
+
+    #[test]
+    fn test_screen_input_rejects_suspicious_input() {
+        assert!(screen_input("data %>%\r\n\tselect(name)").is_ok());
+
+        let nested = format!("select({}x{})", "(".repeat(60), ")".repeat(60));
+        for input in ["select(na\u{0}me)", "filter(x > 1\u{7})", nested.as_str()] {
+            assert!(
+                matches!(screen_input(input), Err(TranspileError::SecurityError(_))),
+                "{input:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_usage_text_mentions_input_options() {
+        let usage = usage_text();
+        assert!(usage.contains("Usage:"), "{usage}");
+        assert!(usage.contains("--text"), "{usage}");
+    }
+
+    #[test]
+    fn test_cli_config_from_args_batch_mode() {
+        let mut args = create_test_args();
+        args.input_files = vec!["a.R".to_string(), "b.R".to_string()];
+        args.output_dir = Some("out".to_string());
+
+        let config = CliConfig::from_args(&args);
+
+        assert_eq!(
+            config.mode,
+            CliMode::BatchMode {
+                input_files: vec!["a.R".to_string(), "b.R".to_string()],
+                output_dir: Some("out".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_diff_args_parse_two_dialects() {
+        let matches = build_command().get_matches_from(["libdplyr", "--diff", "postgres", "mysql"]);
+        assert_eq!(
+            parse_matches(&matches).diff_dialects,
+            Some((SqlDialectType::PostgreSql, SqlDialectType::MySql))
+        );
+        assert!(build_command()
+            .try_get_matches_from(["libdplyr", "--diff", "postgres"])
+            .is_err());
+    }
+
+    #[test]
+    fn test_diff_mode_shows_identifier_quoting_change() {
+        let mut args = create_test_args();
+        args.input_text = Some("data %>% select(name)".to_string());
+        args.diff_dialects = Some((SqlDialectType::PostgreSql, SqlDialectType::MySql));
+        let mut pipeline = ProcessingPipeline::new(CliConfig::from_args(&args)).unwrap();
+
+        let output = pipeline.process().unwrap();
+
+        assert!(
+            output.starts_with("--- postgresql\n+++ mysql\n"),
+            "{output}"
+        );
+        assert!(output.contains("\n-SELECT \"name\"\n"), "{output}");
+        assert!(output.contains("\n+SELECT `name`\n"), "{output}");
+        assert!(
+            output.contains("# identifier quoting: \"name\" (postgresql) vs `name` (mysql)"),
+            "{output}"
+        );
+    }
+
+    #[test]
+    fn test_supported_dialect_names_parse_as_cli_dialects() {
+        for name in supported_dialect_names() {
+            let dialect = name.parse::<SqlDialectType>().unwrap();
+            assert_eq!(dialect.to_string(), *name);
+        }
+        let help = build_command().render_help().to_string();
+        assert!(
+            help.contains(&supported_dialect_names().join(", ")),
+            "{help}"
+        );
+    }
+
+    #[test]
+    fn test_oracle_and_spark_dialects_from_cli() {
+        assert_eq!(
+            "oracle".parse::<SqlDialectType>(),
+            Ok(SqlDialectType::Oracle)
+        );
+        assert_eq!(SqlDialectType::Oracle.to_string(), "oracle");
+        assert_eq!("spark".parse::<SqlDialectType>(), Ok(SqlDialectType::Spark));
+        assert_eq!(SqlDialectType::Spark.to_string(), "spark");
+
+        let mut args = create_test_args();
+        args.input_text = Some("select(name) %>% head(2)".to_string());
+        args.dialect = SqlDialectType::Oracle;
+        let mut pipeline = ProcessingPipeline::new(CliConfig::from_args(&args)).unwrap();
+
+        let output = pipeline.process().unwrap();
+        assert!(output.contains("FETCH FIRST 2 ROWS ONLY"), "{output}");
+    }
+
+    #[test]
+    fn test_repl_transpiles_lines_and_switches_dialect() {
+        let mut args = create_test_args();
+        args.repl = true;
+        let config = CliConfig::from_args(&args);
+        assert_eq!(config.mode, CliMode::ReplMode);
+        let mut pipeline = ProcessingPipeline::new(config).unwrap();
+
+        let script = "select(name)\n\nfilter(\n.dialect mysql\nselect(name)\n.dialect nope\n.quit\nselect(ignored)\n";
+        let mut output = Vec::new();
+        let mut errors = Vec::new();
+        pipeline
+            .run_repl(script.as_bytes(), &mut output, &mut errors, false)
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "SELECT \"name\" FROM \"data\"\nDialect: mysql\nSELECT `name` FROM `data`\n"
+        );
+        let errors = String::from_utf8(errors).unwrap();
+        assert_eq!(errors.lines().count(), 2, "{errors}");
+        assert!(errors.contains("Unexpected token"), "{errors}");
+        assert!(errors.contains("nope"), "{errors}");
+        assert_eq!(pipeline.config().dialect, SqlDialectType::MySql);
+    }
+
+    #[test]
+    fn test_url_input_is_fetched_and_transpiled() {
+        fn fetch(url: &str) -> Result<String, TranspileError> {
+            assert_eq!(url, "https://example.com/query.R");
+            Ok("data %>% select(name)".to_string())
+        }
+
+        let mut args = create_test_args();
+        args.input_file = Some("https://example.com/query.R".to_string());
+        let mut pipeline = ProcessingPipeline::new(CliConfig::from_args(&args))
+            .unwrap()
+            .with_url_fetcher(fetch);
+
+        let output = pipeline.process().unwrap();
+        assert!(output.contains("SELECT \"name\""), "{output}");
+    }
+
+    #[test]
+    fn test_url_fetch_failure_exits_with_network_error() {
+        fn fetch(url: &str) -> Result<String, TranspileError> {
+            Err(TranspileError::NetworkError(format!(
+                "Failed to fetch '{url}': connection refused"
+            )))
+        }
+
+        let mut args = create_test_args();
+        args.input_file = Some("http://example.com/query.R".to_string());
+        let mut pipeline = ProcessingPipeline::new(CliConfig::from_args(&args))
+            .unwrap()
+            .with_url_fetcher(fetch);
+
+        let error = pipeline.process().unwrap_err();
+        assert!(
+            matches!(error, TranspileError::NetworkError(_)),
+            "{error:?}"
+        );
+        assert_eq!(pipeline.handle_error(&error), ExitCode::NETWORK_ERROR);
+        assert!(!is_url("query.R"));
+    }
+
+    #[test]
+    fn test_repl_prompts_and_stops_at_eof() {
+        let mut args = create_test_args();
+        args.repl = true;
+        let mut pipeline = ProcessingPipeline::new(CliConfig::from_args(&args)).unwrap();
+
+        let mut output = Vec::new();
+        pipeline
+            .run_repl("select(a)".as_bytes(), &mut output, &mut Vec::new(), true)
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "dplyr> SELECT \"a\" FROM \"data\"\ndplyr> \n"
+        );
+        assert!(build_command()
+            .try_get_matches_from(["libdplyr", "--repl", "-t", "select(a)"])
+            .is_err());
+    }
+
+    #[test]
+    fn test_line_diff_keeps_common_lines() {
+        assert_eq!(
+            line_diff("SELECT *\nFROM a\nLIMIT 1", "SELECT *\nFROM b\nLIMIT 1"),
+            vec![" SELECT *", "-FROM a", "+FROM b", " LIMIT 1"]
+        );
+    }
+
+    #[test]
+    fn test_cli_config_single_positional_input_uses_file_mode() {
+        let mut args = create_test_args();
+        args.input_files = vec!["a.R".to_string()];
+
+        let config = CliConfig::from_args(&args);
+
+        assert!(
+            matches!(config.mode, CliMode::FileMode { ref input_file, .. } if input_file == "a.R")
+        );
+    }
+
+    #[test]
+    fn test_batch_output_path_replaces_only_final_extension() {
+        let out = Path::new("out");
+        assert_eq!(batch_output_path("queries/top.R", out), out.join("top.sql"));
+        assert_eq!(
+            batch_output_path("queries/report.v2.R", out),
+            out.join("report.v2.sql")
+        );
+    }
+
+    #[test]
+    fn test_cli_config_glob_input_uses_batch_mode() {
+        let mut args = create_test_args();
+        args.input_files = vec!["queries/*.R".to_string()];
+        args.output_dir = None;
+
+        let config = CliConfig::from_args(&args);
+
+        assert!(matches!(config.mode, CliMode::BatchMode { .. }));
+    }
+
+    #[test]
+    fn test_expand_input_patterns_matches_files_and_rejects_empty_patterns() {
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        for name in ["b.R", "a.R", "notes.txt"] {
+            std::fs::write(temp_dir.path().join(name), "data %>% select(x)").expect("write");
+        }
+        let dir = temp_dir.path().to_string_lossy().into_owned();
+
+        let expanded = expand_input_patterns(&[format!("{dir}/*.R")]).expect("pattern matches");
+        let names = expanded
+            .iter()
+            .map(|path| {
+                Path::new(path)
+                    .file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["a.R", "b.R"]);
+
+        let error = expand_input_patterns(&[format!("{dir}/*.sql")]).unwrap_err();
+        assert!(error.to_string().contains("did not match any files"));
+    }
+
+    #[test]
+    fn test_expand_input_patterns_keeps_existing_paths_with_brackets() {
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        let path = temp_dir.path().join("reports[2024].R");
+        std::fs::write(&path, "data %>% select(x)").expect("write");
+        let path = path.to_string_lossy().into_owned();
+
+        assert!(!is_glob_pattern(&path));
+        let expanded = expand_input_patterns(std::slice::from_ref(&path)).expect("literal path");
+        assert_eq!(expanded, vec![path]);
+    }
+
     #[test]
     fn test_cli_config_validation_mode() {
         let mut args = create_test_args();
@@ -773,6 +1742,32 @@ mod tests {
         assert!(pipeline.is_ok());
     }
 
+    #[test]
+    fn test_check_mode_transpiles_without_printing_sql() {
+        let mut args = create_test_args();
+        args.check = true;
+        args.dialect = SqlDialectType::MySql;
+        let config = CliConfig::from_args(&args);
+        assert!(config.check_only);
+
+        let pipeline = ProcessingPipeline::new(config).unwrap();
+        assert!(pipeline.validator.is_some());
+        assert_eq!(
+            pipeline
+                .check_input("data %>% summarise(avg = mean(x))")
+                .unwrap(),
+            "OK"
+        );
+        assert!(matches!(
+            pipeline.check_input("data %>% summarise(mid = median(x))"),
+            Err(TranspileError::GenerationError(_))
+        ));
+        assert!(matches!(
+            pipeline.check_input("data %>% select(x"),
+            Err(TranspileError::ValidationError(_))
+        ));
+    }
+
     #[test]
     fn test_processing_pipeline_validation_mode() {
         let mut args = create_test_args();
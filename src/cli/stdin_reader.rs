@@ -5,8 +5,13 @@
 
 use crate::cli::signal_handler::{ProcessingError, SignalError, SignalHandler};
 use std::io::{self, BufRead, BufReader, IsTerminal, Read};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
 use std::time::Duration;
 
+/// Chunk size used when reading stdin incrementally
+const READ_CHUNK_SIZE: usize = 8192;
+
 /// Result type for stdin reading operations
 pub type StdinResult<T> = Result<T, StdinError>;
 
@@ -41,7 +46,8 @@ pub enum StdinError {
 /// Configuration for stdin reading behavior
 #[derive(Debug, Clone)]
 pub struct StdinConfig {
-    /// Maximum time to wait for input when stdin is not piped
+    /// Maximum time to wait for the next chunk of piped input before failing
+    /// with [`StdinError::Timeout`] (`None` waits indefinitely)
     pub read_timeout: Option<Duration>,
     /// Whether to trim whitespace from input
     pub trim_input: bool,
@@ -123,6 +129,11 @@ impl StdinReader {
 
     /// Reads input from a pipe with signal handling and memory efficiency
     fn read_from_pipe(&self, buffer: &mut String) -> StdinResult<()> {
+        if let Some(timeout) = self.config.read_timeout {
+            *buffer = self.read_source_with_timeout(io::stdin(), timeout)?;
+            return Ok(());
+        }
+
         if let Some(ref signal_handler) = self.signal_handler {
             self.read_from_pipe_with_signals(buffer, signal_handler)
         } else {
@@ -210,6 +221,77 @@ impl StdinReader {
         Ok(())
     }
 
+    /// Reads `source` to EOF, failing with [`StdinError::Timeout`] if no data
+    /// arrives for `timeout`.
+    ///
+    /// The blocking reads run on a helper thread so a writer that never sends
+    /// data or closes the pipe cannot hang the caller.
+    pub fn read_source_with_timeout<R>(&self, source: R, timeout: Duration) -> StdinResult<String>
+    where
+        R: Read + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel::<io::Result<Vec<u8>>>();
+        thread::spawn(move || {
+            let mut source = source;
+            let mut chunk = vec![0u8; READ_CHUNK_SIZE];
+            loop {
+                match source.read(&mut chunk) {
+                    Ok(0) => {
+                        let _ = sender.send(Ok(Vec::new()));
+                        break;
+                    }
+                    Ok(n) => {
+                        if sender.send(Ok(chunk[..n].to_vec())).is_err() {
+                            break;
+                        }
+                    }
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                    Err(e) => {
+                        let _ = sender.send(Err(e));
+                        break;
+                    }
+                }
+            }
+        });
+
+        let mut temp_buffer = Vec::new();
+        loop {
+            self.check_signals()?;
+
+            let chunk = match receiver.recv_timeout(timeout) {
+                Ok(chunk) => chunk?,
+                Err(RecvTimeoutError::Timeout) => return Err(StdinError::Timeout),
+                Err(RecvTimeoutError::Disconnected) => break,
+            };
+            if chunk.is_empty() {
+                break; // EOF reached
+            }
+
+            temp_buffer.extend_from_slice(&chunk);
+            self.check_size_limit(&temp_buffer)?;
+        }
+
+        String::from_utf8(temp_buffer).map_err(|e| {
+            StdinError::ReadError(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Invalid UTF-8 input: {e}"),
+            ))
+        })
+    }
+
+    /// Returns an error if a shutdown signal or closed pipe was observed
+    fn check_signals(&self) -> StdinResult<()> {
+        if let Some(ref signal_handler) = self.signal_handler {
+            if signal_handler.should_shutdown() {
+                if signal_handler.pipe_closed() {
+                    return Err(StdinError::PipeClosed);
+                }
+                return Err(StdinError::Interrupted);
+            }
+        }
+        Ok(())
+    }
+
     /// Check if the buffer size exceeds the configured limit
     fn check_size_limit(&self, buffer: &[u8]) -> StdinResult<()> {
         if let Some(max_size) = self.config.max_input_size {
@@ -413,6 +495,66 @@ mod tests {
         assert_eq!(error.to_string(), "Stdin read timeout");
     }
 
+    /// Reader that blocks until its paired sender is dropped, like an open pipe
+    /// whose writer never sends anything.
+    struct BlockingReader(mpsc::Receiver<()>);
+
+    impl Read for BlockingReader {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            let _ = self.0.recv();
+            Ok(0)
+        }
+    }
+
+    #[test]
+    fn test_read_source_with_timeout_fails_on_silent_source() {
+        let reader = StdinReader::new();
+        let (writer, receiver) = mpsc::channel();
+
+        let started = std::time::Instant::now();
+        let result =
+            reader.read_source_with_timeout(BlockingReader(receiver), Duration::from_millis(50));
+
+        assert!(matches!(result, Err(StdinError::Timeout)), "{result:?}");
+        assert!(started.elapsed() < Duration::from_secs(5));
+        drop(writer);
+    }
+
+    #[test]
+    fn test_read_source_with_timeout_reads_available_input() {
+        let reader = StdinReader::new();
+
+        let input = reader
+            .read_source_with_timeout(
+                io::Cursor::new(b"data %>% select(x)".to_vec()),
+                Duration::from_secs(5),
+            )
+            .unwrap();
+        assert_eq!(input, "data %>% select(x)");
+
+        let empty = reader
+            .read_source_with_timeout(io::empty(), Duration::from_secs(5))
+            .unwrap();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_read_source_with_timeout_enforces_size_limit() {
+        let reader = StdinReader::with_config(StdinConfig {
+            max_input_size: Some(4),
+            ..Default::default()
+        });
+
+        let result = reader.read_source_with_timeout(
+            io::Cursor::new(b"too long".to_vec()),
+            Duration::from_secs(5),
+        );
+        assert!(
+            matches!(result, Err(StdinError::ReadError(_))),
+            "{result:?}"
+        );
+    }
+
     // Note: Testing actual stdin reading is difficult in unit tests
     // as it requires actual pipe input. These would be better tested
     // in integration tests with actual command execution.
@@ -3,7 +3,7 @@
 //! Provides validation-only functionality for dplyr syntax without SQL generation.
 
 use crate::pipe_syntax::disabled_pipe_suggestion_for_error;
-use crate::{Lexer, Parser, PipeSyntax, TranspileError};
+use crate::{Lexer, Parser, PipeSyntax, SqlDialect, TranspileError};
 use std::collections::HashSet;
 
 /// Result type for validation operations
@@ -61,6 +61,18 @@ pub struct ValidationSummary {
     pub complexity_score: u8,
 }
 
+impl ValidationSummary {
+    /// Returns the operations in the pipeline that `dialect` cannot generate.
+    pub fn unsupported_operations(&self, dialect: &dyn SqlDialect) -> Vec<String> {
+        self.operations
+            .iter()
+            // Joins are summarised as "join"; every join type is generated for all dialects
+            .filter(|op| op.as_str() != "join" && !dialect.supports_operation(op))
+            .cloned()
+            .collect()
+    }
+}
+
 /// Detailed error information for validation failures
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ValidationErrorInfo {
@@ -316,6 +328,13 @@ impl DplyrValidator {
                 }
                 *complexity_score += 1;
             }
+            DplyrOperation::RenameWith { columns: cols, .. } => {
+                operations.push("rename_with".to_string());
+                for col in cols {
+                    columns.insert(col.clone());
+                }
+                *complexity_score += 1;
+            }
             DplyrOperation::Arrange { columns: cols, .. } => {
                 operations.push("arrange".to_string());
                 for col in cols {
@@ -344,6 +363,42 @@ impl DplyrValidator {
                 }
                 *complexity_score += 3;
             }
+            DplyrOperation::PivotWider {
+                names_from,
+                values_from,
+                names,
+                ..
+            } => {
+                operations.push("pivot_wider".to_string());
+                *has_aggregation = true;
+                columns.insert(names_from.clone());
+                columns.insert(values_from.clone());
+                for name in names {
+                    columns.insert(name.clone());
+                }
+                *complexity_score += 3;
+            }
+            DplyrOperation::Distinct { columns: cols, .. } => {
+                operations.push("distinct".to_string());
+                for col in cols {
+                    columns.insert(col.clone());
+                }
+                *complexity_score += 1;
+            }
+            DplyrOperation::Fill { columns: cols, .. } => {
+                operations.push("fill".to_string());
+                for col in cols {
+                    columns.insert(col.clone());
+                }
+                *complexity_score += 2;
+            }
+            DplyrOperation::Slice { .. } => {
+                operations.push(operation.operation_name().to_string());
+                *complexity_score += 2;
+            }
+            DplyrOperation::Unsupported { verb, .. } => {
+                operations.push(verb.clone());
+            }
             DplyrOperation::Join { .. } => {
                 operations.push("join".to_string());
                 *complexity_score += 2;
@@ -403,8 +458,9 @@ impl DplyrValidator {
             TranspileError::ParseError(e) => ValidationErrorInfo {
                 error_type: "parse".to_string(),
                 message: e.to_string(),
-                position: None, // TODO: Extract position from error if available
-                context: self.extract_error_context(code, None),
+                position: e.position(),
+                // Parser positions count tokens, not bytes of `code`
+                context: None,
             },
             TranspileError::GenerationError(e) => ValidationErrorInfo {
                 error_type: "generation".to_string(),
@@ -436,6 +492,24 @@ impl DplyrValidator {
                 position: None,
                 context: None,
             },
+            TranspileError::SecurityError(e) => ValidationErrorInfo {
+                error_type: "security".to_string(),
+                message: e.to_string(),
+                position: None,
+                context: None,
+            },
+            TranspileError::TimeoutError(e) => ValidationErrorInfo {
+                error_type: "timeout".to_string(),
+                message: e.to_string(),
+                position: None,
+                context: None,
+            },
+            TranspileError::NetworkError(e) => ValidationErrorInfo {
+                error_type: "network".to_string(),
+                message: e.to_string(),
+                position: None,
+                context: None,
+            },
         }
     }
 
@@ -492,6 +566,18 @@ impl DplyrValidator {
                 "Check system permissions and resources".to_string(),
                 "Verify signal handling or pipeline configuration".to_string(),
             ],
+            TranspileError::SecurityError(_) => vec![
+                "Remove control characters from the input".to_string(),
+                "Reduce deeply nested expressions".to_string(),
+            ],
+            TranspileError::TimeoutError(_) => vec![
+                "Check that input is being provided".to_string(),
+                "Increase the configured timeout".to_string(),
+            ],
+            TranspileError::NetworkError(_) => vec![
+                "Check that the URL is correct and reachable".to_string(),
+                "Download the file and pass its local path instead".to_string(),
+            ],
         }
     }
 
@@ -552,6 +638,26 @@ mod tests {
         assert_eq!(custom_validator.config.max_complexity, Some(5));
     }
 
+    #[test]
+    fn test_unsupported_operations_depend_on_dialect() {
+        let validator = DplyrValidator::new();
+        let summary = match validator
+            .validate("data %>% arrange(day) %>% fill(price) %>% left_join(b, by = \"id\")")
+            .unwrap()
+        {
+            ValidateResult::Valid { summary } => summary,
+            other => panic!("expected valid result, got {other:?}"),
+        };
+
+        assert!(summary
+            .unsupported_operations(&crate::DuckDbDialect::new())
+            .is_empty());
+        assert_eq!(
+            summary.unsupported_operations(&crate::SqliteDialect::new()),
+            vec!["fill".to_string()]
+        );
+    }
+
     #[test]
     fn test_valid_simple_query() {
         let validator = DplyrValidator::new();
@@ -2,8 +2,13 @@
 //!
 //! Defines all error types used in libdplyr.
 
+use core::ffi::CStr;
+
 use thiserror::Error;
 
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+
 /// Errors that occur during lexing (tokenization)
 #[derive(Debug, Error, Clone, PartialEq, Eq)]
 pub enum LexError {
@@ -16,6 +21,9 @@ pub enum LexError {
     #[error("Invalid number format: '{0}' (position: {1})")]
     InvalidNumber(String, usize),
 
+    #[error("Number '{0}' cannot be represented exactly; write it as a string to keep every digit (position: {1})")]
+    ImpreciseNumber(String, usize),
+
     #[error("Invalid identifier: '{0}' (position: {1})")]
     InvalidIdentifier(String, usize),
 
@@ -29,6 +37,50 @@ pub enum LexError {
     EmptyInput,
 }
 
+impl LexError {
+    /// Returns the character position the error points at, if it has one.
+    pub const fn position(&self) -> Option<usize> {
+        match self {
+            Self::UnexpectedCharacter(_, position)
+            | Self::UnterminatedString(position)
+            | Self::InvalidNumber(_, position)
+            | Self::ImpreciseNumber(_, position)
+            | Self::InvalidIdentifier(_, position)
+            | Self::InvalidPipeOperator(_, position)
+            | Self::InvalidEscapeSequence(_, position) => Some(*position),
+            Self::EmptyInput => None,
+        }
+    }
+
+    /// Suggested fix for the error, when there is a specific one.
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            Self::UnexpectedCharacter(..) => {
+                Some("remove the character, or put it inside a quoted string".to_string())
+            }
+            Self::UnterminatedString(_) => Some("add the closing quote to the string".to_string()),
+            Self::InvalidNumber(..) => {
+                Some("write numbers as digits with at most one decimal point".to_string())
+            }
+            Self::InvalidIdentifier(..) => {
+                Some("quote unusual column names with backticks".to_string())
+            }
+            #[cfg(feature = "std")]
+            Self::InvalidPipeOperator(message, _) => {
+                crate::pipe_syntax::disabled_pipe_suggestion_for_error(message)
+                    .or_else(|| Some("separate verbs with %>% or |>".to_string()))
+            }
+            #[cfg(not(feature = "std"))]
+            Self::InvalidPipeOperator(..) => Some("separate verbs with %>% or |>".to_string()),
+            Self::InvalidEscapeSequence(..) => {
+                Some("use one of the escapes \\n, \\t, \\r, \\\\, \\\" or \\'".to_string())
+            }
+            // The message already says what to do
+            Self::ImpreciseNumber(..) | Self::EmptyInput => None,
+        }
+    }
+}
+
 /// Errors that occur during parsing
 #[derive(Debug, Error, Clone, PartialEq, Eq)]
 pub enum ParseError {
@@ -65,6 +117,83 @@ pub enum ParseError {
 
     #[error("Unexpected end of file (position: {0})")]
     UnexpectedEof(usize),
+
+    #[error("Unclosed '(' opened at position {opened_at} (position: {position})")]
+    UnclosedParenthesis { opened_at: usize, position: usize },
+
+    #[error("Unmatched ')' with no opening '(' (position: {position})")]
+    UnmatchedClosingParenthesis { position: usize },
+
+    #[error("Expression nested deeper than {max_depth} levels (position: {position})")]
+    MaxNestingDepthExceeded { max_depth: usize, position: usize },
+}
+
+impl ParseError {
+    /// Returns the token position the error points at, if it has one.
+    pub const fn position(&self) -> Option<usize> {
+        match self {
+            Self::UnexpectedToken { position, .. }
+            | Self::InvalidOperation { position, .. }
+            | Self::MissingArgument { position, .. }
+            | Self::TooManyArguments { position, .. }
+            | Self::InvalidExpression { position, .. }
+            | Self::UnsupportedFunction { position, .. }
+            | Self::InvalidAlias { position, .. }
+            | Self::UnexpectedEof(position)
+            | Self::UnclosedParenthesis { position, .. }
+            | Self::UnmatchedClosingParenthesis { position }
+            | Self::MaxNestingDepthExceeded { position, .. } => Some(*position),
+            Self::LexError(e) => e.position(),
+            Self::EmptyPipeline => None,
+        }
+    }
+
+    /// Suggested fix for the error, when there is a specific one.
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            Self::UnexpectedToken { found, .. } => Some(format!(
+                "check for a missing comma, parenthesis or argument before '{found}'"
+            )),
+            Self::MissingArgument { function, .. } => {
+                Some(format!("pass the required arguments to {function}()"))
+            }
+            Self::TooManyArguments { function, .. } => {
+                Some(format!("remove the extra arguments of {function}()"))
+            }
+            Self::InvalidExpression { .. } => {
+                Some("check the operators and operands of the expression".to_string())
+            }
+            Self::UnsupportedFunction { .. } => Some(
+                "see the supported functions in the README, or compute the value in the database"
+                    .to_string(),
+            ),
+            Self::InvalidAlias { .. } => {
+                Some("use a plain name, or quote it with backticks".to_string())
+            }
+            Self::EmptyPipeline => Some("add a verb such as select() or filter()".to_string()),
+            Self::LexError(e) => e.hint(),
+            Self::UnexpectedEof(_) => {
+                Some("complete the last call and close every parenthesis".to_string())
+            }
+            Self::UnclosedParenthesis { opened_at, .. } => {
+                Some(format!("add ')' to close the '(' at position {opened_at}"))
+            }
+            Self::UnmatchedClosingParenthesis { .. } => {
+                Some("remove the ')' or add the '(' it closes".to_string())
+            }
+            Self::MaxNestingDepthExceeded { .. } => {
+                Some("split the expression over several mutate() steps".to_string())
+            }
+            // The message already says what is wrong
+            Self::InvalidOperation { .. } => None,
+        }
+    }
+}
+
+/// `"; hint: ..."` appended to a message, or nothing without a hint.
+fn hint_suffix(hint: Option<String>) -> String {
+    hint.map(|hint| format!("; hint: {hint}"))
+        .unwrap_or_default()
 }
 
 /// Errors that occur during SQL generation
@@ -102,8 +231,16 @@ pub enum GenerationError {
     #[error("Invalid AST structure: {reason}")]
     InvalidAst { reason: String },
 
-    #[error("Unsupported aggregate function: '{function}' (dialect: {dialect})")]
-    UnsupportedAggregateFunction { function: String, dialect: String },
+    #[error(
+        "Unsupported aggregate function: '{function}' (dialect: {dialect}){}",
+        suggestion.as_deref().map(|s| format!("; try {s} instead")).unwrap_or_default()
+    )]
+    UnsupportedAggregateFunction {
+        function: String,
+        dialect: String,
+        /// Dialect-specific SQL that computes the same value, when one exists.
+        suggestion: Option<String>,
+    },
 
     #[error("Invalid data type conversion: from '{from_type}' to '{to_type}'")]
     InvalidTypeConversion { from_type: String, to_type: String },
@@ -119,15 +256,32 @@ pub enum GenerationError {
 
     #[error("Invalid identifier: '{identifier}' - {reason}")]
     InvalidIdentifier { identifier: String, reason: String },
+
+    #[error("Strict mode rejected warning {code}: {message}")]
+    StrictModeViolation { code: String, message: String },
+
+    #[error(
+        "Aggregate function '{function}' cannot be used in filter(): SQL WHERE runs before aggregation; compute it in summarise() and filter the result afterwards (HAVING)"
+    )]
+    AggregateInFilter { function: String },
+
+    #[error("Duplicate output column '{column}' in {operation}(): each output column needs a unique name")]
+    DuplicateColumnName { column: String, operation: String },
+
+    #[error("Output too large: {length} bytes exceeds maximum {max}")]
+    OutputTooLarge { length: usize, max: usize },
+
+    #[error("Failed to write SQL: {reason}")]
+    WriteFailed { reason: String },
 }
 
 /// Unified error that can occur during the entire conversion process
 #[derive(Debug, Error)]
 pub enum TranspileError {
-    #[error("Lexing error: {0}")]
+    #[error("Lexing error: {0}{hint}", hint = hint_suffix(.0.hint()))]
     LexError(#[from] LexError),
 
-    #[error("Parsing error: {0}")]
+    #[error("Parsing error: {0}{hint}", hint = hint_suffix(.0.hint()))]
     ParseError(#[from] ParseError),
 
     #[error("SQL generation error: {0}")]
@@ -144,28 +298,418 @@ pub enum TranspileError {
 
     #[error("System error: {0}")]
     SystemError(String),
+
+    #[error("Security check failed: {0}")]
+    SecurityError(String),
+
+    #[error("Timed out: {0}")]
+    TimeoutError(String),
+
+    #[error("Network error: {0}")]
+    NetworkError(String),
+}
+
+// Result codes of the C API; the CLI reports the same codes by name.
+pub const DPLYR_SUCCESS: i32 = 0;
+pub const DPLYR_QUERY_NOT_HANDLED: i32 = 1;
+pub const DPLYR_ERROR_NULL_POINTER: i32 = -1;
+pub const DPLYR_ERROR_INVALID_UTF8: i32 = -2;
+pub const DPLYR_ERROR_INPUT_TOO_LARGE: i32 = -3;
+pub const DPLYR_ERROR_TIMEOUT: i32 = -4;
+pub const DPLYR_ERROR_SYNTAX: i32 = -5;
+pub const DPLYR_ERROR_UNSUPPORTED: i32 = -6;
+pub const DPLYR_ERROR_INTERNAL: i32 = -7;
+pub const DPLYR_ERROR_PANIC: i32 = -8;
+
+/// Returns the result code reported for `error`.
+///
+/// | Error | Code |
+/// | :--- | :--- |
+/// | `LexError`, `ParseError`, `ValidationError` | `DPLYR_ERROR_SYNTAX` (-5) |
+/// | `GenerationError::OutputTooLarge`, `GenerationError::WriteFailed` | `DPLYR_ERROR_INTERNAL` (-7) |
+/// | any other `GenerationError` | `DPLYR_ERROR_UNSUPPORTED` (-6) |
+/// | `IoError`, `ConfigurationError`, `SystemError`, `SecurityError`, `TimeoutError`, `NetworkError` | `DPLYR_ERROR_INTERNAL` (-7) |
+pub const fn error_code_for(error: &TranspileError) -> i32 {
+    match error {
+        TranspileError::LexError(_)
+        | TranspileError::ParseError(_)
+        | TranspileError::ValidationError(_) => DPLYR_ERROR_SYNTAX,
+        TranspileError::GenerationError(
+            GenerationError::OutputTooLarge { .. } | GenerationError::WriteFailed { .. },
+        ) => DPLYR_ERROR_INTERNAL,
+        TranspileError::GenerationError(_) => DPLYR_ERROR_UNSUPPORTED,
+        TranspileError::IoError(_)
+        | TranspileError::ConfigurationError(_)
+        | TranspileError::SystemError(_)
+        | TranspileError::SecurityError(_)
+        | TranspileError::TimeoutError(_)
+        | TranspileError::NetworkError(_) => DPLYR_ERROR_INTERNAL,
+    }
+}
+
+/// Returns the stable name of a result code, such as `E-SYNTAX`, or
+/// `E-UNKNOWN` for codes outside the table.
+pub const fn error_code_name(code: i32) -> &'static CStr {
+    match code {
+        DPLYR_SUCCESS => c"SUCCESS",
+        DPLYR_QUERY_NOT_HANDLED => c"QUERY-NOT-HANDLED",
+        DPLYR_ERROR_NULL_POINTER => c"E-NULL-POINTER",
+        DPLYR_ERROR_INVALID_UTF8 => c"E-INVALID-UTF8",
+        DPLYR_ERROR_INPUT_TOO_LARGE => c"E-INPUT-TOO-LARGE",
+        DPLYR_ERROR_TIMEOUT => c"E-TIMEOUT",
+        DPLYR_ERROR_SYNTAX => c"E-SYNTAX",
+        DPLYR_ERROR_UNSUPPORTED => c"E-UNSUPPORTED",
+        DPLYR_ERROR_INTERNAL => c"E-INTERNAL",
+        DPLYR_ERROR_PANIC => c"E-PANIC",
+        _ => c"E-UNKNOWN",
+    }
+}
+
+/// Source of translated error message templates, keyed by message id.
+///
+/// Templates name the arguments of the error in braces, e.g.
+/// `"'{function}' 함수는 지원되지 않습니다"`; see [`fill_message_template`].
+/// Returning `None` falls back to the built-in English message.
+pub trait MessageCatalog {
+    /// Returns the template for `message_id`, if the catalog has one.
+    fn template(&self, message_id: &str) -> Option<&str>;
+}
+
+impl MessageCatalog for crate::collections::HashMap<String, String> {
+    fn template(&self, message_id: &str) -> Option<&str> {
+        self.get(message_id).map(String::as_str)
+    }
+}
+
+/// Replaces each `{name}` in `template` with the matching argument value.
+///
+/// Placeholders without a matching argument are left as written.
+pub fn fill_message_template(template: &str, args: &[(&'static str, String)]) -> String {
+    let mut message = template.to_string();
+    for (name, value) in args {
+        message = message.replace(&format!("{{{name}}}"), value);
+    }
+    message
+}
+
+/// Errors with a stable message id and named arguments, so their text can
+/// be translated without changing the code paths that raise them.
+pub trait LocalizedError: core::fmt::Display {
+    /// Stable identifier of the message, such as `parse.missing-argument`.
+    fn message_id(&self) -> &'static str;
+
+    /// Named values the message refers to.
+    fn message_args(&self) -> Vec<(&'static str, String)>;
+
+    /// Renders the message from `catalog`, or the English message when the
+    /// catalog has no template for it.
+    fn localized_message(&self, catalog: &dyn MessageCatalog) -> String {
+        catalog.template(self.message_id()).map_or_else(
+            || self.to_string(),
+            |template| fill_message_template(template, &self.message_args()),
+        )
+    }
+}
+
+impl LocalizedError for LexError {
+    fn message_id(&self) -> &'static str {
+        match self {
+            Self::UnexpectedCharacter(..) => "lex.unexpected-character",
+            Self::UnterminatedString(_) => "lex.unterminated-string",
+            Self::InvalidNumber(..) => "lex.invalid-number",
+            Self::ImpreciseNumber(..) => "lex.imprecise-number",
+            Self::InvalidIdentifier(..) => "lex.invalid-identifier",
+            Self::InvalidPipeOperator(..) => "lex.invalid-pipe-operator",
+            Self::InvalidEscapeSequence(..) => "lex.invalid-escape-sequence",
+            Self::EmptyInput => "lex.empty-input",
+        }
+    }
+
+    fn message_args(&self) -> Vec<(&'static str, String)> {
+        let mut args = match self {
+            Self::UnexpectedCharacter(ch, _) | Self::InvalidEscapeSequence(ch, _) => {
+                vec![("character", ch.to_string())]
+            }
+            Self::InvalidNumber(text, _)
+            | Self::ImpreciseNumber(text, _)
+            | Self::InvalidIdentifier(text, _)
+            | Self::InvalidPipeOperator(text, _) => vec![("text", text.clone())],
+            Self::UnterminatedString(_) | Self::EmptyInput => Vec::new(),
+        };
+        if let Some(position) = self.position() {
+            args.push(("position", position.to_string()));
+        }
+        args
+    }
+}
+
+impl LocalizedError for ParseError {
+    fn message_id(&self) -> &'static str {
+        match self {
+            Self::UnexpectedToken { .. } => "parse.unexpected-token",
+            Self::InvalidOperation { .. } => "parse.invalid-operation",
+            Self::MissingArgument { .. } => "parse.missing-argument",
+            Self::TooManyArguments { .. } => "parse.too-many-arguments",
+            Self::InvalidExpression { .. } => "parse.invalid-expression",
+            Self::UnsupportedFunction { .. } => "parse.unsupported-function",
+            Self::InvalidAlias { .. } => "parse.invalid-alias",
+            Self::EmptyPipeline => "parse.empty-pipeline",
+            Self::LexError(e) => e.message_id(),
+            Self::UnexpectedEof(_) => "parse.unexpected-eof",
+            Self::UnclosedParenthesis { .. } => "parse.unclosed-parenthesis",
+            Self::UnmatchedClosingParenthesis { .. } => "parse.unmatched-closing-parenthesis",
+            Self::MaxNestingDepthExceeded { .. } => "parse.max-nesting-depth",
+        }
+    }
+
+    fn message_args(&self) -> Vec<(&'static str, String)> {
+        let mut args = match self {
+            Self::LexError(e) => return e.message_args(),
+            Self::UnexpectedToken {
+                expected, found, ..
+            } => vec![("expected", expected.clone()), ("found", found.clone())],
+            Self::InvalidOperation { operation, .. } => vec![("operation", operation.clone())],
+            Self::MissingArgument { function, .. }
+            | Self::TooManyArguments { function, .. }
+            | Self::UnsupportedFunction { function, .. } => vec![("function", function.clone())],
+            Self::InvalidExpression { expr, .. } => vec![("expr", expr.clone())],
+            Self::InvalidAlias { alias, .. } => vec![("alias", alias.clone())],
+            Self::UnclosedParenthesis { opened_at, .. } => {
+                vec![("opened_at", opened_at.to_string())]
+            }
+            Self::MaxNestingDepthExceeded { max_depth, .. } => {
+                vec![("max_depth", max_depth.to_string())]
+            }
+            Self::EmptyPipeline
+            | Self::UnexpectedEof(_)
+            | Self::UnmatchedClosingParenthesis { .. } => Vec::new(),
+        };
+        if let Some(position) = self.position() {
+            args.push(("position", position.to_string()));
+        }
+        args
+    }
+}
+
+impl LocalizedError for GenerationError {
+    fn message_id(&self) -> &'static str {
+        match self {
+            Self::UnsupportedOperation { .. } => "generate.unsupported-operation",
+            Self::UnsupportedFunction { .. } => "generate.unsupported-function",
+            Self::UnsupportedNamedArgument { .. } => "generate.unsupported-named-argument",
+            Self::InvalidColumnReference { .. } => "generate.invalid-column-reference",
+            Self::ComplexExpression { .. } => "generate.complex-expression",
+            Self::InvalidAst { .. } => "generate.invalid-ast",
+            Self::UnsupportedAggregateFunction { .. } => "generate.unsupported-aggregate",
+            Self::InvalidTypeConversion { .. } => "generate.invalid-type-conversion",
+            Self::CircularReference { .. } => "generate.circular-reference",
+            Self::MaxNestingDepthExceeded { .. } => "generate.max-nesting-depth",
+            Self::EmptyQuery => "generate.empty-query",
+            Self::InvalidIdentifier { .. } => "generate.invalid-identifier",
+            Self::StrictModeViolation { .. } => "generate.strict-mode-violation",
+            Self::AggregateInFilter { .. } => "generate.aggregate-in-filter",
+            Self::DuplicateColumnName { .. } => "generate.duplicate-column",
+            Self::OutputTooLarge { .. } => "generate.output-too-large",
+            Self::WriteFailed { .. } => "generate.write-failed",
+        }
+    }
+
+    fn message_args(&self) -> Vec<(&'static str, String)> {
+        match self {
+            Self::UnsupportedOperation { operation, dialect } => vec![
+                ("operation", operation.clone()),
+                ("dialect", dialect.clone()),
+            ],
+            Self::UnsupportedFunction { function, dialect } => {
+                vec![("function", function.clone()), ("dialect", dialect.clone())]
+            }
+            Self::UnsupportedNamedArgument {
+                function,
+                argument,
+                dialect,
+            } => vec![
+                ("function", function.clone()),
+                ("argument", argument.clone()),
+                ("dialect", dialect.clone()),
+            ],
+            Self::InvalidColumnReference { column, table } => vec![
+                ("column", column.clone()),
+                ("table", table.clone().unwrap_or_default()),
+            ],
+            Self::ComplexExpression { expr, expr_type } => {
+                vec![("expr", expr.clone()), ("expr_type", expr_type.clone())]
+            }
+            Self::InvalidAst { reason } | Self::WriteFailed { reason } => {
+                vec![("reason", reason.clone())]
+            }
+            Self::UnsupportedAggregateFunction {
+                function,
+                dialect,
+                suggestion,
+            } => vec![
+                ("function", function.clone()),
+                ("dialect", dialect.clone()),
+                ("suggestion", suggestion.clone().unwrap_or_default()),
+            ],
+            Self::InvalidTypeConversion { from_type, to_type } => vec![
+                ("from_type", from_type.clone()),
+                ("to_type", to_type.clone()),
+            ],
+            Self::CircularReference { reference } => vec![("reference", reference.clone())],
+            Self::MaxNestingDepthExceeded { depth, max_depth } => vec![
+                ("depth", depth.to_string()),
+                ("max_depth", max_depth.to_string()),
+            ],
+            Self::EmptyQuery => Vec::new(),
+            Self::InvalidIdentifier { identifier, reason } => vec![
+                ("identifier", identifier.clone()),
+                ("reason", reason.clone()),
+            ],
+            Self::StrictModeViolation { code, message } => {
+                vec![("code", code.clone()), ("message", message.clone())]
+            }
+            Self::AggregateInFilter { function } => vec![("function", function.clone())],
+            Self::DuplicateColumnName { column, operation } => {
+                vec![("column", column.clone()), ("operation", operation.clone())]
+            }
+            Self::OutputTooLarge { length, max } => {
+                vec![("length", length.to_string()), ("max", max.to_string())]
+            }
+        }
+    }
+}
+
+/// Lex, parse and generation errors keep the id of the wrapped error; the
+/// other variants carry their text as the `detail` argument.
+impl LocalizedError for TranspileError {
+    fn message_id(&self) -> &'static str {
+        match self {
+            Self::LexError(e) => e.message_id(),
+            Self::ParseError(e) => e.message_id(),
+            Self::GenerationError(e) => e.message_id(),
+            Self::IoError(_) => "io",
+            Self::ValidationError(_) => "validation",
+            Self::ConfigurationError(_) => "configuration",
+            Self::SystemError(_) => "system",
+            Self::SecurityError(_) => "security",
+            Self::TimeoutError(_) => "timeout",
+            Self::NetworkError(_) => "network",
+        }
+    }
+
+    fn message_args(&self) -> Vec<(&'static str, String)> {
+        match self {
+            Self::LexError(e) => e.message_args(),
+            Self::ParseError(e) => e.message_args(),
+            Self::GenerationError(e) => e.message_args(),
+            Self::IoError(detail)
+            | Self::ValidationError(detail)
+            | Self::ConfigurationError(detail)
+            | Self::SystemError(detail)
+            | Self::SecurityError(detail)
+            | Self::TimeoutError(detail)
+            | Self::NetworkError(detail) => vec![("detail", detail.clone())],
+        }
+    }
 }
 
 // Import ValidationError for From implementation
-#[cfg(not(target_family = "wasm"))]
+#[cfg(all(feature = "cli", not(target_family = "wasm")))]
 use crate::cli::output_formatter::FormatError;
-#[cfg(not(target_family = "wasm"))]
+#[cfg(all(feature = "cli", not(target_family = "wasm")))]
 use crate::cli::validator::ValidationError;
 
-#[cfg(not(target_family = "wasm"))]
+#[cfg(all(feature = "cli", not(target_family = "wasm")))]
 impl From<ValidationError> for TranspileError {
     fn from(error: ValidationError) -> Self {
         Self::ValidationError(error.to_string())
     }
 }
 
-#[cfg(not(target_family = "wasm"))]
+#[cfg(all(feature = "cli", not(target_family = "wasm")))]
 impl From<FormatError> for TranspileError {
     fn from(error: FormatError) -> Self {
         Self::IoError(format!("Output formatting error: {error}"))
     }
 }
 
+/// Non-fatal diagnostics for translations that succeed with reduced fidelity
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TranspileWarning {
+    /// An order-sensitive window function was emitted without an ORDER BY.
+    UnorderedWindowFunction { function: String },
+
+    /// A cast was emitted with a SQL type that does not match R semantics.
+    LossyTypeConversion {
+        function: String,
+        sql_type: String,
+        dialect: String,
+    },
+
+    /// A requested NULLS FIRST/LAST was dropped because the dialect lacks the clause.
+    NullsOrderingIgnored { column: String, dialect: String },
+
+    /// A condition mixes AND and OR at one level without parentheses.
+    AmbiguousPrecedence { condition: String },
+
+    /// Helper columns a verb computed stay in the result, because the dialect
+    /// cannot drop them from `*` and the other columns are unknown.
+    HelperColumnsKept {
+        operation: String,
+        columns: Vec<String>,
+        dialect: String,
+    },
+}
+
+impl TranspileWarning {
+    /// Stable machine-readable code for this warning.
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Self::UnorderedWindowFunction { .. } => "W-UNORDERED-WINDOW",
+            Self::LossyTypeConversion { .. } => "W-LOSSY-CAST",
+            Self::NullsOrderingIgnored { .. } => "W-NULLS-ORDER",
+            Self::AmbiguousPrecedence { .. } => "W-PRECEDENCE",
+            Self::HelperColumnsKept { .. } => "W-HELPER-COLUMNS",
+        }
+    }
+}
+
+impl core::fmt::Display for TranspileWarning {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::UnorderedWindowFunction { function } => write!(
+                f,
+                "'{function}' has no ordering; row order is database-defined (pass order_by to make it deterministic)"
+            ),
+            Self::LossyTypeConversion {
+                function,
+                sql_type,
+                dialect,
+            } => write!(
+                f,
+                "'{function}' is emitted as {sql_type} in '{dialect}' dialect and may not preserve R semantics"
+            ),
+            Self::NullsOrderingIgnored { column, dialect } => write!(
+                f,
+                "NULL placement for '{column}' is not supported in '{dialect}' dialect; using the database default"
+            ),
+            Self::AmbiguousPrecedence { condition } => write!(
+                f,
+                "'{condition}' mixes AND and OR without parentheses; AND is applied first (add parentheses to make the grouping explicit)"
+            ),
+            Self::HelperColumnsKept {
+                operation,
+                columns,
+                dialect,
+            } => write!(
+                f,
+                "'{operation}' leaves its helper columns {} in the result because '{dialect}' dialect cannot exclude them from * (list the columns with select() first to drop them)",
+                columns.join(", ")
+            ),
+        }
+    }
+}
+
 /// Result type aliases
 pub type LexResult<T> = Result<T, LexError>;
 pub type ParseResult<T> = Result<T, ParseError>;
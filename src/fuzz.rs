@@ -0,0 +1,108 @@
+//! Fuzzing entry point.
+//!
+//! A `cargo fuzz` target only needs to forward its input:
+//!
+//! ```rust,ignore
+//! libfuzzer_sys::fuzz_target!(|data: &[u8]| {
+//!     let _ = libdplyr::fuzz::transpile_once(data);
+//! });
+//! ```
+
+use crate::{PostgreSqlDialect, TranspileError, Transpiler};
+
+/// Transpiles `data`, read as lossy UTF-8, with the PostgreSQL dialect.
+///
+/// Malformed input must come back as `Err`; any panic is a bug.
+pub fn transpile_once(data: &[u8]) -> Result<String, TranspileError> {
+    let code = String::from_utf8_lossy(data);
+    Transpiler::new(Box::new(PostgreSqlDialect::new())).transpile(&code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fragments that steer random input towards the parser's deeper paths.
+    const FRAGMENTS: &[&str] = &[
+        "data",
+        " %>% ",
+        " |> ",
+        "select",
+        "filter",
+        "mutate",
+        "arrange",
+        "group_by",
+        "summarise",
+        "count",
+        "head",
+        "left_join",
+        "desc",
+        "n()",
+        "(",
+        ")",
+        ",",
+        "=",
+        "==",
+        "!",
+        "&",
+        "|",
+        "~",
+        ".x",
+        "c(",
+        "if_any",
+        "case_when",
+        "\"s\"",
+        "'",
+        "1.5",
+        "-",
+        "NA",
+        "TRUE",
+        "#",
+        "\n",
+        " ",
+        "$",
+        "%in%",
+        "%",
+    ];
+
+    /// xorshift64*, so the corpus is the same on every run.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 >> 12;
+            self.0 ^= self.0 << 25;
+            self.0 ^= self.0 >> 27;
+            self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+        }
+
+        fn below(&mut self, bound: usize) -> usize {
+            (self.next() % bound as u64) as usize
+        }
+    }
+
+    #[test]
+    fn test_random_input_never_panics() {
+        let mut rng = Rng(0x9E37_79B9_7F4A_7C15);
+        for _ in 0..4000 {
+            let mut input = Vec::new();
+            for _ in 0..rng.below(24) {
+                if rng.below(4) == 0 {
+                    // Raw bytes, including invalid UTF-8
+                    input.extend((0..rng.below(6)).map(|_| rng.next() as u8));
+                } else {
+                    input.extend_from_slice(FRAGMENTS[rng.below(FRAGMENTS.len())].as_bytes());
+                }
+            }
+            // Either outcome is fine; a panic fails the test
+            let _ = transpile_once(&input);
+        }
+    }
+
+    #[test]
+    fn test_transpile_once_reads_lossy_utf8() {
+        assert!(transpile_once(b"data %>% select(a)").is_ok());
+        assert!(transpile_once(b"data %>% select(\xff)").is_err());
+        assert!(transpile_once(b"").is_err());
+    }
+}
@@ -2,22 +2,35 @@
 //!
 //! Provides functionality to tokenize dplyr code.
 
+use once_cell::race::OnceBox;
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::collections::HashMap;
 use crate::error::{LexError, LexResult};
 use crate::PipeSyntax;
-use std::collections::HashMap;
 
-lazy_static::lazy_static! {
-    /// Keyword mapping for efficient lookup
-    static ref KEYWORDS: HashMap<&'static str, Token> = {
+/// Keyword mapping for efficient lookup, built on first use
+static KEYWORDS: OnceBox<HashMap<&'static str, Token>> = OnceBox::new();
+
+fn keyword_map() -> &'static HashMap<&'static str, Token> {
+    KEYWORDS.get_or_init(|| {
         let mut m = HashMap::new();
         m.insert("select", Token::Select);
         m.insert("filter", Token::Filter);
         m.insert("mutate", Token::Mutate);
         m.insert("rename", Token::Rename);
+        m.insert("rename_with", Token::RenameWith);
+        m.insert("transmute", Token::Transmute);
         m.insert("arrange", Token::Arrange);
         m.insert("group_by", Token::GroupBy);
         m.insert("summarise", Token::Summarise);
         m.insert("summarize", Token::Summarise);
+        m.insert("distinct", Token::Distinct);
+        m.insert("fill", Token::Fill);
+        m.insert("slice", Token::Slice);
+        m.insert("slice_head", Token::SliceHead);
+        m.insert("slice_tail", Token::SliceTail);
         m.insert("inner_join", Token::InnerJoin);
         m.insert("left_join", Token::LeftJoin);
         m.insert("right_join", Token::RightJoin);
@@ -31,7 +44,10 @@ lazy_static::lazy_static! {
         m.insert("is.na", Token::Identifier("is.na".to_string()));
         m.insert("as.numeric", Token::Identifier("as.numeric".to_string()));
         m.insert("as.integer", Token::Identifier("as.integer".to_string()));
-        m.insert("as.character", Token::Identifier("as.character".to_string()));
+        m.insert(
+            "as.character",
+            Token::Identifier("as.character".to_string()),
+        );
         m.insert("as.logical", Token::Identifier("as.logical".to_string()));
         m.insert("as.double", Token::Identifier("as.double".to_string()));
         m.insert("na.fill", Token::Identifier("na.fill".to_string()));
@@ -45,8 +61,20 @@ lazy_static::lazy_static! {
         m.insert("NULL", Token::Null);
         m.insert("null", Token::Null);
         m.insert("NA", Token::Null);
-        m
-    };
+        Box::new(m)
+    })
+}
+
+/// Returns the token a built-in keyword lexes to, or `None` for plain identifiers.
+///
+/// Both British and American spellings are keywords (`summarise` / `summarize`).
+pub fn keyword_token(word: &str) -> Option<Token> {
+    keyword_map().get(word).cloned()
+}
+
+/// Lists every built-in keyword with the token it lexes to, in no particular order.
+pub fn keywords() -> impl Iterator<Item = (&'static str, &'static Token)> {
+    keyword_map().iter().map(|(word, token)| (*word, token))
 }
 
 /// Token types used in dplyr code
@@ -57,9 +85,16 @@ pub enum Token {
     Filter,
     Mutate,
     Rename,
+    RenameWith,
+    Transmute,
     Arrange,
     GroupBy,
     Summarise,
+    Distinct,
+    Fill,
+    Slice,
+    SliceHead,
+    SliceTail,
     InnerJoin,
     LeftJoin,
     RightJoin,
@@ -76,6 +111,8 @@ pub enum Token {
 
     // Operators
     Pipe,               // %>%
+    Between,            // %between% (data.table range test)
+    NullDefault,        // %||% (rlang null default)
     ArrowRight,         // ->
     ArrowLeft,          // <-
     Assignment,         // =
@@ -87,10 +124,15 @@ pub enum Token {
     GreaterThanOrEqual, // >=
     And,                // &
     Or,                 // |
+    Not,                // ! (logical negation)
     Plus,               // +
     Minus,              // -
     Multiply,           // *
     Divide,             // /
+    Caret,              // ^ (exponent)
+    Tilde,              // ~ (formula, e.g. in case_when)
+    Colon,              // : (column ranges, e.g. a:c)
+    Dollar,             // $ (column access, e.g. .data$x)
 
     // Literals
     Identifier(String),
@@ -114,16 +156,23 @@ pub enum Token {
     Whitespace, // Whitespace (usually ignored)
 }
 
-impl std::fmt::Display for Token {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Token {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::Select => write!(f, "select"),
             Self::Filter => write!(f, "filter"),
             Self::Mutate => write!(f, "mutate"),
             Self::Rename => write!(f, "rename"),
+            Self::RenameWith => write!(f, "rename_with"),
+            Self::Transmute => write!(f, "transmute"),
             Self::Arrange => write!(f, "arrange"),
             Self::GroupBy => write!(f, "group_by"),
             Self::Summarise => write!(f, "summarise"),
+            Self::Distinct => write!(f, "distinct"),
+            Self::Fill => write!(f, "fill"),
+            Self::Slice => write!(f, "slice"),
+            Self::SliceHead => write!(f, "slice_head"),
+            Self::SliceTail => write!(f, "slice_tail"),
             Self::InnerJoin => write!(f, "inner_join"),
             Self::LeftJoin => write!(f, "left_join"),
             Self::RightJoin => write!(f, "right_join"),
@@ -136,6 +185,8 @@ impl std::fmt::Display for Token {
             Self::Desc => write!(f, "desc"),
             Self::Asc => write!(f, "asc"),
             Self::Pipe => write!(f, "%>%"),
+            Self::Between => write!(f, "%between%"),
+            Self::NullDefault => write!(f, "%||%"),
             Self::ArrowRight => write!(f, "->"),
             Self::ArrowLeft => write!(f, "<-"),
             Self::Assignment => write!(f, "="),
@@ -147,10 +198,15 @@ impl std::fmt::Display for Token {
             Self::GreaterThanOrEqual => write!(f, ">="),
             Self::And => write!(f, "&"),
             Self::Or => write!(f, "|"),
+            Self::Not => write!(f, "!"),
             Self::Plus => write!(f, "+"),
             Self::Minus => write!(f, "-"),
             Self::Multiply => write!(f, "*"),
+            Self::Caret => write!(f, "^"),
             Self::Divide => write!(f, "/"),
+            Self::Tilde => write!(f, "~"),
+            Self::Colon => write!(f, ":"),
+            Self::Dollar => write!(f, "$"),
             Self::Identifier(name) => write!(f, "{name}"),
             Self::String(s) => write!(f, "\"{s}\""),
             Self::Number(n) => write!(f, "{n}"),
@@ -178,6 +234,8 @@ pub struct Lexer {
     position: usize,
     current_char: Option<char>,
     pipe_syntax: PipeSyntax,
+    comments: Vec<(usize, String)>,
+    keyword_aliases: HashMap<String, Token>,
 }
 
 impl Lexer {
@@ -200,14 +258,30 @@ impl Lexer {
             position: 0,
             current_char,
             pipe_syntax,
+            comments: Vec::new(),
+            keyword_aliases: HashMap::new(),
         }
     }
 
+    /// Lexes each alias as the keyword token it maps to, such as a localized
+    /// `resumir` for [`Token::Summarise`].
+    ///
+    /// Built-in keywords (see [`keywords`]) take precedence over aliases.
+    pub fn with_keyword_aliases(mut self, aliases: HashMap<String, Token>) -> Self {
+        self.keyword_aliases = aliases;
+        self
+    }
+
     /// Returns the pipe syntax this lexer recognizes.
     pub const fn pipe_syntax(&self) -> PipeSyntax {
         self.pipe_syntax
     }
 
+    /// Returns the text of every `#` comment skipped so far, in source order.
+    pub fn comments(&self) -> Vec<String> {
+        self.comments.iter().map(|(_, text)| text.clone()).collect()
+    }
+
     /// Returns the next token.
     ///
     /// # Returns
@@ -276,10 +350,26 @@ impl Lexer {
                         self.advance();
                         Ok(Token::Divide)
                     }
+                    '^' => {
+                        self.advance();
+                        Ok(Token::Caret)
+                    }
                     '\\' => {
                         self.advance();
                         Ok(Token::Backslash)
                     }
+                    '~' => {
+                        self.advance();
+                        Ok(Token::Tilde)
+                    }
+                    ':' => {
+                        self.advance();
+                        Ok(Token::Colon)
+                    }
+                    '$' => {
+                        self.advance();
+                        Ok(Token::Dollar)
+                    }
                     '=' => {
                         self.advance();
                         if self.current_char == Some('=') {
@@ -295,7 +385,7 @@ impl Lexer {
                             self.advance();
                             Ok(Token::NotEqual)
                         } else {
-                            Err(LexError::UnexpectedCharacter(ch, self.position))
+                            Ok(Token::Not)
                         }
                     }
                     '<' => {
@@ -320,18 +410,26 @@ impl Lexer {
                         }
                     }
                     '&' => {
+                        // R's scalar `&&` means the same as `&` in SQL
                         self.advance();
+                        if self.current_char == Some('&') {
+                            self.advance();
+                        }
                         Ok(Token::And)
                     }
                     '|' => {
                         if self.input.get(self.position + 1) == Some(&'>') {
                             return self.read_native_pipe_operator();
                         }
+                        // R's scalar `||` means the same as `|` in SQL
                         self.advance();
+                        if self.current_char == Some('|') {
+                            self.advance();
+                        }
                         Ok(Token::Or)
                     }
                     '%' => {
-                        // Handle pipe operator %>%
+                        // Handle pipe operator %>% and the %between% infix
                         self.read_pipe_operator()
                     }
                     '"' | '\'' => self.read_string(),
@@ -366,18 +464,38 @@ impl Lexer {
         self.current_char = self.input.get(self.position).copied();
     }
 
-    /// Skips whitespace characters.
+    /// Skips whitespace characters and R `#` comments up to the end of the line.
     fn skip_whitespace(&mut self) {
         while let Some(ch) = self.current_char {
             if ch.is_whitespace() && ch != '\n' {
                 self.advance();
+            } else if ch == '#' {
+                self.skip_comment();
             } else {
                 break;
             }
         }
     }
 
-    /// Reads the magrittr pipe operator %>%.
+    /// Skips a `#` comment, recording its text once even when re-scanned by a peek.
+    fn skip_comment(&mut self) {
+        let start = self.position;
+        self.advance();
+        let text_start = self.position;
+        while let Some(ch) = self.current_char {
+            if ch == '\n' {
+                break;
+            }
+            self.advance();
+        }
+
+        if self.comments.last().is_none_or(|(seen, _)| *seen < start) {
+            let text: String = self.input[text_start..self.position].iter().collect();
+            self.comments.push((start, text.trim().to_string()));
+        }
+    }
+
+    /// Reads the magrittr pipe operator %>%, or the data.table infix %between%.
     fn read_pipe_operator(&mut self) -> LexResult<Token> {
         let start_position = self.position;
         let mut pipe_str = String::new();
@@ -403,6 +521,34 @@ impl Lexer {
             } else {
                 Err(LexError::InvalidPipeOperator(pipe_str, start_position))
             }
+        } else if self.current_char == Some('|') {
+            while let Some(ch) = self.current_char.filter(|&ch| ch == '|') {
+                pipe_str.push(ch);
+                self.advance();
+            }
+            if pipe_str == "%||" && self.current_char == Some('%') {
+                self.advance();
+                Ok(Token::NullDefault)
+            } else {
+                if let Some(ch) = self.current_char {
+                    pipe_str.push(ch);
+                }
+                Err(LexError::InvalidPipeOperator(pipe_str, start_position))
+            }
+        } else if self.current_char.is_some_and(|ch| ch.is_ascii_alphabetic()) {
+            while let Some(ch) = self.current_char.filter(char::is_ascii_alphabetic) {
+                pipe_str.push(ch);
+                self.advance();
+            }
+            if pipe_str == "%between" && self.current_char == Some('%') {
+                self.advance();
+                Ok(Token::Between)
+            } else {
+                if let Some(ch) = self.current_char {
+                    pipe_str.push(ch);
+                }
+                Err(LexError::InvalidPipeOperator(pipe_str, start_position))
+            }
         } else {
             // Include the current character in the error string if it exists
             if let Some(ch) = self.current_char {
@@ -462,7 +608,10 @@ impl Lexer {
         Err(LexError::UnterminatedString(self.position))
     }
 
-    /// Reads a number.
+    /// Reads a number, with an optional fraction and exponent (`1.5e-3`).
+    ///
+    /// Whole numbers an `f64` cannot hold exactly (beyond 2^53, such as long
+    /// IDs) are rejected instead of being rounded to a different value.
     fn read_number(&mut self) -> LexResult<Token> {
         let mut number_str = String::new();
 
@@ -475,10 +624,34 @@ impl Lexer {
             }
         }
 
-        number_str
+        // An exponent needs digits after the optional sign; otherwise `e`
+        // starts the next token
+        if matches!(self.current_char, Some('e' | 'E')) {
+            let sign = usize::from(matches!(self.input.get(self.position + 1), Some('+' | '-')));
+            if self
+                .input
+                .get(self.position + 1 + sign)
+                .is_some_and(char::is_ascii_digit)
+            {
+                for _ in 0..=sign {
+                    number_str.extend(self.current_char);
+                    self.advance();
+                }
+                while let Some(ch) = self.current_char.filter(char::is_ascii_digit) {
+                    number_str.push(ch);
+                    self.advance();
+                }
+            }
+        }
+
+        let value = number_str
             .parse::<f64>()
-            .map(Token::Number)
-            .map_err(|_| LexError::InvalidNumber(number_str, self.position))
+            .map_err(|_| LexError::InvalidNumber(number_str.clone(), self.position))?;
+        let is_integer = number_str.bytes().all(|b| b.is_ascii_digit());
+        if is_integer && !number_str.parse::<u128>().is_ok_and(|n| value as u128 == n) {
+            return Err(LexError::ImpreciseNumber(number_str, self.position));
+        }
+        Ok(Token::Number(value))
     }
 
     /// Reads an identifier or keyword.
@@ -495,9 +668,10 @@ impl Lexer {
             }
         }
 
-        // Check for keywords using the static hashmap
-        let token = KEYWORDS
+        // Check for keywords using the static hashmap, then registered aliases
+        let token = keyword_map()
             .get(identifier.as_str())
+            .or_else(|| self.keyword_aliases.get(&identifier))
             .cloned()
             .unwrap_or(Token::Identifier(identifier));
 
@@ -574,12 +748,13 @@ mod tests {
         #[test]
         fn test_arithmetic_operators() {
             assert_tokens(
-                "+ - * /",
+                "+ - * / ^",
                 vec![
                     Token::Plus,
                     Token::Minus,
                     Token::Multiply,
                     Token::Divide,
+                    Token::Caret,
                     Token::EOF,
                 ],
             );
@@ -607,6 +782,22 @@ mod tests {
             assert_tokens("& |", vec![Token::And, Token::Or, Token::EOF]);
         }
 
+        #[test]
+        fn test_scalar_logical_operators_match_vectorized_forms() {
+            assert_tokens("&& ||", vec![Token::And, Token::Or, Token::EOF]);
+            assert_tokens(
+                "a&&b||c",
+                vec![
+                    Token::Identifier("a".to_string()),
+                    Token::And,
+                    Token::Identifier("b".to_string()),
+                    Token::Or,
+                    Token::Identifier("c".to_string()),
+                    Token::EOF,
+                ],
+            );
+        }
+
         #[test]
         fn test_identifiers_basic() {
             assert_tokens(
@@ -741,6 +932,37 @@ mod tests {
             );
         }
 
+        #[test]
+        fn test_numbers_scientific_notation() {
+            assert_tokens("1e10", vec![Token::Number(1e10), Token::EOF]);
+            assert_tokens("1.5e-3", vec![Token::Number(0.0015), Token::EOF]);
+            assert_tokens("2E+3", vec![Token::Number(2000.0), Token::EOF]);
+            // Without exponent digits, `e` is the next token
+            assert_tokens(
+                "2e",
+                vec![
+                    Token::Number(2.0),
+                    Token::Identifier("e".to_string()),
+                    Token::EOF,
+                ],
+            );
+        }
+
+        #[test]
+        fn test_whole_numbers_beyond_f64_precision_are_rejected() {
+            assert_tokens(
+                "9007199254740992",
+                vec![Token::Number(9_007_199_254_740_992.0), Token::EOF],
+            );
+            for input in ["9007199254740993", "12345678901234567890"] {
+                let mut lexer = Lexer::new(input.to_string());
+                assert_eq!(
+                    lexer.next_token(),
+                    Err(LexError::ImpreciseNumber(input.to_string(), input.len()))
+                );
+            }
+        }
+
         #[test]
         fn test_boolean_literals() {
             assert_tokens("TRUE", vec![Token::Boolean(true), Token::EOF]);
@@ -779,6 +1001,35 @@ mod tests {
             assert_tokens("summarize", vec![Token::Summarise, Token::EOF]);
         }
 
+        #[test]
+        fn test_keyword_table_is_exposed() {
+            assert_eq!(keyword_token("summarize"), Some(Token::Summarise));
+            assert_eq!(keyword_token("transmute"), Some(Token::Transmute));
+            assert_eq!(keyword_token("rename"), Some(Token::Rename));
+            assert_eq!(keyword_token("distinct"), Some(Token::Distinct));
+            assert_eq!(keyword_token("salary"), None);
+            assert!(
+                keywords().any(|(word, token)| word == "summarise" && *token == Token::Summarise)
+            );
+        }
+
+        #[test]
+        fn test_keyword_aliases() {
+            let aliases = HashMap::from([
+                ("resumir".to_string(), Token::Summarise),
+                ("summarise".to_string(), Token::Filter),
+            ]);
+            let mut lexer =
+                Lexer::new("resumir summarise resumen".to_string()).with_keyword_aliases(aliases);
+            assert_eq!(lexer.next_token().unwrap(), Token::Summarise);
+            // Built-in keywords cannot be redefined
+            assert_eq!(lexer.next_token().unwrap(), Token::Summarise);
+            assert_eq!(
+                lexer.next_token().unwrap(),
+                Token::Identifier("resumen".to_string())
+            );
+        }
+
         #[test]
         fn test_helper_functions() {
             assert_tokens("desc", vec![Token::Desc, Token::EOF]);
@@ -1035,7 +1286,7 @@ mod tests {
 
         #[test]
         fn test_unexpected_character_symbols() {
-            let test_cases = vec!['@', '#', '$', '^', '~', '`', '[', ']'];
+            let test_cases = vec!['@', '`', '[', ']'];
 
             for ch in test_cases {
                 let mut lexer = Lexer::new(ch.to_string());
@@ -1061,11 +1312,16 @@ mod tests {
 
         #[test]
         fn test_exclamation_without_equals() {
-            let mut lexer = Lexer::new("!".to_string());
-            match lexer.next_token() {
-                Err(LexError::UnexpectedCharacter('!', _)) => {}
-                other => panic!("Expected UnexpectedCharacter error for '!', got: {other:?}"),
-            }
+            assert_tokens(
+                "!is_active != x",
+                vec![
+                    Token::Not,
+                    Token::Identifier("is_active".to_string()),
+                    Token::NotEqual,
+                    Token::Identifier("x".to_string()),
+                    Token::EOF,
+                ],
+            );
         }
 
         #[test]
@@ -1095,6 +1351,35 @@ mod tests {
             assert_tokens("", vec![Token::EOF]);
         }
 
+        #[test]
+        fn test_crlf_and_tabs_are_whitespace_around_single_newlines() {
+            assert_tokens(
+                "select(a)\r\n\tfilter(b)\r\n",
+                vec![
+                    Token::Select,
+                    Token::LeftParen,
+                    Token::Identifier("a".to_string()),
+                    Token::RightParen,
+                    Token::Newline,
+                    Token::Filter,
+                    Token::LeftParen,
+                    Token::Identifier("b".to_string()),
+                    Token::RightParen,
+                    Token::Newline,
+                    Token::EOF,
+                ],
+            );
+            assert_tokens(
+                "a\rb\t# note\r\n",
+                vec![
+                    Token::Identifier("a".to_string()),
+                    Token::Identifier("b".to_string()),
+                    Token::Newline,
+                    Token::EOF,
+                ],
+            );
+        }
+
         #[test]
         fn test_whitespace_only() {
             assert_tokens("   \t  ", vec![Token::EOF]);
@@ -1167,5 +1452,119 @@ mod tests {
             assert!(tokens.contains(&Token::Boolean(false)));
             assert!(tokens.contains(&Token::Null));
         }
+
+        #[test]
+        fn test_case_when_formula_tokens() {
+            assert_tokens(
+                "case_when(is.na(x) ~ 0, TRUE ~ x)",
+                vec![
+                    Token::Identifier("case_when".to_string()),
+                    Token::LeftParen,
+                    Token::Identifier("is.na".to_string()),
+                    Token::LeftParen,
+                    Token::Identifier("x".to_string()),
+                    Token::RightParen,
+                    Token::Tilde,
+                    Token::Number(0.0),
+                    Token::Comma,
+                    Token::Boolean(true),
+                    Token::Tilde,
+                    Token::Identifier("x".to_string()),
+                    Token::RightParen,
+                    Token::EOF,
+                ],
+            );
+        }
+
+        #[test]
+        fn test_colon_range_tokens() {
+            assert_tokens(
+                "a:c",
+                vec![
+                    Token::Identifier("a".to_string()),
+                    Token::Colon,
+                    Token::Identifier("c".to_string()),
+                    Token::EOF,
+                ],
+            );
+        }
+
+        #[test]
+        fn test_dollar_column_access_tokens() {
+            assert_tokens(
+                ".data$age df$x",
+                vec![
+                    Token::Dot,
+                    Token::Identifier("data".to_string()),
+                    Token::Dollar,
+                    Token::Identifier("age".to_string()),
+                    Token::Identifier("df".to_string()),
+                    Token::Dollar,
+                    Token::Identifier("x".to_string()),
+                    Token::EOF,
+                ],
+            );
+        }
+
+        #[test]
+        fn test_between_infix_token() {
+            assert_tokens(
+                "x %between% c",
+                vec![
+                    Token::Identifier("x".to_string()),
+                    Token::Between,
+                    Token::Identifier("c".to_string()),
+                    Token::EOF,
+                ],
+            );
+
+            let mut lexer = Lexer::new("%betwixt%".to_string());
+            assert_eq!(
+                lexer.next_token(),
+                Err(LexError::InvalidPipeOperator("%betwixt%".to_string(), 0))
+            );
+        }
+
+        #[test]
+        fn test_null_default_infix_token() {
+            assert_tokens(
+                "raw %||% \"unknown\"",
+                vec![
+                    Token::Identifier("raw".to_string()),
+                    Token::NullDefault,
+                    Token::String("unknown".to_string()),
+                    Token::EOF,
+                ],
+            );
+
+            let mut lexer = Lexer::new("a %|% b".to_string());
+            assert_eq!(lexer.next_token(), Ok(Token::Identifier("a".to_string())));
+            assert_eq!(
+                lexer.next_token(),
+                Err(LexError::InvalidPipeOperator("%|%".to_string(), 2))
+            );
+        }
+
+        #[test]
+        fn test_hash_comments_are_skipped_and_recorded_once() {
+            let mut lexer = Lexer::new("# keep adults\nfilter(age > 18) # inline\n".to_string());
+            assert_eq!(lexer.next_token().unwrap(), Token::Newline);
+            assert_eq!(lexer.peek_token().unwrap(), Token::Filter);
+
+            let mut tokens = Vec::new();
+            loop {
+                let token = lexer.next_token().unwrap();
+                if token == Token::EOF {
+                    break;
+                }
+                tokens.push(token);
+            }
+
+            assert_eq!(tokens.last(), Some(&Token::Newline));
+            assert!(tokens
+                .iter()
+                .all(|t| !matches!(t, Token::Identifier(name) if name == "keep")));
+            assert_eq!(lexer.comments(), vec!["keep adults", "inline"]);
+        }
     }
 }
@@ -12,13 +12,16 @@
 //!
 //! - **Complete dplyr Function Support**: `select()`, `filter()`, `mutate()`, `arrange()`, `group_by()`, `summarise()`
 //! - **Pipeline Operations**: Chain operations using the `%>%` pipe operator
-//! - **Multiple SQL Dialects**: PostgreSQL, MySQL, SQLite, DuckDB support with dialect-specific optimizations
+//! - **Multiple SQL Dialects**: PostgreSQL, MySQL, SQLite, DuckDB, Oracle, Spark SQL support with dialect-specific optimizations
 
 //! - **Performance Optimized**: Efficient parsing and SQL generation with minimal memory allocation
 //! - **Comprehensive Error Handling**: Detailed error messages with position information and helpful hints
 //! - **CLI Tool**: Full-featured command-line interface with pretty-printing and file I/O
 //! - **Type Safety**: Leverages Rust's type system for safe AST manipulation
 //! - **Extensible Architecture**: Plugin-ready design for adding new SQL dialects
+//! - **`no_std` Core**: With default features off, the lexer, parser and AST build
+//!   on `core` + `alloc`; SQL generation, [`Transpiler`] and the CLI need the
+//!   default `std` feature
 //!
 //! ## Quick Start
 //!
@@ -111,6 +114,16 @@
 //! // Generate SQL from AST
 //! let sql = transpiler.generate_sql(&ast).unwrap();
 //! println!("Generated SQL: {}", sql);
+//!
+//! // Or build the AST directly, without dplyr text
+//! use libdplyr::parser::{BinaryOp, Expr, OrderExpr};
+//!
+//! let built = DplyrNode::pipeline()
+//!     .select(["name", "age"])
+//!     .filter(Expr::binary(Expr::column("age"), BinaryOp::GreaterThan, Expr::number(18.0)))
+//!     .arrange([OrderExpr::desc("age")])
+//!     .build();
+//! let sql = transpiler.generate_sql(&built).unwrap();
 //! ```
 //!
 //! ## Supported SQL Dialects
@@ -185,6 +198,18 @@
 //!         eprintln!("System error: {}", e);
 //!         eprintln!("Check system resources and permissions");
 //!     }
+//!     Err(TranspileError::SecurityError(e)) => {
+//!         eprintln!("Security check failed: {}", e);
+//!         eprintln!("Remove control characters or reduce nesting");
+//!     }
+//!     Err(TranspileError::TimeoutError(e)) => {
+//!         eprintln!("Timed out: {}", e);
+//!         eprintln!("Check that input is being provided");
+//!     }
+//!     Err(TranspileError::NetworkError(e)) => {
+//!         eprintln!("Network error: {}", e);
+//!         eprintln!("Check that the URL is reachable");
+//!     }
 //! }
 //! ```
 //!
@@ -271,28 +296,76 @@
 //!
 //! This project is licensed under the MIT License - see the LICENSE file for details.
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 pub mod error;
+#[cfg(feature = "std")]
+pub mod fuzz;
 pub mod lexer;
 pub mod parser;
+// Transpilation metadata (excluded on wasm targets - no system clock)
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+pub mod metadata;
+#[cfg(feature = "std")]
 pub mod performance;
 pub mod pipe_syntax;
+#[cfg(feature = "std")]
 pub mod sql_generator;
 
 // CLI module (excluded on wasm targets - no signal handling or terminal support)
-#[cfg(not(target_family = "wasm"))]
+#[cfg(all(feature = "cli", not(target_family = "wasm")))]
 pub mod cli;
 
+/// Map and set types of the core: the `std` hash collections, or the B-tree
+/// collections of `alloc` without `std`.
+pub mod collections {
+    #[cfg(not(feature = "std"))]
+    pub use alloc::collections::{BTreeMap as HashMap, BTreeSet as HashSet};
+    #[cfg(feature = "std")]
+    pub use std::collections::{HashMap, HashSet};
+}
+
+/// `std` prelude items the core modules use, taken from `alloc` without `std`.
+#[cfg(not(feature = "std"))]
+mod alloc_prelude {
+    pub use alloc::borrow::ToOwned;
+    pub use alloc::boxed::Box;
+    pub use alloc::format;
+    pub use alloc::string::{String, ToString};
+    pub use alloc::vec;
+    pub use alloc::vec::Vec;
+}
+
+#[cfg(feature = "std")]
+use crate::collections::{HashMap, HashSet};
+
+#[cfg(feature = "std")]
+use crate::parser::{Expr, LiteralValue, DEFAULT_HEAD_ROWS};
+
+/// Version of the libdplyr core library.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
 // Re-export public API
-pub use crate::error::{GenerationError, LexError, ParseError, TranspileError};
+pub use crate::error::{
+    error_code_for, error_code_name, fill_message_template, GenerationError, LexError,
+    LocalizedError, MessageCatalog, ParseError, TranspileError, TranspileWarning,
+};
 pub use crate::lexer::{Lexer, Token};
-pub use crate::parser::{DplyrNode, DplyrOperation, Parser};
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+pub use crate::metadata::{InputInfo, MetadataBuilder, ProcessingStats, TranspileMetadata};
+pub use crate::parser::{ast_equal, DplyrNode, DplyrOperation, Parser};
+#[cfg(feature = "std")]
 pub use crate::performance::{
     BatchPerformanceStats, PerformanceMetrics, PerformanceProfiler, RegressionDetector,
 };
 pub use crate::pipe_syntax::{PipeSyntax, PIPE_SYNTAX_ENV_VAR};
+#[cfg(feature = "std")]
 pub use crate::sql_generator::{
-    DialectConfig, DuckDbDialect, MySqlDialect, PostgreSqlDialect, SqlDialect, SqlGenerator,
-    SqliteDialect,
+    dialect_from_name, format_number, supported_dialect_names, DialectConfig, DuckDbDialect,
+    IdentifierCase, MySqlDialect, OracleDialect, PostgreSqlDialect, SparkSqlDialect, SqlDialect,
+    SqlGenerator, SqliteDialect,
 };
 
 /// Main transpiler struct for converting dplyr code to SQL
@@ -346,13 +419,25 @@ pub use crate::sql_generator::{
 ///     Err(TranspileError::ValidationError(e)) => eprintln!("Validation failed: {}", e),
 ///     Err(TranspileError::ConfigurationError(e)) => eprintln!("Configuration error: {}", e),
 ///     Err(TranspileError::SystemError(e)) => eprintln!("System error: {}", e),
+///     Err(TranspileError::SecurityError(e)) => eprintln!("Security check failed: {}", e),
+///     Err(TranspileError::TimeoutError(e)) => eprintln!("Timed out: {}", e),
+///     Err(TranspileError::NetworkError(e)) => eprintln!("Network error: {}", e),
 /// }
 /// ```
+#[cfg(feature = "std")]
+#[derive(Clone)]
 pub struct Transpiler {
     generator: SqlGenerator,
     pipe_syntax: PipeSyntax,
+    strict: bool,
+    preserve_comments: bool,
+    precedence_warnings: bool,
+    default_head_rows: usize,
+    fold_negations: bool,
+    keyword_aliases: HashMap<String, Token>,
 }
 
+#[cfg(feature = "std")]
 impl Transpiler {
     /// Creates a new transpiler instance with the specified SQL dialect.
     ///
@@ -381,9 +466,42 @@ impl Transpiler {
         Self {
             generator: SqlGenerator::new(dialect),
             pipe_syntax,
+            strict: false,
+            preserve_comments: false,
+            precedence_warnings: false,
+            default_head_rows: DEFAULT_HEAD_ROWS,
+            fold_negations: false,
+            keyword_aliases: HashMap::new(),
         }
     }
 
+    /// Returns a builder for configuring dialect, pipe syntax, strictness, and comments.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use libdplyr::{DuckDbDialect, Transpiler};
+    ///
+    /// let transpiler = Transpiler::builder()
+    ///     .dialect(Box::new(DuckDbDialect::new()))
+    ///     .strict(true)
+    ///     .build();
+    /// assert!(transpiler.transpile("mutate(prev = lag(amount))").is_err());
+    /// ```
+    pub fn builder() -> TranspilerBuilder {
+        TranspilerBuilder::default()
+    }
+
+    /// Name of the target dialect, such as `"postgresql"` or `"duckdb"`.
+    pub fn dialect_name(&self) -> &'static str {
+        self.generator.dialect_name()
+    }
+
+    /// Provenance comment for the generated SQL; see [`SqlGenerator::provenance_comment`].
+    pub fn provenance_comment(&self) -> String {
+        self.generator.provenance_comment()
+    }
+
     /// Creates a new transpiler using `DPLYR_PIPE_SYNTAX`, defaulting to `%>%`.
     pub fn from_env(dialect: Box<dyn SqlDialect>) -> Result<Self, TranspileError> {
         let pipe_syntax =
@@ -431,8 +549,413 @@ impl Transpiler {
     /// "#).unwrap();
     /// ```
     pub fn transpile(&self, dplyr_code: &str) -> Result<String, TranspileError> {
-        let ast = self.parse_dplyr(dplyr_code)?;
-        Ok(self.generate_sql(&ast)?)
+        let (sql, _) = self.transpile_with_warnings(dplyr_code)?;
+        Ok(sql)
+    }
+
+    /// Reads dplyr code from the file at `path` and converts it to SQL.
+    ///
+    /// A file that cannot be read is reported as `TranspileError::IoError`;
+    /// otherwise this behaves like [`Transpiler::transpile`] on its contents.
+    pub fn transpile_file(
+        &self,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<String, TranspileError> {
+        let path = path.as_ref();
+        let code = std::fs::read_to_string(path).map_err(|e| {
+            TranspileError::IoError(format!("Failed to read file '{}': {e}", path.display()))
+        })?;
+        self.transpile(&code)
+    }
+
+    /// Converts dplyr code to SQL and reports degraded translations.
+    ///
+    /// Behaves like [`Transpiler::transpile`], but also returns warnings for
+    /// constructs that were translated with reduced fidelity, such as window
+    /// functions without an ordering.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use libdplyr::{Transpiler, DuckDbDialect};
+    ///
+    /// let transpiler = Transpiler::new(Box::new(DuckDbDialect::new()));
+    /// let (sql, warnings) = transpiler
+    ///     .transpile_with_warnings("mutate(prev = lag(amount))")
+    ///     .unwrap();
+    /// assert!(sql.contains("LAG"));
+    /// assert_eq!(warnings[0].code(), "W-UNORDERED-WINDOW");
+    /// ```
+    pub fn transpile_with_warnings(
+        &self,
+        dplyr_code: &str,
+    ) -> Result<(String, Vec<TranspileWarning>), TranspileError> {
+        let parsed = self.parse_source(dplyr_code)?;
+        let (sql, warnings) = self.generate_sql_with_warnings(&parsed.ast)?;
+        self.finish(sql, parsed.warnings, warnings, &parsed.comments)
+    }
+
+    /// Reports whether [`Transpiler::transpile`] would succeed on `code`,
+    /// without returning the SQL.
+    ///
+    /// Parses the code and runs generation for the configured dialect, so both
+    /// syntax errors and dialect gaps such as `median()` on MySQL are caught;
+    /// in strict mode a degraded translation fails as well.
+    ///
+    /// Many generation errors only show up while the SQL is being built, so
+    /// this costs about as much as [`Transpiler::transpile`]; only comment
+    /// preservation and output formatting are skipped. When the SQL will be
+    /// needed anyway, call `transpile` once instead of checking first.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use libdplyr::{GenerationError, MySqlDialect, TranspileError, Transpiler};
+    ///
+    /// let transpiler = Transpiler::new(Box::new(MySqlDialect::new()));
+    /// assert!(transpiler.can_transpile("summarise(avg = mean(x))").is_ok());
+    /// assert!(matches!(
+    ///     transpiler.can_transpile("summarise(mid = median(x))"),
+    ///     Err(TranspileError::GenerationError(
+    ///         GenerationError::UnsupportedAggregateFunction { .. }
+    ///     ))
+    /// ));
+    /// ```
+    pub fn can_transpile(&self, code: &str) -> Result<(), TranspileError> {
+        let parsed = self.parse_source(code)?;
+        let (_, warnings) = self.generate_sql_with_warnings(&parsed.ast)?;
+        self.finish(String::new(), parsed.warnings, warnings, &[])?;
+        Ok(())
+    }
+
+    /// Lists every function and verb in `code` that the configured dialect
+    /// cannot express, so all portability issues can be shown at once instead
+    /// of only the first one [`Transpiler::can_transpile`] reports.
+    ///
+    /// Names are reported once each, in pipeline order: functions by name
+    /// (`median`) and verbs by [`DplyrOperation::operation_name`] (`rename`).
+    /// Errors unrelated to the dialect, such as an invalid argument, are not
+    /// listed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use libdplyr::{MySqlDialect, Transpiler};
+    ///
+    /// let transpiler = Transpiler::new(Box::new(MySqlDialect::new()));
+    /// assert_eq!(
+    ///     transpiler
+    ///         .unsupported_operations("rename(total = amt) %>% summarise(mid = median(total))")
+    ///         .unwrap(),
+    ///     ["rename", "median"]
+    /// );
+    /// ```
+    pub fn unsupported_operations(&self, code: &str) -> Result<Vec<String>, ParseError> {
+        let DplyrNode::Pipeline {
+            source,
+            source_function,
+            operations,
+            location,
+            ..
+        } = self.parse_source(code)?.ast
+        else {
+            return Ok(Vec::new());
+        };
+
+        let mut unsupported: Vec<String> = Vec::new();
+        let mut report = |name: String| {
+            if !unsupported.contains(&name) {
+                unsupported.push(name);
+            }
+        };
+        // Each operation is tried after the ones that generate, so a failure is
+        // its own; unsupported calls are blanked out to find any others in it
+        let mut generated = Vec::new();
+        for mut operation in operations {
+            loop {
+                let mut candidate = generated.clone();
+                candidate.push(operation.clone());
+                let pipeline = DplyrNode::Pipeline {
+                    source: source.clone(),
+                    source_function: source_function.clone(),
+                    target: None,
+                    operations: candidate,
+                    location: location.clone(),
+                };
+                match self.generator.generate(&pipeline) {
+                    Ok(_) => {
+                        generated.push(operation);
+                        break;
+                    }
+                    Err(
+                        GenerationError::UnsupportedFunction { function, .. }
+                        | GenerationError::UnsupportedAggregateFunction { function, .. },
+                    ) => {
+                        let removed = remove_function_calls(&mut operation, &function);
+                        report(function);
+                        if !removed {
+                            break;
+                        }
+                    }
+                    Err(GenerationError::UnsupportedOperation { .. }) => {
+                        report(match &operation {
+                            DplyrOperation::Unsupported { verb, .. } => verb.clone(),
+                            operation => operation.operation_name().to_string(),
+                        });
+                        break;
+                    }
+                    Err(_) => break,
+                }
+            }
+        }
+        Ok(unsupported)
+    }
+
+    /// Converts an already-lexed token stream to SQL.
+    ///
+    /// Produces the same SQL as [`Transpiler::transpile`] on the source the
+    /// tokens came from, except that `#` comments are not in the token stream
+    /// and so are never preserved. Useful for editor integrations that keep an
+    /// incremental token buffer and do not want to lex twice.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use libdplyr::lexer::{Lexer, Token};
+    /// use libdplyr::{DuckDbDialect, Transpiler};
+    ///
+    /// let code = "filter(x > 1) %>% select(x)";
+    /// let mut lexer = Lexer::new(code.to_string());
+    /// let mut tokens = Vec::new();
+    /// loop {
+    ///     let token = lexer.next_token().unwrap();
+    ///     let done = token == Token::EOF;
+    ///     tokens.push(token);
+    ///     if done {
+    ///         break;
+    ///     }
+    /// }
+    ///
+    /// let transpiler = Transpiler::new(Box::new(DuckDbDialect::new()));
+    /// assert_eq!(
+    ///     transpiler.transpile_tokens(&tokens).unwrap(),
+    ///     transpiler.transpile(code).unwrap()
+    /// );
+    /// ```
+    pub fn transpile_tokens(&self, tokens: &[Token]) -> Result<String, TranspileError> {
+        let parser = Parser::from_tokens(tokens.to_vec()).with_pipe_syntax(self.pipe_syntax);
+        let parsed = self.parse_with(parser)?;
+        let (sql, warnings) = self.generate_sql_with_warnings(&parsed.ast)?;
+        let (sql, _) = self.finish(sql, parsed.warnings, warnings, &parsed.comments)?;
+        Ok(sql)
+    }
+
+    /// Converts dplyr code to SQL and reports how the translation went.
+    ///
+    /// Returns the same SQL as [`Transpiler::transpile`] together with the
+    /// [`TranspileMetadata`] the CLI emits for JSON
+    /// output: the dialect used, the number of pipeline operations, input and
+    /// output sizes and the total processing time.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use libdplyr::{Transpiler, DuckDbDialect};
+    ///
+    /// let transpiler = Transpiler::new(Box::new(DuckDbDialect::new()));
+    /// let (sql, metadata) = transpiler
+    ///     .transpile_with_metadata("filter(x > 1) %>% select(x)")
+    ///     .unwrap();
+    /// assert_eq!(metadata.dialect, "duckdb");
+    /// assert_eq!(metadata.stats.operation_count, 2);
+    /// assert_eq!(metadata.stats.output_size_bytes, sql.len());
+    /// ```
+    #[cfg(not(target_family = "wasm"))]
+    pub fn transpile_with_metadata(
+        &self,
+        dplyr_code: &str,
+    ) -> Result<(String, TranspileMetadata), TranspileError> {
+        let start = std::time::Instant::now();
+        let parsed = self.parse_source(dplyr_code)?;
+        let operation_count = match &parsed.ast {
+            DplyrNode::Pipeline { operations, .. } => operations.len(),
+            DplyrNode::DataSource { .. } => 0,
+        };
+        let (sql, warnings) = self.generate_sql_with_warnings(&parsed.ast)?;
+        let (sql, _) = self.finish(sql, parsed.warnings, warnings, &parsed.comments)?;
+
+        let stats = ProcessingStats {
+            total_time_us: u64::try_from(start.elapsed().as_micros()).unwrap_or(u64::MAX),
+            operation_count,
+            input_size_bytes: dplyr_code.len(),
+            output_size_bytes: sql.len(),
+            ..ProcessingStats::empty()
+        };
+        let input_info = InputInfo {
+            source_type: "text".to_string(),
+            source_id: "text".to_string(),
+            size_bytes: dplyr_code.len(),
+            line_count: dplyr_code.lines().count(),
+        };
+        let metadata = MetadataBuilder::new(self.dialect_name())
+            .with_stats(stats)
+            .with_input_info(input_info)
+            .build();
+        Ok((sql, metadata))
+    }
+
+    /// Converts dplyr code to SQL reading from `source`, which may be
+    /// schema-qualified (`"schema.table"`).
+    ///
+    /// `source` replaces any data source named in the pipeline itself.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use libdplyr::{Transpiler, PostgreSqlDialect};
+    ///
+    /// let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+    /// let sql = transpiler
+    ///     .transpile_with_source("filter(id > 10)", "public.orders")
+    ///     .unwrap();
+    /// assert!(sql.contains(r#"FROM "public"."orders""#));
+    /// ```
+    pub fn transpile_with_source(
+        &self,
+        dplyr_code: &str,
+        source: &str,
+    ) -> Result<String, TranspileError> {
+        let parsed = self.parse_with_replaced_source(dplyr_code, source)?;
+        let (sql, warnings) = self.generate_sql_with_warnings(&parsed.ast)?;
+        let (sql, _) = self.finish(sql, parsed.warnings, warnings, &parsed.comments)?;
+        Ok(sql)
+    }
+
+    /// Converts dplyr code to SQL reading from `source` under the alias `alias`.
+    ///
+    /// The FROM clause becomes `FROM source AS alias` and column references are
+    /// qualified with the alias, which makes the SQL safe to embed as a subquery.
+    /// `source` replaces any data source named in the pipeline itself.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use libdplyr::{Transpiler, PostgreSqlDialect};
+    ///
+    /// let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+    /// let sql = transpiler
+    ///     .transpile_with_source_alias("filter(id > 10)", "orders", "o")
+    ///     .unwrap();
+    /// assert!(sql.contains(r#"FROM "orders" AS "o""#));
+    /// assert!(sql.contains(r#""o"."id""#));
+    /// ```
+    pub fn transpile_with_source_alias(
+        &self,
+        dplyr_code: &str,
+        source: &str,
+        alias: &str,
+    ) -> Result<String, TranspileError> {
+        let parsed = self.parse_with_replaced_source(dplyr_code, source)?;
+        let (sql, warnings) = self
+            .generator
+            .generate_with_source_alias(&parsed.ast, alias)?;
+        let (sql, _) = self.finish(sql, parsed.warnings, warnings, &parsed.comments)?;
+        Ok(sql)
+    }
+
+    /// Parses dplyr code and replaces its data source with `source`.
+    fn parse_with_replaced_source(
+        &self,
+        dplyr_code: &str,
+        source: &str,
+    ) -> Result<ParsedSource, TranspileError> {
+        if source.trim().is_empty() {
+            return Err(GenerationError::InvalidIdentifier {
+                identifier: source.to_string(),
+                reason: "source table must not be empty".to_string(),
+            }
+            .into());
+        }
+
+        let mut parsed = self.parse_source(dplyr_code)?;
+        match &mut parsed.ast {
+            DplyrNode::Pipeline {
+                source: pipeline_source,
+                source_function,
+                ..
+            } => {
+                *pipeline_source = Some(source.to_string());
+                *source_function = None;
+            }
+            DplyrNode::DataSource { name, .. } => *name = source.to_string(),
+        }
+        Ok(parsed)
+    }
+
+    /// Parses dplyr code, keeping the `#` comments skipped along the way and any
+    /// warnings the parser raised.
+    fn parse_source(&self, code: &str) -> Result<ParsedSource, ParseError> {
+        let lexer = Lexer::with_pipe_syntax(code.to_string(), self.pipe_syntax)
+            .with_keyword_aliases(self.keyword_aliases.clone());
+        self.parse_with(Parser::new(lexer)?)
+    }
+
+    /// Runs `parser` with the transpiler's parse settings, collecting its
+    /// comments and warnings.
+    fn parse_with(&self, parser: Parser) -> Result<ParsedSource, ParseError> {
+        let mut parser = parser.with_default_head_rows(self.default_head_rows);
+        let mut ast = parser.parse()?;
+        if self.fold_negations {
+            parser::fold_negations(&mut ast);
+        }
+        let warnings = if self.precedence_warnings {
+            parser
+                .precedence_ambiguities()
+                .into_iter()
+                .map(|condition| TranspileWarning::AmbiguousPrecedence { condition })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        Ok(ParsedSource {
+            ast,
+            comments: parser.comments(),
+            warnings,
+        })
+    }
+
+    /// Applies strict mode and comment preservation to generated SQL.
+    ///
+    /// Parser warnings are reported ahead of generator warnings.
+    fn finish(
+        &self,
+        sql: String,
+        parse_warnings: Vec<TranspileWarning>,
+        generation_warnings: Vec<TranspileWarning>,
+        comments: &[String],
+    ) -> Result<(String, Vec<TranspileWarning>), TranspileError> {
+        let mut warnings = parse_warnings;
+        warnings.extend(generation_warnings);
+        if self.strict {
+            if let Some(warning) = warnings.first() {
+                return Err(GenerationError::StrictModeViolation {
+                    code: warning.code().to_string(),
+                    message: warning.to_string(),
+                }
+                .into());
+            }
+        }
+
+        if self.preserve_comments {
+            let mut annotated: String = comments
+                .iter()
+                .map(|comment| format!("-- {comment}\n"))
+                .collect();
+            annotated.push_str(&sql);
+            return Ok((annotated, warnings));
+        }
+
+        Ok((sql, warnings))
     }
 
     /// Parses dplyr code to generate an Abstract Syntax Tree (AST).
@@ -441,6 +964,9 @@ impl Transpiler {
     /// the AST representation without generating SQL. Useful for inspecting
     /// the parsed structure or performing custom transformations.
     ///
+    /// The AST is the one [`Transpiler::transpile`] generates SQL from, so
+    /// normalizations such as [`TranspilerBuilder::fold_negations`] are applied.
+    ///
     /// # Arguments
     ///
     /// * `code` - The dplyr code string to parse
@@ -462,9 +988,29 @@ impl Transpiler {
     /// assert!(ast.is_pipeline());
     /// ```
     pub fn parse_dplyr(&self, code: &str) -> Result<DplyrNode, ParseError> {
-        let lexer = Lexer::with_pipe_syntax(code.to_string(), self.pipe_syntax);
-        let mut parser = Parser::new(lexer)?;
-        parser.parse()
+        Ok(self.parse_source(code)?.ast)
+    }
+
+    /// Lists the source columns a pipeline reads, without generating SQL.
+    ///
+    /// Columns the pipeline creates itself, such as mutate() targets and
+    /// summarise() aliases, are not included once they are defined.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use libdplyr::{Transpiler, PostgreSqlDialect};
+    ///
+    /// let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+    /// let columns = transpiler
+    ///     .referenced_columns("mutate(total = price * qty) %>% filter(total > 10)")
+    ///     .unwrap();
+    /// assert!(columns.contains("price") && columns.contains("qty"));
+    /// assert!(!columns.contains("total"));
+    /// ```
+    pub fn referenced_columns(&self, code: &str) -> Result<HashSet<String>, TranspileError> {
+        let ast = self.parse_dplyr(code)?;
+        Ok(parser::referenced_columns(&ast))
     }
 
     /// Converts an AST to SQL using the configured dialect.
@@ -499,6 +1045,232 @@ impl Transpiler {
     pub fn generate_sql(&self, ast: &DplyrNode) -> Result<String, GenerationError> {
         self.generator.generate(ast)
     }
+
+    /// Converts an AST to SQL, returning any warnings raised during generation.
+    pub fn generate_sql_with_warnings(
+        &self,
+        ast: &DplyrNode,
+    ) -> Result<(String, Vec<TranspileWarning>), GenerationError> {
+        self.generator.generate_with_warnings(ast)
+    }
+}
+
+/// Replaces calls to `function` in `operation` with `NULL`, and drops
+/// summarise() columns that aggregate with it. Returns whether anything changed.
+#[cfg(feature = "std")]
+fn remove_function_calls(operation: &mut DplyrOperation, function: &str) -> bool {
+    fn remove_from(expr: &mut Expr, function: &str) -> bool {
+        match expr {
+            Expr::Function { name, .. } if name == function => {
+                *expr = Expr::Literal(LiteralValue::Null);
+                true
+            }
+            Expr::Function { args, .. } => args
+                .iter_mut()
+                .fold(false, |removed, arg| remove_from(arg, function) | removed),
+            Expr::Binary { left, right, .. }
+            | Expr::Formula {
+                lhs: left,
+                rhs: right,
+            } => remove_from(left, function) | remove_from(right, function),
+            Expr::NamedArg { value, .. } | Expr::Not(value) | Expr::Negate(value) => {
+                remove_from(value, function)
+            }
+            Expr::Identifier(_) | Expr::QualifiedIdentifier { .. } | Expr::Literal(_) => false,
+        }
+    }
+
+    match operation {
+        DplyrOperation::Select { columns, .. } => {
+            columns.iter_mut().fold(false, |removed, column| {
+                remove_from(&mut column.expr, function) | removed
+            })
+        }
+        DplyrOperation::Filter { condition, .. } => remove_from(condition, function),
+        DplyrOperation::Mutate { assignments, .. } => {
+            assignments.iter_mut().fold(false, |removed, assignment| {
+                remove_from(&mut assignment.expr, function) | removed
+            })
+        }
+        DplyrOperation::Summarise { aggregations, .. } => {
+            let count = aggregations.len();
+            aggregations.retain(|aggregation| aggregation.function != function);
+            aggregations.len() != count
+        }
+        DplyrOperation::Join { spec, .. } => spec
+            .on_expr
+            .as_mut()
+            .is_some_and(|on_expr| remove_from(on_expr, function)),
+        _ => false,
+    }
+}
+
+/// Output of the parsing phase of a transpilation.
+#[cfg(feature = "std")]
+struct ParsedSource {
+    ast: DplyrNode,
+    comments: Vec<String>,
+    warnings: Vec<TranspileWarning>,
+}
+
+/// Builder for a [`Transpiler`] with non-default options
+///
+/// Defaults match [`Transpiler::new`] with the PostgreSQL dialect: `%>%` pipes,
+/// warnings reported but not fatal, `#` comments dropped from the output,
+/// unnamed summarise() columns aliased, no precedence warnings, six rows for an
+/// argument-less `head()`, ordinary (NULL-propagating) `==`, negations kept
+/// as written, and no ORDER BY tiebreaker.
+#[cfg(feature = "std")]
+pub struct TranspilerBuilder {
+    dialect: Box<dyn SqlDialect>,
+    pipe_syntax: PipeSyntax,
+    strict: bool,
+    preserve_comments: bool,
+    auto_aggregate_aliases: bool,
+    max_output_length: Option<usize>,
+    precedence_warnings: bool,
+    default_head_rows: usize,
+    null_safe_equality: bool,
+    boolean_is_comparison: bool,
+    fold_negations: bool,
+    order_tiebreaker: Option<String>,
+    keyword_aliases: HashMap<String, Token>,
+}
+
+#[cfg(feature = "std")]
+impl Default for TranspilerBuilder {
+    fn default() -> Self {
+        Self {
+            dialect: Box::new(PostgreSqlDialect::new()),
+            pipe_syntax: PipeSyntax::default(),
+            strict: false,
+            preserve_comments: false,
+            auto_aggregate_aliases: true,
+            max_output_length: None,
+            precedence_warnings: false,
+            default_head_rows: DEFAULT_HEAD_ROWS,
+            null_safe_equality: false,
+            boolean_is_comparison: false,
+            fold_negations: false,
+            order_tiebreaker: None,
+            keyword_aliases: HashMap::new(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl TranspilerBuilder {
+    /// Sets the SQL dialect used for generation.
+    pub fn dialect(mut self, dialect: Box<dyn SqlDialect>) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
+    /// Sets the pipe operator the lexer recognizes.
+    pub const fn pipe_syntax(mut self, pipe_syntax: PipeSyntax) -> Self {
+        self.pipe_syntax = pipe_syntax;
+        self
+    }
+
+    /// Turns every [`TranspileWarning`] into a [`GenerationError::StrictModeViolation`].
+    pub const fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Emits `#` comments from the dplyr source as `--` lines ahead of the SQL.
+    pub const fn preserve_comments(mut self, preserve_comments: bool) -> Self {
+        self.preserve_comments = preserve_comments;
+        self
+    }
+
+    /// Names unnamed summarise() columns `function_column` (`mean_salary`), as dplyr
+    /// would; pass `false` to leave them unaliased.
+    pub const fn auto_aggregate_aliases(mut self, enabled: bool) -> Self {
+        self.auto_aggregate_aliases = enabled;
+        self
+    }
+
+    /// Fails with [`GenerationError::OutputTooLarge`] as soon as the SQL being
+    /// generated grows past `max` bytes.
+    pub const fn max_output_length(mut self, max: usize) -> Self {
+        self.max_output_length = Some(max);
+        self
+    }
+
+    /// Reports [`TranspileWarning::AmbiguousPrecedence`] for conditions such as
+    /// `a & b | c` that mix `&` and `|` without parentheses.
+    pub const fn precedence_warnings(mut self, enabled: bool) -> Self {
+        self.precedence_warnings = enabled;
+        self
+    }
+
+    /// Sets how many rows an argument-less `head()` / `tail()` keeps (default 6).
+    pub const fn default_head_rows(mut self, rows: usize) -> Self {
+        self.default_head_rows = rows;
+        self
+    }
+
+    /// Translates `==` / `!=` into NULL-safe comparisons (`<=>` on MySQL,
+    /// `IS NOT DISTINCT FROM` elsewhere), so NULLs compare equal to each other.
+    pub const fn null_safe_equality(mut self, enabled: bool) -> Self {
+        self.null_safe_equality = enabled;
+        self
+    }
+
+    /// Renders `x == TRUE` as `x IS TRUE` (and `!=` / `FALSE` likewise) on
+    /// dialects that have the predicate; others keep `= TRUE`.
+    pub const fn boolean_is_comparison(mut self, enabled: bool) -> Self {
+        self.boolean_is_comparison = enabled;
+        self
+    }
+
+    /// Folds redundant negations before generation: `!!x` and `!(!x)` become
+    /// `x`, and `!(a == b)` becomes `a != b` (see [`parser::fold_negations`]).
+    pub const fn fold_negations(mut self, enabled: bool) -> Self {
+        self.fold_negations = enabled;
+        self
+    }
+
+    /// Appends `column`, typically the source table's primary key, as the final
+    /// ORDER BY key of arrange() so ties sort deterministically (see
+    /// [`SqlGenerator::with_order_tiebreaker`]).
+    pub fn order_tiebreaker(mut self, column: impl Into<String>) -> Self {
+        self.order_tiebreaker = Some(column.into());
+        self
+    }
+
+    /// Lexes `alias` as the keyword `token`, e.g. a localized verb name mapped to
+    /// [`Token::Summarise`]; built-in keywords (see [`lexer::keywords`]) cannot be
+    /// redefined.
+    pub fn keyword_alias(mut self, alias: impl Into<String>, token: Token) -> Self {
+        self.keyword_aliases.insert(alias.into(), token);
+        self
+    }
+
+    /// Builds the configured transpiler.
+    pub fn build(self) -> Transpiler {
+        let mut generator = SqlGenerator::new(self.dialect)
+            .with_auto_aggregate_aliases(self.auto_aggregate_aliases)
+            .with_null_safe_equality(self.null_safe_equality)
+            .with_boolean_is_comparison(self.boolean_is_comparison);
+        if let Some(max) = self.max_output_length {
+            generator = generator.with_max_output_length(max);
+        }
+        if let Some(column) = self.order_tiebreaker {
+            generator = generator.with_order_tiebreaker(column);
+        }
+        Transpiler {
+            generator,
+            pipe_syntax: self.pipe_syntax,
+            strict: self.strict,
+            preserve_comments: self.preserve_comments,
+            precedence_warnings: self.precedence_warnings,
+            default_head_rows: self.default_head_rows,
+            fold_negations: self.fold_negations,
+            keyword_aliases: self.keyword_aliases,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -827,61 +1599,2324 @@ mod tests {
     }
 
     #[test]
-    fn test_transpile_with_group_by_and_summarise() {
-        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
-        let dplyr_code = "group_by(department) %>% summarise(avg_salary = mean(salary))";
+    fn test_known_unsupported_verbs_report_unsupported_operation() {
+        let transpiler = Transpiler::new(Box::new(DuckDbDialect::new()));
 
-        let result = transpiler.transpile(dplyr_code);
-        assert!(result.is_ok(), "변환이 성공해야 합니다: {result:?}");
+        for (code, verb) in [
+            (
+                "data %>% pivot_longer(cols = c(a, b), names_to = \"key\")",
+                "pivot_longer",
+            ),
+            (
+                "data %>% separate(name, into = c(\"first\", \"last\"), sep = \" \")",
+                "separate",
+            ),
+            ("unite(data, full, first, last) %>% select(full)", "unite"),
+        ] {
+            match transpiler.transpile(code) {
+                Err(TranspileError::GenerationError(GenerationError::UnsupportedOperation {
+                    operation,
+                    ..
+                })) => assert_eq!(operation, format!("{verb}() is not supported yet")),
+                other => panic!("expected UnsupportedOperation for {verb}, got {other:?}"),
+            }
+        }
+    }
 
-        let sql = result.unwrap();
-        assert!(sql.contains("SELECT"));
-        assert!(sql.contains("GROUP BY"));
-        assert!(sql.contains("AVG"));
+    #[test]
+    fn test_filter_comma_separated_conditions_are_and_combined() {
+        let transpiler = Transpiler::new(Box::new(DuckDbDialect::new()));
+
+        let commas = transpiler
+            .transpile("data %>% filter(a > 1, b < 2)")
+            .unwrap();
+        let ampersand = transpiler
+            .transpile("data %>% filter(a > 1 & b < 2)")
+            .unwrap();
+
+        assert_eq!(commas, ampersand);
+        assert_eq!(
+            commas,
+            "SELECT *\nFROM \"data\"\nWHERE ((\"a\" > 1) AND (\"b\" < 2))"
+        );
     }
 
     #[test]
-    fn test_parse_dplyr_individual_function() {
+    fn test_unnamed_summarise_columns_get_default_alias() {
         let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
-        let dplyr_code = "select(name, age)";
+        assert_eq!(
+            transpiler
+                .transpile("data %>% summarise(mean(salary), total = sum(salary), n())")
+                .unwrap(),
+            "SELECT AVG(\"salary\") AS \"mean_salary\", SUM(\"salary\") AS \"total\", COUNT(*) AS \"n\"\nFROM \"data\""
+        );
 
-        let result = transpiler.parse_dplyr(dplyr_code);
-        assert!(result.is_ok(), "Parsing should succeed: {result:?}");
+        let unaliased = Transpiler::builder()
+            .auto_aggregate_aliases(false)
+            .build()
+            .transpile("data %>% summarise(mean(salary))")
+            .unwrap();
+        assert_eq!(unaliased, "SELECT AVG(\"salary\")\nFROM \"data\"");
+    }
 
-        let ast = result.unwrap();
-        assert!(ast.is_pipeline());
+    #[test]
+    fn test_max_output_length_stops_generation_early() {
+        let code = (0..50)
+            .map(|i| format!("mutate(col_{i} = price * {i} + tax / {i})"))
+            .collect::<Vec<_>>()
+            .join(" %>% ");
+        let code = format!("data %>% {code}");
+
+        let unlimited = Transpiler::new(Box::new(DuckDbDialect::new()));
+        assert!(unlimited.transpile(&code).unwrap().len() > 1000);
+
+        let limited = Transpiler::builder()
+            .dialect(Box::new(DuckDbDialect::new()))
+            .max_output_length(1000)
+            .build();
+        match limited.transpile(&code) {
+            Err(TranspileError::GenerationError(GenerationError::OutputTooLarge {
+                length,
+                max,
+            })) => {
+                assert_eq!(max, 1000);
+                // Aborted after the operation that crossed the limit, not at the end
+                assert!(length < 1100, "{length}");
+            }
+            other => panic!("expected OutputTooLarge, got {other:?}"),
+        }
     }
 
     #[test]
-    fn test_generate_sql_from_ast() {
-        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
-        let dplyr_code = "select(name, age)";
+    fn test_fill_uses_ignore_nulls_window_on_duckdb_and_postgresql() {
+        let code = "data %>% arrange(day) %>% fill(price, .direction = \"down\")";
+        let duckdb = Transpiler::new(Box::new(DuckDbDialect::new()));
+        assert_eq!(
+            duckdb.transpile(code).unwrap(),
+            "SELECT * EXCLUDE (\"price\"), LAST_VALUE(\"price\" IGNORE NULLS) OVER (ORDER BY \"day\" ASC ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW) AS \"price\"\nFROM \"data\"\nORDER BY \"day\" ASC"
+        );
 
-        // First parse to get AST
-        let ast = transpiler.parse_dplyr(dplyr_code).unwrap();
+        // Without * EXCLUDE the filled column must be listed to be replaced
+        let postgres = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        assert!(matches!(
+            postgres.transpile(code),
+            Err(TranspileError::GenerationError(
+                GenerationError::UnsupportedOperation { .. }
+            ))
+        ));
+        assert_eq!(
+            postgres
+                .transpile("data %>% select(day, price) %>% arrange(day) %>% fill(price)")
+                .unwrap(),
+            "SELECT \"day\", LAST_VALUE(\"price\" IGNORE NULLS) OVER (ORDER BY \"day\" ASC ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW) AS \"price\"\nFROM \"data\"\nORDER BY \"day\" ASC"
+        );
 
-        // Then generate SQL from AST
-        let result = transpiler.generate_sql(&ast);
-        assert!(result.is_ok(), "SQL generation should succeed: {result:?}");
+        assert_eq!(
+            duckdb
+                .transpile("data %>% group_by(g) %>% arrange(day) %>% fill(a, .direction = \"up\")")
+                .unwrap(),
+            "SELECT * EXCLUDE (\"a\"), FIRST_VALUE(\"a\" IGNORE NULLS) OVER (PARTITION BY \"g\" ORDER BY \"day\" ASC ROWS BETWEEN CURRENT ROW AND UNBOUNDED FOLLOWING) AS \"a\"\nFROM \"data\"\nORDER BY \"day\" ASC"
+        );
+    }
 
-        let sql = result.unwrap();
-        assert!(sql.contains("SELECT"));
-        assert!(sql.contains("\"name\""));
-        assert!(sql.contains("\"age\""));
+    #[test]
+    fn test_fill_is_unsupported_without_ignore_nulls() {
+        for dialect in [
+            Box::new(MySqlDialect::new()) as Box<dyn SqlDialect>,
+            Box::new(SqliteDialect::new()),
+        ] {
+            let transpiler = Transpiler::new(dialect);
+            assert!(matches!(
+                transpiler.transpile("data %>% arrange(day) %>% fill(price)"),
+                Err(TranspileError::GenerationError(
+                    GenerationError::UnsupportedOperation { .. }
+                ))
+            ));
+        }
     }
 
     #[test]
-    fn test_transpile_error_handling() {
+    fn test_ungrouped_slice_head_is_a_limit() {
+        let transpiler = Transpiler::new(Box::new(DuckDbDialect::new()));
+
+        assert_eq!(
+            transpiler
+                .transpile("data %>% arrange(x) %>% slice_head(n = 3)")
+                .unwrap(),
+            "SELECT *\nFROM \"data\"\nORDER BY \"x\" ASC\nLIMIT 3"
+        );
+    }
+
+    #[test]
+    fn test_verbs_after_head_read_the_limited_rows() {
         let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
 
-        // Test invalid syntax
-        let result = transpiler.transpile("invalid_function(test)");
-        assert!(result.is_err(), "Invalid syntax should return an error");
+        assert_eq!(
+            transpiler
+                .transpile("data %>% head(3) %>% filter(x > 1)")
+                .unwrap(),
+            "SELECT *\nFROM (\nSELECT *\nFROM \"data\"\nLIMIT 3\n) AS subquery\nWHERE (\"x\" > 1)"
+        );
+        assert_eq!(
+            transpiler
+                .transpile("data %>% head(3) %>% arrange(x)")
+                .unwrap(),
+            "SELECT *\nFROM (\nSELECT *\nFROM \"data\"\nLIMIT 3\n) AS subquery\nORDER BY \"x\" ASC"
+        );
+        assert_eq!(
+            transpiler
+                .transpile("data %>% head(3) %>% summarise(s = sum(x))")
+                .unwrap(),
+            "SELECT SUM(\"x\") AS \"s\"\nFROM (\nSELECT *\nFROM \"data\"\nLIMIT 3\n) AS subquery"
+        );
+        let oracle = Transpiler::new(Box::new(OracleDialect::new()));
+        assert!(oracle
+            .transpile("data %>% head(3) %>% filter(x > 1)")
+            .unwrap()
+            .contains("FETCH FIRST 3 ROWS ONLY\n) subquery\nWHERE"));
 
-        match result.unwrap_err() {
-            TranspileError::ParseError(_) => {} // Expected error type
-            other => panic!("Unexpected error type: {other:?}"),
-        }
+        // Projections do not change which rows are kept
+        assert_eq!(
+            transpiler
+                .transpile("data %>% head(3) %>% select(x)")
+                .unwrap(),
+            "SELECT \"x\"\nFROM \"data\"\nLIMIT 3"
+        );
+    }
+
+    #[test]
+    fn test_grouped_slice_head_filters_on_row_number() {
+        let duckdb = Transpiler::new(Box::new(DuckDbDialect::new()));
+        assert_eq!(
+            duckdb
+                .transpile("data %>% group_by(g) %>% arrange(desc(x)) %>% slice_head(n = 2)")
+                .unwrap(),
+            "SELECT * EXCLUDE (\"dplyr_row_number\")\nFROM (\nSELECT *, ROW_NUMBER() OVER (PARTITION BY \"g\" ORDER BY \"x\" DESC) AS \"dplyr_row_number\"\nFROM \"data\"\n) AS subquery\nWHERE \"dplyr_row_number\" <= 2\nORDER BY \"x\" DESC"
+        );
+
+        // Without * EXCLUDE the helper columns are dropped by listing the selected columns
+        let sqlite = Transpiler::new(Box::new(SqliteDialect::new()));
+        assert_eq!(
+            sqlite
+                .transpile("data %>% select(x, y) %>% arrange(x) %>% slice_tail(n = 1)")
+                .unwrap(),
+            "SELECT \"x\", \"y\"\nFROM (\nSELECT \"x\", \"y\", ROW_NUMBER() OVER (ORDER BY \"x\" ASC) AS \"dplyr_row_number\", COUNT(*) OVER () AS \"dplyr_group_size\"\nFROM \"data\"\n) AS subquery\nWHERE \"dplyr_row_number\" > \"dplyr_group_size\" - 1\nORDER BY \"x\" ASC"
+        );
+
+        // Other dialects keep the helper columns of unknown columns, with a warning
+        let dialects: Vec<Box<dyn SqlDialect>> = vec![
+            Box::new(PostgreSqlDialect::new()),
+            Box::new(MySqlDialect::new()),
+            Box::new(SqliteDialect::new()),
+        ];
+        for dialect in dialects {
+            let name = dialect.dialect_name();
+            let row_number = dialect.quote_identifier("dplyr_row_number");
+            let transpiler = Transpiler::new(dialect);
+
+            let (sql, warnings) = transpiler
+                .transpile_with_warnings(
+                    "data %>% group_by(g) %>% arrange(x) %>% slice_head(n = 1)",
+                )
+                .unwrap();
+            assert!(
+                sql.starts_with("SELECT *\nFROM (\nSELECT *, ROW_NUMBER()"),
+                "{sql}"
+            );
+            assert!(sql.contains(&format!("WHERE {row_number} <= 1")), "{sql}");
+            assert_eq!(
+                warnings,
+                vec![TranspileWarning::HelperColumnsKept {
+                    operation: "slice_head".to_string(),
+                    columns: vec!["dplyr_row_number".to_string()],
+                    dialect: name.to_string(),
+                }]
+            );
+
+            let (_, warnings) = transpiler
+                .transpile_with_warnings("data %>% arrange(x) %>% slice_tail(n = 1)")
+                .unwrap();
+            assert_eq!(warnings[0].code(), "W-HELPER-COLUMNS", "{name}");
+
+            // A select() names the columns, so the helpers are dropped
+            let (sql, warnings) = transpiler
+                .transpile_with_warnings(
+                    "data %>% select(g, x) %>% group_by(g) %>% arrange(x) %>% slice_head(n = 1)",
+                )
+                .unwrap();
+            assert!(!sql.starts_with("SELECT *"), "{sql}");
+            assert!(warnings.is_empty(), "{name}: {warnings:?}");
+        }
+    }
+
+    #[test]
+    fn test_dollar_column_access() {
+        let transpiler = Transpiler::new(Box::new(DuckDbDialect::new()));
+
+        assert_eq!(
+            transpiler
+                .transpile("data %>% filter(.data$age > 18)")
+                .unwrap(),
+            "SELECT *\nFROM \"data\"\nWHERE (\"age\" > 18)"
+        );
+        assert_eq!(
+            transpiler
+                .transpile("data %>% mutate(total = df$price * 2)")
+                .unwrap(),
+            "SELECT *, (\"df\".\"price\" * 2) AS \"total\"\nFROM \"data\""
+        );
+    }
+
+    #[test]
+    fn test_data_pronoun_in_select() {
+        let transpiler = Transpiler::new(Box::new(DuckDbDialect::new()));
+
+        assert_eq!(
+            transpiler
+                .transpile("data %>% select(.data$name, years = .data$age)")
+                .unwrap(),
+            "SELECT \"name\", \"age\" AS \"years\"\nFROM \"data\""
+        );
+        assert_eq!(
+            transpiler.transpile("data %>% select(df$name)").unwrap(),
+            "SELECT \"df\".\"name\"\nFROM \"data\""
+        );
+    }
+
+    #[test]
+    fn test_data_pronoun_in_arrange() {
+        let transpiler = Transpiler::new(Box::new(DuckDbDialect::new()));
+
+        assert_eq!(
+            transpiler
+                .transpile("data %>% arrange(.data$x, desc(.data$y))")
+                .unwrap(),
+            "SELECT *\nFROM \"data\"\nORDER BY \"x\" ASC, \"y\" DESC"
+        );
+    }
+
+    #[test]
+    fn test_data_pronoun_in_group_by() {
+        let transpiler = Transpiler::new(Box::new(DuckDbDialect::new()));
+
+        assert_eq!(
+            transpiler
+                .transpile("data %>% group_by(.data$g, .add = TRUE) %>% summarise(n = n())")
+                .unwrap(),
+            "SELECT \"g\", COUNT(*) AS \"n\"\nFROM \"data\"\nGROUP BY \"g\""
+        );
+    }
+
+    #[test]
+    fn test_n_with_column_argument_is_rejected() {
+        let transpiler = Transpiler::new(Box::new(DuckDbDialect::new()));
+
+        for code in ["data %>% summarise(c = n(x))", "data %>% summarise(n(x))"] {
+            let error = transpiler.transpile(code).unwrap_err();
+            assert!(
+                matches!(
+                    &error,
+                    TranspileError::ParseError(ParseError::InvalidOperation { operation, .. })
+                        if operation.contains("n() takes no arguments")
+                ),
+                "{error}"
+            );
+        }
+        assert!(transpiler.transpile("data %>% summarise(c = n())").is_ok());
+    }
+
+    #[test]
+    fn test_filter_on_mutated_column_inlines_expression() {
+        let transpiler = Transpiler::new(Box::new(DuckDbDialect::new()));
+
+        assert_eq!(
+            transpiler
+                .transpile("data %>% mutate(ratio = a / b) %>% filter(ratio > 1)")
+                .unwrap(),
+            "SELECT *, (\"a\" / \"b\") AS \"ratio\"\nFROM \"data\"\nWHERE ((\"a\" / \"b\") > 1)"
+        );
+    }
+
+    #[test]
+    fn test_filter_on_window_column_wraps_subquery() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+
+        assert_eq!(
+            transpiler
+                .transpile("data %>% mutate(r = row_number()) %>% filter(r <= 2)")
+                .unwrap(),
+            "SELECT *\nFROM (\nSELECT *, ROW_NUMBER() OVER () AS \"r\"\nFROM \"data\"\n) AS subquery\nWHERE (\"r\" <= 2)"
+        );
+    }
+
+    #[test]
+    fn test_group_by_add_appends_to_existing_grouping() {
+        let transpiler = Transpiler::new(Box::new(DuckDbDialect::new()));
+
+        assert_eq!(
+            transpiler
+                .transpile(
+                    "data %>% group_by(a) %>% group_by(b, .add = TRUE) %>% summarise(n = n())"
+                )
+                .unwrap(),
+            "SELECT \"a\", \"b\", COUNT(*) AS \"n\"\nFROM \"data\"\nGROUP BY \"a\", \"b\""
+        );
+        // Without .add the later group_by() replaces the grouping
+        assert_eq!(
+            transpiler
+                .transpile("data %>% group_by(a) %>% group_by(b) %>% summarise(n = n())")
+                .unwrap(),
+            "SELECT \"b\", COUNT(*) AS \"n\"\nFROM \"data\"\nGROUP BY \"b\""
+        );
+    }
+
+    #[test]
+    fn test_grouped_summarise_selects_group_keys_first() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+
+        let sql = transpiler
+            .transpile("data %>% group_by(dept) %>% summarise(avg = mean(x))")
+            .unwrap();
+        assert_eq!(
+            sql,
+            "SELECT \"dept\", AVG(\"x\") AS \"avg\"\nFROM \"data\"\nGROUP BY \"dept\""
+        );
+
+        let sql = transpiler
+            .transpile("data %>% group_by(dept, region) %>% summarise(n = n())")
+            .unwrap();
+        assert!(
+            sql.starts_with("SELECT \"dept\", \"region\", COUNT(*) AS \"n\""),
+            "{sql}"
+        );
+    }
+
+    #[test]
+    fn test_unary_minus_negates_the_power() {
+        let mysql = Transpiler::new(Box::new(MySqlDialect::new()));
+        assert_eq!(
+            mysql.transpile("mutate(a = -2^2, b = -x^2)").unwrap(),
+            "SELECT *, (-POWER(2, 2)) AS `a`, (-POWER(`x`, 2)) AS `b`\nFROM `data`"
+        );
+
+        let postgres = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        assert_eq!(
+            postgres.transpile("mutate(a = -2^2, b = -x^2)").unwrap(),
+            "SELECT *, (-(2 ^ 2)) AS \"a\", (-(\"x\" ^ 2)) AS \"b\"\nFROM \"data\""
+        );
+    }
+
+    #[test]
+    fn test_caret_operator_generates_power() {
+        let code = "mutate(area = r^2, tower = 2 * x ^ y ^ 2)";
+
+        let sql = Transpiler::new(Box::new(DuckDbDialect::new()))
+            .transpile(code)
+            .unwrap();
+        assert_eq!(
+            sql,
+            "SELECT *, POWER(\"r\", 2) AS \"area\", (2 * POWER(\"x\", POWER(\"y\", 2))) AS \"tower\"\nFROM \"data\""
+        );
+
+        let sql = Transpiler::new(Box::new(PostgreSqlDialect::new()))
+            .transpile(code)
+            .unwrap();
+        assert_eq!(
+            sql,
+            "SELECT *, (\"r\" ^ 2) AS \"area\", (2 * (\"x\" ^ (\"y\" ^ 2))) AS \"tower\"\nFROM \"data\""
+        );
+
+        assert!(matches!(
+            Transpiler::new(Box::new(SqliteDialect::new())).transpile(code),
+            Err(TranspileError::GenerationError(
+                GenerationError::UnsupportedFunction { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_slice_range_becomes_limit_offset() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+
+        assert_eq!(
+            transpiler.transpile("slice(2:4)").unwrap(),
+            "SELECT *\nFROM \"data\"\nLIMIT 3 OFFSET 1"
+        );
+        assert_eq!(
+            transpiler.transpile("slice(1:3)").unwrap(),
+            "SELECT *\nFROM \"data\"\nLIMIT 3"
+        );
+        // Later slices narrow the rows kept by earlier ones
+        assert_eq!(
+            transpiler
+                .transpile("slice_head(n = 3) %>% slice(2:5)")
+                .unwrap(),
+            "SELECT *\nFROM \"data\"\nLIMIT 2 OFFSET 1"
+        );
+        // Later verbs filter and order only the sliced rows
+        assert_eq!(
+            transpiler
+                .transpile("slice(2:4) %>% filter(x > 1)")
+                .unwrap(),
+            "SELECT *\nFROM (\nSELECT *\nFROM \"data\"\nLIMIT 3 OFFSET 1\n) AS subquery\nWHERE (\"x\" > 1)"
+        );
+        assert_eq!(
+            transpiler.transpile("slice(2:4) %>% arrange(x)").unwrap(),
+            "SELECT *\nFROM (\nSELECT *\nFROM \"data\"\nLIMIT 3 OFFSET 1\n) AS subquery\nORDER BY \"x\" ASC"
+        );
+    }
+
+    #[test]
+    fn test_grouped_slice_range_filters_row_number() {
+        let transpiler = Transpiler::new(Box::new(DuckDbDialect::new()));
+
+        let sql = transpiler
+            .transpile("group_by(g) %>% arrange(x) %>% slice(2:4)")
+            .unwrap();
+
+        assert!(
+            sql.contains("ROW_NUMBER() OVER (PARTITION BY \"g\" ORDER BY \"x\" ASC)"),
+            "{sql}"
+        );
+        assert!(
+            sql.contains("WHERE \"dplyr_row_number\" BETWEEN 2 AND 4"),
+            "{sql}"
+        );
+    }
+
+    #[test]
+    fn test_referenced_columns_for_multi_stage_pipeline() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+
+        let columns = transpiler
+            .referenced_columns(
+                "orders %>% filter(status == \"paid\") %>% mutate(net = amount - discount) \
+                 %>% group_by(region) %>% summarise(total = sum(net), n = n()) %>% arrange(desc(total))",
+            )
+            .unwrap();
+
+        let expected: HashSet<String> = ["status", "amount", "discount", "region"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        assert_eq!(columns, expected);
+        assert!(matches!(
+            transpiler.referenced_columns("filter(x >"),
+            Err(TranspileError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_head_and_tail_default_to_six_rows() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+
+        assert_eq!(
+            transpiler.transpile("df %>% select(a) %>% head()").unwrap(),
+            "SELECT \"a\"\nFROM \"df\"\nLIMIT 6"
+        );
+        assert_eq!(
+            transpiler.transpile("head(10)").unwrap(),
+            "SELECT *\nFROM \"data\"\nLIMIT 10"
+        );
+        // The default limit is applied before a later filter, as in R
+        assert_eq!(
+            transpiler.transpile("head() %>% filter(x > 1)").unwrap(),
+            "SELECT *\nFROM (\nSELECT *\nFROM \"data\"\nLIMIT 6\n) AS subquery\nWHERE (\"x\" > 1)"
+        );
+        // slice_head() keeps dplyr's default of one row
+        assert_eq!(
+            transpiler.transpile("slice_head()").unwrap(),
+            "SELECT *\nFROM \"data\"\nLIMIT 1"
+        );
+        assert!(transpiler
+            .transpile("select(x) %>% arrange(x) %>% tail()")
+            .unwrap()
+            .contains("\"dplyr_row_number\" > \"dplyr_group_size\" - 6"));
+
+        let configured = Transpiler::builder().default_head_rows(20).build();
+        assert!(configured
+            .transpile("head()")
+            .unwrap()
+            .ends_with("LIMIT 20"));
+        // Still usable as column names
+        assert!(transpiler
+            .transpile("select(head, tail)")
+            .unwrap()
+            .starts_with("SELECT \"head\", \"tail\""));
+    }
+
+    #[test]
+    fn test_builder_null_safe_equality() {
+        let transpiler = Transpiler::builder()
+            .dialect(Box::new(MySqlDialect::new()))
+            .null_safe_equality(true)
+            .build();
+
+        assert_eq!(
+            transpiler.transpile("filter(a == b)").unwrap(),
+            "SELECT *\nFROM `data`\nWHERE (`a` <=> `b`)"
+        );
+        // Comparing with NA still becomes IS NULL
+        assert!(transpiler
+            .transpile("filter(a == NA)")
+            .unwrap()
+            .contains("(`a` IS NULL)"));
+    }
+
+    #[test]
+    fn test_builder_fold_negations() {
+        let code = "filter(!!active & !(status == \"closed\"))";
+        assert_eq!(
+            Transpiler::builder()
+                .fold_negations(true)
+                .build()
+                .transpile(code)
+                .unwrap(),
+            "SELECT *\nFROM \"data\"\nWHERE (\"active\" AND (\"status\" != 'closed'))"
+        );
+        // Off by default: negations are generated as written
+        assert_eq!(
+            Transpiler::builder().build().transpile(code).unwrap(),
+            "SELECT *\nFROM \"data\"\nWHERE ((NOT (NOT \"active\")) AND (NOT (\"status\" = 'closed')))"
+        );
+
+        // parse_dplyr() returns the folded AST that transpile() generates from
+        let folding = Transpiler::builder().fold_negations(true).build();
+        let DplyrNode::Pipeline { operations, .. } =
+            folding.parse_dplyr("filter(!!active)").unwrap()
+        else {
+            panic!("Expected Pipeline node");
+        };
+        assert!(matches!(
+            &operations[0],
+            DplyrOperation::Filter { condition: Expr::Identifier(name), .. } if name == "active"
+        ));
+    }
+
+    #[test]
+    fn test_builder_order_tiebreaker() {
+        let transpiler = Transpiler::builder().order_tiebreaker("id").build();
+        assert_eq!(
+            transpiler
+                .transpile_with_source("arrange(desc(score)) %>% head(3)", "public.results")
+                .unwrap(),
+            "SELECT *\nFROM \"public\".\"results\"\nORDER BY \"score\" DESC, \"id\" ASC\nLIMIT 3"
+        );
+        // Not repeated when arrange() already sorts by it
+        assert!(transpiler
+            .transpile("arrange(score, desc(id))")
+            .unwrap()
+            .ends_with("ORDER BY \"score\" ASC, \"id\" DESC"));
+        // Nothing to break ties for without arrange(), and no such column after summarise()
+        for code in [
+            "filter(score > 1)",
+            "group_by(team) %>% summarise(best = max(score)) %>% arrange(best)",
+        ] {
+            assert!(!transpiler.transpile(code).unwrap().contains("\"id\""));
+        }
+        // Off by default
+        assert!(!Transpiler::builder()
+            .build()
+            .transpile("arrange(score)")
+            .unwrap()
+            .contains("\"id\""));
+    }
+
+    #[test]
+    fn test_builder_boolean_is_comparison() {
+        let cases: Vec<(Box<dyn SqlDialect>, &str)> = vec![
+            (
+                Box::new(PostgreSqlDialect::new()),
+                r#"WHERE ("active" IS TRUE)"#,
+            ),
+            (Box::new(MySqlDialect::new()), "WHERE (`active` IS TRUE)"),
+            (
+                Box::new(DuckDbDialect::new()),
+                r#"WHERE ("active" IS TRUE)"#,
+            ),
+            (Box::new(SqliteDialect::new()), r#"WHERE ("active" = TRUE)"#),
+            (Box::new(OracleDialect::new()), r#"WHERE ("ACTIVE" = 1)"#),
+            (Box::new(SparkSqlDialect::new()), "WHERE (`active` = TRUE)"),
+        ];
+
+        for (dialect, expected) in cases {
+            let transpiler = Transpiler::builder()
+                .dialect(dialect)
+                .boolean_is_comparison(true)
+                .build();
+            let sql = transpiler.transpile("filter(active == TRUE)").unwrap();
+            assert!(sql.ends_with(expected), "{sql}");
+        }
+
+        let transpiler = Transpiler::builder().boolean_is_comparison(true).build();
+        assert!(transpiler
+            .transpile("filter(FALSE != active)")
+            .unwrap()
+            .ends_with(r#"WHERE ("active" IS NOT FALSE)"#));
+        // Off by default
+        assert!(Transpiler::new(Box::new(PostgreSqlDialect::new()))
+            .transpile("filter(active == TRUE)")
+            .unwrap()
+            .ends_with(r#"WHERE ("active" = TRUE)"#));
+    }
+
+    #[test]
+    fn test_dialect_name_for_each_dialect() {
+        let cases: Vec<(Box<dyn SqlDialect>, &str)> = vec![
+            (Box::new(PostgreSqlDialect::new()), "postgresql"),
+            (Box::new(MySqlDialect::new()), "mysql"),
+            (Box::new(SqliteDialect::new()), "sqlite"),
+            (Box::new(DuckDbDialect::new()), "duckdb"),
+        ];
+
+        for (dialect, name) in cases {
+            let transpiler = Transpiler::new(dialect);
+            assert_eq!(transpiler.dialect_name(), name);
+            assert_eq!(transpiler.clone().dialect_name(), name);
+        }
+    }
+
+    #[test]
+    fn test_cloned_transpiler_keeps_configuration() {
+        let transpiler = Transpiler::builder()
+            .dialect(Box::new(MySqlDialect::new()))
+            .strict(true)
+            .null_safe_equality(true)
+            .build();
+        let cloned = transpiler.clone();
+
+        let handle = std::thread::spawn(move || cloned.transpile("filter(a == b)"));
+        let sql = handle.join().unwrap().unwrap();
+
+        assert_eq!(sql, transpiler.transpile("filter(a == b)").unwrap());
+        assert!(sql.contains("(`a` <=> `b`)"), "{sql}");
+        assert!(transpiler
+            .clone()
+            .transpile("mutate(prev = lag(amount))")
+            .is_err());
+    }
+
+    #[test]
+    fn test_shared_transpiler_keeps_no_per_call_state() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Transpiler>();
+
+        let transpiler = std::sync::Arc::new(Transpiler::new(Box::new(DuckDbDialect::new())));
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let shared = std::sync::Arc::clone(&transpiler);
+                std::thread::spawn(move || shared.transpile(&format!("filter(x > {i})")))
+            })
+            .collect();
+        for (i, handle) in handles.into_iter().enumerate() {
+            let sql = handle.join().unwrap().unwrap();
+            assert!(sql.contains(&format!("\"x\" > {i}")), "{sql}");
+        }
+
+        // A call that fails halfway through a mutate() leaves nothing behind.
+        assert!(transpiler
+            .transpile("arrange(x) %>% mutate(m = mean(x, frame = \"bogus\"))")
+            .is_err());
+        let code = "mutate(total = cumsum(x), m = mean(x))";
+        let (sql, warnings) = transpiler.transpile_with_warnings(code).unwrap();
+        let (fresh_sql, fresh_warnings) = Transpiler::new(Box::new(DuckDbDialect::new()))
+            .transpile_with_warnings(code)
+            .unwrap();
+        assert_eq!(sql, fresh_sql);
+        assert_eq!(warnings, fresh_warnings);
+    }
+
+    #[test]
+    fn test_spark_dialect_end_to_end() {
+        let transpiler = Transpiler::new(Box::new(SparkSqlDialect::new()));
+
+        assert_eq!(
+            transpiler
+                .transpile("group_by(g) %>% summarise(m = median(x))")
+                .unwrap(),
+            "SELECT `g`, PERCENTILE_APPROX(`x`, 0.5) AS `m`\nFROM `data`\nGROUP BY `g`"
+        );
+        assert_eq!(
+            transpiler.transpile("select(name) %>% head(3)").unwrap(),
+            "SELECT `name`\nFROM `data`\nLIMIT 3"
+        );
+        assert_eq!(
+            transpiler
+                .transpile("data %>% anti_join(orders, by = \"id\")")
+                .unwrap(),
+            "SELECT *\nFROM `data`\nANTI JOIN `orders` ON `data`.`id` = `orders`.`id`"
+        );
+    }
+
+    #[test]
+    fn test_oracle_dialect_end_to_end() {
+        let transpiler = Transpiler::new(Box::new(OracleDialect::new()));
+
+        assert_eq!(
+            transpiler.transpile("select(name) %>% head(5)").unwrap(),
+            "SELECT \"NAME\"\nFROM \"DATA\"\nFETCH FIRST 5 ROWS ONLY"
+        );
+        assert_eq!(
+            transpiler
+                .transpile("filter(active == TRUE) %>% slice(3:5)")
+                .unwrap(),
+            "SELECT *\nFROM \"DATA\"\nWHERE (\"ACTIVE\" = 1)\nOFFSET 2 ROWS FETCH NEXT 3 ROWS ONLY"
+        );
+        assert!(transpiler
+            .transpile("mutate(total = coalesce(amount, 0))")
+            .unwrap()
+            .contains(r#"NVL("AMOUNT", 0) AS "TOTAL""#));
+    }
+
+    #[test]
+    fn test_conditional_branches_are_generated_once_and_parenthesized() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+
+        let sql = transpiler
+            .transpile("mutate(y = if_else(x > 0, x + 1, x - 1) * 2)")
+            .unwrap();
+        assert!(
+            sql.contains(r#"(CASE WHEN ("x" > 0) THEN ("x" + 1) ELSE ("x" - 1) END * 2) AS "y""#)
+        );
+
+        let sql = transpiler
+            .transpile("mutate(z = case_when(x > 0 & y < 2 ~ x / 2, TRUE ~ x - 1))")
+            .unwrap();
+        assert_eq!(sql.matches("CASE WHEN").count(), 1);
+        assert!(sql.contains(r#"WHEN (("x" > 0) AND ("y" < 2)) THEN ("x" / 2)"#));
+    }
+
+    #[test]
+    fn test_grepl_filter_end_to_end() {
+        let postgres = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        assert_eq!(
+            postgres.transpile(r#"filter(grepl("^A", name))"#).unwrap(),
+            "SELECT *\nFROM \"data\"\nWHERE (\"name\" ~ '^A')"
+        );
+
+        let mysql = Transpiler::new(Box::new(MySqlDialect::new()));
+        assert!(mysql
+            .transpile(r#"filter(grepl(pattern = "^A", x = name))"#)
+            .unwrap()
+            .contains("WHERE (`name` REGEXP '^A')"));
+
+        let sqlite = Transpiler::new(Box::new(SqliteDialect::new()));
+        assert!(matches!(
+            sqlite.transpile(r#"filter(grepl("^A", name))"#),
+            Err(TranspileError::GenerationError(
+                GenerationError::UnsupportedFunction { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_count_verb_weighted_and_unweighted() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+
+        assert_eq!(
+            transpiler
+                .transpile("count(category, wt = quantity)")
+                .unwrap(),
+            "SELECT \"category\", SUM(\"quantity\") AS \"n\"\nFROM \"data\"\nGROUP BY \"category\""
+        );
+        assert_eq!(
+            transpiler.transpile("count(category)").unwrap(),
+            "SELECT \"category\", COUNT(*) AS \"n\"\nFROM \"data\"\nGROUP BY \"category\""
+        );
+    }
+
+    #[test]
+    fn test_summarise_mixes_row_count_and_column_aggregates_in_order() {
+        let code =
+            "group_by(dept) %>% summarise(total = n(), non_null = count(email), avg = mean(age))";
+
+        let postgres = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        assert_eq!(
+            postgres.transpile(code).unwrap(),
+            "SELECT \"dept\", COUNT(*) AS \"total\", COUNT(\"email\") AS \"non_null\", AVG(\"age\") AS \"avg\"\nFROM \"data\"\nGROUP BY \"dept\""
+        );
+
+        let mysql = Transpiler::new(Box::new(MySqlDialect::new()));
+        assert!(mysql.transpile(code).unwrap().starts_with(
+            "SELECT `dept`, COUNT(*) AS `total`, COUNT(`email`) AS `non_null`, AVG(`age`) AS `avg`"
+        ));
+
+        // Default aliases follow the same order
+        assert_eq!(
+            postgres
+                .transpile("summarise(n(), count(email), mean(age))")
+                .unwrap(),
+            "SELECT COUNT(*) AS \"n\", COUNT(\"email\") AS \"count_email\", AVG(\"age\") AS \"mean_age\"\nFROM \"data\""
+        );
+    }
+
+    #[test]
+    fn test_filter_only_pipeline_selects_star() {
+        let postgres = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        assert_eq!(
+            postgres.transpile("filter(age > 18)").unwrap(),
+            "SELECT *\nFROM \"data\"\nWHERE (\"age\" > 18)"
+        );
+
+        let mysql = Transpiler::new(Box::new(MySqlDialect::new()));
+        assert_eq!(
+            mysql.transpile("filter(age > 18)").unwrap(),
+            "SELECT *\nFROM `data`\nWHERE (`age` > 18)"
+        );
+    }
+
+    #[test]
+    fn test_error_codes_are_stable_per_variant() {
+        use crate::error::{DPLYR_ERROR_INTERNAL, DPLYR_ERROR_SYNTAX, DPLYR_ERROR_UNSUPPORTED};
+
+        let cases = [
+            (
+                TranspileError::LexError(LexError::UnexpectedCharacter('@', 0)),
+                DPLYR_ERROR_SYNTAX,
+            ),
+            (
+                TranspileError::ParseError(ParseError::EmptyPipeline),
+                DPLYR_ERROR_SYNTAX,
+            ),
+            (
+                TranspileError::ValidationError("v".to_string()),
+                DPLYR_ERROR_SYNTAX,
+            ),
+            (
+                TranspileError::GenerationError(GenerationError::EmptyQuery),
+                DPLYR_ERROR_UNSUPPORTED,
+            ),
+            (
+                TranspileError::GenerationError(GenerationError::OutputTooLarge {
+                    length: 2,
+                    max: 1,
+                }),
+                DPLYR_ERROR_INTERNAL,
+            ),
+            (
+                TranspileError::IoError("io".to_string()),
+                DPLYR_ERROR_INTERNAL,
+            ),
+            (
+                TranspileError::ConfigurationError("c".to_string()),
+                DPLYR_ERROR_INTERNAL,
+            ),
+            (
+                TranspileError::SystemError("s".to_string()),
+                DPLYR_ERROR_INTERNAL,
+            ),
+            (
+                TranspileError::SecurityError("s".to_string()),
+                DPLYR_ERROR_INTERNAL,
+            ),
+            (
+                TranspileError::TimeoutError("t".to_string()),
+                DPLYR_ERROR_INTERNAL,
+            ),
+            (
+                TranspileError::NetworkError("n".to_string()),
+                DPLYR_ERROR_INTERNAL,
+            ),
+        ];
+        for (error, code) in &cases {
+            assert_eq!(error_code_for(error), *code, "{error:?}");
+        }
+
+        assert_eq!(error_code_name(DPLYR_ERROR_SYNTAX), c"E-SYNTAX");
+        assert_eq!(error_code_name(DPLYR_ERROR_UNSUPPORTED), c"E-UNSUPPORTED");
+        assert_eq!(error_code_name(DPLYR_ERROR_INTERNAL), c"E-INTERNAL");
+        assert_eq!(error_code_name(42), c"E-UNKNOWN");
+    }
+
+    #[test]
+    fn test_error_message_uses_catalog_template() {
+        let transpiler = Transpiler::new(Box::new(MySqlDialect::new()));
+        let error = transpiler
+            .transpile("data %>% summarise(m = median(x))")
+            .unwrap_err();
+        assert_eq!(error.message_id(), "generate.unsupported-aggregate");
+
+        let english = HashMap::new();
+        assert_eq!(error.localized_message(&english), error.to_string());
+
+        let korean: HashMap<String, String> = [(
+            "generate.unsupported-aggregate".to_string(),
+            "'{dialect}' 방언은 집계 함수 '{function}'을(를) 지원하지 않습니다".to_string(),
+        )]
+        .into_iter()
+        .collect();
+        assert_eq!(
+            error.localized_message(&korean),
+            "'mysql' 방언은 집계 함수 'median'을(를) 지원하지 않습니다"
+        );
+
+        let parse_error = transpiler.transpile("data %>% select(").unwrap_err();
+        assert!(parse_error.message_id().starts_with("parse."));
+        assert!(parse_error
+            .message_args()
+            .iter()
+            .any(|(name, _)| *name == "position"));
+    }
+
+    #[test]
+    fn test_distinct_ordered_by_unselected_column_is_rejected() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        for code in [
+            "data %>% arrange(x) %>% distinct(y)",
+            "data %>% distinct(y) %>% arrange(desc(x))",
+            "data %>% select(y) %>% distinct() %>% arrange(x)",
+        ] {
+            let error = transpiler.transpile(code).unwrap_err();
+            assert!(
+                matches!(
+                    &error,
+                    TranspileError::GenerationError(GenerationError::InvalidAst { reason })
+                        if reason.contains("conflicts with distinct()") && reason.contains("\"x\"")
+                ),
+                "{code}: {error:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_distinct_ordered_by_selected_column_is_allowed() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        let cases = [
+            (
+                "data %>% arrange(y) %>% distinct(y)",
+                "SELECT DISTINCT \"y\"\nFROM \"data\"\nORDER BY \"y\" ASC",
+            ),
+            (
+                "data %>% distinct(y, z) %>% arrange(desc(z))",
+                "SELECT DISTINCT \"y\", \"z\"\nFROM \"data\"\nORDER BY \"z\" DESC",
+            ),
+            (
+                "data %>% arrange(x) %>% distinct()",
+                "SELECT DISTINCT *\nFROM \"data\"\nORDER BY \"x\" ASC",
+            ),
+        ];
+        for (code, expected) in cases {
+            assert_eq!(transpiler.transpile(code).unwrap(), expected, "{code}");
+        }
+    }
+
+    #[test]
+    fn test_summarize_spelling_matches_summarise() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        let british = transpiler
+            .transpile("data %>% group_by(dept) %>% summarise(avg = mean(salary))")
+            .unwrap();
+        let american = transpiler
+            .transpile("data %>% group_by(dept) %>% summarize(avg = mean(salary))")
+            .unwrap();
+        assert_eq!(american, british);
+        assert!(
+            american.contains("AVG(\"salary\") AS \"avg\""),
+            "{american}"
+        );
+    }
+
+    #[test]
+    fn test_transmute_keeps_only_assigned_columns() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        assert_eq!(
+            transpiler
+                .transpile("data %>% transmute(total = price * qty, name = toupper(name))")
+                .unwrap(),
+            "SELECT (\"price\" * \"qty\") AS \"total\", UPPER(\"name\") AS \"name\"\nFROM \"data\""
+        );
+    }
+
+    #[test]
+    fn test_transmute_with_window_function_stays_flat() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        assert_eq!(
+            transpiler
+                .transpile("data %>% transmute(rank = row_number())")
+                .unwrap(),
+            "SELECT ROW_NUMBER() OVER () AS \"rank\"\nFROM \"data\""
+        );
+        assert_eq!(
+            transpiler
+                .transpile("data %>% group_by(g) %>% transmute(r = rank(x), y = x * 2)")
+                .unwrap(),
+            "SELECT RANK() OVER (PARTITION BY \"g\" ORDER BY \"x\") AS \"r\", (\"x\" * 2) AS \"y\"\nFROM \"data\""
+        );
+    }
+
+    #[test]
+    fn test_keyword_alias_registers_verb_spelling() {
+        let transpiler = Transpiler::builder()
+            .keyword_alias("resumir", Token::Summarise)
+            .build();
+        assert_eq!(
+            transpiler
+                .transpile("data %>% resumir(total = sum(amount))")
+                .unwrap(),
+            transpiler
+                .transpile("data %>% summarize(total = sum(amount))")
+                .unwrap()
+        );
+        // Without the alias the word is an ordinary identifier
+        assert!(Transpiler::new(Box::new(PostgreSqlDialect::new()))
+            .transpile("data %>% resumir(total = sum(amount))")
+            .is_err());
+    }
+
+    #[test]
+    fn test_mutate_replaces_column_projected_earlier() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        let cases = [
+            (
+                "data %>% mutate(y = a + 1) %>% mutate(y = y * 2)",
+                "SELECT *, ((\"a\" + 1) * 2) AS \"y\"\nFROM \"data\"",
+            ),
+            (
+                "data %>% group_by(g) %>% summarise(m = mean(x)) %>% mutate(m = m * 2)",
+                "SELECT \"g\", (AVG(\"x\") * 2) AS \"m\"\nFROM \"data\"\nGROUP BY \"g\"",
+            ),
+            (
+                "data %>% mutate(y = x + 1, z = y * 2)",
+                "SELECT *, (\"x\" + 1) AS \"y\", ((\"x\" + 1) * 2) AS \"z\"\nFROM \"data\"",
+            ),
+            (
+                "data %>% select(a = x) %>% mutate(a = a + 1)",
+                "SELECT (\"x\" + 1) AS \"a\"\nFROM \"data\"",
+            ),
+        ];
+        for (code, expected) in cases {
+            let sql = transpiler.transpile(code).unwrap();
+            assert_eq!(sql, expected, "{code}");
+        }
+
+        // A window column is read from a subquery rather than nested in another window
+        let sql = transpiler
+            .transpile("data %>% arrange(t) %>% mutate(r = row_number()) %>% mutate(s = cumsum(r))")
+            .unwrap();
+        assert!(sql.contains("SUM(\"r\") OVER (ORDER BY \"t\" ASC"), "{sql}");
+        assert!(sql.contains(") AS subquery"), "{sql}");
+    }
+
+    #[test]
+    fn test_duplicate_output_column_names_are_rejected() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        let cases = [
+            ("data %>% select(a = x, a = y)", "a", "select"),
+            ("data %>% select(x, y, x)", "x", "select"),
+            ("data %>% mutate(a = 1, a = 2)", "a", "mutate"),
+            (
+                "data %>% summarise(a = mean(x), a = sum(y))",
+                "a",
+                "summarise",
+            ),
+            (
+                "data %>% summarise(mean(x), mean_x = max(x))",
+                "mean_x",
+                "summarise",
+            ),
+            (
+                "data %>% group_by(g) %>% summarise(g = n())",
+                "g",
+                "summarise",
+            ),
+        ];
+        for (code, column, operation) in cases {
+            let error = transpiler.transpile(code).unwrap_err();
+            assert!(
+                matches!(
+                    &error,
+                    TranspileError::GenerationError(GenerationError::DuplicateColumnName {
+                        column: c,
+                        operation: o,
+                    }) if c == column && o == operation
+                ),
+                "{code}: {error:?}"
+            );
+        }
+
+        // Distinct names, and a name reused after select() dropped the first one
+        for code in [
+            "data %>% select(a = x, b = x)",
+            "data %>% mutate(a = 1, b = 2)",
+            "data %>% group_by(g) %>% summarise(n = n(), total = sum(x))",
+            "data %>% mutate(a = 1) %>% select(b) %>% mutate(a = 2)",
+        ] {
+            assert!(transpiler.transpile(code).is_ok(), "{code}");
+        }
+    }
+
+    #[test]
+    fn test_select_negated_columns_use_star_exclude() {
+        let code = "data %>% select(-id, -created_at)";
+        assert_eq!(
+            Transpiler::new(Box::new(DuckDbDialect::new()))
+                .transpile(code)
+                .unwrap(),
+            "SELECT * EXCLUDE (\"id\", \"created_at\")\nFROM \"data\""
+        );
+        assert!(matches!(
+            Transpiler::new(Box::new(PostgreSqlDialect::new())).transpile(code),
+            Err(TranspileError::GenerationError(
+                GenerationError::UnsupportedOperation { ref operation, .. }
+            )) if operation == "select(-col)"
+        ));
+
+        // Columns already listed by name are simply dropped from the projection
+        assert_eq!(
+            Transpiler::new(Box::new(PostgreSqlDialect::new()))
+                .transpile("data %>% select(x, y, z) %>% select(-y)")
+                .unwrap(),
+            "SELECT \"x\", \"z\"\nFROM \"data\""
+        );
+    }
+
+    #[test]
+    fn test_transpile_with_metadata_reports_dialect_operations_and_sizes() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        let code = "data %>%\n  filter(age > 18) %>%\n  select(name, age) %>%\n  arrange(age)";
+        let (sql, metadata) = transpiler.transpile_with_metadata(code).unwrap();
+
+        assert_eq!(sql, transpiler.transpile(code).unwrap());
+        assert_eq!(metadata.dialect, "postgresql");
+        assert_eq!(metadata.stats.operation_count, 3);
+        assert_eq!(metadata.stats.input_size_bytes, code.len());
+        assert_eq!(metadata.stats.output_size_bytes, sql.len());
+        assert_eq!(metadata.input_info.size_bytes, code.len());
+        assert_eq!(metadata.input_info.line_count, 4);
+        assert_eq!(metadata.version, env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn test_arrange_by_summarise_alias_orders_by_quoted_alias() {
+        let code = "data %>% group_by(dept) %>% summarise(avg = mean(x)) %>% arrange(desc(avg))";
+        let cases: Vec<(Box<dyn SqlDialect>, &str)> = vec![
+            (Box::new(PostgreSqlDialect::new()), r#"ORDER BY "avg" DESC"#),
+            (Box::new(MySqlDialect::new()), "ORDER BY `avg` DESC"),
+            (Box::new(SqliteDialect::new()), r#"ORDER BY "avg" DESC"#),
+            (Box::new(DuckDbDialect::new()), r#"ORDER BY "avg" DESC"#),
+            (Box::new(OracleDialect::new()), r#"ORDER BY "AVG" DESC"#),
+            (Box::new(SparkSqlDialect::new()), "ORDER BY `avg` DESC"),
+        ];
+        for (dialect, order_by) in cases {
+            let name = dialect.dialect_name();
+            let sql = Transpiler::new(dialect).transpile(code).unwrap();
+            assert!(sql.ends_with(order_by), "{name}: {sql}");
+        }
+    }
+
+    #[test]
+    fn test_arrange_by_alias_repeats_expression_where_aliases_are_unsupported() {
+        #[derive(Clone)]
+        struct NoOrderByAlias(PostgreSqlDialect);
+
+        impl SqlDialect for NoOrderByAlias {
+            fn quote_identifier(&self, name: &str) -> String {
+                self.0.quote_identifier(name)
+            }
+            fn quote_string(&self, value: &str) -> String {
+                self.0.quote_string(value)
+            }
+            fn limit_clause(&self, limit: usize) -> String {
+                self.0.limit_clause(limit)
+            }
+            fn string_concat(&self, left: &str, right: &str) -> String {
+                self.0.string_concat(left, right)
+            }
+            fn aggregate_function(&self, function: &str) -> String {
+                self.0.aggregate_function(function)
+            }
+            fn is_case_sensitive(&self) -> bool {
+                self.0.is_case_sensitive()
+            }
+            fn clone_box(&self) -> Box<dyn SqlDialect> {
+                Box::new(self.clone())
+            }
+            fn supports_order_by_alias(&self) -> bool {
+                false
+            }
+        }
+
+        let transpiler = Transpiler::new(Box::new(NoOrderByAlias(PostgreSqlDialect::new())));
+        let sql = transpiler
+            .transpile(
+                "data %>% group_by(dept) %>% summarise(avg = mean(x)) %>% arrange(desc(avg), dept)",
+            )
+            .unwrap();
+        assert!(
+            sql.ends_with(r#"ORDER BY AVG("x") DESC, "dept" ASC"#),
+            "{sql}"
+        );
+    }
+
+    #[test]
+    fn test_chained_comparison_keeps_left_literals_unquoted() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+
+        let sql = transpiler.transpile("data %>% filter(0 < x)").unwrap();
+        assert!(sql.ends_with(r#"WHERE (0 < "x")"#), "{sql}");
+
+        let sql = transpiler
+            .transpile("data %>% filter(0 < x & x < 10)")
+            .unwrap();
+        assert!(
+            sql.ends_with(r#"WHERE ((0 < "x") AND ("x" < 10))"#),
+            "{sql}"
+        );
+
+        let sql = transpiler
+            .transpile("data %>% filter(-1.5 <= x & 'a' < name)")
+            .unwrap();
+        assert!(sql.contains(r#"(-1.5 <= "x") AND ('a' < "name")"#), "{sql}");
+    }
+
+    #[test]
+    fn test_built_pipeline_generates_same_sql_as_parsed_code() {
+        use crate::parser::{Aggregation, BinaryOp, Expr, OrderExpr};
+
+        let transpiler = Transpiler::new(Box::new(DuckDbDialect::new()));
+        let built = DplyrNode::pipeline()
+            .source("employees")
+            .filter(Expr::binary(
+                Expr::binary(
+                    Expr::column("age"),
+                    BinaryOp::GreaterThanOrEqual,
+                    Expr::number(18.0),
+                ),
+                BinaryOp::And,
+                Expr::binary(Expr::column("dept"), BinaryOp::NotEqual, Expr::string("HR")),
+            ))
+            .mutate([(
+                "bonus",
+                Expr::binary(
+                    Expr::column("salary"),
+                    BinaryOp::Multiply,
+                    Expr::number(0.1),
+                ),
+            )])
+            .group_by(["dept"])
+            .summarise([
+                Aggregation::new("avg_bonus", "mean", "bonus"),
+                Aggregation::new("n", "n", ""),
+            ])
+            .arrange([OrderExpr::desc("avg_bonus")])
+            .build();
+
+        let parsed = transpiler
+            .transpile(
+                "employees %>% filter(age >= 18 & dept != 'HR') %>% mutate(bonus = salary * 0.1) \
+                 %>% group_by(dept) %>% summarise(avg_bonus = mean(bonus), n = n()) \
+                 %>% arrange(desc(avg_bonus))",
+            )
+            .unwrap();
+        assert_eq!(transpiler.generate_sql(&built).unwrap(), parsed);
+    }
+
+    #[test]
+    fn test_filter_on_boolean_columns_and_negation() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+
+        let sql = transpiler.transpile("data %>% filter(is_active)").unwrap();
+        assert!(sql.ends_with(r#"WHERE "is_active""#), "{sql}");
+
+        let sql = transpiler.transpile("data %>% filter(!is_active)").unwrap();
+        assert!(sql.ends_with(r#"WHERE (NOT "is_active")"#), "{sql}");
+
+        let sql = transpiler
+            .transpile("data %>% filter(!is_active & !is.na(email))")
+            .unwrap();
+        assert!(
+            sql.ends_with(r#"WHERE ((NOT "is_active") AND (NOT ("email" IS NULL)))"#),
+            "{sql}"
+        );
+
+        let sql = transpiler
+            .transpile("data %>% mutate(inactive = !is_active)")
+            .unwrap();
+        assert!(sql.contains(r#"(NOT "is_active") AS "inactive""#), "{sql}");
+    }
+
+    #[test]
+    fn test_count_and_tally_name_argument() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+
+        assert_eq!(
+            transpiler
+                .transpile("count(category, name = \"cnt\")")
+                .unwrap(),
+            "SELECT \"category\", COUNT(*) AS \"cnt\"\nFROM \"data\"\nGROUP BY \"category\""
+        );
+        assert_eq!(
+            transpiler
+                .transpile("data %>% group_by(dept) %>% tally(wt = qty, name = \"total\")")
+                .unwrap(),
+            "SELECT \"dept\", SUM(\"qty\") AS \"total\"\nFROM \"data\"\nGROUP BY \"dept\""
+        );
+    }
+
+    #[test]
+    fn test_trailing_collect_and_show_query_are_no_ops() {
+        let transpiler = Transpiler::new(Box::new(DuckDbDialect::new()));
+        let pipeline = "orders %>% filter(amount > 10) %>% group_by(region) %>% summarise(total = sum(amount))";
+        let expected = transpiler.transpile(pipeline).unwrap();
+
+        for suffix in [
+            " %>% collect()",
+            " %>% show_query()",
+            " %>%\n  show_query() %>%\n  collect()",
+        ] {
+            assert_eq!(
+                transpiler
+                    .transpile(&format!("{pipeline}{suffix}"))
+                    .unwrap(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_arrange_by_mutated_column() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+
+        // Projected: ordered by the alias
+        let sql = transpiler
+            .transpile("data %>% mutate(total = a + b) %>% arrange(desc(total))")
+            .unwrap();
+        assert!(sql.contains(r#"("a" + "b") AS "total""#));
+        assert!(sql.ends_with(r#"ORDER BY "total" DESC"#), "{sql}");
+
+        // Dropped by select(), before or after arrange(): ordered by the expression
+        for code in [
+            "data %>% mutate(total = a + b) %>% select(a) %>% arrange(desc(total))",
+            "data %>% mutate(total = a + b) %>% arrange(desc(total)) %>% select(a)",
+            "data %>% mutate(total = a + b) %>% arrange(desc(total)) %>% select(-total)",
+        ] {
+            let sql = transpiler.transpile(code).unwrap();
+            assert!(!sql.contains(r#"AS "total""#), "{sql}");
+            assert!(sql.ends_with(r#"ORDER BY ("a" + "b") DESC"#), "{sql}");
+        }
+
+        let sql = transpiler
+            .transpile(
+                "data %>% mutate(total = a + b) %>% arrange(desc(total)) %>% select(a, total)",
+            )
+            .unwrap();
+        assert!(sql.ends_with(r#"ORDER BY "total" DESC"#), "{sql}");
+    }
+
+    #[test]
+    fn test_if_any_and_if_all_in_filter() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+
+        let sql = transpiler
+            .transpile("data %>% filter(if_any(c(a, b), ~ .x > 0))")
+            .unwrap();
+        assert!(sql.ends_with(r#"WHERE (("a" > 0) OR ("b" > 0))"#), "{sql}");
+
+        let sql = transpiler
+            .transpile(r#"data %>% filter(if_all(c(a, b), ~ .x == "y"))"#)
+            .unwrap();
+        assert!(
+            sql.ends_with(r#"WHERE (("a" = 'y') AND ("b" = 'y'))"#),
+            "{sql}"
+        );
+    }
+
+    #[test]
+    fn test_cyclic_mutate_is_rejected() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+
+        let error = transpiler
+            .transpile("data %>% mutate(a = b, b = a)")
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            TranspileError::GenerationError(GenerationError::CircularReference { ref reference })
+                if reference == "a -> b -> a"
+        ));
+        assert!(error.to_string().contains("a -> b -> a"));
+    }
+
+    #[test]
+    fn test_count_sort_merges_with_later_arrange() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+
+        for code in [
+            "data %>% count(category, sort = TRUE)",
+            "data %>% count(category, sort = TRUE) %>% arrange(desc(n))",
+        ] {
+            let sql = transpiler.transpile(code).unwrap();
+            assert_eq!(sql.matches("ORDER BY").count(), 1, "{sql}");
+            assert!(sql.ends_with(r#"ORDER BY "n" DESC"#), "{sql}");
+        }
+
+        // A later arrange() replaces the count ordering, as in dplyr
+        let sql = transpiler
+            .transpile("data %>% count(category, sort = TRUE) %>% arrange(category)")
+            .unwrap();
+        assert!(sql.ends_with(r#"ORDER BY "category" ASC"#), "{sql}");
+    }
+
+    #[test]
+    fn test_replace_na_verb_coalesces_each_column() {
+        let duckdb = Transpiler::new(Box::new(DuckDbDialect::new()));
+        for code in [
+            "data %>% replace_na(list(x = 0))",
+            "data %>% replace_na(x = 0)",
+            "replace_na(x = 0)",
+        ] {
+            assert_eq!(
+                duckdb.transpile(code).unwrap(),
+                "SELECT * EXCLUDE (\"x\"), COALESCE(\"x\", 0) AS \"x\"\nFROM \"data\"",
+                "{code}"
+            );
+        }
+        assert_eq!(
+            duckdb
+                .transpile(r#"data %>% replace_na(x = 0, y = "none")"#)
+                .unwrap(),
+            "SELECT * EXCLUDE (\"x\", \"y\"), COALESCE(\"x\", 0) AS \"x\", COALESCE(\"y\", 'none') AS \"y\"\nFROM \"data\""
+        );
+
+        // Without * EXCLUDE the replaced columns must be listed by select()
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        assert!(matches!(
+            transpiler.transpile("data %>% replace_na(list(x = 0))"),
+            Err(TranspileError::GenerationError(
+                GenerationError::UnsupportedOperation { .. }
+            ))
+        ));
+        assert_eq!(
+            transpiler
+                .transpile(r#"data %>% select(x, y, z) %>% replace_na(x = 0, y = "none")"#)
+                .unwrap(),
+            "SELECT COALESCE(\"x\", 0) AS \"x\", COALESCE(\"y\", 'none') AS \"y\", \"z\"\nFROM \"data\""
+        );
+    }
+
+    #[test]
+    fn test_cumulative_functions_use_ordered_running_windows() {
+        let code = "sales %>% group_by(region) %>% arrange(day) %>% mutate(running = cumsum(amount), top = cummax(amount))";
+        let expected = "SELECT *, SUM(\"amount\") OVER (PARTITION BY \"region\" ORDER BY \"day\" ASC ROWS UNBOUNDED PRECEDING) AS \"running\", MAX(\"amount\") OVER (PARTITION BY \"region\" ORDER BY \"day\" ASC ROWS UNBOUNDED PRECEDING) AS \"top\"\nFROM \"sales\"\nORDER BY \"day\" ASC";
+
+        let duckdb = Transpiler::new(Box::new(DuckDbDialect::new()));
+        assert_eq!(duckdb.transpile(code).unwrap(), expected);
+        let postgres = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        assert_eq!(postgres.transpile(code).unwrap(), expected);
+
+        let (sql, warnings) = postgres
+            .transpile_with_warnings("sales %>% mutate(avg = cummean(amount))")
+            .unwrap();
+        assert_eq!(
+            sql,
+            "SELECT *, AVG(\"amount\") OVER (ROWS UNBOUNDED PRECEDING) AS \"avg\"\nFROM \"sales\""
+        );
+        assert_eq!(
+            warnings,
+            vec![TranspileWarning::UnorderedWindowFunction {
+                function: "cummean".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_cumulative_functions_require_ordering_on_oracle() {
+        let oracle = Transpiler::new(Box::new(OracleDialect::new()));
+        assert!(matches!(
+            oracle.transpile("sales %>% mutate(low = cummin(amount))"),
+            Err(TranspileError::GenerationError(
+                GenerationError::UnsupportedOperation { .. }
+            ))
+        ));
+        assert!(oracle
+            .transpile("sales %>% arrange(day) %>% mutate(low = cummin(amount))")
+            .unwrap()
+            .contains(
+                r#"MIN("AMOUNT") OVER (ORDER BY "DAY" ASC ROWS UNBOUNDED PRECEDING) AS "LOW""#
+            ));
+    }
+
+    #[test]
+    fn test_summarise_groups_controls_downstream_grouping() {
+        let transpiler = Transpiler::new(Box::new(DuckDbDialect::new()));
+        let grouped = |groups: &str| {
+            transpiler
+                .transpile(&format!(
+                    "data %>% group_by(a, b) %>% summarise(x = mean(y){groups}) %>% slice_head(n = 1)"
+                ))
+                .unwrap()
+        };
+
+        let dropped = grouped(", .groups = \"drop\"");
+        assert!(dropped.contains("GROUP BY \"a\", \"b\""), "{dropped}");
+        assert!(dropped.ends_with("LIMIT 1"), "{dropped}");
+
+        let kept = grouped(", .groups = \"keep\"");
+        assert!(kept.contains("PARTITION BY \"a\", \"b\""), "{kept}");
+
+        // dplyr's default peels off the last grouping level
+        let default = grouped("");
+        assert!(default.contains("PARTITION BY \"a\")"), "{default}");
+        assert_eq!(default, grouped(", .groups = \"drop_last\""));
+    }
+
+    #[test]
+    fn test_summarise_of_summarised_result_reads_a_subquery() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        assert_eq!(
+            transpiler
+                .transpile(
+                    "data %>% group_by(g) %>% summarise(a = sum(x), .groups = \"drop\") %>% summarise(b = sum(a))"
+                )
+                .unwrap(),
+            "SELECT SUM(\"a\") AS \"b\"\nFROM (\nSELECT \"g\", SUM(\"x\") AS \"a\"\nFROM \"data\"\nGROUP BY \"g\"\n) AS subquery"
+        );
+        // The grouping left by the first summarise() groups the second
+        assert_eq!(
+            transpiler
+                .transpile("data %>% group_by(g, h) %>% summarise(a = sum(x)) %>% summarise(b = sum(a))")
+                .unwrap(),
+            "SELECT \"g\", SUM(\"a\") AS \"b\"\nFROM (\nSELECT \"g\", \"h\", SUM(\"x\") AS \"a\"\nFROM \"data\"\nGROUP BY \"g\", \"h\"\n) AS subquery\nGROUP BY \"g\""
+        );
+    }
+
+    #[test]
+    fn test_transpile_tokens_matches_string_input() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        let code = "orders %>% filter(amount > 10) %>% group_by(region) %>% summarise(total = sum(amount))";
+        let mut lexer = Lexer::new(code.to_string());
+        let mut tokens = Vec::new();
+        loop {
+            let token = lexer.next_token().unwrap();
+            let done = token == Token::EOF;
+            tokens.push(token);
+            if done {
+                break;
+            }
+        }
+
+        assert_eq!(
+            transpiler.transpile_tokens(&tokens).unwrap(),
+            transpiler.transpile(code).unwrap()
+        );
+        assert!(matches!(
+            transpiler.transpile_tokens(&tokens[..4]),
+            Err(TranspileError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_can_transpile_reports_dialect_support() {
+        let mysql = Transpiler::new(Box::new(MySqlDialect::new()));
+        assert!(mysql
+            .can_transpile("sales %>% group_by(region) %>% summarise(avg = mean(amount))")
+            .is_ok());
+        assert!(matches!(
+            mysql.can_transpile("sales %>% summarise(mid = median(amount))"),
+            Err(TranspileError::GenerationError(
+                GenerationError::UnsupportedAggregateFunction { .. }
+            ))
+        ));
+        assert!(matches!(
+            mysql.can_transpile("sales %>% select(amount"),
+            Err(TranspileError::ParseError(_))
+        ));
+
+        let duckdb = Transpiler::new(Box::new(DuckDbDialect::new()));
+        assert!(duckdb
+            .can_transpile("sales %>% summarise(mid = median(amount))")
+            .is_ok());
+
+        let strict = Transpiler::builder()
+            .dialect(Box::new(DuckDbDialect::new()))
+            .strict(true)
+            .build();
+        assert!(duckdb
+            .can_transpile("sales %>% mutate(r = row_number())")
+            .is_ok());
+        assert!(matches!(
+            strict.can_transpile("sales %>% mutate(r = row_number())"),
+            Err(TranspileError::GenerationError(
+                GenerationError::StrictModeViolation { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_pivot_wider_with_explicit_names_uses_conditional_aggregates() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        let sql = transpiler
+            .transpile(
+                "scores %>% group_by(student) %>% pivot_wider(names_from = subject, values_from = score, names = c(\"math\", \"art\"))",
+            )
+            .unwrap();
+        assert_eq!(
+            sql,
+            "SELECT \"student\", SUM(CASE WHEN \"subject\" = 'math' THEN \"score\" END) AS \"math\", \
+             SUM(CASE WHEN \"subject\" = 'art' THEN \"score\" END) AS \"art\"\n\
+             FROM \"scores\"\nGROUP BY \"student\""
+        );
+
+        assert!(matches!(
+            transpiler.transpile(
+                "scores %>% pivot_wider(names_from = subject, values_from = score, names = \"math\")"
+            ),
+            Err(TranspileError::GenerationError(
+                GenerationError::UnsupportedOperation { .. }
+            ))
+        ));
+        assert!(matches!(
+            transpiler.transpile(
+                "scores %>% group_by(student) %>% pivot_wider(names_from = subject, values_from = score)"
+            ),
+            Err(TranspileError::ParseError(ParseError::InvalidOperation { .. }))
+        ));
+        assert!(matches!(
+            transpiler.transpile(
+                "scores %>% group_by(student) %>% pivot_wider(names_from = subject, values_from = score, names = c(\"student\"))"
+            ),
+            Err(TranspileError::GenerationError(
+                GenerationError::DuplicateColumnName { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_mutate_across_everything_reports_schema_requirement() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        let error = transpiler
+            .transpile("data %>% mutate(across(everything(), as.character))")
+            .unwrap_err();
+        match error {
+            TranspileError::GenerationError(GenerationError::UnsupportedOperation {
+                operation,
+                ..
+            }) => assert_eq!(
+                operation,
+                "across(everything(), as.character) in mutate() needs the table schema, which is not available; list the columns explicitly"
+            ),
+            other => panic!("expected UnsupportedOperation, got {other:?}"),
+        }
+
+        assert_eq!(
+            transpiler
+                .transpile("data %>% mutate(across(c(a, b), as.character))")
+                .unwrap(),
+            transpiler
+                .transpile("data %>% mutate(a = as.character(a), b = as.character(b))")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_distinct_inside_aggregate_functions() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        assert_eq!(
+            transpiler
+                .transpile("data %>% summarise(s = sum(distinct amount))")
+                .unwrap(),
+            "SELECT SUM(DISTINCT \"amount\") AS \"s\"\nFROM \"data\""
+        );
+        assert_eq!(
+            transpiler
+                .transpile(
+                    "data %>% group_by(g) %>% summarise(m = mean(distinct amount), k = count(distinct id))"
+                )
+                .unwrap(),
+            "SELECT \"g\", AVG(DISTINCT \"amount\") AS \"m\", COUNT(DISTINCT \"id\") AS \"k\"\nFROM \"data\"\nGROUP BY \"g\""
+        );
+    }
+
+    #[test]
+    fn test_transpile_file_reads_pipeline_from_disk() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("query.R");
+        std::fs::write(&path, "data %>%\n  filter(x > 1)\n").expect("write pipeline");
+
+        assert_eq!(
+            transpiler.transpile_file(&path).unwrap(),
+            transpiler.transpile("data %>% filter(x > 1)").unwrap()
+        );
+        assert!(matches!(
+            transpiler.transpile_file(dir.path().join("missing.R")),
+            Err(TranspileError::IoError(message)) if message.contains("missing.R")
+        ));
+    }
+
+    #[test]
+    fn test_between_infix_lowers_to_sql_between() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        assert_eq!(
+            transpiler
+                .transpile("data %>% filter(x %between% c(1, 10))")
+                .unwrap(),
+            "SELECT *\nFROM \"data\"\nWHERE (\"x\" BETWEEN 1 AND 10)"
+        );
+    }
+
+    #[test]
+    fn test_empty_group_by_removes_grouping() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        let sql = transpiler
+            .transpile(
+                "data %>% group_by(a) %>% filter(x > 1) %>% group_by() %>% summarise(n = n())",
+            )
+            .unwrap();
+        assert_eq!(
+            sql,
+            "SELECT COUNT(*) AS \"n\"\nFROM \"data\"\nWHERE (\"x\" > 1)"
+        );
+        assert!(!sql.contains("GROUP BY"));
+
+        let sql = transpiler
+            .transpile("data %>% group_by(a) %>% group_by() %>% mutate(r = row_number())")
+            .unwrap();
+        assert!(!sql.contains("PARTITION BY"), "{sql}");
+    }
+
+    #[test]
+    fn test_numeric_literals_keep_their_exact_value() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        let filter_value = |literal: &str| {
+            let sql = transpiler
+                .transpile(&format!("data %>% filter(amount > {literal})"))
+                .unwrap();
+            sql.strip_prefix("SELECT *\nFROM \"data\"\nWHERE (\"amount\" > ")
+                .and_then(|rest| rest.strip_suffix(')'))
+                .unwrap_or_else(|| panic!("unexpected SQL: {sql}"))
+                .to_string()
+        };
+
+        // Monetary values
+        for literal in ["1000000.5", "19.99", "0.01", "123456789.12", "0.1"] {
+            assert_eq!(filter_value(literal), literal);
+        }
+        assert_eq!(filter_value("1000000.50"), "1000000.5");
+        assert_eq!(filter_value("1.5e-3"), "0.0015");
+
+        // Large IDs, up to the last whole number an f64 holds exactly
+        for literal in ["4294967297", "1234567890123", "9007199254740992"] {
+            assert_eq!(filter_value(literal), literal);
+        }
+        assert!(matches!(
+            transpiler.transpile("data %>% filter(id == 9007199254740993)"),
+            Err(TranspileError::ParseError(ParseError::LexError(
+                LexError::ImpreciseNumber(..)
+            )))
+        ));
+    }
+
+    #[test]
+    fn test_error_display_includes_position_and_hint() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+
+        let message = transpiler
+            .transpile("data %>% filter((x > 1)")
+            .unwrap_err()
+            .to_string();
+        assert!(message.starts_with("Parsing error: "), "{message}");
+        assert!(message.contains("(position: "), "{message}");
+        assert!(
+            message.ends_with("; hint: add ')' to close the '(' at position 3"),
+            "{message}"
+        );
+
+        let message = transpiler
+            .transpile("data %>% select(a b)")
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains("(position: "), "{message}");
+        assert!(message.contains("; hint: "), "{message}");
+
+        let message = transpiler
+            .transpile("data %>% filter(x == \"open)")
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains("start position: "), "{message}");
+        assert!(
+            message.ends_with("; hint: add the closing quote to the string"),
+            "{message}"
+        );
+    }
+
+    #[test]
+    fn test_tidyselect_helpers_report_schema_requirement() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        let unsupported = |code: &str| match transpiler.transpile(code) {
+            Err(TranspileError::GenerationError(GenerationError::UnsupportedOperation {
+                operation,
+                ..
+            })) => operation,
+            other => panic!("expected UnsupportedOperation for {code}, got {other:?}"),
+        };
+
+        assert_eq!(
+            unsupported("data %>% select(last_col())"),
+            "last_col() in select() needs the table schema, which is not available; list the columns explicitly"
+        );
+        assert_eq!(
+            unsupported("data %>% select(id, num_range(\"x\", 1:3))"),
+            "num_range(...) in select() needs the table schema, which is not available; list the columns explicitly"
+        );
+        assert!(unsupported("data %>% select(starts_with(\"a\"))").starts_with("starts_with(...) "));
+        assert!(
+            unsupported("data %>% mutate(across(where(is.numeric), round))")
+                .starts_with("across(where(...), round) in mutate() ")
+        );
+    }
+
+    #[test]
+    fn test_quantile_aggregate_per_dialect() {
+        let pipeline = "data %>% summarise(p90 = quantile(amount, 0.9))";
+        assert_eq!(
+            Transpiler::new(Box::new(PostgreSqlDialect::new()))
+                .transpile(pipeline)
+                .unwrap(),
+            "SELECT PERCENTILE_CONT(0.9) WITHIN GROUP (ORDER BY \"amount\") AS \"p90\"\nFROM \"data\""
+        );
+        assert_eq!(
+            Transpiler::new(Box::new(DuckDbDialect::new()))
+                .transpile(pipeline)
+                .unwrap(),
+            "SELECT QUANTILE_CONT(\"amount\", 0.9) AS \"p90\"\nFROM \"data\""
+        );
+        assert!(matches!(
+            Transpiler::new(Box::new(SqliteDialect::new())).transpile(pipeline),
+            Err(TranspileError::GenerationError(
+                GenerationError::UnsupportedAggregateFunction { ref function, .. }
+            )) if function == "quantile"
+        ));
+    }
+
+    #[test]
+    fn test_null_default_operator_becomes_coalesce() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        assert_eq!(
+            transpiler
+                .transpile("data %>% mutate(name = raw %||% \"unknown\")")
+                .unwrap(),
+            "SELECT *, COALESCE(\"raw\", 'unknown') AS \"name\"\nFROM \"data\""
+        );
+    }
+
+    #[test]
+    fn test_grouped_mutate_aggregates_become_window_aggregates() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        assert_eq!(
+            transpiler
+                .transpile("data %>% group_by(dept) %>% mutate(dept_avg = mean(salary))")
+                .unwrap(),
+            "SELECT *, AVG(\"salary\") OVER (PARTITION BY \"dept\") AS \"dept_avg\"\nFROM \"data\""
+        );
+        assert_eq!(
+            transpiler
+                .transpile(
+                    "data %>% group_by(dept, team) %>% mutate(share = salary / sum(salary), size = n())"
+                )
+                .unwrap(),
+            "SELECT *, (\"salary\" / SUM(\"salary\") OVER (PARTITION BY \"dept\", \"team\")) AS \"share\", \
+             COUNT(*) OVER (PARTITION BY \"dept\", \"team\") AS \"size\"\nFROM \"data\""
+        );
+        // Ungrouped, the aggregate spans the whole table
+        assert_eq!(
+            transpiler
+                .transpile("data %>% mutate(top = max(score, na.rm = TRUE))")
+                .unwrap(),
+            "SELECT *, MAX(\"score\") OVER () AS \"top\"\nFROM \"data\""
+        );
+        // An arrange() orders cumulative windows but does not turn the
+        // group aggregate into a running one
+        assert_eq!(
+            transpiler
+                .transpile("data %>% group_by(g) %>% arrange(day) %>% mutate(total = sum(x))")
+                .unwrap(),
+            "SELECT *, SUM(\"x\") OVER (PARTITION BY \"g\") AS \"total\"\nFROM \"data\"\nORDER BY \"day\" ASC"
+        );
+    }
+
+    #[test]
+    fn test_unsupported_operations_lists_every_dialect_gap() {
+        let transpiler = Transpiler::new(Box::new(MySqlDialect::new()));
+        assert_eq!(
+            transpiler
+                .unsupported_operations(
+                    "data %>% fill(price) %>% group_by(g) %>% summarise(mid = median(price), top = max(price))"
+                )
+                .unwrap(),
+            ["fill", "median"]
+        );
+        // Several gaps inside one verb, each reported once
+        assert_eq!(
+            transpiler
+                .unsupported_operations(
+                    "data %>% mutate(a = median(x), b = quantile(y, 0.5) + median(z)) %>% select(-x)"
+                )
+                .unwrap(),
+            ["median", "quantile", "select"]
+        );
+        assert!(transpiler
+            .unsupported_operations("data %>% filter(x > 1) %>% select(x)")
+            .unwrap()
+            .is_empty());
+        assert!(Transpiler::new(Box::new(DuckDbDialect::new()))
+            .unsupported_operations("data %>% fill(price) %>% summarise(mid = median(price))")
+            .unwrap()
+            .is_empty());
+        assert!(transpiler.unsupported_operations("filter(x >").is_err());
+    }
+
+    #[test]
+    fn test_rollup_and_cube_grouping_per_dialect() {
+        let rollup = "data %>% group_by(rollup(region, year)) %>% summarise(total = sum(sales))";
+        let cube = "data %>% group_by(cube(region, year)) %>% summarise(total = sum(sales))";
+        let postgres = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        assert_eq!(
+            postgres.transpile(rollup).unwrap(),
+            "SELECT \"region\", \"year\", SUM(\"sales\") AS \"total\"\nFROM \"data\"\nGROUP BY ROLLUP(\"region\", \"year\")"
+        );
+        assert_eq!(
+            postgres.transpile(cube).unwrap(),
+            "SELECT \"region\", \"year\", SUM(\"sales\") AS \"total\"\nFROM \"data\"\nGROUP BY CUBE(\"region\", \"year\")"
+        );
+        let duckdb = Transpiler::new(Box::new(DuckDbDialect::new()));
+        assert!(duckdb
+            .transpile(rollup)
+            .unwrap()
+            .ends_with("GROUP BY ROLLUP(\"region\", \"year\")"));
+        assert!(duckdb
+            .transpile(cube)
+            .unwrap()
+            .ends_with("GROUP BY CUBE(\"region\", \"year\")"));
+        let mysql = Transpiler::new(Box::new(MySqlDialect::new()));
+        assert!(mysql
+            .transpile(rollup)
+            .unwrap()
+            .ends_with("GROUP BY `region`, `year` WITH ROLLUP"));
+        assert!(matches!(
+            mysql.transpile(cube),
+            Err(TranspileError::GenerationError(
+                GenerationError::UnsupportedOperation { .. }
+            ))
+        ));
+        let sqlite = Transpiler::new(Box::new(SqliteDialect::new()));
+        for pipeline in [rollup, cube] {
+            assert!(matches!(
+                sqlite.transpile(pipeline),
+                Err(TranspileError::GenerationError(
+                    GenerationError::UnsupportedOperation { .. }
+                ))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_inline_by_matches_group_by_pipeline() {
+        let transpiler = Transpiler::new(Box::new(DuckDbDialect::new()));
+        for (inline, grouped) in [
+            (
+                "data %>% summarise(avg = mean(salary), .by = dept)",
+                "data %>% group_by(dept) %>% summarise(avg = mean(salary), .groups = \"drop\")",
+            ),
+            (
+                "data %>% mutate(avg = mean(salary), .by = c(dept, year))",
+                "data %>% group_by(dept, year) %>% mutate(avg = mean(salary))",
+            ),
+            (
+                "data %>% filter(row_number() == 1, .by = dept)",
+                "data %>% group_by(dept) %>% filter(row_number() == 1)",
+            ),
+            (
+                "data %>% filter(x > mean(x), .by = dept)",
+                "data %>% group_by(dept) %>% filter(x > mean(x)) %>% group_by()",
+            ),
+        ] {
+            assert_eq!(
+                transpiler.transpile(inline).unwrap(),
+                transpiler.transpile(grouped).unwrap(),
+                "{inline}"
+            );
+        }
+
+        // The grouping does not outlive the call
+        assert_eq!(
+            transpiler
+                .transpile("data %>% mutate(avg = mean(salary), .by = dept) %>% mutate(r = row_number())")
+                .unwrap(),
+            transpiler
+                .transpile("data %>% group_by(dept) %>% mutate(avg = mean(salary)) %>% group_by() %>% mutate(r = row_number())")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_near_compares_within_tolerance() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        assert_eq!(
+            transpiler
+                .transpile("data %>% filter(near(price, 9.99))")
+                .unwrap(),
+            "SELECT *\nFROM \"data\"\nWHERE (ABS(\"price\" - 9.99) < 0.000000014901161193847656)"
+        );
+        for pipeline in [
+            "data %>% filter(near(price, 9.99, tol = 0.005))",
+            "data %>% filter(near(price, 9.99, 0.005))",
+        ] {
+            assert_eq!(
+                transpiler.transpile(pipeline).unwrap(),
+                "SELECT *\nFROM \"data\"\nWHERE (ABS(\"price\" - 9.99) < 0.005)",
+                "{pipeline}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_mutate_aggregate_with_explicit_window_frame() {
+        let pipeline = "data %>% group_by(store) %>% arrange(day) %>% mutate(ma = mean(sales, frame = \"rows between 2 preceding and current row\"))";
+        for dialect in [
+            Box::new(DuckDbDialect::new()) as Box<dyn SqlDialect>,
+            Box::new(PostgreSqlDialect::new()),
+        ] {
+            let sql = Transpiler::new(dialect).transpile(pipeline).unwrap();
+            assert!(
+                sql.contains("AVG(\"sales\") OVER (PARTITION BY \"store\" ORDER BY \"day\" ASC ROWS BETWEEN 2 PRECEDING AND CURRENT ROW) AS \"ma\""),
+                "{sql}"
+            );
+        }
+
+        let transpiler = Transpiler::new(Box::new(DuckDbDialect::new()));
+        for frame in [
+            "rows between 2 preceding and current row) AS x FROM t; --",
+            "order by x",
+            "",
+        ] {
+            let pipeline = format!("data %>% mutate(ma = mean(sales, frame = \"{frame}\"))");
+            assert!(
+                matches!(
+                    transpiler.transpile(&pipeline),
+                    Err(TranspileError::GenerationError(
+                        GenerationError::InvalidAst { .. }
+                    ))
+                ),
+                "{frame}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_second_filter_keeps_first_or_clause_grouped() {
+        let transpiler = Transpiler::new(Box::new(DuckDbDialect::new()));
+        assert_eq!(
+            transpiler
+                .transpile("data %>% filter(a > 1 | b > 2) %>% filter(c == 3)")
+                .unwrap(),
+            "SELECT *\nFROM \"data\"\nWHERE ((\"a\" > 1) OR (\"b\" > 2)) AND ((\"c\" = 3))"
+        );
+        assert_eq!(
+            transpiler
+                .transpile("data %>% filter(is_active | is_admin) %>% filter(verified)")
+                .unwrap(),
+            "SELECT *\nFROM \"data\"\nWHERE (\"is_active\" OR \"is_admin\") AND (\"verified\")"
+        );
+    }
+
+    #[test]
+    fn test_grouped_filter_compares_against_window_aggregate() {
+        let duckdb = Transpiler::new(Box::new(DuckDbDialect::new()));
+        assert_eq!(
+            duckdb
+                .transpile("data %>% filter(x > mean(x), .by = dept)")
+                .unwrap(),
+            "SELECT * EXCLUDE (\"dplyr_filter_window_1\")\nFROM (\nSELECT *, AVG(\"x\") OVER (PARTITION BY \"dept\") AS \"dplyr_filter_window_1\"\nFROM \"data\"\n) AS subquery\nWHERE (\"x\" > \"dplyr_filter_window_1\")"
+        );
+
+        // Without * EXCLUDE the helper column is dropped by listing the selected columns
+        let postgres = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        assert_eq!(
+            postgres
+                .transpile("data %>% select(dept, x) %>% filter(x > mean(x), n() > 1, .by = dept)")
+                .unwrap(),
+            "SELECT \"dept\", \"x\"\nFROM (\nSELECT \"dept\", \"x\", AVG(\"x\") OVER (PARTITION BY \"dept\") AS \"dplyr_filter_window_1\", COUNT(*) OVER (PARTITION BY \"dept\") AS \"dplyr_filter_window_2\"\nFROM \"data\"\n) AS subquery\nWHERE ((\"x\" > \"dplyr_filter_window_1\") AND (\"dplyr_filter_window_2\" > 1))"
+        );
+        // With unknown columns the helper column stays, with a warning
+        let (sql, warnings) = postgres
+            .transpile_with_warnings("data %>% filter(x > mean(x), .by = dept)")
+            .unwrap();
+        assert_eq!(
+            sql,
+            "SELECT *\nFROM (\nSELECT *, AVG(\"x\") OVER (PARTITION BY \"dept\") AS \"dplyr_filter_window_1\"\nFROM \"data\"\n) AS subquery\nWHERE (\"x\" > \"dplyr_filter_window_1\")"
+        );
+        assert_eq!(
+            warnings,
+            vec![TranspileWarning::HelperColumnsKept {
+                operation: "filter".to_string(),
+                columns: vec!["dplyr_filter_window_1".to_string()],
+                dialect: "postgresql".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_aggregate_in_filter_before_summarise_is_rejected() {
+        let transpiler = Transpiler::new(Box::new(DuckDbDialect::new()));
+
+        let error = transpiler
+            .transpile("data %>% filter(mean(x) > 1) %>% summarise(m = mean(x))")
+            .unwrap_err();
+        assert!(matches!(
+            &error,
+            TranspileError::GenerationError(GenerationError::AggregateInFilter { function })
+                if function == "mean"
+        ));
+        assert!(error.to_string().contains("HAVING"), "{error}");
+
+        // Nested inside a scalar function call is still an aggregate
+        assert!(matches!(
+            transpiler.transpile("data %>% filter(abs(n()) > 1)"),
+            Err(TranspileError::GenerationError(
+                GenerationError::AggregateInFilter { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_summarise_count_column_vs_n() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+
+        let sql = transpiler
+            .transpile("orders %>% summarise(c = count(status), total = n())")
+            .unwrap();
+        assert_eq!(
+            sql,
+            "SELECT COUNT(\"status\") AS \"c\", COUNT(*) AS \"total\"\nFROM \"orders\""
+        );
+    }
+
+    #[test]
+    fn test_duckdb_read_functions_as_pipeline_source() {
+        let transpiler = Transpiler::new(Box::new(DuckDbDialect::new()));
+
+        assert_eq!(
+            transpiler
+                .transpile("read_parquet('f.parquet') %>% select(a, b)")
+                .unwrap(),
+            "SELECT \"a\", \"b\"\nFROM read_parquet('f.parquet')"
+        );
+        assert_eq!(
+            transpiler
+                .transpile("read_csv(\"logs/it's.csv\") %>% filter(x > 1)")
+                .unwrap(),
+            "SELECT *\nFROM read_csv('logs/it''s.csv')\nWHERE (\"x\" > 1)"
+        );
+        assert!(transpiler
+            .transpile("read_json('events.json') %>% select(id)")
+            .unwrap()
+            .contains("FROM read_json('events.json')"));
+    }
+
+    #[test]
+    fn test_read_functions_are_rejected_outside_duckdb() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+
+        assert!(matches!(
+            transpiler.transpile("read_parquet('f.parquet') %>% select(a)"),
+            Err(TranspileError::GenerationError(
+                GenerationError::UnsupportedFunction { function, dialect }
+            )) if function == "read_parquet" && dialect == "postgresql"
+        ));
+    }
+
+    #[test]
+    fn test_transpile_with_rename_with_explicit_columns() {
+        let transpiler = Transpiler::new(Box::new(DuckDbDialect::new()));
+
+        let sql = transpiler
+            .transpile("iris %>% rename_with(toupper, c(a, b))")
+            .unwrap();
+        assert_eq!(
+            sql,
+            "SELECT * EXCLUDE (\"a\", \"b\"), \"a\" AS \"A\", \"b\" AS \"B\"\nFROM \"iris\""
+        );
+
+        let sql = transpiler
+            .transpile("iris %>% rename_with(tolower, Species)")
+            .unwrap();
+        assert!(sql.contains("\"Species\" AS \"species\""), "{sql}");
+    }
+
+    #[test]
+    fn test_transpile_rename_with_requires_explicit_columns() {
+        let transpiler = Transpiler::new(Box::new(DuckDbDialect::new()));
+
+        assert!(matches!(
+            transpiler.transpile("iris %>% rename_with(toupper)"),
+            Err(TranspileError::GenerationError(
+                GenerationError::UnsupportedOperation { .. }
+            ))
+        ));
+        assert!(matches!(
+            transpiler.transpile("iris %>% rename_with(make.names, c(a))"),
+            Err(TranspileError::GenerationError(
+                GenerationError::UnsupportedFunction { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_transpile_with_group_by_and_summarise() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        let dplyr_code = "group_by(department) %>% summarise(avg_salary = mean(salary))";
+
+        let result = transpiler.transpile(dplyr_code);
+        assert!(result.is_ok(), "변환이 성공해야 합니다: {result:?}");
+
+        let sql = result.unwrap();
+        assert!(sql.contains("SELECT"));
+        assert!(sql.contains("GROUP BY"));
+        assert!(sql.contains("AVG"));
+    }
+
+    #[test]
+    fn test_parse_dplyr_individual_function() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        let dplyr_code = "select(name, age)";
+
+        let result = transpiler.parse_dplyr(dplyr_code);
+        assert!(result.is_ok(), "Parsing should succeed: {result:?}");
+
+        let ast = result.unwrap();
+        assert!(ast.is_pipeline());
+    }
+
+    #[test]
+    fn test_generate_sql_from_ast() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        let dplyr_code = "select(name, age)";
+
+        // First parse to get AST
+        let ast = transpiler.parse_dplyr(dplyr_code).unwrap();
+
+        // Then generate SQL from AST
+        let result = transpiler.generate_sql(&ast);
+        assert!(result.is_ok(), "SQL generation should succeed: {result:?}");
+
+        let sql = result.unwrap();
+        assert!(sql.contains("SELECT"));
+        assert!(sql.contains("\"name\""));
+        assert!(sql.contains("\"age\""));
+    }
+
+    #[test]
+    fn test_transpile_error_handling() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+
+        // Test invalid syntax
+        let result = transpiler.transpile("invalid_function(test)");
+        assert!(result.is_err(), "Invalid syntax should return an error");
+
+        match result.unwrap_err() {
+            TranspileError::ParseError(_) => {} // Expected error type
+            other => panic!("Unexpected error type: {other:?}"),
+        }
     }
 
     #[test]
@@ -961,4 +3996,502 @@ mod tests {
         assert!(error_result.is_err());
         let _error: TranspileError = error_result.unwrap_err();
     }
+
+    #[test]
+    fn test_transpile_with_warnings_reports_degraded_window_function() {
+        let transpiler = Transpiler::new(Box::new(DuckDbDialect::new()));
+
+        let (sql, warnings) = transpiler
+            .transpile_with_warnings("mutate(prev_amount = lag(amount))")
+            .expect("unordered lag should still translate");
+
+        assert!(sql.contains("LAG(\"amount\", 1) OVER ()"), "{sql}");
+        assert_eq!(
+            warnings,
+            vec![TranspileWarning::UnorderedWindowFunction {
+                function: "lag".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_unordered_row_number_uses_dialect_default_ordering() {
+        let code = "data %>% mutate(r = row_number())";
+        let cases = [
+            (
+                Box::new(DuckDbDialect::new()) as Box<dyn SqlDialect>,
+                "ROW_NUMBER() OVER () AS \"r\"",
+            ),
+            (
+                Box::new(OracleDialect::new()),
+                r#"ROW_NUMBER() OVER (ORDER BY NULL) AS "R""#,
+            ),
+            (
+                Box::new(SparkSqlDialect::new()),
+                "ROW_NUMBER() OVER (ORDER BY NULL) AS `r`",
+            ),
+        ];
+        for (dialect, expected) in cases {
+            let name = dialect.dialect_name();
+            let (sql, warnings) = Transpiler::new(dialect)
+                .transpile_with_warnings(code)
+                .unwrap();
+            assert!(sql.contains(expected), "{name}: {sql}");
+            assert_eq!(
+                warnings,
+                vec![TranspileWarning::UnorderedWindowFunction {
+                    function: "row_number".to_string()
+                }],
+                "{name}"
+            );
+        }
+
+        // An explicit ordering is kept as written
+        let sql = Transpiler::new(Box::new(OracleDialect::new()))
+            .transpile("data %>% mutate(r = row_number(x))")
+            .unwrap();
+        assert!(
+            sql.contains(r#"ROW_NUMBER() OVER (ORDER BY "X") AS "R""#),
+            "{sql}"
+        );
+    }
+
+    #[test]
+    fn test_last_value_spans_the_whole_ordered_partition() {
+        let code = "data %>% group_by(g) %>% \
+                    mutate(f = first_value(x, order_by = t), l = last_value(x, order_by = t))";
+        for dialect in [
+            Box::new(PostgreSqlDialect::new()) as Box<dyn SqlDialect>,
+            Box::new(DuckDbDialect::new()),
+        ] {
+            let sql = Transpiler::new(dialect).transpile(code).unwrap();
+            // FIRST_VALUE is correct under the default frame, LAST_VALUE is not
+            assert!(
+                sql.contains(
+                    "FIRST_VALUE(\"x\") OVER (PARTITION BY \"g\" ORDER BY \"t\") AS \"f\""
+                ),
+                "{sql}"
+            );
+            assert!(
+                sql.contains(
+                    "LAST_VALUE(\"x\") OVER (PARTITION BY \"g\" ORDER BY \"t\" \
+                     ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING) AS \"l\""
+                ),
+                "{sql}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_arrange_nulls_ordering_is_emitted_where_supported() {
+        let code = "data %>% arrange(desc(x, na_last = FALSE), asc(y, na_last = TRUE))";
+        let expected =
+            "SELECT *\nFROM \"data\"\nORDER BY \"x\" DESC NULLS FIRST, \"y\" ASC NULLS LAST";
+
+        for dialect in [
+            Box::new(PostgreSqlDialect::new()) as Box<dyn SqlDialect>,
+            Box::new(DuckDbDialect::new()),
+        ] {
+            let (sql, warnings) = Transpiler::new(dialect)
+                .transpile_with_warnings(code)
+                .unwrap();
+            assert_eq!(sql, expected);
+            assert!(warnings.is_empty());
+        }
+
+        let (sql, warnings) = Transpiler::new(Box::new(SqliteDialect::new()))
+            .transpile_with_warnings("data %>% arrange(desc(x, na_last = TRUE))")
+            .unwrap();
+        assert_eq!(sql, "SELECT *\nFROM \"data\"\nORDER BY \"x\" DESC");
+        assert_eq!(
+            warnings,
+            vec![TranspileWarning::NullsOrderingIgnored {
+                column: "x".to_string(),
+                dialect: "sqlite".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_arrange_combines_directions_and_nulls_placement_per_dialect() {
+        let code = "data %>% arrange(desc(x, na_last = TRUE), asc(y), z, desc(w, na_last = FALSE))";
+        let cases: Vec<(Box<dyn SqlDialect>, &str, usize)> = vec![
+            (
+                Box::new(PostgreSqlDialect::new()),
+                r#"ORDER BY "x" DESC NULLS LAST, "y" ASC, "z" ASC, "w" DESC NULLS FIRST"#,
+                0,
+            ),
+            (
+                Box::new(DuckDbDialect::new()),
+                r#"ORDER BY "x" DESC NULLS LAST, "y" ASC, "z" ASC, "w" DESC NULLS FIRST"#,
+                0,
+            ),
+            (
+                Box::new(OracleDialect::new()),
+                r#"ORDER BY "X" DESC NULLS LAST, "Y" ASC, "Z" ASC, "W" DESC NULLS FIRST"#,
+                0,
+            ),
+            (
+                Box::new(SparkSqlDialect::new()),
+                "ORDER BY `x` DESC NULLS LAST, `y` ASC, `z` ASC, `w` DESC NULLS FIRST",
+                0,
+            ),
+            (
+                Box::new(MySqlDialect::new()),
+                "ORDER BY `x` DESC, `y` ASC, `z` ASC, `w` DESC",
+                2,
+            ),
+            (
+                Box::new(SqliteDialect::new()),
+                r#"ORDER BY "x" DESC, "y" ASC, "z" ASC, "w" DESC"#,
+                2,
+            ),
+        ];
+
+        for (dialect, order_by, ignored) in cases {
+            let name = dialect.dialect_name();
+            let (sql, warnings) = Transpiler::new(dialect)
+                .transpile_with_warnings(code)
+                .unwrap();
+            assert!(sql.ends_with(order_by), "{name}: {sql}");
+            assert_eq!(warnings.len(), ignored, "{name}: {warnings:?}");
+            assert!(warnings
+                .iter()
+                .all(|w| matches!(w, TranspileWarning::NullsOrderingIgnored { .. })));
+        }
+    }
+
+    #[test]
+    fn test_transpile_with_warnings_is_empty_for_exact_translation() {
+        let transpiler = Transpiler::new(Box::new(DuckDbDialect::new()));
+
+        let (_, warnings) = transpiler
+            .transpile_with_warnings(
+                "mutate(prev_amount = lag(amount, order_by = day)) %>% filter(amount > 1)",
+            )
+            .expect("ordered lag should translate");
+        assert!(warnings.is_empty(), "unexpected warnings: {warnings:?}");
+
+        // Warnings from a previous call must not leak into the next one.
+        let _ = transpiler.transpile_with_warnings("mutate(r = row_number())");
+        let (_, warnings) = transpiler
+            .transpile_with_warnings("select(name)")
+            .expect("select should translate");
+        assert!(warnings.is_empty(), "unexpected warnings: {warnings:?}");
+    }
+
+    #[test]
+    fn test_transpile_with_warnings_reports_lossy_sqlite_cast() {
+        let transpiler = Transpiler::new(Box::new(SqliteDialect::new()));
+
+        let (sql, warnings) = transpiler
+            .transpile_with_warnings("mutate(flag = as.logical(active))")
+            .expect("as.logical should translate in SQLite");
+
+        assert!(sql.contains("CAST(\"active\" AS INTEGER)"), "{sql}");
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].code(), "W-LOSSY-CAST");
+        assert!(warnings[0].to_string().contains("sqlite"));
+    }
+
+    #[test]
+    fn test_precedence_warning_for_mixed_and_or() {
+        let transpiler = Transpiler::builder().precedence_warnings(true).build();
+
+        let (sql, warnings) = transpiler
+            .transpile_with_warnings("filter(a & b | c)")
+            .unwrap();
+
+        assert_eq!(
+            sql,
+            "SELECT *\nFROM \"data\"\nWHERE ((\"a\" AND \"b\") OR \"c\")"
+        );
+        assert_eq!(
+            warnings,
+            vec![TranspileWarning::AmbiguousPrecedence {
+                condition: "a AND b OR c".to_string(),
+            }]
+        );
+        assert_eq!(warnings[0].code(), "W-PRECEDENCE");
+    }
+
+    #[test]
+    fn test_precedence_warning_skips_grouped_or_disabled_conditions() {
+        let transpiler = Transpiler::builder().precedence_warnings(true).build();
+        for code in [
+            "filter((a & b) | c)",
+            "filter(a & (b | c))",
+            "filter(a & b, c | d)",
+        ] {
+            let (_, warnings) = transpiler.transpile_with_warnings(code).unwrap();
+            assert!(warnings.is_empty(), "{code}: {warnings:?}");
+        }
+
+        let (_, warnings) = Transpiler::new(Box::new(PostgreSqlDialect::new()))
+            .transpile_with_warnings("filter(a & b | c)")
+            .unwrap();
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_builder_strict_mode_rejects_warnings() {
+        let lenient = Transpiler::builder()
+            .dialect(Box::new(SqliteDialect::new()))
+            .build();
+        assert!(lenient
+            .transpile("mutate(flag = as.logical(active))")
+            .is_ok());
+
+        let strict = Transpiler::builder()
+            .dialect(Box::new(SqliteDialect::new()))
+            .strict(true)
+            .build();
+        match strict.transpile("mutate(flag = as.logical(active))") {
+            Err(TranspileError::GenerationError(GenerationError::StrictModeViolation {
+                code,
+                ..
+            })) => assert_eq!(code, "W-LOSSY-CAST"),
+            other => panic!("Expected StrictModeViolation, got: {other:?}"),
+        }
+        assert!(strict
+            .transpile_with_warnings("mutate(r = row_number())")
+            .is_err());
+
+        let (sql, warnings) = strict
+            .transpile_with_warnings("select(name) %>% filter(age > 18)")
+            .expect("exact translation should pass strict mode");
+        assert!(sql.contains("WHERE"), "{sql}");
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_builder_preserve_comments() {
+        let code = "# adults only\nselect(name) %>% # keep name\n  filter(age > 18)";
+
+        let plain = Transpiler::builder().build().transpile(code).unwrap();
+        assert!(!plain.contains("--"), "{plain}");
+
+        let annotated = Transpiler::builder()
+            .preserve_comments(true)
+            .build()
+            .transpile(code)
+            .unwrap();
+        assert!(
+            annotated.starts_with("-- adults only\n-- keep name\n"),
+            "{annotated}"
+        );
+        assert!(annotated.ends_with(&plain), "{annotated}");
+    }
+
+    #[test]
+    fn test_builder_pipe_syntax() {
+        let transpiler = Transpiler::builder()
+            .pipe_syntax(PipeSyntax::Native)
+            .build();
+        assert!(transpiler
+            .transpile("select(name) |> filter(age > 18)")
+            .is_ok());
+    }
+
+    #[test]
+    fn test_transpile_with_source_alias_qualifies_columns() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+
+        let sql = transpiler
+            .transpile_with_source_alias(
+                "select(id, amount) %>% filter(amount > 100) %>% arrange(desc(id))",
+                "orders",
+                "o",
+            )
+            .unwrap();
+
+        assert_eq!(
+            sql,
+            "SELECT \"o\".\"id\", \"o\".\"amount\"\nFROM \"orders\" AS \"o\"\nWHERE (\"o\".\"amount\" > 100)\nORDER BY \"o\".\"id\" DESC"
+        );
+    }
+
+    #[test]
+    fn test_transpile_with_source_alias_grouped_summary() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+
+        let sql = transpiler
+            .transpile_with_source_alias(
+                "data %>% group_by(region) %>% summarise(total = sum(amount))",
+                "orders",
+                "o",
+            )
+            .unwrap();
+
+        assert!(sql.contains(r#"FROM "orders" AS "o""#), "{sql}");
+        assert!(sql.contains(r#"SUM("o"."amount") AS "total""#), "{sql}");
+        assert!(sql.contains(r#"GROUP BY "o"."region""#), "{sql}");
+        assert!(!sql.contains(r#""data""#), "{sql}");
+    }
+
+    #[test]
+    fn test_transpile_with_source_alias_rejects_blank_source() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+
+        assert!(matches!(
+            transpiler.transpile_with_source_alias("select(id)", "", "o"),
+            Err(TranspileError::GenerationError(
+                GenerationError::InvalidIdentifier { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_transpile_with_source_bare_table() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+
+        let sql = transpiler
+            .transpile_with_source("select(id)", "orders")
+            .unwrap();
+
+        assert_eq!(sql, "SELECT \"id\"\nFROM \"orders\"");
+    }
+
+    #[test]
+    fn test_transpile_with_source_quotes_schema_and_table_per_dialect() {
+        let cases: Vec<(Box<dyn SqlDialect>, &str)> = vec![
+            (
+                Box::new(PostgreSqlDialect::new()),
+                "FROM \"public\".\"orders\"",
+            ),
+            (Box::new(MySqlDialect::new()), "FROM `public`.`orders`"),
+            (Box::new(SqliteDialect::new()), "FROM \"public\".\"orders\""),
+            (Box::new(DuckDbDialect::new()), "FROM \"public\".\"orders\""),
+        ];
+
+        for (dialect, expected) in cases {
+            let transpiler = Transpiler::new(dialect);
+            let sql = transpiler
+                .transpile_with_source("select(id) %>% filter(id > 1)", "public.orders")
+                .unwrap();
+            assert!(sql.contains(expected), "{sql}");
+            assert!(!sql.contains("public.orders"), "{sql}");
+        }
+    }
+
+    #[test]
+    fn test_schema_qualified_join_uses_table_name_in_condition() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+
+        let sql = transpiler
+            .transpile_with_source(
+                "select(id) %>% left_join(sales.customers, by = \"id\")",
+                "public.orders",
+            )
+            .unwrap();
+
+        assert!(
+            sql.contains(
+                "FROM \"public\".\"orders\"\nLEFT JOIN \"sales\".\"customers\" ON \"orders\".\"id\" = \"customers\".\"id\""
+            ),
+            "{sql}"
+        );
+    }
+
+    #[test]
+    fn test_source_with_quote_characters_is_escaped_per_dialect() {
+        let cases: Vec<(Box<dyn SqlDialect>, &str)> = vec![
+            (
+                Box::new(PostgreSqlDialect::new()),
+                "FROM \"orders\"\"; DROP TABLE x; --\"",
+            ),
+            (
+                Box::new(MySqlDialect::new()),
+                "FROM `orders``; DROP TABLE x; --`",
+            ),
+            (
+                Box::new(SqliteDialect::new()),
+                "FROM \"orders\"\"; DROP TABLE x; --\"",
+            ),
+            (
+                Box::new(DuckDbDialect::new()),
+                "FROM \"orders\"\"; DROP TABLE x; --\"",
+            ),
+        ];
+
+        for (dialect, expected) in cases {
+            let quote = if expected.contains('`') { "`" } else { "\"" };
+            let source = format!("orders{quote}; DROP TABLE x; --");
+            let sql = Transpiler::new(dialect)
+                .transpile_with_source("select(id)", &source)
+                .unwrap();
+            assert!(sql.ends_with(expected), "{sql}");
+        }
+    }
+
+    #[test]
+    fn test_transpile_with_source_rejects_blank_source() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+
+        assert!(matches!(
+            transpiler.transpile_with_source("select(id)", " "),
+            Err(TranspileError::GenerationError(
+                GenerationError::InvalidIdentifier { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_scalar_logical_operators_generate_identical_sql() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+
+        let short = transpiler
+            .transpile("filter(a > 1 & b < 2 | c == 3)")
+            .unwrap();
+        let long = transpiler
+            .transpile("filter(a > 1 && b < 2 || c == 3)")
+            .unwrap();
+
+        assert_eq!(short, long);
+        assert!(long.contains("AND") && long.contains("OR"), "{long}");
+    }
+
+    #[test]
+    fn test_column_to_column_comparisons_and_arithmetic() {
+        let pg = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+        let mysql = Transpiler::new(Box::new(MySqlDialect::new()));
+
+        assert_eq!(
+            pg.transpile("events %>% filter(start_date < end_date)")
+                .unwrap(),
+            "SELECT *\nFROM \"events\"\nWHERE (\"start_date\" < \"end_date\")"
+        );
+        assert_eq!(
+            mysql
+                .transpile("events %>% filter(start_date < end_date)")
+                .unwrap(),
+            "SELECT *\nFROM `events`\nWHERE (`start_date` < `end_date`)"
+        );
+        assert_eq!(
+            pg.transpile("events %>% filter(a == b & c != d)").unwrap(),
+            "SELECT *\nFROM \"events\"\nWHERE ((\"a\" = \"b\") AND (\"c\" != \"d\"))"
+        );
+        assert_eq!(
+            pg.transpile("events %>% mutate(gap = end - start)")
+                .unwrap(),
+            "SELECT *, (\"end\" - \"start\") AS \"gap\"\nFROM \"events\""
+        );
+    }
+
+    #[test]
+    fn test_distinct_across_generates_same_sql_as_distinct_columns() {
+        let transpiler = Transpiler::new(Box::new(PostgreSqlDialect::new()));
+
+        let across = transpiler
+            .transpile("data %>% distinct(across(c(a, b)))")
+            .unwrap();
+        let plain = transpiler.transpile("data %>% distinct(a, b)").unwrap();
+
+        assert_eq!(across, plain);
+        assert_eq!(plain, "SELECT DISTINCT \"a\", \"b\"\nFROM \"data\"");
+        assert_eq!(
+            transpiler.transpile("data %>% distinct()").unwrap(),
+            "SELECT DISTINCT *\nFROM \"data\""
+        );
+    }
 }
@@ -0,0 +1,213 @@
+//! Metadata describing a transpilation.
+//!
+//! [`Transpiler::transpile_with_metadata`](crate::Transpiler::transpile_with_metadata)
+//! returns it, and the CLI serializes it into its JSON output.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Transpilation metadata containing processing information
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TranspileMetadata {
+    /// Timestamp when transpilation started (Unix timestamp)
+    pub timestamp: u64,
+
+    /// SQL dialect used for transpilation
+    pub dialect: String,
+
+    /// Processing statistics
+    pub stats: ProcessingStats,
+
+    /// Input information
+    pub input_info: InputInfo,
+
+    /// Version information
+    pub version: String,
+}
+
+/// Processing statistics for transpilation
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProcessingStats {
+    /// Time taken for lexical analysis (microseconds)
+    pub lex_time_us: u64,
+
+    /// Time taken for parsing (microseconds)
+    pub parse_time_us: u64,
+
+    /// Time taken for SQL generation (microseconds)
+    pub generation_time_us: u64,
+
+    /// Total processing time (microseconds)
+    pub total_time_us: u64,
+
+    /// Number of tokens generated
+    pub token_count: usize,
+
+    /// Number of AST nodes created
+    pub ast_node_count: usize,
+
+    /// Number of pipeline operations (verbs)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub operation_count: usize,
+
+    /// Input size in bytes
+    pub input_size_bytes: usize,
+
+    /// Output size in bytes
+    pub output_size_bytes: usize,
+}
+
+/// Input source information
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InputInfo {
+    /// Source type (file, stdin, text)
+    pub source_type: String,
+
+    /// Source identifier (filename, "stdin", "text")
+    pub source_id: String,
+
+    /// Input size in bytes
+    pub size_bytes: usize,
+
+    /// Number of lines in input
+    pub line_count: usize,
+}
+
+/// Builder for creating TranspileMetadata
+#[derive(Debug)]
+pub struct MetadataBuilder {
+    dialect: String,
+    stats: ProcessingStats,
+    input_info: InputInfo,
+    version: String,
+}
+
+impl MetadataBuilder {
+    /// Creates a new metadata builder
+    pub fn new(dialect: &str) -> Self {
+        Self {
+            dialect: dialect.to_string(),
+            stats: ProcessingStats {
+                lex_time_us: 0,
+                parse_time_us: 0,
+                generation_time_us: 0,
+                total_time_us: 0,
+                token_count: 0,
+                ast_node_count: 0,
+                operation_count: 0,
+                input_size_bytes: 0,
+                output_size_bytes: 0,
+            },
+            input_info: InputInfo {
+                source_type: "unknown".to_string(),
+                source_id: "unknown".to_string(),
+                size_bytes: 0,
+                line_count: 0,
+            },
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+
+    /// Sets processing statistics
+    pub const fn with_stats(mut self, stats: ProcessingStats) -> Self {
+        self.stats = stats;
+        self
+    }
+
+    /// Sets input information
+    pub fn with_input_info(mut self, input_info: InputInfo) -> Self {
+        self.input_info = input_info;
+        self
+    }
+
+    /// Sets version information
+    pub fn with_version(mut self, version: &str) -> Self {
+        self.version = version.to_string();
+        self
+    }
+
+    /// Builds the metadata
+    pub fn build(self) -> TranspileMetadata {
+        TranspileMetadata {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            dialect: self.dialect,
+            stats: self.stats,
+            input_info: self.input_info,
+            version: self.version,
+        }
+    }
+}
+
+/// Helper functions for creating common metadata components
+impl ProcessingStats {
+    /// Creates empty processing stats
+    pub const fn empty() -> Self {
+        Self {
+            lex_time_us: 0,
+            parse_time_us: 0,
+            generation_time_us: 0,
+            total_time_us: 0,
+            token_count: 0,
+            ast_node_count: 0,
+            operation_count: 0,
+            input_size_bytes: 0,
+            output_size_bytes: 0,
+        }
+    }
+
+    /// Creates processing stats with timing information
+    pub const fn with_timing(
+        lex_time_us: u64,
+        parse_time_us: u64,
+        generation_time_us: u64,
+    ) -> Self {
+        Self {
+            lex_time_us,
+            parse_time_us,
+            generation_time_us,
+            total_time_us: lex_time_us + parse_time_us + generation_time_us,
+            token_count: 0,
+            ast_node_count: 0,
+            operation_count: 0,
+            input_size_bytes: 0,
+            output_size_bytes: 0,
+        }
+    }
+}
+
+impl InputInfo {
+    /// Creates input info for file source
+    pub fn from_file(filename: &str, content: &str) -> Self {
+        Self {
+            source_type: "file".to_string(),
+            source_id: filename.to_string(),
+            size_bytes: content.len(),
+            line_count: content.lines().count(),
+        }
+    }
+
+    /// Creates input info for stdin source
+    pub fn from_stdin(content: &str) -> Self {
+        Self {
+            source_type: "stdin".to_string(),
+            source_id: "stdin".to_string(),
+            size_bytes: content.len(),
+            line_count: content.lines().count(),
+        }
+    }
+
+    /// Creates input info for text source
+    pub fn from_text(content: &str) -> Self {
+        Self {
+            source_type: "text".to_string(),
+            source_id: "command_line".to_string(),
+            size_bytes: content.len(),
+            line_count: content.lines().count(),
+        }
+    }
+}
@@ -2,6 +2,9 @@
 //!
 //! This module defines the AST (Abstract Syntax Tree) nodes produced by the parser.
 
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+
 /// Source code location information
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SourceLocation {
@@ -34,6 +37,8 @@ pub enum DplyrNode {
     /// Chain of pipeline operations
     Pipeline {
         source: Option<String>,
+        /// Table function read in place of `source`, e.g. `read_parquet('f.parquet')`
+        source_function: Option<SourceFunction>,
         target: Option<String>,
         operations: Vec<DplyrOperation>,
         location: SourceLocation,
@@ -45,6 +50,65 @@ pub enum DplyrNode {
     },
 }
 
+/// File-reading table function at the head of a pipeline
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceFunction {
+    /// Function name: `read_csv`, `read_parquet`, or `read_json`
+    pub name: String,
+    /// File path argument, rendered as a string literal
+    pub path: String,
+}
+
+/// Table functions accepted as a pipeline source.
+pub const SOURCE_FUNCTIONS: &[&str] = &["read_csv", "read_parquet", "read_json"];
+
+/// Rows kept by an argument-less `head()` / `tail()`, as in R.
+pub const DEFAULT_HEAD_ROWS: usize = 6;
+
+/// Deepest nesting of parentheses, calls, `!` and `^` the parser accepts.
+/// Deeper input is rejected with an error instead of overflowing the stack.
+pub const MAX_NESTING_DEPTH: usize = 64;
+
+/// Deepest expression tree the parser builds and the SQL generator renders.
+/// Operator chains such as `a + b + c` deepen the tree without nesting.
+pub const MAX_EXPRESSION_DEPTH: usize = 2 * MAX_NESTING_DEPTH;
+
+/// dbplyr verbs that only control where results are materialised or shown;
+/// they add nothing to the query and are dropped while parsing.
+pub const NO_OP_VERBS: &[&str] = &["collect", "show_query"];
+
+/// tidyselect helpers that pick columns by name pattern or position; resolving
+/// them needs the table schema, so selections using them are reported as
+/// unsupported.
+pub const TIDYSELECT_HELPERS: &[&str] = &[
+    "everything",
+    "last_col",
+    "starts_with",
+    "ends_with",
+    "contains",
+    "matches",
+    "num_range",
+    "all_of",
+    "any_of",
+    "where",
+];
+
+/// dplyr/tidyr verbs that parse but have no SQL translation yet.
+pub const UNSUPPORTED_VERBS: &[&str] = &[
+    "pivot_longer",
+    "separate",
+    "separate_rows",
+    "unite",
+    "nest",
+    "unnest",
+    "complete",
+    "expand",
+    "drop_na",
+    "relocate",
+    "rowwise",
+    "reframe",
+];
+
 impl DplyrNode {
     /// Returns the location information of the node.
     pub const fn location(&self) -> &SourceLocation {
@@ -65,12 +129,54 @@ impl DplyrNode {
     }
 }
 
+/// Compares two ASTs structurally, ignoring their source locations.
+///
+/// The derived `PartialEq` also compares [`SourceLocation`]s, so the same
+/// pipeline written with different whitespace or line breaks is unequal
+/// under `==`; this treats such inputs as equal.
+pub fn ast_equal(left: &DplyrNode, right: &DplyrNode) -> bool {
+    match (left, right) {
+        (
+            DplyrNode::Pipeline {
+                source,
+                source_function,
+                target,
+                operations,
+                ..
+            },
+            DplyrNode::Pipeline {
+                source: other_source,
+                source_function: other_source_function,
+                target: other_target,
+                operations: other_operations,
+                ..
+            },
+        ) => {
+            source == other_source
+                && source_function == other_source_function
+                && target == other_target
+                && operations.len() == other_operations.len()
+                && operations
+                    .iter()
+                    .zip(other_operations)
+                    .all(|(a, b)| a.without_location() == b.without_location())
+        }
+        (DplyrNode::DataSource { name, .. }, DplyrNode::DataSource { name: other, .. }) => {
+            name == other
+        }
+        _ => false,
+    }
+}
+
 /// dplyr operation types
 #[derive(Debug, Clone, PartialEq)]
 pub enum DplyrOperation {
     /// SELECT operation (column selection)
     Select {
         columns: Vec<ColumnExpr>,
+        /// Columns dropped with `-col`; when set, `columns` is empty and every
+        /// other column is kept.
+        excluded: Vec<String>,
         location: SourceLocation,
     },
     /// WHERE operation (row filtering)
@@ -88,19 +194,52 @@ pub enum DplyrOperation {
         renames: Vec<RenameSpec>,
         location: SourceLocation,
     },
+    /// Rename columns by applying a function to their names (`rename_with(toupper, c(a, b))`)
+    ///
+    /// An empty `columns` list means every column, which requires a schema.
+    RenameWith {
+        function: String,
+        columns: Vec<String>,
+        location: SourceLocation,
+    },
     /// ORDER BY operation (sorting)
     Arrange {
         columns: Vec<OrderExpr>,
         location: SourceLocation,
     },
-    /// GROUP BY operation (grouping)
+    /// GROUP BY operation (grouping); an empty `columns` list without `add`
+    /// removes the grouping, like dplyr's `group_by()`
     GroupBy {
         columns: Vec<String>,
+        /// `.add = TRUE`: append to the current grouping instead of replacing it.
+        add: bool,
+        /// `rollup(...)` / `cube(...)`: summarise() also emits subtotal rows.
+        grouping: GroupingKind,
         location: SourceLocation,
     },
     /// Aggregation operation
     Summarise {
         aggregations: Vec<Aggregation>,
+        /// Grouping left on the result (`.groups`)
+        groups: SummariseGroups,
+        location: SourceLocation,
+    },
+    /// SELECT DISTINCT operation (deduplicate rows, optionally on a subset of columns)
+    Distinct {
+        columns: Vec<String>,
+        location: SourceLocation,
+    },
+    /// Carry the last non-missing value into NA cells (tidyr `fill()`)
+    Fill {
+        columns: Vec<String>,
+        direction: FillDirection,
+        location: SourceLocation,
+    },
+    /// Keep the first or last `n` rows, or `n` rows from a position, per group when
+    /// grouped (`slice_head(n = 1)`, `slice(2:4)`)
+    Slice {
+        position: SlicePosition,
+        n: usize,
         location: SourceLocation,
     },
     /// JOIN operation for combining tables
@@ -115,6 +254,21 @@ pub enum DplyrOperation {
         right_table: String,
         location: SourceLocation,
     },
+    /// tidyr `pivot_wider()` with its output columns listed in `names`: one
+    /// conditional aggregate of `values_from` per name, per group
+    PivotWider {
+        names_from: String,
+        values_from: String,
+        names: Vec<String>,
+        location: SourceLocation,
+    },
+    /// Known dplyr/tidyr verb without a SQL translation yet (see [`UNSUPPORTED_VERBS`])
+    Unsupported {
+        verb: String,
+        /// Explanation reported in place of the generic "not supported yet"
+        reason: Option<String>,
+        location: SourceLocation,
+    },
 }
 
 /// Column rename specification (dplyr-style: new_name = old_name).
@@ -125,6 +279,33 @@ pub struct RenameSpec {
 }
 
 impl DplyrOperation {
+    /// Copy of the operation with its location reset, for [`ast_equal`].
+    fn without_location(&self) -> Self {
+        let mut operation = self.clone();
+        *operation.location_mut() = SourceLocation::unknown();
+        operation
+    }
+
+    fn location_mut(&mut self) -> &mut SourceLocation {
+        match self {
+            Self::Select { location, .. }
+            | Self::Filter { location, .. }
+            | Self::Mutate { location, .. }
+            | Self::Rename { location, .. }
+            | Self::RenameWith { location, .. }
+            | Self::Arrange { location, .. }
+            | Self::GroupBy { location, .. }
+            | Self::Summarise { location, .. }
+            | Self::Distinct { location, .. }
+            | Self::Fill { location, .. }
+            | Self::Slice { location, .. }
+            | Self::Join { location, .. }
+            | Self::SetOp { location, .. }
+            | Self::PivotWider { location, .. }
+            | Self::Unsupported { location, .. } => location,
+        }
+    }
+
     /// Returns the location information of the operation.
     pub const fn location(&self) -> &SourceLocation {
         match self {
@@ -132,11 +313,17 @@ impl DplyrOperation {
             Self::Filter { location, .. } => location,
             Self::Mutate { location, .. } => location,
             Self::Rename { location, .. } => location,
+            Self::RenameWith { location, .. } => location,
             Self::Arrange { location, .. } => location,
             Self::GroupBy { location, .. } => location,
             Self::Summarise { location, .. } => location,
+            Self::Distinct { location, .. } => location,
+            Self::Fill { location, .. } => location,
+            Self::Slice { location, .. } => location,
             Self::Join { location, .. } => location,
             Self::SetOp { location, .. } => location,
+            Self::PivotWider { location, .. } => location,
+            Self::Unsupported { location, .. } => location,
         }
     }
 
@@ -147,15 +334,25 @@ impl DplyrOperation {
             Self::Filter { .. } => "filter",
             Self::Mutate { .. } => "mutate",
             Self::Rename { .. } => "rename",
+            Self::RenameWith { .. } => "rename_with",
             Self::Arrange { .. } => "arrange",
             Self::GroupBy { .. } => "group_by",
             Self::Summarise { .. } => "summarise",
+            Self::Distinct { .. } => "distinct",
+            Self::Fill { .. } => "fill",
+            Self::Slice { position, .. } => match position {
+                SlicePosition::Head => "slice_head",
+                SlicePosition::Tail => "slice_tail",
+                SlicePosition::Rows { .. } => "slice",
+            },
             Self::Join { .. } => "join",
             Self::SetOp { operation, .. } => match operation {
                 SetOperation::Intersect => "intersect",
                 SetOperation::Union => "union",
                 SetOperation::SetDiff => "setdiff",
             },
+            Self::PivotWider { .. } => "pivot_wider",
+            Self::Unsupported { .. } => "unsupported",
         }
     }
 }
@@ -165,6 +362,8 @@ impl DplyrOperation {
 pub enum Expr {
     /// Identifier (column name, variable name, etc.)
     Identifier(String),
+    /// Column of a named data frame, e.g. `df$col`.
+    QualifiedIdentifier { table: String, column: String },
     /// Literal value
     Literal(LiteralValue),
     /// Binary operation
@@ -177,17 +376,36 @@ pub enum Expr {
     Function { name: String, args: Vec<Expr> },
     /// Named function argument, e.g. `sep = " "`.
     NamedArg { name: String, value: Box<Expr> },
+    /// Two-sided formula argument, e.g. `is.na(x) ~ 0` in `case_when()`.
+    Formula { lhs: Box<Expr>, rhs: Box<Expr> },
+    /// Logical negation, e.g. `!is_active`.
+    Not(Box<Expr>),
+    /// Arithmetic negation of a non-literal, e.g. `-x` or `-2^2` (which R reads
+    /// as `-(2^2)`). A negated number literal is folded into the literal.
+    Negate(Box<Expr>),
 }
 
 /// Literal value types
 #[derive(Debug, Clone, PartialEq)]
 pub enum LiteralValue {
     String(String),
+    /// Numeric literal, emitted as the shortest plain decimal that reads back
+    /// as the same `f64` (see [`crate::format_number`]). Decimals keep their
+    /// value but not their spelling: `1000000.50` and `1e3` come out as
+    /// `1000000.5` and `1000`. Whole numbers are exact up to 2^53; the lexer
+    /// rejects longer ones rather than rounding them.
     Number(f64),
     Boolean(bool),
     Null,
 }
 
+/// Returns whether `value` is a finite whole number within the `i64` range.
+///
+/// Stands in for `value.fract() == 0.0`, which needs `std`.
+pub(crate) fn is_whole_number(value: f64) -> bool {
+    value.is_finite() && value == (value as i64) as f64
+}
+
 /// Binary operator types
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum BinaryOp {
@@ -208,6 +426,7 @@ pub enum BinaryOp {
     Minus,
     Multiply,
     Divide,
+    Power,
 }
 
 /// Column expression (with alias support)
@@ -222,6 +441,8 @@ pub struct ColumnExpr {
 pub struct OrderExpr {
     pub column: String,
     pub direction: OrderDirection,
+    /// Explicit NULL placement from `na_last =`; `None` keeps the database default.
+    pub nulls: Option<NullsOrder>,
 }
 
 /// Sort direction
@@ -231,6 +452,13 @@ pub enum OrderDirection {
     Desc,
 }
 
+/// NULL placement in a sort
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NullsOrder {
+    First,
+    Last,
+}
+
 /// Assignment statement (used in mutate)
 #[derive(Debug, Clone, PartialEq)]
 pub struct Assignment {
@@ -239,13 +467,20 @@ pub struct Assignment {
 }
 
 /// Aggregation operation (used in summarise)
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Aggregation {
     pub function: String,
     pub column: String,
     pub alias: Option<String>,
+    /// Aggregates only distinct values: `sum(distinct x)` is `SUM(DISTINCT x)`
+    pub distinct: bool,
+    /// Probability of a quantile aggregate: `0.9` in `quantile(x, 0.9)`
+    pub probability: Option<f64>,
 }
 
+/// Aggregates that take a probability after the column (`quantile(x, 0.9)`).
+pub const QUANTILE_FUNCTIONS: &[&str] = &["quantile", "percentile"];
+
 /// Join type for different join operations
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum JoinType {
@@ -274,6 +509,59 @@ pub struct Join {
     pub spec: JoinSpec,
 }
 
+/// Direction in which `fill()` propagates values (`.direction`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FillDirection {
+    Down, // from earlier rows into later ones (default)
+    Up,
+}
+
+/// Grouping kept after `summarise()` (`.groups`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SummariseGroups {
+    /// Drop the last grouping column (default)
+    #[default]
+    DropLast,
+    /// Drop all grouping
+    Drop,
+    /// Keep every grouping column
+    Keep,
+}
+
+/// Grouping construct a `group_by()` asks summarise() to aggregate over
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GroupingKind {
+    /// One row per combination of the grouping columns (default)
+    #[default]
+    Plain,
+    /// `rollup(a, b)`: per `(a, b)`, per `a`, and a grand total
+    Rollup,
+    /// `cube(a, b)`: per every subset of the grouping columns
+    Cube,
+}
+
+impl GroupingKind {
+    /// Returns the R helper name (`rollup` / `cube`), or `None` for plain grouping.
+    pub const fn helper_name(self) -> Option<&'static str> {
+        match self {
+            Self::Plain => None,
+            Self::Rollup => Some("rollup"),
+            Self::Cube => Some("cube"),
+        }
+    }
+}
+
+/// Which rows `slice_head()` / `slice_tail()` / `slice()` keep
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlicePosition {
+    Head,
+    Tail,
+    /// Rows from the 1-based `start` on, as in `slice(start:end)`
+    Rows {
+        start: usize,
+    },
+}
+
 /// Set operation type (INTERSECT, UNION, EXCEPT)
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SetOperation {
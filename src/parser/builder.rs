@@ -0,0 +1,230 @@
+//! Programmatic pipeline construction.
+//!
+//! Builds the same [`DplyrNode`] the parser produces, so Rust code can create
+//! queries for [`SqlGenerator`](crate::SqlGenerator) without writing dplyr text.
+//! Built nodes carry [`SourceLocation::unknown`] locations.
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+
+use super::ast::{
+    Aggregation, Assignment, BinaryOp, ColumnExpr, DplyrNode, DplyrOperation, Expr, GroupingKind,
+    LiteralValue, OrderDirection, OrderExpr, SourceLocation, SummariseGroups,
+};
+
+/// Fluent builder for a [`DplyrNode::Pipeline`], started with [`DplyrNode::pipeline`].
+///
+/// # Examples
+///
+/// ```rust
+/// use libdplyr::parser::{BinaryOp, DplyrNode, Expr, OrderExpr};
+/// use libdplyr::{PostgreSqlDialect, SqlGenerator};
+///
+/// let ast = DplyrNode::pipeline()
+///     .source("employees")
+///     .select(["name", "salary"])
+///     .filter(Expr::binary(Expr::column("salary"), BinaryOp::GreaterThan, Expr::number(50000.0)))
+///     .arrange([OrderExpr::desc("salary")])
+///     .build();
+///
+/// let sql = SqlGenerator::new(Box::new(PostgreSqlDialect::new()))
+///     .generate(&ast)
+///     .unwrap();
+/// assert!(sql.contains(r#"ORDER BY "salary" DESC"#));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PipelineBuilder {
+    source: Option<String>,
+    operations: Vec<DplyrOperation>,
+}
+
+impl DplyrNode {
+    /// Starts building a pipeline; see [`PipelineBuilder`].
+    pub fn pipeline() -> PipelineBuilder {
+        PipelineBuilder::default()
+    }
+}
+
+impl PipelineBuilder {
+    /// Sets the table the pipeline reads (`data %>% ...`).
+    pub fn source(mut self, table: impl Into<String>) -> Self {
+        self.source = Some(table.into());
+        self
+    }
+
+    /// Appends `select(columns)`.
+    pub fn select<I, S>(self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let columns = columns
+            .into_iter()
+            .map(|column| ColumnExpr {
+                expr: Expr::Identifier(column.into()),
+                alias: None,
+            })
+            .collect();
+        self.operation(DplyrOperation::Select {
+            columns,
+            excluded: Vec::new(),
+            location: SourceLocation::unknown(),
+        })
+    }
+
+    /// Appends `filter(condition)`.
+    pub fn filter(self, condition: Expr) -> Self {
+        self.operation(DplyrOperation::Filter {
+            condition,
+            location: SourceLocation::unknown(),
+        })
+    }
+
+    /// Appends `mutate(column = expr, ...)`.
+    pub fn mutate<I, S>(self, assignments: I) -> Self
+    where
+        I: IntoIterator<Item = (S, Expr)>,
+        S: Into<String>,
+    {
+        let assignments = assignments
+            .into_iter()
+            .map(|(column, expr)| Assignment {
+                column: column.into(),
+                expr,
+            })
+            .collect();
+        self.operation(DplyrOperation::Mutate {
+            assignments,
+            location: SourceLocation::unknown(),
+        })
+    }
+
+    /// Appends `arrange(...)`.
+    pub fn arrange(self, columns: impl IntoIterator<Item = OrderExpr>) -> Self {
+        self.operation(DplyrOperation::Arrange {
+            columns: columns.into_iter().collect(),
+            location: SourceLocation::unknown(),
+        })
+    }
+
+    /// Appends `group_by(columns)`.
+    pub fn group_by<I, S>(self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.operation(DplyrOperation::GroupBy {
+            columns: columns.into_iter().map(Into::into).collect(),
+            add: false,
+            grouping: GroupingKind::Plain,
+            location: SourceLocation::unknown(),
+        })
+    }
+
+    /// Appends `summarise(...)`.
+    pub fn summarise(self, aggregations: impl IntoIterator<Item = Aggregation>) -> Self {
+        self.operation(DplyrOperation::Summarise {
+            aggregations: aggregations.into_iter().collect(),
+            groups: SummariseGroups::default(),
+            location: SourceLocation::unknown(),
+        })
+    }
+
+    /// Appends any operation, for verbs without a dedicated method.
+    pub fn operation(mut self, operation: DplyrOperation) -> Self {
+        self.operations.push(operation);
+        self
+    }
+
+    /// Finishes the pipeline.
+    pub fn build(self) -> DplyrNode {
+        DplyrNode::Pipeline {
+            source: self.source,
+            source_function: None,
+            target: None,
+            operations: self.operations,
+            location: SourceLocation::unknown(),
+        }
+    }
+}
+
+impl Expr {
+    /// Column reference, as `name` in dplyr code.
+    pub fn column(name: impl Into<String>) -> Self {
+        Self::Identifier(name.into())
+    }
+
+    /// Numeric literal.
+    pub const fn number(value: f64) -> Self {
+        Self::Literal(LiteralValue::Number(value))
+    }
+
+    /// String literal.
+    pub fn string(value: impl Into<String>) -> Self {
+        Self::Literal(LiteralValue::String(value.into()))
+    }
+
+    /// Binary operation such as `left > right` or `left & right`.
+    pub fn binary(left: Self, operator: BinaryOp, right: Self) -> Self {
+        Self::Binary {
+            left: Box::new(left),
+            operator,
+            right: Box::new(right),
+        }
+    }
+
+    /// Function call such as `mean(x)`.
+    pub fn call(name: impl Into<String>, args: Vec<Self>) -> Self {
+        Self::Function {
+            name: name.into(),
+            args,
+        }
+    }
+}
+
+impl OrderExpr {
+    /// Ascending sort on `column`, as `arrange(column)`.
+    pub fn asc(column: impl Into<String>) -> Self {
+        Self {
+            column: column.into(),
+            direction: OrderDirection::Asc,
+            nulls: None,
+        }
+    }
+
+    /// Descending sort on `column`, as `arrange(desc(column))`.
+    pub fn desc(column: impl Into<String>) -> Self {
+        Self {
+            column: column.into(),
+            direction: OrderDirection::Desc,
+            nulls: None,
+        }
+    }
+}
+
+impl Aggregation {
+    /// Aggregate `alias = function(column)`; an empty `column` is `n()`-style.
+    pub fn new(
+        alias: impl Into<String>,
+        function: impl Into<String>,
+        column: impl Into<String>,
+    ) -> Self {
+        Self {
+            function: function.into(),
+            column: column.into(),
+            alias: Some(alias.into()),
+            distinct: false,
+            probability: None,
+        }
+    }
+
+    /// Aggregates only the distinct values of the column.
+    pub const fn distinct(mut self) -> Self {
+        self.distinct = true;
+        self
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/builder_tests.rs"]
+mod tests;
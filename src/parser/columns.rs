@@ -0,0 +1,148 @@
+//! Column reference collection.
+//!
+//! Lists the source columns a pipeline reads, for query planning. Columns the
+//! pipeline defines itself (mutate() targets, aliases, rename() targets) are
+//! only counted when read before they are defined.
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::collections::HashSet;
+
+use super::ast::{DplyrNode, DplyrOperation, Expr};
+
+/// Returns the names of the source columns `ast` reads.
+///
+/// Columns of other data frames (`df$col`) and `rename_with()` over every
+/// column are not included, since neither names a source column.
+pub fn referenced_columns(ast: &DplyrNode) -> HashSet<String> {
+    let mut collector = ColumnCollector::default();
+    if let DplyrNode::Pipeline { operations, .. } = ast {
+        for operation in operations {
+            collector.visit_operation(operation);
+        }
+    }
+    collector.referenced
+}
+
+#[derive(Default)]
+struct ColumnCollector {
+    referenced: HashSet<String>,
+    defined: HashSet<String>,
+}
+
+impl ColumnCollector {
+    fn visit_operation(&mut self, operation: &DplyrOperation) {
+        match operation {
+            DplyrOperation::Select {
+                columns, excluded, ..
+            } => {
+                for column in columns {
+                    self.visit_expr(&column.expr);
+                }
+                for column in excluded {
+                    self.read(column);
+                }
+                for alias in columns.iter().filter_map(|column| column.alias.as_ref()) {
+                    self.define(alias);
+                }
+            }
+            DplyrOperation::Filter { condition, .. } => self.visit_expr(condition),
+            DplyrOperation::Mutate { assignments, .. } => {
+                for assignment in assignments {
+                    self.visit_expr(&assignment.expr);
+                    self.define(&assignment.column);
+                }
+            }
+            DplyrOperation::Rename { renames, .. } => {
+                for rename in renames {
+                    self.read(&rename.old_name);
+                    self.define(&rename.new_name);
+                }
+            }
+            DplyrOperation::RenameWith { columns, .. }
+            | DplyrOperation::GroupBy { columns, .. }
+            | DplyrOperation::Distinct { columns, .. }
+            | DplyrOperation::Fill { columns, .. } => {
+                for column in columns {
+                    self.read(column);
+                }
+            }
+            DplyrOperation::Arrange { columns, .. } => {
+                for column in columns {
+                    self.read(&column.column);
+                }
+            }
+            DplyrOperation::Summarise { aggregations, .. } => {
+                for aggregation in aggregations {
+                    // n() has no column
+                    if !aggregation.column.is_empty() {
+                        self.read(&aggregation.column);
+                    }
+                }
+                for alias in aggregations.iter().filter_map(|agg| agg.alias.as_ref()) {
+                    self.define(alias);
+                }
+            }
+            DplyrOperation::PivotWider {
+                names_from,
+                values_from,
+                names,
+                ..
+            } => {
+                self.read(names_from);
+                self.read(values_from);
+                for name in names {
+                    self.define(name);
+                }
+            }
+            DplyrOperation::Join { spec, .. } => {
+                if let Some(column) = &spec.by_column {
+                    self.read(column);
+                }
+                if let Some(on_expr) = &spec.on_expr {
+                    self.visit_expr(on_expr);
+                }
+            }
+            DplyrOperation::Slice { .. }
+            | DplyrOperation::SetOp { .. }
+            | DplyrOperation::Unsupported { .. } => {}
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Identifier(name) => self.read(name),
+            Expr::QualifiedIdentifier { .. } | Expr::Literal(_) => {}
+            Expr::Binary { left, right, .. } => {
+                self.visit_expr(left);
+                self.visit_expr(right);
+            }
+            Expr::Function { args, .. } => {
+                for arg in args {
+                    self.visit_expr(arg);
+                }
+            }
+            Expr::NamedArg { value, .. } | Expr::Not(value) | Expr::Negate(value) => {
+                self.visit_expr(value)
+            }
+            Expr::Formula { lhs, rhs } => {
+                self.visit_expr(lhs);
+                self.visit_expr(rhs);
+            }
+        }
+    }
+
+    fn read(&mut self, column: &str) {
+        if !self.defined.contains(column) {
+            self.referenced.insert(column.to_string());
+        }
+    }
+
+    fn define(&mut self, column: &str) {
+        self.defined.insert(column.to_string());
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/columns_tests.rs"]
+mod tests;
@@ -4,7 +4,15 @@
 //! smaller modules.
 
 pub mod ast;
+pub mod builder;
+pub mod columns;
 pub mod parse;
+pub mod print;
+pub mod simplify;
 
 pub use ast::*;
+pub use builder::PipelineBuilder;
+pub use columns::referenced_columns;
 pub use parse::Parser;
+pub use print::expr_to_string;
+pub use simplify::fold_negations;
@@ -2,17 +2,57 @@
 //!
 //! Provides functionality to convert tokens to AST (Abstract Syntax Tree).
 
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
 use crate::error::{ParseError, ParseResult};
 use crate::lexer::{Lexer, Token};
 use crate::PipeSyntax;
 
 pub use super::ast::*;
+use super::print::expr_to_string;
+
+/// Where the parser reads its tokens from.
+enum TokenSource {
+    Lexer(Lexer),
+    /// Tokens lexed ahead of time, read in order; EOF once they run out.
+    Tokens {
+        tokens: Vec<Token>,
+        next: usize,
+    },
+}
+
+impl TokenSource {
+    fn next_token(&mut self) -> ParseResult<Token> {
+        match self {
+            Self::Lexer(lexer) => Ok(lexer.next_token()?),
+            Self::Tokens { tokens, next } => {
+                let token = tokens.get(*next).cloned().unwrap_or(Token::EOF);
+                *next += 1;
+                Ok(token)
+            }
+        }
+    }
+
+    fn peek_token(&mut self) -> ParseResult<Token> {
+        match self {
+            Self::Lexer(lexer) => Ok(lexer.peek_token()?),
+            Self::Tokens { tokens, next } => Ok(tokens.get(*next).cloned().unwrap_or(Token::EOF)),
+        }
+    }
+
+    fn comments(&self) -> Vec<String> {
+        match self {
+            Self::Lexer(lexer) => lexer.comments(),
+            Self::Tokens { .. } => Vec::new(),
+        }
+    }
+}
 
 /// Parser struct
 ///
 /// Provides functionality to parse dplyr tokens into an Abstract Syntax Tree (AST).
 pub struct Parser {
-    lexer: Lexer,
+    tokens: TokenSource,
     pipe_syntax: PipeSyntax,
     lazy_input_context: Option<LazyInput>,
     lazy_input_consumed: bool,
@@ -20,6 +60,12 @@ pub struct Parser {
     position: usize,
     line: usize,
     column: usize,
+    /// Token positions of the `(` not yet closed, innermost last.
+    open_parens: Vec<usize>,
+    /// Expressions currently being parsed, innermost last.
+    expression_depth: usize,
+    precedence_ambiguities: Vec<String>,
+    default_head_rows: usize,
 }
 
 impl Parser {
@@ -42,11 +88,54 @@ impl Parser {
     /// let lexer = Lexer::new("select(name)".to_string());
     /// let parser = Parser::new(lexer).unwrap();
     /// ```
-    pub fn new(mut lexer: Lexer) -> ParseResult<Self> {
+    pub fn new(lexer: Lexer) -> ParseResult<Self> {
         let pipe_syntax = lexer.pipe_syntax();
-        let current_token = lexer.next_token()?;
-        Ok(Self {
-            lexer,
+        let mut tokens = TokenSource::Lexer(lexer);
+        let current_token = tokens.next_token()?;
+        Ok(Self::with_token_source(tokens, current_token, pipe_syntax))
+    }
+
+    /// Creates a parser over tokens that were already lexed, for callers that
+    /// tokenize once and parse the same buffer repeatedly.
+    ///
+    /// A missing trailing [`Token::EOF`] is implied. Comments are not part of
+    /// the token stream, so [`Parser::comments`] is empty. Tokens lexed with
+    /// [`PipeSyntax::Native`] need [`Parser::with_pipe_syntax`] as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use libdplyr::lexer::{Lexer, Token};
+    /// use libdplyr::parser::Parser;
+    ///
+    /// let mut lexer = Lexer::new("select(name)".to_string());
+    /// let mut tokens = Vec::new();
+    /// loop {
+    ///     let token = lexer.next_token().unwrap();
+    ///     if token == Token::EOF {
+    ///         break;
+    ///     }
+    ///     tokens.push(token);
+    /// }
+    /// let ast = Parser::from_tokens(tokens).parse().unwrap();
+    /// assert!(ast.is_pipeline());
+    /// ```
+    pub fn from_tokens(tokens: Vec<Token>) -> Self {
+        let current_token = tokens.first().cloned().unwrap_or(Token::EOF);
+        Self::with_token_source(
+            TokenSource::Tokens { tokens, next: 1 },
+            current_token,
+            PipeSyntax::default(),
+        )
+    }
+
+    fn with_token_source(
+        tokens: TokenSource,
+        current_token: Token,
+        pipe_syntax: PipeSyntax,
+    ) -> Self {
+        Self {
+            tokens,
             pipe_syntax,
             lazy_input_context: None,
             lazy_input_consumed: false,
@@ -54,7 +143,26 @@ impl Parser {
             position: 0,
             line: 1,
             column: 1,
-        })
+            open_parens: Vec::new(),
+            expression_depth: 0,
+            precedence_ambiguities: Vec::new(),
+            default_head_rows: DEFAULT_HEAD_ROWS,
+        }
+    }
+
+    /// Sets the pipe syntax the tokens were lexed with (default magrittr).
+    ///
+    /// Only needed with [`Parser::from_tokens`]; [`Parser::new`] takes it from
+    /// the lexer.
+    pub const fn with_pipe_syntax(mut self, pipe_syntax: PipeSyntax) -> Self {
+        self.pipe_syntax = pipe_syntax;
+        self
+    }
+
+    /// Sets the row count of an argument-less `head()` / `tail()` (default 6).
+    pub const fn with_default_head_rows(mut self, rows: usize) -> Self {
+        self.default_head_rows = rows;
+        self
     }
 
     /// Parses dplyr code to generate an AST.
@@ -65,6 +173,11 @@ impl Parser {
     pub fn parse(&mut self) -> ParseResult<DplyrNode> {
         let node = self.parse_pipeline()?;
         self.skip_newlines()?;
+        if self.current_token == Token::RightParen {
+            return Err(ParseError::UnmatchedClosingParenthesis {
+                position: self.position,
+            });
+        }
         if self.current_token != Token::EOF {
             return Err(ParseError::UnexpectedToken {
                 expected: "end of input".to_string(),
@@ -75,6 +188,17 @@ impl Parser {
         Ok(node)
     }
 
+    /// Returns the `#` comments the lexer has skipped so far, in source order.
+    pub fn comments(&self) -> Vec<String> {
+        self.tokens.comments()
+    }
+
+    /// Returns the conditions parsed so far that mix `&` and `|` at one level
+    /// without parentheses, rendered as dplyr source.
+    pub fn precedence_ambiguities(&self) -> Vec<String> {
+        self.precedence_ambiguities.clone()
+    }
+
     /// Returns the current source location.
     const fn current_location(&self) -> SourceLocation {
         SourceLocation::new(self.line, self.column, self.position)
@@ -82,23 +206,45 @@ impl Parser {
 
     /// Advances to the next token and updates position tracking.
     fn advance(&mut self) -> ParseResult<()> {
-        // Update line and column tracking
-        if self.current_token == Token::Newline {
-            self.line += 1;
-            self.column = 1;
-        } else {
-            self.column += 1;
+        match self.current_token {
+            Token::LeftParen => self.open_parens.push(self.position),
+            Token::RightParen => {
+                self.open_parens.pop();
+            }
+            _ => {}
         }
 
-        self.current_token = self.lexer.next_token()?;
-        self.position += 1;
-        Ok(())
+        loop {
+            // Update line and column tracking
+            if self.current_token == Token::Newline {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+
+            self.current_token = self.tokens.next_token()?;
+            self.position += 1;
+
+            // As in R, line breaks inside parentheses do not end an expression
+            if self.open_parens.is_empty() || self.current_token != Token::Newline {
+                return Ok(());
+            }
+        }
     }
 
     /// Checks if the current token matches the expected token and advances.
     fn expect_token(&mut self, expected: Token) -> ParseResult<()> {
-        if std::mem::discriminant(&self.current_token) == std::mem::discriminant(&expected) {
+        if core::mem::discriminant(&self.current_token) == core::mem::discriminant(&expected) {
             self.advance()
+        } else if let (Token::RightParen, Token::EOF | Token::Pipe, Some(&opened_at)) =
+            (&expected, &self.current_token, self.open_parens.last())
+        {
+            // The call ran into the end of its pipeline step without closing
+            Err(ParseError::UnclosedParenthesis {
+                opened_at,
+                position: self.position,
+            })
         } else {
             Err(ParseError::UnexpectedToken {
                 expected: format!("{expected}"),
@@ -123,7 +269,7 @@ impl Parser {
     #[allow(dead_code)]
     fn match_token(&self, tokens: &[Token]) -> bool {
         tokens.iter().any(|token| {
-            std::mem::discriminant(&self.current_token) == std::mem::discriminant(token)
+            core::mem::discriminant(&self.current_token) == core::mem::discriminant(token)
         })
     }
 
@@ -213,6 +359,7 @@ impl Parser {
 
                 return Ok(DplyrNode::Pipeline {
                     source: Some(name),
+                    source_function: None,
                     target,
                     operations,
                     location: start_location,
@@ -282,10 +429,65 @@ impl Parser {
 
                 return Ok(DplyrNode::Pipeline {
                     source,
+                    source_function: None,
                     target,
                     operations,
                     location: start_location,
                 });
+            } else if self.current_token == Token::LeftParen
+                && SOURCE_FUNCTIONS.contains(&name.as_str())
+            {
+                // File-reading table function as the data source: read_parquet('f.parquet') %>% ...
+                let source_function = self.parse_source_function_call(name)?;
+                return self.parse_source_function_pipeline(source_function, start_location);
+            } else if self.current_token == Token::LeftParen
+                && (UNSUPPORTED_VERBS.contains(&name.as_str())
+                    || matches!(
+                        name.as_str(),
+                        "head" | "tail" | "count" | "tally" | "pivot_wider" | "replace_na"
+                    ))
+            {
+                // Leading unsupported verb, head()/tail(), count()/tally(), pivot_wider()
+                // or replace_na() without a data source: head() %>% ...
+                let leading = match name.as_str() {
+                    "pivot_wider" => {
+                        vec![self.parse_pivot_wider_arguments(start_location.clone())?]
+                    }
+                    "replace_na" => {
+                        vec![self.parse_replace_na_arguments(start_location.clone())?]
+                    }
+                    "count" | "tally" => {
+                        self.parse_count_arguments(&name, start_location.clone())?
+                    }
+                    "head" | "tail" => {
+                        let position = if name == "head" {
+                            SlicePosition::Head
+                        } else {
+                            SlicePosition::Tail
+                        };
+                        let rows = self.default_head_rows;
+                        vec![self.parse_slice_arguments(
+                            &name,
+                            position,
+                            rows,
+                            start_location.clone(),
+                        )?]
+                    }
+                    _ => vec![self.parse_unsupported_verb(name, start_location.clone())?],
+                };
+                operations.extend(leading);
+                while self.current_token == Token::Pipe {
+                    self.advance()?; // Skip %>%
+                    self.skip_newlines()?;
+                    operations.extend(self.parse_pipeline_step()?);
+                }
+                return Ok(DplyrNode::Pipeline {
+                    source: None,
+                    source_function: None,
+                    target: None,
+                    operations,
+                    location: start_location,
+                });
             } else if self.current_token == Token::LeftParen {
                 // This might be a function call, backtrack and parse as operation
                 // We need to handle this case by creating a synthetic identifier token
@@ -343,12 +545,63 @@ impl Parser {
 
         Ok(DplyrNode::Pipeline {
             source: None,
+            source_function: None,
             target,
             operations,
             location: start_location,
         })
     }
 
+    /// Parses the `('path')` argument list of a source table function.
+    fn parse_source_function_call(&mut self, name: String) -> ParseResult<SourceFunction> {
+        self.expect_token(Token::LeftParen)?;
+        let path = match &self.current_token {
+            Token::String(path) => path.clone(),
+            _ => {
+                return Err(ParseError::UnexpectedToken {
+                    expected: format!("file path string for {name}()"),
+                    found: format!("{}", self.current_token),
+                    position: self.position,
+                })
+            }
+        };
+        self.advance()?;
+        self.expect_token(Token::RightParen)?;
+        Ok(SourceFunction { name, path })
+    }
+
+    /// Parses the operations piped out of a source table function.
+    fn parse_source_function_pipeline(
+        &mut self,
+        source_function: SourceFunction,
+        start_location: SourceLocation,
+    ) -> ParseResult<DplyrNode> {
+        self.skip_newlines()?;
+        if self.current_token != Token::Pipe {
+            return Err(ParseError::UnexpectedToken {
+                expected: "pipe operator".to_string(),
+                found: format!("{}", self.current_token),
+                position: self.position,
+            });
+        }
+
+        let mut operations = Vec::new();
+        while self.current_token == Token::Pipe {
+            self.advance()?; // Skip %>%
+            self.skip_newlines()?;
+            operations.extend(self.parse_pipeline_step()?);
+        }
+        self.skip_newlines()?;
+
+        Ok(DplyrNode::Pipeline {
+            source: None,
+            source_function: Some(source_function),
+            target: None,
+            operations,
+            location: start_location,
+        })
+    }
+
     /// Parses one pipeline step. A native-pipe lambda RHS like
     /// `(\(x) x |> select(col))()` is normalized to the operations in its body.
     fn parse_pipeline_step(&mut self) -> ParseResult<Vec<DplyrOperation>> {
@@ -363,12 +616,9 @@ impl Parser {
                 self.parse_magrittr_lambda_pipeline_application(Token::LeftParen, Token::RightParen)
             }
             (PipeSyntax::Magrittr, _) => {
-                Ok(vec![self.parse_operation_with_lazy_input(
-                    LazyInput::MagrittrDot,
-                    false,
-                )?])
+                self.parse_operation_with_lazy_input(LazyInput::MagrittrDot, false)
             }
-            _ => Ok(vec![self.parse_operation()?]),
+            _ => self.parse_operations(),
         }
     }
 
@@ -376,20 +626,20 @@ impl Parser {
         &mut self,
         input: LazyInput,
         require_input: bool,
-    ) -> ParseResult<DplyrOperation> {
+    ) -> ParseResult<Vec<DplyrOperation>> {
         let previous_context = self.lazy_input_context.clone();
         let previous_consumed = self.lazy_input_consumed;
 
         self.lazy_input_context = Some(input);
         self.lazy_input_consumed = false;
 
-        let result = self.parse_operation();
+        let result = self.parse_operations();
         let consumed = self.lazy_input_consumed;
 
         self.lazy_input_context = previous_context;
         self.lazy_input_consumed = previous_consumed;
 
-        let operation = result?;
+        let operations = result?;
         if require_input && !consumed {
             return Err(ParseError::InvalidOperation {
                 operation: "lambda body must consume the piped data argument".to_string(),
@@ -397,12 +647,15 @@ impl Parser {
             });
         }
 
-        Ok(operation)
+        Ok(operations)
     }
 
     fn consume_optional_lazy_data_argument(&mut self) -> ParseResult<()> {
         let should_consume = match &self.lazy_input_context {
-            Some(LazyInput::MagrittrDot) => self.current_token == Token::Dot,
+            Some(LazyInput::MagrittrDot) => {
+                self.current_token == Token::Dot
+                    && matches!(self.peek_token()?, Token::Comma | Token::RightParen)
+            }
             Some(LazyInput::NativeParameter(param)) => {
                 if let Token::Identifier(name) = &self.current_token {
                     name == param && matches!(self.peek_token()?, Token::Comma | Token::RightParen)
@@ -434,7 +687,7 @@ impl Parser {
     }
 
     fn peek_token(&mut self) -> ParseResult<Token> {
-        Ok(self.lexer.peek_token()?)
+        self.tokens.peek_token()
     }
 
     fn parse_magrittr_lambda_pipeline_application(
@@ -450,15 +703,15 @@ impl Parser {
             self.skip_newlines()?;
             self.expect_token(Token::Pipe)?;
             self.skip_newlines()?;
-            vec![self.parse_operation_with_lazy_input(LazyInput::MagrittrDot, false)?]
+            self.parse_operation_with_lazy_input(LazyInput::MagrittrDot, false)?
         } else {
-            vec![self.parse_operation_with_lazy_input(LazyInput::MagrittrDot, true)?]
+            self.parse_operation_with_lazy_input(LazyInput::MagrittrDot, true)?
         };
 
         while self.current_token == Token::Pipe {
             self.advance()?;
             self.skip_newlines()?;
-            operations.push(self.parse_operation_with_lazy_input(LazyInput::MagrittrDot, false)?);
+            operations.extend(self.parse_operation_with_lazy_input(LazyInput::MagrittrDot, false)?);
         }
 
         self.skip_newlines()?;
@@ -496,22 +749,21 @@ impl Parser {
                 self.skip_newlines()?;
                 self.expect_token(Token::Pipe)?;
                 self.skip_newlines()?;
-                vec![self.parse_operation()?]
+                self.parse_operations()?
             } else {
-                vec![self.parse_operation_with_lazy_input(
+                self.parse_operation_with_lazy_input(
                     LazyInput::NativeParameter(param.clone()),
                     true,
-                )?]
+                )?
             }
         } else {
-            vec![self
-                .parse_operation_with_lazy_input(LazyInput::NativeParameter(param.clone()), true)?]
+            self.parse_operation_with_lazy_input(LazyInput::NativeParameter(param.clone()), true)?
         };
 
         while self.current_token == Token::Pipe {
             self.advance()?;
             self.skip_newlines()?;
-            operations.push(self.parse_operation_with_lazy_input(
+            operations.extend(self.parse_operation_with_lazy_input(
                 LazyInput::NativeParameter(param.clone()),
                 false,
             )?);
@@ -525,16 +777,55 @@ impl Parser {
         Ok(operations)
     }
 
+    /// Parses one dplyr verb, which shorthand verbs such as count() expand into
+    /// several operations and no-op verbs such as collect() into none.
+    fn parse_operations(&mut self) -> ParseResult<Vec<DplyrOperation>> {
+        match &self.current_token {
+            // Not keywords, so columns may still be named `count` or `tally`
+            Token::Identifier(name) if name == "count" || name == "tally" => self.parse_count(),
+            Token::Transmute => self.parse_transmute(),
+            Token::Filter => self.parse_filter(),
+            Token::Mutate => self.parse_mutate(),
+            Token::Summarise => self.parse_summarise(),
+            Token::Identifier(name) if NO_OP_VERBS.contains(&name.as_str()) => {
+                let verb = name.clone();
+                self.advance()?; // Skip verb name
+                self.expect_token(Token::LeftParen)?;
+                if self.current_token != Token::RightParen {
+                    return Err(ParseError::TooManyArguments {
+                        function: verb,
+                        position: self.position,
+                    });
+                }
+                self.advance()?; // Skip ')'
+                Ok(Vec::new())
+            }
+            _ => Ok(vec![self.parse_operation()?]),
+        }
+    }
+
     /// Parses individual dplyr operations.
     fn parse_operation(&mut self) -> ParseResult<DplyrOperation> {
         match &self.current_token {
             Token::Select => self.parse_select(),
-            Token::Filter => self.parse_filter(),
-            Token::Mutate => self.parse_mutate(),
             Token::Rename => self.parse_rename(),
+            Token::RenameWith => self.parse_rename_with(),
             Token::Arrange => self.parse_arrange(),
             Token::GroupBy => self.parse_group_by(),
-            Token::Summarise => self.parse_summarise(),
+            Token::Distinct => self.parse_distinct(),
+            Token::Fill => self.parse_fill(),
+            Token::Slice => self.parse_slice_rows(),
+            Token::SliceHead => self.parse_slice(SlicePosition::Head, 1),
+            Token::SliceTail => self.parse_slice(SlicePosition::Tail, 1),
+            // Not keywords, so columns may still be named `head` / `tail`
+            Token::Identifier(name) if name == "head" => {
+                self.parse_slice(SlicePosition::Head, self.default_head_rows)
+            }
+            Token::Identifier(name) if name == "tail" => {
+                self.parse_slice(SlicePosition::Tail, self.default_head_rows)
+            }
+            Token::Identifier(name) if name == "replace_na" => self.parse_replace_na(),
+            Token::Identifier(name) if name == "pivot_wider" => self.parse_pivot_wider(),
             Token::InnerJoin
             | Token::LeftJoin
             | Token::RightJoin
@@ -544,6 +835,12 @@ impl Parser {
             Token::Intersect => self.parse_set_op(SetOperation::Intersect),
             Token::Union => self.parse_set_op(SetOperation::Union),
             Token::SetDiff => self.parse_set_op(SetOperation::SetDiff),
+            Token::Identifier(name) if UNSUPPORTED_VERBS.contains(&name.as_str()) => {
+                let location = self.current_location();
+                let verb = name.clone();
+                self.advance()?; // Skip verb name
+                self.parse_unsupported_verb(verb, location)
+            }
             _ => Err(ParseError::UnexpectedToken {
                 expected: "dplyr function".to_string(),
                 found: format!("{}", self.current_token),
@@ -552,6 +849,37 @@ impl Parser {
         }
     }
 
+    /// Skips the argument list of a known-but-unsupported verb, keeping only its name
+    /// so generation can report it (see [`UNSUPPORTED_VERBS`]).
+    fn parse_unsupported_verb(
+        &mut self,
+        verb: String,
+        location: SourceLocation,
+    ) -> ParseResult<DplyrOperation> {
+        self.skip_call_arguments()?;
+        Ok(DplyrOperation::Unsupported {
+            verb,
+            reason: None,
+            location,
+        })
+    }
+
+    /// Skips a parenthesized argument list, including nested parentheses.
+    fn skip_call_arguments(&mut self) -> ParseResult<()> {
+        self.expect_token(Token::LeftParen)?;
+        let mut depth = 1usize;
+        while depth > 0 {
+            match self.current_token {
+                Token::LeftParen => depth += 1,
+                Token::RightParen => depth -= 1,
+                Token::EOF => return Err(ParseError::UnexpectedEof(self.position)),
+                _ => {}
+            }
+            self.advance()?;
+        }
+        Ok(())
+    }
+
     /// Parses select() operation.
     fn parse_select(&mut self) -> ParseResult<DplyrOperation> {
         let location = self.current_location();
@@ -560,126 +888,498 @@ impl Parser {
         self.consume_optional_lazy_data_argument()?;
 
         let mut columns = Vec::new();
+        let mut excluded = Vec::new();
+        let mut schema_selection = None;
 
-        // First column
         if self.current_token != Token::RightParen {
-            columns.push(self.parse_column_expr()?);
-
-            // Additional columns (comma-separated)
-            while self.current_token == Token::Comma {
+            loop {
+                if let Some(helper) = self.skip_tidyselect_helper()? {
+                    schema_selection = schema_selection.or(Some(helper));
+                } else if self.current_token == Token::Minus
+                    && matches!(self.peek_token()?, Token::Identifier(_) | Token::String(_))
+                {
+                    self.advance()?; // Skip '-'
+                    excluded.push(self.parse_identifier_like("column name after '-'")?);
+                } else {
+                    columns.push(self.parse_select_column()?);
+                }
+                if !columns.is_empty() && !excluded.is_empty() {
+                    return Err(ParseError::InvalidOperation {
+                        operation: "select() cannot mix kept columns with dropped (-col) columns"
+                            .to_string(),
+                        position: self.position,
+                    });
+                }
+                if self.current_token != Token::Comma {
+                    break;
+                }
                 self.advance()?; // Skip comma
-                columns.push(self.parse_column_expr()?);
             }
         }
 
         self.expect_token(Token::RightParen)?;
-        Ok(DplyrOperation::Select { columns, location })
+        if let Some(selection) = schema_selection {
+            return Ok(Self::schema_dependent_selection(
+                "select", &selection, location,
+            ));
+        }
+        Ok(DplyrOperation::Select {
+            columns,
+            excluded,
+            location,
+        })
+    }
+
+    /// Parses one select() column, rejecting `a:c` / `1:3` ranges.
+    ///
+    /// Ranges can only be resolved against the table schema, which the
+    /// transpiler does not have, so they are reported instead of mis-parsed.
+    fn parse_select_column(&mut self) -> ParseResult<ColumnExpr> {
+        let column = self.parse_column_expr()?;
+        if self.current_token != Token::Colon {
+            return Ok(column);
+        }
+
+        let position = self.position;
+        self.advance()?; // Skip ':'
+        let operation = match (&column.expr, &self.current_token) {
+            (Expr::Literal(LiteralValue::Number(start)), Token::Number(end)) => format!(
+                "positional select ({start}:{end}) needs the table schema; select columns by name"
+            ),
+            (Expr::Identifier(start), Token::Identifier(end)) => format!(
+                "column range ({start}:{end}) needs the table schema; list the columns explicitly"
+            ),
+            _ => "column ranges in select() need the table schema; list the columns explicitly"
+                .to_string(),
+        };
+        Err(ParseError::InvalidOperation {
+            operation,
+            position,
+        })
     }
 
     /// Parses filter() operation.
-    fn parse_filter(&mut self) -> ParseResult<DplyrOperation> {
+    fn parse_filter(&mut self) -> ParseResult<Vec<DplyrOperation>> {
         let location = self.current_location();
         self.advance()?; // Skip 'filter'
         self.expect_token(Token::LeftParen)?;
         self.consume_optional_lazy_data_argument()?;
 
-        let condition = self.parse_expression()?;
+        // Comma-separated conditions are combined with AND, as in dplyr
+        let mut condition: Option<Expr> = None;
+        let mut by = None;
+        loop {
+            if self.at_by_argument()? {
+                by = Some(self.parse_by_argument()?);
+            } else {
+                let next = self.parse_expression()?;
+                condition = Some(match condition {
+                    Some(condition) => Expr::Binary {
+                        left: Box::new(condition),
+                        operator: BinaryOp::And,
+                        right: Box::new(next),
+                    },
+                    None => next,
+                });
+            }
+            if self.current_token != Token::Comma {
+                break;
+            }
+            self.advance()?; // Skip comma
+        }
 
+        let Some(condition) = condition else {
+            return Err(ParseError::MissingArgument {
+                function: "filter".to_string(),
+                position: self.position,
+            });
+        };
         self.expect_token(Token::RightParen)?;
-        Ok(DplyrOperation::Filter {
+        let filter = DplyrOperation::Filter {
             condition,
-            location,
-        })
+            location: location.clone(),
+        };
+        Ok(scope_to_by(vec![filter], by, location))
     }
 
     /// Parses mutate() operation.
-    fn parse_mutate(&mut self) -> ParseResult<DplyrOperation> {
+    fn parse_mutate(&mut self) -> ParseResult<Vec<DplyrOperation>> {
         let location = self.current_location();
-        self.advance()?; // Skip 'mutate'
+        let arguments = self.parse_mutate_assignments()?;
+        if let Some(selection) = arguments.schema_selection {
+            return Ok(vec![Self::schema_dependent_selection(
+                "mutate", &selection, location,
+            )]);
+        }
+        let mutate = DplyrOperation::Mutate {
+            assignments: arguments.assignments,
+            location: location.clone(),
+        };
+        Ok(scope_to_by(vec![mutate], arguments.by, location))
+    }
+
+    /// Parses the argument list of mutate() or transmute(), including the verb name.
+    fn parse_mutate_assignments(&mut self) -> ParseResult<MutateArguments> {
+        self.advance()?; // Skip 'mutate' / 'transmute'
         self.expect_token(Token::LeftParen)?;
         self.consume_optional_lazy_data_argument()?;
 
-        let mut assignments = Vec::new();
+        let mut arguments = MutateArguments::default();
 
-        // First assignment
+        // Assignments and the `.by` argument (comma-separated)
         if self.current_token != Token::RightParen {
-            assignments.push(self.parse_assignment()?);
-
-            // Additional assignments (comma-separated)
-            while self.current_token == Token::Comma {
+            loop {
+                if self.at_by_argument()? {
+                    arguments.by = Some(self.parse_by_argument()?);
+                } else {
+                    let selection = self.parse_mutate_argument(&mut arguments.assignments)?;
+                    arguments.schema_selection = arguments.schema_selection.or(selection);
+                }
+                if self.current_token != Token::Comma {
+                    break;
+                }
                 self.advance()?; // Skip comma
-                assignments.push(self.parse_assignment()?);
             }
         }
 
         self.expect_token(Token::RightParen)?;
-        Ok(DplyrOperation::Mutate {
-            assignments,
-            location,
-        })
+        Ok(arguments)
     }
 
-    /// Parses rename() operation.
-    ///
-    /// dplyr-style syntax: `rename(new_name = old_name, ...)`
-    fn parse_rename(&mut self) -> ParseResult<DplyrOperation> {
-        let location = self.current_location();
-        self.advance()?; // Skip 'rename'
-        self.expect_token(Token::LeftParen)?;
-        self.consume_optional_lazy_data_argument()?;
+    /// Returns whether the current token starts a `.by` argument (rather than,
+    /// say, the `.data` pronoun).
+    fn at_by_argument(&mut self) -> ParseResult<bool> {
+        Ok(self.current_token == Token::Dot
+            && self.peek_token()? == Token::Identifier("by".to_string()))
+    }
 
-        let mut renames = Vec::new();
-        if self.current_token != Token::RightParen {
-            renames.push(self.parse_rename_spec()?);
-            while self.current_token == Token::Comma {
-                self.advance()?; // Skip comma
-                renames.push(self.parse_rename_spec()?);
-            }
+    /// Parses the `.by = col` / `.by = c(a, b)` argument of summarise(),
+    /// mutate() and filter(): grouping that applies to that call only.
+    fn parse_by_argument(&mut self) -> ParseResult<Vec<String>> {
+        self.expect_token(Token::Dot)?;
+        self.expect_identifier_name("by")?;
+        self.expect_token(Token::Assignment)?;
+        self.parse_column_selection()
+    }
+
+    /// Parses one mutate() argument: `name = value`, or `across(cols, fn)`,
+    /// which expands to `col = fn(col)` for each selected column.
+    ///
+    /// For `across(helper(...), fn)` with a tidyselect helper, adds no
+    /// assignments and returns the `across()` call as written.
+    fn parse_mutate_argument(
+        &mut self,
+        assignments: &mut Vec<Assignment>,
+    ) -> ParseResult<Option<String>> {
+        if self.current_token != Token::Identifier("across".to_string())
+            || self.peek_token()? != Token::LeftParen
+        {
+            assignments.push(self.parse_assignment()?);
+            return Ok(None);
         }
 
+        self.advance()?; // Skip 'across'
+        self.advance()?; // Skip (
+        let columns = match self.skip_tidyselect_helper()? {
+            Some(helper) => Err(helper),
+            None => Ok(self.parse_column_selection()?),
+        };
+        self.expect_token(Token::Comma)?;
+        let function = self.parse_identifier_like("function name")?;
         self.expect_token(Token::RightParen)?;
-        Ok(DplyrOperation::Rename { renames, location })
+
+        let columns = match columns {
+            Ok(columns) => columns,
+            Err(helper) => return Ok(Some(format!("across({helper}, {function})"))),
+        };
+        assignments.extend(columns.into_iter().map(|column| Assignment {
+            expr: Expr::Function {
+                name: function.clone(),
+                args: vec![Expr::Identifier(column.clone())],
+            },
+            column,
+        }));
+        Ok(None)
     }
 
-    fn parse_rename_spec(&mut self) -> ParseResult<RenameSpec> {
-        let new_name = self.parse_identifier_like("new column name")?;
-        self.expect_token(Token::Assignment)?;
-        let old_name = self.parse_identifier_like("existing column name")?;
-        Ok(RenameSpec { new_name, old_name })
+    /// Skips a call to one of the [`TIDYSELECT_HELPERS`] and returns it as
+    /// written with its arguments elided (`last_col()`, `num_range(...)`), or
+    /// returns `None` without consuming anything when the current token starts
+    /// no such call.
+    fn skip_tidyselect_helper(&mut self) -> ParseResult<Option<String>> {
+        let name = match &self.current_token {
+            Token::Identifier(name) if TIDYSELECT_HELPERS.contains(&name.as_str()) => name.clone(),
+            _ => return Ok(None),
+        };
+        if self.peek_token()? != Token::LeftParen {
+            return Ok(None);
+        }
+        self.advance()?; // Skip helper name
+        let has_arguments = self.peek_token()? != Token::RightParen;
+        self.skip_call_arguments()?;
+        Ok(Some(if has_arguments {
+            format!("{name}(...)")
+        } else {
+            format!("{name}()")
+        }))
     }
 
-    fn parse_identifier_like(&mut self, expected: &str) -> ParseResult<String> {
-        match &self.current_token {
-            Token::Identifier(name) => {
-                let name = name.clone();
-                self.advance()?;
-                Ok(name)
-            }
-            Token::String(name) => {
-                let name = name.clone();
-                self.advance()?;
-                Ok(name)
-            }
-            _ => Err(ParseError::UnexpectedToken {
-                expected: expected.to_string(),
-                found: format!("{}", self.current_token),
-                position: self.position,
-            }),
+    /// Operation reported for a column `selection` in `verb` that only the
+    /// table schema could resolve, such as `last_col()`.
+    fn schema_dependent_selection(
+        verb: &str,
+        selection: &str,
+        location: SourceLocation,
+    ) -> DplyrOperation {
+        DplyrOperation::Unsupported {
+            verb: verb.to_string(),
+            reason: Some(format!(
+                "{selection} in {verb}() needs the table schema, which is not available; list the columns explicitly"
+            )),
+            location,
         }
     }
 
-    /// Parses arrange() operation.
-    fn parse_arrange(&mut self) -> ParseResult<DplyrOperation> {
+    /// Parses the tidyr verb `replace_na(x = 0, y = "none")`, or its
+    /// `replace_na(list(...))` form, as `mutate(x = replace_na(x, 0), ...)`.
+    fn parse_replace_na(&mut self) -> ParseResult<DplyrOperation> {
         let location = self.current_location();
-        self.advance()?; // Skip 'arrange'
+        self.advance()?; // Skip 'replace_na'
+        self.parse_replace_na_arguments(location)
+    }
+
+    /// Parses the argument list of replace_na(), after the verb name.
+    fn parse_replace_na_arguments(
+        &mut self,
+        location: SourceLocation,
+    ) -> ParseResult<DplyrOperation> {
+        let position = location.offset;
         self.expect_token(Token::LeftParen)?;
         self.consume_optional_lazy_data_argument()?;
 
-        let mut columns = Vec::new();
+        let in_list = self.current_token == Token::Identifier("list".to_string())
+            && self.peek_token()? == Token::LeftParen;
+        if in_list {
+            self.advance()?; // Skip 'list'
+            self.advance()?; // Skip (
+        }
+        if self.current_token == Token::RightParen {
+            return Err(ParseError::MissingArgument {
+                function: "replace_na".to_string(),
+                position,
+            });
+        }
 
-        // First sort column
-        if self.current_token != Token::RightParen {
-            columns.push(self.parse_order_expr()?);
+        let mut assignments = vec![self.parse_assignment()?];
+        while self.current_token == Token::Comma {
+            self.advance()?; // Skip comma
+            assignments.push(self.parse_assignment()?);
+        }
+        if in_list {
+            self.expect_token(Token::RightParen)?;
+        }
+        self.expect_token(Token::RightParen)?;
+
+        let assignments = assignments
+            .into_iter()
+            .map(|Assignment { column, expr }| Assignment {
+                expr: Expr::Function {
+                    name: "replace_na".to_string(),
+                    args: vec![Expr::Identifier(column.clone()), expr],
+                },
+                column,
+            })
+            .collect();
+        Ok(DplyrOperation::Mutate {
+            assignments,
+            location,
+        })
+    }
+
+    /// Parses `pivot_wider(names_from = key, values_from = value, names = c("a", "b"))`.
+    ///
+    /// The distinct values of `names_from` are only known from the data, so the
+    /// output columns must be listed in `names`.
+    fn parse_pivot_wider(&mut self) -> ParseResult<DplyrOperation> {
+        let location = self.current_location();
+        self.advance()?; // Skip 'pivot_wider'
+        self.parse_pivot_wider_arguments(location)
+    }
+
+    /// Parses the argument list of pivot_wider(), after the verb name.
+    fn parse_pivot_wider_arguments(
+        &mut self,
+        location: SourceLocation,
+    ) -> ParseResult<DplyrOperation> {
+        let position = location.offset;
+        self.expect_token(Token::LeftParen)?;
+        self.consume_optional_lazy_data_argument()?;
+
+        let mut names_from = None;
+        let mut values_from = None;
+        let mut names = Vec::new();
+        while self.current_token != Token::RightParen {
+            let argument_position = self.position;
+            let name = self.parse_identifier_like("pivot_wider() argument name")?;
+            self.expect_token(Token::Assignment)?;
+            match name.as_str() {
+                "names_from" => names_from = Some(self.parse_identifier_like("column name")?),
+                "values_from" => values_from = Some(self.parse_identifier_like("column name")?),
+                "names" => names = self.parse_column_selection()?,
+                _ => {
+                    return Err(ParseError::InvalidOperation {
+                        operation: format!(
+                            "pivot_wider() does not support the `{name}` argument; only `names_from`, `values_from` and `names` are supported"
+                        ),
+                        position: argument_position,
+                    });
+                }
+            }
+            if self.current_token != Token::Comma {
+                break;
+            }
+            self.advance()?; // Skip comma
+        }
+        self.expect_token(Token::RightParen)?;
+
+        let (Some(names_from), Some(values_from)) = (names_from, values_from) else {
+            return Err(ParseError::MissingArgument {
+                function: "pivot_wider".to_string(),
+                position,
+            });
+        };
+        if names.is_empty() {
+            return Err(ParseError::InvalidOperation {
+                operation: "pivot_wider() needs its output columns listed, e.g. names = c(\"a\", \"b\"); they cannot be read from the data".to_string(),
+                position,
+            });
+        }
+        Ok(DplyrOperation::PivotWider {
+            names_from,
+            values_from,
+            names,
+            location,
+        })
+    }
+
+    /// Parses transmute() as a mutate() that keeps only the columns it assigns.
+    fn parse_transmute(&mut self) -> ParseResult<Vec<DplyrOperation>> {
+        let location = self.current_location();
+        let arguments = self.parse_mutate_assignments()?;
+        if let Some(selection) = arguments.schema_selection {
+            return Ok(vec![Self::schema_dependent_selection(
+                "transmute",
+                &selection,
+                location,
+            )]);
+        }
+        let assignments = arguments.assignments;
+        let columns = assignments
+            .iter()
+            .map(|assignment| ColumnExpr {
+                expr: Expr::Identifier(assignment.column.clone()),
+                alias: None,
+            })
+            .collect();
+        let operations = vec![
+            DplyrOperation::Mutate {
+                assignments,
+                location: location.clone(),
+            },
+            DplyrOperation::Select {
+                columns,
+                excluded: Vec::new(),
+                location: location.clone(),
+            },
+        ];
+        Ok(scope_to_by(operations, arguments.by, location))
+    }
+
+    /// Parses rename() operation.
+    ///
+    /// dplyr-style syntax: `rename(new_name = old_name, ...)`
+    fn parse_rename(&mut self) -> ParseResult<DplyrOperation> {
+        let location = self.current_location();
+        self.advance()?; // Skip 'rename'
+        self.expect_token(Token::LeftParen)?;
+        self.consume_optional_lazy_data_argument()?;
+
+        let mut renames = Vec::new();
+        if self.current_token != Token::RightParen {
+            renames.push(self.parse_rename_spec()?);
+            while self.current_token == Token::Comma {
+                self.advance()?; // Skip comma
+                renames.push(self.parse_rename_spec()?);
+            }
+        }
+
+        self.expect_token(Token::RightParen)?;
+        Ok(DplyrOperation::Rename { renames, location })
+    }
+
+    /// Parses rename_with() operation.
+    ///
+    /// Syntax: `rename_with(fn)` or `rename_with(fn, c(col1, col2))`.
+    fn parse_rename_with(&mut self) -> ParseResult<DplyrOperation> {
+        let location = self.current_location();
+        self.advance()?; // Skip 'rename_with'
+        self.expect_token(Token::LeftParen)?;
+        self.consume_optional_lazy_data_argument()?;
+
+        let function = self.parse_identifier_like("renaming function")?;
+        let mut columns = Vec::new();
+        if self.current_token == Token::Comma {
+            self.advance()?; // Skip comma
+            columns = self.parse_column_selection()?;
+        }
+
+        self.expect_token(Token::RightParen)?;
+        Ok(DplyrOperation::RenameWith {
+            function,
+            columns,
+            location,
+        })
+    }
+
+    fn parse_rename_spec(&mut self) -> ParseResult<RenameSpec> {
+        let new_name = self.parse_identifier_like("new column name")?;
+        self.expect_token(Token::Assignment)?;
+        let old_name = self.parse_identifier_like("existing column name")?;
+        Ok(RenameSpec { new_name, old_name })
+    }
+
+    fn parse_identifier_like(&mut self, expected: &str) -> ParseResult<String> {
+        match &self.current_token {
+            Token::Identifier(name) => {
+                let name = name.clone();
+                self.advance()?;
+                Ok(name)
+            }
+            Token::String(name) => {
+                let name = name.clone();
+                self.advance()?;
+                Ok(name)
+            }
+            _ => Err(ParseError::UnexpectedToken {
+                expected: expected.to_string(),
+                found: format!("{}", self.current_token),
+                position: self.position,
+            }),
+        }
+    }
+
+    /// Parses arrange() operation.
+    fn parse_arrange(&mut self) -> ParseResult<DplyrOperation> {
+        let location = self.current_location();
+        self.advance()?; // Skip 'arrange'
+        self.expect_token(Token::LeftParen)?;
+        self.consume_optional_lazy_data_argument()?;
+
+        let mut columns = Vec::new();
+
+        // First sort column
+        if self.current_token != Token::RightParen {
+            columns.push(self.parse_order_expr()?);
 
             // Additional sort columns (comma-separated)
             while self.current_token == Token::Comma {
@@ -700,61 +1400,566 @@ impl Parser {
         self.consume_optional_lazy_data_argument()?;
 
         let mut columns = Vec::new();
+        let mut add = false;
+        let mut grouping = GroupingKind::Plain;
+        let mut arguments = 0;
 
-        // First group column
+        // Group columns, rollup()/cube() and the `.add` flag (comma-separated)
         if self.current_token != Token::RightParen {
-            if let Token::Identifier(name) = &self.current_token {
-                columns.push(name.clone());
-                self.advance()?;
-
-                // Additional group columns (comma-separated)
-                while self.current_token == Token::Comma {
-                    self.advance()?; // Skip comma
-                    if let Token::Identifier(name) = &self.current_token {
-                        columns.push(name.clone());
-                        self.advance()?;
-                    } else {
-                        return Err(ParseError::UnexpectedToken {
-                            expected: "identifier".to_string(),
-                            found: format!("{}", self.current_token),
-                            position: self.position,
-                        });
+            loop {
+                let helper_position = self.position;
+                if self.current_token == Token::Dot && !self.at_data_pronoun()? {
+                    if grouping != GroupingKind::Plain {
+                        return Err(grouping_helper_not_alone(grouping, helper_position));
+                    }
+                    add = self.parse_group_add_flag()?;
+                } else if let Some(kind) = self.grouping_helper_kind()? {
+                    if arguments > 0 {
+                        return Err(grouping_helper_not_alone(kind, helper_position));
                     }
+                    grouping = kind;
+                    columns = self.parse_grouping_helper()?;
+                } else {
+                    if grouping != GroupingKind::Plain {
+                        return Err(grouping_helper_not_alone(grouping, helper_position));
+                    }
+                    columns.push(self.parse_group_column()?);
+                }
+                arguments += 1;
+                if self.current_token != Token::Comma {
+                    break;
                 }
+                self.advance()?; // Skip comma
             }
         }
 
         self.expect_token(Token::RightParen)?;
-        Ok(DplyrOperation::GroupBy { columns, location })
+        Ok(DplyrOperation::GroupBy {
+            columns,
+            add,
+            grouping,
+            location,
+        })
+    }
+
+    /// Returns the grouping construct when the current token starts a
+    /// `rollup(...)` or `cube(...)` call.
+    fn grouping_helper_kind(&mut self) -> ParseResult<Option<GroupingKind>> {
+        let kind = match &self.current_token {
+            Token::Identifier(name) if name == "rollup" => GroupingKind::Rollup,
+            Token::Identifier(name) if name == "cube" => GroupingKind::Cube,
+            _ => return Ok(None),
+        };
+        if self.peek_token()? != Token::LeftParen {
+            return Ok(None);
+        }
+        Ok(Some(kind))
+    }
+
+    /// Parses the column list of `rollup(a, b)` / `cube(a, b)`.
+    fn parse_grouping_helper(&mut self) -> ParseResult<Vec<String>> {
+        let helper = format!("{}", self.current_token);
+        let position = self.position;
+        self.advance()?; // Skip 'rollup' / 'cube'
+        self.expect_token(Token::LeftParen)?;
+        let mut columns = Vec::new();
+        if self.current_token != Token::RightParen {
+            loop {
+                columns.push(self.parse_group_column()?);
+                if self.current_token != Token::Comma {
+                    break;
+                }
+                self.advance()?; // Skip comma
+            }
+        }
+        self.expect_token(Token::RightParen)?;
+        if columns.is_empty() {
+            return Err(ParseError::MissingArgument {
+                function: helper,
+                position,
+            });
+        }
+        Ok(columns)
+    }
+
+    /// Parses the `.add = TRUE` argument of group_by().
+    fn parse_group_add_flag(&mut self) -> ParseResult<bool> {
+        self.expect_token(Token::Dot)?;
+        self.expect_identifier_name("add")?;
+        self.expect_token(Token::Assignment)?;
+        let Token::Boolean(add) = self.current_token else {
+            return Err(ParseError::UnexpectedToken {
+                expected: "TRUE or FALSE".to_string(),
+                found: format!("{}", self.current_token),
+                position: self.position,
+            });
+        };
+        self.advance()?;
+        Ok(add)
+    }
+
+    /// Parses one group_by() column, which must be a plain column name or
+    /// `.data$col`.
+    fn parse_group_column(&mut self) -> ParseResult<String> {
+        match &self.current_token {
+            Token::Identifier(name) => {
+                let name = name.clone();
+                self.advance()?;
+                Ok(name)
+            }
+            Token::Dot => self.parse_data_pronoun_column(),
+            Token::Desc | Token::Asc => Err(ParseError::InvalidOperation {
+                operation: format!(
+                    "group_by() takes plain column names, not {}(); sort with arrange() instead",
+                    self.current_token
+                ),
+                position: self.position,
+            }),
+            _ => Err(ParseError::UnexpectedToken {
+                expected: "identifier".to_string(),
+                found: format!("{}", self.current_token),
+                position: self.position,
+            }),
+        }
     }
 
     /// Parses summarise() operation.
-    fn parse_summarise(&mut self) -> ParseResult<DplyrOperation> {
+    fn parse_summarise(&mut self) -> ParseResult<Vec<DplyrOperation>> {
         let location = self.current_location();
         self.advance()?; // Skip 'summarise'
         self.expect_token(Token::LeftParen)?;
         self.consume_optional_lazy_data_argument()?;
 
         let mut aggregations = Vec::new();
+        let mut groups = None;
+        let mut by = None;
+
+        // Aggregations and the `.groups` / `.by` arguments (comma-separated)
+        if self.current_token != Token::RightParen {
+            loop {
+                if self.current_token == Token::Dot {
+                    if self.at_by_argument()? {
+                        by = Some(self.parse_by_argument()?);
+                    } else {
+                        groups = Some(self.parse_summarise_groups()?);
+                    }
+                } else {
+                    aggregations.push(self.parse_aggregation()?);
+                }
+                if self.current_token != Token::Comma {
+                    break;
+                }
+                self.advance()?; // Skip comma
+            }
+        }
+
+        self.expect_token(Token::RightParen)?;
+        if by.is_some() && groups.is_some() {
+            return Err(ParseError::InvalidOperation {
+                operation:
+                    "summarise() cannot combine .by with .groups; .by always returns ungrouped data"
+                        .to_string(),
+                position: location.offset,
+            });
+        }
+        let summarise = DplyrOperation::Summarise {
+            aggregations,
+            // .by leaves the result ungrouped
+            groups: if by.is_some() {
+                SummariseGroups::Drop
+            } else {
+                groups.unwrap_or_default()
+            },
+            location: location.clone(),
+        };
+        Ok(scope_to_by(vec![summarise], by, location))
+    }
+
+    /// Parses the `.groups = "drop_last"` argument of summarise().
+    fn parse_summarise_groups(&mut self) -> ParseResult<SummariseGroups> {
+        self.expect_token(Token::Dot)?;
+        self.expect_identifier_name("groups")?;
+        self.expect_token(Token::Assignment)?;
+        let groups = match &self.current_token {
+            Token::String(value) if value == "drop_last" => SummariseGroups::DropLast,
+            Token::String(value) if value == "drop" => SummariseGroups::Drop,
+            Token::String(value) if value == "keep" => SummariseGroups::Keep,
+            Token::String(value) => {
+                return Err(ParseError::InvalidOperation {
+                    operation: format!(
+                        "summarise() .groups = \"{value}\" is not supported; use \"drop_last\", \"drop\" or \"keep\""
+                    ),
+                    position: self.position,
+                })
+            }
+            _ => {
+                return Err(ParseError::UnexpectedToken {
+                    expected: "groups string".to_string(),
+                    found: format!("{}", self.current_token),
+                    position: self.position,
+                })
+            }
+        };
+        self.advance()?;
+        Ok(groups)
+    }
+
+    /// Parses count() as `group_by(..., .add = TRUE) %>% summarise(n = n())`,
+    /// and tally() as the same without grouping columns.
+    ///
+    /// With `wt = column` the rows are weighted: `n = sum(column)`; `name = "..."`
+    /// renames the count column and `sort = TRUE` adds `arrange(desc(n))`.
+    fn parse_count(&mut self) -> ParseResult<Vec<DplyrOperation>> {
+        let location = self.current_location();
+        let verb = self.current_token.to_string();
+        self.advance()?; // Skip 'count' or 'tally'
+        self.parse_count_arguments(&verb, location)
+    }
+
+    /// Parses the argument list of count() or tally(), after the verb name.
+    ///
+    /// tally() takes no grouping columns and counts within the current groups.
+    fn parse_count_arguments(
+        &mut self,
+        verb: &str,
+        location: SourceLocation,
+    ) -> ParseResult<Vec<DplyrOperation>> {
+        self.expect_token(Token::LeftParen)?;
+        self.consume_optional_lazy_data_argument()?;
+
+        let mut columns = Vec::new();
+        let mut weight = None;
+        let mut count_name = "n".to_string();
+        let mut sort = false;
 
-        // First aggregation
         if self.current_token != Token::RightParen {
-            aggregations.push(self.parse_aggregation()?);
+            loop {
+                let argument_name = match &self.current_token {
+                    Token::Identifier(name) => Some(name.clone()),
+                    _ => None,
+                };
+                match argument_name {
+                    Some(name) if self.peek_token()? == Token::Assignment => {
+                        let position = self.position;
+                        self.advance()?; // Skip argument name
+                        self.advance()?; // Skip =
+                        match name.as_str() {
+                            "wt" => weight = Some(self.parse_identifier_like("weight column")?),
+                            "name" => {
+                                count_name = self.parse_identifier_like("count column name")?;
+                            }
+                            "sort" => {
+                                let Token::Boolean(value) = self.current_token else {
+                                    return Err(ParseError::UnexpectedToken {
+                                        expected: "TRUE or FALSE".to_string(),
+                                        found: format!("{}", self.current_token),
+                                        position: self.position,
+                                    });
+                                };
+                                sort = value;
+                                self.advance()?;
+                            }
+                            _ => {
+                                return Err(ParseError::InvalidOperation {
+                                    operation: format!(
+                                        "{verb}() does not support the `{name}` argument; only `wt`, `name` and `sort` are supported"
+                                    ),
+                                    position,
+                                });
+                            }
+                        }
+                    }
+                    _ if verb == "tally" => {
+                        return Err(ParseError::InvalidOperation {
+                            operation: "tally() does not take grouping columns; use count() or group_by() instead"
+                                .to_string(),
+                            position: self.position,
+                        });
+                    }
+                    _ => columns.push(self.parse_group_column()?),
+                }
+                if self.current_token != Token::Comma {
+                    break;
+                }
+                self.advance()?; // Skip comma
+            }
+        }
+
+        self.expect_token(Token::RightParen)?;
+        let aggregation = match weight {
+            Some(column) => Aggregation {
+                function: "sum".to_string(),
+                column,
+                alias: Some(count_name.clone()),
+                distinct: false,
+                probability: None,
+            },
+            None => Aggregation {
+                function: "n".to_string(),
+                column: String::new(),
+                alias: Some(count_name.clone()),
+                distinct: false,
+                probability: None,
+            },
+        };
+        let mut operations = vec![
+            DplyrOperation::GroupBy {
+                columns,
+                add: true,
+                grouping: GroupingKind::Plain,
+                location: location.clone(),
+            },
+            DplyrOperation::Summarise {
+                aggregations: vec![aggregation],
+                // count() leaves the result ungrouped; tally() peels off one level
+                groups: if verb == "tally" {
+                    SummariseGroups::DropLast
+                } else {
+                    SummariseGroups::Drop
+                },
+                location: location.clone(),
+            },
+        ];
+        if sort {
+            operations.push(DplyrOperation::Arrange {
+                columns: vec![OrderExpr {
+                    column: count_name,
+                    direction: OrderDirection::Desc,
+                    nulls: None,
+                }],
+                location,
+            });
+        }
+        Ok(operations)
+    }
 
-            // Additional aggregations (comma-separated)
+    /// Parses distinct() operation.
+    ///
+    /// Arguments are bare columns or `across(...)` selections, which expand to
+    /// the same column list: `distinct(across(c(a, b)))` equals `distinct(a, b)`.
+    fn parse_distinct(&mut self) -> ParseResult<DplyrOperation> {
+        let location = self.current_location();
+        self.advance()?; // Skip 'distinct'
+        self.expect_token(Token::LeftParen)?;
+        self.consume_optional_lazy_data_argument()?;
+
+        let mut columns = Vec::new();
+        if self.current_token != Token::RightParen {
+            self.parse_distinct_argument(&mut columns)?;
             while self.current_token == Token::Comma {
                 self.advance()?; // Skip comma
-                aggregations.push(self.parse_aggregation()?);
+                self.parse_distinct_argument(&mut columns)?;
             }
         }
 
         self.expect_token(Token::RightParen)?;
-        Ok(DplyrOperation::Summarise {
-            aggregations,
+        Ok(DplyrOperation::Distinct { columns, location })
+    }
+
+    fn parse_distinct_argument(&mut self, columns: &mut Vec<String>) -> ParseResult<()> {
+        let selected = if self.current_token == Token::Identifier("across".to_string()) {
+            self.parse_across_columns()?
+        } else {
+            vec![self.parse_identifier_like("column name")?]
+        };
+
+        for column in selected {
+            if !columns.contains(&column) {
+                columns.push(column);
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses fill() operation.
+    ///
+    /// Syntax: `fill(col1, col2)` or `fill(col, .direction = "up")`.
+    fn parse_fill(&mut self) -> ParseResult<DplyrOperation> {
+        let location = self.current_location();
+        self.advance()?; // Skip 'fill'
+        self.expect_token(Token::LeftParen)?;
+        self.consume_optional_lazy_data_argument()?;
+
+        let mut columns = Vec::new();
+        let mut direction = FillDirection::Down;
+        loop {
+            if self.current_token == Token::Dot {
+                direction = self.parse_fill_direction()?;
+            } else {
+                columns.extend(self.parse_column_selection()?);
+            }
+            if self.current_token != Token::Comma {
+                break;
+            }
+            self.advance()?; // Skip comma
+        }
+
+        self.expect_token(Token::RightParen)?;
+        Ok(DplyrOperation::Fill {
+            columns,
+            direction,
             location,
         })
     }
 
+    /// Parses the `.direction = "down"` argument of fill().
+    fn parse_fill_direction(&mut self) -> ParseResult<FillDirection> {
+        self.expect_token(Token::Dot)?;
+        self.expect_identifier_name("direction")?;
+        self.expect_token(Token::Assignment)?;
+        let direction = match &self.current_token {
+            Token::String(value) if value == "down" => FillDirection::Down,
+            Token::String(value) if value == "up" => FillDirection::Up,
+            Token::String(value) => {
+                return Err(ParseError::InvalidOperation {
+                    operation: format!(
+                        "fill() .direction = \"{value}\" is not supported; use \"down\" or \"up\""
+                    ),
+                    position: self.position,
+                })
+            }
+            _ => {
+                return Err(ParseError::UnexpectedToken {
+                    expected: "fill direction string".to_string(),
+                    found: format!("{}", self.current_token),
+                    position: self.position,
+                })
+            }
+        };
+        self.advance()?;
+        Ok(direction)
+    }
+
+    /// Parses slice_head() / slice_tail() and head() / tail() operations.
+    ///
+    /// Syntax: `slice_head(n = 5)` or `slice_head(5)`; without a count `n` is
+    /// `default_n`, which is 1 for slice_head() as in dplyr and 6 for head().
+    fn parse_slice(
+        &mut self,
+        position: SlicePosition,
+        default_n: usize,
+    ) -> ParseResult<DplyrOperation> {
+        let location = self.current_location();
+        let function = format!("{}", self.current_token);
+        self.advance()?; // Skip 'slice_head' / 'slice_tail' / 'head' / 'tail'
+        self.parse_slice_arguments(&function, position, default_n, location)
+    }
+
+    /// Parses the `(n)` argument list of a slice verb whose name was already consumed.
+    fn parse_slice_arguments(
+        &mut self,
+        function: &str,
+        position: SlicePosition,
+        default_n: usize,
+        location: SourceLocation,
+    ) -> ParseResult<DplyrOperation> {
+        self.expect_token(Token::LeftParen)?;
+        self.consume_optional_lazy_data_argument()?;
+
+        let mut n = default_n;
+        if self.current_token != Token::RightParen {
+            if self.current_token == Token::Identifier("n".to_string()) {
+                self.advance()?; // Skip 'n'
+                self.expect_token(Token::Assignment)?;
+            }
+            n = match self.current_token {
+                Token::Number(value) if value >= 0.0 && is_whole_number(value) => value as usize,
+                _ => {
+                    return Err(ParseError::InvalidOperation {
+                        operation: format!("{function}() needs a whole row count, e.g. n = 5"),
+                        position: self.position,
+                    })
+                }
+            };
+            self.advance()?;
+        }
+
+        self.expect_token(Token::RightParen)?;
+        Ok(DplyrOperation::Slice {
+            position,
+            n,
+            location,
+        })
+    }
+
+    /// Parses slice() with a constant row position or range.
+    ///
+    /// Syntax: `slice(5)` or `slice(2:4)`; positions are 1-based and inclusive.
+    fn parse_slice_rows(&mut self) -> ParseResult<DplyrOperation> {
+        let location = self.current_location();
+        self.advance()?; // Skip 'slice'
+        self.expect_token(Token::LeftParen)?;
+        self.consume_optional_lazy_data_argument()?;
+
+        let start = self.parse_slice_row_position()?;
+        let end = if self.current_token == Token::Colon {
+            self.advance()?; // Skip :
+            self.parse_slice_row_position()?
+        } else {
+            start
+        };
+        if end < start {
+            return Err(ParseError::InvalidOperation {
+                operation: format!(
+                    "slice() range {start}:{end} is reversed; use {end}:{start} instead"
+                ),
+                position: self.position,
+            });
+        }
+
+        self.expect_token(Token::RightParen)?;
+        Ok(DplyrOperation::Slice {
+            position: SlicePosition::Rows { start },
+            n: end - start + 1,
+            location,
+        })
+    }
+
+    /// Parses one positive whole-number row position inside slice().
+    fn parse_slice_row_position(&mut self) -> ParseResult<usize> {
+        match self.current_token {
+            Token::Number(value) if value >= 1.0 && is_whole_number(value) => {
+                self.advance()?;
+                Ok(value as usize)
+            }
+            _ => Err(ParseError::InvalidOperation {
+                operation: "slice() needs constant positive row positions, e.g. slice(2:4)"
+                    .to_string(),
+                position: self.position,
+            }),
+        }
+    }
+
+    /// Parses an `across()` column selection into the columns it names.
+    ///
+    /// Accepts a single column (`across(a)`) or a `c()` vector (`across(c(a, "b"))`).
+    fn parse_across_columns(&mut self) -> ParseResult<Vec<String>> {
+        self.expect_identifier_name("across")?;
+        self.expect_token(Token::LeftParen)?;
+        let columns = self.parse_column_selection()?;
+        self.expect_token(Token::RightParen)?;
+        Ok(columns)
+    }
+
+    /// Parses a column selection: a single column or a `c()` vector of columns.
+    fn parse_column_selection(&mut self) -> ParseResult<Vec<String>> {
+        let mut columns = Vec::new();
+        if self.current_token == Token::Identifier("c".to_string()) {
+            self.advance()?; // Skip 'c'
+            self.expect_token(Token::LeftParen)?;
+            columns.push(self.parse_identifier_like("column name")?);
+            while self.current_token == Token::Comma {
+                self.advance()?; // Skip comma
+                columns.push(self.parse_identifier_like("column name")?);
+            }
+            self.expect_token(Token::RightParen)?;
+        } else {
+            columns.push(self.parse_identifier_like("column name")?);
+        }
+
+        Ok(columns)
+    }
+
     /// Parses join operations (inner_join, left_join, right_join, full_join, semi_join, anti_join).
     fn parse_join(&mut self) -> ParseResult<DplyrOperation> {
         let join_type = match &self.current_token {
@@ -909,6 +2114,14 @@ impl Parser {
                     args,
                 };
                 return Ok(ColumnExpr { expr, alias: None });
+            } else if self.current_token == Token::Dollar {
+                // `df$col` names a column of another data frame
+                self.advance()?; // Skip $
+                let expr = Expr::QualifiedIdentifier {
+                    table: first_name,
+                    column: self.parse_dollar_column()?,
+                };
+                return Ok(ColumnExpr { expr, alias: None });
             } else {
                 // Not an alias or function call, treat the identifier as a regular expression
                 // We already consumed the identifier, so create an Identifier expression
@@ -925,111 +2138,46 @@ impl Parser {
     }
 
     /// Parses assignment statements.
+    ///
+    /// Unnamed arguments (`mutate(salary * 1.1)`) are rejected: the generated
+    /// column needs a SQL alias.
     fn parse_assignment(&mut self) -> ParseResult<Assignment> {
+        let position = self.position;
         if let Token::Identifier(column) = &self.current_token {
             let column = column.clone();
             self.advance()?;
 
-            self.expect_token(Token::Assignment)?;
-            let expr = self.parse_expression()?;
-
-            Ok(Assignment { column, expr })
-        } else {
-            Err(ParseError::UnexpectedToken {
-                expected: "column identifier".to_string(),
-                found: format!("{}", self.current_token),
-                position: self.position,
-            })
+            match self.current_token {
+                Token::Assignment => {
+                    self.advance()?; // Skip =
+                    let expr = self.parse_expression()?;
+                    return Ok(Assignment { column, expr });
+                }
+                // `new_col age + 1`: a name followed by a value is a missing '='
+                Token::Identifier(_) | Token::Number(_) | Token::String(_) => {
+                    self.expect_token(Token::Assignment)?;
+                }
+                _ => {}
+            }
         }
+
+        Err(ParseError::InvalidOperation {
+            operation: "mutate() requires name = value".to_string(),
+            position,
+        })
     }
 
     /// Parses sort expressions.
     fn parse_order_expr(&mut self) -> ParseResult<OrderExpr> {
         // Check for desc() or asc() functions
         match &self.current_token {
-            Token::Desc => {
-                self.advance()?; // Skip 'desc'
-                self.expect_token(Token::LeftParen)?;
-
-                if let Token::Identifier(column) = &self.current_token {
-                    let column = column.clone();
-                    self.advance()?;
-                    self.expect_token(Token::RightParen)?;
-
-                    Ok(OrderExpr {
-                        column,
-                        direction: OrderDirection::Desc,
-                    })
-                } else {
-                    Err(ParseError::UnexpectedToken {
-                        expected: "column identifier".to_string(),
-                        found: format!("{}", self.current_token),
-                        position: self.position,
-                    })
-                }
-            }
-            Token::Asc => {
-                self.advance()?; // Skip 'asc'
-                self.expect_token(Token::LeftParen)?;
-
-                if let Token::Identifier(column) = &self.current_token {
-                    let column = column.clone();
-                    self.advance()?;
-                    self.expect_token(Token::RightParen)?;
-
-                    Ok(OrderExpr {
-                        column,
-                        direction: OrderDirection::Asc,
-                    })
-                } else {
-                    Err(ParseError::UnexpectedToken {
-                        expected: "column identifier".to_string(),
-                        found: format!("{}", self.current_token),
-                        position: self.position,
-                    })
-                }
-            }
+            Token::Desc => self.parse_order_function("desc", OrderDirection::Desc),
+            Token::Asc => self.parse_order_function("asc", OrderDirection::Asc),
             Token::Identifier(name) => {
                 if name == "desc" {
-                    self.advance()?; // Skip 'desc'
-                    self.expect_token(Token::LeftParen)?;
-
-                    if let Token::Identifier(column) = &self.current_token {
-                        let column = column.clone();
-                        self.advance()?;
-                        self.expect_token(Token::RightParen)?;
-
-                        Ok(OrderExpr {
-                            column,
-                            direction: OrderDirection::Desc,
-                        })
-                    } else {
-                        Err(ParseError::UnexpectedToken {
-                            expected: "column identifier".to_string(),
-                            found: format!("{}", self.current_token),
-                            position: self.position,
-                        })
-                    }
+                    self.parse_order_function("desc", OrderDirection::Desc)
                 } else if name == "asc" {
-                    self.advance()?; // Skip 'asc'
-                    self.expect_token(Token::LeftParen)?;
-
-                    if let Token::Identifier(column) = &self.current_token {
-                        let column = column.clone();
-                        self.advance()?;
-                        self.expect_token(Token::RightParen)?;
-
-                        Ok(OrderExpr {
-                            column,
-                            direction: OrderDirection::Asc,
-                        })
-                    } else {
-                        Err(ParseError::UnexpectedToken {
-                            expected: "column identifier".to_string(),
-                            found: format!("{}", self.current_token),
-                            position: self.position,
-                        })
-                    }
+                    self.parse_order_function("asc", OrderDirection::Asc)
                 } else {
                     // Regular column (ascending by default)
                     let column = name.clone();
@@ -1037,9 +2185,15 @@ impl Parser {
                     Ok(OrderExpr {
                         column,
                         direction: OrderDirection::Asc,
+                        nulls: None,
                     })
                 }
             }
+            Token::Dot => Ok(OrderExpr {
+                column: self.parse_data_pronoun_column()?,
+                direction: OrderDirection::Asc,
+                nulls: None,
+            }),
             _ => Err(ParseError::UnexpectedToken {
                 expected: "column identifier, desc(), or asc()".to_string(),
                 found: format!("{}", self.current_token),
@@ -1048,6 +2202,72 @@ impl Parser {
         }
     }
 
+    /// Parses `desc(column)` / `asc(column)`; both take exactly one column.
+    fn parse_order_function(
+        &mut self,
+        function: &str,
+        direction: OrderDirection,
+    ) -> ParseResult<OrderExpr> {
+        self.advance()?; // Skip 'desc' / 'asc'
+        self.expect_token(Token::LeftParen)?;
+
+        let column = match &self.current_token {
+            Token::Identifier(column) => {
+                let column = column.clone();
+                self.advance()?;
+                column
+            }
+            Token::Dot => self.parse_data_pronoun_column()?,
+            _ => {
+                return Err(ParseError::UnexpectedToken {
+                    expected: "column identifier".to_string(),
+                    found: format!("{}", self.current_token),
+                    position: self.position,
+                })
+            }
+        };
+
+        let mut nulls = None;
+        if self.current_token == Token::Comma {
+            self.advance()?; // Skip comma
+            if !matches!(&self.current_token, Token::Identifier(name) if name == "na_last") {
+                return Err(ParseError::InvalidOperation {
+                    operation: format!(
+                        "{function}() takes a single column; use {function}(a), {function}(b) to sort by several"
+                    ),
+                    position: self.position,
+                });
+            }
+            nulls = Some(self.parse_na_last()?);
+        }
+        self.expect_token(Token::RightParen)?;
+
+        Ok(OrderExpr {
+            column,
+            direction,
+            nulls,
+        })
+    }
+
+    /// Parses the `na_last = TRUE` / `na_last = FALSE` argument of desc()/asc().
+    fn parse_na_last(&mut self) -> ParseResult<NullsOrder> {
+        self.expect_identifier_name("na_last")?;
+        self.expect_token(Token::Assignment)?;
+        let nulls = match self.current_token {
+            Token::Boolean(true) => NullsOrder::Last,
+            Token::Boolean(false) => NullsOrder::First,
+            _ => {
+                return Err(ParseError::UnexpectedToken {
+                    expected: "TRUE or FALSE".to_string(),
+                    found: format!("{}", self.current_token),
+                    position: self.position,
+                })
+            }
+        };
+        self.advance()?;
+        Ok(nulls)
+    }
+
     /// Parses aggregation operations.
     fn parse_aggregation(&mut self) -> ParseResult<Aggregation> {
         // Handle alias = aggregation_function(column) format
@@ -1063,34 +2283,7 @@ impl Parser {
                 if let Token::Identifier(function) = &self.current_token {
                     let function = function.clone();
                     self.advance()?;
-
-                    self.expect_token(Token::LeftParen)?;
-
-                    // Handle functions with no arguments (like n())
-                    if self.current_token == Token::RightParen {
-                        self.advance()?; // Skip )
-                        Ok(Aggregation {
-                            function,
-                            column: "".to_string(), // Empty column for functions like n()
-                            alias: Some(first_name),
-                        })
-                    } else if let Token::Identifier(column) = &self.current_token {
-                        let column = column.clone();
-                        self.advance()?;
-                        self.expect_token(Token::RightParen)?;
-
-                        Ok(Aggregation {
-                            function,
-                            column,
-                            alias: Some(first_name),
-                        })
-                    } else {
-                        Err(ParseError::UnexpectedToken {
-                            expected: "column identifier or closing parenthesis".to_string(),
-                            found: format!("{}", self.current_token),
-                            position: self.position,
-                        })
-                    }
+                    self.parse_aggregation_call(function, Some(first_name))
                 } else {
                     Err(ParseError::UnexpectedToken {
                         expected: "aggregation function name".to_string(),
@@ -1100,33 +2293,7 @@ impl Parser {
                 }
             } else {
                 // Function(column) format without alias
-                self.expect_token(Token::LeftParen)?;
-
-                // Handle functions with no arguments (like n())
-                if self.current_token == Token::RightParen {
-                    self.advance()?; // Skip )
-                    Ok(Aggregation {
-                        function: first_name,
-                        column: "".to_string(), // Empty column for functions like n()
-                        alias: None,
-                    })
-                } else if let Token::Identifier(column) = &self.current_token {
-                    let column = column.clone();
-                    self.advance()?;
-                    self.expect_token(Token::RightParen)?;
-
-                    Ok(Aggregation {
-                        function: first_name,
-                        column,
-                        alias: None,
-                    })
-                } else {
-                    Err(ParseError::UnexpectedToken {
-                        expected: "column identifier or closing parenthesis".to_string(),
-                        found: format!("{}", self.current_token),
-                        position: self.position,
-                    })
-                }
+                self.parse_aggregation_call(first_name, None)
             }
         } else {
             Err(ParseError::UnexpectedToken {
@@ -1137,18 +2304,150 @@ impl Parser {
         }
     }
 
+    /// Parses the parenthesized argument of an aggregate: nothing (`n()`), a
+    /// column, or `distinct` followed by a column (`sum(distinct x)`).
+    fn parse_aggregation_call(
+        &mut self,
+        function: String,
+        alias: Option<String>,
+    ) -> ParseResult<Aggregation> {
+        self.expect_token(Token::LeftParen)?;
+
+        // Handle functions with no arguments (like n())
+        if self.current_token == Token::RightParen {
+            self.advance()?; // Skip )
+            return Ok(Aggregation {
+                function,
+                column: String::new(), // Empty column for functions like n()
+                alias,
+                distinct: false,
+                probability: None,
+            });
+        }
+
+        let distinct = self.current_token == Token::Distinct;
+        if distinct {
+            self.advance()?; // Skip 'distinct'
+        }
+        if let Token::Identifier(column) = &self.current_token {
+            let column = column.clone();
+            self.reject_n_argument(&function)?;
+            self.advance()?;
+            let probability = if QUANTILE_FUNCTIONS.contains(&function.as_str()) {
+                Some(self.parse_quantile_probability(&function)?)
+            } else {
+                None
+            };
+            self.expect_token(Token::RightParen)?;
+
+            Ok(Aggregation {
+                function,
+                column,
+                alias,
+                distinct,
+                probability,
+            })
+        } else {
+            Err(ParseError::UnexpectedToken {
+                expected: if distinct {
+                    "column identifier".to_string()
+                } else {
+                    "column identifier or closing parenthesis".to_string()
+                },
+                found: format!("{}", self.current_token),
+                position: self.position,
+            })
+        }
+    }
+
+    /// Parses the `, 0.9` or `, probs = 0.9` after the column of `quantile(x, 0.9)`.
+    fn parse_quantile_probability(&mut self, function: &str) -> ParseResult<f64> {
+        let position = self.position;
+        if self.current_token != Token::Comma {
+            return Err(ParseError::MissingArgument {
+                function: function.to_string(),
+                position,
+            });
+        }
+        self.advance()?; // Skip comma
+        if self.current_token == Token::Identifier("probs".to_string()) {
+            self.advance()?; // Skip 'probs'
+            self.expect_token(Token::Assignment)?;
+        }
+        match self.current_token {
+            Token::Number(probability) if (0.0..=1.0).contains(&probability) => {
+                self.advance()?;
+                Ok(probability)
+            }
+            Token::Number(_) => Err(ParseError::InvalidOperation {
+                operation: format!("{function}() probability must be between 0 and 1"),
+                position: self.position,
+            }),
+            _ => Err(ParseError::UnexpectedToken {
+                expected: "probability between 0 and 1".to_string(),
+                found: format!("{}", self.current_token),
+                position: self.position,
+            }),
+        }
+    }
+
+    /// Rejects `n(x)`: dplyr's n() counts rows and takes no arguments.
+    fn reject_n_argument(&self, function: &str) -> ParseResult<()> {
+        if function == "n" {
+            return Err(ParseError::InvalidOperation {
+                operation: "n() takes no arguments; use n() to count rows in each group"
+                    .to_string(),
+                position: self.position,
+            });
+        }
+        Ok(())
+    }
+
     /// Parses expressions.
     fn parse_expression(&mut self) -> ParseResult<Expr> {
-        self.parse_or_expression()
+        self.nested(Self::parse_or_expression)
+    }
+
+    /// Runs `parse` one nesting level deeper, failing once the input nests
+    /// beyond [`MAX_NESTING_DEPTH`] instead of recursing further.
+    fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> ParseResult<T>) -> ParseResult<T> {
+        if self.expression_depth >= MAX_NESTING_DEPTH {
+            return Err(ParseError::MaxNestingDepthExceeded {
+                max_depth: MAX_NESTING_DEPTH,
+                position: self.position,
+            });
+        }
+        self.expression_depth += 1;
+        let result = parse(self);
+        self.expression_depth -= 1;
+        result
+    }
+
+    /// Fails once a chain of `operators` binary operators would build a
+    /// deeper tree than [`MAX_EXPRESSION_DEPTH`].
+    const fn check_chain_length(&self, operators: usize) -> ParseResult<()> {
+        if self.expression_depth + operators > MAX_EXPRESSION_DEPTH {
+            return Err(ParseError::MaxNestingDepthExceeded {
+                max_depth: MAX_EXPRESSION_DEPTH,
+                position: self.position,
+            });
+        }
+        Ok(())
     }
 
     /// Parses OR expressions.
     fn parse_or_expression(&mut self) -> ParseResult<Expr> {
-        let mut left = self.parse_and_expression()?;
+        let (mut left, mut mixes_and) = self.parse_and_chain()?;
+        let mut has_or = false;
 
+        let mut operators = 0;
         while self.current_token == Token::Or {
             self.advance()?;
-            let right = self.parse_and_expression()?;
+            operators += 1;
+            self.check_chain_length(operators)?;
+            let (right, right_has_and) = self.parse_and_chain()?;
+            has_or = true;
+            mixes_and |= right_has_and;
             left = Expr::Binary {
                 left: Box::new(left),
                 operator: BinaryOp::Or,
@@ -1156,16 +2455,25 @@ impl Parser {
             };
         }
 
+        if has_or && mixes_and {
+            self.precedence_ambiguities.push(expr_to_string(&left));
+        }
+
         Ok(left)
     }
 
-    /// Parses AND expressions.
-    fn parse_and_expression(&mut self) -> ParseResult<Expr> {
-        let mut left = self.parse_equality_expression()?;
+    /// Parses AND expressions, reporting whether an unparenthesized `&` joined them.
+    fn parse_and_chain(&mut self) -> ParseResult<(Expr, bool)> {
+        let mut left = self.parse_not_expression()?;
+        let mut has_and = false;
 
+        let mut operators = 0;
         while self.current_token == Token::And {
             self.advance()?;
-            let right = self.parse_equality_expression()?;
+            operators += 1;
+            self.check_chain_length(operators)?;
+            let right = self.parse_not_expression()?;
+            has_and = true;
             left = Expr::Binary {
                 left: Box::new(left),
                 operator: BinaryOp::And,
@@ -1173,13 +2481,26 @@ impl Parser {
             };
         }
 
-        Ok(left)
+        Ok((left, has_and))
+    }
+
+    /// Parses `!` expressions, which bind looser than comparisons as in R
+    /// (`!x == y` negates `x == y`).
+    fn parse_not_expression(&mut self) -> ParseResult<Expr> {
+        if self.current_token != Token::Not {
+            return self.parse_equality_expression();
+        }
+        self.advance()?; // Skip !
+        Ok(Expr::Not(Box::new(
+            self.nested(Self::parse_not_expression)?,
+        )))
     }
 
     /// Parses equality expressions.
     fn parse_equality_expression(&mut self) -> ParseResult<Expr> {
         let mut left = self.parse_comparison_expression()?;
 
+        let mut operators = 0;
         while matches!(self.current_token, Token::Equal | Token::NotEqual) {
             let operator = match self.current_token {
                 Token::Equal => BinaryOp::Equal,
@@ -1187,6 +2508,8 @@ impl Parser {
                 _ => unreachable!(),
             };
             self.advance()?;
+            operators += 1;
+            self.check_chain_length(operators)?;
             let right = self.parse_comparison_expression()?;
             left = Expr::Binary {
                 left: Box::new(left),
@@ -1202,6 +2525,7 @@ impl Parser {
     fn parse_comparison_expression(&mut self) -> ParseResult<Expr> {
         let mut left = self.parse_additive_expression()?;
 
+        let mut operators = 0;
         while matches!(
             self.current_token,
             Token::LessThan
@@ -1217,6 +2541,8 @@ impl Parser {
                 _ => unreachable!(),
             };
             self.advance()?;
+            operators += 1;
+            self.check_chain_length(operators)?;
             let right = self.parse_additive_expression()?;
             left = Expr::Binary {
                 left: Box::new(left),
@@ -1228,10 +2554,34 @@ impl Parser {
         Ok(left)
     }
 
+    /// Parses the `c(lower, upper)` range of `value %between% c(lower, upper)`
+    /// as `between(value, lower, upper)`.
+    fn parse_between_range(&mut self, value: Expr) -> ParseResult<Expr> {
+        self.advance()?; // Skip %between%
+        if self.current_token != Token::Identifier("c".to_string()) {
+            return Err(ParseError::UnexpectedToken {
+                expected: "c(lower, upper) after %between%".to_string(),
+                found: format!("{}", self.current_token),
+                position: self.position,
+            });
+        }
+        self.advance()?; // Skip 'c'
+        self.expect_token(Token::LeftParen)?;
+        let lower = self.parse_additive_expression()?;
+        self.expect_token(Token::Comma)?;
+        let upper = self.parse_additive_expression()?;
+        self.expect_token(Token::RightParen)?;
+        Ok(Expr::Function {
+            name: "between".to_string(),
+            args: vec![value, lower, upper],
+        })
+    }
+
     /// Parses addition/subtraction expressions.
     fn parse_additive_expression(&mut self) -> ParseResult<Expr> {
         let mut left = self.parse_multiplicative_expression()?;
 
+        let mut operators = 0;
         while matches!(self.current_token, Token::Plus | Token::Minus) {
             let operator = match self.current_token {
                 Token::Plus => BinaryOp::Plus,
@@ -1239,6 +2589,8 @@ impl Parser {
                 _ => unreachable!(),
             };
             self.advance()?;
+            operators += 1;
+            self.check_chain_length(operators)?;
             let right = self.parse_multiplicative_expression()?;
             left = Expr::Binary {
                 left: Box::new(left),
@@ -1252,8 +2604,9 @@ impl Parser {
 
     /// Parses multiplication/division expressions.
     fn parse_multiplicative_expression(&mut self) -> ParseResult<Expr> {
-        let mut left = self.parse_primary_expression()?;
+        let mut left = self.parse_special_infix_expression()?;
 
+        let mut operators = 0;
         while matches!(self.current_token, Token::Multiply | Token::Divide) {
             let operator = match self.current_token {
                 Token::Multiply => BinaryOp::Multiply,
@@ -1261,7 +2614,9 @@ impl Parser {
                 _ => unreachable!(),
             };
             self.advance()?;
-            let right = self.parse_primary_expression()?;
+            operators += 1;
+            self.check_chain_length(operators)?;
+            let right = self.parse_special_infix_expression()?;
             left = Expr::Binary {
                 left: Box::new(left),
                 operator,
@@ -1272,6 +2627,59 @@ impl Parser {
         Ok(left)
     }
 
+    /// Parses `%between%` and `%||%`, which bind tighter than `*` and `/` and
+    /// associate to the left as in R.
+    fn parse_special_infix_expression(&mut self) -> ParseResult<Expr> {
+        let mut value = self.parse_unary_minus_expression()?;
+        let mut operators = 0;
+        loop {
+            value = match self.current_token {
+                Token::Between => self.parse_between_range(value)?,
+                // rlang's `a %||% b` takes `b` when `a` is NULL
+                Token::NullDefault => {
+                    self.advance()?;
+                    Expr::Function {
+                        name: "coalesce".to_string(),
+                        args: vec![value, self.parse_unary_minus_expression()?],
+                    }
+                }
+                _ => return Ok(value),
+            };
+            operators += 1;
+            self.check_chain_length(operators)?;
+        }
+    }
+
+    /// Parses unary `-`, which binds looser than `^` as in R: `-2^2` is
+    /// `-(2^2)`. A negated number is folded into the literal.
+    fn parse_unary_minus_expression(&mut self) -> ParseResult<Expr> {
+        if self.current_token != Token::Minus {
+            return self.parse_power_expression();
+        }
+        self.advance()?; // Skip -
+        match self.nested(Self::parse_unary_minus_expression)? {
+            Expr::Literal(LiteralValue::Number(n)) => Ok(Expr::Literal(LiteralValue::Number(-n))),
+            operand => Ok(Expr::Negate(Box::new(operand))),
+        }
+    }
+
+    /// Parses `^` expressions, which are right-associative as in R.
+    fn parse_power_expression(&mut self) -> ParseResult<Expr> {
+        let base = self.parse_primary_expression()?;
+
+        if self.current_token != Token::Caret {
+            return Ok(base);
+        }
+        self.advance()?;
+        // The exponent may be negated: `2^-1`
+        let exponent = self.nested(Self::parse_unary_minus_expression)?;
+        Ok(Expr::Binary {
+            left: Box::new(base),
+            operator: BinaryOp::Power,
+            right: Box::new(exponent),
+        })
+    }
+
     /// Parses primary expressions.
     fn parse_primary_expression(&mut self) -> ParseResult<Expr> {
         match &self.current_token {
@@ -1279,6 +2687,11 @@ impl Parser {
                 let name = name.clone();
                 self.advance()?;
 
+                if self.current_token == Token::LeftParen && (name == "if_any" || name == "if_all")
+                {
+                    return self.parse_if_any_all(&name);
+                }
+
                 // Check for function call
                 if self.current_token == Token::LeftParen {
                     self.advance()?; // Skip (
@@ -1295,10 +2708,18 @@ impl Parser {
 
                     self.expect_token(Token::RightParen)?;
                     Ok(Expr::Function { name, args })
+                } else if self.current_token == Token::Dollar {
+                    self.advance()?; // Skip $
+                    let column = self.parse_dollar_column()?;
+                    Ok(Expr::QualifiedIdentifier {
+                        table: name,
+                        column,
+                    })
                 } else {
                     Ok(Expr::Identifier(name))
                 }
             }
+            Token::Dot => Ok(Expr::Identifier(self.parse_data_pronoun_column()?)),
             Token::String(s) => {
                 let s = s.clone();
                 self.advance()?;
@@ -1318,6 +2739,18 @@ impl Parser {
                 self.advance()?;
                 Ok(Expr::Literal(LiteralValue::Null))
             }
+            Token::Minus => {
+                self.advance()?; // Skip -
+                let Token::Number(n) = self.current_token else {
+                    return Err(ParseError::UnexpectedToken {
+                        expected: "number after '-'".to_string(),
+                        found: format!("{}", self.current_token),
+                        position: self.position,
+                    });
+                };
+                self.advance()?;
+                Ok(Expr::Literal(LiteralValue::Number(-n)))
+            }
             Token::LeftParen => {
                 self.advance()?; // Skip (
                 let expr = self.parse_expression()?;
@@ -1332,8 +2765,105 @@ impl Parser {
         }
     }
 
+    /// Parses `if_any(cols, ~ .x <op> value)` / `if_all(...)`, after the function
+    /// name, into the predicate applied to each column joined with OR / AND.
+    ///
+    /// Only an explicit column selection and a single comparison of `.x`
+    /// against a literal are supported.
+    fn parse_if_any_all(&mut self, function: &str) -> ParseResult<Expr> {
+        self.advance()?; // Skip (
+        let columns = self.parse_column_selection()?;
+        self.expect_token(Token::Comma)?;
+        self.expect_token(Token::Tilde)?;
+
+        let position = self.position;
+        let unsupported = || ParseError::InvalidExpression {
+            expr: format!("{function}() only supports a `~ .x <op> <value>` predicate"),
+            position,
+        };
+        if self.current_token != Token::Dot {
+            return Err(unsupported());
+        }
+        self.advance()?; // Skip .
+        if self.current_token != Token::Identifier("x".to_string()) {
+            return Err(unsupported());
+        }
+        self.advance()?; // Skip x
+        let operator = match self.current_token {
+            Token::Equal => BinaryOp::Equal,
+            Token::NotEqual => BinaryOp::NotEqual,
+            Token::LessThan => BinaryOp::LessThan,
+            Token::LessThanOrEqual => BinaryOp::LessThanOrEqual,
+            Token::GreaterThan => BinaryOp::GreaterThan,
+            Token::GreaterThanOrEqual => BinaryOp::GreaterThanOrEqual,
+            _ => return Err(unsupported()),
+        };
+        self.advance()?; // Skip operator
+        let value = self.parse_primary_expression()?;
+        if !matches!(value, Expr::Literal(_)) || self.current_token != Token::RightParen {
+            return Err(unsupported());
+        }
+        self.advance()?; // Skip )
+
+        let combine = if function == "if_any" {
+            BinaryOp::Or
+        } else {
+            BinaryOp::And
+        };
+        let predicate = |column: String| Expr::Binary {
+            left: Box::new(Expr::Identifier(column)),
+            operator: operator.clone(),
+            right: Box::new(value.clone()),
+        };
+        let mut columns = columns.into_iter();
+        let first = columns.next().map(predicate).ok_or_else(unsupported)?;
+        Ok(columns.fold(first, |left, column| Expr::Binary {
+            left: Box::new(left),
+            operator: combine.clone(),
+            right: Box::new(predicate(column)),
+        }))
+    }
+
+    /// Checks whether the current token starts a `.data$col` pronoun.
+    fn at_data_pronoun(&mut self) -> ParseResult<bool> {
+        Ok(self.current_token == Token::Dot
+            && self.peek_token()? == Token::Identifier("data".to_string()))
+    }
+
+    /// Parses `.data$col`, the data-mask pronoun for the plain column `col`.
+    fn parse_data_pronoun_column(&mut self) -> ParseResult<String> {
+        self.expect_token(Token::Dot)?;
+        self.expect_identifier_name("data")?;
+        self.expect_token(Token::Dollar)?;
+        self.parse_dollar_column()
+    }
+
+    /// Parses the column name after `$`.
+    fn parse_dollar_column(&mut self) -> ParseResult<String> {
+        match &self.current_token {
+            Token::Identifier(name) => {
+                let name = name.clone();
+                self.advance()?;
+                Ok(name)
+            }
+            _ => Err(ParseError::UnexpectedToken {
+                expected: "column name after '$'".to_string(),
+                found: format!("{}", self.current_token),
+                position: self.position,
+            }),
+        }
+    }
+
     fn parse_function_argument(&mut self) -> ParseResult<Expr> {
         let expr = self.parse_expression()?;
+        if self.current_token == Token::Tilde {
+            self.advance()?; // Skip ~
+            let rhs = self.parse_expression()?;
+            return Ok(Expr::Formula {
+                lhs: Box::new(expr),
+                rhs: Box::new(rhs),
+            });
+        }
         if self.current_token != Token::Assignment {
             return Ok(expr);
         }
@@ -1360,6 +2890,50 @@ impl Parser {
     }
 }
 
+/// Arguments of mutate() / transmute().
+#[derive(Default)]
+struct MutateArguments {
+    assignments: Vec<Assignment>,
+    /// The first `across()` whose columns come from a tidyselect helper
+    /// (`across(everything(), ...)`), which cannot be expanded without the
+    /// table schema.
+    schema_selection: Option<String>,
+    /// `.by` columns
+    by: Option<Vec<String>>,
+}
+
+/// Wraps the operations of one verb call in the grouping of its `.by`
+/// argument: `group_by(by)` before them and an ungrouping `group_by()` after.
+fn scope_to_by(
+    mut operations: Vec<DplyrOperation>,
+    by: Option<Vec<String>>,
+    location: SourceLocation,
+) -> Vec<DplyrOperation> {
+    let Some(columns) = by else {
+        return operations;
+    };
+    let group_by = |columns| DplyrOperation::GroupBy {
+        columns,
+        add: false,
+        grouping: GroupingKind::Plain,
+        location: location.clone(),
+    };
+    operations.insert(0, group_by(columns));
+    operations.push(group_by(Vec::new()));
+    operations
+}
+
+/// Error for a `rollup()` / `cube()` mixed with other group_by() arguments.
+fn grouping_helper_not_alone(kind: GroupingKind, position: usize) -> ParseError {
+    ParseError::InvalidOperation {
+        operation: format!(
+            "{}() must be the only argument of group_by()",
+            kind.helper_name().unwrap_or("group_by")
+        ),
+        position,
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 enum LazyInput {
     MagrittrDot,
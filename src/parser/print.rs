@@ -0,0 +1,134 @@
+//! Expression printer.
+//!
+//! Renders an [`Expr`] back to dplyr-style text for explain output and
+//! diagnostics. Unlike SQL generation this has no dialect dependency.
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+
+use super::ast::{is_whole_number, BinaryOp, Expr, LiteralValue};
+
+/// Renders an expression as dplyr-style text, e.g. `age > 18 AND status == 'active'`.
+///
+/// Parentheses are only added where operator precedence requires them.
+pub fn expr_to_string(expr: &Expr) -> String {
+    match expr {
+        Expr::Identifier(name) => name.clone(),
+        Expr::QualifiedIdentifier { table, column } => format!("{table}${column}"),
+        Expr::Literal(literal) => literal_to_string(literal),
+        Expr::Binary {
+            left,
+            operator,
+            right,
+        } => {
+            let precedence = binary_precedence(operator);
+            let left_text = operand_to_string(left, precedence, false);
+            let right_text = operand_to_string(right, precedence, true);
+            format!("{left_text} {} {right_text}", binary_symbol(operator))
+        }
+        Expr::Function { name, args } => {
+            let args: Vec<String> = args.iter().map(expr_to_string).collect();
+            format!("{name}({})", args.join(", "))
+        }
+        Expr::NamedArg { name, value } => format!("{name} = {}", expr_to_string(value)),
+        Expr::Formula { lhs, rhs } => {
+            format!("{} ~ {}", expr_to_string(lhs), expr_to_string(rhs))
+        }
+        Expr::Not(operand) => match **operand {
+            Expr::Binary { .. } => format!("!({})", expr_to_string(operand)),
+            _ => format!("!{}", expr_to_string(operand)),
+        },
+        // `^` binds tighter than unary minus, so `-a ^ b` needs no parentheses
+        Expr::Negate(operand) => match **operand {
+            Expr::Binary {
+                operator: BinaryOp::Power,
+                ..
+            } => format!("-{}", expr_to_string(operand)),
+            Expr::Binary { .. } | Expr::Not(_) => format!("-({})", expr_to_string(operand)),
+            _ => format!("-{}", expr_to_string(operand)),
+        },
+    }
+}
+
+/// Renders a binary operand, parenthesized when it binds looser than its parent.
+/// Operands of equal precedence on the non-associating side are also
+/// parenthesized (`a - (b - c)`, `(a ^ b) ^ c`).
+fn operand_to_string(expr: &Expr, parent_precedence: u8, is_right: bool) -> String {
+    let text = expr_to_string(expr);
+    match expr {
+        Expr::Binary { operator, .. } => {
+            let precedence = binary_precedence(operator);
+            let right_associative = *operator == BinaryOp::Power;
+            if precedence < parent_precedence
+                || (is_right != right_associative && precedence == parent_precedence)
+            {
+                format!("({text})")
+            } else {
+                text
+            }
+        }
+        // `!` binds looser than comparisons and arithmetic
+        Expr::Not(_) if parent_precedence > binary_precedence(&BinaryOp::And) => {
+            format!("({text})")
+        }
+        // Unary minus binds looser than `^`: `(-a) ^ b`, `(-2) ^ b`
+        Expr::Negate(_) if parent_precedence >= binary_precedence(&BinaryOp::Power) => {
+            format!("({text})")
+        }
+        Expr::Literal(LiteralValue::Number(n))
+            if *n < 0.0 && parent_precedence >= binary_precedence(&BinaryOp::Power) =>
+        {
+            format!("({text})")
+        }
+        _ => text,
+    }
+}
+
+fn literal_to_string(literal: &LiteralValue) -> String {
+    match literal {
+        LiteralValue::String(value) => format!("'{}'", value.replace('\'', "\\'")),
+        LiteralValue::Number(n) if is_whole_number(*n) => format!("{}", *n as i64),
+        LiteralValue::Number(n) => n.to_string(),
+        LiteralValue::Boolean(true) => "TRUE".to_string(),
+        LiteralValue::Boolean(false) => "FALSE".to_string(),
+        LiteralValue::Null => "NA".to_string(),
+    }
+}
+
+const fn binary_precedence(operator: &BinaryOp) -> u8 {
+    match operator {
+        BinaryOp::Or => 1,
+        BinaryOp::And => 2,
+        BinaryOp::Equal
+        | BinaryOp::NotEqual
+        | BinaryOp::LessThan
+        | BinaryOp::LessThanOrEqual
+        | BinaryOp::GreaterThan
+        | BinaryOp::GreaterThanOrEqual => 3,
+        BinaryOp::Plus | BinaryOp::Minus => 4,
+        BinaryOp::Multiply | BinaryOp::Divide => 5,
+        BinaryOp::Power => 6,
+    }
+}
+
+const fn binary_symbol(operator: &BinaryOp) -> &'static str {
+    match operator {
+        BinaryOp::Equal => "==",
+        BinaryOp::NotEqual => "!=",
+        BinaryOp::LessThan => "<",
+        BinaryOp::LessThanOrEqual => "<=",
+        BinaryOp::GreaterThan => ">",
+        BinaryOp::GreaterThanOrEqual => ">=",
+        BinaryOp::And => "AND",
+        BinaryOp::Or => "OR",
+        BinaryOp::Plus => "+",
+        BinaryOp::Minus => "-",
+        BinaryOp::Multiply => "*",
+        BinaryOp::Divide => "/",
+        BinaryOp::Power => "^",
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/print_tests.rs"]
+mod tests;
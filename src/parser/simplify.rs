@@ -0,0 +1,98 @@
+//! Negation folding.
+//!
+//! Removes redundant `!` from a parsed pipeline so the generated SQL reads as
+//! written by hand: `!!x` and `!(!x)` become `x`, and `!(a == b)` becomes
+//! `a != b`. Both rewrites keep SQL's three-valued logic, so NULLs filter the
+//! same way before and after.
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+
+use super::ast::{BinaryOp, DplyrNode, DplyrOperation, Expr};
+
+/// Folds double negations and negated equality tests throughout `ast`.
+pub fn fold_negations(ast: &mut DplyrNode) {
+    if let DplyrNode::Pipeline { operations, .. } = ast {
+        for operation in operations {
+            fold_operation(operation);
+        }
+    }
+}
+
+fn fold_operation(operation: &mut DplyrOperation) {
+    match operation {
+        DplyrOperation::Select { columns, .. } => {
+            for column in columns {
+                fold_expr(&mut column.expr);
+            }
+        }
+        DplyrOperation::Filter { condition, .. } => fold_expr(condition),
+        DplyrOperation::Mutate { assignments, .. } => {
+            for assignment in assignments {
+                fold_expr(&mut assignment.expr);
+            }
+        }
+        DplyrOperation::Join { spec, .. } => {
+            if let Some(on_expr) = &mut spec.on_expr {
+                fold_expr(on_expr);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn fold_expr(expr: &mut Expr) {
+    match expr {
+        Expr::Identifier(_) | Expr::QualifiedIdentifier { .. } | Expr::Literal(_) => {}
+        Expr::Binary { left, right, .. } => {
+            fold_expr(left);
+            fold_expr(right);
+        }
+        Expr::Function { args, .. } => {
+            for arg in args {
+                fold_expr(arg);
+            }
+        }
+        Expr::NamedArg { value, .. } | Expr::Negate(value) => fold_expr(value),
+        Expr::Formula { lhs, rhs } => {
+            fold_expr(lhs);
+            fold_expr(rhs);
+        }
+        Expr::Not(operand) => {
+            fold_expr(operand);
+            if let Some(folded) = negate(operand) {
+                *expr = folded;
+            }
+        }
+    }
+}
+
+/// Returns the simpler equivalent of `!operand`, if there is one.
+fn negate(operand: &mut Expr) -> Option<Expr> {
+    match operand {
+        Expr::Not(inner) => Some(take(inner)),
+        Expr::Binary {
+            left,
+            operator: operator @ (BinaryOp::Equal | BinaryOp::NotEqual),
+            right,
+        } => Some(Expr::Binary {
+            left: Box::new(take(left)),
+            operator: if *operator == BinaryOp::Equal {
+                BinaryOp::NotEqual
+            } else {
+                BinaryOp::Equal
+            },
+            right: Box::new(take(right)),
+        }),
+        _ => None,
+    }
+}
+
+/// Moves `expr` out, leaving a placeholder the caller overwrites.
+fn take(expr: &mut Expr) -> Expr {
+    core::mem::replace(expr, Expr::Identifier(Default::default()))
+}
+
+#[cfg(test)]
+#[path = "tests/simplify_tests.rs"]
+mod tests;
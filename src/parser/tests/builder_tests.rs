@@ -0,0 +1,91 @@
+use super::*;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+
+/// Parses `code`, resetting every location to unknown so it compares equal
+/// to a built pipeline.
+fn parsed_without_locations(code: &str) -> DplyrNode {
+    let ast = Parser::new(Lexer::new(code.to_string()))
+        .unwrap()
+        .parse()
+        .unwrap();
+    let DplyrNode::Pipeline {
+        source, operations, ..
+    } = ast
+    else {
+        panic!("expected a pipeline");
+    };
+    let operations = operations
+        .into_iter()
+        .map(|mut operation| {
+            match &mut operation {
+                DplyrOperation::Select { location, .. }
+                | DplyrOperation::Filter { location, .. }
+                | DplyrOperation::Mutate { location, .. }
+                | DplyrOperation::Arrange { location, .. }
+                | DplyrOperation::GroupBy { location, .. }
+                | DplyrOperation::Summarise { location, .. } => {
+                    *location = SourceLocation::unknown()
+                }
+                other => panic!("unexpected operation {other:?}"),
+            }
+            operation
+        })
+        .collect::<Vec<_>>();
+    operations
+        .into_iter()
+        .fold(
+            DplyrNode::pipeline().source(source.unwrap()),
+            PipelineBuilder::operation,
+        )
+        .build()
+}
+
+#[test]
+fn test_builder_matches_parsed_pipeline() {
+    let built = DplyrNode::pipeline()
+        .source("data")
+        .select(["name", "age"])
+        .filter(Expr::binary(
+            Expr::column("age"),
+            BinaryOp::GreaterThan,
+            Expr::number(18.0),
+        ))
+        .mutate([("label", Expr::call("toupper", vec![Expr::column("name")]))])
+        .arrange([OrderExpr::desc("age"), OrderExpr::asc("name")])
+        .build();
+
+    assert_eq!(
+        built,
+        parsed_without_locations(
+            "data %>% select(name, age) %>% filter(age > 18) %>% mutate(label = toupper(name)) \
+             %>% arrange(desc(age), name)"
+        )
+    );
+}
+
+#[test]
+fn test_builder_groups_and_summarises() {
+    let built = DplyrNode::pipeline()
+        .source("sales")
+        .group_by(["region"])
+        .summarise([Aggregation::new("total", "sum", "amount")])
+        .build();
+
+    assert_eq!(
+        built,
+        parsed_without_locations("sales %>% group_by(region) %>% summarise(total = sum(amount))")
+    );
+}
+
+#[test]
+fn test_empty_builder_is_an_empty_pipeline() {
+    let DplyrNode::Pipeline {
+        source, operations, ..
+    } = DplyrNode::pipeline().build()
+    else {
+        panic!("expected a pipeline");
+    };
+    assert_eq!(source, None);
+    assert!(operations.is_empty());
+}
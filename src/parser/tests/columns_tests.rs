@@ -0,0 +1,45 @@
+use super::*;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+
+fn columns_of(code: &str) -> Vec<String> {
+    let mut parser = Parser::new(Lexer::new(code.to_string())).unwrap();
+    let mut columns: Vec<String> = referenced_columns(&parser.parse().unwrap())
+        .into_iter()
+        .collect();
+    columns.sort();
+    columns
+}
+
+#[test]
+fn test_collects_columns_from_every_verb() {
+    assert_eq!(
+        columns_of(
+            "data %>% select(name, dept, salary, hired) %>% filter(salary > 1000 & coalesce(hired, 0) > 0) \
+             %>% group_by(dept) %>% summarise(avg = mean(salary)) %>% arrange(desc(avg))"
+        ),
+        ["dept", "hired", "name", "salary"]
+    );
+}
+
+#[test]
+fn test_skips_columns_defined_by_the_pipeline() {
+    assert_eq!(
+        columns_of("mutate(total = price * qty) %>% filter(total > 10) %>% arrange(total)"),
+        ["price", "qty"]
+    );
+    assert_eq!(
+        columns_of("rename(amount = amt) %>% filter(amount > 0)"),
+        ["amt"]
+    );
+    // Read before being overwritten, so still a source column
+    assert_eq!(columns_of("mutate(x = x + 1)"), ["x"]);
+}
+
+#[test]
+fn test_ignores_other_data_frames_and_n() {
+    assert_eq!(
+        columns_of("filter(id == other$id) %>% summarise(n = n())"),
+        ["id"]
+    );
+}
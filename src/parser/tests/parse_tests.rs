@@ -412,6 +412,33 @@ fn test_parse_error_empty_pipeline() {
     }
 }
 
+#[test]
+fn test_parse_error_reports_unbalanced_parentheses_position() {
+    let mut parser = Parser::new(Lexer::new("select(a %>% filter(b".to_string())).unwrap();
+    let error = parser.parse().unwrap_err();
+    // `select` is token 0, its `(` token 1, and the pipe token 3
+    assert_eq!(
+        error,
+        ParseError::UnclosedParenthesis {
+            opened_at: 1,
+            position: 3
+        }
+    );
+    assert_eq!(error.position(), Some(3));
+
+    let mut parser = Parser::new(Lexer::new("select(a)) %>% head(2)".to_string())).unwrap();
+    assert_eq!(
+        parser.parse().unwrap_err(),
+        ParseError::UnmatchedClosingParenthesis { position: 4 }
+    );
+
+    let mut parser = Parser::new(Lexer::new("filter((x > 1)".to_string())).unwrap();
+    assert!(matches!(
+        parser.parse().unwrap_err(),
+        ParseError::UnclosedParenthesis { opened_at: 1, .. }
+    ));
+}
+
 #[test]
 fn test_parse_error_missing_parentheses() {
     let lexer = Lexer::new("select name, age".to_string());
@@ -705,6 +732,80 @@ mod select_parsing_tests {
         }
     }
 
+    #[test]
+    fn test_scalar_logical_operators_parse_like_vectorized_forms() {
+        let parse = |code: &str| {
+            let lexer = Lexer::new(code.to_string());
+            Parser::new(lexer).unwrap().parse().unwrap()
+        };
+
+        let short = parse("filter(a > 1 & b < 2 | c == 3)");
+        let long = parse("filter(a > 1 && b < 2 || c == 3)");
+
+        let (
+            DplyrNode::Pipeline {
+                operations: short_ops,
+                ..
+            },
+            DplyrNode::Pipeline {
+                operations: long_ops,
+                ..
+            },
+        ) = (&short, &long)
+        else {
+            panic!("Expected Pipeline nodes");
+        };
+        let (
+            DplyrOperation::Filter {
+                condition: short_condition,
+                ..
+            },
+            DplyrOperation::Filter {
+                condition: long_condition,
+                ..
+            },
+        ) = (&short_ops[0], &long_ops[0])
+        else {
+            panic!("Expected Filter operations");
+        };
+        assert_eq!(short_condition, long_condition);
+    }
+
+    #[test]
+    fn test_case_when_arguments_parse_as_formulas() {
+        let lexer = Lexer::new("mutate(y = case_when(is.na(x) ~ 0, TRUE ~ x))".to_string());
+        let mut parser = Parser::new(lexer).unwrap();
+
+        let ast = parser.parse().unwrap();
+
+        let DplyrNode::Pipeline { operations, .. } = ast else {
+            panic!("Expected Pipeline node");
+        };
+        let DplyrOperation::Mutate { assignments, .. } = &operations[0] else {
+            panic!("Expected Mutate operation");
+        };
+        let Expr::Function { name, args } = &assignments[0].expr else {
+            panic!("Expected case_when function call");
+        };
+        assert_eq!(name, "case_when");
+        assert_eq!(
+            args,
+            &vec![
+                Expr::Formula {
+                    lhs: Box::new(Expr::Function {
+                        name: "is.na".to_string(),
+                        args: vec![Expr::Identifier("x".to_string())],
+                    }),
+                    rhs: Box::new(Expr::Literal(LiteralValue::Number(0.0))),
+                },
+                Expr::Formula {
+                    lhs: Box::new(Expr::Literal(LiteralValue::Boolean(true))),
+                    rhs: Box::new(Expr::Identifier("x".to_string())),
+                },
+            ]
+        );
+    }
+
     #[test]
     fn test_select_with_function_call_and_alias() {
         let lexer = Lexer::new(
@@ -831,6 +932,39 @@ mod select_parsing_tests {
             panic!("Expected Pipeline node");
         }
     }
+
+    #[test]
+    fn test_select_negated_columns_are_excluded() {
+        let lexer = Lexer::new("select(-id, -\"created at\")".to_string());
+        let mut parser = Parser::new(lexer).unwrap();
+
+        let DplyrNode::Pipeline { operations, .. } = parser.parse().unwrap() else {
+            panic!("Expected Pipeline node");
+        };
+        let DplyrOperation::Select {
+            columns, excluded, ..
+        } = &operations[0]
+        else {
+            panic!("Expected Select operation");
+        };
+        assert!(columns.is_empty());
+        assert_eq!(excluded, &["id", "created at"]);
+    }
+
+    #[test]
+    fn test_select_rejects_mixed_kept_and_dropped_columns() {
+        for input in ["select(name, -id)", "select(-id, name)"] {
+            let mut parser = Parser::new(Lexer::new(input.to_string())).unwrap();
+            assert!(
+                matches!(
+                    parser.parse(),
+                    Err(ParseError::InvalidOperation { ref operation, .. })
+                        if operation.contains("dropped (-col)")
+                ),
+                "{input}"
+            );
+        }
+    }
 }
 
 // ===== filter() 함수 파싱 테스트 =====
@@ -869,6 +1003,60 @@ mod filter_parsing_tests {
         }
     }
 
+    #[test]
+    fn test_filter_numeric_literal_on_left_of_comparison() {
+        let lexer = Lexer::new("filter(0 < x)".to_string());
+        let mut parser = Parser::new(lexer).unwrap();
+
+        let ast = parser.parse().unwrap();
+
+        if let DplyrNode::Pipeline { operations, .. } = ast {
+            if let DplyrOperation::Filter { condition, .. } = &operations[0] {
+                assert_eq!(
+                    *condition,
+                    Expr::Binary {
+                        left: Box::new(Expr::Literal(LiteralValue::Number(0.0))),
+                        operator: BinaryOp::LessThan,
+                        right: Box::new(Expr::Identifier("x".to_string())),
+                    }
+                );
+            } else {
+                panic!("Expected Filter operation");
+            }
+        } else {
+            panic!("Expected Pipeline node");
+        }
+    }
+
+    #[test]
+    fn test_filter_not_binds_looser_than_comparison() {
+        let lexer = Lexer::new("filter(!is_active & !x == y)".to_string());
+        let mut parser = Parser::new(lexer).unwrap();
+
+        let ast = parser.parse().unwrap();
+
+        let DplyrNode::Pipeline { operations, .. } = ast else {
+            panic!("Expected Pipeline node");
+        };
+        let DplyrOperation::Filter { condition, .. } = &operations[0] else {
+            panic!("Expected Filter operation");
+        };
+        assert_eq!(
+            *condition,
+            Expr::Binary {
+                left: Box::new(Expr::Not(Box::new(Expr::Identifier(
+                    "is_active".to_string()
+                )))),
+                operator: BinaryOp::And,
+                right: Box::new(Expr::Not(Box::new(Expr::Binary {
+                    left: Box::new(Expr::Identifier("x".to_string())),
+                    operator: BinaryOp::Equal,
+                    right: Box::new(Expr::Identifier("y".to_string())),
+                }))),
+            }
+        );
+    }
+
     #[test]
     fn test_filter_equality_comparison() {
         let lexer = Lexer::new("filter(name == \"John\")".to_string());
@@ -1486,6 +1674,7 @@ mod mutate_parsing_tests {
             ("mutate(result = a - b)", BinaryOp::Minus),
             ("mutate(result = a * b)", BinaryOp::Multiply),
             ("mutate(result = a / b)", BinaryOp::Divide),
+            ("mutate(result = a ^ b)", BinaryOp::Power),
         ];
 
         for (input, expected_op) in test_cases {
@@ -1513,6 +1702,62 @@ mod mutate_parsing_tests {
             }
         }
     }
+
+    #[test]
+    fn test_mutate_power_binds_tighter_and_is_right_associative() {
+        let lexer = Lexer::new("mutate(result = 2 * a ^ b ^ c)".to_string());
+        let mut parser = Parser::new(lexer).unwrap();
+
+        let ast = parser.parse().unwrap();
+
+        let DplyrNode::Pipeline { operations, .. } = ast else {
+            panic!("Expected Pipeline node");
+        };
+        let DplyrOperation::Mutate { assignments, .. } = &operations[0] else {
+            panic!("Expected Mutate operation");
+        };
+        let power = |left: Expr, right: Expr| Expr::Binary {
+            left: Box::new(left),
+            operator: BinaryOp::Power,
+            right: Box::new(right),
+        };
+        let ident = |name: &str| Expr::Identifier(name.to_string());
+        assert_eq!(
+            assignments[0].expr,
+            Expr::Binary {
+                left: Box::new(Expr::Literal(LiteralValue::Number(2.0))),
+                operator: BinaryOp::Multiply,
+                right: Box::new(power(ident("a"), power(ident("b"), ident("c")))),
+            }
+        );
+    }
+
+    #[test]
+    fn test_transmute_expands_to_mutate_and_select() {
+        let lexer = Lexer::new("data %>% transmute(total = a + b, c = d)".to_string());
+        let mut parser = Parser::new(lexer).unwrap();
+
+        let DplyrNode::Pipeline { operations, .. } = parser.parse().unwrap() else {
+            panic!("Expected Pipeline node");
+        };
+        assert_eq!(operations.len(), 2);
+        let DplyrOperation::Mutate { assignments, .. } = &operations[0] else {
+            panic!("Expected Mutate operation");
+        };
+        let names: Vec<_> = assignments.iter().map(|a| a.column.as_str()).collect();
+        assert_eq!(names, ["total", "c"]);
+        let DplyrOperation::Select { columns, .. } = &operations[1] else {
+            panic!("Expected Select operation");
+        };
+        let selected: Vec<_> = columns.iter().map(|column| &column.expr).collect();
+        assert_eq!(
+            selected,
+            [
+                &Expr::Identifier("total".to_string()),
+                &Expr::Identifier("c".to_string())
+            ]
+        );
+    }
 }
 
 // ===== arrange() 함수 파싱 테스트 =====
@@ -2149,6 +2394,585 @@ mod summarise_parsing_tests {
             panic!("Expected Pipeline node");
         }
     }
+
+    #[test]
+    fn test_count_expands_to_group_by_and_summarise() {
+        for (input, function, column) in [
+            ("count(category)", "n", ""),
+            ("data %>% count(category, wt = quantity)", "sum", "quantity"),
+        ] {
+            let mut parser = Parser::new(Lexer::new(input.to_string())).unwrap();
+            let DplyrNode::Pipeline { operations, .. } = parser.parse().unwrap() else {
+                panic!("Expected Pipeline node");
+            };
+
+            assert_eq!(operations.len(), 2);
+            assert!(matches!(
+                &operations[0],
+                DplyrOperation::GroupBy { columns, add: true, .. } if columns == &["category"]
+            ));
+            let DplyrOperation::Summarise { aggregations, .. } = &operations[1] else {
+                panic!("Expected Summarise operation");
+            };
+            assert_eq!(aggregations[0].function, function);
+            assert_eq!(aggregations[0].column, column);
+            assert_eq!(aggregations[0].alias.as_deref(), Some("n"));
+        }
+    }
+
+    #[test]
+    fn test_count_rejects_unsupported_named_arguments() {
+        let mut parser =
+            Parser::new(Lexer::new("count(category, drop = TRUE)".to_string())).unwrap();
+        assert!(matches!(
+            parser.parse(),
+            Err(ParseError::InvalidOperation { operation, .. }) if operation.contains("`drop`")
+        ));
+    }
+
+    #[test]
+    fn test_count_sort_appends_descending_arrange() {
+        let operations = |code: &str| {
+            let mut parser = Parser::new(Lexer::new(code.to_string())).unwrap();
+            let DplyrNode::Pipeline { operations, .. } = parser.parse().unwrap() else {
+                panic!("Expected Pipeline node");
+            };
+            operations
+        };
+
+        let sorted = operations("count(category, sort = TRUE, name = \"cnt\")");
+        assert_eq!(sorted.len(), 3);
+        assert!(matches!(
+            &sorted[2],
+            DplyrOperation::Arrange { columns, .. }
+                if columns == &[OrderExpr::desc("cnt")]
+        ));
+        assert_eq!(operations("count(category, sort = FALSE)").len(), 2);
+
+        let mut parser = Parser::new(Lexer::new("count(category, sort = 1)".to_string())).unwrap();
+        assert!(matches!(
+            parser.parse(),
+            Err(ParseError::UnexpectedToken { expected, .. }) if expected == "TRUE or FALSE"
+        ));
+    }
+
+    #[test]
+    fn test_count_and_tally_name_argument_sets_alias() {
+        for (input, columns, function) in [
+            ("count(category, name = \"cnt\")", vec!["category"], "n"),
+            ("data %>% count(name = \"cnt\", wt = qty)", vec![], "sum"),
+            ("data %>% tally(name = \"cnt\")", vec![], "n"),
+        ] {
+            let mut parser = Parser::new(Lexer::new(input.to_string())).unwrap();
+            let DplyrNode::Pipeline { operations, .. } = parser.parse().unwrap() else {
+                panic!("Expected Pipeline node");
+            };
+
+            assert!(matches!(
+                &operations[0],
+                DplyrOperation::GroupBy { columns: parsed, add: true, .. } if parsed == &columns
+            ));
+            let DplyrOperation::Summarise { aggregations, .. } = &operations[1] else {
+                panic!("Expected Summarise operation");
+            };
+            assert_eq!(aggregations[0].function, function);
+            assert_eq!(aggregations[0].alias.as_deref(), Some("cnt"));
+        }
+    }
+
+    #[test]
+    fn test_tally_rejects_grouping_columns() {
+        let mut parser = Parser::new(Lexer::new("data %>% tally(category)".to_string())).unwrap();
+        assert!(matches!(
+            parser.parse(),
+            Err(ParseError::InvalidOperation { operation, .. }) if operation.starts_with("tally()")
+        ));
+    }
+
+    #[test]
+    fn test_collect_and_show_query_add_no_operations() {
+        let parse = |code: &str| {
+            let mut parser = Parser::new(Lexer::new(code.to_string())).unwrap();
+            parser.parse()
+        };
+        assert_eq!(
+            parse("data %>% select(a) %>% collect()").unwrap(),
+            parse("data %>% select(a)").unwrap()
+        );
+        assert_eq!(
+            parse("data %>% select(a) %>% show_query()").unwrap(),
+            parse("data %>% select(a)").unwrap()
+        );
+        assert!(matches!(
+            parse("data %>% select(a) %>% collect(n = 5)"),
+            Err(ParseError::TooManyArguments { function, .. }) if function == "collect"
+        ));
+    }
+
+    #[test]
+    fn test_if_any_and_if_all_expand_across_columns() {
+        let condition = |code: &str| {
+            let mut parser = Parser::new(Lexer::new(code.to_string())).unwrap();
+            let DplyrNode::Pipeline { operations, .. } = parser.parse().unwrap() else {
+                panic!("Expected Pipeline node");
+            };
+            let DplyrOperation::Filter { condition, .. } = &operations[0] else {
+                panic!("Expected Filter operation");
+            };
+            condition.clone()
+        };
+        let gt_zero = |column: &str| Expr::Binary {
+            left: Box::new(Expr::Identifier(column.to_string())),
+            operator: BinaryOp::GreaterThan,
+            right: Box::new(Expr::Literal(LiteralValue::Number(0.0))),
+        };
+
+        for (function, operator) in [("if_any", BinaryOp::Or), ("if_all", BinaryOp::And)] {
+            assert_eq!(
+                condition(&format!("filter({function}(c(a, b), ~ .x > 0))")),
+                Expr::Binary {
+                    left: Box::new(gt_zero("a")),
+                    operator,
+                    right: Box::new(gt_zero("b")),
+                }
+            );
+            assert_eq!(
+                condition(&format!("filter({function}(a, ~ .x > 0))")),
+                gt_zero("a")
+            );
+        }
+    }
+
+    #[test]
+    fn test_if_any_rejects_complex_lambdas() {
+        for code in [
+            "filter(if_any(c(a, b), ~ .x > 0 & .x < 5))",
+            "filter(if_any(c(a, b), ~ abs(.x) > 0))",
+            "filter(if_all(c(a, b), ~ .x > limit))",
+            "filter(if_all(c(a, b), ~ 0 < .x))",
+        ] {
+            let mut parser = Parser::new(Lexer::new(code.to_string())).unwrap();
+            assert!(
+                matches!(
+                    parser.parse(),
+                    Err(ParseError::InvalidExpression { expr, .. }) if expr.contains("`~ .x <op> <value>`")
+                ),
+                "{code}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_replace_na_parses_as_coalescing_mutate() {
+        let parse = |code: &str| {
+            let mut parser = Parser::new(Lexer::new(code.to_string())).unwrap();
+            parser.parse()
+        };
+        let expected =
+            parse(r#"data %>% mutate(x = replace_na(x, 0), y = replace_na(y, "none"))"#).unwrap();
+        assert_eq!(
+            parse(r#"data %>% replace_na(x = 0, y = "none")"#).unwrap(),
+            expected
+        );
+        assert_eq!(
+            parse(r#"data %>% replace_na(list(x = 0, y = "none"))"#).unwrap(),
+            expected
+        );
+        assert!(matches!(
+            parse("data %>% replace_na()"),
+            Err(ParseError::MissingArgument { function, .. }) if function == "replace_na"
+        ));
+    }
+
+    #[test]
+    fn test_pathological_nesting_is_rejected() {
+        let depth = 10_000;
+        let inputs = [
+            format!("filter({}x{} > 1)", "(".repeat(depth), ")".repeat(depth)),
+            format!("mutate(y = {}x{})", "abs(".repeat(depth), ")".repeat(depth)),
+            format!("filter({}x)", "!".repeat(depth)),
+            format!("mutate(y = {}x)", "x^".repeat(depth)),
+            format!("mutate(y = {}x)", "x + ".repeat(depth)),
+            format!("filter({}x > 1)", "x > 1 & ".repeat(depth)),
+        ];
+
+        for input in inputs {
+            let mut parser = Parser::new(Lexer::new(input)).unwrap();
+            assert!(matches!(
+                parser.parse().unwrap_err(),
+                ParseError::MaxNestingDepthExceeded { .. }
+            ));
+        }
+
+        // Nesting up to the limit still parses
+        let depth = MAX_NESTING_DEPTH - 2;
+        let input = format!("filter({}x{} > 1)", "(".repeat(depth), ")".repeat(depth));
+        assert!(Parser::new(Lexer::new(input)).unwrap().parse().is_ok());
+        let input = format!("mutate(y = {}x)", "x + ".repeat(MAX_EXPRESSION_DEPTH - 2));
+        assert!(Parser::new(Lexer::new(input)).unwrap().parse().is_ok());
+    }
+
+    #[test]
+    fn test_summarise_groups_argument() {
+        let groups = |code: &str| {
+            let mut parser = Parser::new(Lexer::new(code.to_string())).unwrap();
+            let DplyrNode::Pipeline { operations, .. } = parser.parse().unwrap() else {
+                panic!("expected pipeline");
+            };
+            let DplyrOperation::Summarise {
+                aggregations,
+                groups,
+                ..
+            } = &operations[0]
+            else {
+                panic!("expected summarise");
+            };
+            assert_eq!(aggregations.len(), 1);
+            *groups
+        };
+        assert_eq!(
+            groups("summarise(x = mean(y), .groups = \"drop\")"),
+            SummariseGroups::Drop
+        );
+        assert_eq!(
+            groups("summarise(.groups = \"keep\", x = mean(y))"),
+            SummariseGroups::Keep
+        );
+        assert_eq!(groups("summarise(x = mean(y))"), SummariseGroups::DropLast);
+
+        let mut parser = Parser::new(Lexer::new(
+            "summarise(x = mean(y), .groups = \"rowwise\")".to_string(),
+        ))
+        .unwrap();
+        assert!(matches!(
+            parser.parse(),
+            Err(ParseError::InvalidOperation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_from_tokens_matches_lexer_input() {
+        let lex = |code: &str, pipe_syntax: PipeSyntax| {
+            let mut lexer = Lexer::with_pipe_syntax(code.to_string(), pipe_syntax);
+            let mut tokens = Vec::new();
+            loop {
+                let token = lexer.next_token().unwrap();
+                if token == Token::EOF {
+                    return tokens;
+                }
+                tokens.push(token);
+            }
+        };
+
+        let code = "data %>%\n  group_by(dept) %>%\n  summarise(avg = mean(salary))";
+        let expected = Parser::new(Lexer::new(code.to_string()))
+            .unwrap()
+            .parse()
+            .unwrap();
+        let tokens = lex(code, PipeSyntax::Magrittr);
+        assert_eq!(Parser::from_tokens(tokens).parse().unwrap(), expected);
+
+        let code = "data |> (\\(x) x |> select(name))()";
+        let expected = Parser::new(Lexer::with_pipe_syntax(
+            code.to_string(),
+            PipeSyntax::Native,
+        ))
+        .unwrap()
+        .parse()
+        .unwrap();
+        let parsed = Parser::from_tokens(lex(code, PipeSyntax::Native))
+            .with_pipe_syntax(PipeSyntax::Native)
+            .parse()
+            .unwrap();
+        assert_eq!(parsed, expected);
+    }
+
+    #[test]
+    fn test_pivot_wider_requires_explicit_names() {
+        let parse = |code: &str| Parser::new(Lexer::new(code.to_string())).unwrap().parse();
+
+        let ast =
+            parse("pivot_wider(names = c(\"a\", \"b\"), names_from = key, values_from = value)")
+                .unwrap();
+        let DplyrNode::Pipeline { operations, .. } = ast else {
+            panic!("expected pipeline");
+        };
+        assert_eq!(
+            operations[0],
+            DplyrOperation::PivotWider {
+                names_from: "key".to_string(),
+                values_from: "value".to_string(),
+                names: vec!["a".to_string(), "b".to_string()],
+                location: operations[0].location().clone(),
+            }
+        );
+
+        assert!(matches!(
+            parse("pivot_wider(names_from = key, values_from = value)"),
+            Err(ParseError::InvalidOperation { .. })
+        ));
+        assert!(matches!(
+            parse("pivot_wider(names_from = key, names = \"a\")"),
+            Err(ParseError::MissingArgument { .. })
+        ));
+        assert!(matches!(
+            parse("pivot_wider(names_from = key, values_from = value, names = \"a\", values_fill = 0)"),
+            Err(ParseError::InvalidOperation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_distinct_marker_inside_aggregate() {
+        let mut parser = Parser::new(Lexer::new(
+            "summarise(s = sum(distinct amount), m = mean(amount))".to_string(),
+        ))
+        .unwrap();
+        let DplyrNode::Pipeline { operations, .. } = parser.parse().unwrap() else {
+            panic!("expected pipeline");
+        };
+        let DplyrOperation::Summarise { aggregations, .. } = &operations[0] else {
+            panic!("expected summarise");
+        };
+        assert_eq!(
+            aggregations[0],
+            Aggregation::new("s", "sum", "amount").distinct()
+        );
+        assert_eq!(aggregations[1], Aggregation::new("m", "mean", "amount"));
+
+        let mut parser =
+            Parser::new(Lexer::new("summarise(s = sum(distinct))".to_string())).unwrap();
+        assert!(matches!(
+            parser.parse(),
+            Err(ParseError::UnexpectedToken { .. })
+        ));
+    }
+
+    #[test]
+    fn test_between_infix_parses_as_between_call() {
+        let parse = |code: &str| {
+            let mut parser = Parser::new(Lexer::new(code.to_string())).unwrap();
+            let DplyrNode::Pipeline { operations, .. } = parser.parse().unwrap() else {
+                panic!("expected pipeline");
+            };
+            let DplyrOperation::Filter { condition, .. } = &operations[0] else {
+                panic!("expected filter");
+            };
+            condition.clone()
+        };
+        assert_eq!(
+            parse("filter(x %between% c(1, 10))"),
+            parse("filter(between(x, 1, 10))")
+        );
+        // Like R's %op% infixes, %between% binds tighter than arithmetic
+        assert_eq!(
+            parse("filter(y * x %between% c(1, 10))"),
+            parse("filter(y * between(x, 1, 10))")
+        );
+
+        let mut parser = Parser::new(Lexer::new("filter(x %between% 1)".to_string())).unwrap();
+        assert!(matches!(
+            parser.parse(),
+            Err(ParseError::UnexpectedToken { .. })
+        ));
+    }
+
+    #[test]
+    fn test_ast_equal_ignores_source_locations() {
+        let parse = |code: &str| {
+            Parser::new(Lexer::new(code.to_string()))
+                .unwrap()
+                .parse()
+                .unwrap()
+        };
+        let compact = parse("data %>% filter(x > 1) %>% select(a, b)");
+        let spread = parse("data   %>%\n  filter( x > 1 ) %>%\n\n  select(a,\n         b)");
+        assert_ne!(compact, spread);
+        assert!(ast_equal(&compact, &spread));
+
+        let different = parse("data %>% filter(x > 2) %>% select(a, b)");
+        assert!(!ast_equal(&compact, &different));
+    }
+
+    #[test]
+    fn test_tidyselect_helper_in_select_parses_as_unsupported() {
+        let mut parser = Parser::new(Lexer::new(
+            "select(a, num_range(\"x\", 1:3), b)".to_string(),
+        ))
+        .unwrap();
+        let DplyrNode::Pipeline { operations, .. } = parser.parse().unwrap() else {
+            panic!("expected pipeline");
+        };
+        assert!(matches!(
+            &operations[0],
+            DplyrOperation::Unsupported { verb, reason: Some(reason), .. }
+                if verb == "select" && reason.starts_with("num_range(...) in select()")
+        ));
+
+        // A column that merely shares a helper's name is still a column
+        let mut parser = Parser::new(Lexer::new("select(contains)".to_string())).unwrap();
+        let DplyrNode::Pipeline { operations, .. } = parser.parse().unwrap() else {
+            panic!("expected pipeline");
+        };
+        assert!(matches!(&operations[0], DplyrOperation::Select { .. }));
+    }
+
+    #[test]
+    fn test_quantile_probability_is_parsed() {
+        let mut parser = Parser::new(Lexer::new(
+            "summarise(a = quantile(x, 0.9), b = percentile(x, probs = 0.25))".to_string(),
+        ))
+        .unwrap();
+        let DplyrNode::Pipeline { operations, .. } = parser.parse().unwrap() else {
+            panic!("expected pipeline");
+        };
+        let DplyrOperation::Summarise { aggregations, .. } = &operations[0] else {
+            panic!("expected summarise");
+        };
+        assert_eq!(aggregations[0].probability, Some(0.9));
+        assert_eq!(aggregations[1].probability, Some(0.25));
+
+        let mut parser = Parser::new(Lexer::new("summarise(a = quantile(x))".to_string())).unwrap();
+        assert!(matches!(
+            parser.parse(),
+            Err(ParseError::MissingArgument { .. })
+        ));
+
+        let mut parser =
+            Parser::new(Lexer::new("summarise(a = quantile(x, 1.5))".to_string())).unwrap();
+        assert!(matches!(
+            parser.parse(),
+            Err(ParseError::InvalidOperation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_null_default_infix_parses_as_coalesce_call() {
+        let parse = |code: &str| {
+            let mut parser = Parser::new(Lexer::new(code.to_string())).unwrap();
+            let DplyrNode::Pipeline { operations, .. } = parser.parse().unwrap() else {
+                panic!("expected pipeline");
+            };
+            let DplyrOperation::Filter { condition, .. } = &operations[0] else {
+                panic!("expected filter");
+            };
+            condition.clone()
+        };
+        assert_eq!(
+            parse("filter(flag %||% TRUE)"),
+            parse("filter(coalesce(flag, TRUE))")
+        );
+        // Left-associative, and tighter than comparison and arithmetic
+        assert_eq!(
+            parse("filter(a %||% b %||% 0 > x * 2)"),
+            parse("filter(coalesce(coalesce(a, b), 0) > x * 2)")
+        );
+    }
+
+    #[test]
+    fn test_group_by_rollup_and_cube_helpers() {
+        for (code, kind) in [
+            ("group_by(rollup(a, b))", GroupingKind::Rollup),
+            ("group_by(cube(a, b))", GroupingKind::Cube),
+            ("group_by(a, b)", GroupingKind::Plain),
+        ] {
+            let mut parser = Parser::new(Lexer::new(code.to_string())).unwrap();
+            let DplyrNode::Pipeline { operations, .. } = parser.parse().unwrap() else {
+                panic!("expected pipeline");
+            };
+            assert!(matches!(
+                &operations[0],
+                DplyrOperation::GroupBy { columns, grouping, .. }
+                    if columns == &["a", "b"] && *grouping == kind
+            ));
+        }
+
+        for code in [
+            "group_by(x, rollup(a, b))",
+            "group_by(cube(a), x)",
+            "group_by(rollup(a), .add = TRUE)",
+        ] {
+            let mut parser = Parser::new(Lexer::new(code.to_string())).unwrap();
+            assert!(
+                matches!(parser.parse(), Err(ParseError::InvalidOperation { .. })),
+                "{code}"
+            );
+        }
+
+        let mut parser = Parser::new(Lexer::new("group_by(rollup())".to_string())).unwrap();
+        assert!(matches!(
+            parser.parse(),
+            Err(ParseError::MissingArgument { .. })
+        ));
+    }
+
+    #[test]
+    fn test_inline_by_scopes_grouping_to_the_call() {
+        let mut parser =
+            Parser::new(Lexer::new("mutate(m = mean(x), .by = c(a, b))".to_string())).unwrap();
+        let DplyrNode::Pipeline { operations, .. } = parser.parse().unwrap() else {
+            panic!("expected pipeline");
+        };
+        assert_eq!(operations.len(), 3);
+        assert!(matches!(
+            &operations[0],
+            DplyrOperation::GroupBy { columns, add: false, .. } if columns == &["a", "b"]
+        ));
+        assert!(matches!(operations[1], DplyrOperation::Mutate { .. }));
+        assert!(matches!(
+            &operations[2],
+            DplyrOperation::GroupBy { columns, add: false, .. } if columns.is_empty()
+        ));
+
+        let mut parser =
+            Parser::new(Lexer::new("filter(x > 1, .by = g, y < 2)".to_string())).unwrap();
+        let DplyrNode::Pipeline { operations, .. } = parser.parse().unwrap() else {
+            panic!("expected pipeline");
+        };
+        assert!(matches!(
+            &operations[1],
+            DplyrOperation::Filter {
+                condition: Expr::Binary {
+                    operator: BinaryOp::And,
+                    ..
+                },
+                ..
+            }
+        ));
+
+        let mut parser =
+            Parser::new(Lexer::new("summarise(n = n(), .by = g)".to_string())).unwrap();
+        let DplyrNode::Pipeline { operations, .. } = parser.parse().unwrap() else {
+            panic!("expected pipeline");
+        };
+        assert!(matches!(
+            operations[1],
+            DplyrOperation::Summarise {
+                groups: SummariseGroups::Drop,
+                ..
+            }
+        ));
+
+        let mut parser = Parser::new(Lexer::new(
+            "summarise(n = n(), .by = g, .groups = \"keep\")".to_string(),
+        ))
+        .unwrap();
+        assert!(matches!(
+            parser.parse(),
+            Err(ParseError::InvalidOperation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_summarize_and_summarise_parse_identically() {
+        let parse = |code: &str| {
+            let mut parser = Parser::new(Lexer::new(code.to_string())).unwrap();
+            parser.parse().unwrap()
+        };
+        assert_eq!(
+            parse("data %>% group_by(dept) %>% summarize(avg = mean(salary), n = n())"),
+            parse("data %>% group_by(dept) %>% summarise(avg = mean(salary), n = n())")
+        );
+    }
 }
 
 // ===== 파이프라인 파싱 테스트 =====
@@ -2264,6 +3088,384 @@ mod pipeline_parsing_tests {
         }
     }
 
+    #[test]
+    fn test_pipeline_with_crlf_and_tab_indentation() {
+        let input = "data %>%\r\n\tselect(\r\n\t\tname,\r\n\t\tage\r\n\t) %>%\r\n\tfilter(age > 18) %>%\r\n\tarrange(desc(age))\r\n";
+        let lexer = Lexer::new(input.to_string());
+        let mut parser = Parser::new(lexer).unwrap();
+
+        let ast = parser.parse().unwrap();
+
+        let lf_input = input.replace("\r\n", "\n");
+        let lf_ast = Parser::new(Lexer::new(lf_input)).unwrap().parse().unwrap();
+
+        if let DplyrNode::Pipeline {
+            source, operations, ..
+        } = &ast
+        {
+            assert_eq!(source.as_deref(), Some("data"));
+            assert_eq!(operations.len(), 3);
+            assert!(matches!(operations[0], DplyrOperation::Select { .. }));
+            assert!(matches!(operations[1], DplyrOperation::Filter { .. }));
+            assert!(matches!(operations[2], DplyrOperation::Arrange { .. }));
+        } else {
+            panic!("Expected Pipeline node");
+        }
+        assert_eq!(ast, lf_ast);
+    }
+
+    #[test]
+    fn test_read_function_source_pipeline() {
+        let lexer = Lexer::new("read_parquet('f.parquet') %>% select(a)".to_string());
+        let ast = Parser::new(lexer).unwrap().parse().unwrap();
+
+        if let DplyrNode::Pipeline {
+            source,
+            source_function,
+            operations,
+            ..
+        } = &ast
+        {
+            assert_eq!(source, &None);
+            assert_eq!(
+                source_function,
+                &Some(SourceFunction {
+                    name: "read_parquet".to_string(),
+                    path: "f.parquet".to_string(),
+                })
+            );
+            assert_eq!(operations.len(), 1);
+        } else {
+            panic!("Expected Pipeline node");
+        }
+
+        let lexer = Lexer::new("read_csv(path) %>% select(a)".to_string());
+        assert!(Parser::new(lexer).unwrap().parse().is_err());
+    }
+
+    #[test]
+    fn test_distinct_across_expands_to_column_list() {
+        let parse = |input: &str| {
+            Parser::new(Lexer::new(input.to_string()))
+                .unwrap()
+                .parse()
+                .unwrap()
+        };
+
+        let ast = parse("data %>% distinct(across(c(a, b)))");
+        if let DplyrNode::Pipeline { operations, .. } = &ast {
+            assert_eq!(operations.len(), 1);
+            if let DplyrOperation::Distinct { columns, .. } = &operations[0] {
+                assert_eq!(columns, &vec!["a".to_string(), "b".to_string()]);
+            } else {
+                panic!("Expected Distinct operation");
+            }
+        } else {
+            panic!("Expected Pipeline node");
+        }
+
+        assert_eq!(ast, parse("data %>% distinct(a, b)"));
+        assert_eq!(ast, parse("data %>% distinct(across(a), b)"));
+    }
+
+    #[test]
+    fn test_fill_parses_columns_and_direction() {
+        let parse = |input: &str| Parser::new(Lexer::new(input.to_string())).unwrap().parse();
+
+        let ast = parse("data %>% fill(a, c(b, d), .direction = \"up\")").unwrap();
+        if let DplyrNode::Pipeline { operations, .. } = &ast {
+            assert_eq!(
+                operations[0],
+                DplyrOperation::Fill {
+                    columns: vec!["a".to_string(), "b".to_string(), "d".to_string()],
+                    direction: FillDirection::Up,
+                    location: operations[0].location().clone(),
+                }
+            );
+        } else {
+            panic!("Expected Pipeline node");
+        }
+
+        let default = parse("data %>% fill(a)").unwrap();
+        assert_eq!(
+            default,
+            parse("data %>% fill(a, .direction = \"down\")").unwrap()
+        );
+
+        assert!(matches!(
+            parse("data %>% fill(a, .direction = \"downup\")"),
+            Err(ParseError::InvalidOperation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_select_ranges_are_rejected_with_specific_error() {
+        let parse = |input: &str| Parser::new(Lexer::new(input.to_string())).unwrap().parse();
+
+        match parse("data %>% select(1:3)") {
+            Err(ParseError::InvalidOperation { operation, .. }) => assert_eq!(
+                operation,
+                "positional select (1:3) needs the table schema; select columns by name"
+            ),
+            other => panic!("expected InvalidOperation, got {other:?}"),
+        }
+        match parse("data %>% select(id, a:c)") {
+            Err(ParseError::InvalidOperation { operation, .. }) => assert_eq!(
+                operation,
+                "column range (a:c) needs the table schema; list the columns explicitly"
+            ),
+            other => panic!("expected InvalidOperation, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_unnamed_mutate_argument_requires_name() {
+        for input in [
+            "data %>% mutate(salary * 1.1)",
+            "data %>% mutate(salary)",
+            "data %>% mutate(bonus = 1, 2)",
+        ] {
+            match Parser::new(Lexer::new(input.to_string())).unwrap().parse() {
+                Err(ParseError::InvalidOperation { operation, .. }) => {
+                    assert_eq!(operation, "mutate() requires name = value")
+                }
+                other => panic!("expected InvalidOperation for {input}, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_group_by_rejects_sort_helpers() {
+        for (input, helper) in [
+            ("data %>% group_by(desc(x))", "desc"),
+            ("data %>% group_by(dept, asc(x))", "asc"),
+        ] {
+            match Parser::new(Lexer::new(input.to_string())).unwrap().parse() {
+                Err(ParseError::InvalidOperation { operation, .. }) => assert_eq!(
+                    operation,
+                    format!(
+                        "group_by() takes plain column names, not {helper}(); sort with arrange() instead"
+                    )
+                ),
+                other => panic!("expected InvalidOperation for {input}, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_slice_parses_row_count() {
+        let parse = |input: &str| Parser::new(Lexer::new(input.to_string())).unwrap().parse();
+
+        let ast = parse("data %>% slice_tail(n = 5)").unwrap();
+        if let DplyrNode::Pipeline { operations, .. } = &ast {
+            assert_eq!(
+                operations[0],
+                DplyrOperation::Slice {
+                    position: SlicePosition::Tail,
+                    n: 5,
+                    location: operations[0].location().clone(),
+                }
+            );
+        } else {
+            panic!("Expected Pipeline node");
+        }
+
+        assert_eq!(
+            parse("data %>% slice_head()").unwrap(),
+            parse("data %>% slice_head(n = 1)").unwrap()
+        );
+        assert_eq!(
+            parse("data %>% slice_head(3)").unwrap(),
+            parse("data %>% slice_head(n = 3)").unwrap()
+        );
+        assert!(matches!(
+            parse("data %>% slice_head(n = 1.5)"),
+            Err(ParseError::InvalidOperation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_dollar_column_access_parses() {
+        let parse = |input: &str| Parser::new(Lexer::new(input.to_string())).unwrap().parse();
+
+        assert_eq!(
+            parse("data %>% filter(.data$age > 18)").unwrap(),
+            parse("data %>% filter(age > 18)").unwrap()
+        );
+
+        let ast = parse("data %>% filter(df$age > 18)").unwrap();
+        if let DplyrNode::Pipeline { operations, .. } = &ast {
+            let DplyrOperation::Filter { condition, .. } = &operations[0] else {
+                panic!("Expected filter operation");
+            };
+            let Expr::Binary { left, .. } = condition else {
+                panic!("Expected binary condition");
+            };
+            assert_eq!(
+                **left,
+                Expr::QualifiedIdentifier {
+                    table: "df".to_string(),
+                    column: "age".to_string(),
+                }
+            );
+        } else {
+            panic!("Expected Pipeline node");
+        }
+
+        assert!(parse("data %>% filter(.env$age > 18)").is_err());
+    }
+
+    #[test]
+    fn test_negative_number_literal_parses() {
+        let parse = |input: &str| Parser::new(Lexer::new(input.to_string())).unwrap().parse();
+
+        let ast = parse("data %>% filter(x > -2)").unwrap();
+        if let DplyrNode::Pipeline { operations, .. } = &ast {
+            let DplyrOperation::Filter { condition, .. } = &operations[0] else {
+                panic!("Expected filter operation");
+            };
+            let Expr::Binary { right, .. } = condition else {
+                panic!("Expected binary condition");
+            };
+            assert_eq!(**right, Expr::Literal(LiteralValue::Number(-2.0)));
+        } else {
+            panic!("Expected Pipeline node");
+        }
+
+        let DplyrNode::Pipeline { operations, .. } = parse("data %>% filter(x > -y)").unwrap()
+        else {
+            panic!("Expected Pipeline node");
+        };
+        let DplyrOperation::Filter {
+            condition: Expr::Binary { right, .. },
+            ..
+        } = &operations[0]
+        else {
+            panic!("Expected binary filter condition");
+        };
+        assert_eq!(
+            **right,
+            Expr::Negate(Box::new(Expr::Identifier("y".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_unary_minus_binds_looser_than_power() {
+        let parse_expr = |input: &str| {
+            let code = format!("mutate(result = {input})");
+            let DplyrNode::Pipeline { operations, .. } =
+                Parser::new(Lexer::new(code)).unwrap().parse().unwrap()
+            else {
+                panic!("Expected Pipeline node");
+            };
+            let DplyrOperation::Mutate { assignments, .. } = &operations[0] else {
+                panic!("Expected Mutate operation");
+            };
+            assignments[0].expr.clone()
+        };
+        let power = |left: Expr, right: Expr| Expr::Binary {
+            left: Box::new(left),
+            operator: BinaryOp::Power,
+            right: Box::new(right),
+        };
+        let number = |n: f64| Expr::Literal(LiteralValue::Number(n));
+        let ident = |name: &str| Expr::Identifier(name.to_string());
+
+        assert_eq!(
+            parse_expr("-2^2"),
+            Expr::Negate(Box::new(power(number(2.0), number(2.0))))
+        );
+        assert_eq!(
+            parse_expr("-x^2"),
+            Expr::Negate(Box::new(power(ident("x"), number(2.0))))
+        );
+        assert_eq!(parse_expr("(-2)^2"), power(number(-2.0), number(2.0)));
+        assert_eq!(parse_expr("2^-1"), power(number(2.0), number(-1.0)));
+    }
+
+    #[test]
+    fn test_order_function_parses_na_last() {
+        let parse = |input: &str| Parser::new(Lexer::new(input.to_string())).unwrap().parse();
+
+        let ast = parse("data %>% arrange(desc(x, na_last = FALSE))").unwrap();
+        if let DplyrNode::Pipeline { operations, .. } = &ast {
+            let DplyrOperation::Arrange { columns, .. } = &operations[0] else {
+                panic!("Expected arrange operation");
+            };
+            assert_eq!(
+                columns[0],
+                OrderExpr {
+                    column: "x".to_string(),
+                    direction: OrderDirection::Desc,
+                    nulls: Some(NullsOrder::First),
+                }
+            );
+        } else {
+            panic!("Expected Pipeline node");
+        }
+
+        assert!(matches!(
+            parse("data %>% arrange(desc(x, na_last = \"yes\"))"),
+            Err(ParseError::UnexpectedToken { .. })
+        ));
+        assert!(matches!(
+            parse("data %>% arrange(desc(x, y))"),
+            Err(ParseError::InvalidOperation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_group_by_parses_add_flag() {
+        let parse = |input: &str| Parser::new(Lexer::new(input.to_string())).unwrap().parse();
+
+        let ast = parse("data %>% group_by(a, .add = TRUE, b)").unwrap();
+        if let DplyrNode::Pipeline { operations, .. } = &ast {
+            let DplyrOperation::GroupBy { columns, add, .. } = &operations[0] else {
+                panic!("Expected group_by operation");
+            };
+            assert_eq!(columns, &vec!["a".to_string(), "b".to_string()]);
+            assert!(add);
+        } else {
+            panic!("Expected Pipeline node");
+        }
+
+        assert!(matches!(
+            parse("data %>% group_by(a, .add = 1)"),
+            Err(ParseError::UnexpectedToken { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_slice_range() {
+        let lexer = Lexer::new("slice(2:4)".to_string());
+        let mut parser = Parser::new(lexer).unwrap();
+
+        let DplyrNode::Pipeline { operations, .. } = parser.parse().unwrap() else {
+            panic!("Expected Pipeline node");
+        };
+        assert!(matches!(
+            operations[0],
+            DplyrOperation::Slice {
+                position: SlicePosition::Rows { start: 2 },
+                n: 3,
+                ..
+            }
+        ));
+        assert_eq!(operations[0].operation_name(), "slice");
+    }
+
+    #[test]
+    fn test_parse_slice_rejects_reversed_or_non_constant_ranges() {
+        for input in ["slice(4:2)", "slice(a:3)", "slice(0:3)", "slice(1.5)"] {
+            let lexer = Lexer::new(input.to_string());
+            let mut parser = Parser::new(lexer).unwrap();
+            assert!(
+                matches!(parser.parse(), Err(ParseError::InvalidOperation { .. })),
+                "{input}"
+            );
+        }
+    }
+
     #[test]
     fn test_pipeline_with_complex_expressions() {
         let input = "select(name, age) %>% filter(age >= 18 & age <= 65) %>% mutate(category = age * 2 + 1)";
@@ -3246,13 +4448,14 @@ mod pipeline_parsing_tests {
                 let mut parser = Parser::new(lexer).unwrap();
 
                 match parser.parse() {
-                    Err(ParseError::UnexpectedToken {
-                        expected, found, ..
+                    Err(ParseError::UnclosedParenthesis {
+                        opened_at,
+                        position,
                     }) => {
-                        assert!(expected.contains(")"));
-                        assert_eq!(found, "EOF");
+                        assert_eq!(opened_at, 1);
+                        assert_eq!(position, 5);
                     }
-                    other => panic!("Expected UnexpectedToken error, got: {other:?}"),
+                    other => panic!("Expected UnclosedParenthesis error, got: {other:?}"),
                 }
             }
 
@@ -3350,6 +4553,22 @@ mod pipeline_parsing_tests {
                 }
             }
 
+            #[test]
+            fn test_desc_with_multiple_columns_reports_single_column_rule() {
+                let lexer = Lexer::new("arrange(desc(a, b))".to_string());
+                let mut parser = Parser::new(lexer).unwrap();
+
+                match parser.parse() {
+                    Err(error @ ParseError::InvalidOperation { .. }) => {
+                        assert!(
+                            error.to_string().contains("desc() takes a single column"),
+                            "{error}"
+                        );
+                    }
+                    other => panic!("Expected InvalidOperation error, got: {other:?}"),
+                }
+            }
+
             #[test]
             fn test_invalid_aggregation_function() {
                 let lexer = Lexer::new("summarise(result = invalid_agg())".to_string());
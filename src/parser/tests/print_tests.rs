@@ -0,0 +1,147 @@
+use super::*;
+
+fn ident(name: &str) -> Expr {
+    Expr::Identifier(name.to_string())
+}
+
+fn binary(left: Expr, operator: BinaryOp, right: Expr) -> Expr {
+    Expr::Binary {
+        left: Box::new(left),
+        operator,
+        right: Box::new(right),
+    }
+}
+
+#[test]
+fn test_binary_expressions() {
+    let condition = binary(
+        binary(
+            ident("age"),
+            BinaryOp::GreaterThan,
+            Expr::Literal(LiteralValue::Number(18.0)),
+        ),
+        BinaryOp::And,
+        binary(
+            ident("status"),
+            BinaryOp::Equal,
+            Expr::Literal(LiteralValue::String("active".to_string())),
+        ),
+    );
+    assert_eq!(
+        expr_to_string(&condition),
+        "age > 18 AND status == 'active'"
+    );
+
+    // Parentheses only where precedence needs them
+    let sum = binary(ident("a"), BinaryOp::Plus, ident("b"));
+    assert_eq!(
+        expr_to_string(&binary(sum.clone(), BinaryOp::Multiply, ident("c"))),
+        "(a + b) * c"
+    );
+    assert_eq!(
+        expr_to_string(&binary(ident("c"), BinaryOp::Minus, sum.clone())),
+        "c - (a + b)"
+    );
+    assert_eq!(
+        expr_to_string(&binary(sum, BinaryOp::Minus, ident("c"))),
+        "a + b - c"
+    );
+
+    // `^` is right-associative, so only a left-nested power needs parentheses
+    let power = binary(ident("a"), BinaryOp::Power, ident("b"));
+    assert_eq!(
+        expr_to_string(&binary(ident("c"), BinaryOp::Power, power.clone())),
+        "c ^ a ^ b"
+    );
+    assert_eq!(
+        expr_to_string(&binary(power, BinaryOp::Power, ident("c"))),
+        "(a ^ b) ^ c"
+    );
+
+    // Unary minus binds looser than `^`
+    let squared = binary(
+        ident("x"),
+        BinaryOp::Power,
+        Expr::Literal(LiteralValue::Number(2.0)),
+    );
+    assert_eq!(expr_to_string(&Expr::Negate(Box::new(squared))), "-x ^ 2");
+    assert_eq!(
+        expr_to_string(&binary(
+            Expr::Negate(Box::new(ident("x"))),
+            BinaryOp::Power,
+            Expr::Literal(LiteralValue::Number(2.0))
+        )),
+        "(-x) ^ 2"
+    );
+    assert_eq!(
+        expr_to_string(&binary(
+            Expr::Literal(LiteralValue::Number(-2.0)),
+            BinaryOp::Power,
+            Expr::Literal(LiteralValue::Number(2.0))
+        )),
+        "(-2) ^ 2"
+    );
+}
+
+#[test]
+fn test_function_expressions() {
+    let call = Expr::Function {
+        name: "round".to_string(),
+        args: vec![
+            ident("price"),
+            Expr::NamedArg {
+                name: "digits".to_string(),
+                value: Box::new(Expr::Literal(LiteralValue::Number(2.0))),
+            },
+        ],
+    };
+    assert_eq!(expr_to_string(&call), "round(price, digits = 2)");
+
+    let case_when = Expr::Function {
+        name: "case_when".to_string(),
+        args: vec![Expr::Formula {
+            lhs: Box::new(Expr::Function {
+                name: "is.na".to_string(),
+                args: vec![Expr::QualifiedIdentifier {
+                    table: "df".to_string(),
+                    column: "x".to_string(),
+                }],
+            }),
+            rhs: Box::new(Expr::Literal(LiteralValue::Number(0.0))),
+        }],
+    };
+    assert_eq!(expr_to_string(&case_when), "case_when(is.na(df$x) ~ 0)");
+}
+
+#[test]
+fn test_literal_expressions() {
+    let render = |literal| expr_to_string(&Expr::Literal(literal));
+
+    assert_eq!(render(LiteralValue::Number(-3.0)), "-3");
+    assert_eq!(render(LiteralValue::Number(1.5)), "1.5");
+    assert_eq!(render(LiteralValue::String("it's".to_string())), "'it\\'s'");
+    assert_eq!(render(LiteralValue::Boolean(true)), "TRUE");
+    assert_eq!(render(LiteralValue::Null), "NA");
+}
+
+#[test]
+fn test_not_expressions() {
+    let not_active = Expr::Not(Box::new(ident("is_active")));
+    assert_eq!(expr_to_string(&not_active), "!is_active");
+    assert_eq!(
+        expr_to_string(&Expr::Not(Box::new(binary(
+            ident("x"),
+            BinaryOp::GreaterThan,
+            ident("y")
+        )))),
+        "!(x > y)"
+    );
+    assert_eq!(
+        expr_to_string(&binary(not_active.clone(), BinaryOp::And, ident("b"))),
+        "!is_active AND b"
+    );
+    assert_eq!(
+        expr_to_string(&binary(not_active, BinaryOp::Equal, ident("b"))),
+        "(!is_active) == b"
+    );
+}
@@ -0,0 +1,49 @@
+use super::*;
+use crate::lexer::Lexer;
+use crate::parser::{ast_equal, Parser};
+
+fn folded(code: &str) -> DplyrNode {
+    let mut ast = Parser::new(Lexer::new(code.to_string()))
+        .unwrap()
+        .parse()
+        .unwrap();
+    fold_negations(&mut ast);
+    ast
+}
+
+fn parsed(code: &str) -> DplyrNode {
+    Parser::new(Lexer::new(code.to_string()))
+        .unwrap()
+        .parse()
+        .unwrap()
+}
+
+#[test]
+fn test_folds_double_negation() {
+    assert_eq!(folded("filter(!!active)"), parsed("filter(active)"));
+    assert_eq!(folded("filter(!(!active))"), parsed("filter(active)"));
+    assert_eq!(folded("filter(!!!active)"), parsed("filter(!active)"));
+}
+
+#[test]
+fn test_folds_negated_equality() {
+    assert_eq!(folded("filter(!(a == b))"), parsed("filter(a != b)"));
+    assert_eq!(folded("filter(!(a != 1))"), parsed("filter(a == 1)"));
+    // `!` binds looser than `==`, as in R
+    assert_eq!(folded("filter(!a == b)"), parsed("filter(a != b)"));
+}
+
+#[test]
+fn test_folds_inside_nested_expressions_and_other_verbs() {
+    assert!(ast_equal(
+        &folded("mutate(flag = ifelse(!(x == 1), 0, 1)) %>% filter(!!ok & y > 0)"),
+        &parsed("mutate(flag = ifelse(x != 1, 0, 1)) %>% filter(ok & y > 0)")
+    ));
+}
+
+#[test]
+fn test_keeps_negations_without_a_simpler_form() {
+    for code in ["filter(!active)", "filter(!(a > b))", "filter(!(a & b))"] {
+        assert_eq!(folded(code), parsed(code));
+    }
+}
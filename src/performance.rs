@@ -363,14 +363,62 @@ impl RegressionDetector {
     }
 }
 
+/// Representative queries shared by the criterion benchmarks and performance tests.
+///
+/// Every query transpiles in all built-in dialects, so timings measure the
+/// success path rather than error reporting.
+pub mod corpus {
+    /// Single-verb queries.
+    pub const SIMPLE_QUERIES: &[&str] = &[
+        "select(mpg)",
+        "filter(mpg > 20)",
+        "mutate(efficiency = mpg / cyl)",
+        "arrange(mpg)",
+        "group_by(cyl) %>% summarise(n = n())",
+        "summarise(avg_mpg = mean(mpg))",
+        "select(name, age)",
+        "filter(status == 'active')",
+        "mutate(total = price * quantity)",
+        "arrange(desc(date))",
+    ];
+
+    /// Multi-verb pipelines with grouping, conditional expressions and ordering.
+    pub const COMPLEX_QUERIES: &[&str] = &[
+        "mtcars %>% select(mpg, cyl, hp) %>% filter(mpg > 20) %>% arrange(desc(hp))",
+        "data %>% group_by(category, region) %>% summarise(total = sum(value), avg = mean(value), count = n()) %>% arrange(desc(total))",
+        "sales %>% select(date, product, amount, region) %>% filter(amount > 1000) %>% mutate(profit_margin = amount * 0.15) %>% group_by(region) %>% summarise(total_sales = sum(amount), avg_margin = mean(profit_margin), top_sale = max(amount)) %>% arrange(desc(total_sales))",
+        "employees %>% select(id, name, department, salary) %>% filter(salary > 50000 & (department == 'Engineering' | department == 'Sales')) %>% mutate(salary_grade = case_when(salary < 75000 ~ 'Junior', salary < 100000 ~ 'Mid', TRUE ~ 'Senior')) %>% group_by(department, salary_grade) %>% summarise(count = n(), avg_salary = mean(salary)) %>% arrange(department, desc(avg_salary))",
+        "orders %>% select(order_id, customer_id, quantity, price) %>% filter(quantity > 0) %>% mutate(total_amount = quantity * price) %>% group_by(customer_id) %>% summarise(order_count = n(), total_spent = sum(total_amount), largest_order = max(total_amount)) %>% arrange(desc(total_spent))",
+    ];
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::PostgreSqlDialect;
 
+    #[test]
+    fn test_corpus_transpiles_in_every_dialect() {
+        let dialects: Vec<Box<dyn SqlDialect>> = vec![
+            Box::new(PostgreSqlDialect::new()),
+            Box::new(crate::MySqlDialect::new()),
+            Box::new(crate::SqliteDialect::new()),
+            Box::new(crate::DuckDbDialect::new()),
+            Box::new(crate::OracleDialect::new()),
+            Box::new(crate::SparkSqlDialect::new()),
+        ];
+        for dialect in dialects {
+            let name = dialect.dialect_name().to_string();
+            let transpiler = Transpiler::new(dialect);
+            for query in corpus::SIMPLE_QUERIES.iter().chain(corpus::COMPLEX_QUERIES) {
+                assert!(transpiler.transpile(query).is_ok(), "{name}: {query}");
+            }
+        }
+    }
+
     #[test]
     fn test_performance_profiler_basic() {
-        let profiler = PerformanceProfiler::new(Box::new(PostgreSqlDialect));
+        let profiler = PerformanceProfiler::new(Box::new(PostgreSqlDialect::new()));
         let metrics = profiler.profile_transpile("select(name, age)");
 
         assert!(metrics.success);
@@ -382,7 +430,7 @@ mod tests {
 
     #[test]
     fn test_batch_performance_stats() {
-        let profiler = PerformanceProfiler::new(Box::new(PostgreSqlDialect));
+        let profiler = PerformanceProfiler::new(Box::new(PostgreSqlDialect::new()));
         let inputs = vec!["select(name)", "select(age)", "filter(age > 18)"];
 
         let stats = profiler.profile_batch(&inputs);
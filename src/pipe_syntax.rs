@@ -1,6 +1,9 @@
 //! Pipe syntax configuration.
 
-use std::str::FromStr;
+use core::str::FromStr;
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
 
 /// Environment variable used to select the accepted dplyr pipe syntax.
 pub const PIPE_SYNTAX_ENV_VAR: &str = "DPLYR_PIPE_SYNTAX";
@@ -75,6 +78,7 @@ impl PipeSyntax {
     }
 
     /// Reads pipe syntax from `DPLYR_PIPE_SYNTAX`.
+    #[cfg(feature = "std")]
     pub fn from_env() -> Result<Option<Self>, String> {
         match std::env::var(PIPE_SYNTAX_ENV_VAR) {
             Ok(value) => value.parse().map(Some),
@@ -86,12 +90,14 @@ impl PipeSyntax {
     }
 
     /// Reads pipe syntax from `DPLYR_PIPE_SYNTAX`, defaulting to Magrittr.
+    #[cfg(feature = "std")]
     pub fn from_env_or_default() -> Result<Self, String> {
         Ok(Self::from_env()?.unwrap_or_default())
     }
 }
 
 /// Returns pipe syntax configuration guidance when an error mentions a disabled pipe.
+#[cfg(feature = "std")]
 pub(crate) fn disabled_pipe_suggestion_for_error(message: &str) -> Option<String> {
     if message.contains(PipeSyntax::Native.disabled_message()) {
         Some(PipeSyntax::Native.disabled_suggestion())
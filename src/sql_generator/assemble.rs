@@ -1,25 +1,287 @@
 // SQL assembly helpers.
 
 use std::collections::HashMap;
+use std::io::Write;
 
-use super::{DplyrOperation, GenerationResult, SqlGenerator};
+use super::{
+    DplyrOperation, GenContext, GenerationError, GenerationResult, OrderExpr, SqlGenerator,
+};
+use crate::error::TranspileWarning;
+use crate::parser::GroupingKind;
+
+/// Base table used when a pipeline does not name its data source.
+pub(super) const DEFAULT_SOURCE_TABLE: &str = "data";
+
+/// Alias of the derived table that [`GenContext::wrap_in_subquery`] selects from.
+pub(super) const SUBQUERY_ALIAS: &str = "subquery";
+
+/// Resolves the table a pipeline reads from, falling back to [`DEFAULT_SOURCE_TABLE`].
+pub(super) fn source_table_name(source: &Option<String>) -> &str {
+    source.as_deref().unwrap_or(DEFAULT_SOURCE_TABLE)
+}
+
+/// Name that qualifies a table's columns: `orders` for `public.orders`.
+pub(super) fn table_qualifier(table_name: &str) -> &str {
+    match table_name.rsplit_once('.') {
+        Some((schema, table)) if !schema.is_empty() && !table.is_empty() => table,
+        _ => table_name,
+    }
+}
+
+/// Destination of assembled SQL text.
+pub(super) trait SqlSink {
+    fn push_sql(&mut self, sql: &str) -> GenerationResult<()>;
+}
+
+impl SqlSink for String {
+    fn push_sql(&mut self, sql: &str) -> GenerationResult<()> {
+        self.push_str(sql);
+        Ok(())
+    }
+}
+
+/// Writes SQL to an `io::Write`, refusing any piece that would take the
+/// output past `max` bytes.
+pub(super) struct WriterSink<'a> {
+    writer: &'a mut dyn Write,
+    written: usize,
+    max: Option<usize>,
+}
+
+impl<'a> WriterSink<'a> {
+    pub(super) fn new(writer: &'a mut dyn Write, max: Option<usize>) -> Self {
+        Self {
+            writer,
+            written: 0,
+            max,
+        }
+    }
+
+    pub(super) fn flush(&mut self) -> GenerationResult<()> {
+        self.writer.flush().map_err(write_failed)
+    }
+}
+
+impl SqlSink for WriterSink<'_> {
+    fn push_sql(&mut self, sql: &str) -> GenerationResult<()> {
+        let length = self.written + sql.len();
+        if let Some(max) = self.max.filter(|max| length > *max) {
+            return Err(GenerationError::OutputTooLarge { length, max });
+        }
+        self.writer
+            .write_all(sql.as_bytes())
+            .map_err(write_failed)?;
+        self.written = length;
+        Ok(())
+    }
+}
+
+/// Returns whether `sql` is enclosed in one pair of parentheses, so that
+/// `(a) OR (b)` is not mistaken for a parenthesized expression. Parentheses
+/// inside quoted strings and identifiers are ignored.
+fn is_parenthesized(sql: &str) -> bool {
+    if !sql.starts_with('(') || !sql.ends_with(')') {
+        return false;
+    }
+    let mut depth = 0usize;
+    let mut quote = None;
+    for (index, c) in sql.char_indices() {
+        match quote {
+            Some(open) if c == open => quote = None,
+            Some(_) => {}
+            None => match c {
+                '\'' | '"' | '`' => quote = Some(c),
+                '(' => depth += 1,
+                ')' => {
+                    depth = depth.saturating_sub(1);
+                    if depth == 0 {
+                        return index == sql.len() - 1;
+                    }
+                }
+                _ => {}
+            },
+        }
+    }
+    false
+}
+
+fn write_failed(error: std::io::Error) -> GenerationError {
+    GenerationError::WriteFailed {
+        reason: error.to_string(),
+    }
+}
+
+/// One item of a SELECT list.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Projection {
+    /// The projected expression, `*`, or `* EXCLUDE (...)`.
+    pub(super) expr: String,
+    /// Quoted `AS` alias, if any.
+    pub(super) alias: Option<String>,
+    /// Name of the output column, when the item is one known column.
+    pub(super) name: Option<String>,
+}
+
+impl Projection {
+    pub(super) fn star() -> Self {
+        Self::unnamed("*".to_string())
+    }
+
+    /// An expression whose output column name is not tracked.
+    pub(super) fn unnamed(expr: String) -> Self {
+        Self {
+            expr,
+            alias: None,
+            name: None,
+        }
+    }
+
+    /// A plain reference to the column `name`.
+    pub(super) fn column(expr: String, name: &str) -> Self {
+        Self {
+            expr,
+            alias: None,
+            name: Some(name.to_string()),
+        }
+    }
+
+    /// `expr AS alias`, where `alias` is `name` quoted for the dialect.
+    pub(super) fn aliased(expr: String, name: &str, alias: String) -> Self {
+        Self {
+            expr,
+            alias: Some(alias),
+            name: Some(name.to_string()),
+        }
+    }
+
+    pub(super) fn is_star(&self) -> bool {
+        self.expr == "*"
+    }
+
+    fn len(&self) -> usize {
+        self.expr.len() + self.alias.as_ref().map_or(0, |alias| alias.len() + 4)
+    }
+}
+
+impl std::fmt::Display for Projection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.alias {
+            Some(alias) => write!(f, "{} AS {alias}", self.expr),
+            None => f.write_str(&self.expr),
+        }
+    }
+}
+
+impl PartialEq<&str> for Projection {
+    fn eq(&self, other: &&str) -> bool {
+        match &self.alias {
+            Some(alias) => {
+                other
+                    .strip_prefix(self.expr.as_str())
+                    .and_then(|rest| rest.strip_prefix(" AS "))
+                    == Some(alias.as_str())
+            }
+            None => self.expr == *other,
+        }
+    }
+}
+
+/// Projects each grouping column of a `", "`-joined GROUP BY list.
+pub(super) fn group_projections(group_by: &str) -> Vec<Projection> {
+    group_by
+        .split(", ")
+        .filter(|column| !column.is_empty())
+        .map(|column| Projection::unnamed(column.to_string()))
+        .collect()
+}
 
 /// Struct to store SQL query components
 #[derive(Debug, Default)]
 pub(super) struct QueryParts {
-    pub(super) select_columns: Vec<String>,
+    pub(super) select_columns: Vec<Projection>,
+    pub(super) star_excluded: Vec<String>, // columns left out of the `*` item
+    pub(super) distinct: bool,
     pub(super) where_clauses: Vec<String>,
     pub(super) group_by: String,
+    pub(super) grouping: GroupingKind, // construct the next summarise() groups `group_by` with
     pub(super) order_by: String,
+    pub(super) order_columns: Vec<String>, // quoted column refs listed in `order_by`
+    pub(super) order_exprs: Vec<OrderExpr>, // arrange() columns `order_by` was generated from
     pub(super) joins: Vec<String>,
     pub(super) mutated_columns: HashMap<String, String>,
     pub(super) set_operation: Option<(String, String)>, // (operation, right_table)
+    pub(super) from_function: Option<String>,           // table function replacing the source table
+    pub(super) limit: Option<usize>,
+    pub(super) offset: Option<usize>, // only rendered together with `limit`
 }
 
 impl QueryParts {
     pub(super) fn new() -> Self {
         Self::default()
     }
+
+    /// ANDs `predicate` onto the WHERE clause. Once a second predicate is
+    /// added, the first is parenthesized too, so `a OR b` cannot bind to it.
+    pub(super) fn push_where(&mut self, predicate: String) {
+        match self.where_clauses.as_mut_slice() {
+            [] => self.where_clauses.push(predicate),
+            [first] => {
+                if !is_parenthesized(first) {
+                    *first = format!("({first})");
+                }
+                self.where_clauses.push(format!("AND ({predicate})"));
+            }
+            _ => self.where_clauses.push(format!("AND ({predicate})")),
+        }
+    }
+
+    /// Whether a LIMIT or OFFSET is set. Both apply after every other clause of
+    /// the query level, so a later verb has to read the limited rows from a subquery.
+    pub(super) fn is_row_limited(&self) -> bool {
+        self.limit.is_some() || self.offset.is_some()
+    }
+
+    /// Names of the result columns, when the projection names every one of them.
+    pub(super) fn named_columns(&self) -> Option<Vec<String>> {
+        self.select_columns
+            .iter()
+            .map(|item| item.name.clone())
+            .collect::<Option<Vec<_>>>()
+            .filter(|columns| !columns.is_empty())
+    }
+
+    /// Expressions of the projected columns computed under an alias, by name.
+    pub(super) fn computed_columns(&self) -> HashMap<String, String> {
+        self.select_columns
+            .iter()
+            .filter(|item| item.alias.is_some())
+            .filter_map(|item| Some((item.name.clone()?, item.expr.clone())))
+            .collect()
+    }
+
+    /// Lower bound on the assembled SQL length, used to stop oversized output early.
+    pub(super) fn estimated_len(&self) -> usize {
+        let strings = self
+            .where_clauses
+            .iter()
+            .chain(&self.joins)
+            .map(String::len)
+            .sum::<usize>()
+            + self
+                .select_columns
+                .iter()
+                .map(Projection::len)
+                .sum::<usize>();
+        let set_operation = self
+            .set_operation
+            .as_ref()
+            .map_or(0, |(operation, table)| operation.len() + table.len());
+        strings
+            + self.group_by.len()
+            + self.order_by.len()
+            + set_operation
+            + self.from_function.as_ref().map_or(0, String::len)
+    }
 }
 
 impl SqlGenerator {
@@ -37,15 +299,18 @@ impl SqlGenerator {
         operations: &[DplyrOperation],
     ) -> GenerationResult<String> {
         // Process nested operations recursively
+        let context = self.context(None);
         let mut nested_parts = QueryParts::new();
 
         for operation in operations {
-            self.process_operation(operation, &mut nested_parts, "data")?;
+            context.process_operation(operation, &mut nested_parts, DEFAULT_SOURCE_TABLE)?;
         }
 
-        self.assemble_query(&None, &nested_parts)
+        context.assemble_query(&None, &nested_parts)
     }
+}
 
+impl GenContext<'_> {
     /// Assembles the final SQL query.
     pub(super) fn assemble_query(
         &self,
@@ -53,52 +318,271 @@ impl SqlGenerator {
         parts: &QueryParts,
     ) -> GenerationResult<String> {
         let mut query = String::new();
+        self.write_query(source, parts, &mut query)?;
+        Ok(query)
+    }
+
+    /// Writes the SQL query described by `parts` to `out`, one clause at a time.
+    pub(super) fn write_query(
+        &self,
+        source: &Option<String>,
+        parts: &QueryParts,
+        out: &mut dyn SqlSink,
+    ) -> GenerationResult<()> {
+        self.check_distinct_order_by(parts)?;
+
+        // FROM clause (using default table name); joins attach to this base table
+        let table_name = source_table_name(source);
+        if !parts.joins.is_empty() && table_name.trim().is_empty() {
+            return Err(GenerationError::InvalidAst {
+                reason: "join requires a base table for FROM, but the pipeline source is empty"
+                    .to_string(),
+            });
+        }
+        if !parts.joins.is_empty() && parts.from_function.is_some() && self.source_alias.is_none() {
+            // Join conditions qualify the base table by name, which a table function lacks
+            return Err(GenerationError::InvalidAst {
+                reason: "join after a read_*() source requires a source alias".to_string(),
+            });
+        }
 
         // SELECT clause
-        query.push_str("SELECT ");
+        out.push_sql("SELECT ")?;
+        if parts.distinct {
+            out.push_sql("DISTINCT ")?;
+        }
         if parts.select_columns.is_empty() {
-            query.push('*');
+            out.push_sql("*")?;
         } else {
-            query.push_str(&parts.select_columns.join(", "));
+            let star = self.star_projection(table_name, parts)?;
+            for (index, column) in parts.select_columns.iter().enumerate() {
+                if index > 0 {
+                    out.push_sql(", ")?;
+                }
+                if column.is_star() {
+                    out.push_sql(&star)?;
+                } else {
+                    out.push_sql(&column.to_string())?;
+                }
+            }
         }
 
-        // FROM clause (using default table name)
-        query.push_str("\nFROM ");
-        let table_name = source.as_deref().unwrap_or("data");
-        query.push_str(&self.dialect.quote_identifier(table_name));
+        out.push_sql("\nFROM ")?;
+        match &parts.from_function {
+            Some(function_sql) => out.push_sql(&self.alias_from_item(function_sql.clone()))?,
+            None => out.push_sql(&self.render_from_table(table_name))?,
+        }
 
         // JOIN clauses
         for join in &parts.joins {
-            query.push('\n');
-            query.push_str(join);
+            out.push_sql("\n")?;
+            out.push_sql(join)?;
         }
 
         // WHERE clause
         if !parts.where_clauses.is_empty() {
-            query.push_str("\nWHERE ");
-            query.push_str(&parts.where_clauses.join(" "));
+            out.push_sql("\nWHERE ")?;
+            for (index, clause) in parts.where_clauses.iter().enumerate() {
+                if index > 0 {
+                    out.push_sql(" ")?;
+                }
+                out.push_sql(clause)?;
+            }
         }
 
         // GROUP BY clause
         if !parts.group_by.is_empty() {
-            query.push_str("\nGROUP BY ");
-            query.push_str(&parts.group_by);
+            out.push_sql("\nGROUP BY ")?;
+            out.push_sql(&parts.group_by)?;
         }
 
         // ORDER BY clause
         if !parts.order_by.is_empty() {
-            query.push_str("\nORDER BY ");
-            query.push_str(&parts.order_by);
+            out.push_sql("\nORDER BY ")?;
+            out.push_sql(&parts.order_by)?;
+        }
+
+        // LIMIT clause
+        if let Some(limit) = parts.limit {
+            out.push_sql("\n")?;
+            out.push_sql(
+                &self
+                    .dialect
+                    .limit_with_offset_clause(limit, parts.offset.unwrap_or(0)),
+            )?;
         }
 
         // Set operation (INTERSECT, UNION, EXCEPT)
         if let Some((op, right_table)) = &parts.set_operation {
-            query.push_str(&format!(
+            out.push_sql(&format!(
                 "\n{op} SELECT * FROM {}",
-                self.dialect.quote_identifier(right_table)
-            ));
+                self.quote_table_name(right_table)
+            ))?;
         }
 
-        Ok(query)
+        Ok(())
+    }
+
+    /// Projects `expr` as the column `name`.
+    pub(super) fn aliased_projection(&self, expr: String, name: &str) -> Projection {
+        Projection::aliased(expr, name, self.dialect.quote_identifier(name))
+    }
+
+    /// Projects the `kept_columns` of the subquery the query now reads, or
+    /// every column but the `helper_columns` when they are unknown.
+    ///
+    /// Without `* EXCLUDE` unknown columns can only be read with `*`, so the
+    /// helper columns `operation` added stay in the result with a warning.
+    pub(super) fn drop_helper_columns(
+        &self,
+        operation: &str,
+        kept_columns: Option<Vec<String>>,
+        helper_columns: Vec<String>,
+        query_parts: &mut QueryParts,
+    ) {
+        query_parts.select_columns = match kept_columns {
+            Some(columns) => columns
+                .iter()
+                .map(|column| Projection::column(self.column_ref(column), column))
+                .collect(),
+            None if self.dialect.select_star_exclude(&[]).is_none() => {
+                self.warn(TranspileWarning::HelperColumnsKept {
+                    operation: operation.to_string(),
+                    columns: helper_columns,
+                    dialect: self.dialect.dialect_name().to_string(),
+                });
+                vec![Projection::star()]
+            }
+            None => {
+                query_parts.star_excluded = helper_columns;
+                vec![Projection::star()]
+            }
+        };
+    }
+
+    /// Renders the `*` of a projection, qualified by the FROM item where the
+    /// dialect needs that next to other columns.
+    fn star_projection(&self, table_name: &str, parts: &QueryParts) -> GenerationResult<String> {
+        if !parts.star_excluded.is_empty() {
+            return self
+                .dialect
+                .select_star_exclude(&parts.star_excluded)
+                .ok_or_else(|| GenerationError::UnsupportedOperation {
+                    operation: "excluding columns from *".to_string(),
+                    dialect: self.dialect.dialect_name().to_string(),
+                });
+        }
+        if parts.select_columns.len() < 2 {
+            return Ok("*".to_string());
+        }
+        let qualifier = match (self.source_alias.as_deref(), &parts.from_function) {
+            (Some(alias), _) => self.dialect.quote_identifier(alias),
+            (None, Some(_)) => SUBQUERY_ALIAS.to_string(),
+            (None, None) => self.quote_table_name(table_name),
+        };
+        let star = self.dialect.star_projection(&qualifier);
+        if star != "*" && !parts.joins.is_empty() {
+            // A qualified star would drop the joined table's columns
+            return Err(GenerationError::UnsupportedOperation {
+                operation: "adding columns to every column of a join".to_string(),
+                dialect: self.dialect.dialect_name().to_string(),
+            });
+        }
+        Ok(star)
+    }
+
+    /// Rejects a DISTINCT projection ordered by a column it does not select.
+    ///
+    /// SQL only allows SELECT DISTINCT to be ordered by selected expressions, and
+    /// adding the column to the projection would change which rows are distinct.
+    fn check_distinct_order_by(&self, parts: &QueryParts) -> GenerationResult<()> {
+        // An empty projection selects every column
+        if !parts.distinct
+            || parts.select_columns.is_empty()
+            || parts.select_columns.iter().any(Projection::is_star)
+        {
+            return Ok(());
+        }
+        let selected = |column: &String| {
+            parts.select_columns.iter().any(|item| match &item.alias {
+                Some(alias) => alias == column,
+                None => item.expr == *column,
+            })
+        };
+        match parts.order_columns.iter().find(|column| !selected(column)) {
+            Some(column) => Err(GenerationError::InvalidAst {
+                reason: format!(
+                    "arrange() by {column} conflicts with distinct(): ORDER BY columns must \
+                     appear in the DISTINCT projection; add {column} to distinct() or drop it \
+                     from arrange()"
+                ),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Moves the query built so far into a FROM subquery, so later operations
+    /// can refer to its computed columns by name.
+    ///
+    /// Grouping and ordering stay on the outer query; the GROUP BY of an earlier
+    /// summarise() (`aggregation_group_by`) is applied inside the subquery.
+    pub(super) fn wrap_in_subquery(
+        &self,
+        source: &Option<String>,
+        query_parts: &mut QueryParts,
+        aggregation_group_by: &mut Option<String>,
+    ) -> GenerationResult<()> {
+        let mut inner = std::mem::take(query_parts);
+        let group_by = std::mem::take(&mut inner.group_by);
+        let order_by = inner.order_by.clone();
+        let order_columns = inner.order_columns.clone();
+        inner.group_by = aggregation_group_by.take().unwrap_or_default();
+        // Row order only matters inside the subquery when it is also limited
+        if inner.limit.is_none() {
+            inner.order_by.clear();
+            inner.order_columns.clear();
+        }
+
+        let inner_sql = self.assemble_query(source, &inner)?;
+        let subquery = if self.source_alias.is_some() {
+            format!("(\n{inner_sql}\n)")
+        } else {
+            self.dialect
+                .table_alias(&format!("(\n{inner_sql}\n)"), SUBQUERY_ALIAS)
+        };
+
+        *query_parts = QueryParts {
+            group_by,
+            order_by,
+            order_columns,
+            from_function: Some(subquery),
+            ..QueryParts::new()
+        };
+        Ok(())
+    }
+
+    /// Renders the FROM table, appending the source alias when one is set.
+    pub(super) fn render_from_table(&self, table_name: &str) -> String {
+        self.alias_from_item(self.quote_table_name(table_name))
+    }
+
+    /// Quotes a table name, quoting each part of a schema-qualified
+    /// `schema.table` separately.
+    pub(super) fn quote_table_name(&self, table_name: &str) -> String {
+        let parts: Vec<&str> = table_name.split('.').collect();
+        if parts.len() > 1 && parts.iter().all(|part| !part.is_empty()) {
+            self.dialect.quote_identifier_path(&parts)
+        } else {
+            self.dialect.quote_identifier(table_name)
+        }
+    }
+
+    fn alias_from_item(&self, item: String) -> String {
+        match self.source_alias.as_deref() {
+            Some(alias) => self
+                .dialect
+                .table_alias(&item, &self.dialect.quote_identifier(alias)),
+            None => item,
+        }
     }
 }
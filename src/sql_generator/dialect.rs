@@ -1,10 +1,670 @@
 //! SQL dialects.
 
+use crate::error::{GenerationError, GenerationResult};
+use crate::parser::GroupingKind;
+
 fn quote_with_escape(name: &str, quote: char) -> String {
     let escaped = name.replace(quote, &quote.to_string().repeat(2));
     format!("{quote}{escaped}{quote}")
 }
 
+/// Canonical names of the built-in dialects, as accepted by the CLI's
+/// `--dialect` option and reported by [`SqlDialect::dialect_name`].
+pub const fn supported_dialect_names() -> &'static [&'static str] {
+    &["postgresql", "mysql", "sqlite", "duckdb", "oracle", "spark"]
+}
+
+/// Resolves a dialect name such as `"postgres"` or `"DuckDB"` to its built-in
+/// dialect, ignoring case and surrounding whitespace.
+///
+/// Accepts every name in [`supported_dialect_names`] plus the aliases
+/// `postgres`, `pg` and `duck`. This is the one alias table shared by the CLI's
+/// `--dialect` option and the C API's dialect names.
+pub fn dialect_from_name(name: &str) -> Option<Box<dyn SqlDialect>> {
+    match name.trim().to_lowercase().as_str() {
+        "postgresql" | "postgres" | "pg" => Some(Box::new(PostgreSqlDialect::new())),
+        "mysql" => Some(Box::new(MySqlDialect::new())),
+        "sqlite" => Some(Box::new(SqliteDialect::new())),
+        "duckdb" | "duck" => Some(Box::new(DuckDbDialect::new())),
+        "oracle" => Some(Box::new(OracleDialect::new())),
+        "spark" => Some(Box::new(SparkSqlDialect::new())),
+        _ => None,
+    }
+}
+
+/// Formats `value` as a SQL numeric literal for `dialect`.
+///
+/// Unless the dialect overrides [`SqlDialect::format_number`], every dialect
+/// gets the same plain decimal text: never an exponent (`1e6` is `1000000`),
+/// `.` as the decimal separator whatever the locale, the shortest digits that
+/// round-trip (`0.1`), no trailing `.0` on whole numbers, and `-0` as `0`.
+/// `value` must be finite.
+pub fn format_number(value: f64, dialect: &dyn SqlDialect) -> String {
+    dialect.format_number(value)
+}
+
+fn plain_decimal(value: f64) -> String {
+    if value == 0.0 {
+        // Also covers -0.0, which would otherwise print as "-0"
+        "0".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// Case folding applied to identifiers before they are emitted.
+///
+/// `Preserve` keeps every identifier quoted exactly as written. `Lower` and
+/// `Upper` fold the identifier and emit it bare, matching how PostgreSQL
+/// (lowercase) or Oracle-style engines (uppercase) resolve unquoted names.
+/// Identifiers that would not survive unquoted (reserved words, spaces,
+/// punctuation) are still quoted after folding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum IdentifierCase {
+    #[default]
+    Preserve,
+    Lower,
+    Upper,
+}
+
+/// Words reserved in standard SQL or in any supported dialect, kept sorted.
+/// Folded identifiers matching one of them are quoted rather than emitted bare.
+const BARE_IDENTIFIER_RESERVED_WORDS: &[&str] = &[
+    "abort",
+    "abs",
+    "access",
+    "accessible",
+    "acos",
+    "action",
+    "add",
+    "after",
+    "all",
+    "allocate",
+    "alter",
+    "analyse",
+    "analyze",
+    "and",
+    "anti",
+    "any",
+    "are",
+    "array",
+    "array_agg",
+    "array_max_cardinality",
+    "as",
+    "asc",
+    "asensitive",
+    "asin",
+    "asof",
+    "asymmetric",
+    "at",
+    "atan",
+    "atomic",
+    "attach",
+    "audit",
+    "authorization",
+    "autoincrement",
+    "avg",
+    "before",
+    "begin",
+    "begin_frame",
+    "begin_partition",
+    "between",
+    "bigint",
+    "binary",
+    "blob",
+    "boolean",
+    "both",
+    "by",
+    "call",
+    "called",
+    "cardinality",
+    "cascade",
+    "cascaded",
+    "case",
+    "cast",
+    "ceil",
+    "ceiling",
+    "change",
+    "char",
+    "char_length",
+    "character",
+    "character_length",
+    "check",
+    "classifier",
+    "clob",
+    "close",
+    "cluster",
+    "coalesce",
+    "collate",
+    "collation",
+    "collect",
+    "column",
+    "columns",
+    "comment",
+    "commit",
+    "compress",
+    "concurrently",
+    "condition",
+    "conflict",
+    "connect",
+    "constraint",
+    "contains",
+    "convert",
+    "copy",
+    "corr",
+    "corresponding",
+    "cos",
+    "cosh",
+    "count",
+    "covar_pop",
+    "covar_samp",
+    "create",
+    "cross",
+    "cube",
+    "cume_dist",
+    "current",
+    "current_catalog",
+    "current_date",
+    "current_default_transform_group",
+    "current_path",
+    "current_role",
+    "current_row",
+    "current_schema",
+    "current_time",
+    "current_timestamp",
+    "current_transform_group_for_type",
+    "current_user",
+    "cursor",
+    "cycle",
+    "database",
+    "databases",
+    "date",
+    "day",
+    "day_hour",
+    "day_microsecond",
+    "day_minute",
+    "day_second",
+    "deallocate",
+    "dec",
+    "decfloat",
+    "decimal",
+    "declare",
+    "default",
+    "deferrable",
+    "define",
+    "delayed",
+    "delete",
+    "dense_rank",
+    "deref",
+    "desc",
+    "describe",
+    "detach",
+    "deterministic",
+    "disconnect",
+    "distinct",
+    "distinctrow",
+    "div",
+    "do",
+    "double",
+    "drop",
+    "dual",
+    "dynamic",
+    "each",
+    "element",
+    "else",
+    "elseif",
+    "empty",
+    "enclosed",
+    "end",
+    "end_frame",
+    "end_partition",
+    "equals",
+    "escape",
+    "escaped",
+    "every",
+    "except",
+    "exclusive",
+    "exec",
+    "execute",
+    "exists",
+    "exit",
+    "exp",
+    "explain",
+    "external",
+    "extract",
+    "fail",
+    "false",
+    "fetch",
+    "file",
+    "filter",
+    "first_value",
+    "float",
+    "floor",
+    "for",
+    "force",
+    "foreign",
+    "frame_row",
+    "free",
+    "freeze",
+    "from",
+    "full",
+    "fulltext",
+    "function",
+    "fusion",
+    "generated",
+    "get",
+    "glob",
+    "global",
+    "grant",
+    "group",
+    "grouping",
+    "groups",
+    "having",
+    "high_priority",
+    "hold",
+    "hour",
+    "hour_microsecond",
+    "hour_minute",
+    "hour_second",
+    "identified",
+    "identity",
+    "if",
+    "ignore",
+    "ilike",
+    "immediate",
+    "in",
+    "increment",
+    "index",
+    "indicator",
+    "infile",
+    "initial",
+    "initially",
+    "inner",
+    "inout",
+    "insensitive",
+    "insert",
+    "instead",
+    "int",
+    "integer",
+    "intersect",
+    "intersection",
+    "interval",
+    "into",
+    "is",
+    "isnull",
+    "iterate",
+    "join",
+    "json_array",
+    "json_arrayagg",
+    "json_exists",
+    "json_object",
+    "json_objectagg",
+    "json_query",
+    "json_table",
+    "json_table_primitive",
+    "json_value",
+    "key",
+    "keys",
+    "kill",
+    "lag",
+    "language",
+    "large",
+    "last_value",
+    "lateral",
+    "lead",
+    "leading",
+    "leave",
+    "left",
+    "level",
+    "like",
+    "like_regex",
+    "limit",
+    "linear",
+    "lines",
+    "listagg",
+    "ln",
+    "load",
+    "local",
+    "localtime",
+    "localtimestamp",
+    "lock",
+    "log",
+    "log10",
+    "long",
+    "longblob",
+    "longtext",
+    "loop",
+    "low_priority",
+    "lower",
+    "master_ssl_verify_server_cert",
+    "match",
+    "match_number",
+    "match_recognize",
+    "matches",
+    "max",
+    "maxextents",
+    "measures",
+    "mediumblob",
+    "mediumint",
+    "mediumtext",
+    "member",
+    "merge",
+    "method",
+    "middleint",
+    "min",
+    "minus",
+    "minute",
+    "minute_microsecond",
+    "minute_second",
+    "mlslabel",
+    "mod",
+    "mode",
+    "modifies",
+    "modify",
+    "module",
+    "month",
+    "multiset",
+    "national",
+    "natural",
+    "nchar",
+    "nclob",
+    "new",
+    "no",
+    "no_write_to_binlog",
+    "noaudit",
+    "nocompress",
+    "none",
+    "normalize",
+    "not",
+    "nothing",
+    "notnull",
+    "nowait",
+    "nth_value",
+    "ntile",
+    "null",
+    "nullif",
+    "number",
+    "numeric",
+    "occurrences_regex",
+    "octet_length",
+    "of",
+    "offline",
+    "offset",
+    "old",
+    "omit",
+    "on",
+    "one",
+    "online",
+    "only",
+    "open",
+    "optimize",
+    "optimizer_costs",
+    "option",
+    "optionally",
+    "or",
+    "order",
+    "out",
+    "outer",
+    "outfile",
+    "over",
+    "overlaps",
+    "overlay",
+    "parameter",
+    "partition",
+    "pattern",
+    "pctfree",
+    "per",
+    "percent",
+    "percent_rank",
+    "percentile_cont",
+    "percentile_disc",
+    "period",
+    "pivot",
+    "pivot_longer",
+    "pivot_wider",
+    "placing",
+    "plan",
+    "portion",
+    "position",
+    "position_regex",
+    "positional",
+    "power",
+    "pragma",
+    "precedes",
+    "precision",
+    "prepare",
+    "primary",
+    "prior",
+    "procedure",
+    "ptf",
+    "purge",
+    "qualify",
+    "query",
+    "raise",
+    "range",
+    "rank",
+    "raw",
+    "read",
+    "read_write",
+    "reads",
+    "real",
+    "recursive",
+    "ref",
+    "references",
+    "referencing",
+    "regexp",
+    "regr_avgx",
+    "regr_avgy",
+    "regr_count",
+    "regr_intercept",
+    "regr_r2",
+    "regr_slope",
+    "regr_sxx",
+    "regr_sxy",
+    "regr_syy",
+    "reindex",
+    "release",
+    "rename",
+    "repeat",
+    "replace",
+    "require",
+    "resignal",
+    "resource",
+    "restrict",
+    "result",
+    "return",
+    "returning",
+    "returns",
+    "revoke",
+    "right",
+    "rlike",
+    "rollback",
+    "rollup",
+    "row",
+    "row_number",
+    "rowid",
+    "rownum",
+    "rows",
+    "running",
+    "savepoint",
+    "schema",
+    "schemas",
+    "scope",
+    "scroll",
+    "search",
+    "second",
+    "second_microsecond",
+    "seek",
+    "select",
+    "semi",
+    "sensitive",
+    "separator",
+    "session",
+    "session_user",
+    "set",
+    "share",
+    "show",
+    "signal",
+    "similar",
+    "sin",
+    "sinh",
+    "size",
+    "skip",
+    "smallint",
+    "some",
+    "spatial",
+    "specific",
+    "specifictype",
+    "sql",
+    "sql_big_result",
+    "sql_calc_found_rows",
+    "sql_small_result",
+    "sqlexception",
+    "sqlstate",
+    "sqlwarning",
+    "sqrt",
+    "ssl",
+    "start",
+    "starting",
+    "static",
+    "stddev_pop",
+    "stddev_samp",
+    "stored",
+    "straight_join",
+    "struct",
+    "submultiset",
+    "subset",
+    "substring",
+    "substring_regex",
+    "succeeds",
+    "successful",
+    "sum",
+    "summarize",
+    "symmetric",
+    "synonym",
+    "sysdate",
+    "system",
+    "system_time",
+    "system_user",
+    "table",
+    "tablesample",
+    "tan",
+    "tanh",
+    "temp",
+    "temporary",
+    "terminated",
+    "then",
+    "time",
+    "timestamp",
+    "timezone_hour",
+    "timezone_minute",
+    "tinyblob",
+    "tinyint",
+    "tinytext",
+    "to",
+    "trailing",
+    "translate",
+    "translate_regex",
+    "translation",
+    "treat",
+    "trigger",
+    "trim",
+    "trim_array",
+    "true",
+    "truncate",
+    "uescape",
+    "uid",
+    "undo",
+    "union",
+    "unique",
+    "unknown",
+    "unlock",
+    "unnest",
+    "unpivot",
+    "unsigned",
+    "update",
+    "upper",
+    "usage",
+    "use",
+    "user",
+    "using",
+    "utc_date",
+    "utc_time",
+    "utc_timestamp",
+    "vacuum",
+    "validate",
+    "value",
+    "value_of",
+    "values",
+    "var_pop",
+    "var_samp",
+    "varbinary",
+    "varchar",
+    "varchar2",
+    "varcharacter",
+    "variadic",
+    "varying",
+    "verbose",
+    "versioning",
+    "view",
+    "virtual",
+    "when",
+    "whenever",
+    "where",
+    "while",
+    "width_bucket",
+    "window",
+    "with",
+    "within",
+    "without",
+    "write",
+    "xor",
+    "year",
+    "year_month",
+    "zerofill",
+];
+
+fn can_emit_bare_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    let starts_ok = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_');
+    starts_ok
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && BARE_IDENTIFIER_RESERVED_WORDS
+            .binary_search(&name.to_ascii_lowercase().as_str())
+            .is_err()
+}
+
+/// Returns why `name` cannot be used as an identifier quoted with `quote`.
+fn identifier_problem(name: &str, quote: char) -> Option<&'static str> {
+    if name.is_empty() {
+        Some("identifier must not be empty")
+    } else if name.contains(quote) {
+        Some("identifier must not contain the dialect's quote character")
+    } else if name.chars().any(char::is_control) {
+        Some("identifier must not contain control characters")
+    } else {
+        None
+    }
+}
+
+fn quote_with_identifier_case(name: &str, identifier_case: IdentifierCase, quote: char) -> String {
+    let folded = match identifier_case {
+        IdentifierCase::Preserve => return quote_with_escape(name, quote),
+        IdentifierCase::Lower => name.to_lowercase(),
+        IdentifierCase::Upper => name.to_uppercase(),
+    };
+
+    if can_emit_bare_identifier(&folded) {
+        folded
+    } else {
+        quote_with_escape(&folded, quote)
+    }
+}
+
 /// Translates a common R/tidyverse function to dialect-specific SQL.
 fn translate_common_function<D: SqlDialect + ?Sized>(
     dialect: &D,
@@ -30,15 +690,19 @@ fn translate_common_function_with_window_clause<D: SqlDialect + ?Sized>(
         "sqrt" => unary_sql_function("SQRT", args),
         "sign" => unary_sql_function("SIGN", args),
         "exp" => unary_sql_function("EXP", args),
-        "log" => {
-            if args.len() == 1 {
-                Some(format!("LN({})", args[0]))
-            } else if args.len() == 2 {
-                Some(format!("LOG({}, {})", args[1], args[0]))
-            } else {
-                None
-            }
-        }
+        "log" => match args {
+            [value] => Some(format!("LN({value})")),
+            [value, base] => Some(dialect.log_with_base(value, base)),
+            _ => None,
+        },
+        "log2" => match args {
+            [value] => Some(dialect.log_with_base(value, "2")),
+            _ => None,
+        },
+        "pow" | "power" => match args {
+            [base, exponent] => Some(dialect.power(base, exponent)),
+            _ => None,
+        },
         "log10" => {
             if args.len() == 1 {
                 Some(dialect.log10(&args[0]))
@@ -72,7 +736,7 @@ fn translate_common_function_with_window_clause<D: SqlDialect + ?Sized>(
         "cosh" => unary_sql_function("COSH", args),
         "tanh" => unary_sql_function("TANH", args),
         // String functions
-        "concat" | "paste0" => dialect.concat_no_separator(args),
+        "concat" | "paste0" | "str_c" => dialect.concat_no_separator(args),
         "paste" => dialect.concat_with_separator("' '", args),
         "tolower" | "lower" => unary_sql_function("LOWER", args),
         "toupper" | "touppercase" | "upper" => unary_sql_function("UPPER", args),
@@ -83,6 +747,21 @@ fn translate_common_function_with_window_clause<D: SqlDialect + ?Sized>(
                 None
             }
         }
+        // grepl() takes the pattern first
+        "grepl" => {
+            if args.len() == 2 {
+                dialect.regex_detect(&args[1], &args[0])
+            } else {
+                None
+            }
+        }
+        "str_replace" | "str_replace_all" => {
+            if args.len() == 3 {
+                dialect.regex_replace(&args[0], &args[1], &args[2], fn_lower == "str_replace_all")
+            } else {
+                None
+            }
+        }
         "str_length" => {
             if args.len() == 1 {
                 Some(dialect.char_length(&args[0]))
@@ -93,18 +772,13 @@ fn translate_common_function_with_window_clause<D: SqlDialect + ?Sized>(
         "str_to_lower" => unary_sql_function("LOWER", args),
         "str_to_upper" => unary_sql_function("UPPER", args),
         "str_trim" => unary_sql_function("TRIM", args),
-        "substr" => {
-            if args.len() >= 3 {
-                Some(format!(
-                    "SUBSTR({}, {}, (({}) - ({}) + 1))",
-                    args[0], args[1], args[2], args[1]
-                ))
-            } else if args.len() == 2 {
-                Some(format!("SUBSTR({}, {})", args[0], args[1]))
-            } else {
-                None
+        "substr" | "str_sub" => match args {
+            [value, start, stop] => {
+                Some(dialect.substring(value, start, Some(&substring_length(start, stop))))
             }
-        }
+            [value, start] => Some(dialect.substring(value, start, None)),
+            _ => None,
+        },
         "nchar" => {
             if args.len() == 1 {
                 Some(dialect.char_length(&args[0]))
@@ -130,6 +804,8 @@ fn translate_common_function_with_window_clause<D: SqlDialect + ?Sized>(
                 None
             }
         }
+        // Arguments arrive generated once each; compound ones already carry
+        // their own parentheses, so the branches need no extra wrapping.
         "ifelse" | "if_else" => {
             if args.len() == 3 {
                 Some(format!(
@@ -140,6 +816,28 @@ fn translate_common_function_with_window_clause<D: SqlDialect + ?Sized>(
                 None
             }
         }
+        // Logical
+        "xor" => {
+            if args.len() == 2 {
+                Some(dialect.logical_xor(&args[0], &args[1]))
+            } else {
+                None
+            }
+        }
+        // Range test; `x %between% c(lo, hi)` parses to the same call
+        "between" => match args {
+            [value, lower, upper] => Some(format!("({value} BETWEEN {lower} AND {upper})")),
+            _ => None,
+        },
+        // Float equality within a tolerance; dplyr defaults to sqrt(.Machine$double.eps)
+        "near" => match args {
+            [x, y] => Some(format!(
+                "(ABS({x} - {y}) < {})",
+                dialect.format_number(f64::EPSILON.sqrt())
+            )),
+            [x, y, tolerance] => Some(format!("(ABS({x} - {y}) < {tolerance})")),
+            _ => None,
+        },
         // NULL checks
         "is.na" => {
             if args.len() == 1 {
@@ -189,15 +887,15 @@ fn translate_common_function_with_window_clause<D: SqlDialect + ?Sized>(
                 }
             }
         }
-        "rank" => ranking_window_function("RANK", args, window_clause),
-        "dense_rank" => ranking_window_function("DENSE_RANK", args, window_clause),
-        "row_number" => ranking_window_function("ROW_NUMBER", args, window_clause),
+        "rank" => ranking_window_function(dialect, "RANK", args, window_clause),
+        "dense_rank" => ranking_window_function(dialect, "DENSE_RANK", args, window_clause),
+        "row_number" => ranking_window_function(dialect, "ROW_NUMBER", args, window_clause),
         "ntile" => {
             if !args.is_empty() {
                 Some(format!(
                     "NTILE({}) {}",
                     args[0],
-                    window_over_clause(window_clause)
+                    window_over_clause_with_order(window_clause, dialect.default_window_order())
                 ))
             } else {
                 None
@@ -237,15 +935,20 @@ fn one_or_two_arg_sql_function(sql_function: &str, args: &[String]) -> Option<St
     }
 }
 
-fn ranking_window_function(
+fn ranking_window_function<D: SqlDialect + ?Sized>(
+    dialect: &D,
     sql_function: &str,
     args: &[String],
     window_clause: &str,
 ) -> Option<String> {
     if args.len() <= 1 {
+        let order_by = args
+            .first()
+            .map(String::as_str)
+            .or_else(|| dialect.default_window_order());
         Some(format!(
             "{sql_function}() {}",
-            window_over_clause_with_order(window_clause, args.first().map(String::as_str))
+            window_over_clause_with_order(window_clause, order_by)
         ))
     } else {
         None
@@ -325,7 +1028,10 @@ fn is_supported_common_function(function: &str) -> bool {
             | "sign"
             | "exp"
             | "log"
+            | "log2"
             | "log10"
+            | "pow"
+            | "power"
             | "mod"
             | "%%"
             | "sin"
@@ -341,17 +1047,22 @@ fn is_supported_common_function(function: &str) -> bool {
             | "concat"
             | "paste"
             | "paste0"
+            | "str_c"
             | "tolower"
             | "lower"
             | "toupper"
             | "touppercase"
             | "upper"
             | "str_detect"
+            | "grepl"
+            | "str_replace"
+            | "str_replace_all"
             | "str_length"
             | "str_to_lower"
             | "str_to_upper"
             | "str_trim"
             | "substr"
+            | "str_sub"
             | "nchar"
             | "nzchar"
             | "trimws"
@@ -362,6 +1073,10 @@ fn is_supported_common_function(function: &str) -> bool {
             | "as.logical"
             | "ifelse"
             | "if_else"
+            | "case_when"
+            | "xor"
+            | "between"
+            | "near"
             | "is.na"
             | "lead"
             | "lag"
@@ -380,6 +1095,36 @@ fn is_supported_common_function(function: &str) -> bool {
     )
 }
 
+/// Length of the inclusive R range `start..=stop`, folded when both are integers.
+fn substring_length(start: &str, stop: &str) -> String {
+    match (start.parse::<i64>(), stop.parse::<i64>()) {
+        (Ok(start), Ok(stop)) => (stop - start + 1).max(0).to_string(),
+        _ => format!("(({stop}) - ({start}) + 1)"),
+    }
+}
+
+const REGEX_METACHARACTERS: &str = ".^$*+?()[]{}|\\";
+
+/// Returns the quoted SQL string a quoted regex `pattern` matches literally,
+/// when it has no regular expression metacharacters other than escaped ones
+/// (`\.` matches a literal `.`).
+fn literal_regex_pattern(pattern: &str) -> Option<String> {
+    let body = pattern.strip_prefix('\'')?.strip_suffix('\'')?;
+    let mut literal = String::with_capacity(pattern.len());
+    let mut chars = body.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => match chars.next() {
+                Some(escaped) if REGEX_METACHARACTERS.contains(escaped) => literal.push(escaped),
+                _ => return None,
+            },
+            _ if REGEX_METACHARACTERS.contains(ch) => return None,
+            _ => literal.push(ch),
+        }
+    }
+    Some(format!("'{literal}'"))
+}
+
 fn sqlite_requires_math_extension(function: &str) -> bool {
     matches!(
         function.to_ascii_lowercase().as_str(),
@@ -390,7 +1135,10 @@ fn sqlite_requires_math_extension(function: &str) -> bool {
             | "sign"
             | "exp"
             | "log"
+            | "log2"
             | "log10"
+            | "pow"
+            | "power"
             | "sin"
             | "cos"
             | "tan"
@@ -467,7 +1215,7 @@ fn concat_with_separator_operator(separator: &str, args: &[String]) -> Option<St
 /// assert_eq!(pg_dialect.string_concat(left, right), "\"first\" || \"last\"");
 /// assert_eq!(mysql_dialect.string_concat(left, right), "CONCAT(\"first\", \"last\")");
 /// ```
-pub trait SqlDialect {
+pub trait SqlDialect: Send + Sync {
     /// Quotes identifiers according to the database's conventions.
     ///
     /// Different databases use different characters to quote identifiers
@@ -495,6 +1243,54 @@ pub trait SqlDialect {
     /// ```
     fn quote_identifier(&self, name: &str) -> String;
 
+    /// Character `quote_identifier` wraps identifiers in.
+    fn identifier_quote(&self) -> char {
+        '"'
+    }
+
+    /// Returns whether `name` is safe to use as a column or table name taken
+    /// from user input: not empty, and free of this dialect's identifier quote
+    /// and of control characters.
+    ///
+    /// `quote_identifier` escapes an embedded quote, so a rejected name would
+    /// still quote correctly; rejecting it keeps injection attempts out of the
+    /// query altogether.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use libdplyr::{MySqlDialect, PostgreSqlDialect, SqlDialect};
+    ///
+    /// let pg = PostgreSqlDialect::new();
+    /// assert!(pg.is_valid_identifier("order total"));
+    /// assert!(!pg.is_valid_identifier("x\"; DROP TABLE users; --"));
+    /// assert!(MySqlDialect::new().is_valid_identifier("x\"y"));
+    /// ```
+    fn is_valid_identifier(&self, name: &str) -> bool {
+        identifier_problem(name, self.identifier_quote()).is_none()
+    }
+
+    /// Quotes `name` with `quote_identifier` after checking it with
+    /// [`is_valid_identifier`](Self::is_valid_identifier).
+    ///
+    /// # Errors
+    ///
+    /// Returns `GenerationError::InvalidIdentifier` when `name` is rejected.
+    fn sanitize_identifier(&self, name: &str) -> GenerationResult<String> {
+        match identifier_problem(name, self.identifier_quote()) {
+            Some(reason) => Err(GenerationError::InvalidIdentifier {
+                identifier: name.to_string(),
+                reason: reason.to_string(),
+            }),
+            None => Ok(self.quote_identifier(name)),
+        }
+    }
+
+    /// Identifier case folding mode applied by `quote_identifier`.
+    fn identifier_case(&self) -> IdentifierCase {
+        IdentifierCase::Preserve
+    }
+
     /// Quotes a qualified identifier path, e.g. table + column.
     fn quote_identifier_path(&self, parts: &[&str]) -> String {
         parts
@@ -517,11 +1313,16 @@ pub trait SqlDialect {
     /// The properly quoted and escaped string literal
     fn quote_string(&self, value: &str) -> String;
 
-    /// Dialect name for error messages.
+    /// Dialect name, as used in error messages.
     fn dialect_name(&self) -> &'static str {
         "unknown"
     }
 
+    /// Prefix that starts a comment running to the end of the line.
+    fn line_comment_prefix(&self) -> &'static str {
+        "--"
+    }
+
     /// Generates a LIMIT clause for the database.
     ///
     /// Most databases use `LIMIT n` syntax, but some variations exist.
@@ -535,6 +1336,46 @@ pub trait SqlDialect {
     /// The LIMIT clause string
     fn limit_clause(&self, limit: usize) -> String;
 
+    /// Equality that treats two NULLs as equal and NULL as unequal to any value.
+    fn null_safe_equal(&self, left: &str, right: &str) -> String {
+        format!("({left} IS NOT DISTINCT FROM {right})")
+    }
+
+    /// Generates the clause skipping `offset` rows, rendered after [`Self::limit_clause`].
+    fn offset_clause(&self, offset: usize) -> String {
+        format!("OFFSET {offset}")
+    }
+
+    /// Generates the full row-limiting clause, skipping `offset` rows when nonzero.
+    ///
+    /// Defaults to [`Self::limit_clause`] followed by [`Self::offset_clause`];
+    /// dialects that expect the offset first override this.
+    fn limit_with_offset_clause(&self, limit: usize, offset: usize) -> String {
+        if offset == 0 {
+            self.limit_clause(limit)
+        } else {
+            format!(
+                "{} {}",
+                self.limit_clause(limit),
+                self.offset_clause(offset)
+            )
+        }
+    }
+
+    /// Renders a finite number as a numeric literal; see [`format_number`].
+    fn format_number(&self, value: f64) -> String {
+        plain_decimal(value)
+    }
+
+    /// SQL for R's `TRUE`/`FALSE`.
+    fn boolean_literal(&self, value: bool) -> &'static str {
+        if value {
+            "TRUE"
+        } else {
+            "FALSE"
+        }
+    }
+
     /// Generates string concatenation operation.
     ///
     /// Different databases have different ways to concatenate strings:
@@ -584,11 +1425,80 @@ pub trait SqlDialect {
     /// `true` if case-sensitive, `false` otherwise
     fn is_case_sensitive(&self) -> bool;
 
+    /// Renders `*` in a projection that also lists other columns, given the
+    /// quoted FROM item it selects from.
+    fn star_projection(&self, _qualifier: &str) -> String {
+        "*".to_string()
+    }
+
+    /// Gives a FROM item, such as a derived table, the alias `alias`.
+    fn table_alias(&self, item: &str, alias: &str) -> String {
+        format!("{item} AS {alias}")
+    }
+
+    /// Turns a logical value, such as a bare column, into a condition for
+    /// WHERE or `CASE WHEN`.
+    fn boolean_predicate(&self, value: &str) -> String {
+        value.to_string()
+    }
+
     /// Returns `* EXCLUDE (...)`-style projection if supported by the dialect.
     fn select_star_exclude(&self, _excluded_identifiers: &[String]) -> Option<String> {
         None
     }
 
+    /// Renders a file-reading table function (`read_parquet('f.parquet')`) for FROM.
+    ///
+    /// Returns `None` when the dialect cannot read files in a query.
+    fn read_file_source(&self, _function: &str, _path: &str) -> Option<String> {
+        None
+    }
+
+    /// Returns whether `SEMI JOIN` / `ANTI JOIN` can be emitted natively.
+    ///
+    /// Dialects without native support get `[NOT] EXISTS` subqueries instead.
+    fn supports_semi_anti_join(&self) -> bool {
+        false
+    }
+
+    /// Returns whether value window functions accept `IGNORE NULLS`
+    /// (`LAST_VALUE(x IGNORE NULLS) OVER (...)`), which `fill()` relies on.
+    fn supports_ignore_nulls(&self) -> bool {
+        false
+    }
+
+    /// Returns whether `IS [NOT] TRUE` / `IS [NOT] FALSE` predicates are available.
+    fn supports_is_boolean(&self) -> bool {
+        false
+    }
+
+    /// Returns whether ORDER BY accepts `NULLS FIRST` / `NULLS LAST`.
+    fn supports_nulls_ordering(&self) -> bool {
+        false
+    }
+
+    /// Returns whether ORDER BY can name an alias from the SELECT list, as in
+    /// `ORDER BY "avg" DESC` after `summarise(avg = mean(x))`.
+    ///
+    /// Dialects without it get the aliased expression repeated instead.
+    fn supports_order_by_alias(&self) -> bool {
+        true
+    }
+
+    /// Returns whether a `ROWS` window frame, as in the running aggregates
+    /// behind `cumsum()`, must be ordered.
+    fn requires_ordered_window_frame(&self) -> bool {
+        false
+    }
+
+    /// ORDER BY expression for ranking windows (`row_number()`, `rank()`,
+    /// `ntile()`, ...) that have no ordering, in dialects that reject `OVER ()`.
+    ///
+    /// Defaults to `None`, which leaves the window unordered.
+    fn default_window_order(&self) -> Option<&'static str> {
+        None
+    }
+
     /// Translates R/dplyr function names to SQL equivalents.
     ///
     /// Maps common R functions to their SQL counterparts. Override this
@@ -625,6 +1535,58 @@ pub trait SqlDialect {
         translate_common_aggregate_function(function)
     }
 
+    /// Renders the aggregate `function` applied to the SQL `argument`.
+    ///
+    /// Defaults to `NAME(argument)` using [`Self::translate_aggregate_function`];
+    /// dialects whose aggregate needs extra arguments override this.
+    fn aggregate_call(&self, function: &str, argument: &str) -> Option<String> {
+        self.translate_aggregate_function(function)
+            .map(|name| format!("{name}({argument})"))
+    }
+
+    /// Suggests SQL for an aggregate this dialect cannot translate, used in the
+    /// unsupported-aggregate error (e.g. `median` on PostgreSQL).
+    fn aggregate_alternative(&self, _function: &str, _column: &str) -> Option<String> {
+        None
+    }
+
+    /// Renders the continuous quantile of the SQL `argument` at `probability`,
+    /// used for `quantile(x, 0.9)`; `None` when the dialect has no such aggregate.
+    fn quantile_aggregate(&self, _argument: &str, _probability: &str) -> Option<String> {
+        None
+    }
+
+    /// Renders the GROUP BY clause body for `group_by(rollup(...))` or
+    /// `group_by(cube(...))` over the comma-separated SQL `columns`; `None`
+    /// when the dialect has no such construct.
+    fn grouping_construct(&self, kind: GroupingKind, columns: &str) -> Option<String> {
+        match kind {
+            GroupingKind::Plain => Some(columns.to_string()),
+            GroupingKind::Rollup => Some(format!("ROLLUP({columns})")),
+            GroupingKind::Cube => Some(format!("CUBE({columns})")),
+        }
+    }
+
+    /// Returns whether summarise() can translate the aggregate `function`
+    /// (`mean`, `median`, ...) in this dialect.
+    fn supports_aggregate(&self, function: &str) -> bool {
+        self.translate_aggregate_function(function).is_some()
+    }
+
+    /// Returns whether the dplyr verb `operation` (`select`, `fill`, `left_join`, ...)
+    /// can be generated in this dialect.
+    fn supports_operation(&self, operation: &str) -> bool {
+        match operation.to_lowercase().as_str() {
+            "rename" | "rename_with" => self.select_star_exclude(&[]).is_some(),
+            "fill" => self.supports_ignore_nulls(),
+            "slice_head" | "slice_tail" => true,
+            "select" | "filter" | "mutate" | "arrange" | "group_by" | "summarise" | "summarize"
+            | "distinct" | "inner_join" | "left_join" | "right_join" | "full_join"
+            | "semi_join" | "anti_join" | "intersect" | "union" | "setdiff" | "pivot_wider" => true,
+            _ => false,
+        }
+    }
+
     /// Late-bound translation hook for dialects that can resolve functions later.
     fn translate_unknown_function(&self, _function: &str, _args: &[String]) -> Option<String> {
         None
@@ -635,11 +1597,40 @@ pub trait SqlDialect {
         None
     }
 
+    /// Dialect-specific regular expression replacement for stringr::str_replace()
+    /// (first match) and str_replace_all() (`global`).
+    fn regex_replace(
+        &self,
+        _value: &str,
+        _pattern: &str,
+        _replacement: &str,
+        _global: bool,
+    ) -> Option<String> {
+        None
+    }
+
+    /// Dialect-specific substring of `length` characters from 1-based `start`,
+    /// or to the end of the string without a length.
+    fn substring(&self, value: &str, start: &str, length: Option<&str>) -> String {
+        match length {
+            Some(length) => format!("SUBSTR({value}, {start}, {length})"),
+            None => format!("SUBSTR({value}, {start})"),
+        }
+    }
+
     /// Dialect-specific character-count function for R string helpers.
     fn char_length(&self, value: &str) -> String {
         format!("LENGTH({value})")
     }
 
+    /// Exclusive OR of two boolean expressions, for R's `xor()`.
+    ///
+    /// Expands to `AND`/`OR`/`NOT` by default; dialects with a native
+    /// operator override this.
+    fn logical_xor(&self, left: &str, right: &str) -> String {
+        format!("(({left} AND NOT {right}) OR (NOT {left} AND {right}))")
+    }
+
     /// Dialect-specific SQL type for R cast helpers.
     fn r_cast_type(&self, function: &str) -> Option<&'static str> {
         match function {
@@ -656,6 +1647,16 @@ pub trait SqlDialect {
         format!("LOG10({value})")
     }
 
+    /// Raises `base` to `exponent`.
+    fn power(&self, base: &str, exponent: &str) -> String {
+        format!("POWER({base}, {exponent})")
+    }
+
+    /// Logarithm of `value` in `base`; the base comes first in every supported dialect.
+    fn log_with_base(&self, value: &str, base: &str) -> String {
+        format!("LOG({base}, {value})")
+    }
+
     /// Concatenates string expressions without a separator.
     fn concat_no_separator(&self, args: &[String]) -> Option<String> {
         if args.is_empty() {
@@ -665,18 +1666,27 @@ pub trait SqlDialect {
         }
     }
 
-    /// Concatenates string expressions with a separator.
+    /// Concatenates string expressions with a separator: `CONCAT_WS` where
+    /// [`supports_concat_ws`](Self::supports_concat_ws), otherwise the separator
+    /// interleaved with `||`.
     fn concat_with_separator(&self, separator: &str, args: &[String]) -> Option<String> {
         if args.is_empty() {
             None
-        } else {
+        } else if self.supports_concat_ws() {
             Some(format!("CONCAT_WS({separator}, {})", args.join(", ")))
+        } else {
+            concat_with_separator_operator(separator, args)
         }
     }
 
+    /// Whether the dialect has `CONCAT_WS(separator, ...)`.
+    fn supports_concat_ws(&self) -> bool {
+        true
+    }
+
     /// Creates a boxed clone of this dialect.
     ///
-    /// Used internally for performance benchmarking and testing.
+    /// Backs `Clone` for `Box<dyn SqlDialect>`.
     ///
     /// # Returns
     ///
@@ -684,6 +1694,12 @@ pub trait SqlDialect {
     fn clone_box(&self) -> Box<dyn SqlDialect>;
 }
 
+impl Clone for Box<dyn SqlDialect> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
 /// PostgreSQL dialect implementation
 ///
 /// Implements SQL generation for PostgreSQL databases. PostgreSQL uses
@@ -709,7 +1725,9 @@ pub trait SqlDialect {
 /// // SELECT "name", "age" FROM "data" WHERE "age" > 18
 /// ```
 #[derive(Debug, Clone)]
-pub struct PostgreSqlDialect;
+pub struct PostgreSqlDialect {
+    identifier_case: IdentifierCase,
+}
 
 impl PostgreSqlDialect {
     /// Creates a new PostgreSQL dialect instance.
@@ -728,7 +1746,15 @@ impl PostgreSqlDialect {
     /// assert_eq!(dialect.string_concat("'a'", "'b'"), "'a' || 'b'");
     /// ```
     pub const fn new() -> Self {
-        Self
+        Self {
+            identifier_case: IdentifierCase::Preserve,
+        }
+    }
+
+    /// Returns this dialect with the given identifier case folding mode.
+    pub const fn with_identifier_case(mut self, identifier_case: IdentifierCase) -> Self {
+        self.identifier_case = identifier_case;
+        self
     }
 }
 
@@ -740,7 +1766,11 @@ impl Default for PostgreSqlDialect {
 
 impl SqlDialect for PostgreSqlDialect {
     fn quote_identifier(&self, name: &str) -> String {
-        quote_with_escape(name, '"')
+        quote_with_identifier_case(name, self.identifier_case, '"')
+    }
+
+    fn identifier_case(&self) -> IdentifierCase {
+        self.identifier_case
     }
 
     fn quote_string(&self, value: &str) -> String {
@@ -760,6 +1790,12 @@ impl SqlDialect for PostgreSqlDialect {
         format!("{left} || {right}")
     }
 
+    fn quantile_aggregate(&self, argument: &str, probability: &str) -> Option<String> {
+        Some(format!(
+            "PERCENTILE_CONT({probability}) WITHIN GROUP (ORDER BY {argument})"
+        ))
+    }
+
     fn aggregate_function(&self, function: &str) -> String {
         match function.to_lowercase().as_str() {
             "mean" | "avg" => "AVG".to_string(),
@@ -776,6 +1812,41 @@ impl SqlDialect for PostgreSqlDialect {
         Some(format!("({value} ~ {pattern})"))
     }
 
+    fn regex_replace(
+        &self,
+        value: &str,
+        pattern: &str,
+        replacement: &str,
+        global: bool,
+    ) -> Option<String> {
+        let flags = if global { ", 'g'" } else { "" };
+        Some(format!(
+            "REGEXP_REPLACE({value}, {pattern}, {replacement}{flags})"
+        ))
+    }
+
+    fn supports_ignore_nulls(&self) -> bool {
+        true
+    }
+
+    fn supports_nulls_ordering(&self) -> bool {
+        true
+    }
+
+    fn supports_is_boolean(&self) -> bool {
+        true
+    }
+
+    fn aggregate_alternative(&self, function: &str, column: &str) -> Option<String> {
+        match function.to_lowercase().as_str() {
+            "median" => Some(format!(
+                "PERCENTILE_CONT(0.5) WITHIN GROUP (ORDER BY {})",
+                self.quote_identifier(column)
+            )),
+            _ => None,
+        }
+    }
+
     fn r_cast_type(&self, function: &str) -> Option<&'static str> {
         match function {
             "as.numeric" | "as.double" => Some("DOUBLE PRECISION"),
@@ -790,6 +1861,10 @@ impl SqlDialect for PostgreSqlDialect {
         format!("LOG({value})")
     }
 
+    fn power(&self, base: &str, exponent: &str) -> String {
+        format!("({base} ^ {exponent})")
+    }
+
     fn is_case_sensitive(&self) -> bool {
         false
     }
@@ -823,7 +1898,9 @@ impl SqlDialect for PostgreSqlDialect {
 /// // SELECT `name`, `age` FROM `data` WHERE `age` > 18
 /// ```
 #[derive(Debug, Clone)]
-pub struct MySqlDialect;
+pub struct MySqlDialect {
+    identifier_case: IdentifierCase,
+}
 
 impl MySqlDialect {
     /// Creates a new MySQL dialect instance.
@@ -842,7 +1919,15 @@ impl MySqlDialect {
     /// assert_eq!(dialect.string_concat("'a'", "'b'"), "CONCAT('a', 'b')");
     /// ```
     pub const fn new() -> Self {
-        Self
+        Self {
+            identifier_case: IdentifierCase::Preserve,
+        }
+    }
+
+    /// Returns this dialect with the given identifier case folding mode.
+    pub const fn with_identifier_case(mut self, identifier_case: IdentifierCase) -> Self {
+        self.identifier_case = identifier_case;
+        self
     }
 }
 
@@ -854,11 +1939,21 @@ impl Default for MySqlDialect {
 
 impl SqlDialect for MySqlDialect {
     fn quote_identifier(&self, name: &str) -> String {
-        quote_with_escape(name, '`')
+        quote_with_identifier_case(name, self.identifier_case, self.identifier_quote())
+    }
+
+    fn identifier_quote(&self) -> char {
+        '`'
+    }
+
+    fn identifier_case(&self) -> IdentifierCase {
+        self.identifier_case
     }
 
     fn quote_string(&self, value: &str) -> String {
-        let escaped = value.replace('\'', "''");
+        // MySQL reads backslash as an escape character by default
+        // (without NO_BACKSLASH_ESCAPES), so a literal one must be doubled
+        let escaped = value.replace('\\', "\\\\").replace('\'', "''");
         format!("'{escaped}'")
     }
 
@@ -870,6 +1965,22 @@ impl SqlDialect for MySqlDialect {
         format!("LIMIT {limit}")
     }
 
+    fn null_safe_equal(&self, left: &str, right: &str) -> String {
+        format!("({left} <=> {right})")
+    }
+
+    fn supports_is_boolean(&self) -> bool {
+        true
+    }
+
+    fn grouping_construct(&self, kind: GroupingKind, columns: &str) -> Option<String> {
+        match kind {
+            GroupingKind::Plain => Some(columns.to_string()),
+            GroupingKind::Rollup => Some(format!("{columns} WITH ROLLUP")),
+            GroupingKind::Cube => None,
+        }
+    }
+
     fn string_concat(&self, left: &str, right: &str) -> String {
         format!("CONCAT({left}, {right})")
     }
@@ -887,13 +1998,38 @@ impl SqlDialect for MySqlDialect {
     }
 
     fn regex_detect(&self, value: &str, pattern: &str) -> Option<String> {
-        Some(format!("REGEXP_LIKE({value}, {pattern})"))
+        Some(format!("({value} REGEXP {pattern})"))
+    }
+
+    fn regex_replace(
+        &self,
+        value: &str,
+        pattern: &str,
+        replacement: &str,
+        global: bool,
+    ) -> Option<String> {
+        // MySQL replaces every match unless given a start position and occurrence
+        let occurrence = if global { "" } else { ", 1, 1" };
+        Some(format!(
+            "REGEXP_REPLACE({value}, {pattern}, {replacement}{occurrence})"
+        ))
+    }
+
+    fn substring(&self, value: &str, start: &str, length: Option<&str>) -> String {
+        match length {
+            Some(length) => format!("SUBSTRING({value}, {start}, {length})"),
+            None => format!("SUBSTRING({value}, {start})"),
+        }
     }
 
     fn char_length(&self, value: &str) -> String {
         format!("CHAR_LENGTH({value})")
     }
 
+    fn logical_xor(&self, left: &str, right: &str) -> String {
+        format!("({left} XOR {right})")
+    }
+
     fn r_cast_type(&self, function: &str) -> Option<&'static str> {
         match function {
             "as.numeric" | "as.double" => Some("DOUBLE"),
@@ -939,7 +2075,9 @@ impl SqlDialect for MySqlDialect {
 /// // SELECT "name", "age" FROM "data" WHERE "age" > 18
 /// ```
 #[derive(Debug, Clone)]
-pub struct SqliteDialect;
+pub struct SqliteDialect {
+    identifier_case: IdentifierCase,
+}
 
 impl SqliteDialect {
     /// Creates a new SQLite dialect instance.
@@ -958,7 +2096,15 @@ impl SqliteDialect {
     /// assert_eq!(dialect.string_concat("'a'", "'b'"), "'a' || 'b'");
     /// ```
     pub const fn new() -> Self {
-        Self
+        Self {
+            identifier_case: IdentifierCase::Preserve,
+        }
+    }
+
+    /// Returns this dialect with the given identifier case folding mode.
+    pub const fn with_identifier_case(mut self, identifier_case: IdentifierCase) -> Self {
+        self.identifier_case = identifier_case;
+        self
     }
 }
 
@@ -995,7 +2141,9 @@ impl Default for SqliteDialect {
 /// // GROUP BY "category"
 /// ```
 #[derive(Debug, Clone)]
-pub struct DuckDbDialect;
+pub struct DuckDbDialect {
+    identifier_case: IdentifierCase,
+}
 
 impl DuckDbDialect {
     /// Creates a new DuckDB dialect instance.
@@ -1015,7 +2163,15 @@ impl DuckDbDialect {
     /// assert_eq!(dialect.string_concat("'a'", "'b'"), "'a' || 'b'");
     /// ```
     pub const fn new() -> Self {
-        Self
+        Self {
+            identifier_case: IdentifierCase::Preserve,
+        }
+    }
+
+    /// Returns this dialect with the given identifier case folding mode.
+    pub const fn with_identifier_case(mut self, identifier_case: IdentifierCase) -> Self {
+        self.identifier_case = identifier_case;
+        self
     }
 }
 
@@ -1027,7 +2183,11 @@ impl Default for DuckDbDialect {
 
 impl SqlDialect for DuckDbDialect {
     fn quote_identifier(&self, name: &str) -> String {
-        quote_with_escape(name, '"')
+        quote_with_identifier_case(name, self.identifier_case, '"')
+    }
+
+    fn identifier_case(&self) -> IdentifierCase {
+        self.identifier_case
     }
 
     fn quote_string(&self, value: &str) -> String {
@@ -1047,6 +2207,10 @@ impl SqlDialect for DuckDbDialect {
         format!("{left} || {right}")
     }
 
+    fn quantile_aggregate(&self, argument: &str, probability: &str) -> Option<String> {
+        Some(format!("QUANTILE_CONT({argument}, {probability})"))
+    }
+
     fn aggregate_function(&self, function: &str) -> String {
         match function.to_lowercase().as_str() {
             "mean" | "avg" => "AVG".to_string(),
@@ -1075,6 +2239,19 @@ impl SqlDialect for DuckDbDialect {
         Some(format!("regexp_matches({value}, {pattern})"))
     }
 
+    fn regex_replace(
+        &self,
+        value: &str,
+        pattern: &str,
+        replacement: &str,
+        global: bool,
+    ) -> Option<String> {
+        let flags = if global { ", 'g'" } else { "" };
+        Some(format!(
+            "regexp_replace({value}, {pattern}, {replacement}{flags})"
+        ))
+    }
+
     fn is_case_sensitive(&self) -> bool {
         false
     }
@@ -1091,13 +2268,37 @@ impl SqlDialect for DuckDbDialect {
         Some(format!("* EXCLUDE ({list})"))
     }
 
-    fn clone_box(&self) -> Box<dyn SqlDialect> {
-        Box::new(self.clone())
+    fn read_file_source(&self, function: &str, path: &str) -> Option<String> {
+        Some(format!("{function}({})", self.quote_string(path)))
     }
-}
+
+    fn supports_semi_anti_join(&self) -> bool {
+        true
+    }
+
+    fn supports_nulls_ordering(&self) -> bool {
+        true
+    }
+
+    fn supports_is_boolean(&self) -> bool {
+        true
+    }
+
+    fn supports_ignore_nulls(&self) -> bool {
+        true
+    }
+
+    fn clone_box(&self) -> Box<dyn SqlDialect> {
+        Box::new(self.clone())
+    }
+}
 
 /// Configuration for SQL dialect behavior
-#[derive(Debug, Clone)]
+///
+/// With the `serde` feature it can be stored as JSON. Identifier case folding
+/// is set on the dialect itself, with `with_identifier_case`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DialectConfig {
     pub identifier_quote: char,
     pub string_quote: char,
@@ -1106,9 +2307,39 @@ pub struct DialectConfig {
     pub case_sensitive: bool,
 }
 
+#[cfg(feature = "serde")]
+impl DialectConfig {
+    /// Loads a dialect configuration from JSON.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use libdplyr::DialectConfig;
+    ///
+    /// let config = DialectConfig::from_json(
+    ///     r#"{"identifier_quote": "`", "string_quote": "'", "supports_limit": true,
+    ///         "supports_offset": true, "case_sensitive": false}"#,
+    /// )
+    /// .unwrap();
+    /// assert_eq!(config.identifier_quote, '`');
+    /// ```
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Serializes this configuration to JSON that [`Self::from_json`] reads back.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
 impl SqlDialect for SqliteDialect {
     fn quote_identifier(&self, name: &str) -> String {
-        quote_with_escape(name, '"')
+        quote_with_identifier_case(name, self.identifier_case, '"')
+    }
+
+    fn identifier_case(&self) -> IdentifierCase {
+        self.identifier_case
     }
 
     fn quote_string(&self, value: &str) -> String {
@@ -1124,10 +2355,34 @@ impl SqlDialect for SqliteDialect {
         format!("LIMIT {limit}")
     }
 
+    fn null_safe_equal(&self, left: &str, right: &str) -> String {
+        format!("({left} IS {right})")
+    }
+
+    fn grouping_construct(&self, kind: GroupingKind, columns: &str) -> Option<String> {
+        (kind == GroupingKind::Plain).then(|| columns.to_string())
+    }
+
     fn string_concat(&self, left: &str, right: &str) -> String {
         format!("{left} || {right}")
     }
 
+    fn regex_replace(
+        &self,
+        value: &str,
+        pattern: &str,
+        replacement: &str,
+        global: bool,
+    ) -> Option<String> {
+        // SQLite has no regex replace; REPLACE() is literal and replaces every
+        // match, so it only stands in for literal patterns without backreferences
+        if !global || replacement.contains('\\') {
+            return None;
+        }
+        let pattern = literal_regex_pattern(pattern)?;
+        Some(format!("REPLACE({value}, {pattern}, {replacement})"))
+    }
+
     fn aggregate_function(&self, function: &str) -> String {
         match function.to_lowercase().as_str() {
             "mean" | "avg" => "AVG".to_string(),
@@ -1185,8 +2440,416 @@ impl SqlDialect for SqliteDialect {
         concat_with_operator(args)
     }
 
-    fn concat_with_separator(&self, separator: &str, args: &[String]) -> Option<String> {
-        concat_with_separator_operator(separator, args)
+    fn supports_concat_ws(&self) -> bool {
+        false
+    }
+
+    fn is_case_sensitive(&self) -> bool {
+        false
+    }
+
+    fn clone_box(&self) -> Box<dyn SqlDialect> {
+        Box::new(self.clone())
+    }
+}
+
+/// Oracle dialect implementation
+///
+/// Implements SQL generation for Oracle Database. Oracle folds unquoted
+/// identifiers to uppercase, so identifiers are uppercased by default and
+/// double-quoted only when they would not survive unquoted.
+///
+/// # Features
+///
+/// - Uppercased identifiers, double-quoted when needed: `NAME`, `"FIRST NAME"`
+/// - String concatenation with `||` operator
+/// - `NVL()` for two-argument `coalesce()`/`replace_na()`
+/// - `FETCH FIRST n ROWS ONLY` row limiting
+/// - No boolean type: `TRUE`/`FALSE` render as `1`/`0`
+///
+/// # Examples
+///
+/// ```rust
+/// use libdplyr::{Transpiler, OracleDialect};
+///
+/// let transpiler = Transpiler::new(Box::new(OracleDialect::new()));
+/// let sql = transpiler.transpile("select(name) %>% head(5)").unwrap();
+///
+/// // Generated SQL:
+/// // SELECT NAME FROM DATA FETCH FIRST 5 ROWS ONLY
+/// ```
+#[derive(Debug, Clone)]
+pub struct OracleDialect {
+    identifier_case: IdentifierCase,
+}
+
+impl OracleDialect {
+    /// Creates a new Oracle dialect instance that uppercases identifiers.
+    ///
+    /// Identifiers are always quoted, since Oracle reserves many common column
+    /// names such as `DATE`, `LEVEL` and `SIZE`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use libdplyr::{OracleDialect, SqlDialect};
+    ///
+    /// let dialect = OracleDialect::new();
+    /// assert_eq!(dialect.quote_identifier("name"), "\"NAME\"");
+    /// assert_eq!(dialect.quote_identifier("first name"), "\"FIRST NAME\"");
+    /// assert_eq!(dialect.limit_clause(10), "FETCH FIRST 10 ROWS ONLY");
+    /// ```
+    pub const fn new() -> Self {
+        Self {
+            identifier_case: IdentifierCase::Upper,
+        }
+    }
+
+    /// Returns this dialect with the given identifier case folding mode.
+    pub const fn with_identifier_case(mut self, identifier_case: IdentifierCase) -> Self {
+        self.identifier_case = identifier_case;
+        self
+    }
+}
+
+impl Default for OracleDialect {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SqlDialect for OracleDialect {
+    fn quote_identifier(&self, name: &str) -> String {
+        let folded = match self.identifier_case {
+            IdentifierCase::Preserve => name.to_string(),
+            IdentifierCase::Lower => name.to_lowercase(),
+            IdentifierCase::Upper => name.to_uppercase(),
+        };
+        quote_with_escape(&folded, '"')
+    }
+
+    fn identifier_case(&self) -> IdentifierCase {
+        self.identifier_case
+    }
+
+    fn quote_string(&self, value: &str) -> String {
+        let escaped = value.replace('\'', "''");
+        format!("'{escaped}'")
+    }
+
+    fn dialect_name(&self) -> &'static str {
+        "oracle"
+    }
+
+    // ROW_NUMBER(), RANK() and NTILE() require an ORDER BY in Oracle
+    fn default_window_order(&self) -> Option<&'static str> {
+        Some("NULL")
+    }
+
+    fn requires_ordered_window_frame(&self) -> bool {
+        true
+    }
+
+    fn supports_nulls_ordering(&self) -> bool {
+        true
+    }
+
+    fn limit_clause(&self, limit: usize) -> String {
+        format!("FETCH FIRST {limit} ROWS ONLY")
+    }
+
+    // Oracle rejects `SELECT *, expr`
+    fn star_projection(&self, qualifier: &str) -> String {
+        format!("{qualifier}.*")
+    }
+
+    // Oracle table aliases take no AS
+    fn table_alias(&self, item: &str, alias: &str) -> String {
+        format!("{item} {alias}")
+    }
+
+    // Without a boolean type, logical columns hold 1/0
+    fn boolean_predicate(&self, value: &str) -> String {
+        format!("({value} = 1)")
+    }
+
+    fn offset_clause(&self, offset: usize) -> String {
+        format!("OFFSET {offset} ROWS")
+    }
+
+    fn limit_with_offset_clause(&self, limit: usize, offset: usize) -> String {
+        if offset == 0 {
+            self.limit_clause(limit)
+        } else {
+            format!("OFFSET {offset} ROWS FETCH NEXT {limit} ROWS ONLY")
+        }
+    }
+
+    fn boolean_literal(&self, value: bool) -> &'static str {
+        if value {
+            "1"
+        } else {
+            "0"
+        }
+    }
+
+    fn string_concat(&self, left: &str, right: &str) -> String {
+        format!("{left} || {right}")
+    }
+
+    fn quantile_aggregate(&self, argument: &str, probability: &str) -> Option<String> {
+        Some(format!(
+            "PERCENTILE_CONT({probability}) WITHIN GROUP (ORDER BY {argument})"
+        ))
+    }
+
+    fn aggregate_function(&self, function: &str) -> String {
+        match function.to_lowercase().as_str() {
+            "mean" | "avg" => "AVG".to_string(),
+            "sum" => "SUM".to_string(),
+            "count" => "COUNT".to_string(),
+            "min" => "MIN".to_string(),
+            "max" => "MAX".to_string(),
+            "n" => "COUNT".to_string(),
+            "median" => "MEDIAN".to_string(),
+            _ => function.to_uppercase(),
+        }
+    }
+
+    fn translate_aggregate_function(&self, function: &str) -> Option<String> {
+        translate_common_aggregate_function(function).or_else(|| {
+            match function.to_lowercase().as_str() {
+                "median" => Some("MEDIAN".to_string()),
+                _ => None,
+            }
+        })
+    }
+
+    fn translate_function(&self, function: &str, args: &[String]) -> Option<String> {
+        match (function.to_lowercase().as_str(), args) {
+            ("coalesce" | "na.replace" | "replace_na", [value, fallback]) => {
+                Some(format!("NVL({value}, {fallback})"))
+            }
+            _ => translate_common_function(self, function, args),
+        }
+    }
+
+    fn regex_detect(&self, value: &str, pattern: &str) -> Option<String> {
+        Some(format!("REGEXP_LIKE({value}, {pattern})"))
+    }
+
+    fn regex_replace(
+        &self,
+        value: &str,
+        pattern: &str,
+        replacement: &str,
+        global: bool,
+    ) -> Option<String> {
+        // Oracle replaces every match unless given a start position and occurrence
+        let occurrence = if global { "" } else { ", 1, 1" };
+        Some(format!(
+            "REGEXP_REPLACE({value}, {pattern}, {replacement}{occurrence})"
+        ))
+    }
+
+    fn r_cast_type(&self, function: &str) -> Option<&'static str> {
+        match function {
+            "as.numeric" | "as.double" => Some("BINARY_DOUBLE"),
+            "as.integer" => Some("INTEGER"),
+            "as.character" => Some("VARCHAR2(4000)"),
+            "as.logical" => Some("NUMBER(1)"),
+            _ => None,
+        }
+    }
+
+    fn log10(&self, value: &str) -> String {
+        format!("LOG(10, {value})")
+    }
+
+    fn concat_no_separator(&self, args: &[String]) -> Option<String> {
+        concat_with_operator(args)
+    }
+
+    fn supports_concat_ws(&self) -> bool {
+        false
+    }
+
+    fn is_case_sensitive(&self) -> bool {
+        false
+    }
+
+    fn clone_box(&self) -> Box<dyn SqlDialect> {
+        Box::new(self.clone())
+    }
+}
+
+/// Spark SQL dialect implementation
+///
+/// Implements SQL generation for Apache Spark SQL. Spark uses backticks for
+/// identifier quoting, `concat()` for string concatenation, and supports
+/// filtering joins natively.
+///
+/// # Features
+///
+/// - Backtick-quoted identifiers: `` `column_name` ``
+/// - String concatenation with `concat()` function
+/// - Backslash-escaped string literals
+/// - `percentile_approx()` for median
+/// - Native `SEMI JOIN` / `ANTI JOIN`
+///
+/// # Examples
+///
+/// ```rust
+/// use libdplyr::{Transpiler, SparkSqlDialect};
+///
+/// let transpiler = Transpiler::new(Box::new(SparkSqlDialect::new()));
+/// let sql = transpiler.transpile("select(name, age) %>% filter(age > 18)").unwrap();
+///
+/// // Generated SQL:
+/// // SELECT `name`, `age` FROM `data` WHERE `age` > 18
+/// ```
+#[derive(Debug, Clone)]
+pub struct SparkSqlDialect {
+    identifier_case: IdentifierCase,
+}
+
+impl SparkSqlDialect {
+    /// Creates a new Spark SQL dialect instance.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use libdplyr::{SparkSqlDialect, SqlDialect};
+    ///
+    /// let dialect = SparkSqlDialect::new();
+    /// assert_eq!(dialect.quote_identifier("user"), "`user`");
+    /// assert_eq!(dialect.string_concat("'a'", "'b'"), "concat('a', 'b')");
+    /// ```
+    pub const fn new() -> Self {
+        Self {
+            identifier_case: IdentifierCase::Preserve,
+        }
+    }
+
+    /// Returns this dialect with the given identifier case folding mode.
+    pub const fn with_identifier_case(mut self, identifier_case: IdentifierCase) -> Self {
+        self.identifier_case = identifier_case;
+        self
+    }
+}
+
+impl Default for SparkSqlDialect {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SqlDialect for SparkSqlDialect {
+    fn quote_identifier(&self, name: &str) -> String {
+        quote_with_identifier_case(name, self.identifier_case, self.identifier_quote())
+    }
+
+    fn identifier_quote(&self) -> char {
+        '`'
+    }
+
+    fn identifier_case(&self) -> IdentifierCase {
+        self.identifier_case
+    }
+
+    fn quote_string(&self, value: &str) -> String {
+        // Spark reads '' as two adjacent literals, so quotes are backslash-escaped
+        let escaped = value.replace('\\', "\\\\").replace('\'', "\\'");
+        format!("'{escaped}'")
+    }
+
+    fn dialect_name(&self) -> &'static str {
+        "spark"
+    }
+
+    // Spark rejects ranking functions over an unordered window
+    fn default_window_order(&self) -> Option<&'static str> {
+        Some("NULL")
+    }
+
+    fn limit_clause(&self, limit: usize) -> String {
+        format!("LIMIT {limit}")
+    }
+
+    fn null_safe_equal(&self, left: &str, right: &str) -> String {
+        format!("({left} <=> {right})")
+    }
+
+    fn string_concat(&self, left: &str, right: &str) -> String {
+        format!("concat({left}, {right})")
+    }
+
+    fn quantile_aggregate(&self, argument: &str, probability: &str) -> Option<String> {
+        Some(format!("PERCENTILE({argument}, {probability})"))
+    }
+
+    fn aggregate_function(&self, function: &str) -> String {
+        match function.to_lowercase().as_str() {
+            "mean" | "avg" => "AVG".to_string(),
+            "sum" => "SUM".to_string(),
+            "count" => "COUNT".to_string(),
+            "min" => "MIN".to_string(),
+            "max" => "MAX".to_string(),
+            "n" => "COUNT".to_string(),
+            "median" => "PERCENTILE_APPROX".to_string(),
+            _ => function.to_uppercase(),
+        }
+    }
+
+    fn translate_aggregate_function(&self, function: &str) -> Option<String> {
+        translate_common_aggregate_function(function).or_else(|| {
+            match function.to_lowercase().as_str() {
+                "median" => Some("PERCENTILE_APPROX".to_string()),
+                _ => None,
+            }
+        })
+    }
+
+    fn aggregate_call(&self, function: &str, argument: &str) -> Option<String> {
+        match function.to_lowercase().as_str() {
+            "median" => Some(format!("PERCENTILE_APPROX({argument}, 0.5)")),
+            _ => self
+                .translate_aggregate_function(function)
+                .map(|name| format!("{name}({argument})")),
+        }
+    }
+
+    fn regex_detect(&self, value: &str, pattern: &str) -> Option<String> {
+        Some(format!("({value} RLIKE {pattern})"))
+    }
+
+    fn regex_replace(
+        &self,
+        value: &str,
+        pattern: &str,
+        replacement: &str,
+        global: bool,
+    ) -> Option<String> {
+        // regexp_replace() always replaces every match
+        global.then(|| format!("REGEXP_REPLACE({value}, {pattern}, {replacement})"))
+    }
+
+    fn r_cast_type(&self, function: &str) -> Option<&'static str> {
+        match function {
+            "as.numeric" | "as.double" => Some("DOUBLE"),
+            "as.integer" => Some("INT"),
+            "as.character" => Some("STRING"),
+            "as.logical" => Some("BOOLEAN"),
+            _ => None,
+        }
+    }
+
+    fn supports_semi_anti_join(&self) -> bool {
+        true
+    }
+
+    fn supports_nulls_ordering(&self) -> bool {
+        true
     }
 
     fn is_case_sensitive(&self) -> bool {
@@ -2,10 +2,15 @@
 //!
 //! Provides functionality to convert AST to various SQL dialects.
 
-use crate::error::{GenerationError, GenerationResult};
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::ops::Deref;
+
+use crate::error::{GenerationError, GenerationResult, TranspileWarning};
 use crate::parser::{
-    Aggregation, BinaryOp, ColumnExpr, DplyrNode, DplyrOperation, Expr, JoinSpec, JoinType,
-    LiteralValue, OrderDirection, OrderExpr, RenameSpec, SetOperation,
+    expr_to_string, Aggregation, BinaryOp, ColumnExpr, DplyrNode, DplyrOperation, Expr,
+    GroupingKind, JoinSpec, JoinType, LiteralValue, NullsOrder, OrderDirection, OrderExpr,
+    RenameSpec, SetOperation, SourceFunction, SummariseGroups, MAX_EXPRESSION_DEPTH,
 };
 
 // Decomposition scaffolding (“Tidy First”): these modules are placeholders to
@@ -13,16 +18,74 @@ use crate::parser::{
 pub mod assemble;
 pub mod dialect;
 pub mod mutate_support;
+pub mod pivot_support;
+pub mod slice_support;
 
-use assemble::QueryParts;
+use assemble::{
+    group_projections, source_table_name, table_qualifier, Projection, QueryParts, SqlSink,
+    WriterSink,
+};
 
 pub use dialect::{
-    DialectConfig, DuckDbDialect, MySqlDialect, PostgreSqlDialect, SqlDialect, SqliteDialect,
+    dialect_from_name, format_number, supported_dialect_names, DialectConfig, DuckDbDialect,
+    IdentifierCase, MySqlDialect, OracleDialect, PostgreSqlDialect, SparkSqlDialect, SqlDialect,
+    SqliteDialect,
 };
 
+/// Rejects an operation that gives two of its output columns the same name.
+fn check_unique_output_names<'a>(
+    operation: &str,
+    names: impl IntoIterator<Item = &'a str>,
+) -> GenerationResult<()> {
+    let mut seen = HashSet::new();
+    for name in names {
+        if !seen.insert(name) {
+            return Err(GenerationError::DuplicateColumnName {
+                column: name.to_string(),
+                operation: operation.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
 /// SQL generator struct
+///
+/// Holds configuration only, so one generator can be shared across threads;
+/// each `generate*` call keeps its working state in its own [`GenContext`].
+#[derive(Clone)]
 pub struct SqlGenerator {
     dialect: Box<dyn SqlDialect>,
+    auto_aggregate_aliases: bool,
+    null_safe_equality: bool,
+    boolean_is_comparison: bool,
+    order_tiebreaker: Option<String>,
+    max_output_length: Option<usize>,
+}
+
+/// State of a single `generate*` call, dropped when the call returns.
+pub(crate) struct GenContext<'g> {
+    generator: &'g SqlGenerator,
+    warnings: RefCell<Vec<TranspileWarning>>,
+    source_alias: Option<String>,
+    /// Mutated columns a filter() reads, rendered as their expressions.
+    inlined_columns: RefCell<HashMap<String, String>>,
+    /// ORDER BY of the arrange() before the mutate() being generated, which
+    /// orders cumulative windows such as `cumsum()`.
+    window_order: RefCell<String>,
+    /// Whether aggregates such as `mean()` render as window aggregates over the
+    /// group_by() partition, as they do inside mutate().
+    window_aggregates: Cell<bool>,
+    /// Expressions currently being generated, innermost last.
+    expression_depth: Cell<usize>,
+}
+
+impl Deref for GenContext<'_> {
+    type Target = SqlGenerator;
+
+    fn deref(&self) -> &SqlGenerator {
+        self.generator
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -69,6 +132,30 @@ const STR_DETECT_FORMALS: &[NamedArgFormal] = &[
         default_sql: None,
     },
 ];
+const GREPL_FORMALS: &[NamedArgFormal] = &[
+    NamedArgFormal {
+        name: "pattern",
+        default_sql: None,
+    },
+    NamedArgFormal {
+        name: "x",
+        default_sql: None,
+    },
+];
+const STR_REPLACE_FORMALS: &[NamedArgFormal] = &[
+    NamedArgFormal {
+        name: "string",
+        default_sql: None,
+    },
+    NamedArgFormal {
+        name: "pattern",
+        default_sql: None,
+    },
+    NamedArgFormal {
+        name: "replacement",
+        default_sql: None,
+    },
+];
 const SUBSTR_FORMALS: &[NamedArgFormal] = &[
     NamedArgFormal {
         name: "x",
@@ -83,6 +170,20 @@ const SUBSTR_FORMALS: &[NamedArgFormal] = &[
         default_sql: None,
     },
 ];
+const STR_SUB_FORMALS: &[NamedArgFormal] = &[
+    NamedArgFormal {
+        name: "string",
+        default_sql: None,
+    },
+    NamedArgFormal {
+        name: "start",
+        default_sql: None,
+    },
+    NamedArgFormal {
+        name: "end",
+        default_sql: None,
+    },
+];
 const LOG_FORMALS: &[NamedArgFormal] = &[
     NamedArgFormal {
         name: "x",
@@ -135,23 +236,108 @@ const IF_ELSE_FORMALS: &[NamedArgFormal] = &[
         default_sql: None,
     },
 ];
+const NEAR_FORMALS: &[NamedArgFormal] = &[
+    NamedArgFormal {
+        name: "x",
+        default_sql: None,
+    },
+    NamedArgFormal {
+        name: "y",
+        default_sql: None,
+    },
+    NamedArgFormal {
+        name: "tol",
+        default_sql: None,
+    },
+];
 
 fn named_argument_formals(function: &str) -> Option<&'static [NamedArgFormal]> {
     match function.to_ascii_lowercase().as_str() {
         "round" => Some(ROUND_FORMALS),
         "lead" | "lag" => Some(LEAD_LAG_FORMALS),
         "str_detect" => Some(STR_DETECT_FORMALS),
+        "grepl" => Some(GREPL_FORMALS),
+        "str_replace" | "str_replace_all" => Some(STR_REPLACE_FORMALS),
         "substr" => Some(SUBSTR_FORMALS),
+        "str_sub" => Some(STR_SUB_FORMALS),
         "log" => Some(LOG_FORMALS),
-        "abs" | "floor" | "ceiling" | "ceil" | "sqrt" | "sign" | "exp" | "log10" | "sin"
-        | "cos" | "tan" | "asin" | "acos" | "atan" | "sinh" | "cosh" | "tanh" | "str_length"
-        | "str_to_lower" | "str_to_upper" | "str_trim" | "nchar" | "nzchar" | "trimws"
-        | "as.numeric" | "as.double" | "as.integer" | "as.character" | "as.logical" => {
+        "abs" | "floor" | "ceiling" | "ceil" | "sqrt" | "sign" | "exp" | "log2" | "log10"
+        | "sin" | "cos" | "tan" | "asin" | "acos" | "atan" | "sinh" | "cosh" | "tanh"
+        | "str_length" | "str_to_lower" | "str_to_upper" | "str_trim" | "nchar" | "nzchar"
+        | "trimws" | "as.numeric" | "as.double" | "as.integer" | "as.character" | "as.logical" => {
             Some(UNARY_X_FORMALS)
         }
         "first" | "first_value" | "last" | "last_value" => Some(VALUE_ORDER_FORMALS),
         "ifelse" => Some(IFELSE_FORMALS),
         "if_else" => Some(IF_ELSE_FORMALS),
+        "near" => Some(NEAR_FORMALS),
+        _ => None,
+    }
+}
+
+/// Whether `operation` works on the rows an earlier head()/slice() kept, so it
+/// cannot share a query level with that LIMIT. Projections and renames do not
+/// change which rows are returned, and slices handle an earlier limit themselves.
+/// Joins stay on the base table, whose name their conditions are qualified by.
+const fn reads_limited_rows(operation: &DplyrOperation) -> bool {
+    !matches!(
+        operation,
+        DplyrOperation::Select { .. }
+            | DplyrOperation::Rename { .. }
+            | DplyrOperation::RenameWith { .. }
+            | DplyrOperation::Slice { .. }
+            | DplyrOperation::Join { .. }
+            | DplyrOperation::Unsupported { .. }
+    )
+}
+
+const fn is_null_literal(expr: &Expr) -> bool {
+    matches!(expr, Expr::Literal(LiteralValue::Null))
+}
+
+const fn boolean_literal(expr: &Expr) -> Option<bool> {
+    match expr {
+        Expr::Literal(LiteralValue::Boolean(value)) => Some(*value),
+        _ => None,
+    }
+}
+
+/// Words a `frame =` window frame may use, besides row counts.
+const WINDOW_FRAME_KEYWORDS: &[&str] = &[
+    "rows",
+    "range",
+    "groups",
+    "between",
+    "and",
+    "unbounded",
+    "preceding",
+    "following",
+    "current",
+    "row",
+];
+
+/// Normalizes a `frame = "rows between 2 preceding and current row"` window
+/// frame to uppercase SQL, or returns `None` when it starts with something
+/// other than ROWS/RANGE/GROUPS or uses a word outside [`WINDOW_FRAME_KEYWORDS`].
+fn window_frame_sql(frame: &str) -> Option<String> {
+    let words: Vec<String> = frame.split_whitespace().map(str::to_lowercase).collect();
+    let starts_with_unit = words
+        .first()
+        .is_some_and(|unit| matches!(unit.as_str(), "rows" | "range" | "groups"));
+    let allowed = words.iter().all(|word| {
+        WINDOW_FRAME_KEYWORDS.contains(&word.as_str())
+            || (!word.is_empty() && word.bytes().all(|b| b.is_ascii_digit()))
+    });
+    (starts_with_unit && allowed).then(|| words.join(" ").to_uppercase())
+}
+
+/// Aggregate a dplyr cumulative function such as `cumsum()` runs over its window.
+fn cumulative_aggregate(function: &str) -> Option<&'static str> {
+    match function.to_ascii_lowercase().as_str() {
+        "cumsum" => Some("sum"),
+        "cummean" => Some("mean"),
+        "cummax" => Some("max"),
+        "cummin" => Some("min"),
         _ => None,
     }
 }
@@ -163,7 +349,92 @@ impl SqlGenerator {
     ///
     /// * `dialect` - The SQL dialect to use
     pub fn new(dialect: Box<dyn SqlDialect>) -> Self {
-        Self { dialect }
+        Self {
+            dialect,
+            auto_aggregate_aliases: true,
+            null_safe_equality: false,
+            boolean_is_comparison: false,
+            order_tiebreaker: None,
+            max_output_length: None,
+        }
+    }
+
+    /// Name of the target dialect, such as `"postgresql"`.
+    pub fn dialect_name(&self) -> &'static str {
+        self.dialect.dialect_name()
+    }
+
+    /// Comment line recording the libdplyr version and target dialect, such as
+    /// `-- generated by libdplyr <version> (dialect=postgresql)`, without a newline.
+    pub fn provenance_comment(&self) -> String {
+        format!(
+            "{} generated by libdplyr {} (dialect={})",
+            self.dialect.line_comment_prefix(),
+            env!("CARGO_PKG_VERSION"),
+            self.dialect.dialect_name()
+        )
+    }
+
+    /// Aborts generation with [`GenerationError::OutputTooLarge`] once the SQL
+    /// being built exceeds `max` bytes, instead of checking only the finished query.
+    pub fn with_max_output_length(mut self, max: usize) -> Self {
+        self.max_output_length = Some(max);
+        self
+    }
+
+    /// Starts the per-call state for one generation, with columns qualified by
+    /// `source_alias` when one is given.
+    pub(crate) fn context(&self, source_alias: Option<&str>) -> GenContext<'_> {
+        GenContext {
+            generator: self,
+            warnings: RefCell::new(Vec::new()),
+            source_alias: source_alias.map(str::to_string),
+            inlined_columns: RefCell::new(HashMap::new()),
+            window_order: RefCell::new(String::new()),
+            window_aggregates: Cell::new(false),
+            expression_depth: Cell::new(0),
+        }
+    }
+
+    fn check_output_length(&self, length: usize) -> GenerationResult<()> {
+        match self.max_output_length {
+            Some(max) if length > max => Err(GenerationError::OutputTooLarge { length, max }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Sets whether unnamed summarise() columns get a `function_column` alias
+    /// (`mean(salary)` becomes `AVG("salary") AS "mean_salary"`). Enabled by default.
+    pub fn with_auto_aggregate_aliases(mut self, enabled: bool) -> Self {
+        self.auto_aggregate_aliases = enabled;
+        self
+    }
+
+    /// Sets whether `==` and `!=` compare NULLs as ordinary values, using the
+    /// dialect's [`SqlDialect::null_safe_equal`], as R's `identical()` would.
+    /// Disabled by default.
+    pub fn with_null_safe_equality(mut self, enabled: bool) -> Self {
+        self.null_safe_equality = enabled;
+        self
+    }
+
+    /// Sets whether `==` / `!=` against `TRUE` or `FALSE` become `IS [NOT] TRUE`
+    /// / `IS [NOT] FALSE` on dialects with [`SqlDialect::supports_is_boolean`],
+    /// which never yield NULL. Other dialects keep `= TRUE`. Disabled by default.
+    pub fn with_boolean_is_comparison(mut self, enabled: bool) -> Self {
+        self.boolean_is_comparison = enabled;
+        self
+    }
+
+    /// Appends `column` as the last ORDER BY key of every arrange(), so rows that
+    /// tie on the arranged columns still come back in a deterministic order.
+    ///
+    /// Use a unique column of the source table, such as its primary key. It is
+    /// not appended after summarise() or distinct(), whose output no longer has
+    /// that column, nor when arrange() already sorts by it. Unset by default.
+    pub fn with_order_tiebreaker(mut self, column: impl Into<String>) -> Self {
+        self.order_tiebreaker = Some(column.into());
+        self
     }
 
     /// Converts AST to SQL query.
@@ -176,17 +447,145 @@ impl SqlGenerator {
     ///
     /// Returns SQL query string on success, GenerationError on failure.
     pub fn generate(&self, ast: &DplyrNode) -> GenerationResult<String> {
+        self.generate_with_warnings(ast).map(|(sql, _)| sql)
+    }
+
+    /// Converts AST to SQL and collects warnings for degraded translations.
+    ///
+    /// Warnings never turn a successful translation into an error; they flag
+    /// SQL that runs but may not match dplyr semantics exactly.
+    pub fn generate_with_warnings(
+        &self,
+        ast: &DplyrNode,
+    ) -> GenerationResult<(String, Vec<TranspileWarning>)> {
+        let context = self.context(None);
+        let sql = context.generate_node(ast)?;
+        Ok((sql, context.warnings.take()))
+    }
+
+    /// Converts AST to SQL and writes it to `out` clause by clause, without
+    /// first building the whole query as one string.
+    ///
+    /// With [`SqlGenerator::with_max_output_length`], writing stops with
+    /// [`GenerationError::OutputTooLarge`] before the limit is passed, so `out`
+    /// never receives more than `max` bytes. Warnings are discarded; use
+    /// [`SqlGenerator::generate_with_warnings`] to collect them.
+    pub fn generate_to_writer(
+        &self,
+        ast: &DplyrNode,
+        out: &mut dyn std::io::Write,
+    ) -> GenerationResult<()> {
+        let context = self.context(None);
+        let mut sink = WriterSink::new(out, self.max_output_length);
         match ast {
             DplyrNode::Pipeline {
                 source,
+                source_function,
                 target,
                 operations,
                 ..
-            } => self.generate_pipeline(source, target, operations),
-            DplyrNode::DataSource { name, .. } => Ok(format!(
-                "SELECT * FROM {}",
-                self.dialect.quote_identifier(name)
-            )),
+            } => {
+                let query_parts =
+                    context.plan_pipeline(source, source_function.as_ref(), target, operations)?;
+                context.write_query(source, &query_parts, &mut sink)?;
+            }
+            DplyrNode::DataSource { name, .. } => {
+                sink.push_sql("SELECT * FROM ")?;
+                sink.push_sql(&context.render_from_table(name))?;
+            }
+        }
+        sink.flush()
+    }
+
+    /// Converts AST to SQL with the source table aliased as `alias` in FROM.
+    ///
+    /// Column references are qualified with the alias (`"o"."id"`), so the
+    /// result can be embedded as a subquery next to other tables.
+    pub fn generate_with_source_alias(
+        &self,
+        ast: &DplyrNode,
+        alias: &str,
+    ) -> GenerationResult<(String, Vec<TranspileWarning>)> {
+        if alias.trim().is_empty() {
+            return Err(GenerationError::InvalidIdentifier {
+                identifier: alias.to_string(),
+                reason: "source alias must not be empty".to_string(),
+            });
+        }
+
+        let context = self.context(Some(alias));
+        let sql = context.generate_node(ast)?;
+        Ok((sql, context.warnings.take()))
+    }
+}
+
+impl GenContext<'_> {
+    /// Quotes a column reference, qualifying it with the source alias when one is set.
+    fn column_ref(&self, column: &str) -> String {
+        match self.source_alias.as_deref() {
+            Some(alias) => self.dialect.quote_identifier_path(&[alias, column]),
+            None => self.dialect.quote_identifier(column),
+        }
+    }
+
+    fn generate_node(&self, ast: &DplyrNode) -> GenerationResult<String> {
+        match ast {
+            DplyrNode::Pipeline {
+                source,
+                source_function,
+                target,
+                operations,
+                ..
+            } => self.generate_pipeline(source, source_function.as_ref(), target, operations),
+            DplyrNode::DataSource { name, .. } => {
+                Ok(format!("SELECT * FROM {}", self.render_from_table(name)))
+            }
+        }
+    }
+
+    fn warn(&self, warning: TranspileWarning) {
+        let mut warnings = self.warnings.borrow_mut();
+        if !warnings.contains(&warning) {
+            warnings.push(warning);
+        }
+    }
+
+    /// Records warnings for function translations that succeed with reduced fidelity.
+    fn check_degraded_function(&self, name: &str, args: &[Expr]) {
+        let fn_lower = name.to_ascii_lowercase();
+        let positional = args
+            .iter()
+            .filter(|arg| !matches!(arg, Expr::NamedArg { .. }))
+            .count();
+        let has_order_by = args.iter().any(|arg| {
+            matches!(arg, Expr::NamedArg { name, .. } if name.eq_ignore_ascii_case("order_by"))
+        });
+
+        let unordered = match fn_lower.as_str() {
+            "lead" | "lag" => !has_order_by && positional < 4,
+            "first" | "first_value" | "last" | "last_value" => !has_order_by && positional < 2,
+            "row_number" | "rank" | "dense_rank" => positional == 0,
+            "nth_value" | "ntile" => true,
+            _ => false,
+        };
+        if unordered {
+            self.warn(TranspileWarning::UnorderedWindowFunction {
+                function: name.to_string(),
+            });
+        }
+
+        if fn_lower == "as.logical" {
+            if let Some(sql_type) = self
+                .dialect
+                .r_cast_type(&fn_lower)
+                .filter(|sql_type| *sql_type != "BOOLEAN")
+            {
+                self.warn(TranspileWarning::LossyTypeConversion {
+                    function: name.to_string(),
+                    sql_type: sql_type.to_string(),
+                    dialect: self.dialect.dialect_name().to_string(),
+                });
+            }
         }
     }
 
@@ -194,9 +593,26 @@ impl SqlGenerator {
     fn generate_pipeline(
         &self,
         source: &Option<String>,
+        source_function: Option<&SourceFunction>,
         target: &Option<String>,
         operations: &[DplyrOperation],
     ) -> GenerationResult<String> {
+        let query_parts = self.plan_pipeline(source, source_function, target, operations)?;
+
+        // Assemble final SQL query
+        let sql = self.assemble_query(source, &query_parts)?;
+        self.check_output_length(sql.len())?;
+        Ok(sql)
+    }
+
+    /// Processes every operation of a pipeline into the parts of its outermost query.
+    fn plan_pipeline(
+        &self,
+        source: &Option<String>,
+        source_function: Option<&SourceFunction>,
+        target: &Option<String>,
+        operations: &[DplyrOperation],
+    ) -> GenerationResult<QueryParts> {
         // Allow empty operations if we have a direct table assignment
         if operations.is_empty() && target.is_none() {
             return Err(GenerationError::InvalidAst {
@@ -206,26 +622,174 @@ impl SqlGenerator {
 
         let mut query_parts = QueryParts::new();
         let mut aggregation_group_by = None;
+        let mut aggregated = false;
+
+        if let Some(function) = source_function {
+            query_parts.from_function = Some(
+                self.dialect
+                    .read_file_source(&function.name, &function.path)
+                    .ok_or_else(|| GenerationError::UnsupportedFunction {
+                        function: function.name.clone(),
+                        dialect: self.dialect.dialect_name().to_string(),
+                    })?,
+            );
+        }
 
-        // Get the source table name for join operations
-        let source_table = source.as_deref().unwrap_or("data");
+        // Join conditions qualify the base table by its alias when one is set
+        let source_alias = self.source_alias.clone();
+        let source_table = source_alias
+            .as_deref()
+            .unwrap_or_else(|| source_table_name(source));
 
         // Process each operation in order
         for operation in operations {
-            self.process_operation(operation, &mut query_parts, source_table)?;
-            if matches!(operation, DplyrOperation::Summarise { .. }) {
+            if query_parts.is_row_limited() && reads_limited_rows(operation) {
+                // LIMIT is applied last; later verbs must see only the rows it kept
+                self.wrap_in_subquery(source, &mut query_parts, &mut aggregation_group_by)?;
+            }
+            match operation {
+                DplyrOperation::Slice { position, n, .. } => self.process_slice_operation(
+                    *position,
+                    *n,
+                    source,
+                    &mut query_parts,
+                    &mut aggregation_group_by,
+                )?,
+                DplyrOperation::Filter { condition, .. }
+                    if self.filter_needs_window(condition, &query_parts) =>
+                {
+                    self.process_window_filter(
+                        condition,
+                        source,
+                        &mut query_parts,
+                        &mut aggregation_group_by,
+                    )?;
+                }
+                DplyrOperation::Filter { condition, .. }
+                    if self.filter_reads_window_columns(condition, &query_parts) =>
+                {
+                    // WHERE cannot evaluate window functions; filter their results outside
+                    self.wrap_in_subquery(source, &mut query_parts, &mut aggregation_group_by)?;
+                    self.process_operation(operation, &mut query_parts, source_table)?;
+                }
+                DplyrOperation::Summarise { .. } | DplyrOperation::PivotWider { .. }
+                    if aggregated =>
+                {
+                    // Aggregates cannot nest; aggregate the earlier result outside
+                    self.wrap_in_subquery(source, &mut query_parts, &mut aggregation_group_by)?;
+                    self.process_operation(operation, &mut query_parts, source_table)?;
+                }
+                DplyrOperation::Mutate { assignments, .. }
+                    if self.mutate_reads_window_columns(assignments, &query_parts) =>
+                {
+                    // Window functions cannot nest; compute on their results outside
+                    self.wrap_in_subquery(source, &mut query_parts, &mut aggregation_group_by)?;
+                    self.process_operation(operation, &mut query_parts, source_table)?;
+                }
+                _ => self.process_operation(operation, &mut query_parts, source_table)?,
+            }
+            self.check_output_length(query_parts.estimated_len())?;
+            let retained_groups = match operation {
+                DplyrOperation::Summarise { groups, .. } => Some(*groups),
+                // tidyr keeps the grouping of a pivoted table
+                DplyrOperation::PivotWider { .. } => Some(SummariseGroups::Keep),
+                _ => None,
+            };
+            if let Some(groups) = retained_groups {
+                aggregated = true;
                 aggregation_group_by = if query_parts.group_by.is_empty() {
                     None
                 } else {
-                    Some(query_parts.group_by.clone())
+                    Some(self.grouping_clause(&query_parts)?)
+                };
+                // The regrouped result is grouped plainly
+                query_parts.grouping = GroupingKind::Plain;
+                // Later verbs see only the grouping .groups leaves on the result
+                query_parts.group_by = match groups {
+                    SummariseGroups::Keep => query_parts.group_by.clone(),
+                    SummariseGroups::Drop => String::new(),
+                    SummariseGroups::DropLast => query_parts
+                        .group_by
+                        .rsplit_once(", ")
+                        .map(|(retained, _)| retained.to_string())
+                        .unwrap_or_default(),
                 };
             }
         }
 
+        if !aggregated && !query_parts.distinct {
+            self.append_order_tiebreaker(&mut query_parts)?;
+        }
+
         query_parts.group_by = aggregation_group_by.unwrap_or_default();
+        Ok(query_parts)
+    }
 
-        // Assemble final SQL query
-        self.assemble_query(source, &query_parts)
+    /// Renders the GROUP BY clause body for the current grouping, wrapping the
+    /// columns in ROLLUP/CUBE when group_by() asked for one.
+    fn grouping_clause(&self, query_parts: &QueryParts) -> GenerationResult<String> {
+        self.dialect
+            .grouping_construct(query_parts.grouping, &query_parts.group_by)
+            .ok_or_else(|| GenerationError::UnsupportedOperation {
+                operation: format!(
+                    "group_by({}())",
+                    query_parts.grouping.helper_name().unwrap_or_default()
+                ),
+                dialect: self.dialect.dialect_name().to_string(),
+            })
+    }
+
+    /// Adds the configured tiebreaker column after the arrange() keys.
+    fn append_order_tiebreaker(&self, query_parts: &mut QueryParts) -> GenerationResult<()> {
+        let Some(column) = &self.order_tiebreaker else {
+            return Ok(());
+        };
+        if query_parts.order_exprs.is_empty()
+            || query_parts
+                .order_exprs
+                .iter()
+                .any(|order| order.column == *column)
+        {
+            return Ok(());
+        }
+        query_parts.order_exprs.push(OrderExpr {
+            column: column.clone(),
+            direction: OrderDirection::Asc,
+            nulls: None,
+        });
+        query_parts.order_columns.push(self.column_ref(column));
+        let mutated_columns = query_parts.mutated_columns.clone();
+        self.refresh_order_by(query_parts, &mutated_columns)
+    }
+
+    /// Rejects output names of an aggregating operation that repeat one of the
+    /// grouping columns, which are part of its output too.
+    fn check_names_clear_of_groups(
+        &self,
+        operation: &str,
+        names: &[String],
+        group_by: &str,
+    ) -> GenerationResult<()> {
+        let group_columns: Vec<&str> = if group_by.is_empty() {
+            Vec::new()
+        } else {
+            group_by.split(", ").collect()
+        };
+        match names.iter().find(|name| {
+            let quoted = self.dialect.quote_identifier(name);
+            group_columns.iter().any(|group| {
+                *group == quoted
+                    || group
+                        .strip_suffix(quoted.as_str())
+                        .is_some_and(|qualifier| qualifier.ends_with('.'))
+            })
+        }) {
+            Some(name) => Err(GenerationError::DuplicateColumnName {
+                column: name.clone(),
+                operation: operation.to_string(),
+            }),
+            None => Ok(()),
+        }
     }
 
     /// Processes individual operations.
@@ -236,19 +800,45 @@ impl SqlGenerator {
         source_table: &str,
     ) -> GenerationResult<()> {
         match operation {
+            DplyrOperation::Select { excluded, .. } if !excluded.is_empty() => {
+                // Excluded mutate() columns can still be sorted by their expression
+                let mutated_columns = query_parts.mutated_columns.clone();
+                self.process_select_exclusion(excluded, query_parts)?;
+                self.refresh_order_by(query_parts, &mutated_columns)?;
+            }
             DplyrOperation::Select { columns, .. } => {
+                check_unique_output_names(
+                    "select",
+                    columns
+                        .iter()
+                        .filter_map(|column| match (&column.alias, &column.expr) {
+                            (Some(alias), _) => Some(alias.as_str()),
+                            (None, Expr::Identifier(name)) => Some(name.as_str()),
+                            (None, Expr::QualifiedIdentifier { column, .. }) => {
+                                Some(column.as_str())
+                            }
+                            (None, _) => None,
+                        }),
+                )?;
                 query_parts.select_columns =
                     self.generate_select_columns_with_mutations(columns, query_parts)?;
+                let mutated_columns = query_parts.mutated_columns.clone();
+                self.refresh_order_by(query_parts, &mutated_columns)?;
             }
             DplyrOperation::Filter { condition, .. } => {
-                let where_clause = self.generate_expression(condition)?;
-                if query_parts.where_clauses.is_empty() {
-                    query_parts.where_clauses.push(where_clause);
-                } else {
-                    query_parts
-                        .where_clauses
-                        .push(format!("AND ({where_clause})"));
+                if let Some(function) = self.find_aggregate_call(condition) {
+                    return Err(GenerationError::AggregateInFilter {
+                        function: function.to_string(),
+                    });
                 }
+                // WHERE runs before SELECT, so columns created by mutate() are
+                // replaced with their expressions
+                self.inlined_columns
+                    .replace(query_parts.mutated_columns.clone());
+                let where_clause = self.generate_predicate_with_window_partition(condition, "");
+                self.inlined_columns.borrow_mut().clear();
+                let where_clause = where_clause?;
+                query_parts.push_where(where_clause);
             }
             DplyrOperation::Mutate { assignments, .. } => {
                 // Handle mutate operations - may need subqueries for complex cases
@@ -257,24 +847,94 @@ impl SqlGenerator {
             DplyrOperation::Rename { renames, .. } => {
                 self.process_rename_operation(renames, query_parts)?;
             }
-            DplyrOperation::Arrange { columns, .. } => {
-                query_parts.order_by = self.generate_order_by(columns)?;
+            DplyrOperation::RenameWith {
+                function, columns, ..
+            } => {
+                let renames = self.rename_with_specs(function, columns)?;
+                self.process_rename_operation(&renames, query_parts)?;
             }
-            DplyrOperation::GroupBy { columns, .. } => {
-                query_parts.group_by = columns
+            DplyrOperation::Arrange { columns, .. } => {
+                query_parts.order_by = self.generate_order_by(
+                    columns,
+                    &query_parts.select_columns,
+                    &query_parts.mutated_columns,
+                )?;
+                query_parts.order_columns = columns
                     .iter()
-                    .map(|col| self.dialect.quote_identifier(col))
-                    .collect::<Vec<_>>()
-                    .join(", ");
+                    .map(|col| self.column_ref(&col.column))
+                    .collect();
+                query_parts.order_exprs = columns.clone();
             }
-            DplyrOperation::Summarise { aggregations, .. } => {
-                let mut select_columns = Vec::new();
-                if !query_parts.group_by.is_empty() {
-                    select_columns.push(query_parts.group_by.clone());
+            DplyrOperation::GroupBy {
+                columns,
+                add,
+                grouping,
+                ..
+            } => {
+                let mut group_columns: Vec<String> = if *add && !query_parts.group_by.is_empty() {
+                    query_parts
+                        .group_by
+                        .split(", ")
+                        .map(str::to_string)
+                        .collect()
+                } else {
+                    Vec::new()
+                };
+                for column in columns {
+                    let column = self.column_ref(column);
+                    if !group_columns.contains(&column) {
+                        group_columns.push(column);
+                    }
                 }
+                query_parts.group_by = group_columns.join(", ");
+                query_parts.grouping = *grouping;
+            }
+            DplyrOperation::Summarise { aggregations, .. } => {
+                let aliases: Vec<String> = aggregations
+                    .iter()
+                    .filter_map(|agg| self.aggregation_alias(agg))
+                    .collect();
+                check_unique_output_names("summarise", aliases.iter().map(String::as_str))?;
+                self.check_names_clear_of_groups("summarise", &aliases, &query_parts.group_by)?;
+                let mut select_columns = group_projections(&query_parts.group_by);
                 select_columns.extend(self.generate_aggregations(aggregations)?);
                 query_parts.select_columns = select_columns;
             }
+            DplyrOperation::PivotWider {
+                names_from,
+                values_from,
+                names,
+                ..
+            } => {
+                self.process_pivot_wider(names_from, values_from, names, query_parts)?;
+            }
+            DplyrOperation::Unsupported { verb, reason, .. } => {
+                return Err(GenerationError::UnsupportedOperation {
+                    operation: reason
+                        .clone()
+                        .unwrap_or_else(|| format!("{verb}() is not supported yet")),
+                    dialect: self.dialect.dialect_name().to_string(),
+                });
+            }
+            DplyrOperation::Distinct { columns, .. } => {
+                if !columns.is_empty() {
+                    query_parts.select_columns = columns
+                        .iter()
+                        .map(|col| Projection::column(self.column_ref(col), col))
+                        .collect();
+                }
+                query_parts.distinct = true;
+            }
+            DplyrOperation::Fill {
+                columns, direction, ..
+            } => {
+                self.process_fill_operation(columns, *direction, query_parts)?;
+            }
+            DplyrOperation::Slice { position, n, .. } => {
+                // generate_pipeline handles slices itself so they can wrap the real
+                // source; nested pipelines always read the default table
+                self.process_slice_operation(*position, *n, &None, query_parts, &mut None)?;
+            }
             DplyrOperation::Join {
                 join_type, spec, ..
             } => {
@@ -296,6 +956,54 @@ impl SqlGenerator {
         Ok(())
     }
 
+    /// Drops the `select(-col)` columns from the projection.
+    ///
+    /// Columns the projection lists by name are removed from it; the rest are
+    /// excluded from `*` with the dialect's `* EXCLUDE (...)` form.
+    fn process_select_exclusion(
+        &self,
+        excluded: &[String],
+        query_parts: &mut QueryParts,
+    ) -> GenerationResult<()> {
+        let mut from_star = Vec::new();
+        for column in excluded {
+            let before = query_parts.select_columns.len();
+            query_parts
+                .select_columns
+                .retain(|item| item.name.as_ref() != Some(column));
+            query_parts.mutated_columns.remove(column);
+            if query_parts.select_columns.len() == before {
+                from_star.push(column.clone());
+            }
+        }
+
+        if !from_star.is_empty() {
+            let has_star = query_parts.select_columns.is_empty()
+                || query_parts.select_columns.iter().any(Projection::is_star);
+            if !has_star {
+                return Err(GenerationError::InvalidColumnReference {
+                    column: from_star.join(", "),
+                    table: None,
+                });
+            }
+            if self.dialect.select_star_exclude(&from_star).is_none() {
+                return Err(GenerationError::UnsupportedOperation {
+                    operation: "select(-col)".to_string(),
+                    dialect: self.dialect.dialect_name().to_string(),
+                });
+            }
+            if query_parts.select_columns.is_empty() {
+                query_parts.select_columns.push(Projection::star());
+            }
+            query_parts.star_excluded.extend(from_star);
+        } else if query_parts.select_columns.is_empty() {
+            return Err(GenerationError::InvalidAst {
+                reason: "select() dropped every column".to_string(),
+            });
+        }
+        Ok(())
+    }
+
     fn process_rename_operation(
         &self,
         renames: &[RenameSpec],
@@ -312,41 +1020,89 @@ impl SqlGenerator {
             .map(|spec| spec.old_name.clone())
             .collect::<Vec<_>>();
 
-        let star_exclude = self.dialect.select_star_exclude(&excluded).ok_or_else(|| {
-            GenerationError::UnsupportedOperation {
+        if self.dialect.select_star_exclude(&excluded).is_none() {
+            return Err(GenerationError::UnsupportedOperation {
                 operation: "rename".to_string(),
                 dialect: self.dialect.dialect_name().to_string(),
-            }
-        })?;
+            });
+        }
 
         if query_parts.select_columns.is_empty() {
-            query_parts.select_columns.push(star_exclude);
-        } else {
-            let mut replaced_star = false;
-            for col in &mut query_parts.select_columns {
-                if col == "*" {
-                    *col = star_exclude.clone();
-                    replaced_star = true;
+            query_parts.select_columns.push(Projection::star());
+        } else if !query_parts.select_columns.iter().any(Projection::is_star) {
+            return Err(GenerationError::InvalidAst {
+                reason:
+                    "rename() currently requires an implicit '*' projection (no prior select())"
+                        .to_string(),
+            });
+        }
+        query_parts.star_excluded.extend(excluded);
+
+        for spec in renames {
+            query_parts
+                .select_columns
+                .push(self.aliased_projection(self.column_ref(&spec.old_name), &spec.new_name));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the first aggregate function (`mean`, `n`, ...) called anywhere in `expr`.
+    fn find_aggregate_call<'a>(&self, expr: &'a Expr) -> Option<&'a str> {
+        match expr {
+            Expr::Identifier(_) | Expr::QualifiedIdentifier { .. } | Expr::Literal(_) => None,
+            Expr::Binary { left, right, .. }
+            | Expr::Formula {
+                lhs: left,
+                rhs: right,
+            } => self
+                .find_aggregate_call(left)
+                .or_else(|| self.find_aggregate_call(right)),
+            Expr::Function { name, args } => {
+                if self.dialect.translate_aggregate_function(name).is_some() {
+                    Some(name)
+                } else {
+                    args.iter().find_map(|arg| self.find_aggregate_call(arg))
                 }
             }
-            if !replaced_star {
-                return Err(GenerationError::InvalidAst {
-                    reason:
-                        "rename() currently requires an implicit '*' projection (no prior select())"
-                            .to_string(),
-                });
+            Expr::NamedArg { value, .. } | Expr::Not(value) | Expr::Negate(value) => {
+                self.find_aggregate_call(value)
             }
         }
+    }
 
-        for spec in renames {
-            query_parts.select_columns.push(format!(
-                "{} AS {}",
-                self.dialect.quote_identifier(&spec.old_name),
-                self.dialect.quote_identifier(&spec.new_name)
-            ));
+    /// Expands `rename_with(fn, cols)` into explicit renames by applying `fn` to each name.
+    fn rename_with_specs(
+        &self,
+        function: &str,
+        columns: &[String],
+    ) -> GenerationResult<Vec<RenameSpec>> {
+        if columns.is_empty() {
+            // Renaming every column needs the table schema, which the transpiler never sees
+            return Err(GenerationError::UnsupportedOperation {
+                operation: "rename_with() without an explicit column selection".to_string(),
+                dialect: self.dialect.dialect_name().to_string(),
+            });
         }
 
-        Ok(())
+        let transform: fn(&str) -> String = match function {
+            "toupper" => str::to_uppercase,
+            "tolower" => str::to_lowercase,
+            _ => {
+                return Err(GenerationError::UnsupportedFunction {
+                    function: function.to_string(),
+                    dialect: self.dialect.dialect_name().to_string(),
+                })
+            }
+        };
+
+        Ok(columns
+            .iter()
+            .map(|column| RenameSpec {
+                new_name: transform(column),
+                old_name: column.clone(),
+            })
+            .collect())
     }
 
     fn process_join_operation(
@@ -356,79 +1112,18 @@ impl SqlGenerator {
         query_parts: &mut QueryParts,
         source_table: &str,
     ) -> GenerationResult<()> {
-        use crate::parser::JoinType;
-
-        // Check if dialect supports SEMI/ANTI JOIN natively (DuckDB only)
-        let is_duckdb = self.dialect.dialect_name() == "duckdb";
-
-        // For SEMI and ANTI joins, non-DuckDB dialects need subquery transformation
-        match join_type {
-            JoinType::Semi | JoinType::Anti if !is_duckdb => {
-                // Generate EXISTS/NOT EXISTS subquery for non-DuckDB dialects
-                let exists_keyword = match join_type {
-                    JoinType::Semi => "EXISTS",
-                    JoinType::Anti => "NOT EXISTS",
-                    _ => unreachable!(),
-                };
-
-                // Generate the condition
-                let condition = if let Some(by_column) = &spec.by_column {
-                    format!(
-                        "{} = {}",
-                        self.dialect
-                            .quote_identifier_path(&[source_table, by_column]),
-                        self.dialect
-                            .quote_identifier_path(&[&spec.table, by_column])
-                    )
-                } else if let Some(expr) = &spec.on_expr {
-                    self.generate_expression(expr)?
-                } else {
-                    return Err(GenerationError::InvalidAst {
-                        reason: "join operation requires either 'by' parameter or 'on' condition"
-                            .to_string(),
-                    });
-                };
-
-                // Create subquery: WHERE (NOT) EXISTS (SELECT 1 FROM right_table ON condition)
-                let subquery = format!(
-                    "{exists_keyword} (SELECT 1 FROM {} WHERE {condition})",
-                    self.dialect.quote_identifier(&spec.table)
-                );
-
-                // Add as WHERE clause (SEMI/ANTI don't need actual JOIN)
-                if query_parts.where_clauses.is_empty() {
-                    query_parts.where_clauses.push(subquery);
-                } else {
-                    query_parts.where_clauses.push(format!("AND ({subquery})"));
-                }
-
-                return Ok(());
-            }
-            _ => {}
-        }
-
-        // For DuckDB or standard joins, use native JOIN syntax
-        let join_sql = match join_type {
-            JoinType::Inner => "INNER JOIN",
-            JoinType::Left => "LEFT JOIN",
-            JoinType::Right => "RIGHT JOIN",
-            JoinType::Full => "FULL JOIN",
-            JoinType::Semi => "SEMI JOIN",
-            JoinType::Anti => "ANTI JOIN",
-        };
-
-        // Generate ON clause based on join specification
-        let on_clause = if let Some(by_column) = &spec.by_column {
-            // by = "column_name" -> ON "source"."column" = "right_table"."column"
+        // Generate the join condition based on the join specification
+        let condition = if let Some(by_column) = &spec.by_column {
+            // by = "column_name" -> "source"."column" = "right_table"."column"
             format!(
                 "{} = {}",
                 self.dialect
-                    .quote_identifier_path(&[source_table, by_column]),
+                    .quote_identifier_path(&[table_qualifier(source_table), by_column]),
                 self.dialect
-                    .quote_identifier_path(&[&spec.table, by_column])
+                    .quote_identifier_path(&[table_qualifier(&spec.table), by_column])
             )
         } else if let Some(expr) = &spec.on_expr {
-            // Fallback to expression-based ON clause
+            // Fallback to expression-based condition
             self.generate_expression(expr)?
         } else {
             // No join condition specified
@@ -438,18 +1133,70 @@ impl SqlGenerator {
             });
         };
 
+        let join_sql = match join_type {
+            JoinType::Inner => "INNER JOIN",
+            JoinType::Left => "LEFT JOIN",
+            JoinType::Right => "RIGHT JOIN",
+            JoinType::Full => "FULL JOIN",
+            JoinType::Semi | JoinType::Anti if !self.dialect.supports_semi_anti_join() => {
+                // Filtering joins become correlated (NOT) EXISTS predicates
+                let exists_keyword = if matches!(join_type, JoinType::Semi) {
+                    "EXISTS"
+                } else {
+                    "NOT EXISTS"
+                };
+                let subquery = format!(
+                    "{exists_keyword} (SELECT 1 FROM {} WHERE {condition})",
+                    self.quote_table_name(&spec.table)
+                );
+
+                query_parts.push_where(subquery);
+
+                return Ok(());
+            }
+            JoinType::Semi => "SEMI JOIN",
+            JoinType::Anti => "ANTI JOIN",
+        };
+
         query_parts.joins.push(format!(
             "{} {} ON {}",
             join_sql,
-            self.dialect.quote_identifier(&spec.table),
-            on_clause
+            self.quote_table_name(&spec.table),
+            condition
         ));
 
         Ok(())
     }
 
+    /// Regenerates the ORDER BY of an earlier arrange() after the projection
+    /// changed, so columns the projection dropped are ordered by expression.
+    fn refresh_order_by(
+        &self,
+        query_parts: &mut QueryParts,
+        mutated_columns: &HashMap<String, String>,
+    ) -> GenerationResult<()> {
+        if !query_parts.order_exprs.is_empty() {
+            query_parts.order_by = self.generate_order_by(
+                &query_parts.order_exprs,
+                &query_parts.select_columns,
+                mutated_columns,
+            )?;
+        }
+        Ok(())
+    }
+
     /// Generates ORDER BY clause.
-    fn generate_order_by(&self, columns: &[OrderExpr]) -> GenerationResult<String> {
+    ///
+    /// Columns that name an alias in the projection are ordered by the alias, or
+    /// by the aliased expression where the dialect cannot order by aliases.
+    /// mutate() columns that a later select() dropped are ordered by their
+    /// expression, since the alias no longer exists.
+    fn generate_order_by(
+        &self,
+        columns: &[OrderExpr],
+        projection: &[Projection],
+        mutated_columns: &HashMap<String, String>,
+    ) -> GenerationResult<String> {
         let order_items: Result<Vec<_>, _> = columns
             .iter()
             .map(|col| {
@@ -457,10 +1204,38 @@ impl SqlGenerator {
                     OrderDirection::Asc => "ASC",
                     OrderDirection::Desc => "DESC",
                 };
+                let nulls = match col.nulls {
+                    Some(_) if !self.dialect.supports_nulls_ordering() => {
+                        self.warn(TranspileWarning::NullsOrderingIgnored {
+                            column: col.column.clone(),
+                            dialect: self.dialect.dialect_name().to_string(),
+                        });
+                        ""
+                    }
+                    Some(NullsOrder::First) => " NULLS FIRST",
+                    Some(NullsOrder::Last) => " NULLS LAST",
+                    None => "",
+                };
+                let quoted = self.dialect.quote_identifier(&col.column);
+                let aliased = projection
+                    .iter()
+                    .find(|item| item.alias.as_deref() == Some(quoted.as_str()));
+                let reference = match aliased {
+                    Some(_) if self.dialect.supports_order_by_alias() => None,
+                    Some(item) => Some(item.expr.clone()),
+                    None if projection
+                        .iter()
+                        .any(|item| item.alias.is_none() && item.expr == quoted) =>
+                    {
+                        None
+                    }
+                    None => mutated_columns.get(&col.column).cloned(),
+                };
                 Ok(format!(
-                    "{} {}",
-                    self.dialect.quote_identifier(&col.column),
-                    direction
+                    "{} {}{}",
+                    reference.unwrap_or_else(|| self.column_ref(&col.column)),
+                    direction,
+                    nulls
                 ))
             })
             .collect();
@@ -469,51 +1244,165 @@ impl SqlGenerator {
     }
 
     /// Generates aggregate functions.
-    fn generate_aggregations(&self, aggregations: &[Aggregation]) -> GenerationResult<Vec<String>> {
+    fn generate_aggregations(
+        &self,
+        aggregations: &[Aggregation],
+    ) -> GenerationResult<Vec<Projection>> {
         aggregations
             .iter()
             .map(|agg| {
-                let func_name = self
-                    .dialect
-                    .translate_aggregate_function(&agg.function)
-                    .ok_or_else(|| GenerationError::UnsupportedAggregateFunction {
-                        function: agg.function.clone(),
-                        dialect: self.dialect.dialect_name().to_string(),
-                    })?;
-                let column_ref = if agg.function.to_lowercase() == "n" {
+                // n() and a bare count() count rows; count(x) counts non-NULL x
+                let counts_rows = match agg.function.to_lowercase().as_str() {
+                    "n" => true,
+                    "count" => agg.column.is_empty(),
+                    _ => false,
+                };
+                let column_ref = if counts_rows {
                     "*".to_string()
+                } else if agg.distinct {
+                    format!("DISTINCT {}", self.column_ref(&agg.column))
                 } else {
-                    self.dialect.quote_identifier(&agg.column)
+                    self.column_ref(&agg.column)
                 };
 
-                let expr = format!("{func_name}({column_ref})");
-
-                if let Some(alias) = &agg.alias {
-                    Ok(format!(
-                        "{} AS {}",
-                        expr,
-                        self.dialect.quote_identifier(alias)
-                    ))
-                } else {
-                    Ok(expr)
+                let expr = match agg.probability {
+                    Some(probability) => self.dialect.quantile_aggregate(
+                        &column_ref,
+                        &format_number(probability, self.dialect.as_ref()),
+                    ),
+                    None => self.dialect.aggregate_call(&agg.function, &column_ref),
                 }
+                .ok_or_else(|| GenerationError::UnsupportedAggregateFunction {
+                    function: agg.function.clone(),
+                    dialect: self.dialect.dialect_name().to_string(),
+                    suggestion: self
+                        .dialect
+                        .aggregate_alternative(&agg.function, &agg.column),
+                })?;
+
+                Ok(match self.aggregation_alias(agg) {
+                    Some(alias) => self.aliased_projection(expr, &alias),
+                    None => Projection::unnamed(expr),
+                })
             })
             .collect()
     }
 
+    /// Output name of a summarise() column: its alias, or `function_column`
+    /// when automatic aliases are enabled.
+    fn aggregation_alias(&self, agg: &Aggregation) -> Option<String> {
+        match &agg.alias {
+            Some(alias) => Some(alias.clone()),
+            None if !self.auto_aggregate_aliases => None,
+            None if agg.column.is_empty() => Some(agg.function.clone()),
+            None => Some(format!("{}_{}", agg.function, agg.column)),
+        }
+    }
+
     /// Converts expressions to SQL.
     fn generate_expression(&self, expr: &Expr) -> GenerationResult<String> {
         self.generate_expression_with_window_partition(expr, "")
     }
 
+    /// Generates `expr` one nesting level deeper, failing beyond
+    /// [`MAX_EXPRESSION_DEPTH`] instead of recursing further.
     fn generate_expression_with_window_partition(
         &self,
         expr: &Expr,
         partition_by: &str,
+    ) -> GenerationResult<String> {
+        let depth = self.expression_depth.get();
+        if depth >= MAX_EXPRESSION_DEPTH {
+            return Err(GenerationError::MaxNestingDepthExceeded {
+                depth: depth + 1,
+                max_depth: MAX_EXPRESSION_DEPTH,
+            });
+        }
+        self.expression_depth.set(depth + 1);
+        let result = self.generate_nested_expression(expr, partition_by);
+        self.expression_depth.set(depth);
+        result
+    }
+
+    fn generate_nested_expression(
+        &self,
+        expr: &Expr,
+        partition_by: &str,
     ) -> GenerationResult<String> {
         match expr {
-            Expr::Identifier(name) => Ok(self.dialect.quote_identifier(name)),
+            Expr::Identifier(name) => Ok(self
+                .inlined_columns
+                .borrow()
+                .get(name)
+                .cloned()
+                .unwrap_or_else(|| self.column_ref(name))),
+            Expr::QualifiedIdentifier { table, column } => {
+                Ok(self.dialect.quote_identifier_path(&[table, column]))
+            }
             Expr::Literal(literal) => self.generate_literal(literal),
+            Expr::Binary {
+                left,
+                operator: operator @ (BinaryOp::Equal | BinaryOp::NotEqual),
+                right,
+            } if is_null_literal(left) || is_null_literal(right) => {
+                // `x == NA` is never true in SQL (`= NULL` yields NULL), so use IS [NOT] NULL
+                let operand = if is_null_literal(left) { right } else { left };
+                let operand_sql =
+                    self.generate_expression_with_window_partition(operand, partition_by)?;
+                let predicate = if *operator == BinaryOp::Equal {
+                    "IS NULL"
+                } else {
+                    "IS NOT NULL"
+                };
+                Ok(format!("({operand_sql} {predicate})"))
+            }
+            Expr::Binary {
+                left,
+                operator: operator @ (BinaryOp::Equal | BinaryOp::NotEqual),
+                right,
+            } if self.boolean_is_comparison
+                && self.dialect.supports_is_boolean()
+                && (boolean_literal(left).is_some() || boolean_literal(right).is_some()) =>
+            {
+                let (operand, value) = match boolean_literal(right) {
+                    Some(value) => (left, value),
+                    None => (right, boolean_literal(left).unwrap_or_default()),
+                };
+                let operand_sql =
+                    self.generate_expression_with_window_partition(operand, partition_by)?;
+                let not = if *operator == BinaryOp::Equal {
+                    ""
+                } else {
+                    "NOT "
+                };
+                let value = if value { "TRUE" } else { "FALSE" };
+                Ok(format!("({operand_sql} IS {not}{value})"))
+            }
+            Expr::Binary {
+                left,
+                operator: operator @ (BinaryOp::Equal | BinaryOp::NotEqual),
+                right,
+            } if self.null_safe_equality => {
+                let left_sql =
+                    self.generate_expression_with_window_partition(left, partition_by)?;
+                let right_sql =
+                    self.generate_expression_with_window_partition(right, partition_by)?;
+                let equal = self.dialect.null_safe_equal(&left_sql, &right_sql);
+                Ok(if *operator == BinaryOp::Equal {
+                    equal
+                } else {
+                    format!("(NOT {equal})")
+                })
+            }
+            Expr::Binary {
+                left,
+                operator: BinaryOp::Power,
+                right,
+            } => self.generate_function_expression_with_window_partition(
+                "pow",
+                &[(**left).clone(), (**right).clone()],
+                partition_by,
+            ),
             Expr::Binary {
                 left,
                 operator,
@@ -529,10 +1418,106 @@ impl SqlGenerator {
             Expr::Function { name, args } => {
                 self.generate_function_expression_with_window_partition(name, args, partition_by)
             }
+            Expr::Not(operand) => Ok(format!(
+                "(NOT {})",
+                self.generate_expression_with_window_partition(operand, partition_by)?
+            )),
+            Expr::Negate(operand) => Ok(format!(
+                "(-{})",
+                self.generate_expression_with_window_partition(operand, partition_by)?
+            )),
             Expr::NamedArg { name, .. } => Err(GenerationError::InvalidAst {
                 reason: format!("named argument '{name}' cannot be used outside a function call"),
             }),
+            Expr::Formula { .. } => Err(GenerationError::InvalidAst {
+                reason: format!(
+                    "formula '{}' can only be used inside case_when()",
+                    expr_to_string(expr)
+                ),
+            }),
+        }
+    }
+
+    /// Generates `expr` where SQL expects a condition, such as WHERE or `CASE
+    /// WHEN`, so bare logical columns go through [`SqlDialect::boolean_predicate`].
+    fn generate_predicate_with_window_partition(
+        &self,
+        expr: &Expr,
+        partition_by: &str,
+    ) -> GenerationResult<String> {
+        match expr {
+            Expr::Binary {
+                left,
+                operator: operator @ (BinaryOp::And | BinaryOp::Or),
+                right,
+            } => {
+                let left_sql = self.generate_predicate_with_window_partition(left, partition_by)?;
+                let right_sql =
+                    self.generate_predicate_with_window_partition(right, partition_by)?;
+                let op_sql = self.generate_binary_operator(operator);
+                Ok(format!("({left_sql} {op_sql} {right_sql})"))
+            }
+            Expr::Not(operand) => Ok(format!(
+                "(NOT {})",
+                self.generate_predicate_with_window_partition(operand, partition_by)?
+            )),
+            // A filtered mutate() column is inlined as its own expression
+            Expr::Identifier(name) if self.inlined_columns.borrow().contains_key(name) => {
+                self.generate_expression_with_window_partition(expr, partition_by)
+            }
+            Expr::Identifier(_)
+            | Expr::QualifiedIdentifier { .. }
+            | Expr::Literal(LiteralValue::Boolean(_)) => {
+                let value = self.generate_expression_with_window_partition(expr, partition_by)?;
+                Ok(self.dialect.boolean_predicate(&value))
+            }
+            _ => self.generate_expression_with_window_partition(expr, partition_by),
+        }
+    }
+
+    /// Generates `CASE WHEN ... END` from `case_when(condition ~ value, ...)`.
+    ///
+    /// A `TRUE ~ value` branch becomes the ELSE clause; without one, unmatched
+    /// rows are NULL, as in dplyr.
+    fn generate_case_when_with_window_partition(
+        &self,
+        args: &[Expr],
+        partition_by: &str,
+    ) -> GenerationResult<String> {
+        let mut branches = Vec::new();
+        let mut else_sql = None;
+
+        for arg in args {
+            let Expr::Formula { lhs, rhs } = arg else {
+                return Err(GenerationError::InvalidAst {
+                    reason: "case_when() arguments must be formulas like `condition ~ value`"
+                        .to_string(),
+                });
+            };
+
+            let value_sql = self.generate_expression_with_window_partition(rhs, partition_by)?;
+            if matches!(**lhs, Expr::Literal(LiteralValue::Boolean(true))) {
+                // Branches after a catch-all are unreachable
+                else_sql = Some(value_sql);
+                break;
+            }
+
+            let condition_sql = self.generate_predicate_with_window_partition(lhs, partition_by)?;
+            branches.push(format!("WHEN {condition_sql} THEN {value_sql}"));
+        }
+
+        if branches.is_empty() {
+            return else_sql.ok_or_else(|| GenerationError::InvalidAst {
+                reason: "case_when() requires at least one `condition ~ value` formula".to_string(),
+            });
         }
+
+        let mut sql = format!("CASE {}", branches.join(" "));
+        if let Some(else_sql) = else_sql {
+            sql.push_str(&format!(" ELSE {else_sql}"));
+        }
+        sql.push_str(" END");
+        Ok(sql)
     }
 
     fn generate_function_expression_with_window_partition(
@@ -541,9 +1526,21 @@ impl SqlGenerator {
         args: &[Expr],
         partition_by: &str,
     ) -> GenerationResult<String> {
-        if name.eq_ignore_ascii_case("paste") {
+        if name.eq_ignore_ascii_case("paste") || name.eq_ignore_ascii_case("str_c") {
             return self.generate_paste_expression_with_window_partition(name, args, partition_by);
         }
+        if name.eq_ignore_ascii_case("case_when") {
+            return self.generate_case_when_with_window_partition(args, partition_by);
+        }
+        if let Some(aggregate) = cumulative_aggregate(name) {
+            return self.generate_cumulative_window(name, aggregate, args, partition_by);
+        }
+        if self.window_aggregates.get() && self.dialect.supports_aggregate(name) {
+            return self.generate_window_aggregate(name, args, partition_by);
+        }
+        if matches!(name.to_ascii_lowercase().as_str(), "substr" | "str_sub") {
+            self.check_substring_indices(name, args)?;
+        }
 
         let args_str =
             self.generate_function_arguments_with_window_partition(name, args, partition_by)?;
@@ -552,6 +1549,7 @@ impl SqlGenerator {
             self.dialect
                 .translate_function_with_window_partition(name, &args_str, partition_by)
         {
+            self.check_degraded_function(name, args);
             return Ok(translated);
         }
 
@@ -561,6 +1559,164 @@ impl SqlGenerator {
         })
     }
 
+    /// Renders `cumsum(x)`-style calls as the running `aggregate` over the rows up
+    /// to the current one, ordered by the preceding arrange().
+    fn generate_cumulative_window(
+        &self,
+        name: &str,
+        aggregate: &str,
+        args: &[Expr],
+        partition_by: &str,
+    ) -> GenerationResult<String> {
+        let [arg] = args else {
+            return Err(GenerationError::InvalidAst {
+                reason: format!("{name}() takes exactly one argument"),
+            });
+        };
+        let argument = self.generate_expression_with_window_partition(arg, partition_by)?;
+        let call = self
+            .dialect
+            .aggregate_call(aggregate, &argument)
+            .ok_or_else(|| GenerationError::UnsupportedFunction {
+                function: name.to_string(),
+                dialect: self.dialect.dialect_name().to_string(),
+            })?;
+
+        let mut window = Vec::new();
+        if !partition_by.trim().is_empty() {
+            window.push(format!("PARTITION BY {}", partition_by.trim()));
+        }
+        let order_by = self.window_order.borrow().clone();
+        if order_by.is_empty() {
+            if self.dialect.requires_ordered_window_frame() {
+                return Err(GenerationError::UnsupportedOperation {
+                    operation: format!("{name}() without a preceding arrange()"),
+                    dialect: self.dialect.dialect_name().to_string(),
+                });
+            }
+            self.warn(TranspileWarning::UnorderedWindowFunction {
+                function: name.to_string(),
+            });
+        } else {
+            window.push(format!("ORDER BY {order_by}"));
+        }
+        window.push("ROWS UNBOUNDED PRECEDING".to_string());
+        Ok(format!("{call} OVER ({})", window.join(" ")))
+    }
+
+    /// Renders an aggregate inside mutate(), such as `mean(x)`, over the whole
+    /// group: `AVG(x) OVER (PARTITION BY g)`, or `OVER ()` when ungrouped.
+    ///
+    /// A `frame = "rows between 2 preceding and current row"` argument limits
+    /// the window to that frame, ordered by the preceding arrange().
+    fn generate_window_aggregate(
+        &self,
+        name: &str,
+        args: &[Expr],
+        partition_by: &str,
+    ) -> GenerationResult<String> {
+        let mut frame = None;
+        for arg in args {
+            if let Expr::NamedArg {
+                name: arg_name,
+                value,
+            } = arg
+            {
+                if arg_name == "frame" {
+                    frame = Some(self.window_frame_argument(name, value)?);
+                }
+            }
+        }
+        // SQL aggregates already skip NULLs, as `na.rm = TRUE` asks
+        let args: Vec<&Expr> = args
+            .iter()
+            .filter(|arg| {
+                !matches!(arg, Expr::NamedArg { name, .. } if name == "na.rm" || name == "frame")
+            })
+            .collect();
+        let counts_rows = name.eq_ignore_ascii_case("n");
+        let argument = match args.as_slice() {
+            [] if counts_rows || name.eq_ignore_ascii_case("count") => "*".to_string(),
+            [arg] if !counts_rows => {
+                self.generate_expression_with_window_partition(arg, partition_by)?
+            }
+            _ => {
+                return Err(GenerationError::InvalidAst {
+                    reason: if counts_rows {
+                        "n() takes no arguments".to_string()
+                    } else {
+                        format!("{name}() in mutate() takes exactly one column")
+                    },
+                })
+            }
+        };
+        let call = self
+            .dialect
+            .aggregate_call(name, &argument)
+            .ok_or_else(|| GenerationError::UnsupportedAggregateFunction {
+                function: name.to_string(),
+                dialect: self.dialect.dialect_name().to_string(),
+                suggestion: None,
+            })?;
+
+        let mut window = Vec::new();
+        let partition_by = partition_by.trim();
+        if !partition_by.is_empty() {
+            window.push(format!("PARTITION BY {partition_by}"));
+        }
+        if let Some(frame) = frame {
+            let order_by = self.window_order.borrow().clone();
+            if order_by.is_empty() {
+                if self.dialect.requires_ordered_window_frame() {
+                    return Err(GenerationError::UnsupportedOperation {
+                        operation: format!("{name}(frame = ...) without a preceding arrange()"),
+                        dialect: self.dialect.dialect_name().to_string(),
+                    });
+                }
+                self.warn(TranspileWarning::UnorderedWindowFunction {
+                    function: name.to_string(),
+                });
+            } else {
+                window.push(format!("ORDER BY {order_by}"));
+            }
+            window.push(frame);
+        }
+        Ok(format!("{call} OVER ({})", window.join(" ")))
+    }
+
+    /// Validates the `frame =` argument of a windowed aggregate and returns it as SQL.
+    fn window_frame_argument(&self, function: &str, value: &Expr) -> GenerationResult<String> {
+        let Expr::Literal(LiteralValue::String(frame)) = value else {
+            return Err(GenerationError::InvalidAst {
+                reason: format!("{function}(frame = ...) takes a string such as \"rows between 2 preceding and current row\""),
+            });
+        };
+        window_frame_sql(frame).ok_or_else(|| GenerationError::InvalidAst {
+            reason: format!(
+                "{function}(frame = \"{frame}\") is not a window frame; use ROWS, RANGE or GROUPS with BETWEEN, AND, UNBOUNDED, PRECEDING, FOLLOWING, CURRENT ROW and row counts"
+            ),
+        })
+    }
+
+    /// Rejects negative substring positions; R counts them from the end of the
+    /// string, which SQL `SUBSTR` does not.
+    fn check_substring_indices(&self, name: &str, args: &[Expr]) -> GenerationResult<()> {
+        let is_negative = |arg: &Expr| match arg {
+            Expr::Literal(LiteralValue::Number(n)) => *n < 0.0,
+            Expr::NamedArg { value, .. } => {
+                matches!(**value, Expr::Literal(LiteralValue::Number(n)) if n < 0.0)
+            }
+            _ => false,
+        };
+        if args.iter().skip(1).any(is_negative) {
+            return Err(GenerationError::UnsupportedOperation {
+                operation: format!("{name}() with negative positions"),
+                dialect: self.dialect.dialect_name().to_string(),
+            });
+        }
+        Ok(())
+    }
+
     fn generate_function_arguments_with_window_partition(
         &self,
         function: &str,
@@ -569,9 +1725,18 @@ impl SqlGenerator {
     ) -> GenerationResult<Vec<String>> {
         let has_named_args = args.iter().any(|arg| matches!(arg, Expr::NamedArg { .. }));
         if !has_named_args {
+            let conditional =
+                matches!(function.to_ascii_lowercase().as_str(), "if_else" | "ifelse");
             return args
                 .iter()
-                .map(|arg| self.generate_expression_with_window_partition(arg, partition_by))
+                .enumerate()
+                .map(|(index, arg)| {
+                    if conditional && index == 0 {
+                        self.generate_predicate_with_window_partition(arg, partition_by)
+                    } else {
+                        self.generate_expression_with_window_partition(arg, partition_by)
+                    }
+                })
                 .collect();
         }
 
@@ -663,7 +1828,10 @@ impl SqlGenerator {
         partition_by: &str,
     ) -> GenerationResult<String> {
         let mut positional_args = Vec::new();
-        let mut separator = self.dialect.quote_string(" ");
+        // paste() separates with a space by default, str_c() with nothing
+        let mut separator = name
+            .eq_ignore_ascii_case("paste")
+            .then(|| self.dialect.quote_string(" "));
         let mut seen_separator = false;
 
         for arg in args {
@@ -679,7 +1847,7 @@ impl SqlGenerator {
                         });
                     }
                     separator =
-                        self.generate_expression_with_window_partition(value, partition_by)?;
+                        Some(self.generate_expression_with_window_partition(value, partition_by)?);
                     seen_separator = true;
                 }
                 Expr::NamedArg { name: arg_name, .. } => {
@@ -694,24 +1862,27 @@ impl SqlGenerator {
             }
         }
 
-        self.dialect
-            .concat_with_separator(&separator, &positional_args)
-            .ok_or_else(|| GenerationError::UnsupportedFunction {
-                function: name.to_string(),
-                dialect: self.dialect.dialect_name().to_string(),
-            })
+        match &separator {
+            Some(separator) => self
+                .dialect
+                .concat_with_separator(separator, &positional_args),
+            None => self.dialect.concat_no_separator(&positional_args),
+        }
+        .ok_or_else(|| GenerationError::UnsupportedFunction {
+            function: name.to_string(),
+            dialect: self.dialect.dialect_name().to_string(),
+        })
     }
 
     /// Converts literal values to SQL.
     fn generate_literal(&self, literal: &LiteralValue) -> GenerationResult<String> {
         match literal {
             LiteralValue::String(s) => Ok(self.dialect.quote_string(s)),
-            LiteralValue::Number(n) => Ok(n.to_string()),
-            LiteralValue::Boolean(b) => Ok(if *b {
-                "TRUE".to_string()
-            } else {
-                "FALSE".to_string()
+            LiteralValue::Number(n) if !n.is_finite() => Err(GenerationError::InvalidAst {
+                reason: format!("number literal {n} is out of range"),
             }),
+            LiteralValue::Number(n) => Ok(format_number(*n, self.dialect.as_ref())),
+            LiteralValue::Boolean(b) => Ok(self.dialect.boolean_literal(*b).to_string()),
             LiteralValue::Null => Ok("NULL".to_string()),
         }
     }
@@ -731,6 +1902,7 @@ impl SqlGenerator {
             BinaryOp::Minus => "-",
             BinaryOp::Multiply => "*",
             BinaryOp::Divide => "/",
+            BinaryOp::Power => "^",
         }
     }
 }
@@ -1,9 +1,29 @@
 // Mutate-related helpers.
 
-use super::QueryParts;
-use super::{ColumnExpr, Expr, GenerationResult, SqlGenerator};
+use super::assemble::SUBQUERY_ALIAS;
+use super::{
+    check_unique_output_names, ColumnExpr, Expr, GenContext, GenerationError, GenerationResult,
+    SqlGenerator,
+};
+use super::{Projection, QueryParts};
+use crate::error::TranspileWarning;
+use crate::parser::FillDirection;
 
-impl SqlGenerator {
+/// Prefix of the helper columns holding the window values a filter() compares against.
+const FILTER_WINDOW_COLUMN: &str = "dplyr_filter_window";
+
+/// Returns whether `assignment` is `x = replace_na(x, ...)`, as the
+/// replace_na() verb is parsed.
+fn replaces_missing_values(assignment: &crate::parser::Assignment) -> bool {
+    matches!(
+        &assignment.expr,
+        Expr::Function { name, args }
+            if name == "replace_na"
+                && matches!(args.first(), Some(Expr::Identifier(column)) if *column == assignment.column)
+    )
+}
+
+impl GenContext<'_> {
     /// Generates SELECT columns, inlining any columns created by previous mutate() calls.
     ///
     /// This allows pipelines like `mutate(x = a + b) %>% select(x)` to work by
@@ -12,7 +32,7 @@ impl SqlGenerator {
         &self,
         columns: &[ColumnExpr],
         parts: &QueryParts,
-    ) -> GenerationResult<Vec<String>> {
+    ) -> GenerationResult<Vec<Projection>> {
         columns
             .iter()
             .map(|col| {
@@ -28,15 +48,14 @@ impl SqlGenerator {
                 };
 
                 let alias = col.alias.as_deref().or(implicit_alias);
-                if let Some(alias) = alias {
-                    Ok(format!(
-                        "{} AS {}",
-                        expr_sql,
-                        self.dialect.quote_identifier(alias)
-                    ))
-                } else {
-                    Ok(expr_sql)
-                }
+                Ok(match (alias, &col.expr) {
+                    (Some(alias), _) => self.aliased_projection(expr_sql, alias),
+                    (None, Expr::Identifier(name)) => Projection::column(expr_sql, name),
+                    (None, Expr::QualifiedIdentifier { column, .. }) => {
+                        Projection::column(expr_sql, column)
+                    }
+                    (None, _) => Projection::unnamed(expr_sql),
+                })
             })
             .collect()
     }
@@ -57,7 +76,7 @@ impl SqlGenerator {
         query_parts: &mut QueryParts,
     ) -> GenerationResult<()> {
         // Check if we need subqueries for complex expressions
-        let needs_subquery = self.mutate_needs_subquery(assignments, query_parts);
+        let needs_subquery = self.mutate_needs_subquery(assignments, query_parts)?;
 
         if needs_subquery {
             // For complex cases, we'll use a simpler approach for now
@@ -69,25 +88,34 @@ impl SqlGenerator {
     }
 
     /// Determines if mutate operation needs subquery or CTE.
+    ///
+    /// Fails with [`GenerationError::CircularReference`] when assignments depend
+    /// on each other in a cycle, such as `mutate(a = b, b = a)`.
     pub(super) fn mutate_needs_subquery(
         &self,
         assignments: &[crate::parser::Assignment],
         query_parts: &QueryParts,
-    ) -> bool {
+    ) -> GenerationResult<bool> {
+        if let Some(cycle) = self.find_mutate_cycle(assignments) {
+            return Err(GenerationError::CircularReference {
+                reference: cycle.join(" -> "),
+            });
+        }
+
         // Need subquery if:
         // 1. There are existing aggregations (GROUP BY + HAVING)
         // 2. Mutate expressions reference other mutated columns
         // 3. Complex window functions are used
 
         if !query_parts.group_by.is_empty() {
-            return true;
+            return Ok(true);
         }
 
         // Check for column dependencies within mutate
         let mut defined_columns = std::collections::HashSet::new();
         for assignment in assignments {
             if self.expression_references_columns(&assignment.expr, &defined_columns) {
-                return true;
+                return Ok(true);
             }
             defined_columns.insert(assignment.column.clone());
         }
@@ -95,11 +123,81 @@ impl SqlGenerator {
         // Check for window functions or complex expressions
         for assignment in assignments {
             if self.expression_is_complex(&assignment.expr) {
-                return true;
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Returns a dependency cycle among the assignment targets of one mutate(),
+    /// as the column path from the first column back to itself.
+    ///
+    /// A column reading its own previous value (`x = x + 1`) is not a cycle.
+    fn find_mutate_cycle(&self, assignments: &[crate::parser::Assignment]) -> Option<Vec<String>> {
+        // Edges from each target to the other targets its expression reads
+        let dependencies: Vec<Vec<usize>> = assignments
+            .iter()
+            .enumerate()
+            .map(|(index, assignment)| {
+                assignments
+                    .iter()
+                    .enumerate()
+                    .filter(|(other, target)| {
+                        *other != index
+                            && target.column != assignment.column
+                            && self.expression_references_columns(
+                                &assignment.expr,
+                                &std::iter::once(target.column.clone()).collect(),
+                            )
+                    })
+                    .map(|(other, _)| other)
+                    .collect()
+            })
+            .collect();
+
+        // Depth-first search; 1 = on the current path, 2 = finished
+        fn visit(
+            node: usize,
+            dependencies: &[Vec<usize>],
+            state: &mut [u8],
+            path: &mut Vec<usize>,
+        ) -> Option<Vec<usize>> {
+            state[node] = 1;
+            path.push(node);
+            for &next in &dependencies[node] {
+                match state[next] {
+                    1 => {
+                        let start = path.iter().position(|&n| n == next).unwrap_or(0);
+                        let mut cycle = path[start..].to_vec();
+                        cycle.push(next);
+                        return Some(cycle);
+                    }
+                    0 => {
+                        if let Some(cycle) = visit(next, dependencies, state, path) {
+                            return Some(cycle);
+                        }
+                    }
+                    _ => {}
+                }
             }
+            path.pop();
+            state[node] = 2;
+            None
         }
 
-        false
+        let mut state = vec![0u8; assignments.len()];
+        (0..assignments.len()).find_map(|node| {
+            if state[node] != 0 {
+                return None;
+            }
+            visit(node, &dependencies, &mut state, &mut Vec::new()).map(|cycle| {
+                cycle
+                    .into_iter()
+                    .map(|index| assignments[index].column.clone())
+                    .collect()
+            })
+        })
     }
 
     /// Processes simple mutate operations by adding columns to SELECT clause.
@@ -108,26 +206,166 @@ impl SqlGenerator {
         assignments: &[crate::parser::Assignment],
         query_parts: &mut QueryParts,
     ) -> GenerationResult<()> {
+        check_unique_output_names(
+            "mutate",
+            assignments
+                .iter()
+                .map(|assignment| assignment.column.as_str()),
+        )?;
+
         // If no columns selected yet, implies all columns (*) are included
         if query_parts.select_columns.is_empty() {
-            query_parts.select_columns.push("*".to_string());
+            query_parts.select_columns.push(Projection::star());
         }
 
         for assignment in assignments {
-            let expr_sql = self.generate_expression_with_window_partition(
-                &assignment.expr,
-                &query_parts.group_by,
-            )?;
+            // Columns computed in the same SELECT cannot be read by name, so
+            // they are replaced with their expressions
+            self.inlined_columns.replace(query_parts.computed_columns());
+            self.window_order.replace(query_parts.order_by.clone());
+            self.window_aggregates.set(true);
+            let expr_sql = self
+                .generate_expression_with_window_partition(&assignment.expr, &query_parts.group_by);
+            self.window_aggregates.set(false);
+            self.window_order.borrow_mut().clear();
+            self.inlined_columns.borrow_mut().clear();
+            let expr_sql = expr_sql?;
             query_parts
                 .mutated_columns
                 .insert(assignment.column.clone(), expr_sql.clone());
-            let column_expr = format!(
-                "{} AS {}",
-                expr_sql,
-                self.dialect.quote_identifier(&assignment.column)
+            // A column that is projected, or that the assignment reads, already
+            // exists and is replaced rather than added. Without `* EXCLUDE` a
+            // column reached through `*` is only replaced for replace_na(),
+            // which fails rather than project the column twice.
+            let reads_itself = self.expression_references_columns(
+                &assignment.expr,
+                &std::iter::once(assignment.column.clone()).collect(),
+            );
+            let projected = query_parts
+                .select_columns
+                .iter()
+                .any(|item| item.name.as_ref() == Some(&assignment.column));
+            let replaces = projected
+                || (reads_itself
+                    && (self.dialect.select_star_exclude(&[]).is_some()
+                        || replaces_missing_values(assignment)));
+            if replaces {
+                self.replace_column("mutate", &assignment.column, expr_sql, query_parts)?;
+            } else {
+                query_parts
+                    .select_columns
+                    .push(self.aliased_projection(expr_sql, &assignment.column));
+            }
+        }
+        Ok(())
+    }
+
+    /// Processes fill() by replacing each column with the nearest non-NULL value
+    /// in the fill direction, using `IGNORE NULLS` window functions.
+    ///
+    /// Rows are ordered by a preceding arrange() and partitioned by group_by().
+    pub(super) fn process_fill_operation(
+        &self,
+        columns: &[String],
+        direction: FillDirection,
+        query_parts: &mut QueryParts,
+    ) -> GenerationResult<()> {
+        if !self.dialect.supports_ignore_nulls() {
+            return Err(GenerationError::UnsupportedOperation {
+                operation: "fill() (requires IGNORE NULLS window functions)".to_string(),
+                dialect: self.dialect.dialect_name().to_string(),
+            });
+        }
+        if columns.is_empty() {
+            return Err(GenerationError::InvalidAst {
+                reason: "fill() requires at least one column".to_string(),
+            });
+        }
+
+        let (function, frame) = match direction {
+            FillDirection::Down => (
+                "LAST_VALUE",
+                "ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW",
+            ),
+            FillDirection::Up => (
+                "FIRST_VALUE",
+                "ROWS BETWEEN CURRENT ROW AND UNBOUNDED FOLLOWING",
+            ),
+        };
+
+        let mut window = Vec::new();
+        if !query_parts.group_by.is_empty() {
+            window.push(format!("PARTITION BY {}", query_parts.group_by));
+        }
+        if query_parts.order_by.is_empty() {
+            self.warn(TranspileWarning::UnorderedWindowFunction {
+                function: "fill".to_string(),
+            });
+        } else {
+            window.push(format!("ORDER BY {}", query_parts.order_by));
+        }
+        window.push(frame.to_string());
+        let over = window.join(" ");
+
+        for column in columns {
+            let expr_sql = format!(
+                "{function}({} IGNORE NULLS) OVER ({over})",
+                self.column_ref(column)
             );
-            query_parts.select_columns.push(column_expr);
+            query_parts
+                .mutated_columns
+                .insert(column.clone(), expr_sql.clone());
+            self.replace_column("fill", column, expr_sql, query_parts)?;
+        }
+        Ok(())
+    }
+
+    /// Projects `expr` as the column `name` in place of its current value.
+    ///
+    /// A column the projection lists is replaced where it stands. A column only
+    /// reached through `*` is excluded from it with the dialect's
+    /// `* EXCLUDE (...)` form; without that form the column must be listed by
+    /// an earlier select(), since the source columns are unknown.
+    pub(super) fn replace_column(
+        &self,
+        operation: &str,
+        name: &str,
+        expr: String,
+        query_parts: &mut QueryParts,
+    ) -> GenerationResult<()> {
+        let projection = self.aliased_projection(expr, name);
+        if let Some(projected) = query_parts
+            .select_columns
+            .iter_mut()
+            .find(|item| item.name.as_deref() == Some(name))
+        {
+            *projected = projection;
+            return Ok(());
+        }
+
+        let has_star = query_parts.select_columns.is_empty()
+            || query_parts.select_columns.iter().any(Projection::is_star);
+        if !has_star {
+            return Err(GenerationError::InvalidColumnReference {
+                column: name.to_string(),
+                table: None,
+            });
+        }
+        if self
+            .dialect
+            .select_star_exclude(&[name.to_string()])
+            .is_none()
+        {
+            return Err(GenerationError::UnsupportedOperation {
+                operation: format!("{operation}() of a column not listed by a preceding select()"),
+                dialect: self.dialect.dialect_name().to_string(),
+            });
         }
+        if query_parts.select_columns.is_empty() {
+            query_parts.select_columns.push(Projection::star());
+        }
+        query_parts.star_excluded.push(name.to_string());
+        query_parts.select_columns.push(projection);
         Ok(())
     }
 
@@ -140,6 +378,7 @@ impl SqlGenerator {
     ) -> bool {
         match expr {
             Expr::Identifier(name) => columns.contains(name),
+            Expr::QualifiedIdentifier { .. } => false,
             Expr::Binary { left, right, .. } => {
                 self.expression_references_columns(left, columns)
                     || self.expression_references_columns(right, columns)
@@ -147,11 +386,135 @@ impl SqlGenerator {
             Expr::Function { args, .. } => args
                 .iter()
                 .any(|arg| self.expression_references_columns(arg, columns)),
-            Expr::NamedArg { value, .. } => self.expression_references_columns(value, columns),
+            Expr::NamedArg { value, .. } | Expr::Not(value) | Expr::Negate(value) => {
+                self.expression_references_columns(value, columns)
+            }
+            Expr::Formula { lhs, rhs } => {
+                self.expression_references_columns(lhs, columns)
+                    || self.expression_references_columns(rhs, columns)
+            }
             Expr::Literal(_) => false,
         }
     }
 
+    /// Returns true when a filter condition reads a mutated column computed by a
+    /// window function, which WHERE cannot evaluate in place.
+    pub(super) fn filter_reads_window_columns(&self, condition: &Expr, parts: &QueryParts) -> bool {
+        let window_columns: std::collections::HashSet<String> = parts
+            .mutated_columns
+            .iter()
+            .filter(|(_, expr_sql)| expr_sql.contains(" OVER ("))
+            .map(|(column, _)| column.clone())
+            .collect();
+        !window_columns.is_empty() && self.expression_references_columns(condition, &window_columns)
+    }
+
+    /// Returns whether a filter() needs window functions: it calls one such as
+    /// `row_number()`, or aggregates per group as in `filter(x > mean(x), .by = dept)`.
+    pub(super) fn filter_needs_window(&self, condition: &Expr, parts: &QueryParts) -> bool {
+        self.expression_is_complex(condition)
+            || (!parts.group_by.is_empty() && self.find_aggregate_call(condition).is_some())
+    }
+
+    /// Processes a filter() that [needs window functions](Self::filter_needs_window).
+    ///
+    /// WHERE cannot evaluate them, so each window function or aggregate is
+    /// computed in a subquery over the group_by() partition, as mutate() would,
+    /// and the outer query filters on it before dropping the helper columns again.
+    pub(super) fn process_window_filter(
+        &self,
+        condition: &Expr,
+        source: &Option<String>,
+        query_parts: &mut QueryParts,
+        aggregation_group_by: &mut Option<String>,
+    ) -> GenerationResult<()> {
+        if self.filter_reads_window_columns(condition, query_parts) {
+            self.wrap_in_subquery(source, query_parts, aggregation_group_by)?;
+        }
+        let kept_columns = query_parts.named_columns();
+
+        let mut helpers = Vec::new();
+        let condition = self.extract_window_calls(condition, &mut helpers);
+        let helper_columns = helpers.iter().map(|helper| helper.column.clone()).collect();
+        self.process_simple_mutate(&helpers, query_parts)?;
+        self.wrap_in_subquery(source, query_parts, aggregation_group_by)?;
+        query_parts.push_where(self.generate_predicate_with_window_partition(&condition, "")?);
+        self.drop_helper_columns("filter", kept_columns, helper_columns, query_parts);
+        Ok(())
+    }
+
+    /// Replaces each window function or aggregate call in `expr` with a helper
+    /// column, adding an assignment that computes it to `helpers`.
+    fn extract_window_calls(
+        &self,
+        expr: &Expr,
+        helpers: &mut Vec<crate::parser::Assignment>,
+    ) -> Expr {
+        match expr {
+            Expr::Function { name, .. }
+                if self.expression_is_complex(expr)
+                    || self.dialect.translate_aggregate_function(name).is_some() =>
+            {
+                let column = format!("{FILTER_WINDOW_COLUMN}_{}", helpers.len() + 1);
+                helpers.push(crate::parser::Assignment {
+                    column: column.clone(),
+                    expr: expr.clone(),
+                });
+                Expr::Identifier(column)
+            }
+            Expr::Function { name, args } => Expr::Function {
+                name: name.clone(),
+                args: args
+                    .iter()
+                    .map(|arg| self.extract_window_calls(arg, helpers))
+                    .collect(),
+            },
+            Expr::Binary {
+                left,
+                operator,
+                right,
+            } => Expr::Binary {
+                left: Box::new(self.extract_window_calls(left, helpers)),
+                operator: operator.clone(),
+                right: Box::new(self.extract_window_calls(right, helpers)),
+            },
+            Expr::NamedArg { name, value } => Expr::NamedArg {
+                name: name.clone(),
+                value: Box::new(self.extract_window_calls(value, helpers)),
+            },
+            Expr::Formula { lhs, rhs } => Expr::Formula {
+                lhs: Box::new(self.extract_window_calls(lhs, helpers)),
+                rhs: Box::new(self.extract_window_calls(rhs, helpers)),
+            },
+            Expr::Not(value) => Expr::Not(Box::new(self.extract_window_calls(value, helpers))),
+            Expr::Negate(value) => {
+                Expr::Negate(Box::new(self.extract_window_calls(value, helpers)))
+            }
+            Expr::Identifier(_) | Expr::QualifiedIdentifier { .. } | Expr::Literal(_) => {
+                expr.clone()
+            }
+        }
+    }
+
+    /// Returns whether a mutate() reads a window function column projected
+    /// earlier, which cannot be inlined into another window or aggregate.
+    pub(super) fn mutate_reads_window_columns(
+        &self,
+        assignments: &[crate::parser::Assignment],
+        parts: &QueryParts,
+    ) -> bool {
+        let window_columns: std::collections::HashSet<String> = parts
+            .computed_columns()
+            .into_iter()
+            .filter(|(_, expr_sql)| expr_sql.contains(" OVER ("))
+            .map(|(column, _)| column)
+            .collect();
+        !window_columns.is_empty()
+            && assignments.iter().any(|assignment| {
+                self.expression_references_columns(&assignment.expr, &window_columns)
+            })
+    }
+
     /// Checks if expression is complex and might need special handling.
     #[allow(clippy::only_used_in_recursion)]
     pub(super) fn expression_is_complex(&self, expr: &Expr) -> bool {
@@ -173,11 +536,18 @@ impl SqlGenerator {
             Expr::Binary { left, right, .. } => {
                 self.expression_is_complex(left) || self.expression_is_complex(right)
             }
-            Expr::NamedArg { value, .. } => self.expression_is_complex(value),
+            Expr::NamedArg { value, .. } | Expr::Not(value) | Expr::Negate(value) => {
+                self.expression_is_complex(value)
+            }
+            Expr::Formula { lhs, rhs } => {
+                self.expression_is_complex(lhs) || self.expression_is_complex(rhs)
+            }
             _ => false,
         }
     }
+}
 
+impl SqlGenerator {
     /// Generates a subquery for complex mutate operations.
     ///
     /// # Arguments
@@ -193,25 +563,27 @@ impl SqlGenerator {
         base_query: &str,
         assignments: &[crate::parser::Assignment],
     ) -> GenerationResult<String> {
+        let context = self.context(None);
         let mut outer_select = Vec::new();
 
         // Add all existing columns (SELECT *)
-        outer_select.push("*".to_string());
+        outer_select.push(self.dialect.star_projection(SUBQUERY_ALIAS));
 
         // Add mutated columns
         for assignment in assignments {
             let column_expr = format!(
                 "{} AS {}",
-                self.generate_expression(&assignment.expr)?,
+                context.generate_expression(&assignment.expr)?,
                 self.dialect.quote_identifier(&assignment.column)
             );
             outer_select.push(column_expr);
         }
 
         let query = format!(
-            "SELECT {}\nFROM (\n{}\n) AS subquery",
+            "SELECT {}\nFROM {}",
             outer_select.join(", "),
-            base_query
+            self.dialect
+                .table_alias(&format!("(\n{base_query}\n)"), SUBQUERY_ALIAS)
         );
 
         Ok(query)
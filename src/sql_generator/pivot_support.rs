@@ -0,0 +1,51 @@
+// pivot_wider() helpers.
+
+use super::assemble::group_projections;
+use super::{check_unique_output_names, QueryParts};
+use super::{GenContext, GenerationResult};
+use crate::error::GenerationError;
+
+impl GenContext<'_> {
+    /// Processes pivot_wider() with explicit output names.
+    ///
+    /// Each name becomes `SUM(CASE WHEN names_from = 'name' THEN values_from END)`
+    /// aliased to the name, computed per group. The grouping columns play the
+    /// part of tidyr's id columns, so a preceding group_by() is required.
+    pub(super) fn process_pivot_wider(
+        &self,
+        names_from: &str,
+        values_from: &str,
+        names: &[String],
+        query_parts: &mut QueryParts,
+    ) -> GenerationResult<()> {
+        if query_parts.group_by.is_empty() {
+            return Err(GenerationError::UnsupportedOperation {
+                operation: "pivot_wider() without a preceding group_by() naming the id columns"
+                    .to_string(),
+                dialect: self.dialect.dialect_name().to_string(),
+            });
+        }
+        check_unique_output_names("pivot_wider", names.iter().map(String::as_str))?;
+        self.check_names_clear_of_groups("pivot_wider", names, &query_parts.group_by)?;
+
+        let key = self.column_ref(names_from);
+        let value = self.column_ref(values_from);
+        let mut select_columns = group_projections(&query_parts.group_by);
+        for name in names {
+            let case = format!(
+                "CASE WHEN {key} = {} THEN {value} END",
+                self.dialect.quote_string(name)
+            );
+            let aggregate = self.dialect.aggregate_call("sum", &case).ok_or_else(|| {
+                GenerationError::UnsupportedAggregateFunction {
+                    function: "sum".to_string(),
+                    dialect: self.dialect.dialect_name().to_string(),
+                    suggestion: None,
+                }
+            })?;
+            select_columns.push(self.aliased_projection(aggregate, name));
+        }
+        query_parts.select_columns = select_columns;
+        Ok(())
+    }
+}
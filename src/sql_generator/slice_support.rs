@@ -0,0 +1,114 @@
+// Slice-related helpers.
+
+use super::{GenContext, GenerationResult};
+use super::{Projection, QueryParts};
+use crate::error::TranspileWarning;
+use crate::parser::SlicePosition;
+
+/// Helper column numbering rows inside each slice partition.
+const ROW_NUMBER_COLUMN: &str = "dplyr_row_number";
+/// Helper column holding the partition size, for slice_tail().
+const GROUP_SIZE_COLUMN: &str = "dplyr_group_size";
+
+impl GenContext<'_> {
+    /// Processes slice_head() / slice_tail() / slice().
+    ///
+    /// An ungrouped slice_head() is a plain LIMIT, and an ungrouped slice(a:b) a
+    /// LIMIT with an OFFSET; both narrow any earlier limit. Grouped slices and slice_tail()
+    /// number rows with `ROW_NUMBER()` in a subquery and filter on that number.
+    /// The helper columns are dropped by listing the columns a select() named,
+    /// or with `* EXCLUDE` where the dialect supports it; otherwise they stay
+    /// in the result with a warning.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - Pipeline source table, read by the subquery
+    /// * `aggregation_group_by` - GROUP BY of an earlier summarise(), which moves into the subquery
+    pub(super) fn process_slice_operation(
+        &self,
+        position: SlicePosition,
+        n: usize,
+        source: &Option<String>,
+        query_parts: &mut QueryParts,
+        aggregation_group_by: &mut Option<String>,
+    ) -> GenerationResult<()> {
+        if query_parts.group_by.is_empty() {
+            let skip = match position {
+                SlicePosition::Head => Some(0),
+                SlicePosition::Rows { start } => Some(start - 1),
+                SlicePosition::Tail => None,
+            };
+            if let Some(skip) = skip {
+                let available = query_parts.limit.map(|limit| limit.saturating_sub(skip));
+                query_parts.limit = Some(available.map_or(n, |available| available.min(n)));
+                if skip > 0 {
+                    query_parts.offset = Some(query_parts.offset.unwrap_or(0) + skip);
+                }
+                return Ok(());
+            }
+        }
+
+        let verb = match position {
+            SlicePosition::Head => "slice_head",
+            SlicePosition::Tail => "slice_tail",
+            SlicePosition::Rows { .. } => "slice",
+        };
+        if query_parts.is_row_limited() {
+            // Number the rows an earlier limit kept, not the whole table
+            self.wrap_in_subquery(source, query_parts, aggregation_group_by)?;
+        }
+        let kept_columns = query_parts.named_columns();
+
+        let partition_clause = if query_parts.group_by.is_empty() {
+            String::new()
+        } else {
+            format!("PARTITION BY {}", query_parts.group_by)
+        };
+        let order_by = &query_parts.order_by;
+        let window = if order_by.is_empty() {
+            self.warn(TranspileWarning::UnorderedWindowFunction {
+                function: verb.to_string(),
+            });
+            match self.dialect.default_window_order() {
+                Some(default_order) => format!("{partition_clause} ORDER BY {default_order}")
+                    .trim_start()
+                    .to_string(),
+                None => partition_clause.clone(),
+            }
+        } else {
+            format!("{partition_clause} ORDER BY {order_by}")
+                .trim_start()
+                .to_string()
+        };
+
+        if query_parts.select_columns.is_empty() {
+            query_parts.select_columns.push(Projection::star());
+        }
+        let row_number = self.dialect.quote_identifier(ROW_NUMBER_COLUMN);
+        query_parts.select_columns.push(
+            self.aliased_projection(format!("ROW_NUMBER() OVER ({window})"), ROW_NUMBER_COLUMN),
+        );
+
+        let mut helper_columns = vec![ROW_NUMBER_COLUMN.to_string()];
+        let predicate = match position {
+            SlicePosition::Head => format!("{row_number} <= {n}"),
+            SlicePosition::Rows { start } => {
+                format!("{row_number} BETWEEN {start} AND {}", start + n - 1)
+            }
+            SlicePosition::Tail => {
+                let group_size = self.dialect.quote_identifier(GROUP_SIZE_COLUMN);
+                query_parts.select_columns.push(self.aliased_projection(
+                    format!("COUNT(*) OVER ({partition_clause})"),
+                    GROUP_SIZE_COLUMN,
+                ));
+                helper_columns.push(GROUP_SIZE_COLUMN.to_string());
+                format!("{row_number} > {group_size} - {n}")
+            }
+        };
+
+        self.wrap_in_subquery(source, query_parts, aggregation_group_by)?;
+        self.drop_helper_columns(verb, kept_columns, helper_columns, query_parts);
+        query_parts.push_where(predicate);
+        Ok(())
+    }
+}
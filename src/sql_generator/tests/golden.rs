@@ -0,0 +1,78 @@
+//! Golden SQL output per dialect.
+//!
+//! One line per case: dialect, dplyr input and the expected SQL. Whitespace is
+//! collapsed before comparing, so the pretty-printed line breaks don't matter.
+
+use super::*;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+
+#[rustfmt::skip]
+const GOLDEN_CASES: &[(&str, &str, &str)] = &[
+    ("postgresql", r#"data %>% select(id, name)"#, r#"SELECT "id", "name" FROM "data""#),
+    ("mysql", r#"data %>% filter(age > 18 & status == "active")"#, r#"SELECT * FROM `data` WHERE ((`age` > 18) AND (`status` = 'active'))"#),
+    ("sqlite", r#"data %>% mutate(total = price * qty)"#, r#"SELECT *, ("price" * "qty") AS "total" FROM "data""#),
+    ("duckdb", r#"data %>% arrange(desc(date), id)"#, r#"SELECT * FROM "data" ORDER BY "date" DESC, "id" ASC"#),
+    ("postgresql", r#"data %>% group_by(dept) %>% summarise(avg = mean(salary), n = n())"#, r#"SELECT "dept", AVG("salary") AS "avg", COUNT(*) AS "n" FROM "data" GROUP BY "dept""#),
+    ("oracle", r#"data %>% select(id) %>% head(5)"#, r#"SELECT "ID" FROM "DATA" FETCH FIRST 5 ROWS ONLY"#),
+    ("spark", r#"data %>% filter(!is.na(x))"#, r#"SELECT * FROM `data` WHERE (NOT (`x` IS NULL))"#),
+    ("postgresql", r#"orders %>% inner_join(customers, by = "customer_id")"#, r#"SELECT * FROM "orders" INNER JOIN "customers" ON "orders"."customer_id" = "customers"."customer_id""#),
+    ("duckdb", r#"orders %>% left_join(customers, by = "id") %>% select(id, name)"#, r#"SELECT "id", "name" FROM "orders" LEFT JOIN "customers" ON "orders"."id" = "customers"."id""#),
+    ("duckdb", r#"data %>% rename(new_name = old_name)"#, r#"SELECT * EXCLUDE ("old_name"), "old_name" AS "new_name" FROM "data""#),
+    ("duckdb", r#"data %>% distinct(dept)"#, r#"SELECT DISTINCT "dept" FROM "data""#),
+    ("postgresql", r#"data %>% count(dept, sort = TRUE)"#, r#"SELECT "dept", COUNT(*) AS "n" FROM "data" GROUP BY "dept" ORDER BY "n" DESC"#),
+    ("sqlite", r#"data %>% transmute(total = price * qty)"#, r#"SELECT ("price" * "qty") AS "total" FROM "data""#),
+    ("duckdb", r#"data %>% summarise(p90 = quantile(amount, 0.9))"#, r#"SELECT QUANTILE_CONT("amount", 0.9) AS "p90" FROM "data""#),
+    ("oracle", r#"data %>% summarise(med = quantile(x, 0.5))"#, r#"SELECT PERCENTILE_CONT(0.5) WITHIN GROUP (ORDER BY "X") AS "MED" FROM "DATA""#),
+    ("spark", r#"data %>% summarise(med = quantile(x, 0.5))"#, r#"SELECT PERCENTILE(`x`, 0.5) AS `med` FROM `data`"#),
+    ("postgresql", r#"data %>% group_by(rollup(region, year)) %>% summarise(total = sum(sales))"#, r#"SELECT "region", "year", SUM("sales") AS "total" FROM "data" GROUP BY ROLLUP("region", "year")"#),
+    ("mysql", r#"data %>% group_by(rollup(a, b)) %>% summarise(n = n())"#, r#"SELECT `a`, `b`, COUNT(*) AS `n` FROM `data` GROUP BY `a`, `b` WITH ROLLUP"#),
+    ("duckdb", r#"data %>% summarise(avg = mean(salary), .by = dept)"#, r#"SELECT "dept", AVG("salary") AS "avg" FROM "data" GROUP BY "dept""#),
+    ("postgresql", r#"data %>% mutate(name = raw %||% "unknown")"#, r#"SELECT *, COALESCE("raw", 'unknown') AS "name" FROM "data""#),
+    ("duckdb", r#"data %>% group_by(g) %>% mutate(m = mean(y))"#, r#"SELECT *, AVG("y") OVER (PARTITION BY "g") AS "m" FROM "data""#),
+    ("sqlite", r#"data %>% slice_head(n = 3)"#, r#"SELECT * FROM "data" LIMIT 3"#),
+    ("postgresql", r#"data %>% filter(between(x, 1, 10))"#, r#"SELECT * FROM "data" WHERE ("x" BETWEEN 1 AND 10)"#),
+    ("duckdb", r#"data %>% mutate(s = if_else(x > 0, "pos", "neg"))"#, r#"SELECT *, CASE WHEN ("x" > 0) THEN 'pos' ELSE 'neg' END AS "s" FROM "data""#),
+    ("duckdb", r#"a %>% union(b)"#, r#"SELECT * FROM "a" UNION SELECT * FROM "b""#),
+    ("postgresql", r#"data %>% arrange(desc(score)) %>% head(10)"#, r#"SELECT * FROM "data" ORDER BY "score" DESC LIMIT 10"#),
+    ("mysql", r#"data %>% mutate(full = paste0(first, last))"#, r#"SELECT *, CONCAT(`first`, `last`) AS `full` FROM `data`"#),
+    ("spark", r#"data %>% group_by(dept) %>% summarise(total = sum(distinct amount))"#, r#"SELECT `dept`, SUM(DISTINCT `amount`) AS `total` FROM `data` GROUP BY `dept`"#),
+    ("sqlite", r#"orders %>% semi_join(customers, by = "id")"#, r#"SELECT * FROM "orders" WHERE EXISTS (SELECT 1 FROM "customers" WHERE "orders"."id" = "customers"."id")"#),
+    ("oracle", r#"data %>% filter(x > 1) %>% arrange(x)"#, r#"SELECT * FROM "DATA" WHERE ("X" > 1) ORDER BY "X" ASC"#),
+];
+
+fn dialect_named(name: &str) -> Box<dyn SqlDialect> {
+    match name {
+        "postgresql" => Box::new(PostgreSqlDialect::new()),
+        "mysql" => Box::new(MySqlDialect::new()),
+        "sqlite" => Box::new(SqliteDialect::new()),
+        "duckdb" => Box::new(DuckDbDialect::new()),
+        "oracle" => Box::new(OracleDialect::new()),
+        "spark" => Box::new(SparkSqlDialect::new()),
+        _ => panic!("unknown dialect in golden case: {name}"),
+    }
+}
+
+fn collapse_whitespace(sql: &str) -> String {
+    sql.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[test]
+fn test_golden_sql_per_dialect() {
+    let mut mismatches = Vec::new();
+    for (dialect, code, expected) in GOLDEN_CASES {
+        let ast = Parser::new(Lexer::new((*code).to_string()))
+            .and_then(|mut parser| parser.parse())
+            .unwrap_or_else(|err| panic!("{dialect}: {code}: {err}"));
+        let actual = SqlGenerator::new(dialect_named(dialect))
+            .generate(&ast)
+            .map(|sql| collapse_whitespace(&sql))
+            .unwrap_or_else(|err| format!("error: {err}"));
+        if actual != collapse_whitespace(expected) {
+            mismatches.push(format!(
+                "{dialect}: {code}\n  expected: {expected}\n  actual:   {actual}"
+            ));
+        }
+    }
+    assert!(mismatches.is_empty(), "{}", mismatches.join("\n"));
+}
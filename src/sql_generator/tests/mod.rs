@@ -4,6 +4,8 @@ use crate::parser::{
     OrderExpr, SourceLocation,
 };
 
+mod golden;
+
 // Helper function to normalize SQL for comparison
 fn normalize_sql(sql: &str) -> String {
     sql.split_whitespace()
@@ -22,6 +24,7 @@ fn create_test_select_operation(columns: Vec<&str>) -> DplyrOperation {
                 alias: None,
             })
             .collect(),
+        excluded: Vec::new(),
         location: SourceLocation::unknown(),
     }
 }
@@ -51,6 +54,85 @@ mod dialect_tests {
         assert_eq!(dialect.quote_identifier("bad\"name"), "\"bad\"\"name\"");
     }
 
+    #[test]
+    fn test_identifier_validation_rejects_malicious_names() {
+        let dialects: Vec<Box<dyn SqlDialect>> = vec![
+            Box::new(PostgreSqlDialect::new()),
+            Box::new(MySqlDialect::new()),
+            Box::new(SqliteDialect::new()),
+            Box::new(DuckDbDialect::new()),
+            Box::new(OracleDialect::new()),
+            Box::new(SparkSqlDialect::new()),
+        ];
+        for dialect in &dialects {
+            let name = dialect.dialect_name();
+            for valid in ["amount", "order total", "Ünïcode_col", "select"] {
+                assert!(dialect.is_valid_identifier(valid), "{name}: {valid}");
+                assert_eq!(
+                    dialect.sanitize_identifier(valid).unwrap(),
+                    dialect.quote_identifier(valid),
+                    "{name}"
+                );
+            }
+
+            let quote = dialect.identifier_quote();
+            let injection = format!("x{quote}; DROP TABLE users; --");
+            for invalid in [
+                "",
+                "col\u{0}",
+                "line\nbreak",
+                "tab\tbed",
+                injection.as_str(),
+            ] {
+                assert!(!dialect.is_valid_identifier(invalid), "{name}: {invalid:?}");
+                assert!(
+                    matches!(
+                        dialect.sanitize_identifier(invalid),
+                        Err(GenerationError::InvalidIdentifier { .. })
+                    ),
+                    "{name}: {invalid:?}"
+                );
+            }
+        }
+
+        // Only the dialect's own quote character is dangerous
+        assert!(MySqlDialect::new().is_valid_identifier("x\"y"));
+        assert!(!MySqlDialect::new().is_valid_identifier("x`y"));
+        assert!(PostgreSqlDialect::new().is_valid_identifier("x`y"));
+    }
+
+    #[test]
+    fn test_supported_dialect_names_are_the_builtin_dialects() {
+        let dialects: Vec<Box<dyn SqlDialect>> = vec![
+            Box::new(PostgreSqlDialect::new()),
+            Box::new(MySqlDialect::new()),
+            Box::new(SqliteDialect::new()),
+            Box::new(DuckDbDialect::new()),
+            Box::new(OracleDialect::new()),
+            Box::new(SparkSqlDialect::new()),
+        ];
+        let names: Vec<&str> = dialects.iter().map(|d| d.dialect_name()).collect();
+        assert_eq!(supported_dialect_names(), names.as_slice());
+    }
+
+    #[test]
+    fn test_dialect_from_name_resolves_names_and_aliases() {
+        for name in supported_dialect_names() {
+            let dialect = dialect_from_name(name).expect("canonical name resolves");
+            assert_eq!(dialect.dialect_name(), *name);
+        }
+        for (alias, name) in [
+            ("postgres", "postgresql"),
+            ("pg", "postgresql"),
+            ("duck", "duckdb"),
+            (" DuckDB ", "duckdb"),
+        ] {
+            let dialect = dialect_from_name(alias).expect("alias resolves");
+            assert_eq!(dialect.dialect_name(), name);
+        }
+        assert!(dialect_from_name("mssql").is_none());
+    }
+
     #[test]
     fn test_postgresql_dialect_string_quoting() {
         let dialect = PostgreSqlDialect::new();
@@ -90,6 +172,31 @@ mod dialect_tests {
         assert_eq!(dialect.quote_identifier("bad`name"), "`bad``name`");
     }
 
+    #[test]
+    fn test_string_quoting_escapes_backslashes_per_dialect() {
+        let cases: Vec<(Box<dyn SqlDialect>, &str)> = vec![
+            (Box::new(PostgreSqlDialect::new()), r"'C:\dir\n'"),
+            (Box::new(SqliteDialect::new()), r"'C:\dir\n'"),
+            (Box::new(DuckDbDialect::new()), r"'C:\dir\n'"),
+            (Box::new(OracleDialect::new()), r"'C:\dir\n'"),
+            (Box::new(MySqlDialect::new()), r"'C:\\dir\\n'"),
+            (Box::new(SparkSqlDialect::new()), r"'C:\\dir\\n'"),
+        ];
+        for (dialect, expected) in cases {
+            assert_eq!(
+                dialect.quote_string(r"C:\dir\n"),
+                expected,
+                "{}",
+                dialect.dialect_name()
+            );
+        }
+
+        // A backslash before a quote must not turn the doubled quote into an escape
+        let mysql = MySqlDialect::new();
+        assert_eq!(mysql.quote_string(r"x\' OR 1=1 -- "), r"'x\\'' OR 1=1 -- '");
+        assert_eq!(mysql.quote_string("it's"), "'it''s'");
+    }
+
     #[test]
     fn test_mysql_dialect_string_concat() {
         let dialect = MySqlDialect::new();
@@ -141,6 +248,188 @@ mod dialect_tests {
         assert!(!sqlite_dialect.is_case_sensitive());
         assert!(!duckdb_dialect.is_case_sensitive());
     }
+
+    #[test]
+    fn test_identifier_case_preserve_keeps_quoted_identifiers() {
+        let dialect = PostgreSqlDialect::new().with_identifier_case(IdentifierCase::Preserve);
+        assert_eq!(dialect.identifier_case(), IdentifierCase::Preserve);
+        assert_eq!(dialect.quote_identifier("CamelCase"), "\"CamelCase\"");
+        assert_eq!(
+            PostgreSqlDialect::new().quote_identifier("CamelCase"),
+            "\"CamelCase\""
+        );
+    }
+
+    #[test]
+    fn test_identifier_case_lower_emits_bare_folded_identifiers() {
+        let dialect = PostgreSqlDialect::new().with_identifier_case(IdentifierCase::Lower);
+        assert_eq!(dialect.quote_identifier("CamelCase"), "camelcase");
+        assert_eq!(dialect.quote_identifier("order_id"), "order_id");
+        // Reserved words and non-simple names stay quoted after folding.
+        assert_eq!(dialect.quote_identifier("User"), "\"user\"");
+        // Including words only some engines reserve.
+        assert_eq!(dialect.quote_identifier("Date"), "\"date\"");
+        assert_eq!(dialect.quote_identifier("level"), "\"level\"");
+        assert_eq!(dialect.quote_identifier("Qualify"), "\"qualify\"");
+        assert_eq!(dialect.quote_identifier("First Name"), "\"first name\"");
+        assert_eq!(dialect.quote_identifier("Bad\"Name"), "\"bad\"\"name\"");
+    }
+
+    #[test]
+    fn test_identifier_case_upper_emits_bare_folded_identifiers() {
+        let dialect = MySqlDialect::new().with_identifier_case(IdentifierCase::Upper);
+        assert_eq!(dialect.quote_identifier("amount"), "AMOUNT");
+        assert_eq!(dialect.quote_identifier("select"), "`SELECT`");
+        assert_eq!(dialect.quote_identifier("comment"), "`COMMENT`");
+        assert_eq!(dialect.quote_identifier("abort"), "`ABORT`");
+        assert_eq!(dialect.quote_identifier("zerofill"), "`ZEROFILL`");
+        assert_eq!(dialect.quote_identifier("2nd_col"), "`2ND_COL`");
+    }
+
+    #[test]
+    fn test_identifier_case_applies_to_generated_sql() {
+        let generator = SqlGenerator::new(Box::new(
+            DuckDbDialect::new().with_identifier_case(IdentifierCase::Lower),
+        ));
+        let ast = DplyrNode::Pipeline {
+            source: Some("Orders".to_string()),
+            source_function: None,
+            target: None,
+            operations: vec![
+                create_test_select_operation(vec!["Region", "Amount"]),
+                create_test_filter_operation("Amount", 10.0),
+            ],
+            location: SourceLocation::unknown(),
+        };
+
+        let sql = generator.generate(&ast).expect("generation should succeed");
+        assert!(sql.contains("SELECT region, amount"), "{sql}");
+        assert!(sql.contains("FROM orders"), "{sql}");
+        assert!(sql.contains("amount > 10"), "{sql}");
+    }
+
+    #[test]
+    fn test_spark_dialect_quoting_and_concat() {
+        let dialect = SparkSqlDialect::new();
+        assert_eq!(dialect.dialect_name(), "spark");
+        assert_eq!(dialect.quote_identifier("order"), "`order`");
+        assert_eq!(dialect.quote_identifier("a`b"), "`a``b`");
+        assert_eq!(dialect.quote_string("it's"), "'it\\'s'");
+        assert_eq!(dialect.quote_string("a\\b"), "'a\\\\b'");
+        assert_eq!(dialect.string_concat("`a`", "`b`"), "concat(`a`, `b`)");
+        assert_eq!(dialect.limit_clause(7), "LIMIT 7");
+    }
+
+    #[test]
+    fn test_spark_dialect_median_uses_percentile_approx() {
+        let dialect = SparkSqlDialect::new();
+        assert!(dialect.supports_aggregate("median"));
+        assert_eq!(
+            dialect.aggregate_call("median", "`price`").unwrap(),
+            "PERCENTILE_APPROX(`price`, 0.5)"
+        );
+        assert_eq!(
+            dialect.aggregate_call("mean", "`price`").unwrap(),
+            "AVG(`price`)"
+        );
+        assert_eq!(
+            PostgreSqlDialect::new().aggregate_call("median", "\"price\""),
+            None
+        );
+    }
+
+    #[test]
+    fn test_oracle_dialect_limit_clause() {
+        let dialect = OracleDialect::new();
+        assert_eq!(dialect.dialect_name(), "oracle");
+        assert_eq!(dialect.limit_clause(10), "FETCH FIRST 10 ROWS ONLY");
+        assert_eq!(
+            dialect.limit_with_offset_clause(10, 0),
+            "FETCH FIRST 10 ROWS ONLY"
+        );
+        assert_eq!(
+            dialect.limit_with_offset_clause(3, 2),
+            "OFFSET 2 ROWS FETCH NEXT 3 ROWS ONLY"
+        );
+        assert_eq!(
+            PostgreSqlDialect::new().limit_with_offset_clause(3, 2),
+            "LIMIT 3 OFFSET 2"
+        );
+    }
+
+    #[test]
+    fn test_oracle_dialect_maps_two_argument_coalesce_to_nvl() {
+        let dialect = OracleDialect::new();
+        let args = vec!["A".to_string(), "0".to_string()];
+        assert_eq!(
+            dialect.translate_function("coalesce", &args).unwrap(),
+            "NVL(A, 0)"
+        );
+        assert_eq!(
+            dialect.translate_function("replace_na", &args).unwrap(),
+            "NVL(A, 0)"
+        );
+
+        let args = vec!["A".to_string(), "B".to_string(), "0".to_string()];
+        assert_eq!(
+            dialect.translate_function("coalesce", &args).unwrap(),
+            "COALESCE(A, B, 0)"
+        );
+    }
+
+    #[test]
+    fn test_oracle_dialect_uppercases_identifiers_and_has_no_booleans() {
+        let dialect = OracleDialect::new();
+        assert_eq!(dialect.identifier_case(), IdentifierCase::Upper);
+        // Every identifier is quoted, reserved or not
+        assert_eq!(dialect.quote_identifier("amount"), "\"AMOUNT\"");
+        assert_eq!(dialect.quote_identifier("date"), "\"DATE\"");
+        assert_eq!(dialect.quote_identifier("order"), "\"ORDER\"");
+        assert_eq!(dialect.string_concat("'a'", "'b'"), "'a' || 'b'");
+        assert_eq!(dialect.boolean_literal(true), "1");
+        assert_eq!(dialect.boolean_literal(false), "0");
+        assert_eq!(PostgreSqlDialect::new().boolean_literal(true), "TRUE");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_dialect_config_json_round_trip() {
+        let config = DialectConfig {
+            identifier_quote: '`',
+            string_quote: '\'',
+            supports_limit: true,
+            supports_offset: false,
+            case_sensitive: true,
+        };
+        let json = config.to_json().unwrap();
+        assert!(json.contains("\"identifier_quote\": \"`\""), "{json}");
+        assert_eq!(DialectConfig::from_json(&json).unwrap(), config);
+
+        assert!(DialectConfig::from_json(r#"{"identifier_quote": "`"}"#).is_err());
+    }
+
+    #[test]
+    fn test_ranking_windows_get_default_order_where_required() {
+        let oracle = OracleDialect::new();
+        assert_eq!(oracle.default_window_order(), Some("NULL"));
+        assert_eq!(
+            oracle.translate_function("row_number", &[]).unwrap(),
+            "ROW_NUMBER() OVER (ORDER BY NULL)"
+        );
+        assert_eq!(
+            oracle
+                .translate_function_with_window_partition("ntile", &["4".to_string()], "G")
+                .unwrap(),
+            "NTILE(4) OVER (PARTITION BY G ORDER BY NULL)"
+        );
+
+        let duckdb = DuckDbDialect::new();
+        assert_eq!(duckdb.default_window_order(), None);
+        assert_eq!(
+            duckdb.translate_function("row_number", &[]).unwrap(),
+            "ROW_NUMBER() OVER ()"
+        );
+    }
 }
 
 // ===== SQL Clause Generation Tests =====
@@ -148,6 +437,34 @@ mod dialect_tests {
 mod clause_generation_tests {
     use super::*;
 
+    #[test]
+    fn test_where_parenthesizes_first_clause_once_another_is_added() {
+        let mut parts = QueryParts::new();
+        parts.push_where("\"a\" OR \"b\"".to_string());
+        assert_eq!(parts.where_clauses, ["\"a\" OR \"b\""]);
+
+        parts.push_where("\"c\"".to_string());
+        parts.push_where("\"d\" OR \"e\"".to_string());
+        assert_eq!(
+            parts.where_clauses,
+            ["(\"a\" OR \"b\")", "AND (\"c\")", "AND (\"d\" OR \"e\")"]
+        );
+
+        // Already enclosed: no second pair of parentheses
+        let mut parts = QueryParts::new();
+        parts.push_where("(\"a\" OR \"b\")".to_string());
+        parts.push_where("\"c\"".to_string());
+        assert_eq!(parts.where_clauses[0], "(\"a\" OR \"b\")");
+
+        // `(a) OR (b)` and a quoted `)` are not enclosed by one pair
+        for first in ["(\"a\") OR (\"b\")", "(\"a\" = ')') OR \"b\""] {
+            let mut parts = QueryParts::new();
+            parts.push_where(first.to_string());
+            parts.push_where("\"c\"".to_string());
+            assert_eq!(parts.where_clauses[0], format!("({first})"));
+        }
+    }
+
     #[test]
     fn test_select_clause_generation() {
         let generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
@@ -165,6 +482,7 @@ mod clause_generation_tests {
 
         let parts = QueryParts::new();
         let result = generator
+            .context(None)
             .generate_select_columns_with_mutations(&columns, &parts)
             .unwrap();
         assert_eq!(result.len(), 2);
@@ -182,7 +500,10 @@ mod clause_generation_tests {
             right: Box::new(Expr::Literal(LiteralValue::Number(18.0))),
         };
 
-        let result = generator.generate_expression(&condition).unwrap();
+        let result = generator
+            .context(None)
+            .generate_expression(&condition)
+            .unwrap();
         assert_eq!(result, "(\"age\" >= 18)");
     }
 
@@ -194,14 +515,19 @@ mod clause_generation_tests {
             OrderExpr {
                 column: "name".to_string(),
                 direction: OrderDirection::Asc,
+                nulls: None,
             },
             OrderExpr {
                 column: "age".to_string(),
                 direction: OrderDirection::Desc,
+                nulls: None,
             },
         ];
 
-        let result = generator.generate_order_by(&columns).unwrap();
+        let result = generator
+            .context(None)
+            .generate_order_by(&columns, &[], &HashMap::new())
+            .unwrap();
         assert_eq!(result, "\"name\" ASC, \"age\" DESC");
     }
 
@@ -214,20 +540,69 @@ mod clause_generation_tests {
                 function: "mean".to_string(),
                 column: "salary".to_string(),
                 alias: Some("avg_salary".to_string()),
+                distinct: false,
+                probability: None,
             },
             Aggregation {
                 function: "n".to_string(),
                 column: "".to_string(),
                 alias: Some("count".to_string()),
+                distinct: false,
+                probability: None,
             },
         ];
 
-        let result = generator.generate_aggregations(&aggregations).unwrap();
+        let result = generator
+            .context(None)
+            .generate_aggregations(&aggregations)
+            .unwrap();
         assert_eq!(result.len(), 2);
         assert_eq!(result[0], "AVG(\"salary\") AS \"avg_salary\"");
         assert_eq!(result[1], "COUNT(*) AS \"count\"");
     }
 
+    #[test]
+    fn test_count_column_differs_from_n() {
+        let generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
+
+        let aggregations = vec![
+            Aggregation {
+                function: "count".to_string(),
+                column: "status".to_string(),
+                alias: Some("c".to_string()),
+                distinct: false,
+                probability: None,
+            },
+            Aggregation {
+                function: "n".to_string(),
+                column: "".to_string(),
+                alias: Some("total".to_string()),
+                distinct: false,
+                probability: None,
+            },
+            Aggregation {
+                function: "count".to_string(),
+                column: "".to_string(),
+                alias: Some("rows".to_string()),
+                distinct: false,
+                probability: None,
+            },
+        ];
+
+        let result = generator
+            .context(None)
+            .generate_aggregations(&aggregations)
+            .unwrap();
+        assert_eq!(
+            result,
+            vec![
+                "COUNT(\"status\") AS \"c\"",
+                "COUNT(*) AS \"total\"",
+                "COUNT(*) AS \"rows\"",
+            ]
+        );
+    }
+
     #[test]
     fn test_complex_expression_generation() {
         let generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
@@ -247,7 +622,10 @@ mod clause_generation_tests {
             }),
         };
 
-        let result = generator.generate_expression(&condition).unwrap();
+        let result = generator
+            .context(None)
+            .generate_expression(&condition)
+            .unwrap();
         assert_eq!(result, "((\"age\" > 18) AND (\"status\" = 'active'))");
     }
 
@@ -260,7 +638,10 @@ mod clause_generation_tests {
             args: vec![Expr::Identifier("name".to_string())],
         };
 
-        let result = generator.generate_expression(&function_expr).unwrap();
+        let result = generator
+            .context(None)
+            .generate_expression(&function_expr)
+            .unwrap();
         assert_eq!(result, "UPPER(\"name\")");
     }
 
@@ -270,7 +651,10 @@ mod clause_generation_tests {
 
         let identifier_expr = Expr::Identifier("upper".to_string());
 
-        let result = generator.generate_expression(&identifier_expr).unwrap();
+        let result = generator
+            .context(None)
+            .generate_expression(&identifier_expr)
+            .unwrap();
         assert_eq!(result, "\"upper\"");
     }
 
@@ -283,7 +667,10 @@ mod clause_generation_tests {
             args: vec![Expr::Identifier("name".to_string())],
         };
 
-        let error = generator.generate_expression(&function_expr).unwrap_err();
+        let error = generator
+            .context(None)
+            .generate_expression(&function_expr)
+            .unwrap_err();
         assert!(matches!(
             error,
             GenerationError::UnsupportedFunction { function, dialect }
@@ -297,33 +684,138 @@ mod clause_generation_tests {
 
         assert_eq!(
             generator
+                .context(None)
                 .generate_literal(&LiteralValue::String("test".to_string()))
                 .unwrap(),
             "'test'"
         );
         assert_eq!(
             generator
+                .context(None)
                 .generate_literal(&LiteralValue::Number(42.5))
                 .unwrap(),
             "42.5"
         );
         assert_eq!(
             generator
+                .context(None)
                 .generate_literal(&LiteralValue::Boolean(true))
                 .unwrap(),
             "TRUE"
         );
         assert_eq!(
             generator
+                .context(None)
                 .generate_literal(&LiteralValue::Boolean(false))
                 .unwrap(),
             "FALSE"
         );
         assert_eq!(
-            generator.generate_literal(&LiteralValue::Null).unwrap(),
+            generator
+                .context(None)
+                .generate_literal(&LiteralValue::Null)
+                .unwrap(),
             "NULL"
         );
     }
+
+    fn inner_join_pipeline(source: Option<&str>) -> DplyrNode {
+        DplyrNode::Pipeline {
+            source: source.map(str::to_string),
+            source_function: None,
+            target: None,
+            operations: vec![DplyrOperation::Join {
+                join_type: JoinType::Inner,
+                spec: JoinSpec {
+                    table: "orders".to_string(),
+                    by_column: Some("id".to_string()),
+                    on_expr: None,
+                },
+                location: SourceLocation::unknown(),
+            }],
+            location: SourceLocation::unknown(),
+        }
+    }
+
+    #[test]
+    fn test_join_emits_explicit_source_as_from_table() {
+        let generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
+
+        let sql = generator
+            .generate(&inner_join_pipeline(Some("users")))
+            .unwrap();
+
+        assert_eq!(
+            normalize_sql(&sql),
+            normalize_sql(
+                r#"SELECT * FROM "users" INNER JOIN "orders" ON "users"."id" = "orders"."id""#
+            )
+        );
+    }
+
+    #[test]
+    fn test_join_without_source_defaults_from_table_to_data() {
+        let generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
+
+        let sql = generator.generate(&inner_join_pipeline(None)).unwrap();
+
+        assert_eq!(
+            normalize_sql(&sql),
+            normalize_sql(
+                r#"SELECT * FROM "data" INNER JOIN "orders" ON "data"."id" = "orders"."id""#
+            )
+        );
+    }
+
+    #[test]
+    fn test_join_with_blank_source_is_rejected() {
+        let generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
+
+        let err = generator
+            .generate(&inner_join_pipeline(Some("  ")))
+            .unwrap_err();
+
+        assert!(
+            matches!(&err, GenerationError::InvalidAst { reason } if reason.contains("base table")),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn test_join_with_source_alias_qualifies_base_table_by_alias() {
+        let generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
+
+        let (sql, _) = generator
+            .generate_with_source_alias(&inner_join_pipeline(Some("users")), "u")
+            .unwrap();
+
+        assert_eq!(
+            normalize_sql(&sql),
+            normalize_sql(
+                r#"SELECT * FROM "users" AS "u" INNER JOIN "orders" ON "u"."id" = "orders"."id""#
+            )
+        );
+
+        // The alias applies to a single call only
+        let sql = generator
+            .generate(&inner_join_pipeline(Some("users")))
+            .unwrap();
+        assert!(!sql.contains(r#"AS "u""#), "{sql}");
+    }
+
+    #[test]
+    fn test_blank_source_alias_is_rejected() {
+        let generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
+
+        let err = generator
+            .generate_with_source_alias(&inner_join_pipeline(Some("users")), " ")
+            .unwrap_err();
+
+        assert!(
+            matches!(&err, GenerationError::InvalidIdentifier { .. }),
+            "{err:?}"
+        );
+    }
 }
 
 // ===== Dialect-Specific SQL Generation Tests =====
@@ -338,6 +830,7 @@ mod dialect_specific_tests {
 
         let ast = DplyrNode::Pipeline {
             source: None,
+            source_function: None,
             target: None,
             operations: vec![create_test_select_operation(vec!["name", "age"])],
             location: SourceLocation::unknown(),
@@ -352,6 +845,23 @@ mod dialect_specific_tests {
         assert!(mysql_sql.contains("`age`"));
     }
 
+    #[test]
+    fn test_capability_queries_differ_by_dialect() {
+        let duckdb = DuckDbDialect::new();
+        let postgres = PostgreSqlDialect::new();
+
+        assert!(duckdb.supports_aggregate("median"));
+        assert!(!postgres.supports_aggregate("median"));
+        assert!(postgres.supports_aggregate("mean"));
+
+        assert!(duckdb.supports_operation("rename"));
+        assert!(!postgres.supports_operation("rename"));
+        assert!(postgres.supports_operation("fill"));
+        assert!(!SqliteDialect::new().supports_operation("fill"));
+        assert!(duckdb.supports_operation("pivot_wider"));
+        assert!(!duckdb.supports_operation("pivot_longer"));
+    }
+
     #[test]
     fn test_string_concatenation_differences() {
         let pg_generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
@@ -366,8 +876,14 @@ mod dialect_specific_tests {
             ],
         };
 
-        let pg_result = pg_generator.generate_expression(&concat_expr).unwrap();
-        let mysql_result = mysql_generator.generate_expression(&concat_expr).unwrap();
+        let pg_result = pg_generator
+            .context(None)
+            .generate_expression(&concat_expr)
+            .unwrap();
+        let mysql_result = mysql_generator
+            .context(None)
+            .generate_expression(&concat_expr)
+            .unwrap();
 
         assert_eq!(pg_result, "CONCAT(\"first_name\", ' ', \"last_name\")");
         assert_eq!(mysql_result, "CONCAT(`first_name`, ' ', `last_name`)");
@@ -389,23 +905,29 @@ mod dialect_specific_tests {
         };
 
         assert_eq!(
-            pg_generator.generate_expression(&str_detect_expr).unwrap(),
+            pg_generator
+                .context(None)
+                .generate_expression(&str_detect_expr)
+                .unwrap(),
             "(\"name\" ~ '^A')"
         );
         assert_eq!(
             mysql_generator
+                .context(None)
                 .generate_expression(&str_detect_expr)
                 .unwrap(),
-            "REGEXP_LIKE(`name`, '^A')"
+            "(`name` REGEXP '^A')"
         );
         assert_eq!(
             duckdb_generator
+                .context(None)
                 .generate_expression(&str_detect_expr)
                 .unwrap(),
             "regexp_matches(\"name\", '^A')"
         );
         assert!(matches!(
             sqlite_generator
+                .context(None)
                 .generate_expression(&str_detect_expr)
                 .unwrap_err(),
             GenerationError::UnsupportedFunction { function, dialect }
@@ -413,6 +935,166 @@ mod dialect_specific_tests {
         ));
     }
 
+    #[test]
+    fn test_grepl_matches_str_detect_with_pattern_first() {
+        let grepl_expr = Expr::Function {
+            name: "grepl".to_string(),
+            args: vec![
+                Expr::Literal(LiteralValue::String("^A".to_string())),
+                Expr::Identifier("name".to_string()),
+            ],
+        };
+        let cases: [(Box<dyn SqlDialect>, &str); 3] = [
+            (Box::new(PostgreSqlDialect::new()), "(\"name\" ~ '^A')"),
+            (Box::new(MySqlDialect::new()), "(`name` REGEXP '^A')"),
+            (
+                Box::new(DuckDbDialect::new()),
+                "regexp_matches(\"name\", '^A')",
+            ),
+        ];
+        for (dialect, expected) in cases {
+            let generator = SqlGenerator::new(dialect);
+            assert_eq!(
+                generator
+                    .context(None)
+                    .generate_expression(&grepl_expr)
+                    .unwrap(),
+                expected
+            );
+        }
+
+        let sqlite_generator = SqlGenerator::new(Box::new(SqliteDialect::new()));
+        assert!(matches!(
+            sqlite_generator.context(None).generate_expression(&grepl_expr).unwrap_err(),
+            GenerationError::UnsupportedFunction { function, dialect }
+                if function == "grepl" && dialect == "sqlite"
+        ));
+    }
+
+    #[test]
+    fn test_tidyverse_string_replacement_is_dialect_specific() {
+        let replace_expr = |function: &str, pattern: &str| Expr::Function {
+            name: function.to_string(),
+            args: vec![
+                Expr::Identifier("name".to_string()),
+                Expr::Literal(LiteralValue::String(pattern.to_string())),
+                Expr::Literal(LiteralValue::String("b".to_string())),
+            ],
+        };
+        let pg_generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
+        let mysql_generator = SqlGenerator::new(Box::new(MySqlDialect::new()));
+        let duckdb_generator = SqlGenerator::new(Box::new(DuckDbDialect::new()));
+        let sqlite_generator = SqlGenerator::new(Box::new(SqliteDialect::new()));
+
+        let first = replace_expr("str_replace", "a");
+        let all = replace_expr("str_replace_all", "a");
+        assert_eq!(
+            pg_generator
+                .context(None)
+                .generate_expression(&first)
+                .unwrap(),
+            "REGEXP_REPLACE(\"name\", 'a', 'b')"
+        );
+        assert_eq!(
+            pg_generator
+                .context(None)
+                .generate_expression(&all)
+                .unwrap(),
+            "REGEXP_REPLACE(\"name\", 'a', 'b', 'g')"
+        );
+        assert_eq!(
+            duckdb_generator
+                .context(None)
+                .generate_expression(&all)
+                .unwrap(),
+            "regexp_replace(\"name\", 'a', 'b', 'g')"
+        );
+        assert_eq!(
+            mysql_generator
+                .context(None)
+                .generate_expression(&first)
+                .unwrap(),
+            "REGEXP_REPLACE(`name`, 'a', 'b', 1, 1)"
+        );
+        assert_eq!(
+            mysql_generator
+                .context(None)
+                .generate_expression(&all)
+                .unwrap(),
+            "REGEXP_REPLACE(`name`, 'a', 'b')"
+        );
+
+        // SQLite only has literal, replace-all REPLACE()
+        assert_eq!(
+            sqlite_generator
+                .context(None)
+                .generate_expression(&all)
+                .unwrap(),
+            "REPLACE(\"name\", 'a', 'b')"
+        );
+        // Escaped metacharacters are literal too
+        assert_eq!(
+            sqlite_generator
+                .context(None)
+                .generate_expression(&replace_expr("str_replace_all", "\\."))
+                .unwrap(),
+            "REPLACE(\"name\", '.', 'b')"
+        );
+        // Real regexes, and backreferences in the replacement, have no REPLACE() equivalent
+        let backreference = Expr::Function {
+            name: "str_replace_all".to_string(),
+            args: vec![
+                Expr::Identifier("name".to_string()),
+                Expr::Literal(LiteralValue::String("a".to_string())),
+                Expr::Literal(LiteralValue::String("\\1".to_string())),
+            ],
+        };
+        for expr in [
+            first,
+            replace_expr("str_replace_all", "^a+"),
+            replace_expr("str_replace_all", "\\d"),
+            backreference,
+        ] {
+            assert!(matches!(
+                sqlite_generator.context(None).generate_expression(&expr).unwrap_err(),
+                GenerationError::UnsupportedFunction { dialect, .. } if dialect == "sqlite"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_xor_expands_or_uses_native_operator_by_dialect() {
+        let pg_generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
+        let mysql_generator = SqlGenerator::new(Box::new(MySqlDialect::new()));
+
+        let xor_expr = Expr::Function {
+            name: "xor".to_string(),
+            args: vec![
+                Expr::Identifier("is_member".to_string()),
+                Expr::Binary {
+                    left: Box::new(Expr::Identifier("age".to_string())),
+                    operator: BinaryOp::GreaterThan,
+                    right: Box::new(Expr::Literal(LiteralValue::Number(65.0))),
+                },
+            ],
+        };
+
+        assert_eq!(
+            pg_generator
+                .context(None)
+                .generate_expression(&xor_expr)
+                .unwrap(),
+            "((\"is_member\" AND NOT (\"age\" > 65)) OR (NOT \"is_member\" AND (\"age\" > 65)))"
+        );
+        assert_eq!(
+            mysql_generator
+                .context(None)
+                .generate_expression(&xor_expr)
+                .unwrap(),
+            "(`is_member` XOR (`age` > 65))"
+        );
+    }
+
     #[test]
     fn test_tidyverse_casts_are_dialect_specific() {
         let pg_generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
@@ -426,23 +1108,29 @@ mod dialect_specific_tests {
         };
 
         assert_eq!(
-            pg_generator.generate_expression(&as_numeric_expr).unwrap(),
+            pg_generator
+                .context(None)
+                .generate_expression(&as_numeric_expr)
+                .unwrap(),
             "CAST(\"score\" AS DOUBLE PRECISION)"
         );
         assert_eq!(
             mysql_generator
+                .context(None)
                 .generate_expression(&as_numeric_expr)
                 .unwrap(),
             "CAST(`score` AS DOUBLE)"
         );
         assert_eq!(
             duckdb_generator
+                .context(None)
                 .generate_expression(&as_numeric_expr)
                 .unwrap(),
             "CAST(\"score\" AS DOUBLE)"
         );
         assert_eq!(
             sqlite_generator
+                .context(None)
                 .generate_expression(&as_numeric_expr)
                 .unwrap(),
             "CAST(\"score\" AS REAL)"
@@ -472,36 +1160,60 @@ mod dialect_specific_tests {
         };
 
         assert_eq!(
-            pg_generator.generate_expression(&paste0_expr).unwrap(),
+            pg_generator
+                .context(None)
+                .generate_expression(&paste0_expr)
+                .unwrap(),
             "CONCAT(\"first_name\", \"last_name\")"
         );
         assert_eq!(
-            mysql_generator.generate_expression(&paste0_expr).unwrap(),
+            mysql_generator
+                .context(None)
+                .generate_expression(&paste0_expr)
+                .unwrap(),
             "CONCAT(`first_name`, `last_name`)"
         );
         assert_eq!(
-            duckdb_generator.generate_expression(&paste0_expr).unwrap(),
+            duckdb_generator
+                .context(None)
+                .generate_expression(&paste0_expr)
+                .unwrap(),
             "CONCAT(\"first_name\", \"last_name\")"
         );
         assert_eq!(
-            sqlite_generator.generate_expression(&paste0_expr).unwrap(),
+            sqlite_generator
+                .context(None)
+                .generate_expression(&paste0_expr)
+                .unwrap(),
             "(\"first_name\" || \"last_name\")"
         );
 
         assert_eq!(
-            pg_generator.generate_expression(&paste_expr).unwrap(),
+            pg_generator
+                .context(None)
+                .generate_expression(&paste_expr)
+                .unwrap(),
             "CONCAT_WS(' ', \"first_name\", \"last_name\")"
         );
         assert_eq!(
-            mysql_generator.generate_expression(&paste_expr).unwrap(),
+            mysql_generator
+                .context(None)
+                .generate_expression(&paste_expr)
+                .unwrap(),
             "CONCAT_WS(' ', `first_name`, `last_name`)"
         );
         assert_eq!(
-            duckdb_generator.generate_expression(&paste_expr).unwrap(),
+            duckdb_generator
+                .context(None)
+                .generate_expression(&paste_expr)
+                .unwrap(),
             "CONCAT_WS(' ', \"first_name\", \"last_name\")"
         );
         assert_eq!(
-            sqlite_generator.generate_expression(&paste_expr).unwrap(),
+            sqlite_generator
+                .context(None)
+                .generate_expression(&paste_expr)
+                .unwrap(),
             "(\"first_name\" || ' ' || \"last_name\")"
         );
     }
@@ -524,15 +1236,108 @@ mod dialect_specific_tests {
         };
 
         assert_eq!(
-            pg_generator.generate_expression(&paste_expr).unwrap(),
+            pg_generator
+                .context(None)
+                .generate_expression(&paste_expr)
+                .unwrap(),
             "CONCAT_WS('-', \"first_name\", \"last_name\")"
         );
         assert_eq!(
-            sqlite_generator.generate_expression(&paste_expr).unwrap(),
+            sqlite_generator
+                .context(None)
+                .generate_expression(&paste_expr)
+                .unwrap(),
             "(\"first_name\" || '-' || \"last_name\")"
         );
     }
 
+    #[test]
+    fn test_separator_concatenation_uses_concat_ws_only_where_supported() {
+        let str_c = |args: Vec<Expr>| Expr::Function {
+            name: "str_c".to_string(),
+            args,
+        };
+        let sep = Expr::NamedArg {
+            name: "sep".to_string(),
+            value: Box::new(Expr::Literal(LiteralValue::String("-".to_string()))),
+        };
+        let with_sep = str_c(vec![
+            Expr::Identifier("a".to_string()),
+            Expr::Identifier("b".to_string()),
+            sep.clone(),
+        ]);
+        let without_sep = str_c(vec![
+            Expr::Identifier("a".to_string()),
+            Expr::Identifier("b".to_string()),
+        ]);
+        let paste = Expr::Function {
+            name: "paste".to_string(),
+            args: vec![
+                Expr::Identifier("a".to_string()),
+                Expr::Identifier("b".to_string()),
+                sep,
+            ],
+        };
+
+        let cases: Vec<(Box<dyn SqlDialect>, &str, &str)> = vec![
+            (
+                Box::new(PostgreSqlDialect::new()),
+                "CONCAT_WS('-', \"a\", \"b\")",
+                "CONCAT(\"a\", \"b\")",
+            ),
+            (
+                Box::new(MySqlDialect::new()),
+                "CONCAT_WS('-', `a`, `b`)",
+                "CONCAT(`a`, `b`)",
+            ),
+            (
+                Box::new(DuckDbDialect::new()),
+                "CONCAT_WS('-', \"a\", \"b\")",
+                "CONCAT(\"a\", \"b\")",
+            ),
+            (
+                Box::new(SparkSqlDialect::new()),
+                "CONCAT_WS('-', `a`, `b`)",
+                "CONCAT(`a`, `b`)",
+            ),
+            (
+                Box::new(SqliteDialect::new()),
+                "(\"a\" || '-' || \"b\")",
+                "(\"a\" || \"b\")",
+            ),
+            (
+                Box::new(OracleDialect::new()),
+                "(\"A\" || '-' || \"B\")",
+                "(\"A\" || \"B\")",
+            ),
+        ];
+        for (dialect, separated, plain) in cases {
+            let name = dialect.dialect_name().to_string();
+            let generator = SqlGenerator::new(dialect);
+            assert_eq!(
+                generator
+                    .context(None)
+                    .generate_expression(&with_sep)
+                    .unwrap(),
+                separated,
+                "{name}"
+            );
+            assert_eq!(
+                generator.context(None).generate_expression(&paste).unwrap(),
+                separated,
+                "{name}"
+            );
+            assert_eq!(
+                generator
+                    .context(None)
+                    .generate_expression(&without_sep)
+                    .unwrap(),
+                plain,
+                "{name}"
+            );
+        }
+    }
+
     #[test]
     fn test_is_na_predicate_is_parenthesized_in_binary_expression() {
         let generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
@@ -546,7 +1351,7 @@ mod dialect_specific_tests {
         };
 
         assert_eq!(
-            generator.generate_expression(&expr).unwrap(),
+            generator.context(None).generate_expression(&expr).unwrap(),
             "((\"value\" IS NULL) = TRUE)"
         );
     }
@@ -593,15 +1398,24 @@ mod dialect_specific_tests {
         };
 
         assert_eq!(
-            generator.generate_expression(&round_expr).unwrap(),
+            generator
+                .context(None)
+                .generate_expression(&round_expr)
+                .unwrap(),
             "ROUND(\"value\", 2)"
         );
         assert_eq!(
-            generator.generate_expression(&lead_expr).unwrap(),
+            generator
+                .context(None)
+                .generate_expression(&lead_expr)
+                .unwrap(),
             "LEAD(\"value\", 1, 0) OVER ()"
         );
         assert_eq!(
-            generator.generate_expression(&lag_expr).unwrap(),
+            generator
+                .context(None)
+                .generate_expression(&lag_expr)
+                .unwrap(),
             "LAG(\"value\", 2, 0) OVER ()"
         );
     }
@@ -650,11 +1464,17 @@ mod dialect_specific_tests {
         };
 
         assert_eq!(
-            generator.generate_expression(&ifelse_expr).unwrap(),
+            generator
+                .context(None)
+                .generate_expression(&ifelse_expr)
+                .unwrap(),
             "CASE WHEN (\"score\" > 80) THEN 'high' ELSE 'low' END"
         );
         assert_eq!(
-            generator.generate_expression(&if_else_expr).unwrap(),
+            generator
+                .context(None)
+                .generate_expression(&if_else_expr)
+                .unwrap(),
             "CASE WHEN \"active\" THEN 'yes' ELSE 'no' END"
         );
     }
@@ -675,7 +1495,7 @@ mod dialect_specific_tests {
         };
 
         assert!(matches!(
-            generator.generate_expression(&round_expr),
+            generator.context(None).generate_expression(&round_expr),
             Err(GenerationError::UnsupportedNamedArgument {
                 function,
                 argument,
@@ -683,7 +1503,10 @@ mod dialect_specific_tests {
             }) if function == "round" && argument == "missing" && dialect == "postgresql"
         ));
 
-        let error = generator.generate_expression(&round_expr).unwrap_err();
+        let error = generator
+            .context(None)
+            .generate_expression(&round_expr)
+            .unwrap_err();
         assert!(error.to_string().contains("missing"));
         assert!(error.to_string().contains("round"));
     }
@@ -767,53 +1590,84 @@ mod dialect_specific_tests {
         };
 
         assert_eq!(
-            generator.generate_expression(&lead_expr).unwrap(),
+            generator
+                .context(None)
+                .generate_expression(&lead_expr)
+                .unwrap(),
             "LEAD(\"value\", (1 + 1)) OVER ()"
         );
         assert_eq!(
-            generator.generate_expression(&lag_expr).unwrap(),
+            generator
+                .context(None)
+                .generate_expression(&lag_expr)
+                .unwrap(),
             "LAG(\"value\", (1 + 1)) OVER ()"
         );
         assert_eq!(
-            generator.generate_expression(&row_number_expr).unwrap(),
+            generator
+                .context(None)
+                .generate_expression(&row_number_expr)
+                .unwrap(),
             "ROW_NUMBER() OVER ()"
         );
         assert_eq!(
-            generator.generate_expression(&ranked_expr).unwrap(),
+            generator
+                .context(None)
+                .generate_expression(&ranked_expr)
+                .unwrap(),
             "RANK() OVER (ORDER BY \"value\")"
         );
         assert_eq!(
-            generator.generate_expression(&dense_ranked_expr).unwrap(),
+            generator
+                .context(None)
+                .generate_expression(&dense_ranked_expr)
+                .unwrap(),
             "DENSE_RANK() OVER (ORDER BY \"value\")"
         );
         assert_eq!(
             generator
+                .context(None)
                 .generate_expression(&ordered_row_number_expr)
                 .unwrap(),
             "ROW_NUMBER() OVER (ORDER BY \"value\")"
         );
         assert_eq!(
-            generator.generate_expression(&lead_default_expr).unwrap(),
+            generator
+                .context(None)
+                .generate_expression(&lead_default_expr)
+                .unwrap(),
             "LEAD(\"value\", 2, 0) OVER ()"
         );
         assert_eq!(
-            generator.generate_expression(&lag_default_expr).unwrap(),
+            generator
+                .context(None)
+                .generate_expression(&lag_default_expr)
+                .unwrap(),
             "LAG(\"value\", 2, 0) OVER ()"
         );
         assert_eq!(
-            generator.generate_expression(&first_expr).unwrap(),
+            generator
+                .context(None)
+                .generate_expression(&first_expr)
+                .unwrap(),
             "FIRST_VALUE(\"value\") OVER ()"
         );
         assert_eq!(
-            generator.generate_expression(&last_expr).unwrap(),
+            generator
+                .context(None)
+                .generate_expression(&last_expr)
+                .unwrap(),
             "LAST_VALUE(\"value\") OVER ()"
         );
         assert_eq!(
-            generator.generate_expression(&ordered_first_expr).unwrap(),
+            generator
+                .context(None)
+                .generate_expression(&ordered_first_expr)
+                .unwrap(),
             "FIRST_VALUE(\"value\") OVER (ORDER BY \"event_date\")"
         );
         assert_eq!(
-            generator.generate_expression(&ordered_last_expr).unwrap(),
+            generator.context(None).generate_expression(&ordered_last_expr).unwrap(),
             "LAST_VALUE(\"value\") OVER (ORDER BY \"event_date\" ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING)"
         );
     }
@@ -833,23 +1687,94 @@ mod dialect_specific_tests {
         };
 
         assert_eq!(
-            generator.generate_expression(&nzchar_expr).unwrap(),
+            generator
+                .context(None)
+                .generate_expression(&nzchar_expr)
+                .unwrap(),
             "(LENGTH(\"name\") > 0)"
         );
         assert_eq!(
-            generator.generate_expression(&nchar_expr).unwrap(),
+            generator
+                .context(None)
+                .generate_expression(&nchar_expr)
+                .unwrap(),
             "LENGTH(\"name\")"
         );
         assert_eq!(
-            mysql_generator.generate_expression(&nchar_expr).unwrap(),
+            mysql_generator
+                .context(None)
+                .generate_expression(&nchar_expr)
+                .unwrap(),
             "CHAR_LENGTH(`name`)"
         );
         assert_eq!(
-            mysql_generator.generate_expression(&nzchar_expr).unwrap(),
+            mysql_generator
+                .context(None)
+                .generate_expression(&nzchar_expr)
+                .unwrap(),
             "(CHAR_LENGTH(`name`) > 0)"
         );
     }
 
+    #[test]
+    fn test_null_safe_equality_is_dialect_specific() {
+        let equal = Expr::Binary {
+            left: Box::new(Expr::Identifier("a".to_string())),
+            operator: BinaryOp::Equal,
+            right: Box::new(Expr::Identifier("b".to_string())),
+        };
+        let not_equal = Expr::Binary {
+            left: Box::new(Expr::Identifier("a".to_string())),
+            operator: BinaryOp::NotEqual,
+            right: Box::new(Expr::Literal(LiteralValue::Number(1.0))),
+        };
+        let cases: Vec<(Box<dyn SqlDialect>, &str, &str)> = vec![
+            (
+                Box::new(PostgreSqlDialect::new()),
+                "(\"a\" IS NOT DISTINCT FROM \"b\")",
+                "(NOT (\"a\" IS NOT DISTINCT FROM 1))",
+            ),
+            (
+                Box::new(DuckDbDialect::new()),
+                "(\"a\" IS NOT DISTINCT FROM \"b\")",
+                "(NOT (\"a\" IS NOT DISTINCT FROM 1))",
+            ),
+            (
+                Box::new(MySqlDialect::new()),
+                "(`a` <=> `b`)",
+                "(NOT (`a` <=> 1))",
+            ),
+            (
+                Box::new(SqliteDialect::new()),
+                "(\"a\" IS \"b\")",
+                "(NOT (\"a\" IS 1))",
+            ),
+        ];
+
+        for (dialect, expected_equal, expected_not_equal) in cases {
+            // Off by default
+            let plain = SqlGenerator::new(dialect.clone_box());
+            assert!(plain
+                .context(None)
+                .generate_expression(&equal)
+                .unwrap()
+                .contains(" = "));
+
+            let generator = SqlGenerator::new(dialect).with_null_safe_equality(true);
+            assert_eq!(
+                generator.context(None).generate_expression(&equal).unwrap(),
+                expected_equal
+            );
+            assert_eq!(
+                generator
+                    .context(None)
+                    .generate_expression(&not_equal)
+                    .unwrap(),
+                expected_not_equal
+            );
+        }
+    }
+
     #[test]
     fn test_tidyverse_log10_is_dialect_specific() {
         let pg_generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
@@ -863,33 +1788,144 @@ mod dialect_specific_tests {
         };
 
         assert_eq!(
-            pg_generator.generate_expression(&log10_expr).unwrap(),
+            pg_generator
+                .context(None)
+                .generate_expression(&log10_expr)
+                .unwrap(),
             "LOG(\"value\")"
         );
         assert_eq!(
-            mysql_generator.generate_expression(&log10_expr).unwrap(),
+            mysql_generator
+                .context(None)
+                .generate_expression(&log10_expr)
+                .unwrap(),
             "LOG10(`value`)"
         );
         assert_eq!(
-            duckdb_generator.generate_expression(&log10_expr).unwrap(),
+            duckdb_generator
+                .context(None)
+                .generate_expression(&log10_expr)
+                .unwrap(),
             "LOG10(\"value\")"
         );
         assert!(matches!(
-            sqlite_generator.generate_expression(&log10_expr),
+            sqlite_generator.context(None).generate_expression(&log10_expr),
             Err(GenerationError::UnsupportedFunction { function, dialect })
                 if function == "log10" && dialect == "sqlite"
         ));
     }
 
+    #[test]
+    fn test_tidyverse_log_and_power_mappings() {
+        let value = || Expr::Identifier("value".to_string());
+        let log_expr = Expr::Function {
+            name: "log".to_string(),
+            args: vec![value()],
+        };
+        let log_base_expr = Expr::Function {
+            name: "log".to_string(),
+            args: vec![value(), Expr::Literal(LiteralValue::Number(3.0))],
+        };
+        let log2_expr = Expr::Function {
+            name: "log2".to_string(),
+            args: vec![value()],
+        };
+        let exp_expr = Expr::Function {
+            name: "exp".to_string(),
+            args: vec![value()],
+        };
+        let pow_expr = Expr::Function {
+            name: "pow".to_string(),
+            args: vec![value(), Expr::Literal(LiteralValue::Number(2.0))],
+        };
+
+        let pg_generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
+        let duckdb_generator = SqlGenerator::new(Box::new(DuckDbDialect::new()));
+        for generator in [&pg_generator, &duckdb_generator] {
+            assert_eq!(
+                generator
+                    .context(None)
+                    .generate_expression(&log_expr)
+                    .unwrap(),
+                "LN(\"value\")"
+            );
+            assert_eq!(
+                generator
+                    .context(None)
+                    .generate_expression(&log_base_expr)
+                    .unwrap(),
+                "LOG(3, \"value\")"
+            );
+            assert_eq!(
+                generator
+                    .context(None)
+                    .generate_expression(&log2_expr)
+                    .unwrap(),
+                "LOG(2, \"value\")"
+            );
+            assert_eq!(
+                generator
+                    .context(None)
+                    .generate_expression(&exp_expr)
+                    .unwrap(),
+                "EXP(\"value\")"
+            );
+        }
+        assert_eq!(
+            pg_generator
+                .context(None)
+                .generate_expression(&pow_expr)
+                .unwrap(),
+            "(\"value\" ^ 2)"
+        );
+        assert_eq!(
+            duckdb_generator
+                .context(None)
+                .generate_expression(&pow_expr)
+                .unwrap(),
+            "POWER(\"value\", 2)"
+        );
+
+        let mysql_generator = SqlGenerator::new(Box::new(MySqlDialect::new()));
+        assert_eq!(
+            mysql_generator
+                .context(None)
+                .generate_expression(&log_expr)
+                .unwrap(),
+            "LN(`value`)"
+        );
+        assert_eq!(
+            mysql_generator
+                .context(None)
+                .generate_expression(&log_base_expr)
+                .unwrap(),
+            "LOG(3, `value`)"
+        );
+        assert_eq!(
+            mysql_generator
+                .context(None)
+                .generate_expression(&log2_expr)
+                .unwrap(),
+            "LOG(2, `value`)"
+        );
+        assert_eq!(
+            mysql_generator
+                .context(None)
+                .generate_expression(&pow_expr)
+                .unwrap(),
+            "POWER(`value`, 2)"
+        );
+    }
+
     #[test]
     fn test_sqlite_rejects_non_standard_math_functions() {
         let sqlite_generator = SqlGenerator::new(Box::new(SqliteDialect::new()));
 
         for function in [
-            "floor", "ceiling", "sqrt", "sign", "exp", "log", "log10", "sin", "cos", "tan", "asin",
-            "acos", "atan", "atan2", "sinh", "cosh", "tanh",
+            "floor", "ceiling", "sqrt", "sign", "exp", "log", "log2", "log10", "pow", "power",
+            "sin", "cos", "tan", "asin", "acos", "atan", "atan2", "sinh", "cosh", "tanh",
         ] {
-            let args = if function == "atan2" || function == "log" {
+            let args = if matches!(function, "atan2" | "log" | "pow" | "power") {
                 vec![
                     Expr::Identifier("value".to_string()),
                     Expr::Literal(LiteralValue::Number(2.0)),
@@ -903,7 +1939,7 @@ mod dialect_specific_tests {
             };
 
             assert!(matches!(
-                sqlite_generator.generate_expression(&expr),
+                sqlite_generator.context(None).generate_expression(&expr),
                 Err(GenerationError::UnsupportedFunction {
                     function: actual,
                     dialect
@@ -920,7 +1956,10 @@ mod dialect_specific_tests {
         };
 
         assert_eq!(
-            sqlite_generator.generate_expression(&round_expr).unwrap(),
+            sqlite_generator
+                .context(None)
+                .generate_expression(&round_expr)
+                .unwrap(),
             "ROUND(\"value\", 2)"
         );
     }
@@ -939,8 +1978,11 @@ mod dialect_specific_tests {
         };
 
         assert_eq!(
-            generator.generate_expression(&substr_expr).unwrap(),
-            "SUBSTR(\"name\", 2, ((4) - (2) + 1))"
+            generator
+                .context(None)
+                .generate_expression(&substr_expr)
+                .unwrap(),
+            "SUBSTR(\"name\", 2, 3)"
         );
 
         let complex_substr_expr = Expr::Function {
@@ -961,11 +2003,67 @@ mod dialect_specific_tests {
         };
 
         assert_eq!(
-            generator.generate_expression(&complex_substr_expr).unwrap(),
+            generator
+                .context(None)
+                .generate_expression(&complex_substr_expr)
+                .unwrap(),
             "SUBSTR(\"name\", (1 + 1), (((5 + 1)) - ((1 + 1)) + 1))"
         );
     }
 
+    #[test]
+    fn test_str_sub_computes_length_from_start_and_end() {
+        let str_sub = |start: f64, end: Option<f64>| Expr::Function {
+            name: "str_sub".to_string(),
+            args: std::iter::once(Expr::Identifier("name".to_string()))
+                .chain(
+                    std::iter::once(start)
+                        .chain(end)
+                        .map(|n| Expr::Literal(LiteralValue::Number(n))),
+                )
+                .collect(),
+        };
+
+        let pg_generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
+        assert_eq!(
+            pg_generator
+                .context(None)
+                .generate_expression(&str_sub(1.0, Some(3.0)))
+                .unwrap(),
+            "SUBSTR(\"name\", 1, 3)"
+        );
+        assert_eq!(
+            pg_generator
+                .context(None)
+                .generate_expression(&str_sub(4.0, Some(2.0)))
+                .unwrap(),
+            "SUBSTR(\"name\", 4, 0)"
+        );
+        assert_eq!(
+            pg_generator
+                .context(None)
+                .generate_expression(&str_sub(2.0, None))
+                .unwrap(),
+            "SUBSTR(\"name\", 2)"
+        );
+
+        let mysql_generator = SqlGenerator::new(Box::new(MySqlDialect::new()));
+        assert_eq!(
+            mysql_generator
+                .context(None)
+                .generate_expression(&str_sub(2.0, Some(5.0)))
+                .unwrap(),
+            "SUBSTRING(`name`, 2, 4)"
+        );
+
+        assert!(matches!(
+            pg_generator
+                .context(None)
+                .generate_expression(&str_sub(-3.0, Some(-1.0))),
+            Err(GenerationError::UnsupportedOperation { .. })
+        ));
+    }
+
     #[test]
     fn test_tidyverse_null_replacement_helpers_translate_to_coalesce() {
         let generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
@@ -994,15 +2092,24 @@ mod dialect_specific_tests {
         };
 
         assert_eq!(
-            generator.generate_expression(&coalesce_expr).unwrap(),
+            generator
+                .context(None)
+                .generate_expression(&coalesce_expr)
+                .unwrap(),
             "COALESCE(\"nickname\", \"name\", 'unknown')"
         );
         assert_eq!(
-            generator.generate_expression(&replace_na_expr).unwrap(),
+            generator
+                .context(None)
+                .generate_expression(&replace_na_expr)
+                .unwrap(),
             "COALESCE(\"nickname\", 'unknown')"
         );
         assert_eq!(
-            generator.generate_expression(&na_replace_expr).unwrap(),
+            generator
+                .context(None)
+                .generate_expression(&na_replace_expr)
+                .unwrap(),
             "COALESCE(\"nickname\", 'unknown')"
         );
     }
@@ -1016,7 +2123,10 @@ mod dialect_specific_tests {
             args: vec![Expr::Identifier("score".to_string())],
         };
 
-        let error = generator.generate_expression(&case_expr).unwrap_err();
+        let error = generator
+            .context(None)
+            .generate_expression(&case_expr)
+            .unwrap_err();
         assert!(matches!(
             error,
             GenerationError::UnsupportedFunction { function, dialect }
@@ -1041,12 +2151,12 @@ mod dialect_specific_tests {
         };
 
         assert!(matches!(
-            generator.generate_expression(&missing_arg_expr),
+            generator.context(None).generate_expression(&missing_arg_expr),
             Err(GenerationError::UnsupportedFunction { function, dialect })
                 if function == "tolower" && dialect == "postgresql"
         ));
         assert!(matches!(
-            generator.generate_expression(&too_many_args_expr),
+            generator.context(None).generate_expression(&too_many_args_expr),
             Err(GenerationError::UnsupportedFunction { function, dialect })
                 if function == "toupper" && dialect == "postgresql"
         ));
@@ -1065,6 +2175,7 @@ mod dialect_specific_tests {
         };
 
         let error = duckdb_generator
+            .context(None)
             .generate_expression(&extension_expr)
             .unwrap_err();
         assert!(matches!(
@@ -1075,20 +2186,55 @@ mod dialect_specific_tests {
     }
 
     #[test]
-    fn test_postgresql_unknown_aggregate_is_rejected() {
+    fn test_postgresql_unknown_aggregate_is_rejected() {
+        let generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
+        let aggregations = vec![Aggregation {
+            function: "extension_agg".to_string(),
+            column: "value".to_string(),
+            alias: Some("result".to_string()),
+            distinct: false,
+            probability: None,
+        }];
+
+        let error = generator
+            .context(None)
+            .generate_aggregations(&aggregations)
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            GenerationError::UnsupportedAggregateFunction { function, dialect, .. }
+                if function == "extension_agg" && dialect == "postgresql"
+        ));
+    }
+
+    #[test]
+    fn test_postgresql_median_error_suggests_percentile_cont() {
         let generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
         let aggregations = vec![Aggregation {
-            function: "extension_agg".to_string(),
-            column: "value".to_string(),
-            alias: Some("result".to_string()),
+            function: "median".to_string(),
+            column: "price".to_string(),
+            alias: Some("median_price".to_string()),
+            distinct: false,
+            probability: None,
         }];
 
-        let error = generator.generate_aggregations(&aggregations).unwrap_err();
-        assert!(matches!(
+        let error = generator
+            .context(None)
+            .generate_aggregations(&aggregations)
+            .unwrap_err();
+        assert_eq!(
             error,
-            GenerationError::UnsupportedAggregateFunction { function, dialect }
-                if function == "extension_agg" && dialect == "postgresql"
-        ));
+            GenerationError::UnsupportedAggregateFunction {
+                function: "median".to_string(),
+                dialect: "postgresql".to_string(),
+                suggestion: Some(
+                    "PERCENTILE_CONT(0.5) WITHIN GROUP (ORDER BY \"price\")".to_string()
+                ),
+            }
+        );
+        assert!(error
+            .to_string()
+            .ends_with("; try PERCENTILE_CONT(0.5) WITHIN GROUP (ORDER BY \"price\") instead"));
     }
 
     #[test]
@@ -1098,12 +2244,17 @@ mod dialect_specific_tests {
             function: "extension_agg".to_string(),
             column: "value".to_string(),
             alias: Some("result".to_string()),
+            distinct: false,
+            probability: None,
         }];
 
-        let error = generator.generate_aggregations(&aggregations).unwrap_err();
+        let error = generator
+            .context(None)
+            .generate_aggregations(&aggregations)
+            .unwrap_err();
         assert!(matches!(
             error,
-            GenerationError::UnsupportedAggregateFunction { function, dialect }
+            GenerationError::UnsupportedAggregateFunction { function, dialect, .. }
                 if function == "extension_agg" && dialect == "duckdb"
         ));
     }
@@ -1113,6 +2264,7 @@ mod dialect_specific_tests {
         let generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
         let ast = DplyrNode::Pipeline {
             source: Some("data\"set".to_string()),
+            source_function: None,
             target: None,
             operations: vec![
                 DplyrOperation::Select {
@@ -1120,6 +2272,7 @@ mod dialect_specific_tests {
                         expr: Expr::Identifier("name\"x".to_string()),
                         alias: None,
                     }],
+                    excluded: Vec::new(),
                     location: SourceLocation::unknown(),
                 },
                 DplyrOperation::Join {
@@ -1135,6 +2288,7 @@ mod dialect_specific_tests {
                     columns: vec![OrderExpr {
                         column: "name\"x".to_string(),
                         direction: OrderDirection::Asc,
+                        nulls: None,
                     }],
                     location: SourceLocation::unknown(),
                 },
@@ -1157,10 +2311,13 @@ mod dialect_specific_tests {
         let grouped_generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
         let grouped_ast = DplyrNode::Pipeline {
             source: Some("data".to_string()),
+            source_function: None,
             target: None,
             operations: vec![
                 DplyrOperation::GroupBy {
                     columns: vec!["dept\"x".to_string()],
+                    add: false,
+                    grouping: GroupingKind::Plain,
                     location: SourceLocation::unknown(),
                 },
                 DplyrOperation::Summarise {
@@ -1168,7 +2325,10 @@ mod dialect_specific_tests {
                         function: "mean".to_string(),
                         column: "salary\"x".to_string(),
                         alias: Some("avg\"x".to_string()),
+                        distinct: false,
+                        probability: None,
                     }],
+                    groups: SummariseGroups::default(),
                     location: SourceLocation::unknown(),
                 },
             ],
@@ -1182,6 +2342,7 @@ mod dialect_specific_tests {
         let rename_generator = SqlGenerator::new(Box::new(DuckDbDialect::new()));
         let rename_ast = DplyrNode::Pipeline {
             source: Some("data".to_string()),
+            source_function: None,
             target: None,
             operations: vec![DplyrOperation::Rename {
                 renames: vec![RenameSpec {
@@ -1233,27 +2394,164 @@ mod dialect_specific_tests {
 
     #[test]
     fn test_duckdb_specific_functions() {
-        let duckdb_generator = SqlGenerator::new(Box::new(DuckDbDialect::new()));
+        let duckdb_generator =
+            SqlGenerator::new(Box::new(DuckDbDialect::new())).with_auto_aggregate_aliases(false);
 
         let aggregations = vec![
             Aggregation {
                 function: "median".to_string(),
                 column: "salary".to_string(),
                 alias: None,
+                distinct: false,
+                probability: None,
             },
             Aggregation {
                 function: "mode".to_string(),
                 column: "category".to_string(),
                 alias: None,
+                distinct: false,
+                probability: None,
             },
         ];
 
         let result = duckdb_generator
+            .context(None)
             .generate_aggregations(&aggregations)
             .unwrap();
         assert_eq!(result[0], "MEDIAN(\"salary\")");
         assert_eq!(result[1], "MODE(\"category\")");
     }
+
+    fn filtering_join_pipeline(join_type: JoinType) -> DplyrNode {
+        DplyrNode::Pipeline {
+            source: Some("orders".to_string()),
+            source_function: None,
+            target: None,
+            operations: vec![
+                create_test_filter_operation("amount", 100.0),
+                DplyrOperation::Join {
+                    join_type,
+                    spec: JoinSpec {
+                        table: "customers".to_string(),
+                        by_column: Some("customer_id".to_string()),
+                        on_expr: None,
+                    },
+                    location: SourceLocation::unknown(),
+                },
+            ],
+            location: SourceLocation::unknown(),
+        }
+    }
+
+    #[test]
+    fn test_semi_anti_join_become_exists_for_standard_dialects() {
+        let dialects: Vec<Box<dyn SqlDialect>> = vec![
+            Box::new(PostgreSqlDialect::new()),
+            Box::new(MySqlDialect::new()),
+            Box::new(SqliteDialect::new()),
+        ];
+
+        for dialect in dialects {
+            let generator = SqlGenerator::new(dialect.clone_box());
+            let condition = format!(
+                "{} = {}",
+                dialect.quote_identifier_path(&["orders", "customer_id"]),
+                dialect.quote_identifier_path(&["customers", "customer_id"])
+            );
+            let customers = dialect.quote_identifier("customers");
+
+            let semi = generator
+                .generate(&filtering_join_pipeline(JoinType::Semi))
+                .unwrap();
+            assert!(!semi.contains("JOIN"), "{semi}");
+            assert!(
+                semi.contains(&format!(
+                    "AND (EXISTS (SELECT 1 FROM {customers} WHERE {condition}))"
+                )),
+                "{semi}"
+            );
+
+            let anti = generator
+                .generate(&filtering_join_pipeline(JoinType::Anti))
+                .unwrap();
+            assert!(!anti.contains("JOIN"), "{anti}");
+            assert!(
+                anti.contains(&format!(
+                    "AND (NOT EXISTS (SELECT 1 FROM {customers} WHERE {condition}))"
+                )),
+                "{anti}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_semi_anti_join_stay_native_for_duckdb() {
+        let generator = SqlGenerator::new(Box::new(DuckDbDialect::new()));
+
+        let semi = generator
+            .generate(&filtering_join_pipeline(JoinType::Semi))
+            .unwrap();
+        assert!(
+            semi.contains(
+                "SEMI JOIN \"customers\" ON \"orders\".\"customer_id\" = \"customers\".\"customer_id\""
+            ),
+            "{semi}"
+        );
+        assert!(!semi.contains("EXISTS"));
+
+        let anti = generator
+            .generate(&filtering_join_pipeline(JoinType::Anti))
+            .unwrap();
+        assert!(anti.contains("ANTI JOIN \"customers\""), "{anti}");
+        assert!(!anti.contains("EXISTS"));
+    }
+
+    #[test]
+    fn test_semi_anti_join_stay_native_for_spark() {
+        let generator = SqlGenerator::new(Box::new(SparkSqlDialect::new()));
+
+        let semi = generator
+            .generate(&filtering_join_pipeline(JoinType::Semi))
+            .unwrap();
+        assert!(
+            semi.contains(
+                "SEMI JOIN `customers` ON `orders`.`customer_id` = `customers`.`customer_id`"
+            ),
+            "{semi}"
+        );
+        assert!(!semi.contains("EXISTS"));
+
+        let anti = generator
+            .generate(&filtering_join_pipeline(JoinType::Anti))
+            .unwrap();
+        assert!(anti.contains("ANTI JOIN `customers`"), "{anti}");
+        assert!(!anti.contains("EXISTS"));
+    }
+
+    #[test]
+    fn test_semi_join_without_condition_is_rejected() {
+        let generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
+        let ast = DplyrNode::Pipeline {
+            source: None,
+            source_function: None,
+            target: None,
+            operations: vec![DplyrOperation::Join {
+                join_type: JoinType::Semi,
+                spec: JoinSpec {
+                    table: "customers".to_string(),
+                    by_column: None,
+                    on_expr: None,
+                },
+                location: SourceLocation::unknown(),
+            }],
+            location: SourceLocation::unknown(),
+        };
+
+        assert!(matches!(
+            generator.generate(&ast),
+            Err(GenerationError::InvalidAst { .. })
+        ));
+    }
 }
 
 // ===== Complex Query Generation Tests =====
@@ -1261,12 +2559,71 @@ mod dialect_specific_tests {
 mod complex_query_tests {
     use super::*;
 
+    #[test]
+    fn test_generate_to_writer_matches_string_output() {
+        let generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
+        let ast = DplyrNode::Pipeline {
+            source: Some("employees".to_string()),
+            source_function: None,
+            target: None,
+            operations: vec![
+                create_test_select_operation(vec!["name", "age", "salary"]),
+                create_test_filter_operation("age", 25.0),
+                create_test_filter_operation("salary", 1000.0),
+                DplyrOperation::Arrange {
+                    columns: vec![OrderExpr::desc("salary")],
+                    location: SourceLocation::unknown(),
+                },
+            ],
+            location: SourceLocation::unknown(),
+        };
+
+        let mut out = Vec::new();
+        generator.generate_to_writer(&ast, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            generator.generate(&ast).unwrap()
+        );
+
+        let source = DplyrNode::DataSource {
+            name: "employees".to_string(),
+            location: SourceLocation::unknown(),
+        };
+        let mut out = Vec::new();
+        generator.generate_to_writer(&source, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            generator.generate(&source).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_generate_to_writer_stops_at_output_limit() {
+        let generator =
+            SqlGenerator::new(Box::new(PostgreSqlDialect::new())).with_max_output_length(30);
+        let ast = DplyrNode::Pipeline {
+            source: None,
+            source_function: None,
+            target: None,
+            operations: vec![create_test_select_operation(vec!["name", "age", "salary"])],
+            location: SourceLocation::unknown(),
+        };
+
+        let mut out = Vec::new();
+        assert!(matches!(
+            generator.generate_to_writer(&ast, &mut out),
+            Err(GenerationError::OutputTooLarge { max: 30, .. })
+        ));
+        assert!(out.len() <= 30, "{} bytes written", out.len());
+    }
+
     #[test]
     fn test_complete_pipeline_generation() {
         let generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
 
         let ast = DplyrNode::Pipeline {
             source: None,
+            source_function: None,
             target: None,
             operations: vec![
                 create_test_select_operation(vec!["name", "age", "salary"]),
@@ -1275,6 +2632,7 @@ mod complex_query_tests {
                     columns: vec![OrderExpr {
                         column: "salary".to_string(),
                         direction: OrderDirection::Desc,
+                        nulls: None,
                     }],
                     location: SourceLocation::unknown(),
                 },
@@ -1301,10 +2659,13 @@ mod complex_query_tests {
 
         let ast = DplyrNode::Pipeline {
             source: None,
+            source_function: None,
             target: None,
             operations: vec![
                 DplyrOperation::GroupBy {
                     columns: vec!["department".to_string()],
+                    add: false,
+                    grouping: GroupingKind::Plain,
                     location: SourceLocation::unknown(),
                 },
                 DplyrOperation::Summarise {
@@ -1313,13 +2674,18 @@ mod complex_query_tests {
                             function: "mean".to_string(),
                             column: "salary".to_string(),
                             alias: Some("avg_salary".to_string()),
+                            distinct: false,
+                            probability: None,
                         },
                         Aggregation {
                             function: "n".to_string(),
                             column: "".to_string(),
                             alias: Some("count".to_string()),
+                            distinct: false,
+                            probability: None,
                         },
                     ],
+                    groups: SummariseGroups::default(),
                     location: SourceLocation::unknown(),
                 },
             ],
@@ -1342,10 +2708,13 @@ mod complex_query_tests {
 
         let ast = DplyrNode::Pipeline {
             source: Some("data".to_string()),
+            source_function: None,
             target: None,
             operations: vec![
                 DplyrOperation::GroupBy {
                     columns: vec!["dept".to_string()],
+                    add: false,
+                    grouping: GroupingKind::Plain,
                     location: SourceLocation::unknown(),
                 },
                 DplyrOperation::Summarise {
@@ -1353,7 +2722,10 @@ mod complex_query_tests {
                         function: "mean".to_string(),
                         column: "salary".to_string(),
                         alias: Some("avg".to_string()),
+                        distinct: false,
+                        probability: None,
                     }],
+                    groups: SummariseGroups::default(),
                     location: SourceLocation::unknown(),
                 },
             ],
@@ -1378,6 +2750,7 @@ mod complex_query_tests {
 
         let ast = DplyrNode::Pipeline {
             source: Some("data".to_string()),
+            source_function: None,
             target: None,
             operations: vec![
                 DplyrOperation::Summarise {
@@ -1385,11 +2758,16 @@ mod complex_query_tests {
                         function: "n".to_string(),
                         column: "".to_string(),
                         alias: Some("n".to_string()),
+                        distinct: false,
+                        probability: None,
                     }],
+                    groups: SummariseGroups::default(),
                     location: SourceLocation::unknown(),
                 },
                 DplyrOperation::GroupBy {
                     columns: vec!["g".to_string()],
+                    add: false,
+                    grouping: GroupingKind::Plain,
                     location: SourceLocation::unknown(),
                 },
             ],
@@ -1410,10 +2788,13 @@ mod complex_query_tests {
 
         let ast = DplyrNode::Pipeline {
             source: Some("data".to_string()),
+            source_function: None,
             target: None,
             operations: vec![
                 DplyrOperation::GroupBy {
                     columns: vec!["g".to_string()],
+                    add: false,
+                    grouping: GroupingKind::Plain,
                     location: SourceLocation::unknown(),
                 },
                 DplyrOperation::Summarise {
@@ -1421,11 +2802,16 @@ mod complex_query_tests {
                         function: "n".to_string(),
                         column: "".to_string(),
                         alias: Some("n".to_string()),
+                        distinct: false,
+                        probability: None,
                     }],
+                    groups: SummariseGroups::default(),
                     location: SourceLocation::unknown(),
                 },
                 DplyrOperation::GroupBy {
                     columns: vec!["h".to_string()],
+                    add: false,
+                    grouping: GroupingKind::Plain,
                     location: SourceLocation::unknown(),
                 },
             ],
@@ -1450,6 +2836,7 @@ mod complex_query_tests {
 
         let ast = DplyrNode::Pipeline {
             source: None,
+            source_function: None,
             target: None,
             operations: vec![
                 create_test_select_operation(vec!["name"]),
@@ -1488,6 +2875,7 @@ mod complex_query_tests {
 
         let ast = DplyrNode::Pipeline {
             source: None,
+            source_function: None,
             target: None,
             operations: vec![DplyrOperation::Mutate {
                 assignments: vec![
@@ -1535,6 +2923,7 @@ mod error_case_tests {
 
         let ast = DplyrNode::Pipeline {
             source: None,
+            source_function: None,
             target: None,
             operations: vec![],
             location: SourceLocation::unknown(),
@@ -1566,10 +2955,36 @@ mod error_case_tests {
         }
 
         // This should not panic or cause stack overflow
-        let result = generator.generate_expression(&nested_expr);
+        let result = generator.context(None).generate_expression(&nested_expr);
         assert!(result.is_ok(), "Should handle deeply nested expressions");
     }
 
+    #[test]
+    fn test_pathological_nesting_is_rejected() {
+        let generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
+
+        let mut nested_expr = Expr::Identifier("base".to_string());
+        for _ in 0..10_000 {
+            nested_expr = Expr::Not(Box::new(nested_expr));
+        }
+
+        assert_eq!(
+            generator.context(None).generate_expression(&nested_expr),
+            Err(GenerationError::MaxNestingDepthExceeded {
+                depth: MAX_EXPRESSION_DEPTH + 1,
+                max_depth: MAX_EXPRESSION_DEPTH,
+            })
+        );
+
+        // The depth count unwinds, so the generator stays usable
+        assert_eq!(
+            generator
+                .context(None)
+                .generate_expression(&Expr::Identifier("base".to_string())),
+            Ok("\"base\"".to_string())
+        );
+    }
+
     #[test]
     fn test_data_source_generation() {
         let generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
@@ -1603,7 +3018,7 @@ mod error_case_tests {
         ];
 
         for (op, expected) in operators {
-            let result = generator.generate_binary_operator(&op);
+            let result = generator.context(None).generate_binary_operator(&op);
             assert_eq!(result, expected, "Operator {op:?} should map to {expected}");
         }
     }
@@ -1622,7 +3037,7 @@ mod error_case_tests {
 
         for (input, expected) in test_strings {
             let literal = LiteralValue::String(input.to_string());
-            let result = generator.generate_literal(&literal).unwrap();
+            let result = generator.context(None).generate_literal(&literal).unwrap();
             assert_eq!(
                 result, expected,
                 "String '{input}' should be quoted as {expected}"
@@ -1644,13 +3059,60 @@ mod error_case_tests {
 
         for (input, expected) in test_numbers {
             let literal = LiteralValue::Number(input);
-            let result = generator.generate_literal(&literal).unwrap();
+            let result = generator.context(None).generate_literal(&literal).unwrap();
             assert_eq!(
                 result, expected,
                 "Number {input} should be formatted as {expected}"
             );
         }
     }
+
+    #[test]
+    fn test_format_number_is_plain_decimal_in_every_dialect() {
+        let cases = [
+            (-0.0, "0"),
+            (42.0, "42"),
+            (0.1, "0.1"),
+            (-2.5, "-2.5"),
+            (1e-7, "0.0000001"),
+            (1e21, "1000000000000000000000"),
+            (123_456_789.125, "123456789.125"),
+        ];
+        let dialects: Vec<Box<dyn SqlDialect>> = vec![
+            Box::new(PostgreSqlDialect::new()),
+            Box::new(MySqlDialect::new()),
+            Box::new(SqliteDialect::new()),
+            Box::new(DuckDbDialect::new()),
+            Box::new(OracleDialect::new()),
+            Box::new(SparkSqlDialect::new()),
+        ];
+
+        for dialect in &dialects {
+            for (input, expected) in cases {
+                assert_eq!(
+                    format_number(input, dialect.as_ref()),
+                    expected,
+                    "{} formatting of {input}",
+                    dialect.dialect_name()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_non_finite_number_literal_is_rejected() {
+        let generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
+
+        for value in [f64::INFINITY, f64::NEG_INFINITY, f64::NAN] {
+            let result = generator
+                .context(None)
+                .generate_literal(&LiteralValue::Number(value));
+            assert!(
+                matches!(result, Err(GenerationError::InvalidAst { .. })),
+                "{value}: {result:?}"
+            );
+        }
+    }
 }
 
 // ===== Mutate Operation Advanced Tests =====
@@ -1681,10 +3143,48 @@ mod mutate_advanced_tests {
         ];
 
         let query_parts = QueryParts::new();
-        let needs_subquery = generator.mutate_needs_subquery(&assignments, &query_parts);
+        let needs_subquery = generator
+            .context(None)
+            .mutate_needs_subquery(&assignments, &query_parts)
+            .unwrap();
         assert!(needs_subquery, "Should detect column dependencies");
     }
 
+    #[test]
+    fn test_mutate_dependency_cycle_is_rejected() {
+        let generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
+        let assign = |column: &str, reads: &str| Assignment {
+            column: column.to_string(),
+            expr: Expr::Identifier(reads.to_string()),
+        };
+        let query_parts = QueryParts::new();
+
+        for (assignments, cycle) in [
+            (vec![assign("a", "b"), assign("b", "a")], "a -> b -> a"),
+            (
+                vec![assign("x", "y"), assign("z", "x"), assign("y", "z")],
+                "x -> y -> z -> x",
+            ),
+        ] {
+            assert!(matches!(
+                generator.context(None).mutate_needs_subquery(&assignments, &query_parts),
+                Err(GenerationError::CircularReference { reference }) if reference == cycle
+            ));
+        }
+
+        // Reading a column's own previous value, or a chain, is not a cycle
+        for assignments in [
+            vec![assign("a", "a")],
+            vec![assign("a", "b"), assign("b", "b")],
+            vec![assign("a", "b"), assign("c", "a")],
+        ] {
+            assert!(generator
+                .context(None)
+                .mutate_needs_subquery(&assignments, &query_parts)
+                .is_ok());
+        }
+    }
+
     #[test]
     fn test_mutate_with_window_functions() {
         let generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
@@ -1697,10 +3197,15 @@ mod mutate_advanced_tests {
         }];
 
         let query_parts = QueryParts::new();
-        let is_complex = generator.expression_is_complex(&assignments[0].expr);
+        let is_complex = generator
+            .context(None)
+            .expression_is_complex(&assignments[0].expr);
         assert!(is_complex, "Should detect window function as complex");
 
-        let needs_subquery = generator.mutate_needs_subquery(&assignments, &query_parts);
+        let needs_subquery = generator
+            .context(None)
+            .mutate_needs_subquery(&assignments, &query_parts)
+            .unwrap();
         assert!(needs_subquery, "Should need subquery for window functions");
     }
 
@@ -1709,10 +3214,13 @@ mod mutate_advanced_tests {
         let generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
         let ast = DplyrNode::Pipeline {
             source: Some("employees".to_string()),
+            source_function: None,
             target: None,
             operations: vec![
                 DplyrOperation::GroupBy {
                     columns: vec!["department".to_string()],
+                    add: false,
+                    grouping: GroupingKind::Plain,
                     location: SourceLocation::unknown(),
                 },
                 DplyrOperation::Mutate {
@@ -1849,11 +3357,15 @@ mod mutate_advanced_tests {
 
         // Expression that references existing column
         let expr1 = Expr::Identifier("existing_col".to_string());
-        assert!(generator.expression_references_columns(&expr1, &columns));
+        assert!(generator
+            .context(None)
+            .expression_references_columns(&expr1, &columns));
 
         // Expression that doesn't reference existing column
         let expr2 = Expr::Identifier("other_col".to_string());
-        assert!(!generator.expression_references_columns(&expr2, &columns));
+        assert!(!generator
+            .context(None)
+            .expression_references_columns(&expr2, &columns));
 
         // Binary expression with reference
         let expr3 = Expr::Binary {
@@ -1861,7 +3373,9 @@ mod mutate_advanced_tests {
             operator: BinaryOp::Plus,
             right: Box::new(Expr::Literal(LiteralValue::Number(1.0))),
         };
-        assert!(generator.expression_references_columns(&expr3, &columns));
+        assert!(generator
+            .context(None)
+            .expression_references_columns(&expr3, &columns));
     }
 
     #[test]
@@ -1885,7 +3399,7 @@ mod mutate_advanced_tests {
                 args: vec![],
             };
             assert!(
-                generator.expression_is_complex(&expr),
+                generator.context(None).expression_is_complex(&expr),
                 "Function {} should be detected as complex",
                 func_name
             );
@@ -1896,10 +3410,187 @@ mod mutate_advanced_tests {
             name: "upper".to_string(),
             args: vec![Expr::Identifier("name".to_string())],
         };
-        assert!(!generator.expression_is_complex(&regular_expr));
+        assert!(!generator.context(None).expression_is_complex(&regular_expr));
 
         // Literals should not be complex
         let literal_expr = Expr::Literal(LiteralValue::Number(42.0));
-        assert!(!generator.expression_is_complex(&literal_expr));
+        assert!(!generator.context(None).expression_is_complex(&literal_expr));
+    }
+
+    fn formula(lhs: Expr, rhs: Expr) -> Expr {
+        Expr::Formula {
+            lhs: Box::new(lhs),
+            rhs: Box::new(rhs),
+        }
+    }
+
+    fn column(name: &str) -> Expr {
+        Expr::Identifier(name.to_string())
+    }
+
+    #[test]
+    fn test_case_when_is_na_with_catch_all() {
+        let generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
+        // case_when(is.na(x) ~ 0, TRUE ~ x)
+        let expr = Expr::Function {
+            name: "case_when".to_string(),
+            args: vec![
+                formula(
+                    Expr::Function {
+                        name: "is.na".to_string(),
+                        args: vec![column("x")],
+                    },
+                    Expr::Literal(LiteralValue::Number(0.0)),
+                ),
+                formula(Expr::Literal(LiteralValue::Boolean(true)), column("x")),
+            ],
+        };
+
+        assert_eq!(
+            generator.context(None).generate_expression(&expr).unwrap(),
+            r#"CASE WHEN ("x" IS NULL) THEN 0 ELSE "x" END"#
+        );
+    }
+
+    #[test]
+    fn test_case_when_na_branch_and_missing_else_yield_null() {
+        let generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
+        let expr = Expr::Function {
+            name: "case_when".to_string(),
+            args: vec![
+                formula(
+                    Expr::Binary {
+                        left: Box::new(column("x")),
+                        operator: BinaryOp::GreaterThan,
+                        right: Box::new(Expr::Literal(LiteralValue::Number(10.0))),
+                    },
+                    Expr::Literal(LiteralValue::Null),
+                ),
+                formula(
+                    Expr::Binary {
+                        left: Box::new(column("x")),
+                        operator: BinaryOp::Equal,
+                        right: Box::new(Expr::Literal(LiteralValue::Null)),
+                    },
+                    Expr::Literal(LiteralValue::Number(0.0)),
+                ),
+            ],
+        };
+
+        assert_eq!(
+            generator.context(None).generate_expression(&expr).unwrap(),
+            r#"CASE WHEN ("x" > 10) THEN NULL WHEN ("x" IS NULL) THEN 0 END"#
+        );
+    }
+
+    #[test]
+    fn test_comparison_with_na_uses_is_null() {
+        let generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
+        let na = || Box::new(Expr::Literal(LiteralValue::Null));
+
+        let equal = Expr::Binary {
+            left: Box::new(column("x")),
+            operator: BinaryOp::Equal,
+            right: na(),
+        };
+        let not_equal_reversed = Expr::Binary {
+            left: na(),
+            operator: BinaryOp::NotEqual,
+            right: Box::new(column("x")),
+        };
+        let if_else = Expr::Function {
+            name: "if_else".to_string(),
+            args: vec![
+                equal.clone(),
+                Expr::Literal(LiteralValue::Number(0.0)),
+                column("x"),
+            ],
+        };
+
+        assert_eq!(
+            generator.context(None).generate_expression(&equal).unwrap(),
+            r#"("x" IS NULL)"#
+        );
+        assert_eq!(
+            generator
+                .context(None)
+                .generate_expression(&not_equal_reversed)
+                .unwrap(),
+            r#"("x" IS NOT NULL)"#
+        );
+        assert_eq!(
+            generator
+                .context(None)
+                .generate_expression(&if_else)
+                .unwrap(),
+            r#"CASE WHEN ("x" IS NULL) THEN 0 ELSE "x" END"#
+        );
+    }
+
+    #[test]
+    fn test_if_else_and_case_when_parenthesize_arithmetic_branches() {
+        let generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
+        let binary = |left: Expr, operator: BinaryOp, right: f64| Expr::Binary {
+            left: Box::new(left),
+            operator,
+            right: Box::new(Expr::Literal(LiteralValue::Number(right))),
+        };
+        // if_else(x > 0, x + 1, x - 1)
+        let if_else = Expr::Function {
+            name: "if_else".to_string(),
+            args: vec![
+                binary(column("x"), BinaryOp::GreaterThan, 0.0),
+                binary(column("x"), BinaryOp::Plus, 1.0),
+                binary(column("x"), BinaryOp::Minus, 1.0),
+            ],
+        };
+        // case_when(x > 0 ~ x * 2, TRUE ~ x - 1)
+        let case_when = Expr::Function {
+            name: "case_when".to_string(),
+            args: vec![
+                formula(
+                    binary(column("x"), BinaryOp::GreaterThan, 0.0),
+                    binary(column("x"), BinaryOp::Multiply, 2.0),
+                ),
+                formula(
+                    Expr::Literal(LiteralValue::Boolean(true)),
+                    binary(column("x"), BinaryOp::Minus, 1.0),
+                ),
+            ],
+        };
+
+        assert_eq!(
+            generator
+                .context(None)
+                .generate_expression(&if_else)
+                .unwrap(),
+            r#"CASE WHEN ("x" > 0) THEN ("x" + 1) ELSE ("x" - 1) END"#
+        );
+        assert_eq!(
+            generator
+                .context(None)
+                .generate_expression(&case_when)
+                .unwrap(),
+            r#"CASE WHEN ("x" > 0) THEN ("x" * 2) ELSE ("x" - 1) END"#
+        );
+    }
+
+    #[test]
+    fn test_case_when_rejects_non_formula_arguments() {
+        let generator = SqlGenerator::new(Box::new(PostgreSqlDialect::new()));
+        let expr = Expr::Function {
+            name: "case_when".to_string(),
+            args: vec![column("x")],
+        };
+        assert!(matches!(
+            generator.context(None).generate_expression(&expr),
+            Err(GenerationError::InvalidAst { .. })
+        ));
+
+        let bare_formula = formula(column("x"), Expr::Literal(LiteralValue::Number(1.0)));
+        assert!(matches!(
+            generator.context(None).generate_expression(&bare_formula),
+            Err(GenerationError::InvalidAst { .. })
+        ));
     }
 }
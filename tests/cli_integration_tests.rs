@@ -218,6 +218,137 @@ fn test_explicit_dialect_overrides_environment_default() {
     assert_eq!(stdout.trim(), "SELECT \"name\" FROM \"data\"");
 }
 
+#[test]
+fn test_degraded_translation_warns_on_stderr_without_failing() {
+    let output = Command::new(get_libdplyr_path())
+        .args([
+            "--text",
+            "data %>% mutate(prev = lag(amount))",
+            "--dialect",
+            "duckdb",
+            "--compact",
+        ])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .expect("Failed to run libdplyr process");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        output.status.success(),
+        "Degraded translation should still succeed. stderr: {stderr}"
+    );
+    assert!(stderr.contains("W-UNORDERED-WINDOW"), "stderr: {stderr}");
+
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+    assert!(stdout.contains("LAG("), "stdout: {stdout}");
+    assert!(!stdout.contains("W-UNORDERED-WINDOW"));
+}
+
+#[test]
+fn test_batch_mode_writes_each_input_and_reports_failures() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let valid_input = temp_dir.path().join("input1.R");
+    let broken_input = temp_dir.path().join("input2.R");
+    let output_dir = temp_dir.path().join("out");
+    fs::write(&valid_input, "data %>% select(name)").expect("Failed to write input");
+    fs::write(&broken_input, "data %>% select(").expect("Failed to write input");
+
+    let output = Command::new(get_libdplyr_path())
+        .arg("-d")
+        .arg("mysql")
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .arg(&valid_input)
+        .arg(&broken_input)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .expect("Failed to run libdplyr process");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        !output.status.success(),
+        "A failing input should make the batch exit nonzero. stderr: {stderr}"
+    );
+    assert!(stderr.contains("input2.R"), "stderr: {stderr}");
+    assert!(stderr.contains("1 succeeded, 1 failed"), "stderr: {stderr}");
+
+    let sql = fs::read_to_string(output_dir.join("input1.sql"))
+        .expect("The valid input should still be written");
+    assert!(sql.contains("SELECT `name`"), "sql: {sql}");
+    assert!(!output_dir.join("input2.sql").exists());
+}
+
+#[test]
+fn test_batch_mode_expands_glob_patterns_internally() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let query_dir = temp_dir.path().join("queries");
+    let output_dir = temp_dir.path().join("out");
+    fs::create_dir(&query_dir).expect("Failed to create query dir");
+    fs::write(query_dir.join("first.R"), "data %>% select(a)").expect("Failed to write input");
+    fs::write(query_dir.join("second.R"), "data %>% select(b)").expect("Failed to write input");
+    fs::write(query_dir.join("notes.txt"), "not dplyr").expect("Failed to write input");
+
+    // Passed as a single argument so the pattern is never expanded by a shell.
+    let pattern = query_dir.join("*.R");
+    let output = Command::new(get_libdplyr_path())
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .arg(&pattern)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .expect("Failed to run libdplyr process");
+
+    assert!(
+        output.status.success(),
+        "Glob batch should succeed. stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(output_dir.join("first.sql").exists());
+    assert!(output_dir.join("second.sql").exists());
+    assert!(!output_dir.join("notes.sql").exists());
+
+    let empty_pattern = query_dir.join("*.dplyr");
+    let output = Command::new(get_libdplyr_path())
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .arg(&empty_pattern)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .expect("Failed to run libdplyr process");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("did not match any files"),
+        "stderr: {stderr}"
+    );
+}
+
+#[test]
+fn test_multiple_inputs_without_output_dir_is_rejected() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let first = temp_dir.path().join("a.R");
+    let second = temp_dir.path().join("b.R");
+    fs::write(&first, "data %>% select(a)").expect("Failed to write input");
+    fs::write(&second, "data %>% select(b)").expect("Failed to write input");
+
+    let output = Command::new(get_libdplyr_path())
+        .arg(&first)
+        .arg(&second)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .expect("Failed to run libdplyr process");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--output-dir"), "stderr: {stderr}");
+}
+
 #[test]
 fn test_stdin_stdout_complex_query() {
     let mut child = Command::new(get_libdplyr_path())
@@ -273,6 +404,56 @@ fn test_stdin_stdout_empty_input() {
     );
 }
 
+#[test]
+fn test_suspicious_input_exits_with_security_error() {
+    let mut child = Command::new(get_libdplyr_path())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to start libdplyr process");
+
+    write_to_stdin(&mut child, b"data %>% select(name\x00)");
+
+    let output = child.wait_with_output().expect("Failed to read output");
+    assert_eq!(output.status.code(), Some(12), "Expected SECURITY_ERROR");
+    let stderr = String::from_utf8(output.stderr).expect("Invalid UTF-8");
+    assert!(stderr.contains("Security check failed"), "{stderr}");
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn test_stdin_timeout_fails_when_writer_sends_nothing() {
+    let mut child = Command::new(get_libdplyr_path())
+        .args(["--stdin-timeout", "200"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to start libdplyr process");
+
+    // Keep the pipe open without writing so a plain read would block forever
+    let open_stdin = child.stdin.take();
+
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+    let status = loop {
+        if let Some(status) = child.try_wait().expect("Failed to poll libdplyr") {
+            break status;
+        }
+        if std::time::Instant::now() > deadline {
+            let _ = child.kill();
+            panic!("libdplyr kept waiting on stdin despite --stdin-timeout");
+        }
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    };
+    drop(open_stdin);
+
+    assert_eq!(status.code(), Some(10), "Timeout should be a TIMEOUT_ERROR");
+    let output = child.wait_with_output().expect("Failed to read stderr");
+    let stderr = String::from_utf8(output.stderr).expect("Invalid UTF-8");
+    assert!(stderr.contains("within 200 ms"), "{stderr}");
+}
+
 #[test]
 fn test_validation_only_mode_success() {
     let mut child = Command::new(get_libdplyr_path())
@@ -338,6 +519,49 @@ fn test_validation_only_mode_failure() {
     );
 }
 
+#[test]
+fn test_check_mode_reports_ok_without_sql() {
+    let output = Command::new(get_libdplyr_path())
+        .args(["--check", "-d", "mysql", "-t", "data %>% select(name, age)"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .expect("Failed to run libdplyr");
+
+    assert!(
+        output.status.success(),
+        "Supported snippet should pass --check"
+    );
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+    assert_eq!(stdout.trim(), "OK");
+    assert!(!stdout.contains("SELECT"), "--check must not print SQL");
+}
+
+#[test]
+fn test_check_mode_rejects_dialect_incompatible_function() {
+    let output = Command::new(get_libdplyr_path())
+        .args([
+            "--check",
+            "-d",
+            "mysql",
+            "-t",
+            "data %>% summarise(mid = median(x))",
+        ])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .expect("Failed to run libdplyr");
+
+    assert!(
+        !output.status.success(),
+        "median() is not available on MySQL"
+    );
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+    assert!(!stdout.contains("SELECT"), "--check must not print SQL");
+    let stderr = String::from_utf8(output.stderr).expect("Invalid UTF-8");
+    assert!(stderr.contains("median"), "stderr: {stderr}");
+}
+
 #[test]
 fn test_json_output_format() {
     let mut child = Command::new(get_libdplyr_path())
@@ -406,6 +630,41 @@ fn test_json_output_with_validation_error() {
     assert!(json["error"].is_object(), "Should contain error object");
 }
 
+#[test]
+fn test_format_json_reports_parse_error_structure() {
+    let output = Command::new(get_libdplyr_path())
+        .args(["--format", "json", "-t", "data %>% filter(x @ 1)"])
+        .output()
+        .expect("Failed to run libdplyr");
+
+    assert!(!output.status.success(), "Invalid dplyr should fail");
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+    let json: serde_json::Value =
+        serde_json::from_str(&stdout).expect("Error output should be valid JSON");
+
+    assert_eq!(json["success"], false);
+    assert!(json["sql"].is_null());
+    assert_eq!(json["error"]["error_type"], "parse");
+    assert_eq!(json["error"]["code"], "E-SYNTAX");
+    assert!(json["error"]["message"]
+        .as_str()
+        .is_some_and(|message| message.contains("Unexpected character: '@'")));
+    assert_eq!(json["error"]["position"], 18);
+    assert!(json["metadata"]["dialect"].is_string());
+}
+
+#[test]
+fn test_format_text_matches_default_output() {
+    let output = Command::new(get_libdplyr_path())
+        .args(["--format", "text", "-t", "data %>% select(x)"])
+        .output()
+        .expect("Failed to run libdplyr");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+    assert_eq!(stdout.trim(), r#"SELECT "x" FROM "data""#);
+}
+
 #[test]
 fn test_pretty_formatting() {
     let mut child = Command::new(get_libdplyr_path())
@@ -466,6 +725,106 @@ fn test_compact_formatting() {
     assert!(trimmed.contains("WHERE"), "Should contain WHERE");
 }
 
+#[test]
+fn test_semicolon_flag_terminates_output_once() {
+    let run = |extra: &[&str]| {
+        let output = Command::new(get_libdplyr_path())
+            .args(["--text", "data %>% select(name) %>% filter(age > 18)"])
+            .args(extra)
+            .output()
+            .expect("Failed to run libdplyr");
+        assert!(output.status.success(), "{extra:?} should succeed");
+        String::from_utf8(output.stdout).expect("Invalid UTF-8")
+    };
+
+    let plain = run(&[]);
+    assert!(!plain.trim_end().ends_with(';'), "{plain}");
+
+    for extra in [
+        &["--semicolon"][..],
+        &["--semicolon", "--pretty"],
+        &["--semicolon", "--minify"],
+    ] {
+        let terminated = run(extra);
+        assert!(
+            terminated.trim_end().ends_with(';'),
+            "{extra:?}: {terminated}"
+        );
+        assert_eq!(
+            terminated.matches(';').count(),
+            1,
+            "{extra:?}: {terminated}"
+        );
+    }
+}
+
+#[test]
+fn test_newline_style_flags_control_output_bytes() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let run = |extra: &[&str]| {
+        let output_file = temp_dir.path().join("out.sql");
+        let output = Command::new(get_libdplyr_path())
+            .args([
+                "--text",
+                "data %>% select(name) %>% filter(age > 18)",
+                "--pretty",
+            ])
+            .args(extra)
+            .arg("--output")
+            .arg(&output_file)
+            .output()
+            .expect("Failed to run libdplyr");
+        assert!(output.status.success(), "{extra:?} should succeed");
+        fs::read(&output_file).expect("Failed to read output file")
+    };
+
+    let lf = run(&[]);
+    assert!(lf.ends_with(b"\n"));
+    assert!(!lf.contains(&b'\r'));
+
+    let crlf = run(&["--crlf"]);
+    assert!(crlf.ends_with(b"\r\n"));
+    let line_feeds = crlf.iter().filter(|&&byte| byte == b'\n').count();
+    assert!(line_feeds > 1, "pretty output spans several lines");
+    assert_eq!(
+        crlf.windows(2).filter(|pair| pair == b"\r\n").count(),
+        line_feeds
+    );
+
+    let bare = run(&["--crlf", "--no-trailing-newline"]);
+    assert!(!bare.ends_with(b"\n"));
+    assert_eq!(bare, crlf[..crlf.len() - 2]);
+}
+
+#[test]
+fn test_minified_output_matches_pretty_output_tokens() {
+    let pipeline = "data %>% select(name, age) %>% filter(age > 18 & status == 'active') %>% arrange(desc(age))";
+
+    let run = |flag: &str| {
+        let output = Command::new(get_libdplyr_path())
+            .args(["--text", pipeline, flag])
+            .output()
+            .expect("Failed to run libdplyr");
+        assert!(output.status.success(), "{flag} should succeed");
+        String::from_utf8(output.stdout).expect("Invalid UTF-8")
+    };
+
+    let pretty = run("--pretty");
+    let minified = run("--minify");
+
+    assert!(pretty.trim().lines().count() > 1);
+    assert!(
+        !minified.contains('\n'),
+        "Minified output should be a single line without a trailing newline"
+    );
+    assert!(!minified.ends_with(';'));
+    assert!(!minified.contains("  "));
+    assert_eq!(
+        pretty.split_whitespace().collect::<Vec<_>>(),
+        minified.split_whitespace().collect::<Vec<_>>()
+    );
+}
+
 #[test]
 fn test_verbose_mode() {
     let mut child = Command::new(get_libdplyr_path())
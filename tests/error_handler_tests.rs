@@ -21,6 +21,7 @@ fn test_exit_code_constants() {
         ExitCode::NETWORK_ERROR,
         ExitCode::TIMEOUT_ERROR,
         ExitCode::INTERNAL_ERROR,
+        ExitCode::SECURITY_ERROR,
     ];
 
     // Check all codes are unique
@@ -53,6 +54,7 @@ fn test_exit_code_constants() {
     assert_eq!(ExitCode::NETWORK_ERROR, 9);
     assert_eq!(ExitCode::TIMEOUT_ERROR, 10);
     assert_eq!(ExitCode::INTERNAL_ERROR, 11);
+    assert_eq!(ExitCode::SECURITY_ERROR, 12);
 }
 
 #[test]
@@ -64,6 +66,8 @@ fn test_error_categories() {
         ErrorCategory::Configuration,
         ErrorCategory::Network,
         ErrorCategory::Internal,
+        ErrorCategory::Security,
+        ErrorCategory::Timeout,
     ];
 
     // Test that categories can be compared
@@ -360,6 +364,16 @@ fn test_error_handler_general_errors() {
             ErrorCategory::Internal,
             ExitCode::INTERNAL_ERROR,
         ),
+        (
+            "Security error",
+            ErrorCategory::Security,
+            ExitCode::SECURITY_ERROR,
+        ),
+        (
+            "Timeout error",
+            ErrorCategory::Timeout,
+            ExitCode::TIMEOUT_ERROR,
+        ),
     ];
 
     for (message, category, expected_exit_code) in test_cases {
@@ -469,6 +483,15 @@ fn test_error_handler_transpile_error_types() {
     let system_error = TranspileError::SystemError("Signal handling failed".to_string());
     let exit_code = handler.handle_transpile_error(&system_error);
     assert_eq!(exit_code, ExitCode::SYSTEM_ERROR);
+
+    // Test security and timeout errors
+    let security_error = TranspileError::SecurityError("control character".to_string());
+    let exit_code = handler.handle_error(&security_error);
+    assert_eq!(exit_code, ExitCode::SECURITY_ERROR);
+
+    let timeout_error = TranspileError::TimeoutError("no input".to_string());
+    let exit_code = handler.handle_error(&timeout_error);
+    assert_eq!(exit_code, ExitCode::TIMEOUT_ERROR);
 }
 
 #[test]
@@ -0,0 +1,300 @@
+//! Generated SQL validation tests
+//!
+//! Re-parses transpiler output with `sqlparser` to confirm it is syntactically
+//! valid for each target dialect. This catches malformed output (unbalanced
+//! parentheses, unsupported operators, broken subqueries) that exact-string
+//! tests would happily lock in.
+
+use libdplyr::{
+    DuckDbDialect, MySqlDialect, OracleDialect, PostgreSqlDialect, SqliteDialect, Transpiler,
+};
+use sqlparser::ast::{
+    BinaryOperator, Expr, Query, SelectItem, SetExpr, Statement, TableFactor, UnaryOperator,
+};
+use sqlparser::dialect::{self, Dialect};
+use sqlparser::parser::Parser;
+use sqlparser::tokenizer::{Token, Tokenizer};
+
+/// Parses `sql` with the sqlparser dialect matching `dialect_name`.
+///
+/// Returns the parser error message when the SQL is not a single valid statement.
+fn validate_generated_sql(sql: &str, dialect_name: &str) -> Result<(), String> {
+    let parser_dialect: Box<dyn Dialect> = match dialect_name {
+        "postgresql" => Box::new(dialect::PostgreSqlDialect {}),
+        "mysql" => Box::new(dialect::MySqlDialect {}),
+        "sqlite" => Box::new(dialect::SQLiteDialect {}),
+        "duckdb" => Box::new(dialect::DuckDbDialect {}),
+        "oracle" => Box::new(dialect::OracleDialect {}),
+        other => return Err(format!("no sqlparser dialect for '{other}'")),
+    };
+
+    let statements = Parser::parse_sql(parser_dialect.as_ref(), sql).map_err(|e| e.to_string())?;
+    match statements.len() {
+        1 => Ok(()),
+        n => Err(format!("expected one statement, found {n}")),
+    }
+}
+
+fn transpilers() -> Vec<(&'static str, Transpiler)> {
+    vec![
+        (
+            "postgresql",
+            Transpiler::new(Box::new(PostgreSqlDialect::new())),
+        ),
+        ("mysql", Transpiler::new(Box::new(MySqlDialect::new()))),
+        ("sqlite", Transpiler::new(Box::new(SqliteDialect::new()))),
+        ("duckdb", Transpiler::new(Box::new(DuckDbDialect::new()))),
+    ]
+}
+
+/// Pipelines every dialect can translate.
+const PORTABLE_PIPELINES: &[&str] = &[
+    "data %>% select(name, age)",
+    "data %>% filter(age > 18 & status != \"inactive\")",
+    "data %>% filter(is.na(x) | x == NA)",
+    "data %>% filter(age >= 18, age < 65)",
+    "data %>% mutate(ratio = a / b, total = (a + b) * 2)",
+    "data %>% mutate(ratio = a / b) %>% filter(ratio > 1)",
+    "data %>% mutate(label = case_when(x > 1 ~ \"big\", TRUE ~ \"small\"))",
+    "data %>% mutate(y = if_else(x > 0, x, 0))",
+    "data %>% arrange(desc(age), name)",
+    "data %>% group_by(dept) %>% summarise(avg = mean(salary), n = n())",
+    "data %>% distinct(dept)",
+    "data %>% arrange(x) %>% slice_head(n = 3)",
+    "data %>% select(g, x) %>% group_by(g) %>% arrange(x) %>% slice_tail(n = 2)",
+    "data %>% mutate(r = row_number()) %>% filter(r <= 2)",
+    "data %>% mutate(s = substr(name, 2, 4))",
+    "data %>% left_join(other, by = \"id\")",
+];
+
+#[test]
+fn test_portable_pipelines_parse_in_every_dialect() {
+    for (dialect_name, transpiler) in transpilers() {
+        for code in PORTABLE_PIPELINES {
+            let sql = transpiler
+                .transpile(code)
+                .unwrap_or_else(|e| panic!("{dialect_name}: failed to transpile {code}: {e}"));
+            if let Err(error) = validate_generated_sql(&sql, dialect_name) {
+                panic!("{dialect_name}: invalid SQL for {code}\n{sql}\n{error}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_dialect_specific_pipelines_parse() {
+    let cases = [
+        (
+            "postgresql",
+            "data %>% mutate(a = str_replace_all(x, \"a\", \"b\"))",
+        ),
+        ("postgresql", "data %>% arrange(desc(x, na_last = TRUE))"),
+        ("postgresql", "data %>% filter(str_detect(name, \"^A\"))"),
+        ("mysql", "data %>% mutate(a = str_replace(x, \"a\", \"b\"))"),
+        (
+            "sqlite",
+            "data %>% mutate(a = str_replace_all(x, \"a\", \"b\"))",
+        ),
+        (
+            "duckdb",
+            "data %>% group_by(g) %>% summarise(m = median(x))",
+        ),
+        ("duckdb", "data %>% group_by(g) %>% slice_head(n = 1)"),
+    ];
+
+    let transpilers = transpilers();
+    for (dialect_name, code) in cases {
+        let (_, transpiler) = transpilers
+            .iter()
+            .find(|(name, _)| *name == dialect_name)
+            .expect("known dialect");
+        let sql = transpiler.transpile(code).unwrap();
+        if let Err(error) = validate_generated_sql(&sql, dialect_name) {
+            panic!("{dialect_name}: invalid SQL for {code}\n{sql}\n{error}");
+        }
+    }
+}
+
+#[test]
+fn test_validator_rejects_malformed_sql() {
+    assert!(validate_generated_sql("SELECT * FROM \"data\" WHERE ((\"x\" > 1)", "duckdb").is_err());
+    assert!(validate_generated_sql("SELECT * FROM \"data\"", "duckdb").is_ok());
+}
+
+/// Pipelines covering the Oracle-specific rendering: reserved column names,
+/// bare logical columns, `*` next to other columns, and derived tables.
+const ORACLE_PIPELINES: &[&str] = &[
+    "data %>% select(date, level, size, comment, number)",
+    "data %>% filter(is_active)",
+    "data %>% filter(!is_active | level > 2)",
+    "data %>% mutate(total = coalesce(amount, 0)) %>% head(5)",
+    "data %>% mutate(k = if_else(flag, 1, 0), c = case_when(flag ~ \"y\", TRUE ~ \"n\"))",
+    "data %>% mutate(r = row_number(date)) %>% filter(r <= 2)",
+    "data %>% group_by(level) %>% summarise(size = n()) %>% arrange(desc(size))",
+    "data %>% select(g, x) %>% group_by(g) %>% arrange(x) %>% slice_head(n = 2)",
+    "data %>% filter(x > 1) %>% slice(3:5)",
+];
+
+/// Words the generator emits unquoted in Oracle SQL besides function names.
+const ORACLE_SQL_WORDS: &[&str] = &[
+    "AND",
+    "AS",
+    "ASC",
+    "BY",
+    "CASE",
+    "DESC",
+    "ELSE",
+    "END",
+    "FETCH",
+    "FIRST",
+    "FROM",
+    "IS",
+    "NEXT",
+    "NOT",
+    "NULL",
+    "OFFSET",
+    "ONLY",
+    "OR",
+    "ORDER",
+    "OVER",
+    "PARTITION",
+    "ROWS",
+    "SELECT",
+    "THEN",
+    "WHEN",
+    "WHERE",
+    "GROUP",
+    "NULLS",
+    "LAST",
+    "subquery",
+];
+
+/// Returns what in `query` Oracle would reject, beyond plain syntax errors.
+fn oracle_problems(query: &Query, problems: &mut Vec<String>) {
+    let SetExpr::Select(select) = query.body.as_ref() else {
+        return;
+    };
+    if select.projection.len() > 1
+        && select
+            .projection
+            .iter()
+            .any(|item| matches!(item, SelectItem::Wildcard(_)))
+    {
+        problems.push("unqualified * next to other columns".to_string());
+    }
+    for item in &select.projection {
+        if let SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. } = item {
+            if let Expr::Case { conditions, .. } = expr {
+                for when in conditions {
+                    condition_problems(&when.condition, problems);
+                }
+            }
+        }
+    }
+    for table in &select.from {
+        match &table.relation {
+            TableFactor::Derived {
+                subquery, alias, ..
+            } => {
+                if alias.as_ref().is_some_and(|alias| alias.explicit) {
+                    problems.push("AS before a derived table alias".to_string());
+                }
+                oracle_problems(subquery, problems);
+            }
+            TableFactor::Table {
+                alias: Some(alias), ..
+            } if alias.explicit => problems.push("AS before a table alias".to_string()),
+            _ => {}
+        }
+    }
+    if let Some(selection) = &select.selection {
+        condition_problems(selection, problems);
+    }
+}
+
+/// Flags bare columns used as conditions, which need `= 1` without a boolean type.
+fn condition_problems(condition: &Expr, problems: &mut Vec<String>) {
+    match condition {
+        Expr::Identifier(_) | Expr::CompoundIdentifier(_) => {
+            problems.push(format!("bare column {condition} used as a condition"));
+        }
+        Expr::Nested(inner)
+        | Expr::UnaryOp {
+            op: UnaryOperator::Not,
+            expr: inner,
+        } => condition_problems(inner, problems),
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And | BinaryOperator::Or,
+            right,
+        } => {
+            condition_problems(left, problems);
+            condition_problems(right, problems);
+        }
+        _ => {}
+    }
+}
+
+/// Flags unquoted words that are neither SQL syntax nor a function name, so a
+/// reserved column name such as `DATE` cannot slip through unquoted.
+fn unquoted_identifier_problems(sql: &str, problems: &mut Vec<String>) {
+    let tokens = Tokenizer::new(&dialect::OracleDialect {}, sql)
+        .tokenize()
+        .expect("tokenizes");
+    let significant: Vec<&Token> = tokens
+        .iter()
+        .filter(|token| !matches!(token, Token::Whitespace(_)))
+        .collect();
+    for (index, token) in significant.iter().enumerate() {
+        let Token::Word(word) = token else {
+            continue;
+        };
+        let is_function = matches!(significant.get(index + 1), Some(Token::LParen));
+        if word.quote_style.is_none()
+            && !is_function
+            && !ORACLE_SQL_WORDS.contains(&word.value.as_str())
+        {
+            problems.push(format!("unquoted identifier {}", word.value));
+        }
+    }
+}
+
+#[test]
+fn test_oracle_output_is_valid_oracle_sql() {
+    let transpiler = Transpiler::new(Box::new(OracleDialect::new()));
+    for code in ORACLE_PIPELINES {
+        let sql = transpiler
+            .transpile(code)
+            .unwrap_or_else(|e| panic!("failed to transpile {code}: {e}"));
+        let statements = Parser::parse_sql(&dialect::OracleDialect {}, &sql)
+            .unwrap_or_else(|e| panic!("invalid SQL for {code}\n{sql}\n{e}"));
+        let [Statement::Query(query)] = statements.as_slice() else {
+            panic!("expected one query for {code}\n{sql}");
+        };
+
+        let mut problems = Vec::new();
+        oracle_problems(query, &mut problems);
+        unquoted_identifier_problems(&sql, &mut problems);
+        assert!(problems.is_empty(), "{code}\n{sql}\n{problems:?}");
+    }
+}
+
+#[test]
+fn test_oracle_checks_reject_generic_sql() {
+    let sql = "SELECT *, (x + 1) AS y FROM (SELECT * FROM data) AS subquery WHERE is_active";
+    let statements = Parser::parse_sql(&dialect::OracleDialect {}, sql).unwrap();
+    let [Statement::Query(query)] = statements.as_slice() else {
+        panic!("expected one query");
+    };
+    let mut problems = Vec::new();
+    oracle_problems(query, &mut problems);
+    unquoted_identifier_problems(sql, &mut problems);
+    for expected in [
+        "unqualified * next to other columns",
+        "AS before a derived table alias",
+        "bare column is_active used as a condition",
+        "unquoted identifier x",
+    ] {
+        assert!(problems.iter().any(|p| p == expected), "{problems:?}");
+    }
+}
@@ -49,6 +49,7 @@ fn test_json_error_output_integration() {
     let error_info = ErrorInfo {
         error_type: "parse".to_string(),
         message: "Unexpected token at position 5".to_string(),
+        code: Some("E-SYNTAX".to_string()),
         position: Some(5),
         suggestions: vec![
             "Check function syntax".to_string(),
@@ -67,6 +68,7 @@ fn test_json_error_output_integration() {
     assert!(json.contains("\"success\": false"));
     assert!(json.contains("\"error_type\": \"parse\""));
     assert!(json.contains("\"message\": \"Unexpected token at position 5\""));
+    assert!(json.contains("\"code\": \"E-SYNTAX\""));
     assert!(json.contains("\"position\": 5"));
     assert!(json.contains("\"suggestions\""));
     assert!(json.contains("Check function syntax"));
@@ -101,6 +103,7 @@ fn test_metadata_completeness() {
         total_time_us: 500,
         token_count: 10,
         ast_node_count: 5,
+        operation_count: 3,
         input_size_bytes: 25,
         output_size_bytes: 45,
     };
@@ -0,0 +1,93 @@
+//! Builds the lexer/parser core as a dependency of a `#![no_std]` crate.
+//!
+//! The harness crate is generated in a temporary directory and checked with
+//! `cargo check --offline`, so the test needs no network access or extra
+//! toolchain targets.
+
+use std::path::Path;
+use std::process::Command;
+
+const HARNESS_LIB: &str = r#"#![no_std]
+
+extern crate alloc;
+
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use libdplyr::collections::HashSet;
+use libdplyr::lexer::{Lexer, Token};
+use libdplyr::parser::{expr_to_string, referenced_columns, DplyrNode, DplyrOperation, Parser};
+
+pub fn operation_count(code: &str) -> usize {
+    let lexer = Lexer::new(code.to_string());
+    match Parser::new(lexer).and_then(|mut parser| parser.parse()) {
+        Ok(DplyrNode::Pipeline { operations, .. }) => operations.len(),
+        _ => 0,
+    }
+}
+
+pub fn filter_conditions(code: &str) -> Vec<alloc::string::String> {
+    let Ok(ast) = Parser::new(Lexer::new(code.to_string())).and_then(|mut p| p.parse()) else {
+        return Vec::new();
+    };
+    let DplyrNode::Pipeline { operations, .. } = &ast else {
+        return Vec::new();
+    };
+    operations
+        .iter()
+        .filter_map(|operation| match operation {
+            DplyrOperation::Filter { condition, .. } => Some(expr_to_string(condition)),
+            _ => None,
+        })
+        .collect()
+}
+
+pub fn columns(code: &str) -> HashSet<alloc::string::String> {
+    Parser::new(Lexer::new(code.to_string()))
+        .and_then(|mut parser| parser.parse())
+        .map(|ast| referenced_columns(&ast))
+        .unwrap_or_default()
+}
+
+pub fn is_select(word: &str) -> bool {
+    libdplyr::lexer::keyword_token(word) == Some(Token::Select)
+}
+"#;
+
+#[test]
+fn test_core_builds_without_std() {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let harness = tempfile::tempdir().expect("create harness directory");
+    let manifest = format!(
+        r#"[package]
+name = "libdplyr_no_std_harness"
+version = "0.0.0"
+edition = "2021"
+
+[dependencies]
+libdplyr = {{ path = {:?}, default-features = false }}
+
+[workspace]
+"#,
+        crate_dir.display().to_string()
+    );
+    std::fs::write(harness.path().join("Cargo.toml"), manifest).unwrap();
+    std::fs::create_dir(harness.path().join("src")).unwrap();
+    std::fs::write(harness.path().join("src/lib.rs"), HARNESS_LIB).unwrap();
+
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let output = Command::new(cargo)
+        .args(["check", "--offline", "--quiet", "--manifest-path"])
+        .arg(harness.path().join("Cargo.toml"))
+        // Reuse one target directory so dependencies build only once
+        .env("CARGO_TARGET_DIR", crate_dir.join("target/no_std_harness"))
+        .env_remove("RUSTFLAGS")
+        .output()
+        .expect("run cargo check");
+
+    assert!(
+        output.status.success(),
+        "no_std harness failed to build:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
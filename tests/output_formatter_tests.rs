@@ -41,6 +41,9 @@ fn test_format_config_custom() {
         add_newline: false,
         indent: "    ".to_string(),
         preserve_case: false,
+        trailing_semicolon: false,
+        header_comment: None,
+        crlf: false,
     };
 
     assert_eq!(config.format, OutputFormat::Compact);
@@ -56,6 +59,9 @@ fn test_format_config_clone() {
         add_newline: false,
         indent: "\t".to_string(),
         preserve_case: true,
+        trailing_semicolon: false,
+        header_comment: None,
+        crlf: false,
     };
 
     let config2 = config1.clone();
@@ -79,6 +85,9 @@ fn test_output_formatter_creation() {
         add_newline: false,
         indent: "    ".to_string(),
         preserve_case: false,
+        trailing_semicolon: false,
+        header_comment: None,
+        crlf: false,
     };
     let custom_formatter = OutputFormatter::with_config(custom_config);
     assert_eq!(custom_formatter.config().format, OutputFormat::Compact);
@@ -256,6 +265,9 @@ fn test_config_updates() {
         add_newline: false,
         indent: "    ".to_string(),
         preserve_case: false,
+        trailing_semicolon: false,
+        header_comment: None,
+        crlf: false,
     };
     formatter.set_config(new_config);
     assert_eq!(formatter.config().format, OutputFormat::Compact);
@@ -341,6 +353,9 @@ fn test_custom_indent_formatting() {
         add_newline: true,
         indent: "    ".to_string(), // 4 spaces
         preserve_case: true,
+        trailing_semicolon: false,
+        header_comment: None,
+        crlf: false,
     };
     let formatter = OutputFormatter::with_config(config);
 